@@ -0,0 +1,275 @@
+//! Environment of an agent's Claude process
+//!
+//! When an agent behaves differently in one worktree, the cause is often
+//! outside the code: another node, a `CLAUDE_*` or `ANTHROPIC_*` variable
+//! set in that shell, or other MCP servers. These are read from the running
+//! process (`/proc/<pid>/environ` on Linux) and from the MCP configs it
+//! loads: `.mcp.json` in its directory and `--mcp-config` arguments.
+
+use std::fs;
+use std::path::Path;
+use std::process::Command;
+
+use crate::tmux::{self, Tmux};
+
+/// Variable name prefixes shown in the details
+const PREFIXES: [&str; 2] = ["CLAUDE_", "ANTHROPIC_"];
+
+/// Parts of a variable name whose value is never shown
+const SECRET_MARKERS: [&str; 4] = ["KEY", "TOKEN", "SECRET", "PASSWORD"];
+
+/// What the details show of an agent's environment
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct AgentEnv {
+    /// Version of node, when Claude runs under node
+    pub node_version: Option<String>,
+    /// `CLAUDE_*` and `ANTHROPIC_*` variables, secrets masked
+    pub vars: Vec<(String, String)>,
+    /// MCP servers, by the config that declares them
+    pub mcp: Vec<McpConfig>,
+}
+
+/// MCP servers declared in one config
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct McpConfig {
+    /// Where they come from: `.mcp.json`, a file name or `--mcp-config`
+    pub source: String,
+    /// Server names
+    pub servers: Vec<String>,
+}
+
+impl AgentEnv {
+    /// Whether there is nothing to show
+    pub fn is_empty(&self) -> bool {
+        self.node_version.is_none() && self.vars.is_empty() && self.mcp.is_empty()
+    }
+}
+
+/// Read the environment of the Claude process in `pane_id`, running in `dir`
+pub fn read(pane_id: &str, dir: &Path) -> AgentEnv {
+    let pid = Tmux::claude_pid(pane_id);
+    let args = pid.and_then(tmux::command_line).unwrap_or_default();
+    AgentEnv {
+        node_version: pid
+            .and_then(tmux::executable)
+            .filter(|exe| is_node(exe))
+            .and_then(|exe| version(&exe)),
+        vars: pid
+            .and_then(tmux::environment)
+            .map(shown_vars)
+            .unwrap_or_default(),
+        mcp: mcp_configs(dir, &args),
+    }
+}
+
+fn is_node(exe: &Path) -> bool {
+    exe.file_name()
+        .is_some_and(|name| name.to_string_lossy().starts_with("node"))
+}
+
+/// Output of `<exe> --version`
+fn version(exe: &Path) -> Option<String> {
+    let output = Command::new(exe).arg("--version").output().ok()?;
+    let version = String::from_utf8_lossy(&output.stdout).trim().to_string();
+    (output.status.success() && !version.is_empty()).then_some(version)
+}
+
+/// The variables worth showing, sorted, with secret values masked
+fn shown_vars(vars: Vec<(String, String)>) -> Vec<(String, String)> {
+    let mut shown: Vec<(String, String)> = vars
+        .into_iter()
+        .filter(|(name, _)| PREFIXES.iter().any(|p| name.starts_with(p)))
+        .map(|(name, value)| {
+            let secret = SECRET_MARKERS.iter().any(|m| name.contains(m));
+            let value = if secret { "***".to_string() } else { value };
+            (name, value)
+        })
+        .collect();
+    shown.sort();
+    shown
+}
+
+/// MCP configs loaded by a Claude process started in `dir` with `args`
+fn mcp_configs(dir: &Path, args: &[String]) -> Vec<McpConfig> {
+    let project = dir.join(".mcp.json");
+    let mut configs: Vec<McpConfig> = fs::read_to_string(&project)
+        .ok()
+        .map(|json| McpConfig {
+            source: ".mcp.json".to_string(),
+            servers: mcp_server_names(&json),
+        })
+        .into_iter()
+        .collect();
+
+    for value in mcp_config_args(args) {
+        // Either inline JSON or a file
+        let (source, json) = if value.trim_start().starts_with('{') {
+            ("--mcp-config".to_string(), value.to_string())
+        } else {
+            let path = dir.join(value);
+            let source = path
+                .file_name()
+                .map(|name| name.to_string_lossy().to_string())
+                .unwrap_or_else(|| value.to_string());
+            match fs::read_to_string(&path) {
+                Ok(json) => (source, json),
+                Err(_) => continue,
+            }
+        };
+        configs.push(McpConfig {
+            source,
+            servers: mcp_server_names(&json),
+        });
+    }
+
+    configs.retain(|c| !c.servers.is_empty());
+    configs
+}
+
+/// Values of `--mcp-config`, which takes one or more configs
+fn mcp_config_args(args: &[String]) -> Vec<&str> {
+    let mut values = Vec::new();
+    let mut taking = false;
+    for arg in args {
+        if let Some(value) = arg.strip_prefix("--mcp-config=") {
+            values.push(value);
+            taking = false;
+        } else if arg == "--mcp-config" {
+            taking = true;
+        } else if taking && !arg.starts_with('-') {
+            values.push(arg.as_str());
+        } else {
+            taking = false;
+        }
+    }
+    values
+}
+
+/// Keys of the `mcpServers` object of an MCP config
+fn mcp_server_names(json: &str) -> Vec<String> {
+    let Some(start) = json.find("\"mcpServers\"") else {
+        return Vec::new();
+    };
+    let rest = json[start + "\"mcpServers\"".len()..].trim_start();
+    let Some(rest) = rest.strip_prefix(':').map(str::trim_start) else {
+        return Vec::new();
+    };
+    if !rest.starts_with('{') {
+        return Vec::new();
+    }
+
+    let mut names = Vec::new();
+    let mut depth = 0;
+    let mut expect_key = false;
+    let mut chars = rest.chars();
+    while let Some(c) = chars.next() {
+        match c {
+            '{' | '[' => {
+                depth += 1;
+                expect_key = depth == 1;
+            }
+            '}' | ']' => {
+                depth -= 1;
+                if depth == 0 {
+                    break;
+                }
+            }
+            ',' if depth == 1 => expect_key = true,
+            '"' => {
+                let string = read_string(&mut chars);
+                if depth == 1 && expect_key {
+                    names.push(string);
+                    expect_key = false;
+                }
+            }
+            _ => {}
+        }
+    }
+    names
+}
+
+/// Rest of a JSON string whose opening quote was read, unescaped loosely
+fn read_string(chars: &mut std::str::Chars) -> String {
+    let mut string = String::new();
+    while let Some(c) = chars.next() {
+        match c {
+            '"' => break,
+            '\\' => string.extend(chars.next()),
+            c => string.push(c),
+        }
+    }
+    string
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(line: &str) -> Vec<String> {
+        line.split(' ').map(str::to_string).collect()
+    }
+
+    #[test]
+    fn test_mcp_server_names() {
+        let json = r#"{
+          "mcpServers": {
+            "github": { "command": "gh-mcp", "args": ["--read-only", "{x}"] },
+            "post\"gres": { "env": { "PGHOST": "db" } }
+          },
+          "other": { "ignored": 1 }
+        }"#;
+        assert_eq!(mcp_server_names(json), vec!["github", "post\"gres"]);
+        assert!(mcp_server_names(r#"{"mcpServers": {}}"#).is_empty());
+        assert!(mcp_server_names(r#"{"servers": {"a": {}}}"#).is_empty());
+    }
+
+    #[test]
+    fn test_mcp_config_args() {
+        assert_eq!(
+            mcp_config_args(&args("claude --mcp-config a.json b.json --verbose")),
+            vec!["a.json", "b.json"]
+        );
+        assert_eq!(
+            mcp_config_args(&args("claude --mcp-config=c.json x.json")),
+            vec!["c.json"]
+        );
+        assert!(mcp_config_args(&args("claude --model opus")).is_empty());
+
+        let inline = vec![
+            "claude".to_string(),
+            "--mcp-config".to_string(),
+            r#"{"mcpServers":{"linear":{}}}"#.to_string(),
+        ];
+        assert_eq!(
+            mcp_configs(Path::new("/nonexistent"), &inline),
+            vec![McpConfig {
+                source: "--mcp-config".to_string(),
+                servers: vec!["linear".to_string()],
+            }]
+        );
+    }
+
+    #[test]
+    fn test_shown_vars() {
+        let vars = vec![
+            ("PATH".to_string(), "/usr/bin".to_string()),
+            (
+                "CLAUDE_CONFIG_DIR".to_string(),
+                "/home/me/.claude-work".to_string(),
+            ),
+            ("ANTHROPIC_API_KEY".to_string(), "sk-ant-123".to_string()),
+            ("ANTHROPIC_MODEL".to_string(), "opus".to_string()),
+        ];
+        assert_eq!(
+            shown_vars(vars),
+            vec![
+                ("ANTHROPIC_API_KEY".to_string(), "***".to_string()),
+                ("ANTHROPIC_MODEL".to_string(), "opus".to_string()),
+                (
+                    "CLAUDE_CONFIG_DIR".to_string(),
+                    "/home/me/.claude-work".to_string()
+                ),
+            ]
+        );
+    }
+}
@@ -1,8 +1,11 @@
 //! Helper utilities for the app module
 //!
-//! Pure functions for path manipulation and name sanitization.
+//! Pure functions for path manipulation, name sanitization, and work ordering.
 
-use std::path::PathBuf;
+use std::path::{Component, Path, PathBuf};
+
+use crate::config::{expand_template, TemplateContext};
+use crate::session::ClaudeInstance;
 
 /// Expand ~ to home directory in a path string
 pub fn expand_path(path: &str) -> PathBuf {
@@ -28,14 +31,223 @@ pub fn sanitize_for_session_name(branch: &str) -> String {
         .replace(['/', '\\', ' ', ':', '.'], "-")
 }
 
-/// Generate default worktree path from repo path and branch name
+/// Session name from the `session_name_template`
+///
+/// `{repo}` is the repository's directory name and `{branch}` the last part
+/// of the branch name. Characters tmux refuses become `-`, separators left
+/// dangling by an empty placeholder are trimmed, and a number is added when
+/// a session in `taken` already has the name.
+/// e.g., "{repo}-{branch}" + api + feature/login -> "api-login", or "api-login-2"
+pub fn templated_session_name(
+    template: &str,
+    repo: &str,
+    branch: Option<&str>,
+    taken: &[String],
+) -> String {
+    let ctx = TemplateContext {
+        branch: branch.map(sanitize_for_session_name),
+        repo: Some(repo.to_string()),
+        ..TemplateContext::default()
+    };
+    let mut name = String::new();
+    for c in expand_template(template, &ctx).chars() {
+        let c = if c.is_whitespace() || c.is_control() || "/\\:.".contains(c) {
+            '-'
+        } else {
+            c
+        };
+        // No empty or repeated separators
+        if matches!(c, '-' | '_') && (name.is_empty() || name.ends_with(['-', '_'])) {
+            continue;
+        }
+        name.push(c);
+    }
+    let base = match name.trim_end_matches(['-', '_']) {
+        "" => "claude",
+        base => base,
+    };
+    (1..)
+        .map(|n| match n {
+            1 => base.to_string(),
+            n => format!("{}-{}", base, n),
+        })
+        .find(|name| !taken.contains(name))
+        .unwrap_or_else(|| base.to_string())
+}
+
+/// Branch name for an issue from the `issue_branch_template`
+///
+/// `{issue}` is the issue number or ticket reference and `{title}` the
+/// title in lowercase words joined by `-`, cut to a few words. Characters
+/// git refuses are dropped, and separators left dangling by an empty
+/// placeholder are trimmed.
+/// e.g., 42 + "Fix login redirect!" -> "issue-42-fix-login-redirect"
+pub fn issue_branch_name(template: &str, reference: &str, title: &str) -> String {
+    let slug = |text: &str, max_words: usize| {
+        text.split(|c: char| !c.is_alphanumeric())
+            .filter(|w| !w.is_empty())
+            .take(max_words)
+            .collect::<Vec<_>>()
+            .join("-")
+    };
+    let name = template
+        .replace("{issue}", &slug(reference, usize::MAX))
+        .replace("{title}", &slug(&title.to_lowercase(), 6));
+
+    let mut branch = String::with_capacity(name.len());
+    for c in name.chars() {
+        if c.is_whitespace() || c.is_control() || "~^:?*[\\".contains(c) {
+            continue;
+        }
+        // No empty or repeated separators
+        if matches!(c, '-' | '/') && (branch.is_empty() || branch.ends_with(['-', '/'])) {
+            continue;
+        }
+        branch.push(c);
+    }
+    branch.trim_end_matches(['-', '/', '.']).to_string()
+}
+
+/// Editor to open files with: `$VISUAL`, then `$EDITOR`, then `vi`
+pub fn editor_command() -> String {
+    std::env::var("VISUAL")
+        .or_else(|_| std::env::var("EDITOR"))
+        .ok()
+        .filter(|e| !e.trim().is_empty())
+        .unwrap_or_else(|| "vi".to_string())
+}
+
+/// Log file for a pane, named after its target and the local start time
+/// e.g., "api:0.1" at 2024-01-10 09:30:00 -> <dir>/api_0.1-20240110-093000.log
+pub fn pane_log_path(dir: &Path, target: &str, local_time: &str) -> PathBuf {
+    let name: String = target
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() || "-_.".contains(c) {
+                c
+            } else {
+                '_'
+            }
+        })
+        .collect();
+    let stamp: String = local_time
+        .chars()
+        .filter(|c| c.is_ascii_digit() || *c == ' ')
+        .map(|c| if c == ' ' { '-' } else { c })
+        .collect();
+    dir.join(format!("{}-{}.log", name, stamp))
+}
+
+/// Branch a worktree is created for, from the branch chosen in the dialog:
+/// its name, whether it is new, and the remote branch it starts from and
+/// tracks, like `git worktree add --track`
+///
+/// A remote branch is chosen by its `remote/branch` name, or by its branch
+/// name when no local branch has it and a single remote does.
+pub fn resolve_worktree_branch(
+    choice: &str,
+    all_branches: &[String],
+    remote_branches: &[(String, String)],
+) -> (String, bool, Option<String>) {
+    if let Some((full, short)) = remote_branches.iter().find(|(full, _)| full == choice) {
+        return (short.clone(), true, Some(full.clone()));
+    }
+    if all_branches.iter().any(|b| b == choice) {
+        return (choice.to_string(), false, None);
+    }
+    let mut tracked = remote_branches.iter().filter(|(_, short)| short == choice);
+    let tracked = match (tracked.next(), tracked.next()) {
+        (Some((full, _)), None) => Some(full.clone()),
+        _ => None,
+    };
+    (choice.to_string(), true, tracked)
+}
+
+/// Position of the instance a tmux target names: a pane
+/// (`session:window.pane` or `%id`), else the first instance of a window
+/// (`session:window`) or session (`session`)
+pub fn target_position(instances: &[&ClaudeInstance], target: &str) -> Option<usize> {
+    instances
+        .iter()
+        .position(|i| i.tmux_target() == target || i.pane_id == target)
+        .or_else(|| {
+            instances.iter().position(|i| {
+                format!("{}:{}", i.session_name, i.window_index) == target
+                    || i.session_name == target
+            })
+        })
+}
+
+/// Generate default worktree path from repo path and branch name, with the
+/// `worktree_path` template resolved against the repository
 /// e.g., ~/repos/project + feature/foo -> ~/repos/project-foo
-pub fn default_worktree_path(repo_path: &std::path::Path, branch: &str) -> PathBuf {
-    let parent = repo_path.parent().unwrap_or(repo_path);
-    let repo_name = repo_path
-        .file_name()
-        .and_then(|s| s.to_str())
-        .unwrap_or("repo");
-    let branch_suffix = sanitize_for_session_name(branch);
-    parent.join(format!("{}-{}", repo_name, branch_suffix))
+pub fn default_worktree_path(repo_path: &Path, branch: &str, template: &str) -> PathBuf {
+    let ctx = TemplateContext {
+        branch: Some(sanitize_for_session_name(branch)),
+        repo: Some(
+            repo_path
+                .file_name()
+                .and_then(|s| s.to_str())
+                .unwrap_or("repo")
+                .to_string(),
+        ),
+        path: repo_path.display().to_string(),
+        ..TemplateContext::default()
+    };
+    let path = repo_path.join(expand_path(&expand_template(template, &ctx)));
+
+    // Resolve `..` so the path reads as it will be created
+    let mut resolved = PathBuf::new();
+    for component in path.components() {
+        match component {
+            Component::ParentDir => {
+                resolved.pop();
+            }
+            Component::CurDir => {}
+            other => resolved.push(other),
+        }
+    }
+    resolved
+}
+
+/// Move `path` from under `from` to the same place under `to`
+///
+/// Paths outside `from` are returned unchanged.
+pub fn rebase_path(path: &Path, from: &Path, to: &Path) -> PathBuf {
+    match path.strip_prefix(from) {
+        Ok(relative) => to.join(relative),
+        Err(_) => path.to_path_buf(),
+    }
+}
+
+/// `base` with the first `-N` suffix (from 2) not in `taken`
+/// e.g., "api" with "api-2" taken -> "api-3"
+pub fn distinct_name(base: &str, taken: &[String]) -> String {
+    // Duplicating a duplicate counts from the original name
+    let base = match base.rsplit_once('-') {
+        Some((stem, n)) if !stem.is_empty() && n.parse::<u32>().is_ok() => stem,
+        _ => base,
+    };
+    (2..)
+        .map(|n| format!("{}-{}", base, n))
+        .find(|name| !taken.contains(name))
+        .unwrap_or_default()
+}
+
+/// Remove up to `count` items from `pending`, the one whose key is `priority` first
+///
+/// The remaining items keep their order.
+pub fn take_prioritized<T>(
+    pending: &mut Vec<T>,
+    priority: Option<&str>,
+    count: usize,
+    key: impl Fn(&T) -> &str,
+) -> Vec<T> {
+    let mut taken = Vec::with_capacity(count);
+    if let Some(index) = priority.and_then(|p| pending.iter().position(|item| key(item) == p)) {
+        taken.push(pending.remove(index));
+    }
+    let rest = count.saturating_sub(taken.len()).min(pending.len());
+    taken.extend(pending.drain(..rest));
+    taken
 }
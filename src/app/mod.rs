@@ -8,23 +8,77 @@
 
 mod helpers;
 mod mode;
+mod palette;
 
-use std::path::PathBuf;
+use std::collections::{HashMap, HashSet};
+use std::fs;
+use std::path::{Path, PathBuf};
 use std::sync::mpsc::{self, Receiver};
+use std::sync::{Arc, Mutex};
 use std::thread;
-
-use anyhow::Result;
-
-use crate::git::{self, GitContext, PullRequestInfo};
+use std::time::{Duration, Instant};
+
+use anyhow::{Context, Result};
+use crossterm::event::KeyCode;
+use ratatui::layout::Rect;
+
+use crate::agent_env::{self, AgentEnv};
+use crate::archive;
+use crate::cache;
+use crate::clipboard;
+use crate::config::{
+    self, save_bindings, Action, ActionContext, Config, HookEvent, TemplateContext,
+};
+use crate::git::{
+    self, CancelToken, ConflictSide, Credentials, CredentialsRequired, DiffStat, GitContext, Issue,
+    PassphraseRequired, PendingOperation, PullRequestChecks, PullRequestInfo, QueuedOperation,
+    SyncOutcome,
+};
+use crate::hooks;
+use crate::i18n::{self, tr, trf};
+use crate::logging;
+use crate::poller::StatusPoller;
+use crate::preview::PreviewBuffer;
+use crate::review::{self, ReviewComment};
+use crate::schedule;
 use crate::scroll_state::ScrollState;
-use crate::session::ClaudeInstance;
+use crate::search;
+use crate::session::{short_path, ClaudeCodeStatus, ClaudeInstance, PaneEntry, Session, SortOrder};
+use crate::snapshot::{self, SnapshotWatch};
+use crate::stats::{self, Counter, InputWatch};
+use crate::store;
+use crate::tags::{self, Tags};
 use crate::tmux::Tmux;
+use crate::trash;
+use crate::ui_state::{self, UiState, PREVIEW_PERCENT_RANGE, PREVIEW_PERCENT_STEP};
+use crate::workspace::{self, Member, Outcome};
+use crate::worktime::{self, BranchTotal, Tracker};
 
 // Re-export types that are part of the public API
-pub use mode::{CreatePullRequestField, Mode, NewSessionField, NewWorktreeField, SessionAction};
+pub use helpers::{expand_path, templated_session_name};
+pub use mode::{
+    ActionRun, CloneLayoutField, CreatePullRequestField, CredentialsField, Mode, NewSessionField,
+    NewWorktreeField, PaneArrangement, SchedulePromptField, SessionAction, SyncRow, SyncState,
+};
+pub use palette::{matches as palette_matches, Command};
 
 // Use helpers internally
-use helpers::{default_worktree_path, expand_path, sanitize_for_session_name};
+use helpers::{
+    default_worktree_path, distinct_name, editor_command, issue_branch_name, pane_log_path,
+    rebase_path, resolve_worktree_branch, take_prioritized, target_position,
+};
+
+/// Panes captured per batch during startup status detection
+const STATUS_BATCH: usize = 4;
+
+/// How often splits running an interactive rebase are checked for exit
+const REBASE_CHECK_INTERVAL: Duration = Duration::from_secs(1);
+
+/// How often the TUI samples agents for working time
+const WORK_TIME_SAMPLE: Duration = Duration::from_secs(10);
+
+/// Largest file or clipboard content that can be sent as a prompt
+const MAX_SEND_BYTES: usize = 256 * 1024;
 
 /// Loading state for progressive data loading
 #[derive(Debug, Clone, Default, PartialEq)]
@@ -32,8 +86,10 @@ pub enum LoadingState {
     /// Not started loading yet
     #[default]
     NotStarted,
-    /// Loading basic instance data
+    /// Loading basic instance data (cached instances may be shown)
     LoadingInstances,
+    /// Capturing panes to detect each instance's status
+    DetectingStatus,
     /// Loading git contexts for instances
     LoadingGitContexts,
     /// All loading complete
@@ -42,17 +98,181 @@ pub enum LoadingState {
 
 /// Messages sent from background loading thread
 pub enum LoadingMessage {
-    /// Basic instances loaded (without git context)
+    /// Basic instances loaded (without status or git context)
     Instances(Vec<ClaudeInstance>),
-    /// Git context loaded for a specific instance
+    /// Status detected for a pane
+    Status {
+        pane_id: String,
+        status: ClaudeCodeStatus,
+    },
+    /// Status detection finished, git contexts follow
+    StatusesDone,
+    /// Git context loaded for a pane
     GitContext {
-        index: usize,
+        pane_id: String,
         context: Option<GitContext>,
     },
     /// All loading complete
     Complete,
 }
 
+/// Pane captures made on a worker thread, so that a slow or wedged tmux
+/// server never holds up the UI
+enum Capture {
+    /// Statuses of the panes polled at the given time; panes that could not
+    /// be captured are missing
+    Statuses(HashMap<String, ClaudeCodeStatus>, Instant),
+    /// Contents of a pane for its preview, `None` if it could not be captured
+    Preview(String, Option<String>),
+}
+
+/// A network git operation that runs on a worker thread
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GitOperation {
+    Push,
+    PushSetUpstream,
+    Fetch,
+    Pull,
+    /// Push replacing the remote branch, for a rewritten branch
+    ForcePush,
+    /// Rebase onto a diverged upstream
+    Rebase,
+    /// Merge a diverged upstream
+    Merge,
+}
+
+impl GitOperation {
+    /// Progress label shown in the status bar
+    pub fn label(&self) -> &'static str {
+        match self {
+            GitOperation::Push | GitOperation::PushSetUpstream | GitOperation::ForcePush => {
+                tr("pushing")
+            }
+            GitOperation::Fetch => tr("fetching"),
+            GitOperation::Pull => tr("pulling"),
+            GitOperation::Rebase => tr("rebasing"),
+            GitOperation::Merge => tr("merging"),
+        }
+    }
+
+    fn run(
+        self,
+        path: &std::path::Path,
+        cancel: &CancelToken,
+        credentials: Option<&Credentials>,
+    ) -> Result<()> {
+        match self {
+            GitOperation::Push => GitContext::push(path, cancel, credentials),
+            GitOperation::PushSetUpstream => {
+                GitContext::push_set_upstream(path, cancel, credentials)
+            }
+            GitOperation::Fetch => GitContext::fetch(path, cancel, credentials),
+            GitOperation::Pull => GitContext::pull(path, cancel, credentials),
+            GitOperation::ForcePush => GitContext::force_push(path, cancel, credentials),
+            GitOperation::Rebase => GitContext::rebase_onto_upstream(path, cancel, credentials),
+            GitOperation::Merge => GitContext::merge_upstream(path, cancel, credentials),
+        }
+    }
+
+    fn success_message(&self) -> &'static str {
+        match self {
+            GitOperation::Push => tr("Pushed to remote"),
+            GitOperation::PushSetUpstream => tr("Pushed and set upstream"),
+            GitOperation::Fetch => tr("Fetched from remote"),
+            GitOperation::Pull => tr("Pulled from remote"),
+            GitOperation::ForcePush => tr("Force-pushed to remote"),
+            GitOperation::Rebase => tr("Rebased onto upstream"),
+            GitOperation::Merge => tr("Merged upstream"),
+        }
+    }
+
+    fn failure_prefix(&self) -> &'static str {
+        match self {
+            GitOperation::Push | GitOperation::PushSetUpstream | GitOperation::ForcePush => {
+                tr("Push failed")
+            }
+            GitOperation::Fetch => tr("Fetch failed"),
+            GitOperation::Pull => tr("Pull failed"),
+            GitOperation::Rebase => tr("Rebase failed"),
+            GitOperation::Merge => tr("Merge failed"),
+        }
+    }
+
+    /// `[[hooks]]` run after the operation succeeds
+    fn hook_event(self) -> HookEvent {
+        match self {
+            GitOperation::Push | GitOperation::PushSetUpstream | GitOperation::ForcePush => {
+                HookEvent::Push
+            }
+            GitOperation::Fetch => HookEvent::Fetch,
+            GitOperation::Pull | GitOperation::Rebase | GitOperation::Merge => HookEvent::Pull,
+        }
+    }
+}
+
+/// Secrets entered in a prompt for a retried git operation
+struct PromptedCredentials {
+    credentials: Credentials,
+    /// URL to save a username and password for with `git credential
+    /// approve` if the operation succeeds
+    store_for: Option<String>,
+}
+
+/// A pull request to open once its branch is pushed
+struct PullRequestDraft {
+    path: PathBuf,
+    session: String,
+    title: String,
+    body: String,
+    base_branch: String,
+}
+
+/// A running background git operation
+pub struct GitJob {
+    pub operation: GitOperation,
+    /// Session the operation was started from
+    pub session: String,
+    /// What it does, e.g. "pulling api", as listed in the repository queue
+    pub description: String,
+    path: PathBuf,
+    prompted: Option<PromptedCredentials>,
+    cancel: CancelToken,
+    receiver: Receiver<Result<()>>,
+}
+
+/// Checkouts being synced one after the other on a worker thread
+struct SyncJob {
+    cancel: CancelToken,
+    /// Results by row of `Mode::Sync`
+    receiver: Receiver<(usize, Result<SyncOutcome>)>,
+}
+
+/// A custom action running on a worker thread
+struct ActionJob {
+    name: String,
+    /// Instance it runs for (`session:window.pane`)
+    target: String,
+    receiver: Receiver<(Vec<String>, ActionRun)>,
+}
+
+/// A key hint of the footer, clickable as a button
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FooterButton {
+    /// Where the hint was drawn
+    pub area: Rect,
+    /// Key the hint names, the first of several as in "y/⏎"
+    pub key: KeyCode,
+}
+
+/// What picking an instance prints (`--pick`, `--print-cwd`)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PickOutput {
+    /// Its pane as `session:window.pane`
+    Target,
+    /// Its working directory
+    Directory,
+}
+
 /// Main application state
 pub struct App {
     /// All discovered Claude Code instances
@@ -63,30 +283,195 @@ pub struct App {
     pub mode: Mode,
     /// Whether the app should quit
     pub should_quit: bool,
+    /// Pane to attach to after quitting, when running outside tmux, or to
+    /// print with `pick`
+    pub attach_on_exit: Option<String>,
+    /// Show the instance list alone and pick an instance instead of
+    /// switching to it (`--pick`, `--print-cwd`)
+    pub pick: Option<PickOutput>,
+    /// Quit after launching a session too, switching to it (`--once`)
+    pub once: bool,
+    /// Directory the list is limited to, with the instances under it
+    /// (`--cwd`)
+    pub scope: Option<PathBuf>,
+    /// Instance to select once panes are listed, from `--select`
+    select_on_load: Option<String>,
+    /// Instance selected when the previous run exited, selected again once
+    /// panes are listed if it is still there
+    resume_selection: Option<String>,
     /// Current pane target (session:window.pane format)
     pub current_pane: Option<String>,
+    /// Pane switched away from last, to switch back to
+    pub previous_pane: Option<String>,
     /// Filter text for filtering instances
     pub filter: String,
     /// Error message to display (clears on next action)
     pub error: Option<String>,
     /// Success message to display (clears on next action)
     pub message: Option<String>,
-    /// Cached preview content for the selected pane
-    pub preview_content: Option<String>,
+    /// Captured preview lines per pane ID
+    pub previews: HashMap<String, PreviewBuffer>,
+    /// Sends status and preview captures back from their worker threads
+    capture_sender: mpsc::Sender<Capture>,
+    /// Status and preview captures to pick up
+    capture_receiver: Receiver<Capture>,
+    /// Whether a round of status captures is running
+    capturing_statuses: bool,
+    /// Panes whose preview is being captured
+    capturing_previews: HashSet<String>,
     /// Available actions for the selected instance (computed when entering action menu)
     pub available_actions: Vec<SessionAction>,
     /// Currently highlighted action in ActionMenu mode
     pub selected_action: usize,
     /// Action pending confirmation
     pub pending_action: Option<SessionAction>,
+    /// Commits of the selected instance no remote has (computed before
+    /// killing or deleting a worktree)
+    pub unpushed_commits: usize,
+    /// `unpushed_commits` being counted in the background
+    unpushed_receiver: Option<Receiver<usize>>,
     /// PR info for the selected instance (computed when entering action menu)
     pub pr_info: Option<PullRequestInfo>,
-    /// Scroll state for the instance list
+    /// Working time of the selected instance's branch (computed when entering action menu)
+    pub branch_time: Option<BranchTotal>,
+    /// Environment of the selected instance's Claude process (computed when entering action menu)
+    pub agent_env: Option<AgentEnv>,
+    /// Scroll state for the instance list and preview
     pub scroll_state: ScrollState,
+    /// Key hints of the footer at the last render
+    pub footer_buttons: Vec<FooterButton>,
     /// Current loading state for progressive data loading
     pub loading_state: LoadingState,
     /// Receiver for background loading messages
     pub loading_receiver: Option<Receiver<LoadingMessage>>,
+    /// Pane the loading thread should handle next (the selected row)
+    loading_priority: Arc<Mutex<Option<String>>>,
+    /// User configuration
+    pub config: Config,
+    /// Whether state changed since the last draw
+    pub needs_redraw: bool,
+    /// Schedules periodic status captures
+    pub poller: StatusPoller,
+    /// Push/pull/fetch running in the background
+    pub git_job: Option<GitJob>,
+    /// Git operations waiting for another one in their repository
+    pub queued_operations: Vec<QueuedOperation>,
+    /// Instances marked for syncing, by pane ID
+    pub marked: HashSet<String>,
+    /// Tags of each tagged working directory
+    pub tags: Tags,
+    /// List instances under a header for their first tag
+    pub group_by_tag: bool,
+    /// Order of the list, `sort_order` unless changed in the TUI
+    pub sort_order: SortOrder,
+    /// Share of the space below the header given to the preview, in percent
+    pub preview_percent: u16,
+    /// Sync of several checkouts running in the background
+    sync_job: Option<SyncJob>,
+    /// Custom action running in the background
+    action_job: Option<ActionJob>,
+    /// Pull request waiting for the running push to finish
+    pull_request_after_push: Option<PullRequestDraft>,
+    /// Diffstat of each listed checkout with changes, by working directory
+    pub diff_stats: HashMap<PathBuf, DiffStat>,
+    /// Diffstats being computed in the background
+    diff_stat_receiver: Option<Receiver<(PathBuf, Option<DiffStat>)>>,
+    /// When diffstats were last computed (`None` to recompute right away)
+    last_diff_stats: Option<Instant>,
+    /// CI checks of each listed checkout with a pull request, by working directory
+    pub pr_checks: HashMap<PathBuf, PullRequestChecks>,
+    /// CI checks being polled in the background
+    pr_checks_receiver: Option<Receiver<(PathBuf, Option<PullRequestChecks>)>>,
+    /// When CI checks were last polled (`None` to poll right away)
+    last_pr_checks: Option<Instant>,
+    /// Background fetches running, reporting each repository that was fetched
+    auto_fetch_receiver: Option<Receiver<PathBuf>>,
+    /// When background fetches last started (`None` to fetch right away)
+    last_auto_fetch: Option<Instant>,
+    /// Records working time while the daemon isn't running
+    work_tracker: Tracker,
+    /// Records input waits while the daemon isn't running
+    input_watch: InputWatch,
+    /// Status changes that take pane snapshots, while no daemon runs
+    snapshot_watch: SnapshotWatch,
+    /// When agents were last sampled for working time and input waits
+    last_work_sample: Option<Instant>,
+    /// Splits running an interactive rebase: pane ID and working directory
+    rebase_panes: Vec<(String, PathBuf)>,
+    /// When the rebase splits were last checked for exit
+    last_rebase_check: Option<Instant>,
+}
+
+/// Load the user config, returning defaults plus an error message on failure,
+/// and a message listing settings that were upgraded from an older version
+fn load_config() -> (Config, Option<String>, Option<String>) {
+    let (config, error) = match Config::load() {
+        Ok(config) => (config, None),
+        Err(e) => (Config::default(), Some(format!("{:#}", e))),
+    };
+    apply_process_settings(&config);
+    if let Some(e) = &error {
+        tracing::warn!("config: {}", e);
+    }
+    let warning = config_warning(&config);
+    (config, error, warning)
+}
+
+/// Apply the settings that live outside the app: log level, tmux control
+/// mode, Claude process matching, the forge client, and locale
+fn apply_process_settings(config: &Config) {
+    logging::init(config.log_level);
+    Tmux::set_control_mode(config.tmux_control_mode);
+    Tmux::set_claude_process(config.claude_process.clone());
+    git::configure_forge(&config.forge);
+    i18n::set_locale(config.locale);
+}
+
+/// Log the settings upgraded from an older version, returning a message
+/// listing them
+fn config_warning(config: &Config) -> Option<String> {
+    for warning in &config.warnings {
+        tracing::warn!("config: {}", warning);
+    }
+    (!config.warnings.is_empty()).then(|| trf!("Outdated config: {}", config.warnings.join("; ")))
+}
+
+fn lock_priority(priority: &Mutex<Option<String>>) -> std::sync::MutexGuard<'_, Option<String>> {
+    priority.lock().unwrap_or_else(|e| e.into_inner())
+}
+
+/// Run `command` with `sh -c` in `dir`, returning its output lines
+fn run_shell(command: &str, dir: &std::path::Path) -> (Vec<String>, ActionRun) {
+    // One stream, so that errors show where they happened
+    let output = std::process::Command::new("sh")
+        .arg("-c")
+        .arg(format!("exec 2>&1\n{}", command))
+        .current_dir(dir)
+        .stdin(std::process::Stdio::null())
+        .output();
+    match output {
+        Ok(output) => {
+            let lines = String::from_utf8_lossy(&output.stdout)
+                .lines()
+                .map(str::to_string)
+                .collect();
+            let state = match output.status.code() {
+                Some(code) => ActionRun::Exited(code),
+                None => ActionRun::Failed(tr("killed by a signal").to_string()),
+            };
+            (lines, state)
+        }
+        Err(e) => (Vec::new(), ActionRun::Failed(e.to_string())),
+    }
+}
+
+/// Read a file that is expected to contain UTF-8 text
+fn read_text_file(path: &std::path::Path) -> Result<String> {
+    if path.is_dir() {
+        anyhow::bail!("is a directory");
+    }
+    let bytes = fs::read(path)?;
+    String::from_utf8(bytes).map_err(|_| anyhow::anyhow!("not a text file"))
 }
 
 impl App {
@@ -102,25 +487,12 @@ impl App {
     pub fn new() -> Result<Self> {
         let instances = Tmux::list_claude_instances()?;
         let current_pane = Tmux::current_pane()?;
+        let (config, config_error, config_warning) = load_config();
 
-        let mut app = Self {
-            instances,
-            selected: 0,
-            mode: Mode::Normal,
-            should_quit: false,
-            current_pane,
-            filter: String::new(),
-            error: None,
-            message: None,
-            preview_content: None,
-            available_actions: Vec::new(),
-            selected_action: 0,
-            pending_action: None,
-            pr_info: None,
-            scroll_state: ScrollState::new(),
-            loading_state: LoadingState::Complete,
-            loading_receiver: None,
-        };
+        let mut app = Self::with_instances(instances, current_pane, config);
+        app.error = config_error;
+        app.message = config_warning;
+        app.loading_state = LoadingState::Complete;
 
         app.update_preview();
         Ok(app)
@@ -128,67 +500,217 @@ impl App {
 
     /// Create a new App instance with fast startup (UI appears immediately)
     ///
+    /// The instance list starts out as the one cached by the previous run.
     /// Call `start_background_loading()` after this to begin loading data.
     /// Call `poll_loading()` in the event loop to receive updates.
     pub fn new_fast() -> Result<Self> {
         // Only get current_pane - this is a single fast tmux call
         let current_pane = Tmux::current_pane()?;
+        let (config, config_error, config_warning) = load_config();
+        let mut instances = cache::load_instances();
+        config.sort_order.sort(&mut instances);
+
+        // Last-known instances, replaced once tmux has been queried
+        let mut app = Self::with_instances(instances, current_pane, config);
+        app.error = config_error;
+        app.message = config_warning;
+        app.restore_ui_state(ui_state::load());
+        Ok(app)
+    }
 
-        Ok(Self {
-            instances: Vec::new(),
+    /// App showing `instances`, with nothing loaded yet
+    fn with_instances(
+        instances: Vec<ClaudeInstance>,
+        current_pane: Option<String>,
+        config: Config,
+    ) -> Self {
+        let sort_order = config.sort_order;
+        let (capture_sender, capture_receiver) = mpsc::channel();
+        Self {
+            instances,
             selected: 0,
             mode: Mode::Normal,
             should_quit: false,
+            attach_on_exit: None,
+            pick: None,
+            once: false,
+            scope: None,
+            select_on_load: None,
+            resume_selection: None,
             current_pane,
+            previous_pane: None,
             filter: String::new(),
             error: None,
             message: None,
-            preview_content: None,
+            previews: HashMap::new(),
+            capture_sender,
+            capture_receiver,
+            capturing_statuses: false,
+            capturing_previews: HashSet::new(),
             available_actions: Vec::new(),
             selected_action: 0,
             pending_action: None,
+            unpushed_commits: 0,
+            unpushed_receiver: None,
             pr_info: None,
+            branch_time: None,
+            agent_env: None,
             scroll_state: ScrollState::new(),
+            footer_buttons: Vec::new(),
             loading_state: LoadingState::NotStarted,
             loading_receiver: None,
-        })
+            loading_priority: Arc::default(),
+            poller: StatusPoller::new(config.capture_budget, Instant::now()),
+            config,
+            needs_redraw: true,
+            git_job: None,
+            queued_operations: Vec::new(),
+            marked: HashSet::new(),
+            tags: tags::load(),
+            group_by_tag: false,
+            sort_order,
+            preview_percent: ui_state::DEFAULT_PREVIEW_PERCENT,
+            sync_job: None,
+            action_job: None,
+            pull_request_after_push: None,
+            diff_stats: HashMap::new(),
+            diff_stat_receiver: None,
+            last_diff_stats: None,
+            pr_checks: HashMap::new(),
+            pr_checks_receiver: None,
+            last_pr_checks: None,
+            auto_fetch_receiver: None,
+            last_auto_fetch: None,
+            work_tracker: Tracker::new(),
+            input_watch: InputWatch::default(),
+            snapshot_watch: SnapshotWatch::default(),
+            last_work_sample: None,
+            rebase_panes: Vec::new(),
+            last_rebase_check: None,
+        }
+    }
+
+    /// App showing `instances` for tests: no config file, tags or cache are
+    /// read, and tmux commands go to the thread's backend (see `MockTmux`)
+    #[cfg(test)]
+    pub fn for_test(instances: Vec<ClaudeInstance>, config: Config) -> Self {
+        let mut app = Self::with_instances(instances, Tmux::current_pane().ok().flatten(), config);
+        app.tags = Tags::default();
+        app.loading_state = LoadingState::Complete;
+        app.update_preview();
+        app
+    }
+
+    /// Reload the config file, applying keybindings, colors, and other
+    /// settings right away
+    ///
+    /// An invalid config is reported in a dialog and the current settings
+    /// are kept. Outside the list or action menu, where a dialog would
+    /// replace the one in use, the error goes to the status line instead.
+    pub fn reload_config(&mut self) {
+        self.clear_messages();
+        self.needs_redraw = true;
+        let config = match Config::load() {
+            Ok(config) => config,
+            Err(e) => {
+                let error = format!("{:#}", e);
+                tracing::warn!("config: {}", error);
+                if matches!(self.mode, Mode::Normal | Mode::ActionMenu) {
+                    self.mode = Mode::ConfigError { error };
+                } else {
+                    self.error = Some(trf!("Config not reloaded: {}", error));
+                }
+                return;
+            }
+        };
+        apply_process_settings(&config);
+        self.poller.set_budget(config.capture_budget);
+        self.message =
+            Some(config_warning(&config).unwrap_or_else(|| tr("Config reloaded").to_string()));
+        tracing::info!("config: reloaded");
+        // A changed `sort_order` replaces the order picked in the TUI
+        let resort = config.sort_order != self.config.sort_order;
+        self.config = config;
+        if resort {
+            self.set_sort_order(self.config.sort_order);
+        }
+        // Labels and availability of actions may depend on the config
+        if self.mode == Mode::ActionMenu {
+            self.compute_actions();
+        }
     }
 
-    /// Start background loading of instances and git contexts
+    /// Start background loading of instances, statuses, and git contexts
+    ///
+    /// Work is done in stages so the list can be painted as early as
+    /// possible: panes and processes first, then status detection, then git
+    /// detection. Within each stage the selected row is handled first.
     pub fn start_background_loading(&mut self) {
         let (tx, rx) = mpsc::channel();
         self.loading_receiver = Some(rx);
         self.loading_state = LoadingState::LoadingInstances;
+        self.update_loading_priority();
+        let priority = Arc::clone(&self.loading_priority);
 
         thread::spawn(move || {
-            // Phase 1: Load basic instances (with status detection but without git context)
-            match Tmux::list_claude_instances_basic() {
-                Ok(instances) => {
-                    // Collect paths before sending instances
-                    let paths: Vec<PathBuf> = instances
-                        .iter()
-                        .map(|i| i.working_directory.clone())
-                        .collect();
+            // Phase 1: List panes and find the ones running Claude
+            let instances = match Tmux::list_claude_panes() {
+                Ok(instances) => instances,
+                Err(_) => {
+                    // Send empty list on error
+                    let _ = tx.send(LoadingMessage::Instances(Vec::new()));
+                    let _ = tx.send(LoadingMessage::Complete);
+                    return;
+                }
+            };
+            let mut pending_status: Vec<String> = instances
+                .iter()
+                .filter(|i| i.status != ClaudeCodeStatus::Crashed)
+                .map(|i| i.pane_id.clone())
+                .collect();
+            let mut pending_git: Vec<(String, PathBuf)> = instances
+                .iter()
+                .map(|i| (i.pane_id.clone(), i.working_directory.clone()))
+                .collect();
 
-                    // Send instances to main thread
-                    if tx.send(LoadingMessage::Instances(instances)).is_err() {
-                        return; // Receiver dropped
-                    }
+            if tx.send(LoadingMessage::Instances(instances)).is_err() {
+                return; // Receiver dropped
+            }
 
-                    // Phase 2: Load git contexts progressively
-                    for (index, path) in paths.iter().enumerate() {
-                        let context = GitContext::detect(path);
-                        if tx
-                            .send(LoadingMessage::GitContext { index, context })
-                            .is_err()
-                        {
-                            return; // Receiver dropped
-                        }
+            // Phase 2: Detect statuses, a pool-sized batch at a time
+            while !pending_status.is_empty() {
+                let selected = lock_priority(&priority).clone();
+                let batch = take_prioritized(
+                    &mut pending_status,
+                    selected.as_deref(),
+                    STATUS_BATCH,
+                    |id| id.as_str(),
+                );
+                for (pane_id, status) in Tmux::capture_statuses(&batch) {
+                    if tx.send(LoadingMessage::Status { pane_id, status }).is_err() {
+                        return;
                     }
                 }
-                Err(_) => {
-                    // Send empty list on error
-                    let _ = tx.send(LoadingMessage::Instances(Vec::new()));
+            }
+            if tx.send(LoadingMessage::StatusesDone).is_err() {
+                return;
+            }
+
+            // Phase 3: Load git contexts progressively
+            while !pending_git.is_empty() {
+                let selected = lock_priority(&priority).clone();
+                for (pane_id, path) in
+                    take_prioritized(&mut pending_git, selected.as_deref(), 1, |(id, _)| {
+                        id.as_str()
+                    })
+                {
+                    let context = GitContext::detect(&path);
+                    if tx
+                        .send(LoadingMessage::GitContext { pane_id, context })
+                        .is_err()
+                    {
+                        return;
+                    }
                 }
             }
 
@@ -197,31 +719,56 @@ impl App {
         });
     }
 
+    /// Tell the loading thread which pane is selected
+    fn update_loading_priority(&self) {
+        let pane_id = self.selected_instance().map(|i| i.pane_id.clone());
+        *lock_priority(&self.loading_priority) = pane_id;
+    }
+
     /// Poll for background loading updates (call this in the event loop)
     pub fn poll_loading(&mut self) {
         // Take the receiver out to avoid borrow conflicts
         let Some(rx) = self.loading_receiver.take() else {
             return;
         };
+        self.update_loading_priority();
 
         let mut should_update_preview = false;
         let mut completed = false;
 
         // Process all available messages without blocking
         while let Ok(msg) = rx.try_recv() {
+            self.needs_redraw = true;
             match msg {
                 LoadingMessage::Instances(instances) => {
-                    self.instances = instances;
-                    self.loading_state = LoadingState::LoadingGitContexts;
+                    self.replace_cached_instances(instances);
+                    if let Some(target) = self.resume_selection.take() {
+                        self.select_target(&target);
+                    }
+                    if let Some(target) = self.select_on_load.take() {
+                        if !self.select_target(&target) {
+                            self.error = Some(trf!("No Claude instance at '{}'", target));
+                        }
+                    }
+                    self.loading_state = LoadingState::DetectingStatus;
                     should_update_preview = true;
                 }
-                LoadingMessage::GitContext { index, context } => {
-                    if let Some(inst) = self.instances.get_mut(index) {
+                LoadingMessage::Status { pane_id, status } => {
+                    if let Some(inst) = self.instances.iter_mut().find(|i| i.pane_id == pane_id) {
+                        inst.status = status;
+                    }
+                }
+                LoadingMessage::StatusesDone => {
+                    self.loading_state = LoadingState::LoadingGitContexts;
+                }
+                LoadingMessage::GitContext { pane_id, context } => {
+                    if let Some(inst) = self.instances.iter_mut().find(|i| i.pane_id == pane_id) {
                         inst.git_context = context;
                     }
                 }
                 LoadingMessage::Complete => {
                     self.loading_state = LoadingState::Complete;
+                    let _ = cache::save_instances(&self.instances);
                     completed = true;
                     break;
                 }
@@ -239,23 +786,221 @@ impl App {
         }
     }
 
+    /// Select the instance at `target` once panes are listed, and right
+    /// away if the cached list already has it
+    pub fn select_on_load(&mut self, target: String) {
+        if self.select_target(&target) {
+            self.update_preview();
+        }
+        self.select_on_load = Some(target);
+    }
+
+    /// Select the instance at a tmux target, returning whether there is one
+    fn select_target(&mut self, target: &str) -> bool {
+        let Some(index) = target_position(&self.filtered_instances(), target) else {
+            return false;
+        };
+        self.selected = index;
+        true
+    }
+
+    /// Swap the cached instance list for freshly listed instances
+    ///
+    /// Until their status is detected, instances keep the last-known status
+    /// of the same pane, and the selection stays on the same pane.
+    fn replace_cached_instances(&mut self, mut instances: Vec<ClaudeInstance>) {
+        let selected_pane = self.selected_instance().map(|i| i.pane_id.clone());
+
+        self.sort_order.sort(&mut instances);
+        for instance in &mut instances {
+            if instance.status != ClaudeCodeStatus::Unknown {
+                continue;
+            }
+            if let Some(cached) = self
+                .instances
+                .iter()
+                .find(|c| c.pane_id == instance.pane_id)
+            {
+                instance.status = cached.status;
+            }
+        }
+        self.instances = instances;
+
+        if let Some(index) = selected_pane.and_then(|id| {
+            self.filtered_instances()
+                .iter()
+                .position(|i| i.pane_id == id)
+        }) {
+            self.selected = index;
+        } else if self.selected >= self.instances.len() {
+            self.selected = self.instances.len().saturating_sub(1);
+        }
+    }
+
     /// Check if loading is still in progress
     pub fn is_loading(&self) -> bool {
         self.loading_state != LoadingState::Complete
     }
 
-    /// Update the preview content for the currently selected instance
-    pub fn update_preview(&mut self) {
-        const PREVIEW_LINES: usize = 15;
+    /// Short description of the current state, for crash reports
+    pub fn state_summary(&self) -> String {
+        let mode = format!("{:?}", self.mode);
+        let mode_name = mode
+            .split(|c: char| !c.is_alphanumeric())
+            .next()
+            .unwrap_or_default();
+        let selected = self
+            .selected_instance()
+            .map_or_else(|| "none".to_string(), |i| i.pane_id.clone());
+        format!(
+            "mode: {}\nloading: {:?}\ninstances: {} ({} shown)\nselected: {}\nfilter: {:?}\ngit job: {}\nerror: {}",
+            mode_name,
+            self.loading_state,
+            self.instances.len(),
+            self.filtered_instances().len(),
+            selected,
+            self.filter,
+            self.git_job
+                .as_ref()
+                .map_or("none".to_string(), |job| format!("{:?}", job.operation)),
+            self.error.as_deref().unwrap_or("none"),
+        )
+    }
+
+    /// Pick up finished captures, and capture and re-detect the status of
+    /// panes the poller says are due on a worker thread
+    pub fn poll_statuses(&mut self) {
+        while let Ok(capture) = self.capture_receiver.try_recv() {
+            self.apply_capture(capture);
+        }
+
+        if self.is_loading() || self.capturing_statuses {
+            return;
+        }
+
+        let now = Instant::now();
+        let panes: Vec<(&str, ClaudeCodeStatus)> = self
+            .instances
+            .iter()
+            .filter(|i| i.status != ClaudeCodeStatus::Crashed)
+            .map(|i| (i.pane_id.as_str(), i.status))
+            .collect();
+        let visible = self.visible_pane_ids();
+        self.poller.set_visible(visible);
+        let due = self.poller.due(&panes, now);
+
+        if due.is_empty() {
+            return;
+        }
 
-        let pane_id = self.selected_instance().map(|inst| inst.pane_id.clone());
+        // Keep the preview of the selected pane live while it is being polled
+        let selected = self.selected_instance().map(|i| i.pane_id.clone());
+        if selected.is_some_and(|id| due.contains(&id)) {
+            self.update_preview();
+        }
+
+        self.capturing_statuses = true;
+        let tx = self.capture_sender.clone();
+        Tmux::spawn_worker(move || {
+            let _ = tx.send(Capture::Statuses(Tmux::capture_statuses(&due), now));
+        });
+    }
+
+    /// Record a capture made on a worker thread
+    fn apply_capture(&mut self, capture: Capture) {
+        match capture {
+            Capture::Statuses(statuses, at) => {
+                self.capturing_statuses = false;
+                for (pane_id, status) in statuses {
+                    self.poller.record(&pane_id, status, at);
+
+                    if let Some(instance) = self.instances.iter_mut().find(|i| i.pane_id == pane_id)
+                    {
+                        if instance.status != status {
+                            instance.status = status;
+                            self.needs_redraw = true;
+                        }
+                    }
+                }
+            }
+            Capture::Preview(pane_id, content) => {
+                self.capturing_previews.remove(&pane_id);
+                if !self.instances.iter().any(|i| i.pane_id == pane_id) {
+                    return;
+                }
+                let Some(content) = content else {
+                    self.needs_redraw |= self.previews.remove(&pane_id).is_some();
+                    return;
+                };
+                let max_bytes = self.config.preview_buffer_kb * 1024;
+                let buffer = self
+                    .previews
+                    .entry(pane_id)
+                    .or_insert_with(|| PreviewBuffer::new(max_bytes));
+                if buffer.update(&content) {
+                    self.needs_redraw = true;
+                }
+            }
+        }
+    }
 
-        self.preview_content = pane_id.and_then(|id| {
+    /// Wait for the captures and the count of commits on no remote in
+    /// flight, and record them
+    #[cfg(test)]
+    pub fn wait_for_workers(&mut self) {
+        while self.capturing_statuses || !self.capturing_previews.is_empty() {
+            match self.capture_receiver.recv_timeout(Duration::from_secs(5)) {
+                Ok(capture) => self.apply_capture(capture),
+                Err(_) => break,
+            }
+        }
+        if let Some(rx) = self.unpushed_receiver.take() {
+            self.unpushed_commits = rx.recv_timeout(Duration::from_secs(5)).unwrap_or(0);
+        }
+    }
+
+    /// Capture the selected pane into its preview buffer on a worker thread
+    ///
+    /// Buffers of panes that no longer exist are dropped. The capture shows
+    /// up once `poll_statuses` picks it up.
+    pub fn update_preview(&mut self) {
+        let instances = &self.instances;
+        self.previews
+            .retain(|pane_id, _| instances.iter().any(|i| &i.pane_id == pane_id));
+
+        let Some(pane_id) = self.selected_instance().map(|inst| inst.pane_id.clone()) else {
+            return;
+        };
+        if !self.capturing_previews.insert(pane_id.clone()) {
+            return;
+        }
+        let lines = self.config.preview_lines;
+        let tx = self.capture_sender.clone();
+        Tmux::spawn_worker(move || {
             // Don't strip empty lines - preserve visual layout for preview
-            Tmux::capture_pane(&id, PREVIEW_LINES, false).ok()
+            let content = Tmux::capture_pane(&pane_id, lines, false).ok();
+            let _ = tx.send(Capture::Preview(pane_id, content));
         });
     }
 
+    /// Scroll the selected pane's preview back into its history by `delta`
+    /// lines, or forward when negative
+    pub fn scroll_preview(&mut self, delta: isize) {
+        let Some(instance) = self.selected_instance() else {
+            return;
+        };
+        let pane_id = instance.pane_id.clone();
+        let lines = self.previews.get(&pane_id).map_or(0, |buffer| buffer.len());
+        self.scroll_state.scroll_preview(&pane_id, delta, lines);
+        self.needs_redraw = true;
+    }
+
+    /// Preview buffer of the selected pane, if it has been captured
+    pub fn selected_preview(&self) -> Option<&PreviewBuffer> {
+        let instance = self.selected_instance()?;
+        self.previews.get(&instance.pane_id)
+    }
+
     /// Clear any displayed messages
     pub fn clear_messages(&mut self) {
         self.error = None;
@@ -266,15 +1011,19 @@ impl App {
     pub fn refresh(&mut self) {
         self.clear_messages();
         if self.refresh_instances() {
-            self.message = Some("Refreshed".to_string());
+            self.message = Some(tr("Refreshed").to_string());
         }
     }
 
     /// Refresh instances without affecting messages (for use after git operations)
     fn refresh_instances(&mut self) -> bool {
         match Tmux::list_claude_instances() {
-            Ok(instances) => {
+            Ok(mut instances) => {
+                self.sort_order.sort(&mut instances);
                 self.instances = instances;
+                // Changes were likely made; don't wait for the next interval
+                self.last_diff_stats = None;
+                self.last_pr_checks = None;
                 // Ensure selected index is still valid
                 if self.selected >= self.instances.len() && !self.instances.is_empty() {
                     self.selected = self.instances.len() - 1;
@@ -283,76 +1032,524 @@ impl App {
                 true
             }
             Err(e) => {
-                self.error = Some(format!("Failed to refresh: {}", e));
+                self.error = Some(trf!("Failed to refresh: {}", e));
                 false
             }
         }
     }
 
-    // =========================================================================
-    // Instance selection and navigation
-    // =========================================================================
-
-    /// Get filtered instances based on current filter
-    pub fn filtered_instances(&self) -> Vec<&ClaudeInstance> {
-        if self.filter.is_empty() {
-            self.instances.iter().collect()
-        } else {
-            let filter_lower = self.filter.to_lowercase();
-            self.instances
-                .iter()
-                .filter(|inst| {
-                    inst.session_name.to_lowercase().contains(&filter_lower)
-                        || inst.display_path().to_lowercase().contains(&filter_lower)
-                        || inst.window_name.to_lowercase().contains(&filter_lower)
-                })
-                .collect()
+    /// Pick up diffstats computed in the background, and start computing
+    /// them again every `diff_stat_interval`
+    pub fn poll_diff_stats(&mut self) {
+        if let Some(rx) = &self.diff_stat_receiver {
+            loop {
+                match rx.try_recv() {
+                    Ok((path, stat)) => {
+                        let changed = match stat {
+                            Some(stat) => self.diff_stats.insert(path, stat) != Some(stat),
+                            None => self.diff_stats.remove(&path).is_some(),
+                        };
+                        self.needs_redraw |= changed;
+                    }
+                    // Still computing
+                    Err(mpsc::TryRecvError::Empty) => return,
+                    Err(mpsc::TryRecvError::Disconnected) => {
+                        self.diff_stat_receiver = None;
+                        break;
+                    }
+                }
+            }
         }
-    }
-
-    /// Get the currently selected instance
-    pub fn selected_instance(&self) -> Option<&ClaudeInstance> {
-        let filtered = self.filtered_instances();
-        filtered.get(self.selected).copied()
-    }
 
-    /// Move selection up
-    pub fn select_prev(&mut self) {
-        let count = self.filtered_instances().len();
-        if count > 0 && self.selected > 0 {
-            self.selected -= 1;
-            self.update_preview();
+        if self.is_loading()
+            || self
+                .last_diff_stats
+                .is_some_and(|last| last.elapsed() < self.config.diff_stat_interval)
+        {
+            return;
         }
-    }
+        self.last_diff_stats = Some(Instant::now());
+
+        let mut paths: Vec<PathBuf> = self
+            .instances
+            .iter()
+            .filter(|i| i.git_context.is_some())
+            .map(|i| i.working_directory.clone())
+            .collect();
+        paths.sort();
+        paths.dedup();
+        let instances = &self.instances;
+        self.diff_stats
+            .retain(|path, _| instances.iter().any(|i| &i.working_directory == path));
 
-    /// Move selection down
-    pub fn select_next(&mut self) {
-        let count = self.filtered_instances().len();
-        if count > 0 && self.selected < count - 1 {
-            self.selected += 1;
-            self.update_preview();
-        }
+        let (tx, rx) = mpsc::channel();
+        self.diff_stat_receiver = Some(rx);
+        thread::spawn(move || {
+            for path in paths {
+                let stat = git::working_tree_diff_stat(&path).unwrap_or_else(|e| {
+                    tracing::debug!("diffstat of {}: {:#}", path.display(), e);
+                    None
+                });
+                if tx.send((path, stat)).is_err() {
+                    return;
+                }
+            }
+        });
     }
 
-    /// Switch to the selected instance's pane
-    pub fn switch_to_selected(&mut self) {
-        self.clear_messages();
-        if let Some(instance) = self.selected_instance() {
-            let target = instance.tmux_target();
-            match Tmux::switch_to_pane(&target) {
-                Ok(_) => {
-                    self.should_quit = true;
-                }
-                Err(e) => {
-                    self.error = Some(format!("Failed to switch: {}", e));
+    /// Fetch repositories with auto-fetch on every `auto_fetch_interval`, and
+    /// refresh ahead/behind once they are fetched
+    pub fn poll_auto_fetch(&mut self) {
+        if let Some(rx) = &self.auto_fetch_receiver {
+            let mut fetched = false;
+            loop {
+                match rx.try_recv() {
+                    Ok(_) => fetched = true,
+                    Err(mpsc::TryRecvError::Empty) => break,
+                    Err(mpsc::TryRecvError::Disconnected) => {
+                        self.auto_fetch_receiver = None;
+                        break;
+                    }
                 }
             }
+            if fetched && self.mode == Mode::Normal {
+                self.needs_redraw |= self.refresh_instances();
+            }
+            return;
         }
-    }
 
-    // =========================================================================
-    // Action menu
-    // =========================================================================
+        if self.is_loading()
+            || self
+                .last_auto_fetch
+                .is_some_and(|last| last.elapsed() < self.config.auto_fetch_interval)
+        {
+            return;
+        }
+        self.last_auto_fetch = Some(Instant::now());
+
+        // Worktrees share their repository's remote-tracking branches
+        let mut repos: Vec<PathBuf> = self
+            .instances
+            .iter()
+            .filter_map(|i| {
+                let git = i.git_context.as_ref()?;
+                (git.has_remote && git.auto_fetch.unwrap_or(self.config.auto_fetch)).then(|| {
+                    git.main_repo_path
+                        .clone()
+                        .unwrap_or_else(|| i.working_directory.clone())
+                })
+            })
+            .collect();
+        repos.sort();
+        repos.dedup();
+        if repos.is_empty() {
+            return;
+        }
+
+        let (tx, rx) = mpsc::channel();
+        self.auto_fetch_receiver = Some(rx);
+        thread::spawn(move || {
+            for repo in repos {
+                let name = repo.file_name().unwrap_or_default().to_string_lossy();
+                let description = trf!("auto-fetching {}", name);
+                let cancel = CancelToken::new();
+                // Never prompts; remotes needing credentials are skipped
+                let result = git::lock_repo(&repo, &description, &cancel)
+                    .and_then(|_lock| GitContext::fetch(&repo, &cancel, None));
+                match result {
+                    Ok(()) => {
+                        if tx.send(repo).is_err() {
+                            return;
+                        }
+                    }
+                    Err(e) => tracing::debug!("auto-fetch of {}: {:#}", repo.display(), e),
+                }
+            }
+        });
+    }
+
+    /// Pick up CI checks polled in the background, and poll them again every
+    /// `pr_checks_interval`
+    pub fn poll_pr_checks(&mut self) {
+        if !self.config.ci_checks {
+            return;
+        }
+        if let Some(rx) = &self.pr_checks_receiver {
+            loop {
+                match rx.try_recv() {
+                    Ok((path, checks)) => {
+                        let changed = match checks {
+                            Some(checks) => {
+                                self.pr_checks.insert(path, checks.clone()) != Some(checks)
+                            }
+                            None => self.pr_checks.remove(&path).is_some(),
+                        };
+                        self.needs_redraw |= changed;
+                    }
+                    // Still polling
+                    Err(mpsc::TryRecvError::Empty) => return,
+                    Err(mpsc::TryRecvError::Disconnected) => {
+                        self.pr_checks_receiver = None;
+                        break;
+                    }
+                }
+            }
+        }
+
+        if self.is_loading()
+            || self
+                .last_pr_checks
+                .is_some_and(|last| last.elapsed() < self.config.pr_checks_interval)
+        {
+            return;
+        }
+        self.last_pr_checks = Some(Instant::now());
+
+        let mut paths: Vec<PathBuf> = self
+            .instances
+            .iter()
+            .filter(|i| i.git_context.as_ref().is_some_and(|g| g.has_remote))
+            .map(|i| i.working_directory.clone())
+            .collect();
+        paths.sort();
+        paths.dedup();
+        let instances = &self.instances;
+        self.pr_checks
+            .retain(|path, _| instances.iter().any(|i| &i.working_directory == path));
+
+        let (tx, rx) = mpsc::channel();
+        self.pr_checks_receiver = Some(rx);
+        thread::spawn(move || {
+            let forge = git::forge();
+            for path in paths {
+                let checks = forge.checks(&path);
+                if tx.send((path, checks)).is_err() {
+                    return;
+                }
+            }
+        });
+    }
+
+    // =========================================================================
+    // Instance selection and navigation
+    // =========================================================================
+
+    /// Get filtered instances based on current filter
+    pub fn filtered_instances(&self) -> Vec<&ClaudeInstance> {
+        let scoped = self.instances.iter().filter(|inst| {
+            self.scope
+                .as_ref()
+                .is_none_or(|scope| inst.working_directory.starts_with(scope))
+        });
+        let mut filtered: Vec<&ClaudeInstance> = if self.filter.is_empty() {
+            scoped.collect()
+        } else if let Some(tag) = self.filter.strip_prefix('#') {
+            // `#tag` keeps the instances with exactly that tag
+            scoped
+                .filter(|inst| {
+                    self.instance_tags(inst)
+                        .iter()
+                        .any(|t| t.eq_ignore_ascii_case(tag))
+                })
+                .collect()
+        } else {
+            let filter_lower = self.filter.to_lowercase();
+            scoped
+                .filter(|inst| {
+                    inst.session_name.to_lowercase().contains(&filter_lower)
+                        || inst.display_path().to_lowercase().contains(&filter_lower)
+                        || inst.window_name.to_lowercase().contains(&filter_lower)
+                        || self
+                            .instance_tags(inst)
+                            .iter()
+                            .any(|t| t.to_lowercase().contains(&filter_lower))
+                })
+                .collect()
+        };
+        if self.group_by_tag {
+            // Untagged instances last, each group in list order
+            filtered.sort_by_key(|inst| {
+                let group = self.group_of(inst);
+                (group.is_none(), group.map(str::to_lowercase))
+            });
+        }
+        filtered
+    }
+
+    /// Tags of the instance's working directory
+    pub fn instance_tags(&self, instance: &ClaudeInstance) -> &[String] {
+        self.tags
+            .get(&instance.working_directory)
+            .map(Vec::as_slice)
+            .unwrap_or_default()
+    }
+
+    /// Tag an instance is grouped under: its first one
+    fn group_of(&self, instance: &ClaudeInstance) -> Option<&str> {
+        self.instance_tags(instance).first().map(String::as_str)
+    }
+
+    /// Header shown above the `index`th filtered instance when it starts a
+    /// group: its tag, or `None` inside the untagged group
+    pub fn group_header(
+        &self,
+        filtered: &[&ClaudeInstance],
+        index: usize,
+    ) -> Option<Option<String>> {
+        if !self.group_by_tag {
+            return None;
+        }
+        let group = filtered.get(index).map(|inst| self.group_of(inst))?;
+        if index > 0 && filtered.get(index - 1).map(|inst| self.group_of(inst)) == Some(group) {
+            return None;
+        }
+        Some(group.map(str::to_string))
+    }
+
+    /// Number of group headers up to and including the `index`th filtered
+    /// instance's
+    fn group_headers_through(&self, index: usize) -> usize {
+        let filtered = self.filtered_instances();
+        (0..filtered.len().min(index + 1))
+            .filter(|&i| self.group_header(&filtered, i).is_some())
+            .count()
+    }
+
+    /// Switch between the plain and grouped list, keeping the selection on
+    /// the same instance
+    pub fn toggle_group_by_tag(&mut self) {
+        let selected_pane = self.selected_instance().map(|i| i.pane_id.clone());
+        self.group_by_tag = !self.group_by_tag;
+        if let Some(pane_id) = selected_pane {
+            self.select_target(&pane_id);
+        }
+    }
+
+    /// Sort the list in the next order, keeping the selection on the same
+    /// instance
+    pub fn cycle_sort_order(&mut self) {
+        self.clear_messages();
+        let orders = SortOrder::ALL;
+        let index = orders
+            .iter()
+            .position(|o| *o == self.sort_order)
+            .unwrap_or(0);
+        self.set_sort_order(orders[(index + 1) % orders.len()]);
+        self.message = Some(trf!("Sorted by {}", self.sort_order.label()));
+    }
+
+    fn set_sort_order(&mut self, order: SortOrder) {
+        let selected_pane = self.selected_instance().map(|i| i.pane_id.clone());
+        self.sort_order = order;
+        self.sort_order.sort(&mut self.instances);
+        if let Some(pane_id) = selected_pane {
+            self.select_target(&pane_id);
+        }
+    }
+
+    /// Give the preview more (`grow`) or less of the screen
+    pub fn resize_preview(&mut self, grow: bool) {
+        let (min, max) = PREVIEW_PERCENT_RANGE;
+        self.preview_percent = if grow {
+            (self.preview_percent + PREVIEW_PERCENT_STEP).min(max)
+        } else {
+            self.preview_percent
+                .saturating_sub(PREVIEW_PERCENT_STEP)
+                .max(min)
+        };
+    }
+
+    /// Apply the state saved by the previous run
+    fn restore_ui_state(&mut self, state: UiState) {
+        self.filter = state.filter;
+        self.group_by_tag = state.group_by_tag;
+        self.preview_percent = state.preview_percent;
+        self.previous_pane = state.previous;
+        if let Some(order) = state.sort_order {
+            self.set_sort_order(order);
+        }
+        if let Some(target) = state.selected {
+            if self.select_target(&target) {
+                self.update_preview();
+            }
+            self.resume_selection = Some(target);
+        }
+    }
+
+    /// Save the selection, filter, grouping, sort order and preview size for
+    /// the next run
+    pub fn save_ui_state(&self) -> Result<()> {
+        ui_state::save(&UiState {
+            selected: self.selected_instance().map(|i| i.tmux_target()),
+            filter: self.filter.clone(),
+            group_by_tag: self.group_by_tag,
+            sort_order: Some(self.sort_order).filter(|o| *o != self.config.sort_order),
+            preview_percent: self.preview_percent,
+            previous: self.previous_pane.clone(),
+        })
+    }
+
+    /// Get the currently selected instance
+    pub fn selected_instance(&self) -> Option<&ClaudeInstance> {
+        let filtered = self.filtered_instances();
+        filtered.get(self.selected).copied()
+    }
+
+    /// Move selection up
+    pub fn select_prev(&mut self) {
+        let count = self.filtered_instances().len();
+        if count > 0 && self.selected > 0 {
+            self.selected -= 1;
+            self.update_preview();
+        }
+    }
+
+    /// Move selection down
+    pub fn select_next(&mut self) {
+        let count = self.filtered_instances().len();
+        if count > 0 && self.selected < count - 1 {
+            self.selected += 1;
+            self.update_preview();
+        }
+    }
+
+    /// Move selection by one page of visible rows
+    pub fn select_page(&mut self, forward: bool) {
+        let page = self.scroll_state.page_size().max(1);
+        let index = if forward {
+            self.selected.saturating_add(page)
+        } else {
+            self.selected.saturating_sub(page)
+        };
+        self.select_index(index);
+    }
+
+    /// Select the instance at `index`, clamped to the list
+    pub fn select_index(&mut self, index: usize) {
+        let count = self.filtered_instances().len();
+        if count == 0 {
+            return;
+        }
+        let index = index.min(count - 1);
+        if index != self.selected {
+            self.selected = index;
+            self.update_preview();
+        }
+    }
+
+    /// Pane IDs of the instances shown in the list window at the last render
+    fn visible_pane_ids(&self) -> HashSet<String> {
+        self.filtered_instances()
+            .iter()
+            .skip(self.scroll_state.offset())
+            .take(self.scroll_state.page_size())
+            .map(|inst| inst.pane_id.clone())
+            .collect()
+    }
+
+    /// Switch to the selected instance's pane
+    pub fn switch_to_selected(&mut self) {
+        self.clear_messages();
+        if let Some(instance) = self.selected_instance() {
+            let target = match self.pick {
+                Some(PickOutput::Directory) => instance.working_directory.display().to_string(),
+                _ => instance.tmux_target(),
+            };
+            self.switch_to(target);
+        } else if self.offers_agent_here() {
+            self.start_agent_here();
+        }
+    }
+
+    /// Whether no agent runs in the `--cwd` directory, so switching starts
+    /// one there instead
+    pub fn offers_agent_here(&self) -> bool {
+        self.scope.is_some()
+            && self.filter.is_empty()
+            && self.pick.is_none()
+            && !matches!(
+                self.loading_state,
+                LoadingState::NotStarted | LoadingState::LoadingInstances
+            )
+            && self.filtered_instances().is_empty()
+    }
+
+    /// Start Claude in a new session in the `--cwd` directory and switch to it
+    pub fn start_agent_here(&mut self) {
+        self.clear_messages();
+        let Some(dir) = self.scope.clone() else {
+            return;
+        };
+        let taken = Tmux::list_sessions().unwrap_or_default();
+        let name = workspace::session_name(&self.config.session_name_template, &dir, &taken);
+        let command = self.config.claude_command(&[&dir]);
+        match Tmux::new_session(&name, &dir, Some(&command)) {
+            Ok(()) => {
+                self.count(Counter::SessionsCreated);
+                self.switch_to(name);
+            }
+            Err(e) => self.error = Some(trf!("Failed to create session: {}", e)),
+        }
+    }
+
+    /// Switch to a pane and quit
+    ///
+    /// Outside tmux there is no client to switch, so the pane is attached to
+    /// once the TUI has exited. When picking, it is printed instead.
+    fn switch_to(&mut self, target: String) {
+        if self.pick.is_some() {
+            self.attach_on_exit = Some(target);
+            self.should_quit = true;
+            return;
+        }
+        if !Tmux::inside_tmux() {
+            self.remember_previous(&target);
+            self.attach_on_exit = Some(target);
+            self.should_quit = true;
+            return;
+        }
+        match Tmux::switch_to_pane(&target) {
+            Ok(_) => {
+                self.remember_previous(&target);
+                self.should_quit = true;
+            }
+            Err(e) => self.error = Some(trf!("Failed to switch: {}", e)),
+        }
+    }
+
+    /// With `--once`, switch to a session or pane just launched, which quits
+    fn switch_after_launch(&mut self, target: String) {
+        if self.once {
+            self.switch_to(target);
+        }
+    }
+
+    /// Switch to a pane for a client of the socket, keeping the TUI open
+    pub fn switch_for_client(&mut self, target: &str) -> Result<()> {
+        Tmux::switch_to_pane(target)?;
+        self.remember_previous(target);
+        Ok(())
+    }
+
+    /// Keep the pane being left, to switch back to it
+    fn remember_previous(&mut self, target: &str) {
+        if let Some(current) = self.current_pane.as_ref().filter(|c| *c != target) {
+            self.previous_pane = Some(current.clone());
+        }
+    }
+
+    /// Switch back to the pane switched away from last
+    pub fn switch_back(&mut self) {
+        self.clear_messages();
+        match self.previous_pane.clone() {
+            Some(target) => self.switch_to(target),
+            None => self.error = Some(tr("No previous instance to switch back to").to_string()),
+        }
+    }
+
+    // =========================================================================
+    // Action menu
+    // =========================================================================
 
     /// Enter the action menu for the selected instance
     pub fn enter_action_menu(&mut self) {
@@ -381,15 +1578,71 @@ impl App {
         }
     }
 
+    /// Open the action menu on what the ahead/behind indicator calls for:
+    /// pull when behind, push when ahead, rebase or merge when diverged
+    pub fn offer_upstream_actions(&mut self) {
+        self.clear_messages();
+        let Some(git) = self
+            .selected_instance()
+            .and_then(|i| i.git_context.as_ref())
+        else {
+            return;
+        };
+        if !git.has_upstream {
+            self.error = Some(tr("No upstream branch to compare with").to_string());
+            return;
+        }
+        if git.ahead == 0 && git.behind == 0 {
+            self.message = Some(tr("Up to date with upstream").to_string());
+            return;
+        }
+        let wanted = match (git.ahead, git.behind) {
+            (0, _) => SessionAction::Pull,
+            (_, 0) => SessionAction::Push,
+            // Merge is listed right after
+            _ => SessionAction::RebaseOnUpstream,
+        };
+        let dirty = git.behind > 0 && git.is_dirty();
+        self.compute_actions();
+        match self.available_actions.iter().position(|a| *a == wanted) {
+            Some(index) => {
+                self.selected_action = index;
+                self.mode = Mode::ActionMenu;
+            }
+            None if dirty => {
+                self.error = Some(tr("Commit or stash changes before pulling").to_string());
+            }
+            None => {
+                self.error = Some(trf!("{} isn't available for this instance", wanted.label()));
+            }
+        }
+    }
+
     /// Execute the currently selected action from the action menu
     pub fn execute_selected_action(&mut self) {
         if let Some(action) = self.available_actions.get(self.selected_action).cloned() {
-            if action.requires_confirmation() {
-                self.pending_action = Some(action);
-                self.mode = Mode::ConfirmAction;
-            } else {
-                // execute_action handles its own mode transitions
-                self.execute_action(action);
+            self.confirm_or_execute(action);
+        }
+    }
+
+    /// Run a menu action from its own key, if the menu would offer it for
+    /// the selected instance
+    pub fn run_action(&mut self, action: SessionAction) {
+        self.clear_messages();
+        if self.selected_instance().is_none() {
+            return;
+        }
+        self.compute_actions();
+        let index = self.available_actions.iter().position(|a| {
+            *a == action || (action == SessionAction::Push && *a == SessionAction::PushSetUpstream)
+        });
+        match index {
+            Some(index) => {
+                self.selected_action = index;
+                self.execute_selected_action();
+            }
+            None => {
+                self.error = Some(trf!("{} isn't available for this instance", action.label()));
             }
         }
     }
@@ -397,38 +1650,103 @@ impl App {
     /// Compute available actions for the selected instance
     fn compute_actions(&mut self) {
         // Extract data we need from the instance first to avoid borrow conflicts
-        let instance_data = self
-            .selected_instance()
-            .map(|inst| (inst.working_directory.clone(), inst.git_context.clone()));
+        let instance_data = self.selected_instance().map(|inst| {
+            (
+                inst.working_directory.clone(),
+                inst.git_context.clone(),
+                inst.status,
+            )
+        });
 
-        let Some((working_dir, git_context)) = instance_data else {
+        let Some((working_dir, git_context, status)) = instance_data else {
             self.available_actions = vec![];
             self.pr_info = None;
+            self.branch_time = None;
+            self.agent_env = None;
             return;
         };
 
         let mut actions = vec![SessionAction::SwitchTo];
 
+        match status {
+            ClaudeCodeStatus::Working => actions.push(SessionAction::Interrupt),
+            ClaudeCodeStatus::Idle | ClaudeCodeStatus::WaitingInput => {
+                actions.push(SessionAction::Continue)
+            }
+            ClaudeCodeStatus::Crashed => {
+                actions.push(SessionAction::Restart);
+                actions.push(SessionAction::DismissCrash);
+            }
+            ClaudeCodeStatus::Unknown => {}
+        }
+        if self.selected_missing_dir_repo().is_some() {
+            actions.push(SessionAction::RespawnInMainRepo);
+        }
+        actions.push(SessionAction::SlashCommand);
+        if !self.config.templates.is_empty() {
+            actions.push(SessionAction::SendTemplate);
+        }
+        actions.push(SessionAction::SchedulePrompt);
+        actions.push(SessionAction::SendFile);
+        if working_dir.is_dir() {
+            actions.push(SessionAction::DuplicateWindow);
+            actions.push(SessionAction::DuplicateSplit);
+            actions.push(SessionAction::SplitShell);
+            actions.push(SessionAction::SplitEditor);
+        }
+
         // Reset PR info
         self.pr_info = None;
 
+        self.agent_env = self
+            .selected_instance()
+            .map(|i| agent_env::read(&i.pane_id, &working_dir));
+
+        self.branch_time = git_context.as_ref().and_then(|git| {
+            let repo = GitContext::repo_name(&working_dir)?;
+            worktime::branch_totals(store::now_unix())
+                .into_iter()
+                .find(|t| t.repo == repo && t.branch == git.branch)
+        });
+
         // Add git actions if applicable
         if let Some(ref git) = git_context {
             // New worktree: available for any git repo
             actions.push(SessionAction::NewWorktree);
+            actions.push(SessionAction::BranchFromIssue);
 
-            // Stage: if there are unstaged changes
-            if git.has_unstaged {
+            // Review: if there is anything to review
+            if git.is_dirty() {
+                actions.push(SessionAction::Review);
+            }
+
+            // Stage: if there are unstaged changes, or conflicts to mark resolved
+            if git.has_unstaged || git.has_conflicts {
                 actions.push(SessionAction::Stage);
             }
-            // Commit: if there are staged changes
-            if git.has_staged {
+            if git.has_conflicts {
+                actions.push(SessionAction::ResolveConflicts);
+            }
+            if let Some(operation) = git.in_progress {
+                // A stopped merge or rebase is finished by git, not by a plain commit
+                if !git.has_conflicts {
+                    actions.push(SessionAction::ContinueOperation(operation));
+                }
+                actions.push(SessionAction::AbortOperation(operation));
+            } else if git.has_staged {
+                // Commit: if there are staged changes
                 actions.push(SessionAction::Commit);
             }
+            // Interactive rebase: onto the upstream, or the default branch
+            if git.in_progress.is_none() && (git.has_upstream || git.default_branch.is_some()) {
+                actions.push(SessionAction::InteractiveRebase);
+            }
 
             // Fetch: always available if there's a remote (safe operation)
             if git.has_remote {
                 actions.push(SessionAction::Fetch);
+                let auto_fetch = git.auto_fetch.unwrap_or(self.config.auto_fetch);
+                actions.push(SessionAction::ToggleAutoFetch(!auto_fetch));
             }
 
             if git.has_upstream {
@@ -438,41 +1756,72 @@ impl App {
                 }
                 // Pull: behind > 0 and clean (dirty state can cause merge conflicts)
                 if git.behind > 0 && !git.is_dirty() {
-                    actions.push(SessionAction::Pull);
-                }
-
-                // PR actions: upstream exists, gh available, GitHub remote, not on default branch
-                if git::is_gh_available() && git::is_github_remote(&working_dir) {
-                    // Check if not on default branch
-                    if let Some(default_branch) = git::get_default_branch(&working_dir) {
-                        if git.branch != default_branch {
-                            // Check if PR already exists for this branch
-                            let pr_info = git::get_pull_request_info(&working_dir);
-                            if let Some(ref info) = pr_info {
-                                if info.state == "OPEN" {
-                                    actions.push(SessionAction::ViewPullRequest);
-                                    actions.push(SessionAction::ClosePullRequest);
-                                    actions.push(SessionAction::MergePullRequest);
-                                    actions.push(SessionAction::MergePullRequestAndClose);
-                                } else {
-                                    // PR exists but is CLOSED or MERGED - can create a new one
-                                    actions.push(SessionAction::CreatePullRequest);
+                    // A diverged branch can't fast-forward: rebase or merge,
+                    // or replace the upstream once rewritten on purpose
+                    if git.ahead > 0 && git.in_progress.is_none() {
+                        actions.push(SessionAction::RebaseOnUpstream);
+                        actions.push(SessionAction::MergeUpstream);
+                        actions.push(SessionAction::ForcePush);
+                    } else {
+                        actions.push(SessionAction::Pull);
+                    }
+                }
+            } else if git.has_remote {
+                // No upstream but remote exists - offer to push and set upstream
+                actions.push(SessionAction::PushSetUpstream);
+            }
+
+            // PR actions: remote exists, gh available, GitHub remote, not on default branch
+            // (creating one pushes the branch first when needed)
+            if git.has_remote && git.in_progress.is_none() && git::forge().supports(&working_dir) {
+                // Check if not on default branch
+                if let Some(default_branch) = git::get_default_branch(&working_dir) {
+                    if git.branch != default_branch {
+                        // Check if PR already exists for this branch
+                        let pr_info = git::forge().pull_request(&working_dir);
+                        if let Some(ref info) = pr_info {
+                            if info.state == "OPEN" {
+                                actions.push(SessionAction::ViewPullRequest);
+                                if self
+                                    .pr_checks
+                                    .get(&working_dir)
+                                    .is_some_and(|c| !c.runs.is_empty())
+                                {
+                                    actions.push(SessionAction::ViewChecks);
                                 }
+                                actions.push(SessionAction::ClosePullRequest);
+                                actions.push(SessionAction::MergePullRequest);
+                                actions.push(SessionAction::MergePullRequestAndClose);
                             } else {
-                                // No PR exists, offer to create one
+                                // PR exists but is CLOSED or MERGED - can create a new one
                                 actions.push(SessionAction::CreatePullRequest);
                             }
-                            // Store PR info for UI display
-                            self.pr_info = pr_info;
+                        } else {
+                            // No PR exists, offer to create one
+                            actions.push(SessionAction::CreatePullRequest);
                         }
+                        // Store PR info for UI display
+                        self.pr_info = pr_info;
                     }
                 }
-            } else if git.has_remote {
-                // No upstream but remote exists - offer to push and set upstream
-                actions.push(SessionAction::PushSetUpstream);
             }
         }
 
+        if Tmux::inside_tmux() {
+            let pane_id = self.selected_instance().map(|i| i.pane_id.clone());
+            if pane_id.is_some_and(|p| Tmux::is_joined(&p)) {
+                actions.push(SessionAction::SendPaneBack);
+            } else {
+                actions.push(SessionAction::JoinPane);
+            }
+        }
+        let logging = self.selected_instance().is_some_and(|i| i.logging);
+        actions.push(SessionAction::TogglePaneLog(!logging));
+        actions.push(SessionAction::CloneLayout);
+        actions.push(SessionAction::ArrangePanes);
+        actions.push(SessionAction::MoveWindow);
+        actions.push(SessionAction::LinkWindow);
+        actions.push(SessionAction::Archive);
         actions.push(SessionAction::Kill);
 
         // Add worktree deletion option if this is a worktree
@@ -493,37 +1842,125 @@ impl App {
     /// Start the kill confirmation flow (direct kill without action menu)
     pub fn start_kill(&mut self) {
         self.clear_messages();
-        if self.selected_instance().is_some() {
-            self.pending_action = Some(SessionAction::Kill);
-            self.mode = Mode::ConfirmAction;
+        if self.selected_instance().is_none() {
+            return;
         }
+        self.confirm_or_execute(SessionAction::Kill);
     }
 
-    /// Confirm and execute the pending action
-    pub fn confirm_action(&mut self) {
-        if let Some(action) = self.pending_action.take() {
+    /// Ask to confirm `action` if it needs it, else execute it
+    fn confirm_or_execute(&mut self, action: SessionAction) {
+        self.unpushed_commits = 0;
+        self.unpushed_receiver = None;
+        if matches!(
+            action,
+            SessionAction::Kill | SessionAction::KillAndDeleteWorktree
+        ) {
+            if let Some(path) = self
+                .selected_instance()
+                .map(|i| i.working_directory.clone())
+            {
+                self.check_unpushed(vec![path]);
+            }
+        }
+        if self.needs_confirmation(&action) {
+            self.pending_action = Some(action);
+            self.mode = Mode::ConfirmAction;
+        } else {
+            // execute_action handles its own mode transitions
             self.execute_action(action);
         }
-        self.mode = Mode::Normal;
     }
 
-    /// Execute an action on the selected instance
-    fn execute_action(&mut self, action: SessionAction) {
-        let Some(instance) = self.selected_instance() else {
-            self.mode = Mode::Normal;
-            return;
-        };
-        let target = instance.tmux_target();
-        let session_name = instance.session_name.clone();
-        let working_directory = instance.working_directory.clone();
-        let git_context = instance.git_context.clone();
+    /// Whether `action` asks for confirmation: always for some, and for
+    /// killing, deleting a worktree and force-pushing unless `[confirm]`
+    /// turns it off, in which case killing and deleting a worktree still ask
+    /// when commits would be left on no remote, or until they are counted
+    fn needs_confirmation(&self, action: &SessionAction) -> bool {
+        let confirm = &self.config.confirm;
+        let unpushed = self.unpushed_commits > 0 || self.checking_unpushed();
+        match action {
+            SessionAction::Kill => confirm.kill || unpushed,
+            SessionAction::KillAndDeleteWorktree => confirm.delete_worktree || unpushed,
+            SessionAction::ForcePush => confirm.force_push,
+            _ => action.requires_confirmation(),
+        }
+    }
+
+    /// Count the commits of the checkouts at `paths` that no remote has on
+    /// a worker thread, before killing them
+    fn check_unpushed(&mut self, paths: Vec<PathBuf>) {
+        let (tx, rx) = mpsc::channel();
+        self.unpushed_receiver = Some(rx);
+        thread::spawn(move || {
+            let _ = tx.send(git::unpushed_commits(&paths));
+        });
+    }
+
+    /// Whether commits on no remote are still being counted
+    pub fn checking_unpushed(&self) -> bool {
+        self.unpushed_receiver.is_some()
+    }
+
+    /// Pick up the count of commits on no remote, and kill right away when
+    /// there are none and the confirmation only waited for the count
+    pub fn poll_unpushed(&mut self) {
+        let Some(rx) = &self.unpushed_receiver else {
+            return;
+        };
+        let result = rx.try_recv();
+        if result == Err(mpsc::TryRecvError::Empty) {
+            return;
+        }
+        self.unpushed_receiver = None;
+        self.needs_redraw = true;
+        // Keep asking when the count failed
+        let Ok(count) = result else {
+            return;
+        };
+        self.unpushed_commits = count;
+        if count > 0 {
+            return;
+        }
+        match self.mode {
+            Mode::ConfirmAction
+                if self
+                    .pending_action
+                    .as_ref()
+                    .is_some_and(|action| !self.needs_confirmation(action)) =>
+            {
+                self.confirm_action()
+            }
+            Mode::Sessions {
+                confirm_kill: true, ..
+            } if !self.config.confirm.kill => self.kill_listed_session(),
+            _ => {}
+        }
+    }
+
+    /// Confirm and execute the pending action
+    pub fn confirm_action(&mut self) {
+        if let Some(action) = self.pending_action.take() {
+            self.execute_action(action);
+        }
+        self.mode = Mode::Normal;
+    }
+
+    /// Execute an action on the selected instance
+    fn execute_action(&mut self, action: SessionAction) {
+        let Some(instance) = self.selected_instance() else {
+            self.mode = Mode::Normal;
+            return;
+        };
+        let target = instance.tmux_target();
+        let pane_id = instance.pane_id.clone();
+        let session_name = instance.session_name.clone();
+        let working_directory = instance.working_directory.clone();
+        let git_context = instance.git_context.clone();
 
         match action {
             SessionAction::SwitchTo => {
-                match Tmux::switch_to_pane(&target) {
-                    Ok(_) => self.should_quit = true,
-                    Err(e) => self.error = Some(format!("Failed to switch: {}", e)),
-                }
+                self.switch_to(target.clone());
                 self.mode = Mode::Normal;
             }
             SessionAction::Rename => {
@@ -532,13 +1969,35 @@ impl App {
                     new_name: session_name,
                 };
             }
+            SessionAction::Review => {
+                self.start_review();
+            }
+            SessionAction::InteractiveRebase => {
+                let Some(git) = git_context else {
+                    self.mode = Mode::Normal;
+                    return;
+                };
+                let base = match git.default_branch {
+                    Some(branch) if !git.has_upstream => branch,
+                    _ => "@{upstream}".to_string(),
+                };
+                self.mode = Mode::Rebase {
+                    pane_id,
+                    path: working_directory,
+                    branch: git.branch,
+                    base,
+                };
+            }
             SessionAction::Stage => {
-                match GitContext::stage_all(&working_directory) {
+                match git::try_lock_repo(&working_directory, tr("staging"))
+                    .and_then(|_lock| GitContext::stage_all(&working_directory))
+                {
                     Ok(_) => {
                         self.refresh_instances();
-                        self.message = Some("Staged all changes".to_string());
+                        self.message = Some(tr("Staged all changes").to_string());
+                        self.run_hooks(HookEvent::Stage, &session_name, &working_directory);
                     }
-                    Err(e) => self.error = Some(format!("Stage failed: {}", e)),
+                    Err(e) => self.error = Some(trf!("Stage failed: {}", e)),
                 }
                 self.mode = Mode::Normal;
             }
@@ -548,42 +2007,130 @@ impl App {
                 };
             }
             SessionAction::Push => {
-                match GitContext::push(&working_directory) {
-                    Ok(_) => {
+                self.start_git_job(session_name, GitOperation::Push, working_directory);
+            }
+            SessionAction::PushSetUpstream => {
+                self.start_git_job(
+                    session_name,
+                    GitOperation::PushSetUpstream,
+                    working_directory,
+                );
+            }
+            SessionAction::Fetch => {
+                self.start_git_job(session_name, GitOperation::Fetch, working_directory);
+            }
+            SessionAction::ToggleAutoFetch(enabled) => {
+                match git::set_auto_fetch(&working_directory, enabled) {
+                    Ok(()) => {
+                        self.message = Some(if enabled {
+                            tr("Auto-fetch turned on for this repository").to_string()
+                        } else {
+                            tr("Auto-fetch turned off for this repository").to_string()
+                        });
+                        // Fetch right away rather than at the next interval
+                        self.last_auto_fetch = None;
                         self.refresh_instances();
-                        self.message = Some("Pushed to remote".to_string());
                     }
-                    Err(e) => self.error = Some(format!("Push failed: {}", e)),
+                    Err(e) => self.error = Some(trf!("Failed to change auto-fetch: {}", e)),
                 }
                 self.mode = Mode::Normal;
             }
-            SessionAction::PushSetUpstream => {
-                match GitContext::push_set_upstream(&working_directory) {
+            SessionAction::Pull => {
+                self.start_git_job(session_name, GitOperation::Pull, working_directory);
+            }
+            SessionAction::ForcePush => {
+                self.start_git_job(session_name, GitOperation::ForcePush, working_directory);
+            }
+            SessionAction::RebaseOnUpstream => {
+                self.start_git_job(session_name, GitOperation::Rebase, working_directory);
+            }
+            SessionAction::MergeUpstream => {
+                self.start_git_job(session_name, GitOperation::Merge, working_directory);
+            }
+            SessionAction::ResolveConflicts => {
+                self.show_conflicts();
+            }
+            SessionAction::ContinueOperation(operation) => {
+                match git::try_lock_repo(&working_directory, tr("continuing"))
+                    .and_then(|_lock| GitContext::continue_operation(&working_directory, operation))
+                {
                     Ok(_) => {
-                        self.refresh_instances();
-                        self.message = Some("Pushed and set upstream".to_string());
+                        // A rebase may stop again at a later commit (e.g., `edit`)
+                        let stopped = GitContext::detect(&working_directory)
+                            .is_some_and(|git| git.in_progress.is_some());
+                        self.message = Some(
+                            match operation {
+                                PendingOperation::Merge => tr("Merge completed"),
+                                PendingOperation::Rebase if stopped => tr("Rebase continued"),
+                                PendingOperation::Rebase => tr("Rebase completed"),
+                            }
+                            .to_string(),
+                        );
                     }
-                    Err(e) => self.error = Some(format!("Push failed: {}", e)),
+                    Err(e) => self.error = Some(trf!("Continue failed: {}", e)),
                 }
+                self.refresh_instances();
                 self.mode = Mode::Normal;
             }
-            SessionAction::Fetch => {
-                match GitContext::fetch(&working_directory) {
+            SessionAction::AbortOperation(operation) => {
+                match git::try_lock_repo(&working_directory, tr("aborting"))
+                    .and_then(|_lock| GitContext::abort_operation(&working_directory, operation))
+                {
                     Ok(_) => {
-                        self.refresh_instances();
-                        self.message = Some("Fetched from remote".to_string());
+                        self.message = Some(
+                            match operation {
+                                PendingOperation::Merge => tr("Merge aborted"),
+                                PendingOperation::Rebase => tr("Rebase aborted"),
+                            }
+                            .to_string(),
+                        );
                     }
-                    Err(e) => self.error = Some(format!("Fetch failed: {}", e)),
+                    Err(e) => self.error = Some(trf!("Abort failed: {}", e)),
                 }
+                self.refresh_instances();
                 self.mode = Mode::Normal;
             }
-            SessionAction::Pull => {
-                match GitContext::pull(&working_directory) {
-                    Ok(_) => {
-                        self.refresh_instances();
-                        self.message = Some("Pulled from remote".to_string());
-                    }
-                    Err(e) => self.error = Some(format!("Pull failed: {}", e)),
+            SessionAction::Continue => {
+                self.continue_selected();
+            }
+            SessionAction::Interrupt => {
+                self.interrupt_selected();
+            }
+            SessionAction::Restart => {
+                self.restart_selected();
+            }
+            SessionAction::DismissCrash => {
+                self.dismiss_selected_crash();
+            }
+            SessionAction::RespawnInMainRepo => {
+                self.respawn_selected_in_main_repo();
+            }
+            SessionAction::SlashCommand => {
+                self.start_slash_command_menu();
+            }
+            SessionAction::SendTemplate => {
+                self.start_template_picker();
+            }
+            SessionAction::SchedulePrompt => {
+                self.start_schedule_prompt();
+            }
+            SessionAction::SendFile => {
+                self.start_send_file();
+            }
+            SessionAction::DuplicateWindow => {
+                self.duplicate_selected(false);
+                self.mode = Mode::Normal;
+            }
+            SessionAction::DuplicateSplit => {
+                self.duplicate_selected(true);
+                self.mode = Mode::Normal;
+            }
+            SessionAction::SplitShell | SessionAction::SplitEditor => {
+                let command = (action == SessionAction::SplitEditor)
+                    .then(|| format!("{} .", editor_command()));
+                match Tmux::split_running(&pane_id, &working_directory, command.as_deref()) {
+                    Ok(new_pane) => self.switch_to(new_pane),
+                    Err(e) => self.error = Some(trf!("Failed to split pane: {}", e)),
                 }
                 self.mode = Mode::Normal;
             }
@@ -591,30 +2138,44 @@ impl App {
                 self.start_create_pull_request();
             }
             SessionAction::ViewPullRequest => {
-                match git::view_pull_request(&working_directory) {
+                match git::forge().view_pull_request(&working_directory) {
+                    Ok(_) => {
+                        self.message = Some(tr("Opened PR in browser").to_string());
+                    }
+                    Err(e) => self.error = Some(trf!("Failed to open PR: {}", e)),
+                }
+                self.mode = Mode::Normal;
+            }
+            SessionAction::ViewChecks => {
+                match git::forge().view_checks(&working_directory) {
                     Ok(_) => {
-                        self.message = Some("Opened PR in browser".to_string());
+                        self.message = Some(tr("Opened CI checks in browser").to_string());
                     }
-                    Err(e) => self.error = Some(format!("Failed to open PR: {}", e)),
+                    Err(e) => self.error = Some(trf!("Failed to open CI checks: {}", e)),
                 }
                 self.mode = Mode::Normal;
             }
             SessionAction::ClosePullRequest => {
-                match git::close_pull_request(&working_directory) {
+                match git::forge().close_pull_request(&working_directory) {
                     Ok(_) => {
-                        self.message = Some("Closed pull request".to_string());
+                        self.message = Some(tr("Closed pull request").to_string());
                     }
-                    Err(e) => self.error = Some(format!("Failed to close PR: {}", e)),
+                    Err(e) => self.error = Some(trf!("Failed to close PR: {}", e)),
                 }
                 self.mode = Mode::Normal;
             }
             SessionAction::MergePullRequest => {
-                match git::merge_pull_request(&working_directory, false) {
+                match git::forge().merge_pull_request(&working_directory, false) {
                     Ok(_) => {
                         self.refresh_instances();
-                        self.message = Some("Merged pull request".to_string());
+                        self.message = Some(tr("Merged pull request").to_string());
+                        self.run_hooks(
+                            HookEvent::MergePullRequest,
+                            &session_name,
+                            &working_directory,
+                        );
                     }
-                    Err(e) => self.error = Some(format!("Failed to merge PR: {}", e)),
+                    Err(e) => self.error = Some(trf!("Failed to merge PR: {}", e)),
                 }
                 self.mode = Mode::Normal;
             }
@@ -622,13 +2183,20 @@ impl App {
                 let is_worktree = git_context.as_ref().map(|g| g.is_worktree).unwrap_or(false);
 
                 // Step 1: Merge PR
-                match git::merge_pull_request(&working_directory, false) {
+                match git::forge().merge_pull_request(&working_directory, false) {
                     Ok(_) => {
+                        // A worktree is removed below, so its hooks run in the main checkout
+                        let hook_dir = git_context
+                            .as_ref()
+                            .and_then(|g| g.main_repo_path.clone())
+                            .filter(|_| is_worktree)
+                            .unwrap_or_else(|| working_directory.clone());
+                        self.run_hooks(HookEvent::MergePullRequest, &session_name, &hook_dir);
                         // Step 2: Delete worktree if applicable
                         if is_worktree {
-                            if let Err(e) = GitContext::delete_worktree(&working_directory, true) {
+                            if let Err(e) = self.remove_worktree(&working_directory, true) {
                                 self.error =
-                                    Some(format!("PR merged but failed to delete worktree: {}", e));
+                                    Some(trf!("PR merged but failed to delete worktree: {}", e));
                                 self.mode = Mode::Normal;
                                 return;
                             }
@@ -639,199 +2207,833 @@ impl App {
                             Ok(_) => {
                                 self.refresh_instances();
                                 self.message = Some(if is_worktree {
-                                    "Merged PR, removed worktree, and closed session".to_string()
+                                    tr("Merged PR, removed worktree, and closed session")
+                                        .to_string()
                                 } else {
-                                    "Merged PR and closed session".to_string()
+                                    tr("Merged PR and closed session").to_string()
                                 });
                             }
                             Err(e) => {
                                 self.refresh_instances();
                                 self.error =
-                                    Some(format!("PR merged but failed to kill session: {}", e));
+                                    Some(trf!("PR merged but failed to kill session: {}", e));
                             }
                         }
                     }
-                    Err(e) => self.error = Some(format!("Failed to merge PR: {}", e)),
+                    Err(e) => self.error = Some(trf!("Failed to merge PR: {}", e)),
+                }
+                self.mode = Mode::Normal;
+            }
+            SessionAction::JoinPane => match Tmux::join_pane(&pane_id) {
+                // Quit, as when switching, so the pane can be used right away
+                Ok(_) => self.should_quit = true,
+                Err(e) => {
+                    self.error = Some(trf!("Failed to join pane: {}", e));
+                    self.mode = Mode::Normal;
+                }
+            },
+            SessionAction::SendPaneBack => {
+                match Tmux::send_pane_back(&pane_id) {
+                    Ok(home) => {
+                        self.refresh_instances();
+                        self.message = Some(trf!("Sent pane back to {}", home));
+                    }
+                    Err(e) => self.error = Some(trf!("Failed to send pane back: {}", e)),
+                }
+                self.mode = Mode::Normal;
+            }
+            SessionAction::TogglePaneLog(true) => {
+                self.start_pane_log();
+                self.mode = Mode::Normal;
+            }
+            SessionAction::TogglePaneLog(false) => {
+                match Tmux::stop_pane_log(&pane_id) {
+                    Ok(()) => {
+                        self.refresh_instances();
+                        self.message = Some(tr("Stopped logging the pane").to_string());
+                    }
+                    Err(e) => self.error = Some(trf!("Failed to stop logging: {}", e)),
                 }
                 self.mode = Mode::Normal;
             }
+            SessionAction::Archive => {
+                self.archive_selected();
+                self.mode = Mode::Normal;
+            }
             SessionAction::Kill => {
                 match Tmux::kill_session(&session_name) {
                     Ok(_) => {
                         self.refresh_instances();
-                        self.message = Some(format!("Killed session '{}'", session_name));
+                        self.message = Some(trf!("Killed session '{}'", session_name));
                     }
-                    Err(e) => self.error = Some(format!("Failed to kill: {}", e)),
+                    Err(e) => self.error = Some(trf!("Failed to kill: {}", e)),
                 }
                 self.mode = Mode::Normal;
             }
             SessionAction::NewWorktree => {
                 self.start_new_worktree();
             }
+            SessionAction::BranchFromIssue => {
+                self.start_branch_from_issue();
+            }
+            SessionAction::CloneLayout => {
+                self.start_clone_layout();
+            }
+            SessionAction::ArrangePanes => {
+                self.mode = Mode::ArrangePanes {
+                    pane_id,
+                    selected: 0,
+                };
+            }
+            SessionAction::MoveWindow => {
+                self.start_move_window(false);
+            }
+            SessionAction::LinkWindow => {
+                self.start_move_window(true);
+            }
             SessionAction::KillAndDeleteWorktree => {
                 // First delete the worktree
-                match GitContext::delete_worktree(&working_directory, false) {
+                match self.remove_worktree(&working_directory, false) {
                     Ok(_) => {
                         // Then kill the session
                         match Tmux::kill_session(&session_name) {
                             Ok(_) => {
                                 self.refresh_instances();
-                                self.message = Some(format!(
-                                    "Deleted worktree and killed session '{}'",
-                                    session_name
-                                ));
+                                self.message = Some(if self.config.worktree_trash_days > 0 {
+                                    trf!(
+                                        "Moved worktree to the trash and killed session '{}'",
+                                        session_name
+                                    )
+                                } else {
+                                    trf!("Deleted worktree and killed session '{}'", session_name)
+                                });
                             }
                             Err(e) => {
                                 self.refresh_instances();
-                                self.error = Some(format!(
+                                self.error = Some(trf!(
                                     "Worktree deleted but failed to kill session: {}",
                                     e
                                 ));
                             }
                         }
                     }
-                    Err(e) => self.error = Some(format!("Failed to delete worktree: {}", e)),
+                    Err(e) => self.error = Some(trf!("Failed to delete worktree: {}", e)),
                 }
                 self.mode = Mode::Normal;
             }
         }
     }
 
+    /// Delete a worktree, or move it to the trash with `worktree_trash_days`
+    fn remove_worktree(&self, path: &Path, force: bool) -> Result<()> {
+        let _lock = git::try_lock_repo(path, tr("removing a worktree"))?;
+        let days = self.config.worktree_trash_days;
+        if days == 0 {
+            return GitContext::delete_worktree(path, force);
+        }
+        let trashed = GitContext::trash_worktree(path, force)?;
+        tracing::info!("moved worktree {} to {}", path.display(), trashed.display());
+        match trash::purge(days) {
+            Ok(0) => {}
+            Ok(n) => tracing::info!("deleted {} trashed worktrees older than {} days", n, days),
+            Err(e) => tracing::warn!("trash not purged: {:#}", e),
+        }
+        Ok(())
+    }
+
     // =========================================================================
-    // Dialog flows: Rename
+    // Background git operations
     // =========================================================================
 
-    /// Start the rename flow (renames the session)
-    pub fn start_rename(&mut self) {
-        self.clear_messages();
-        if let Some(instance) = self.selected_instance() {
-            self.mode = Mode::Rename {
-                old_name: instance.session_name.clone(),
-                new_name: instance.session_name.clone(),
-            };
-        }
+    /// Run a push/pull/fetch on a worker thread so the UI stays responsive
+    fn start_git_job(&mut self, session: String, operation: GitOperation, path: PathBuf) {
+        self.spawn_git_job(session, operation, path, None);
     }
 
-    /// Confirm and execute session rename
-    pub fn confirm_rename(&mut self) {
-        if let Mode::Rename {
-            ref old_name,
-            ref new_name,
-        } = self.mode
-        {
-            let old = old_name.clone();
-            let new = new_name.clone();
+    fn spawn_git_job(
+        &mut self,
+        session: String,
+        operation: GitOperation,
+        path: PathBuf,
+        prompted: Option<PromptedCredentials>,
+    ) {
+        self.mode = Mode::Normal;
+        if let Some(job) = &self.git_job {
+            self.error = Some(trf!(
+                "Already {} {}; press Ctrl-C to cancel it",
+                job.operation.label(),
+                job.session
+            ));
+            return;
+        }
 
-            if old == new {
-                self.mode = Mode::Normal;
-                return;
-            }
+        let (tx, rx) = mpsc::channel();
+        let cancel = CancelToken::new();
+        let token = cancel.clone();
+        let worker_path = path.clone();
+        let credentials = prompted.as_ref().map(|p| p.credentials.clone());
+        let description = format!("{} {}", operation.label(), session);
+        let worker_description = description.clone();
+        tracing::info!("{:?} started in {}", operation, path.display());
+        thread::spawn(move || {
+            // Waits for other operations in the repository first
+            let result = git::lock_repo(&worker_path, &worker_description, &token)
+                .and_then(|_lock| operation.run(&worker_path, &token, credentials.as_ref()));
+            let _ = tx.send(result);
+        });
 
-            match Tmux::rename_session(&old, &new) {
-                Ok(_) => {
-                    self.refresh_instances();
-                    self.message = Some(format!("Renamed '{}' to '{}'", old, new));
-                }
-                Err(e) => {
-                    self.error = Some(format!("Failed to rename: {}", e));
-                }
-            }
+        self.git_job = Some(GitJob {
+            operation,
+            session,
+            description,
+            path,
+            prompted,
+            cancel,
+            receiver: rx,
+        });
+    }
+
+    /// Follow the git operations waiting for their repository (call this in
+    /// the event loop)
+    pub fn poll_repo_queue(&mut self) {
+        let queued = git::queued_operations();
+        if queued != self.queued_operations {
+            self.queued_operations = queued;
+            self.needs_redraw = true;
         }
-        self.mode = Mode::Normal;
     }
 
-    // =========================================================================
-    // Dialog flows: Commit
-    // =========================================================================
+    /// Check whether the background git operation finished (call this in the event loop)
+    pub fn poll_git_job(&mut self) {
+        let Some(job) = &self.git_job else {
+            return;
+        };
 
-    /// Confirm and execute the commit
-    pub fn confirm_commit(&mut self) {
-        if let Mode::Commit { ref message } = self.mode {
-            if message.trim().is_empty() {
-                self.error = Some("Commit message cannot be empty".to_string());
-                self.mode = Mode::Normal;
-                return;
+        let result = match job.receiver.try_recv() {
+            Ok(result) => result,
+            Err(mpsc::TryRecvError::Empty) => return,
+            Err(mpsc::TryRecvError::Disconnected) => {
+                Err(anyhow::anyhow!("worker thread exited unexpectedly"))
             }
+        };
 
-            if let Some(instance) = self.selected_instance() {
-                let path = instance.working_directory.clone();
-                let msg = message.clone();
-                match GitContext::commit(&path, &msg) {
-                    Ok(_) => {
+        let Some(job) = self.git_job.take() else {
+            return;
+        };
+        let operation = job.operation;
+        self.needs_redraw = true;
+        match &result {
+            Ok(()) => tracing::info!("{:?} finished in {}", operation, job.path.display()),
+            Err(e) => tracing::warn!("{:?} failed in {}: {:#}", operation, job.path.display(), e),
+        }
+        match result {
+            Ok(()) => {
+                self.refresh_instances();
+                self.message = Some(operation.success_message().to_string());
+                self.run_hooks(operation.hook_event(), &job.session, &job.path);
+                if matches!(
+                    operation,
+                    GitOperation::Push | GitOperation::PushSetUpstream | GitOperation::ForcePush
+                ) {
+                    self.count(Counter::Pushes);
+                    if let Some(draft) = self.pull_request_after_push.take() {
+                        self.create_pull_request(draft);
+                    }
+                }
+                if let Some(PromptedCredentials {
+                    credentials: Credentials::UserPass { username, password },
+                    store_for: Some(url),
+                }) = job.prompted
+                {
+                    if let Err(e) = git::approve_credentials(&url, &username, &password) {
+                        self.error = Some(trf!("Credentials not saved: {}", e));
+                    }
+                }
+            }
+            // Ask for secrets unless the user is busy in another dialog
+            Err(e) if !matches!(self.mode, Mode::Normal | Mode::ActionMenu) => {
+                self.pull_request_after_push = None;
+                self.error = Some(format!("{}: {}", operation.failure_prefix(), e));
+            }
+            Err(e) => {
+                if let Some(request) = e.downcast_ref::<CredentialsRequired>() {
+                    let previous = job.prompted.as_ref();
+                    let username = request
+                        .username
+                        .clone()
+                        .or_else(|| match previous.map(|p| &p.credentials) {
+                            Some(Credentials::UserPass { username, .. }) => Some(username.clone()),
+                            _ => None,
+                        })
+                        .unwrap_or_default();
+                    self.mode = Mode::Credentials {
+                        operation,
+                        session: job.session,
+                        path: job.path,
+                        url: request.url.clone(),
+                        field: if username.is_empty() {
+                            CredentialsField::Username
+                        } else {
+                            CredentialsField::Password
+                        },
+                        username,
+                        password: String::new(),
+                        store: previous.is_some_and(|p| p.store_for.is_some()),
+                        rejected: request.rejected,
+                    };
+                } else if let Some(request) = e.downcast_ref::<PassphraseRequired>() {
+                    self.mode = Mode::Passphrase {
+                        operation,
+                        session: job.session,
+                        path: job.path,
+                        key: request.key.clone(),
+                        passphrase: String::new(),
+                        rejected: request.rejected,
+                    };
+                } else {
+                    self.pull_request_after_push = None;
+                    self.error = Some(format!("{}: {}", operation.failure_prefix(), e));
+                    // Show a merge or rebase that stopped on a conflict
+                    if matches!(operation, GitOperation::Rebase | GitOperation::Merge) {
                         self.refresh_instances();
-                        self.message = Some("Committed changes".to_string());
                     }
-                    Err(e) => self.error = Some(format!("Commit failed: {}", e)),
                 }
             }
         }
-        self.mode = Mode::Normal;
     }
 
-    // =========================================================================
-    // Dialog flows: New Session
-    // =========================================================================
-
-    /// Start the new session flow
-    pub fn start_new_session(&mut self) {
-        self.clear_messages();
-        // Default to current directory
-        let default_path = std::env::current_dir()
-            .map(|p| p.to_string_lossy().to_string())
-            .unwrap_or_else(|_| "~".to_string());
-
-        // Get initial path suggestions
-        let completion = crate::completion::complete_path(&default_path);
+    /// Retry the operation waiting in the credentials prompt
+    pub fn confirm_credentials(&mut self) {
+        let Mode::Credentials {
+            operation,
+            ref session,
+            ref path,
+            ref url,
+            ref username,
+            ref password,
+            store,
+            ..
+        } = self.mode
+        else {
+            return;
+        };
+        if username.is_empty() || password.is_empty() {
+            self.error = Some(tr("Username and password are required").to_string());
+            return;
+        }
 
-        self.mode = Mode::NewSession {
-            name: String::new(),
-            path: default_path,
-            field: NewSessionField::Name,
-            path_suggestions: completion.suggestions,
-            path_selected: None,
+        let prompted = PromptedCredentials {
+            credentials: Credentials::UserPass {
+                username: username.clone(),
+                password: password.clone(),
+            },
+            store_for: store.then(|| url.clone()),
         };
+        let (session, path) = (session.clone(), path.clone());
+        self.clear_messages();
+        self.spawn_git_job(session, operation, path, Some(prompted));
     }
 
-    /// Create the new session
-    pub fn confirm_new_session(&mut self, start_claude: bool) {
-        if let Mode::NewSession {
-            ref name, ref path, ..
+    /// Retry the operation waiting in the passphrase prompt
+    pub fn confirm_passphrase(&mut self) {
+        let Mode::Passphrase {
+            operation,
+            ref session,
+            ref path,
+            ref key,
+            ref passphrase,
+            ..
         } = self.mode
-        {
-            if name.is_empty() {
-                self.error = Some("Session name cannot be empty".to_string());
-                self.mode = Mode::Normal;
-                return;
-            }
+        else {
+            return;
+        };
+        if passphrase.is_empty() {
+            self.error = Some(tr("Passphrase is required").to_string());
+            return;
+        }
 
-            let session_name = name.clone();
-            let session_path = expand_path(path);
+        let prompted = PromptedCredentials {
+            credentials: Credentials::SshPassphrase {
+                key: key.clone(),
+                passphrase: passphrase.clone(),
+            },
+            store_for: None,
+        };
+        let (session, path) = (session.clone(), path.clone());
+        self.clear_messages();
+        self.spawn_git_job(session, operation, path, Some(prompted));
+    }
 
-            match Tmux::new_session(&session_name, &session_path, start_claude) {
-                Ok(_) => {
-                    self.refresh_instances();
-                    self.message = Some(format!("Created session '{}'", session_name));
-                }
-                Err(e) => {
-                    self.error = Some(format!("Failed to create session: {}", e));
-                }
-            }
-        }
-        self.mode = Mode::Normal;
+    /// Abort the background git operation, if any
+    ///
+    /// The worker stops at libgit2's next callback; its result is discarded
+    /// either way, so a hung connection no longer blocks another operation.
+    pub fn cancel_git_job(&mut self) {
+        let Some(job) = self.git_job.take() else {
+            return;
+        };
+        job.cancel.cancel();
+        self.pull_request_after_push = None;
+        self.message = Some(trf!("Cancelled {} {}", job.operation.label(), job.session));
     }
 
     // =========================================================================
-    // Dialog flows: New Worktree
+    // Batch sync
     // =========================================================================
 
-    /// Start the new worktree flow
-    pub fn start_new_worktree(&mut self) {
-        self.clear_messages();
-        let Some(instance) = self.selected_instance() else {
+    /// Mark or unmark the selected instance for syncing, then move down
+    pub fn toggle_mark(&mut self) {
+        let Some(pane_id) = self.selected_instance().map(|i| i.pane_id.clone()) else {
             return;
         };
+        if !self.marked.remove(&pane_id) {
+            self.marked.insert(pane_id);
+        }
+        self.select_next();
+    }
 
-        // Get the repo path (use main repo if this is a worktree)
+    /// Number of listed instances that are marked
+    pub fn marked_count(&self) -> usize {
+        self.instances
+            .iter()
+            .filter(|i| self.marked.contains(&i.pane_id))
+            .count()
+    }
+
+    /// Sync the checkouts of the marked instances with their upstreams, or
+    /// those of every listed instance when none is marked
+    ///
+    /// Checkouts are fetched and fast-forwarded or rebased one at a time on a
+    /// worker thread; the summary fills in as they finish.
+    pub fn start_sync(&mut self) {
+        self.clear_messages();
+        let marked: Vec<&ClaudeInstance> = self
+            .instances
+            .iter()
+            .filter(|i| self.marked.contains(&i.pane_id))
+            .collect();
+        let targets = if marked.is_empty() {
+            self.filtered_instances()
+        } else {
+            marked
+        };
+
+        // One row per checkout; any agent working in it holds it back
+        let mut rows: Vec<SyncRow> = Vec::new();
+        for instance in targets {
+            let path = &instance.working_directory;
+            let skip = match &instance.git_context {
+                _ if instance.status == ClaudeCodeStatus::Working => Some(tr("agent is working")),
+                None => Some(tr("not a git repository")),
+                Some(git) if git.in_progress.is_some() || git.has_conflicts => {
+                    Some(tr("merge or rebase in progress"))
+                }
+                Some(git) if !git.has_upstream => Some(tr("no upstream branch")),
+                Some(_) => None,
+            };
+            match rows.iter_mut().find(|row| &row.path == path) {
+                Some(row) => {
+                    if let Some(reason) = skip.filter(|_| row.state == SyncState::Pending) {
+                        row.state = SyncState::Skipped(reason.to_string());
+                    }
+                }
+                None => rows.push(SyncRow {
+                    session: instance.session_name.clone(),
+                    path: path.clone(),
+                    label: match &instance.git_context {
+                        Some(git) => format!("{} ({})", instance.display_path(), git.branch),
+                        None => instance.display_path(),
+                    },
+                    state: match skip {
+                        Some(reason) => SyncState::Skipped(reason.to_string()),
+                        None => SyncState::Pending,
+                    },
+                }),
+            }
+        }
+        if rows.is_empty() {
+            self.error = Some(tr("No instances to sync").to_string());
+            return;
+        }
+
+        let jobs: Vec<(usize, PathBuf)> = rows
+            .iter()
+            .enumerate()
+            .filter(|(_, row)| row.state == SyncState::Pending)
+            .map(|(i, row)| (i, row.path.clone()))
+            .collect();
+        tracing::info!("sync: started for {} checkouts", jobs.len());
+        let (tx, rx) = mpsc::channel();
+        let cancel = CancelToken::new();
+        let token = cancel.clone();
+        thread::spawn(move || {
+            for (i, path) in jobs {
+                if token.is_cancelled() {
+                    break;
+                }
+                let name = path.file_name().unwrap_or_default().to_string_lossy();
+                let description = trf!("syncing {}", name);
+                let result = git::lock_repo(&path, &description, &token)
+                    .and_then(|_lock| GitContext::sync(&path, &token));
+                if tx.send((i, result)).is_err() {
+                    break;
+                }
+            }
+        });
+        self.sync_job = Some(SyncJob {
+            cancel,
+            receiver: rx,
+        });
+        self.mode = Mode::Sync { rows, selected: 0 };
+    }
+
+    /// Record the results of the running sync (call this in the event loop)
+    pub fn poll_sync_job(&mut self) {
+        let Some(job) = &self.sync_job else {
+            return;
+        };
+        let mut finished = false;
+        let mut results = Vec::new();
+        loop {
+            match job.receiver.try_recv() {
+                Ok(result) => results.push(result),
+                Err(mpsc::TryRecvError::Empty) => break,
+                Err(mpsc::TryRecvError::Disconnected) => {
+                    finished = true;
+                    break;
+                }
+            }
+        }
+        if results.is_empty() && !finished {
+            return;
+        }
+        self.needs_redraw = true;
+
+        let mut updated = Vec::new();
+        if let Mode::Sync { ref mut rows, .. } = self.mode {
+            for (i, result) in results {
+                let Some(row) = rows.get_mut(i) else {
+                    continue;
+                };
+                row.state = match result {
+                    Ok(outcome) => {
+                        tracing::info!("sync: {} {:?}", row.path.display(), outcome);
+                        if outcome != SyncOutcome::UpToDate {
+                            updated.push((row.session.clone(), row.path.clone()));
+                        }
+                        SyncState::Done(outcome)
+                    }
+                    Err(e) => {
+                        tracing::warn!("sync: {} failed: {:#}", row.path.display(), e);
+                        SyncState::Failed(format!("{:#}", e))
+                    }
+                };
+            }
+        }
+        for (session, path) in updated {
+            self.run_hooks(HookEvent::Pull, &session, &path);
+        }
+        if finished {
+            self.sync_job = None;
+            self.refresh_instances();
+        }
+    }
+
+    /// Whether checkouts are still being synced
+    pub fn is_syncing(&self) -> bool {
+        self.sync_job.is_some()
+    }
+
+    /// Move the sync summary selection by `delta` (wrapping)
+    pub fn select_sync_row(&mut self, delta: isize) {
+        if let Mode::Sync {
+            ref rows,
+            ref mut selected,
+        } = self.mode
+        {
+            if !rows.is_empty() {
+                let len = rows.len() as isize;
+                *selected = (*selected as isize + delta).rem_euclid(len) as usize;
+            }
+        }
+    }
+
+    /// Close the sync summary, stopping before the checkouts not synced yet
+    pub fn close_sync(&mut self) {
+        if let Some(job) = self.sync_job.take() {
+            job.cancel.cancel();
+            self.refresh_instances();
+            self.message = Some(tr("Sync stopped").to_string());
+        }
+        self.mode = Mode::Normal;
+    }
+
+    // =========================================================================
+    // Dialog flows: Rename
+    // =========================================================================
+
+    /// Start the rename flow (renames the session)
+    pub fn start_rename(&mut self) {
+        self.clear_messages();
+        if let Some(instance) = self.selected_instance() {
+            self.mode = Mode::Rename {
+                old_name: instance.session_name.clone(),
+                new_name: instance.session_name.clone(),
+            };
+        }
+    }
+
+    /// Confirm and execute session rename
+    pub fn confirm_rename(&mut self) {
+        if let Mode::Rename {
+            ref old_name,
+            ref new_name,
+        } = self.mode
+        {
+            let old = old_name.clone();
+            let new = new_name.clone();
+
+            if old == new {
+                self.mode = Mode::Normal;
+                return;
+            }
+
+            match Tmux::rename_session(&old, &new) {
+                Ok(_) => {
+                    self.refresh_instances();
+                    self.message = Some(trf!("Renamed '{}' to '{}'", old, new));
+                }
+                Err(e) => {
+                    self.error = Some(trf!("Failed to rename: {}", e));
+                }
+            }
+        }
+        self.mode = Mode::Normal;
+    }
+
+    // =========================================================================
+    // Dialog flows: Interactive rebase
+    // =========================================================================
+
+    /// Open `git rebase -i` onto the chosen base in a split beside the agent
+    ///
+    /// The split uses `$VISUAL`/`$EDITOR` unless git has an editor of its
+    /// own, and stays open on failure so the error can be read. The client
+    /// is switched to it while the TUI keeps running, and git context is
+    /// refreshed once the split closes.
+    pub fn confirm_rebase(&mut self) {
+        let Mode::Rebase {
+            ref pane_id,
+            ref path,
+            ref base,
+            ..
+        } = self.mode
+        else {
+            return;
+        };
+        let base = base.trim();
+        if base.is_empty() {
+            return;
+        }
+        let (pane_id, path, base) = (pane_id.clone(), path.clone(), base.to_string());
+        self.mode = Mode::Normal;
+
+        let command = format!(
+            "VISUAL={} git rebase -i {} || {{ printf '%s' {}; read -r _; }}",
+            config::shell_quote(&editor_command()),
+            config::shell_quote(&base),
+            config::shell_quote(tr("Press Enter to close")),
+        );
+        match Tmux::split_running(&pane_id, &path, Some(&command)) {
+            Ok(new_pane) => {
+                if Tmux::inside_tmux() {
+                    let _ = Tmux::switch_to_pane(&new_pane);
+                }
+                tracing::info!("interactive rebase onto {} in {}", base, path.display());
+                self.rebase_panes.push((new_pane, path));
+                self.message = Some(trf!("Rebasing onto {} in a split", base));
+                // Already switched to; the rebase finishes without the TUI
+                self.should_quit |= self.once;
+            }
+            Err(e) => self.error = Some(trf!("Failed to start rebase: {}", e)),
+        }
+    }
+
+    /// Refresh git context once a split running an interactive rebase closes
+    /// (call this in the event loop)
+    pub fn poll_rebases(&mut self) {
+        if self.rebase_panes.is_empty()
+            || self
+                .last_rebase_check
+                .is_some_and(|last| last.elapsed() < REBASE_CHECK_INTERVAL)
+        {
+            return;
+        }
+        self.last_rebase_check = Some(Instant::now());
+
+        let (running, finished): (Vec<_>, Vec<_>) = std::mem::take(&mut self.rebase_panes)
+            .into_iter()
+            .partition(|(pane_id, _)| Tmux::pane_exists(pane_id));
+        self.rebase_panes = running;
+        let Some((_, path)) = finished.last() else {
+            return;
+        };
+        self.refresh_instances();
+        self.needs_redraw = true;
+        // A rebase stops at `edit` commits and conflicts
+        let stopped = GitContext::detect(path).is_some_and(|git| git.in_progress.is_some());
+        self.message = Some(if stopped {
+            trf!("Rebase stopped in {}", short_path(path))
+        } else {
+            trf!("Rebase finished in {}", short_path(path))
+        });
+    }
+
+    // =========================================================================
+    // Dialog flows: Tags
+    // =========================================================================
+
+    /// Start editing the tags of the selected instance's working directory
+    pub fn start_edit_tags(&mut self) {
+        self.clear_messages();
+        if let Some(instance) = self.selected_instance() {
+            self.mode = Mode::EditTags {
+                path: instance.working_directory.clone(),
+                input: self.instance_tags(instance).join(" "),
+            };
+        }
+    }
+
+    /// Save the tags being edited
+    pub fn confirm_tags(&mut self) {
+        if let Mode::EditTags {
+            ref path,
+            ref input,
+        } = self.mode
+        {
+            let selected_pane = self.selected_instance().map(|i| i.pane_id.clone());
+            let tags = tags::parse_tags(input);
+            if tags.is_empty() {
+                self.tags.remove(path);
+            } else {
+                self.tags.insert(path.clone(), tags);
+            }
+            if let Err(e) = tags::save(&self.tags) {
+                self.error = Some(trf!("Failed to save tags: {}", e));
+            }
+            // Groups and filter matches may have changed
+            if let Some(pane_id) = selected_pane {
+                if !self.select_target(&pane_id) {
+                    self.selected = 0;
+                }
+            }
+        }
+        self.mode = Mode::Normal;
+    }
+
+    // =========================================================================
+    // Dialog flows: Commit
+    // =========================================================================
+
+    /// Confirm and execute the commit
+    pub fn confirm_commit(&mut self) {
+        if let Mode::Commit { ref message } = self.mode {
+            if message.trim().is_empty() {
+                self.error = Some(tr("Commit message cannot be empty").to_string());
+                self.mode = Mode::Normal;
+                return;
+            }
+
+            if let Some(instance) = self.selected_instance() {
+                let path = instance.working_directory.clone();
+                let session = instance.session_name.clone();
+                let msg = message.clone();
+                match git::try_lock_repo(&path, tr("committing"))
+                    .and_then(|_lock| GitContext::commit(&path, &msg))
+                {
+                    Ok(_) => {
+                        self.refresh_instances();
+                        self.message = Some(tr("Committed changes").to_string());
+                        self.run_hooks(HookEvent::Commit, &session, &path);
+                        self.count(Counter::Commits);
+                    }
+                    Err(e) => self.error = Some(trf!("Commit failed: {}", e)),
+                }
+            }
+        }
+        self.mode = Mode::Normal;
+    }
+
+    // =========================================================================
+    // Dialog flows: New Session
+    // =========================================================================
+
+    /// Start the new session flow
+    pub fn start_new_session(&mut self) {
+        self.clear_messages();
+        // Default to the `--cwd` directory, or the current one
+        let default_path = self
+            .scope
+            .clone()
+            .or_else(|| std::env::current_dir().ok())
+            .map(|p| p.to_string_lossy().to_string())
+            .unwrap_or_else(|| "~".to_string());
+
+        // Get initial path suggestions
+        let completion = crate::completion::complete_path(&default_path);
+
+        self.mode = Mode::NewSession {
+            name: String::new(),
+            path: default_path,
+            field: NewSessionField::Name,
+            path_suggestions: completion.suggestions,
+            path_selected: None,
+        };
+    }
+
+    /// Create the new session
+    pub fn confirm_new_session(&mut self, start_claude: bool) {
+        if let Mode::NewSession {
+            ref name, ref path, ..
+        } = self.mode
+        {
+            let session_path = expand_path(path);
+            // Unnamed: named from the path
+            let session_name = if name.is_empty() {
+                let taken = Tmux::list_sessions().unwrap_or_default();
+                workspace::session_name(&self.config.session_name_template, &session_path, &taken)
+            } else {
+                name.clone()
+            };
+            let claude_command = start_claude.then(|| self.config.claude_command(&[&session_path]));
+
+            match Tmux::new_session(&session_name, &session_path, claude_command.as_deref()) {
+                Ok(_) => {
+                    self.refresh_instances();
+                    self.message = Some(trf!("Created session '{}'", session_name));
+                    self.count(Counter::SessionsCreated);
+                    self.switch_after_launch(session_name);
+                }
+                Err(e) => {
+                    self.error = Some(trf!("Failed to create session: {}", e));
+                }
+            }
+        }
+        self.mode = Mode::Normal;
+    }
+
+    // =========================================================================
+    // Dialog flows: New Worktree
+    // =========================================================================
+
+    /// Start the new worktree flow
+    pub fn start_new_worktree(&mut self) {
+        self.clear_messages();
+        let Some(instance) = self.selected_instance() else {
+            return;
+        };
+
+        // Get the repo path (use main repo if this is a worktree)
         let source_repo = if let Some(ref git) = instance.git_context {
             if git.is_worktree {
                 git.main_repo_path
@@ -844,20 +3046,26 @@ impl App {
             return; // Not a git repo
         };
 
-        // Get list of branches
-        let all_branches = match GitContext::list_branches(&source_repo) {
+        // Get list of branches, local ones first
+        let mut all_branches = match GitContext::list_branches(&source_repo) {
             Ok(branches) => branches,
             Err(e) => {
-                self.error = Some(format!("Failed to list branches: {}", e));
+                self.error = Some(trf!("Failed to list branches: {}", e));
                 return;
             }
         };
+        let remote_branches = GitContext::list_remote_branches(&source_repo).unwrap_or_default();
+        all_branches.extend(remote_branches.iter().map(|(full, _)| full.clone()));
+        let default_remote = GitContext::default_remote_name(&source_repo);
 
         self.mode = Mode::NewWorktree {
             source_repo,
             all_branches,
+            remote_branches,
             branch_input: String::new(),
             selected_branch: None,
+            upstream: String::new(),
+            default_remote,
             worktree_path: String::new(),
             session_name: String::new(),
             field: NewWorktreeField::Branch,
@@ -894,8 +3102,10 @@ impl App {
         if let Mode::NewWorktree {
             ref source_repo,
             ref all_branches,
+            ref remote_branches,
             ref branch_input,
             ref mut selected_branch,
+            ref mut upstream,
             ref mut worktree_path,
             ref mut session_name,
             ..
@@ -929,61 +3139,83 @@ impl App {
                 branch_input.as_str()
             };
 
+            // Remote branches are tracked by default
+            let (branch_for_path, _, tracked) =
+                resolve_worktree_branch(branch_for_path, all_branches, remote_branches);
+            let branch_for_path = branch_for_path.as_str();
+            *upstream = tracked.unwrap_or_default();
+
             if !branch_for_path.is_empty() {
-                *worktree_path = default_worktree_path(source_repo, branch_for_path)
-                    .to_string_lossy()
-                    .to_string();
-                // Session name: repo-name + branch suffix
+                *worktree_path =
+                    default_worktree_path(source_repo, branch_for_path, &self.config.worktree_path)
+                        .to_string_lossy()
+                        .to_string();
                 let repo_name = source_repo
                     .file_name()
                     .and_then(|s| s.to_str())
                     .unwrap_or("repo");
-                let branch_suffix = sanitize_for_session_name(branch_for_path);
-                *session_name = format!("{}-{}", repo_name, branch_suffix);
+                *session_name = templated_session_name(
+                    &self.config.session_name_template,
+                    repo_name,
+                    Some(branch_for_path),
+                    &Tmux::list_sessions().unwrap_or_default(),
+                );
             }
         }
     }
 
     /// Create the new worktree and session
     pub fn confirm_new_worktree(&mut self) {
-        let (source_repo, all_branches, branch_input, selected_branch, worktree_path, session_name) =
-            if let Mode::NewWorktree {
-                ref source_repo,
-                ref all_branches,
-                ref branch_input,
+        let (
+            source_repo,
+            all_branches,
+            remote_branches,
+            branch_input,
+            selected_branch,
+            upstream,
+            worktree_path,
+            session_name,
+        ) = if let Mode::NewWorktree {
+            ref source_repo,
+            ref all_branches,
+            ref remote_branches,
+            ref branch_input,
+            selected_branch,
+            ref upstream,
+            ref worktree_path,
+            ref session_name,
+            ..
+        } = self.mode
+        {
+            (
+                source_repo.clone(),
+                all_branches.clone(),
+                remote_branches.clone(),
+                branch_input.clone(),
                 selected_branch,
-                ref worktree_path,
-                ref session_name,
-                ..
-            } = self.mode
-            {
-                (
-                    source_repo.clone(),
-                    all_branches.clone(),
-                    branch_input.clone(),
-                    selected_branch,
-                    worktree_path.clone(),
-                    session_name.clone(),
-                )
-            } else {
-                return;
-            };
+                upstream.clone(),
+                worktree_path.clone(),
+                session_name.clone(),
+            )
+        } else {
+            return;
+        };
 
         // Validate inputs
         if branch_input.is_empty() && selected_branch.is_none() {
-            self.error = Some("Branch name cannot be empty".to_string());
+            self.error = Some(tr("Branch name cannot be empty").to_string());
             self.mode = Mode::Normal;
             return;
         }
 
         if session_name.is_empty() {
-            self.error = Some("Session name cannot be empty".to_string());
+            self.error = Some(tr("Session name cannot be empty").to_string());
             self.mode = Mode::Normal;
             return;
         }
 
         if worktree_path.is_empty() {
-            self.error = Some("Worktree path cannot be empty".to_string());
+            self.error = Some(tr("Worktree path cannot be empty").to_string());
             self.mode = Mode::Normal;
             return;
         }
@@ -1000,115 +3232,2367 @@ impl App {
                 .collect()
         };
 
-        let (branch_name, is_new_branch) = if let Some(idx) = selected_branch {
-            // User selected an existing branch
-            (
-                filtered
-                    .get(idx)
-                    .copied()
-                    .unwrap_or(&branch_input)
-                    .to_string(),
-                false,
-            )
-        } else if all_branches.iter().any(|b| b == &branch_input) {
-            // Exact match with existing branch
-            (branch_input.clone(), false)
-        } else {
-            // New branch
-            (branch_input.clone(), true)
-        };
+        // A selected branch, or an exact match, exists locally or on a
+        // remote; anything else is a new branch
+        let choice = selected_branch
+            .and_then(|idx| filtered.get(idx).copied())
+            .unwrap_or(&branch_input);
+        let (branch_name, is_new_branch, _) =
+            resolve_worktree_branch(choice, &all_branches, &remote_branches);
+        let upstream = Some(upstream.trim()).filter(|u| !u.is_empty());
 
         let worktree_path_buf = expand_path(&worktree_path);
 
         // Create the worktree
-        match GitContext::create_worktree(
-            &source_repo,
-            &worktree_path_buf,
-            &branch_name,
-            is_new_branch,
-        ) {
+        let created =
+            git::try_lock_repo(&source_repo, tr("creating a worktree")).and_then(|_lock| {
+                GitContext::create_worktree(
+                    &source_repo,
+                    &worktree_path_buf,
+                    &branch_name,
+                    is_new_branch,
+                    upstream,
+                )
+            });
+        match created {
             Ok(_) => {
-                // Create the session
-                match Tmux::new_session(&session_name, &worktree_path_buf, true) {
+                // Create the session, using the profile of the worktree or its repo
+                let claude_command = self
+                    .config
+                    .claude_command(&[&worktree_path_buf, &source_repo]);
+                match Tmux::new_session(&session_name, &worktree_path_buf, Some(&claude_command)) {
                     Ok(_) => {
                         self.refresh_instances();
-                        self.message = Some(format!(
+                        self.message = Some(trf!(
                             "Created worktree '{}' and session '{}'",
-                            branch_name, session_name
+                            branch_name,
+                            session_name
                         ));
+                        self.count(Counter::SessionsCreated);
+                        self.switch_after_launch(session_name);
                     }
                     Err(e) => {
-                        self.error = Some(format!(
-                            "Worktree created but session creation failed: {}",
-                            e
-                        ));
+                        self.error =
+                            Some(trf!("Worktree created but session creation failed: {}", e));
                     }
                 }
             }
             Err(e) => {
-                self.error = Some(format!("Failed to create worktree: {}", e));
+                self.error = Some(trf!("Failed to create worktree: {}", e));
+            }
+        }
+
+        self.mode = Mode::Normal;
+    }
+
+    // =========================================================================
+    // Dialog flows: New session from issue
+    // =========================================================================
+
+    /// Start the flow creating a branch, worktree and session for an issue,
+    /// listing the open GitHub issues when gh can
+    pub fn start_branch_from_issue(&mut self) {
+        self.clear_messages();
+        let Some(instance) = self.selected_instance() else {
+            return;
+        };
+        let Some(git) = instance.git_context.as_ref() else {
+            return;
+        };
+        let source_repo = git
+            .main_repo_path
+            .clone()
+            .filter(|_| git.is_worktree)
+            .unwrap_or_else(|| instance.working_directory.clone());
+
+        let forge = git::forge();
+        let issues = if forge.supports(&source_repo) {
+            forge.list_issues(&source_repo).unwrap_or_else(|e| {
+                self.error = Some(trf!("Failed to list issues: {}", e));
+                Vec::new()
+            })
+        } else {
+            Vec::new()
+        };
+
+        self.mode = Mode::BranchFromIssue {
+            source_repo,
+            issues,
+            input: String::new(),
+            selected: 0,
+            send_prompt: true,
+        };
+    }
+
+    /// Issues matching the input: by number, or by words of the title
+    pub fn filtered_issues(&self) -> Vec<&Issue> {
+        let Mode::BranchFromIssue {
+            ref issues,
+            ref input,
+            ..
+        } = self.mode
+        else {
+            return Vec::new();
+        };
+        let query = input.trim().trim_start_matches('#').to_lowercase();
+        issues
+            .iter()
+            .filter(|issue| {
+                issue.number.to_string().starts_with(&query)
+                    || issue.title.to_lowercase().contains(&query)
+            })
+            .collect()
+    }
+
+    /// Move the issue selection by `delta` (wrapping)
+    pub fn select_issue(&mut self, delta: isize) {
+        let len = self.filtered_issues().len() as isize;
+        if let Mode::BranchFromIssue {
+            ref mut selected, ..
+        } = self.mode
+        {
+            if len > 0 {
+                *selected = (*selected as isize + delta).rem_euclid(len) as usize;
+            }
+        }
+    }
+
+    /// The issue the dialog would create a branch for: the highlighted one,
+    /// or else what was typed, as an issue number when it reads as one
+    ///
+    /// Returns the issue reference, its number, and its title when known.
+    fn chosen_issue(&self) -> Option<(String, Option<u64>, String)> {
+        let Mode::BranchFromIssue {
+            ref input,
+            selected,
+            ..
+        } = self.mode
+        else {
+            return None;
+        };
+        if let Some(issue) = self.filtered_issues().get(selected) {
+            return Some((
+                issue.number.to_string(),
+                Some(issue.number),
+                issue.title.clone(),
+            ));
+        }
+        let reference = input.trim();
+        if reference.is_empty() {
+            return None;
+        }
+        // `42`, `#42`, or an issue URL
+        let number = reference
+            .trim_start_matches('#')
+            .rsplit("/issues/")
+            .next()
+            .and_then(|n| n.trim_end_matches('/').parse::<u64>().ok());
+        Some(match number {
+            Some(n) => (n.to_string(), Some(n), String::new()),
+            None => (reference.to_string(), None, String::new()),
+        })
+    }
+
+    /// Branch the dialog would create, as far as it is known before the
+    /// issue is fetched
+    pub fn issue_branch_preview(&self) -> Option<String> {
+        let (reference, _, title) = self.chosen_issue()?;
+        Some(issue_branch_name(
+            &self.config.issue_branch_template,
+            &reference,
+            &title,
+        ))
+    }
+
+    /// Create the branch, worktree and session for the chosen issue, starting
+    /// Claude with the issue as its prompt when asked to
+    ///
+    /// An existing branch of the same name is checked out instead of created.
+    pub fn confirm_branch_from_issue(&mut self) {
+        let Some((reference, number, mut title)) = self.chosen_issue() else {
+            return;
+        };
+        let Mode::BranchFromIssue {
+            ref source_repo,
+            send_prompt,
+            ..
+        } = self.mode
+        else {
+            return;
+        };
+        let source_repo = source_repo.clone();
+        self.mode = Mode::Normal;
+
+        // The list has titles only; the description comes with the issue
+        let mut body = String::new();
+        let forge = git::forge();
+        if let Some(number) = number.filter(|_| forge.supports(&source_repo)) {
+            match forge.issue(&source_repo, number) {
+                Ok(issue) => (title, body) = (issue.title, issue.body),
+                Err(e) => tracing::warn!("issue #{}: {:#}", number, e),
+            }
+        }
+
+        let branch = issue_branch_name(&self.config.issue_branch_template, &reference, &title);
+        if branch.is_empty() {
+            self.error = Some(tr("Branch name cannot be empty").to_string());
+            return;
+        }
+        let is_new_branch = !GitContext::list_branches(&source_repo)
+            .unwrap_or_default()
+            .contains(&branch);
+        let worktree_path =
+            default_worktree_path(&source_repo, &branch, &self.config.worktree_path);
+        let repo_name = source_repo
+            .file_name()
+            .and_then(|s| s.to_str())
+            .unwrap_or("repo");
+        let session_name = templated_session_name(
+            &self.config.session_name_template,
+            repo_name,
+            Some(&branch),
+            &Tmux::list_sessions().unwrap_or_default(),
+        );
+
+        if let Err(e) =
+            git::try_lock_repo(&source_repo, tr("creating a worktree")).and_then(|_lock| {
+                GitContext::create_worktree(
+                    &source_repo,
+                    &worktree_path,
+                    &branch,
+                    is_new_branch,
+                    None,
+                )
+            })
+        {
+            self.error = Some(trf!("Failed to create worktree: {}", e));
+            return;
+        }
+
+        let mut claude_command = self.config.claude_command(&[&worktree_path, &source_repo]);
+        if send_prompt && !title.is_empty() {
+            let heading = match number {
+                Some(n) => format!("Work on issue #{}: {}", n, title),
+                None => format!("Work on {}: {}", reference, title),
+            };
+            let prompt = if body.is_empty() {
+                heading
+            } else {
+                format!("{}\n\n{}", heading, body)
+            };
+            claude_command = format!("{} {}", claude_command, config::shell_quote(&prompt));
+        }
+        match Tmux::new_session(&session_name, &worktree_path, Some(&claude_command)) {
+            Ok(_) => {
+                self.refresh_instances();
+                self.message = Some(trf!(
+                    "Created worktree '{}' and session '{}'",
+                    branch,
+                    session_name
+                ));
+                self.count(Counter::SessionsCreated);
+                self.switch_after_launch(session_name);
+            }
+            Err(e) => {
+                self.error = Some(trf!("Worktree created but session creation failed: {}", e));
+            }
+        }
+    }
+
+    // =========================================================================
+    // Dialog flows: Clone Session Layout
+    // =========================================================================
+
+    /// Start the clone layout flow for the selected instance's session
+    pub fn start_clone_layout(&mut self) {
+        self.clear_messages();
+        let Some(instance) = self.selected_instance() else {
+            return;
+        };
+
+        let main_repo = instance.git_context.as_ref().map(|git| {
+            git.main_repo_path
+                .clone()
+                .filter(|_| git.is_worktree)
+                .unwrap_or_else(|| instance.working_directory.clone())
+        });
+        let source_root = instance
+            .git_context
+            .as_ref()
+            .and_then(|_| GitContext::checkout_root(&instance.working_directory));
+
+        self.mode = Mode::CloneLayout {
+            name: format!("{}-copy", instance.session_name),
+            source_session: instance.session_name.clone(),
+            source_root,
+            main_repo,
+            branch: String::new(),
+            field: CloneLayoutField::Name,
+        };
+    }
+
+    /// Create the new session, and its worktree when a branch was entered
+    ///
+    /// Pane directories inside the source checkout are moved to the same
+    /// place in the new worktree; panes that ran Claude start it again.
+    pub fn confirm_clone_layout(&mut self) {
+        let Mode::CloneLayout {
+            ref source_session,
+            ref source_root,
+            ref main_repo,
+            ref name,
+            ref branch,
+            ..
+        } = self.mode
+        else {
+            return;
+        };
+        let (source_session, source_root, main_repo) = (
+            source_session.clone(),
+            source_root.clone(),
+            main_repo.clone(),
+        );
+        let (name, branch) = (name.trim().to_string(), branch.trim().to_string());
+        self.mode = Mode::Normal;
+
+        if name.is_empty() {
+            self.error = Some(tr("Session name cannot be empty").to_string());
+            return;
+        }
+
+        let mut layout = match Tmux::capture_layout(&source_session) {
+            Ok(layout) => layout,
+            Err(e) => {
+                self.error = Some(trf!("Failed to read layout: {}", e));
+                return;
+            }
+        };
+
+        let mut repo_dirs: Vec<PathBuf> = main_repo.iter().cloned().collect();
+        if !branch.is_empty() {
+            let (Some(main_repo), Some(source_root)) = (&main_repo, &source_root) else {
+                self.error = Some(tr("A worktree needs a git repository").to_string());
+                return;
+            };
+            let worktree_path =
+                default_worktree_path(main_repo, &branch, &self.config.worktree_path);
+            let is_new_branch = !GitContext::list_branches(main_repo)
+                .unwrap_or_default()
+                .contains(&branch);
+            // Like `git worktree add`, a new branch named after a remote
+            // branch tracks it
+            let upstream = is_new_branch
+                .then(|| GitContext::guess_remote_branch(main_repo, &branch))
+                .flatten();
+            let created =
+                git::try_lock_repo(main_repo, tr("creating a worktree")).and_then(|_lock| {
+                    GitContext::create_worktree(
+                        main_repo,
+                        &worktree_path,
+                        &branch,
+                        is_new_branch,
+                        upstream.as_deref(),
+                    )
+                });
+            if let Err(e) = created {
+                self.error = Some(trf!("Failed to create worktree: {}", e));
+                return;
+            }
+            // Untracked directories do not exist in the new worktree; use
+            // their nearest existing parent
+            for pane in layout.windows.iter_mut().flat_map(|w| w.panes.iter_mut()) {
+                let path = rebase_path(&pane.path, source_root, &worktree_path);
+                pane.path = path
+                    .ancestors()
+                    .find(|dir| dir.is_dir())
+                    .map(PathBuf::from)
+                    .unwrap_or(path);
+            }
+            repo_dirs.insert(0, worktree_path);
+        }
+
+        for pane in layout.windows.iter_mut().flat_map(|w| w.panes.iter_mut()) {
+            if pane.has_claude {
+                let mut dirs: Vec<&std::path::Path> = vec![&pane.path];
+                dirs.extend(repo_dirs.iter().map(PathBuf::as_path));
+                pane.command = Some(self.config.claude_command(&dirs));
+            }
+        }
+
+        match Tmux::new_session_from_layout(&name, &layout) {
+            Ok(()) => {
+                self.refresh_instances();
+                self.count(Counter::SessionsCreated);
+                self.message = Some(if branch.is_empty() {
+                    trf!("Cloned layout of '{}' into '{}'", source_session, name)
+                } else {
+                    trf!(
+                        "Cloned layout of '{}' into '{}' on worktree '{}'",
+                        source_session,
+                        name,
+                        branch
+                    )
+                });
+                self.switch_after_launch(name);
+            }
+            Err(e) => self.error = Some(trf!("Failed to clone layout: {}", e)),
+        }
+    }
+
+    // =========================================================================
+    // Dialog flows: Rearrange Panes
+    // =========================================================================
+
+    /// Move the arrangement selection by `delta` (wrapping)
+    pub fn select_arrangement(&mut self, delta: isize) {
+        if let Mode::ArrangePanes {
+            ref mut selected, ..
+        } = self.mode
+        {
+            let len = PaneArrangement::ALL.len() as isize;
+            *selected = (*selected as isize + delta).rem_euclid(len) as usize;
+        }
+    }
+
+    /// Apply the highlighted arrangement, keeping the picker open for more
+    pub fn apply_arrangement(&mut self) {
+        let Mode::ArrangePanes {
+            ref pane_id,
+            selected,
+        } = self.mode
+        else {
+            return;
+        };
+        let pane_id = pane_id.clone();
+        let Some(arrangement) = PaneArrangement::ALL.get(selected).copied() else {
+            return;
+        };
+
+        let result = match arrangement {
+            PaneArrangement::SwapNext => Tmux::swap_pane(&pane_id, true),
+            PaneArrangement::SwapPrevious => Tmux::swap_pane(&pane_id, false),
+            PaneArrangement::Rotate => Tmux::rotate_window(&pane_id),
+            PaneArrangement::Layout(layout) => Tmux::select_layout(&pane_id, layout),
+        };
+        self.clear_messages();
+        match result {
+            Ok(()) => {
+                // Pane indexes change with swaps and rotations
+                self.refresh_instances();
+                self.message = Some(trf!("Applied: {}", arrangement.label()));
+            }
+            Err(e) => self.error = Some(trf!("Failed to rearrange panes: {}", e)),
+        }
+    }
+
+    // =========================================================================
+    // Dialog flows: Move / Link Window
+    // =========================================================================
+
+    /// Start picking the session to move (or link) the selected window to
+    pub fn start_move_window(&mut self, link: bool) {
+        self.clear_messages();
+        let Some(instance) = self.selected_instance() else {
+            return;
+        };
+        let window = format!("{}:{}", instance.session_name, instance.window_index);
+        let own_session = instance.session_name.clone();
+
+        let sessions = match Tmux::list_sessions() {
+            Ok(sessions) => sessions.into_iter().filter(|s| *s != own_session).collect(),
+            Err(e) => {
+                self.error = Some(trf!("Failed to list sessions: {}", e));
+                return;
+            }
+        };
+        self.mode = Mode::MoveWindow {
+            window,
+            link,
+            sessions,
+            input: String::new(),
+            selected: 0,
+        };
+    }
+
+    /// Sessions matching the move window filter
+    pub fn filtered_move_sessions(&self) -> Vec<&str> {
+        let Mode::MoveWindow {
+            ref sessions,
+            ref input,
+            ..
+        } = self.mode
+        else {
+            return vec![];
+        };
+        let input_lower = input.to_lowercase();
+        sessions
+            .iter()
+            .filter(|s| s.to_lowercase().contains(&input_lower))
+            .map(String::as_str)
+            .collect()
+    }
+
+    /// Move the move window selection by `delta` (wrapping)
+    pub fn select_move_session(&mut self, delta: isize) {
+        let len = self.filtered_move_sessions().len() as isize;
+        if let Mode::MoveWindow {
+            ref mut selected, ..
+        } = self.mode
+        {
+            if len > 0 {
+                *selected = (*selected as isize + delta).rem_euclid(len) as usize;
+            }
+        }
+    }
+
+    /// Move or link the window to the selected session, or to a new session
+    /// named by the filter when nothing matches
+    pub fn confirm_move_window(&mut self) {
+        let target = match self.filtered_move_sessions().get(self.move_selected()) {
+            Some(session) => session.to_string(),
+            None => match &self.mode {
+                Mode::MoveWindow { input, .. } => input.trim().to_string(),
+                _ => return,
+            },
+        };
+        let Mode::MoveWindow {
+            ref window, link, ..
+        } = self.mode
+        else {
+            return;
+        };
+        let window = window.clone();
+        self.mode = Mode::Normal;
+
+        if target.is_empty() {
+            self.error = Some(tr("Session name cannot be empty").to_string());
+            return;
+        }
+
+        match Tmux::move_window(&window, &target, link) {
+            Ok(()) => {
+                self.refresh_instances();
+                self.message = Some(if link {
+                    trf!("Linked window {} into '{}'", window, target)
+                } else {
+                    trf!("Moved window {} to '{}'", window, target)
+                });
+            }
+            Err(e) => self.error = Some(trf!("Failed to move window: {}", e)),
+        }
+    }
+
+    fn move_selected(&self) -> usize {
+        match self.mode {
+            Mode::MoveWindow { selected, .. } => selected,
+            _ => 0,
+        }
+    }
+
+    // =========================================================================
+    // Dialog flows: Create Pull Request
+    // =========================================================================
+
+    /// Start the create pull request flow
+    pub fn start_create_pull_request(&mut self) {
+        self.clear_messages();
+        let Some(instance) = self.selected_instance() else {
+            return;
+        };
+
+        let path = &instance.working_directory;
+        let base_branch = git::get_default_branch(path).unwrap_or_else(|| "main".to_string());
+        let summary = archive::transcript_summary(path);
+        let (title, body) = git::suggest_pull_request(path, &base_branch, summary.as_deref());
+
+        self.mode = Mode::CreatePullRequest {
+            title,
+            body,
+            base_branch,
+            field: CreatePullRequestField::Title,
+        };
+    }
+
+    /// Confirm and execute PR creation
+    pub fn confirm_create_pull_request(&mut self) {
+        let (title, body, base_branch) = if let Mode::CreatePullRequest {
+            ref title,
+            ref body,
+            ref base_branch,
+            ..
+        } = self.mode
+        {
+            (title.clone(), body.clone(), base_branch.clone())
+        } else {
+            self.mode = Mode::Normal;
+            return;
+        };
+
+        if title.trim().is_empty() {
+            self.error = Some(tr("PR title cannot be empty").to_string());
+            self.mode = Mode::Normal;
+            return;
+        }
+
+        let Some(instance) = self.selected_instance() else {
+            self.mode = Mode::Normal;
+            return;
+        };
+        let draft = PullRequestDraft {
+            path: instance.working_directory.clone(),
+            session: instance.session_name.clone(),
+            title,
+            body,
+            base_branch,
+        };
+        // Push first when GitHub doesn't have the branch's commits yet
+        let push = match &instance.git_context {
+            Some(git) if !git.has_upstream => Some(GitOperation::PushSetUpstream),
+            Some(git) if git.ahead > 0 => Some(GitOperation::Push),
+            _ => None,
+        };
+
+        self.mode = Mode::Normal;
+        match push {
+            Some(operation) => {
+                if self.git_job.is_some() {
+                    self.error = Some(tr("Wait for the running git operation first").to_string());
+                    return;
+                }
+                let (session, path) = (draft.session.clone(), draft.path.clone());
+                self.pull_request_after_push = Some(draft);
+                self.start_git_job(session, operation, path);
+            }
+            None => self.create_pull_request(draft),
+        }
+    }
+
+    fn create_pull_request(&mut self, draft: PullRequestDraft) {
+        match git::forge().create_pull_request(
+            &draft.path,
+            &draft.title,
+            &draft.body,
+            &draft.base_branch,
+        ) {
+            Ok(result) => {
+                self.message = Some(trf!("Created PR: {}", result.url));
+                self.run_hooks(HookEvent::CreatePullRequest, &draft.session, &draft.path);
+            }
+            Err(e) => {
+                self.error = Some(trf!("Failed to create PR: {}", e));
             }
         }
+    }
+
+    // =========================================================================
+    // Hooks
+    // =========================================================================
+
+    /// Start the `[[hooks]]` configured for `event` in `path`
+    fn run_hooks(&self, event: HookEvent, session: &str, path: &std::path::Path) {
+        hooks::run_after(&self.config.hooks, event, session, path);
+    }
+
+    // =========================================================================
+    // Quick interactions
+    // =========================================================================
+
+    /// Nudge the selected agent without switching to it
+    ///
+    /// A waiting agent gets Enter (accepting the highlighted choice), an idle
+    /// one gets a "continue" prompt, and a crashed one is restarted.
+    pub fn continue_selected(&mut self) {
+        self.clear_messages();
+        let Some(instance) = self.selected_instance() else {
+            return;
+        };
+        let pane_id = instance.pane_id.clone();
+        let label = instance.display_name();
+
+        let result = match instance.status {
+            ClaudeCodeStatus::WaitingInput => Tmux::send_key(&pane_id, "Enter"),
+            ClaudeCodeStatus::Idle => Tmux::send_prompt(&pane_id, "continue"),
+            ClaudeCodeStatus::Crashed => {
+                self.restart_selected();
+                return;
+            }
+            ClaudeCodeStatus::Working => {
+                self.error = Some(trf!("{} is already working", label));
+                return;
+            }
+            ClaudeCodeStatus::Unknown => {
+                self.error = Some(trf!("{} is not waiting for input", label));
+                return;
+            }
+        };
+
+        self.poller.wake(&pane_id, Instant::now());
+        match result {
+            Ok(_) => self.message = Some(trf!("Continued {}", label)),
+            Err(e) => self.error = Some(trf!("Failed to continue: {}", e)),
+        }
+        self.mode = Mode::Normal;
+    }
+
+    /// Interrupt the selected agent by sending Escape
+    pub fn interrupt_selected(&mut self) {
+        self.clear_messages();
+        let Some(instance) = self.selected_instance() else {
+            return;
+        };
+        let pane_id = instance.pane_id.clone();
+        let label = instance.display_name();
+
+        self.poller.wake(&pane_id, Instant::now());
+        match Tmux::send_key(&pane_id, "Escape") {
+            Ok(_) => self.message = Some(trf!("Interrupted {}", label)),
+            Err(e) => self.error = Some(trf!("Failed to interrupt: {}", e)),
+        }
+        self.mode = Mode::Normal;
+    }
+
+    /// Restart Claude with `--continue` in the selected crashed pane
+    pub fn restart_selected(&mut self) {
+        self.clear_messages();
+        let Some(instance) = self.selected_instance() else {
+            return;
+        };
+        let pane_id = instance.pane_id.clone();
+        let label = instance.display_name();
+        let command = self.config.claude_command(&[&instance.working_directory]);
+
+        match Tmux::restart_claude(&pane_id, &command) {
+            Ok(_) => {
+                self.refresh_instances();
+                self.message = Some(trf!("Restarted claude in {}", label));
+            }
+            Err(e) => self.error = Some(trf!("Failed to restart: {}", e)),
+        }
+        self.mode = Mode::Normal;
+    }
+
+    /// Forget the selected crashed pane so it leaves the list
+    pub fn dismiss_selected_crash(&mut self) {
+        self.clear_messages();
+        let Some(instance) = self.selected_instance() else {
+            return;
+        };
+        let pane_id = instance.pane_id.clone();
+
+        match Tmux::set_agent_marker(&pane_id, false) {
+            Ok(_) => {
+                self.refresh_instances();
+            }
+            Err(e) => self.error = Some(trf!("Failed to dismiss: {}", e)),
+        }
+        self.mode = Mode::Normal;
+    }
+
+    /// Main repository of the selected pane, if its worktree directory was deleted
+    fn selected_missing_dir_repo(&self) -> Option<PathBuf> {
+        let instance = self.selected_instance()?;
+        if !instance.is_directory_missing() {
+            return None;
+        }
+        instance
+            .git_context
+            .as_ref()
+            .and_then(|git| git.main_repo_path.clone())
+            .filter(|repo| repo.is_dir())
+            .or_else(|| GitContext::find_main_repo_of_missing(&instance.working_directory))
+    }
+
+    /// Restart the selected pane in its main repository, starting a new Claude
+    pub fn respawn_selected_in_main_repo(&mut self) {
+        self.clear_messages();
+        let Some(repo) = self.selected_missing_dir_repo() else {
+            self.error = Some(tr("Main repository not found").to_string());
+            self.mode = Mode::Normal;
+            return;
+        };
+        let Some(instance) = self.selected_instance() else {
+            return;
+        };
+        let pane_id = instance.pane_id.clone();
+        let label = instance.display_name();
+        let command = self.config.claude_command(&[&repo]);
+
+        match Tmux::respawn_pane(&pane_id, &repo, &command) {
+            Ok(_) => {
+                self.refresh_instances();
+                self.message = Some(trf!("Respawned {} in {}", label, repo.display()));
+            }
+            Err(e) => self.error = Some(trf!("Failed to respawn: {}", e)),
+        }
+        self.mode = Mode::Normal;
+    }
+
+    // =========================================================================
+    // Dialog flows: Slash commands
+    // =========================================================================
+
+    /// Open the slash command menu for the selected instance
+    pub fn start_slash_command_menu(&mut self) {
+        self.clear_messages();
+        if self.selected_instance().is_some() {
+            self.mode = Mode::SlashCommandMenu { selected: 0 };
+        }
+    }
+
+    /// Move the slash command selection by `delta` (wrapping)
+    pub fn select_slash_command(&mut self, delta: isize) {
+        let len = self.config.all_slash_commands().len() as isize;
+        if let Mode::SlashCommandMenu { ref mut selected } = self.mode {
+            if len > 0 {
+                *selected = (*selected as isize + delta).rem_euclid(len) as usize;
+            }
+        }
+    }
+
+    /// Send the highlighted slash command to the selected instance
+    pub fn confirm_slash_command(&mut self) {
+        let Mode::SlashCommandMenu { selected } = self.mode else {
+            return;
+        };
+        self.mode = Mode::Normal;
+
+        let Some(command) = self.config.all_slash_commands().into_iter().nth(selected) else {
+            return;
+        };
+        if let Some(instance) = self.selected_instance() {
+            let pane_id = instance.pane_id.clone();
+            let label = instance.display_name();
+            match Tmux::send_prompt(&pane_id, &command.command) {
+                Ok(_) => self.message = Some(trf!("Sent {} to {}", command.command, label)),
+                Err(e) => self.error = Some(trf!("Failed to send {}: {}", command.command, e)),
+            }
+        }
+    }
+
+    // =========================================================================
+    // Dialog flows: Prompt templates
+    // =========================================================================
+
+    /// Open the prompt template picker for the selected instance
+    pub fn start_template_picker(&mut self) {
+        self.clear_messages();
+        if self.selected_instance().is_none() {
+            return;
+        }
+        if self.config.templates.is_empty() {
+            self.error = Some(trf!(
+                "No prompt templates defined in {}",
+                Config::path().display()
+            ));
+            self.mode = Mode::Normal;
+            return;
+        }
+        self.mode = Mode::TemplatePicker { selected: 0 };
+    }
+
+    /// Move the template picker selection by `delta` (wrapping)
+    pub fn select_template(&mut self, delta: isize) {
+        let len = self.config.templates.len() as isize;
+        if let Mode::TemplatePicker { ref mut selected } = self.mode {
+            if len > 0 {
+                *selected = (*selected as isize + delta).rem_euclid(len) as usize;
+            }
+        }
+    }
+
+    /// Placeholder values for the selected instance
+    pub fn template_context(&self) -> Option<TemplateContext> {
+        let instance = self.selected_instance()?;
+        Some(TemplateContext {
+            branch: instance.git_context.as_ref().map(|g| g.branch.clone()),
+            repo: instance
+                .git_context
+                .as_ref()
+                .and_then(|_| GitContext::repo_name(&instance.working_directory)),
+            path: instance.working_directory.display().to_string(),
+            last_commit: git::last_commit_summary(&instance.working_directory),
+            session: instance.session_name.clone(),
+        })
+    }
+
+    /// Expanded text of the highlighted template for the selected instance
+    pub fn expanded_selected_template(&self) -> Option<String> {
+        let Mode::TemplatePicker { selected } = self.mode else {
+            return None;
+        };
+        let template = self.config.templates.get(selected)?;
+        let ctx = self.template_context()?;
+        Some(config::expand_template(&template.prompt, &ctx))
+    }
+
+    /// Send the highlighted template to the selected instance
+    pub fn confirm_template(&mut self) {
+        let Some(text) = self.expanded_selected_template() else {
+            self.mode = Mode::Normal;
+            return;
+        };
+
+        if let Some(instance) = self.selected_instance() {
+            let pane_id = instance.pane_id.clone();
+            let label = instance.display_name();
+            match Tmux::send_prompt(&pane_id, &text) {
+                Ok(_) => {
+                    self.message = Some(trf!("Sent prompt to {}", label));
+                    self.count(Counter::PromptsSent);
+                }
+                Err(e) => self.error = Some(trf!("Failed to send prompt: {}", e)),
+            }
+        }
+
+        self.mode = Mode::Normal;
+    }
+
+    // =========================================================================
+    // Custom actions
+    // =========================================================================
+
+    /// Run the `index`th `[[actions]]` command for the selected instance and
+    /// show its output
+    pub fn run_custom_action(&mut self, index: usize) {
+        self.clear_messages();
+        let Some(action) = self.config.actions.get(index) else {
+            return;
+        };
+        let Some(instance) = self.selected_instance() else {
+            return;
+        };
+        if let Some(job) = &self.action_job {
+            self.error = Some(trf!("'{}' is still running", job.name));
+            return;
+        }
+
+        let ctx = ActionContext {
+            path: instance.working_directory.display().to_string(),
+            branch: instance
+                .git_context
+                .as_ref()
+                .map(|g| g.branch.clone())
+                .unwrap_or_default(),
+            target: instance.tmux_target(),
+            pane_id: instance.pane_id.clone(),
+            session: instance.session_name.clone(),
+        };
+        let command = action.expand(&ctx);
+        let name = action.name.clone();
+        let dir = instance.working_directory.clone();
+        tracing::info!("action '{}' started for {}: {}", name, ctx.target, command);
+
+        let (tx, rx) = mpsc::channel();
+        thread::spawn(move || {
+            let _ = tx.send(run_shell(&command, &dir));
+        });
+        self.action_job = Some(ActionJob {
+            name: name.clone(),
+            target: ctx.target.clone(),
+            receiver: rx,
+        });
+        self.mode = Mode::ActionOutput {
+            name,
+            target: ctx.target,
+            lines: Vec::new(),
+            state: ActionRun::Running,
+            scroll: 0,
+        };
+    }
+
+    /// Run the custom action whose output is shown again
+    pub fn rerun_custom_action(&mut self) {
+        let Mode::ActionOutput { ref name, .. } = self.mode else {
+            return;
+        };
+        if let Some(index) = self.config.actions.iter().position(|a| a.name == *name) {
+            self.run_custom_action(index);
+        }
+    }
+
+    /// Check whether the running custom action finished (call this in the event loop)
+    pub fn poll_action_job(&mut self) {
+        let Some(job) = &self.action_job else {
+            return;
+        };
+        let (lines, state) = match job.receiver.try_recv() {
+            Ok(result) => result,
+            Err(mpsc::TryRecvError::Empty) => return,
+            Err(mpsc::TryRecvError::Disconnected) => (
+                Vec::new(),
+                ActionRun::Failed("worker thread exited unexpectedly".to_string()),
+            ),
+        };
+        let Some(job) = self.action_job.take() else {
+            return;
+        };
+        tracing::info!("action '{}' for {}: {:?}", job.name, job.target, state);
+        self.needs_redraw = true;
+
+        if let Mode::ActionOutput {
+            ref name,
+            ref target,
+            lines: ref mut shown,
+            state: ref mut shown_state,
+            ..
+        } = self.mode
+        {
+            if *name == job.name && *target == job.target {
+                *shown = lines;
+                *shown_state = state;
+                return;
+            }
+        }
+        // The output was closed while the command ran
+        match state {
+            ActionRun::Exited(0) => self.message = Some(trf!("'{}' finished", job.name)),
+            ActionRun::Exited(code) => {
+                self.error = Some(trf!("'{}' exited with status {}", job.name, code))
+            }
+            ActionRun::Failed(e) => self.error = Some(trf!("'{}' failed: {}", job.name, e)),
+            ActionRun::Running => {}
+        }
+    }
+
+    /// Scroll the action output by `delta` lines (positive is towards the top)
+    pub fn scroll_action_output(&mut self, delta: isize) {
+        if let Mode::ActionOutput {
+            ref lines,
+            ref mut scroll,
+            ..
+        } = self.mode
+        {
+            *scroll = scroll
+                .saturating_add_signed(delta)
+                .min(lines.len().saturating_sub(1));
+        }
+    }
+
+    // =========================================================================
+    // Dialog flows: Workspaces
+    // =========================================================================
+
+    /// Open the workspace picker
+    pub fn start_workspace_picker(&mut self) {
+        self.clear_messages();
+        if self.config.workspaces.is_empty() {
+            self.error = Some(trf!(
+                "No workspaces defined in {}",
+                Config::path().display()
+            ));
+            self.mode = Mode::Normal;
+            return;
+        }
+        self.mode = Mode::Workspaces { selected: 0 };
+    }
+
+    /// Move the workspace picker selection by `delta` (wrapping)
+    pub fn select_workspace(&mut self, delta: isize) {
+        let len = self.config.workspaces.len() as isize;
+        if let Mode::Workspaces { ref mut selected } = self.mode {
+            if len > 0 {
+                *selected = (*selected as isize + delta).rem_euclid(len) as usize;
+            }
+        }
+    }
+
+    /// Where each directory of the highlighted workspace stands
+    pub fn selected_workspace_members(&self) -> Vec<Member> {
+        let Mode::Workspaces { selected } = self.mode else {
+            return Vec::new();
+        };
+        self.config
+            .workspaces
+            .get(selected)
+            .map(|w| workspace::members(w, &self.instances))
+            .unwrap_or_default()
+    }
+
+    /// Start an agent in each directory of the highlighted workspace that
+    /// has none
+    pub fn confirm_workspace(&mut self) {
+        let Mode::Workspaces { selected } = self.mode else {
+            return;
+        };
+        self.mode = Mode::Normal;
+        let Some(workspace) = self.config.workspaces.get(selected) else {
+            return;
+        };
+
+        let outcomes = workspace::start(workspace, &self.config, &self.instances);
+        let started = outcomes
+            .iter()
+            .filter(|(_, o)| matches!(o, Outcome::Started(_)))
+            .count();
+        let running = outcomes
+            .iter()
+            .filter(|(_, o)| matches!(o, Outcome::Running(_)))
+            .count();
+        let failed: Vec<String> = outcomes
+            .iter()
+            .filter(|(_, o)| matches!(o, Outcome::Missing | Outcome::Failed(_)))
+            .map(|(dir, _)| short_path(dir))
+            .collect();
+        if failed.is_empty() {
+            self.message = Some(trf!(
+                "Workspace {}: {} started, {} already running",
+                workspace.name,
+                started,
+                running
+            ));
+        } else {
+            self.error = Some(trf!(
+                "Workspace {}: could not start {}",
+                workspace.name,
+                failed.join(", ")
+            ));
+        }
+        if started > 0 {
+            self.refresh_instances();
+        }
+        if failed.is_empty() {
+            let first = outcomes.into_iter().find_map(|(_, o)| match o {
+                Outcome::Started(name) | Outcome::Running(name) => Some(name),
+                _ => None,
+            });
+            if let Some(first) = first {
+                self.switch_after_launch(first);
+            }
+        }
+    }
+
+    // =========================================================================
+    // Dialog flows: Send file or clipboard
+    // =========================================================================
+
+    /// Start the send file flow, with the path prefilled to the instance's directory
+    pub fn start_send_file(&mut self) {
+        self.clear_messages();
+        let Some(instance) = self.selected_instance() else {
+            return;
+        };
+
+        let path = format!(
+            "{}/",
+            instance
+                .working_directory
+                .display()
+                .to_string()
+                .trim_end_matches('/')
+        );
+        let completion = crate::completion::complete_path(&path);
+
+        self.mode = Mode::SendFile {
+            path,
+            template: None,
+            path_suggestions: completion.suggestions,
+            path_selected: None,
+        };
+    }
+
+    /// Cycle the wrapping template: none -> first -> ... -> last -> none
+    pub fn cycle_send_file_template(&mut self) {
+        let count = self.config.templates.len();
+        if let Mode::SendFile {
+            ref mut template, ..
+        } = self.mode
+        {
+            *template = match *template {
+                None if count > 0 => Some(0),
+                Some(i) if i + 1 < count => Some(i + 1),
+                _ => None,
+            };
+        }
+    }
+
+    /// Read the file (or clipboard when the path is empty) and send it
+    pub fn confirm_send_file(&mut self) {
+        let (path, template) = if let Mode::SendFile {
+            ref path, template, ..
+        } = self.mode
+        {
+            (path.trim().to_string(), template)
+        } else {
+            return;
+        };
+        self.mode = Mode::Normal;
+
+        let Some(instance) = self.selected_instance() else {
+            return;
+        };
+        let pane_id = instance.pane_id.clone();
+        let label = instance.display_name();
+
+        let (content, source) = if path.is_empty() {
+            (clipboard::read(), tr("clipboard").to_string())
+        } else {
+            // Relative paths are relative to the instance, not to claude-tmux
+            let file = instance.working_directory.join(expand_path(&path));
+            let source = file
+                .file_name()
+                .map(|n| n.to_string_lossy().to_string())
+                .unwrap_or_else(|| path.clone());
+            (read_text_file(&file), source)
+        };
+
+        let content = match content {
+            Ok(content) if content.trim().is_empty() => {
+                self.error = Some(trf!("Nothing to send: {} is empty", source));
+                return;
+            }
+            Ok(content) if content.len() > MAX_SEND_BYTES => {
+                self.error = Some(trf!(
+                    "{} is too large to send ({} KiB, max {} KiB)",
+                    source,
+                    content.len() / 1024,
+                    MAX_SEND_BYTES / 1024
+                ));
+                return;
+            }
+            Ok(content) => content,
+            Err(e) => {
+                self.error = Some(trf!("Failed to read {}: {}", source, e));
+                return;
+            }
+        };
+
+        let text = match template.and_then(|i| self.config.templates.get(i)) {
+            Some(t) => {
+                let ctx = self.template_context().unwrap_or_default();
+                config::wrap_content(&config::expand_template(&t.prompt, &ctx), &content)
+            }
+            None => content,
+        };
+
+        match Tmux::send_prompt(&pane_id, &text) {
+            Ok(_) => {
+                self.message = Some(trf!("Sent {} to {}", source, label));
+                self.count(Counter::PromptsSent);
+            }
+            Err(e) => self.error = Some(trf!("Failed to send {}: {}", source, e)),
+        }
+    }
+
+    // =========================================================================
+    // Dialog flows: Review
+    // =========================================================================
+
+    /// Open the diff viewer on the selected instance's uncommitted changes
+    pub fn start_review(&mut self) {
+        self.clear_messages();
+        let Some(instance) = self.selected_instance() else {
+            return;
+        };
+
+        match git::working_tree_diff(&instance.working_directory) {
+            Ok(lines) if lines.is_empty() => {
+                self.message = Some(tr("No changes to review").to_string());
+                self.mode = Mode::Normal;
+            }
+            Ok(lines) => {
+                self.mode = Mode::Review {
+                    lines,
+                    cursor: 0,
+                    comments: Vec::new(),
+                    input: None,
+                };
+            }
+            Err(e) => {
+                self.error = Some(trf!("Failed to load diff: {}", e));
+                self.mode = Mode::Normal;
+            }
+        }
+    }
+
+    /// Move the review cursor by `delta` lines (clamped)
+    pub fn review_move(&mut self, delta: isize) {
+        if let Mode::Review {
+            ref lines,
+            ref mut cursor,
+            ..
+        } = self.mode
+        {
+            let max = lines.len().saturating_sub(1) as isize;
+            *cursor = (*cursor as isize + delta).clamp(0, max) as usize;
+        }
+    }
+
+    /// Jump to the next (`forward`) or previous file header
+    pub fn review_jump_file(&mut self, forward: bool) {
+        if let Mode::Review {
+            ref lines,
+            ref mut cursor,
+            ..
+        } = self.mode
+        {
+            let is_file = |i: &usize| lines[*i].kind == git::DiffLineKind::File;
+            let target = if forward {
+                (*cursor + 1..lines.len()).find(is_file)
+            } else {
+                (0..*cursor).rev().find(is_file)
+            };
+            if let Some(target) = target {
+                *cursor = target;
+            }
+        }
+    }
+
+    /// Start editing the comment on the cursor line (prefilled if one exists)
+    pub fn review_start_comment(&mut self) {
+        if let Mode::Review {
+            cursor,
+            ref comments,
+            ref mut input,
+            ..
+        } = self.mode
+        {
+            let existing = comments.iter().find(|c| c.line == cursor);
+            *input = Some(existing.map(|c| c.text.clone()).unwrap_or_default());
+        }
+    }
+
+    /// Save the comment being edited (an empty comment removes it)
+    pub fn review_confirm_comment(&mut self) {
+        if let Mode::Review {
+            cursor,
+            ref mut comments,
+            ref mut input,
+            ..
+        } = self.mode
+        {
+            let Some(text) = input.take() else {
+                return;
+            };
+            comments.retain(|c| c.line != cursor);
+            if !text.trim().is_empty() {
+                comments.push(ReviewComment {
+                    line: cursor,
+                    text: text.trim().to_string(),
+                });
+            }
+        }
+    }
+
+    /// Delete the comment on the cursor line
+    pub fn review_delete_comment(&mut self) {
+        if let Mode::Review {
+            cursor,
+            ref mut comments,
+            ..
+        } = self.mode
+        {
+            comments.retain(|c| c.line != cursor);
+        }
+    }
+
+    /// Send all review comments to the agent as one prompt
+    pub fn send_review(&mut self) {
+        let prompt = if let Mode::Review {
+            ref lines,
+            ref comments,
+            ..
+        } = self.mode
+        {
+            if comments.is_empty() {
+                self.error = Some(tr("No comments to send (press c to comment)").to_string());
+                return;
+            }
+            review::build_review_prompt(lines, comments)
+        } else {
+            return;
+        };
+
+        let Some(instance) = self.selected_instance() else {
+            return;
+        };
+        let pane_id = instance.pane_id.clone();
+        let label = instance.display_name();
+
+        match Tmux::send_prompt(&pane_id, &prompt) {
+            Ok(_) => {
+                self.message = Some(trf!("Sent review to {}", label));
+                self.mode = Mode::Normal;
+                self.count(Counter::PromptsSent);
+            }
+            Err(e) => self.error = Some(trf!("Failed to send review: {}", e)),
+        }
+    }
+
+    // =========================================================================
+    // Dialog flows: Scheduled prompts
+    // =========================================================================
+
+    /// Start the schedule prompt flow for the selected instance
+    pub fn start_schedule_prompt(&mut self) {
+        self.clear_messages();
+        if self.selected_instance().is_some() {
+            self.mode = Mode::SchedulePrompt {
+                prompt: String::new(),
+                when: String::new(),
+                field: SchedulePromptField::Prompt,
+            };
+        }
+    }
+
+    /// Confirm and persist the scheduled prompt
+    pub fn confirm_schedule_prompt(&mut self) {
+        let (prompt, when) = if let Mode::SchedulePrompt {
+            ref prompt,
+            ref when,
+            ..
+        } = self.mode
+        {
+            (prompt.clone(), when.clone())
+        } else {
+            return;
+        };
+
+        if prompt.trim().is_empty() {
+            self.error = Some(tr("Prompt cannot be empty").to_string());
+            self.mode = Mode::Normal;
+            return;
+        }
+
+        let now = store::now_unix();
+        let due = match schedule::parse_when(&when, now, store::local_utc_offset) {
+            Ok(due) => due,
+            Err(e) => {
+                self.error = Some(e.to_string());
+                self.mode = Mode::Normal;
+                return;
+            }
+        };
+
+        if let Some(instance) = self.selected_instance() {
+            let pane_id = instance.pane_id.clone();
+            let label = instance.display_name();
+            match schedule::add_job(due, &pane_id, &label, &prompt) {
+                Ok(_) => {
+                    let mut msg = trf!(
+                        "Scheduled prompt for {} ({})",
+                        label,
+                        schedule::format_due(due, now)
+                    );
+                    if !crate::daemon::is_running() {
+                        msg.push_str(tr(" - start `claude-tmux daemon` to dispatch it"));
+                    }
+                    self.message = Some(msg);
+                }
+                Err(e) => self.error = Some(trf!("Failed to schedule prompt: {}", e)),
+            }
+        }
+
+        self.mode = Mode::Normal;
+    }
+
+    /// Open the pending jobs view
+    pub fn show_pending_jobs(&mut self) {
+        self.clear_messages();
+        self.mode = Mode::PendingJobs {
+            jobs: schedule::load_jobs(),
+            selected: 0,
+        };
+    }
+
+    /// Move the pending jobs selection by `delta` (wrapping)
+    pub fn select_pending_job(&mut self, delta: isize) {
+        if let Mode::PendingJobs {
+            ref jobs,
+            ref mut selected,
+        } = self.mode
+        {
+            if !jobs.is_empty() {
+                let len = jobs.len() as isize;
+                *selected = (*selected as isize + delta).rem_euclid(len) as usize;
+            }
+        }
+    }
+
+    /// Cancel the highlighted pending job
+    pub fn cancel_selected_job(&mut self) {
+        let job_id = if let Mode::PendingJobs { ref jobs, selected } = self.mode {
+            jobs.get(selected).map(|j| j.id)
+        } else {
+            None
+        };
+
+        let Some(id) = job_id else {
+            return;
+        };
+
+        match schedule::cancel_job(id) {
+            Ok(_) => self.message = Some(tr("Cancelled scheduled prompt").to_string()),
+            Err(e) => self.error = Some(trf!("Failed to cancel: {}", e)),
+        }
+
+        // Reload so the view reflects jobs dispatched in the meantime
+        let jobs = schedule::load_jobs();
+        if let Mode::PendingJobs {
+            jobs: ref mut current,
+            ref mut selected,
+        } = self.mode
+        {
+            *selected = (*selected).min(jobs.len().saturating_sub(1));
+            *current = jobs;
+        }
+    }
+
+    // =========================================================================
+    // Duplicate agent
+    // =========================================================================
+
+    /// Start a second Claude in the selected instance's directory, in a new
+    /// window of its session or in a split next to it
+    ///
+    /// The new window (or, for a split, the new pane's title) is named after
+    /// the instance's window with the first free `-N` suffix. The launch
+    /// profile is the one matching the directory or its main repository.
+    pub fn duplicate_selected(&mut self, split: bool) {
+        self.clear_messages();
+        let Some(instance) = self.selected_instance().cloned() else {
+            return;
+        };
+        let dir = instance.working_directory.clone();
+        if !dir.is_dir() {
+            self.error = Some(trf!("{} no longer exists", dir.display()));
+            return;
+        }
+
+        let mut profile_dirs = vec![dir.clone()];
+        if let Some(repo) = instance
+            .git_context
+            .as_ref()
+            .and_then(|g| g.main_repo_path.clone())
+        {
+            profile_dirs.push(repo);
+        }
+        let profile_dirs: Vec<&std::path::Path> =
+            profile_dirs.iter().map(|p| p.as_path()).collect();
+        let command = self.config.claude_command(&profile_dirs);
+
+        let taken = Tmux::list_window_names(&instance.session_name).unwrap_or_default();
+        let name = distinct_name(&instance.window_name, &taken);
+        let result = if split {
+            Tmux::split_with_claude(&instance.pane_id, &dir, &command)
+                .and_then(|pane| Tmux::set_pane_title(&pane, &name).map(|_| pane))
+        } else {
+            Tmux::new_window_with_claude(&instance.session_name, &name, &dir, &command)
+        };
+
+        match result {
+            Ok(pane) => {
+                self.refresh_instances();
+                self.message = Some(trf!("Started {} in {}", name, short_path(&dir)));
+                self.switch_after_launch(pane);
+            }
+            Err(e) => self.error = Some(trf!("Failed to duplicate agent: {}", e)),
+        }
+    }
+
+    // =========================================================================
+    // Archived sessions
+    // =========================================================================
+
+    /// Archive the selected instance's session and kill it
+    fn archive_selected(&mut self) {
+        let Some(instance) = self.selected_instance() else {
+            return;
+        };
+        let args = Tmux::claude_args(&instance.pane_id).unwrap_or_default();
+        let entry = archive::describe(
+            &instance.session_name,
+            &instance.working_directory,
+            instance.git_context.as_ref().map(|g| g.branch.clone()),
+            &args,
+        );
+        let name = entry.name.clone();
+
+        if let Err(e) = archive::add(entry) {
+            self.error = Some(trf!("Failed to archive: {}", e));
+            return;
+        }
+        match Tmux::kill_session(&name) {
+            Ok(_) => {
+                self.refresh_instances();
+                self.message = Some(trf!("Archived session '{}'", name));
+            }
+            Err(e) => self.error = Some(trf!("Archived, but failed to kill: {}", e)),
+        }
+    }
+
+    /// Open the archived sessions view
+    pub fn show_archived(&mut self) {
+        self.clear_messages();
+        self.mode = Mode::Archived {
+            sessions: archive::load_archived(),
+            selected: 0,
+        };
+    }
+
+    /// Move the archived sessions selection by `delta` (wrapping)
+    pub fn select_archived(&mut self, delta: isize) {
+        if let Mode::Archived {
+            ref sessions,
+            ref mut selected,
+        } = self.mode
+        {
+            if !sessions.is_empty() {
+                let len = sessions.len() as isize;
+                *selected = (*selected as isize + delta).rem_euclid(len) as usize;
+            }
+        }
+    }
+
+    fn selected_archived(&self) -> Option<archive::ArchivedSession> {
+        match &self.mode {
+            Mode::Archived { sessions, selected } => sessions.get(*selected).cloned(),
+            _ => None,
+        }
+    }
+
+    /// Recreate the highlighted archived session and resume its conversation
+    ///
+    /// The session keeps its name unless another session took it meanwhile.
+    pub fn restore_archived(&mut self) {
+        let Some(entry) = self.selected_archived() else {
+            return;
+        };
+        if !entry.path.is_dir() {
+            self.error = Some(trf!("{} no longer exists", entry.path.display()));
+            return;
+        }
+
+        let existing = Tmux::list_sessions().unwrap_or_default();
+        let name = (1..)
+            .map(|n| match n {
+                1 => entry.name.clone(),
+                n => format!("{}-{}", entry.name, n),
+            })
+            .find(|name| !existing.contains(name))
+            .unwrap_or_default();
+
+        match Tmux::new_session(&name, &entry.path, Some(&entry.restore_command())) {
+            Ok(_) => {
+                if let Err(e) = archive::remove(entry.id) {
+                    self.error = Some(trf!("Restored, but failed to update archive: {}", e));
+                } else {
+                    self.message = Some(trf!("Restored session '{}'", name));
+                }
+                self.refresh_instances();
+                self.mode = Mode::Normal;
+                self.switch_after_launch(name);
+            }
+            Err(e) => self.error = Some(trf!("Failed to restore: {}", e)),
+        }
+    }
+
+    /// Drop the highlighted session from the archive
+    pub fn delete_archived(&mut self) {
+        let Some(entry) = self.selected_archived() else {
+            return;
+        };
+        match archive::remove(entry.id) {
+            Ok(_) => self.message = Some(trf!("Removed '{}' from the archive", entry.name)),
+            Err(e) => self.error = Some(trf!("Failed to remove: {}", e)),
+        }
+
+        let sessions = archive::load_archived();
+        if let Mode::Archived {
+            sessions: ref mut current,
+            ref mut selected,
+        } = self.mode
+        {
+            *selected = (*selected).min(sessions.len().saturating_sub(1));
+            *current = sessions;
+        }
+    }
+
+    // =========================================================================
+    // Status snapshots
+    // =========================================================================
+
+    /// Open the snapshots, on the latest one of the selected instance
+    pub fn show_snapshots(&mut self) {
+        self.clear_messages();
+        let snapshots = snapshot::load();
+        let pane_id = self.selected_instance().map(|i| i.pane_id.clone());
+        let selected = snapshots
+            .iter()
+            .position(|s| Some(&s.pane_id) == pane_id.as_ref())
+            .unwrap_or(0);
+        self.mode = Mode::Snapshots {
+            snapshots,
+            selected,
+            content: None,
+            scroll: 0,
+        };
+    }
+
+    /// Move the snapshots selection by `delta` (wrapping)
+    pub fn select_snapshot(&mut self, delta: isize) {
+        if let Mode::Snapshots {
+            ref snapshots,
+            ref mut selected,
+            ..
+        } = self.mode
+        {
+            if !snapshots.is_empty() {
+                let len = snapshots.len() as isize;
+                *selected = (*selected as isize + delta).rem_euclid(len) as usize;
+            }
+        }
+    }
+
+    /// Read the highlighted snapshot
+    pub fn open_snapshot(&mut self) {
+        let Mode::Snapshots {
+            ref snapshots,
+            selected,
+            ref mut content,
+            ref mut scroll,
+        } = self.mode
+        else {
+            return;
+        };
+        let Some(entry) = snapshots.get(selected) else {
+            return;
+        };
+        match snapshot::content(entry) {
+            Ok(text) => {
+                *content = Some(text.lines().map(str::to_string).collect());
+                *scroll = 0;
+            }
+            Err(e) => self.error = Some(trf!("Failed to open snapshot: {}", e)),
+        }
+    }
+
+    /// Go back from a snapshot to the list
+    pub fn close_snapshot(&mut self) {
+        if let Mode::Snapshots {
+            ref mut content, ..
+        } = self.mode
+        {
+            *content = None;
+        }
+    }
+
+    /// Scroll the snapshot being read by `delta` lines (positive is up)
+    pub fn scroll_snapshot(&mut self, delta: isize) {
+        if let Mode::Snapshots {
+            content: Some(ref lines),
+            ref mut scroll,
+            ..
+        } = self.mode
+        {
+            *scroll = scroll
+                .saturating_add_signed(delta)
+                .min(lines.len().saturating_sub(1));
+        }
+    }
+
+    /// Delete the highlighted snapshot
+    pub fn delete_snapshot(&mut self) {
+        let Mode::Snapshots {
+            ref snapshots,
+            selected,
+            ..
+        } = self.mode
+        else {
+            return;
+        };
+        let Some(id) = snapshots.get(selected).map(|s| s.id) else {
+            return;
+        };
+        match snapshot::remove(id) {
+            Ok(_) => self.message = Some(tr("Deleted snapshot").to_string()),
+            Err(e) => self.error = Some(trf!("Failed to delete snapshot: {}", e)),
+        }
+
+        let snapshots = snapshot::load();
+        if let Mode::Snapshots {
+            snapshots: ref mut current,
+            ref mut selected,
+            ..
+        } = self.mode
+        {
+            *selected = (*selected).min(snapshots.len().saturating_sub(1));
+            *current = snapshots;
+        }
+    }
+
+    // =========================================================================
+    // Conflict resolution
+    // =========================================================================
+
+    /// Open the conflicted files of the selected checkout
+    pub fn show_conflicts(&mut self) {
+        self.clear_messages();
+        let Some(instance) = self.selected_instance() else {
+            return;
+        };
+        let dir = instance.working_directory.clone();
+        let operation = instance.git_context.as_ref().and_then(|g| g.in_progress);
+        let Some(root) = GitContext::checkout_root(&dir) else {
+            self.error = Some(tr("Not a git checkout").to_string());
+            self.mode = Mode::Normal;
+            return;
+        };
+        match git::conflicted_files(&root) {
+            Ok(files) => {
+                self.mode = Mode::Conflicts {
+                    root,
+                    files,
+                    selected: 0,
+                    operation,
+                }
+            }
+            Err(e) => {
+                self.error = Some(trf!("Failed to list conflicts: {}", e));
+                self.mode = Mode::Normal;
+            }
+        }
+    }
+
+    /// Move the conflicted file selection by `delta` (wrapping)
+    pub fn select_conflict(&mut self, delta: isize) {
+        if let Mode::Conflicts {
+            ref files,
+            ref mut selected,
+            ..
+        } = self.mode
+        {
+            if !files.is_empty() {
+                let len = files.len() as isize;
+                *selected = (*selected as isize + delta).rem_euclid(len) as usize;
+            }
+        }
+    }
+
+    fn selected_conflict(&self) -> Option<(PathBuf, String)> {
+        match &self.mode {
+            Mode::Conflicts {
+                root,
+                files,
+                selected,
+                ..
+            } => files.get(*selected).map(|f| (root.clone(), f.path.clone())),
+            _ => None,
+        }
+    }
+
+    /// Resolve the highlighted file by keeping one side, or with its
+    /// working tree content when `side` is None
+    pub fn resolve_conflict(&mut self, side: Option<ConflictSide>) {
+        let Some((root, file)) = self.selected_conflict() else {
+            return;
+        };
+        let result = match side {
+            Some(side) => git::take_side(&root, &file, side),
+            None => git::mark_resolved(&root, &file),
+        };
+        match result {
+            Ok(_) => self.message = Some(trf!("Resolved {}", file)),
+            Err(e) => self.error = Some(trf!("Failed to resolve: {}", e)),
+        }
+        self.reload_conflicts();
+    }
+
+    fn reload_conflicts(&mut self) {
+        let Mode::Conflicts {
+            ref root,
+            ref mut files,
+            ref mut selected,
+            ..
+        } = self.mode
+        else {
+            return;
+        };
+        match git::conflicted_files(root) {
+            Ok(current) => {
+                *selected = (*selected).min(current.len().saturating_sub(1));
+                *files = current;
+            }
+            Err(e) => self.error = Some(trf!("Failed to list conflicts: {}", e)),
+        }
+    }
+
+    /// Open the highlighted file in the editor, in a new window of the session
+    pub fn edit_conflict(&mut self) {
+        let Some((root, file)) = self.selected_conflict() else {
+            return;
+        };
+        let Some(session) = self.selected_instance().map(|i| i.session_name.clone()) else {
+            return;
+        };
+        let command = format!("{} {}", editor_command(), config::shell_quote(&file));
+        match Tmux::new_window_running(&session, "conflict", &root, &command) {
+            Ok(pane_id) => self.switch_to(pane_id),
+            Err(e) => self.error = Some(trf!("Failed to open editor: {}", e)),
+        }
+    }
+
+    /// Continue the merge or rebase once every conflict is resolved
+    ///
+    /// A rebase that stops on new conflicts lists them instead.
+    pub fn continue_after_conflicts(&mut self) {
+        let Mode::Conflicts {
+            ref root,
+            ref files,
+            operation,
+            ..
+        } = self.mode
+        else {
+            return;
+        };
+        let Some(operation) = operation else {
+            return;
+        };
+        if !files.is_empty() {
+            self.error = Some(tr("Resolve every conflict first").to_string());
+            return;
+        }
+        let root = root.clone();
+        let result = git::try_lock_repo(&root, tr("continuing"))
+            .and_then(|_lock| GitContext::continue_operation(&root, operation));
+        self.refresh_instances();
+        match result {
+            Ok(_) => {
+                let stopped = GitContext::detect(&root).and_then(|git| git.in_progress);
+                self.message = Some(
+                    match operation {
+                        PendingOperation::Merge => tr("Merge completed"),
+                        PendingOperation::Rebase if stopped.is_some() => tr("Rebase continued"),
+                        PendingOperation::Rebase => tr("Rebase completed"),
+                    }
+                    .to_string(),
+                );
+                self.mode = Mode::Normal;
+            }
+            Err(e) => {
+                self.error = Some(trf!("Continue failed: {}", e));
+                self.reload_conflicts();
+            }
+        }
+    }
+
+    // =========================================================================
+    // Pane logging
+    // =========================================================================
+
+    /// Log everything the selected pane outputs to a new timestamped file
+    fn start_pane_log(&mut self) {
+        let Some(instance) = self.selected_instance() else {
+            return;
+        };
+        let pane_id = instance.pane_id.clone();
+        let dir = match &self.config.pane_log_dir {
+            Some(dir) => expand_path(dir),
+            None => store::data_dir().join("pane-logs"),
+        };
+        let local_time = logging::format_local(store::now_unix());
+        let file = pane_log_path(&dir, &instance.tmux_target(), &local_time);
+
+        let result = fs::create_dir_all(&dir)
+            .with_context(|| format!("Failed to create {}", dir.display()))
+            .and_then(|_| Tmux::start_pane_log(&pane_id, &file));
+        match result {
+            Ok(()) => {
+                self.refresh_instances();
+                self.message = Some(trf!("Logging the pane to {}", short_path(&file)));
+            }
+            Err(e) => self.error = Some(trf!("Failed to start logging: {}", e)),
+        }
+    }
+
+    // =========================================================================
+    // Pane content search
+    // =========================================================================
+
+    /// Open the search across the content of every Claude pane
+    pub fn start_search(&mut self) {
+        self.clear_messages();
+        self.mode = Mode::Search {
+            query: String::new(),
+            results: None,
+            selected: 0,
+        };
+    }
+
+    /// Move the search result selection by `delta` (wrapping)
+    pub fn select_search_result(&mut self, delta: isize) {
+        if let Mode::Search {
+            results: Some(ref results),
+            ref mut selected,
+            ..
+        } = self.mode
+        {
+            if !results.is_empty() {
+                let len = results.len() as isize;
+                *selected = (*selected as isize + delta).rem_euclid(len) as usize;
+            }
+        }
+    }
+
+    /// Run the search, or switch to the highlighted result once it has run
+    pub fn confirm_search(&mut self) {
+        let Mode::Search {
+            ref query,
+            ref results,
+            selected,
+        } = self.mode
+        else {
+            return;
+        };
+        match results {
+            Some(results) => {
+                if let Some(result) = results.get(selected) {
+                    let target = result.target.clone();
+                    self.switch_to(target);
+                }
+            }
+            None if !query.trim().is_empty() => {
+                let found = search::search_panes(&self.instances, query);
+                if let Mode::Search {
+                    ref mut results,
+                    ref mut selected,
+                    ..
+                } = self.mode
+                {
+                    *results = Some(found);
+                    *selected = 0;
+                }
+            }
+            None => {}
+        }
+    }
+
+    // =========================================================================
+    // Command palette
+    // =========================================================================
+
+    /// Open the command palette, offering the menu entries of the selected
+    /// instance along with every keyed action
+    pub fn start_palette(&mut self) {
+        self.clear_messages();
+        self.compute_actions();
+        self.mode = Mode::Palette {
+            input: String::new(),
+            commands: palette::commands(&self.available_actions),
+            selected: 0,
+        };
+    }
+
+    /// Move the palette selection by `delta` among the matches (wrapping)
+    pub fn select_palette_command(&mut self, delta: isize) {
+        if let Mode::Palette {
+            ref input,
+            ref commands,
+            ref mut selected,
+        } = self.mode
+        {
+            let len = palette::matches(input, commands).len() as isize;
+            if len > 0 {
+                *selected = (*selected as isize + delta).rem_euclid(len) as usize;
+            }
+        }
+    }
+
+    /// Close the palette, returning the highlighted command to run
+    pub fn confirm_palette(&mut self) -> Option<Command> {
+        let Mode::Palette {
+            ref input,
+            ref commands,
+            selected,
+        } = self.mode
+        else {
+            return None;
+        };
+        let command = palette::matches(input, commands)
+            .get(selected)
+            .map(|(command, _)| (*command).clone());
+        self.cancel();
+        command
+    }
+
+    // =========================================================================
+    // All panes view
+    // =========================================================================
+
+    /// Open the view listing every tmux pane, highlighting the selected instance
+    pub fn show_all_panes(&mut self) {
+        self.clear_messages();
+        let panes = match Tmux::list_every_pane() {
+            Ok(panes) => panes,
+            Err(e) => {
+                self.error = Some(trf!("Failed to list panes: {}", e));
+                return;
+            }
+        };
+        let selected = self
+            .selected_instance()
+            .and_then(|i| panes.iter().position(|p| p.pane.id == i.pane_id))
+            .unwrap_or(0);
+        self.mode = Mode::AllPanes { panes, selected };
+    }
+
+    /// Move the all-panes selection by `delta` (wrapping)
+    pub fn select_listed_pane(&mut self, delta: isize) {
+        if let Mode::AllPanes {
+            ref panes,
+            ref mut selected,
+        } = self.mode
+        {
+            if !panes.is_empty() {
+                let len = panes.len() as isize;
+                *selected = (*selected as isize + delta).rem_euclid(len) as usize;
+            }
+        }
+    }
+
+    fn listed_pane(&self) -> Option<&PaneEntry> {
+        match &self.mode {
+            Mode::AllPanes { panes, selected } => panes.get(*selected),
+            _ => None,
+        }
+    }
+
+    /// Switch to the highlighted pane
+    pub fn switch_to_listed_pane(&mut self) {
+        if let Some(pane_id) = self.listed_pane().map(|p| p.pane.id.clone()) {
+            self.switch_to(pane_id);
+        }
+    }
+
+    /// Start Claude in the highlighted pane, or in a split next to it
+    ///
+    /// Typing into a pane is only done at a shell prompt; a pane running
+    /// another program (an editor, a server) can still get a split.
+    pub fn start_claude_in_listed_pane(&mut self, split: bool) {
+        let Some(entry) = self.listed_pane().cloned() else {
+            return;
+        };
+        self.clear_messages();
+        if !split && entry.has_claude {
+            self.error = Some(trf!("Claude already runs in {}", entry.display_name()));
+            return;
+        }
+        if !split && !entry.is_shell() {
+            self.error = Some(trf!(
+                "{} is running {}; press v to start Claude in a split",
+                entry.display_name(),
+                entry.pane.current_command
+            ));
+            return;
+        }
 
-        self.mode = Mode::Normal;
+        let dir = &entry.pane.current_path;
+        let command = self.config.claude_command(&[dir]);
+        let result = if split {
+            Tmux::split_with_claude(&entry.pane.id, dir, &command)
+        } else {
+            Tmux::start_claude(&entry.pane.id, &command).map(|_| entry.pane.id.clone())
+        };
+        match result {
+            Ok(pane) => {
+                self.mode = Mode::Normal;
+                self.refresh_instances();
+                self.message = Some(if split {
+                    trf!("Started Claude next to {}", entry.display_name())
+                } else {
+                    trf!("Started Claude in {}", entry.display_name())
+                });
+                self.switch_after_launch(pane);
+            }
+            Err(e) => self.error = Some(trf!("Failed to start Claude: {}", e)),
+        }
     }
 
     // =========================================================================
-    // Dialog flows: Create Pull Request
+    // Sessions view
     // =========================================================================
 
-    /// Start the create pull request flow
-    pub fn start_create_pull_request(&mut self) {
+    /// Open the view listing every tmux session, highlighting the selected
+    /// instance's
+    ///
+    /// With `quit_on_close`, closing the view quits, as when it is opened on
+    /// its own with `claude-tmux sessions`.
+    pub fn show_sessions(&mut self, quit_on_close: bool) {
         self.clear_messages();
-        let Some(instance) = self.selected_instance() else {
-            return;
+        let sessions = match Tmux::list_every_session() {
+            Ok(sessions) => sessions,
+            Err(e) => {
+                self.error = Some(trf!("Failed to list sessions: {}", e));
+                return;
+            }
+        };
+        let selected = self
+            .selected_instance()
+            .and_then(|i| sessions.iter().position(|s| s.name == i.session_name))
+            .unwrap_or(0);
+        self.mode = Mode::Sessions {
+            sessions,
+            selected,
+            confirm_kill: false,
+            rename: None,
+            quit_on_close,
         };
+    }
 
-        let path = &instance.working_directory;
-        let base_branch = git::get_default_branch(path).unwrap_or_else(|| "main".to_string());
+    /// List the sessions again, highlighting `id` if it is still there
+    fn reload_listed_sessions(&mut self, id: &str) {
+        let listed = Tmux::list_every_session();
+        if let Mode::Sessions {
+            ref mut sessions,
+            ref mut selected,
+            ref mut confirm_kill,
+            ref mut rename,
+            ..
+        } = self.mode
+        {
+            match listed {
+                Ok(listed) => {
+                    *selected = listed
+                        .iter()
+                        .position(|s| s.id == id)
+                        .unwrap_or(*selected)
+                        .min(listed.len().saturating_sub(1));
+                    *sessions = listed;
+                }
+                Err(e) => self.error = Some(trf!("Failed to list sessions: {}", e)),
+            }
+            *confirm_kill = false;
+            *rename = None;
+        }
+        self.refresh_instances();
+    }
 
-        self.mode = Mode::CreatePullRequest {
-            title: String::new(),
-            body: String::new(),
-            base_branch,
-            field: CreatePullRequestField::Title,
-        };
+    /// Move the sessions selection by `delta` (wrapping)
+    pub fn select_listed_session(&mut self, delta: isize) {
+        if let Mode::Sessions {
+            ref sessions,
+            ref mut selected,
+            ..
+        } = self.mode
+        {
+            if !sessions.is_empty() {
+                let len = sessions.len() as isize;
+                *selected = (*selected as isize + delta).rem_euclid(len) as usize;
+            }
+        }
     }
 
-    /// Confirm and execute PR creation
-    pub fn confirm_create_pull_request(&mut self) {
-        let (title, body, base_branch) = if let Mode::CreatePullRequest {
-            ref title,
-            ref body,
-            ref base_branch,
+    fn listed_session(&self) -> Option<&Session> {
+        match &self.mode {
+            Mode::Sessions {
+                sessions, selected, ..
+            } => sessions.get(*selected),
+            _ => None,
+        }
+    }
+
+    /// Switch to the highlighted session
+    pub fn switch_to_listed_session(&mut self) {
+        if let Some(id) = self.listed_session().map(|s| s.id.clone()) {
+            self.switch_to(id);
+        }
+    }
+
+    /// Ask to confirm killing the highlighted session, which is skipped when
+    /// turned off and none of its checkouts has commits on no remote
+    pub fn start_kill_listed_session(&mut self) {
+        let Some(session) = self.listed_session() else {
+            return;
+        };
+        let mut paths: Vec<PathBuf> = session
+            .panes
+            .iter()
+            .map(|pane| pane.current_path.clone())
+            .collect();
+        paths.sort();
+        paths.dedup();
+        self.unpushed_commits = 0;
+        self.check_unpushed(paths);
+        if let Mode::Sessions {
+            ref mut confirm_kill,
             ..
         } = self.mode
         {
-            (title.clone(), body.clone(), base_branch.clone())
-        } else {
-            self.mode = Mode::Normal;
+            *confirm_kill = true;
+        }
+    }
+
+    /// Kill the highlighted session once confirmed
+    pub fn kill_listed_session(&mut self) {
+        let Some(session) = self.listed_session().cloned() else {
             return;
         };
+        match Tmux::kill_session(&session.id) {
+            Ok(()) => self.message = Some(trf!("Killed session '{}'", session.name)),
+            Err(e) => self.error = Some(trf!("Failed to kill: {}", e)),
+        }
+        self.unpushed_commits = 0;
+        self.unpushed_receiver = None;
+        self.reload_listed_sessions(&session.id);
+    }
 
-        if title.trim().is_empty() {
-            self.error = Some("PR title cannot be empty".to_string());
-            self.mode = Mode::Normal;
+    /// Start typing a new name for the highlighted session
+    pub fn start_rename_listed_session(&mut self) {
+        let Some(name) = self.listed_session().map(|s| s.name.clone()) else {
             return;
+        };
+        if let Mode::Sessions { ref mut rename, .. } = self.mode {
+            *rename = Some(name);
         }
+    }
 
-        if let Some(instance) = self.selected_instance() {
-            let path = instance.working_directory.clone();
-            match git::create_pull_request(&path, &title, &body, &base_branch) {
-                Ok(result) => {
-                    self.message = Some(format!("Created PR: {}", result.url));
-                }
-                Err(e) => {
-                    self.error = Some(format!("Failed to create PR: {}", e));
-                }
+    /// Rename the highlighted session to the typed name
+    pub fn confirm_session_rename(&mut self) {
+        let Some(session) = self.listed_session().cloned() else {
+            return;
+        };
+        let new_name = match &self.mode {
+            Mode::Sessions {
+                rename: Some(name), ..
+            } => name.trim().to_string(),
+            _ => return,
+        };
+        if !new_name.is_empty() && new_name != session.name {
+            match Tmux::rename_session(&session.id, &new_name) {
+                Ok(()) => self.message = Some(trf!("Renamed '{}' to '{}'", session.name, new_name)),
+                Err(e) => self.error = Some(trf!("Failed to rename: {}", e)),
             }
         }
+        self.reload_listed_sessions(&session.id);
+    }
 
-        self.mode = Mode::Normal;
+    /// Leave a kill confirmation or rename, staying in the view
+    pub fn cancel_session_edit(&mut self) {
+        if let Mode::Sessions {
+            ref mut confirm_kill,
+            ref mut rename,
+            ..
+        } = self.mode
+        {
+            *confirm_kill = false;
+            *rename = None;
+        }
+        self.unpushed_commits = 0;
+        self.unpushed_receiver = None;
+    }
+
+    /// Close the sessions view
+    pub fn close_sessions(&mut self) {
+        if matches!(
+            self.mode,
+            Mode::Sessions {
+                quit_on_close: true,
+                ..
+            }
+        ) {
+            self.should_quit = true;
+        } else {
+            self.cancel();
+        }
     }
 
     // =========================================================================
@@ -1142,16 +5626,251 @@ impl App {
     /// Show help
     pub fn show_help(&mut self) {
         self.clear_messages();
-        self.mode = Mode::Help;
+        self.mode = Mode::Help { scroll: 0 };
+    }
+
+    /// Scroll the help screen by `delta` lines (positive scrolls down); the
+    /// renderer keeps it within the content
+    pub fn scroll_help(&mut self, delta: isize) {
+        if let Mode::Help { ref mut scroll } = self.mode {
+            *scroll = scroll.saturating_add_signed(delta);
+            self.needs_redraw = true;
+        }
     }
 
     /// Cancel current mode and return to normal
     pub fn cancel(&mut self) {
+        // Dismissing a credentials prompt gives up on the push
+        if matches!(
+            self.mode,
+            Mode::Credentials { .. } | Mode::Passphrase { .. }
+        ) {
+            self.pull_request_after_push = None;
+        }
         self.pending_action = None;
+        self.unpushed_commits = 0;
+        self.unpushed_receiver = None;
         self.pr_info = None;
+        self.branch_time = None;
+        self.agent_env = None;
         self.mode = Mode::Normal;
     }
 
+    // =========================================================================
+    // Log viewer
+    // =========================================================================
+
+    /// Show the most recent log lines
+    pub fn show_logs(&mut self) {
+        self.clear_messages();
+        self.mode = Mode::Logs {
+            lines: logging::recent_lines()
+                .iter()
+                .flat_map(|entry| entry.lines().map(str::to_string))
+                .collect(),
+            scroll: 0,
+        };
+    }
+
+    /// Scroll the log view by `delta` lines (positive is towards older lines)
+    pub fn scroll_logs(&mut self, delta: isize) {
+        if let Mode::Logs {
+            ref lines,
+            ref mut scroll,
+        } = self.mode
+        {
+            *scroll = scroll
+                .saturating_add_signed(delta)
+                .min(lines.len().saturating_sub(1));
+        }
+    }
+
+    // =========================================================================
+    // Working time
+    // =========================================================================
+
+    /// Sample the agents for working time, input waits and status snapshots,
+    /// at most every `WORK_TIME_SAMPLE`
+    ///
+    /// Nothing is recorded while the daemon runs, since it records instead.
+    pub fn sample_agents(&mut self) {
+        let sampling =
+            self.config.track_work_time || self.config.usage_stats || self.config.status_snapshots;
+        if !sampling || self.is_loading() {
+            return;
+        }
+        let now = Instant::now();
+        if self
+            .last_work_sample
+            .is_some_and(|last| now.duration_since(last) < WORK_TIME_SAMPLE)
+        {
+            return;
+        }
+        self.last_work_sample = Some(now);
+
+        if crate::daemon::is_running() {
+            self.work_tracker.pause();
+            self.input_watch.pause();
+            self.snapshot_watch.pause();
+            return;
+        }
+        let now = store::now_unix();
+        if self.config.track_work_time {
+            self.work_tracker.sample(&self.instances, now);
+        }
+        if self.config.usage_stats {
+            self.input_watch.sample(&self.instances, now);
+        }
+        if self.config.status_snapshots {
+            self.snapshot_watch.sample(&self.instances);
+        }
+    }
+
+    /// Open the working time summary
+    pub fn show_work_time(&mut self) {
+        self.clear_messages();
+        self.mode = Mode::WorkTime {
+            totals: worktime::branch_totals(store::now_unix()),
+            selected: 0,
+        };
+    }
+
+    /// Move the working time selection by `delta` (wrapping)
+    pub fn select_work_time(&mut self, delta: isize) {
+        if let Mode::WorkTime {
+            ref totals,
+            ref mut selected,
+        } = self.mode
+        {
+            if !totals.is_empty() {
+                let len = totals.len() as isize;
+                *selected = (*selected as isize + delta).rem_euclid(len) as usize;
+            }
+        }
+    }
+
+    // =========================================================================
+    // Usage statistics
+    // =========================================================================
+
+    /// Count an action in the usage statistics, unless they are turned off
+    fn count(&self, counter: Counter) {
+        if self.config.usage_stats {
+            stats::record(&[(counter, 1)]);
+        }
+    }
+
+    /// Open the usage statistics
+    pub fn show_stats(&mut self) {
+        self.clear_messages();
+        self.mode = Mode::Stats {
+            stats: stats::load(),
+        };
+    }
+
+    // =========================================================================
+    // Keybinding editor
+    // =========================================================================
+
+    /// Show the keybinding editor
+    pub fn show_keybindings(&mut self) {
+        self.clear_messages();
+        self.mode = Mode::Keybindings {
+            selected: 0,
+            capturing: false,
+            conflict: None,
+        };
+    }
+
+    /// Move the highlight in the keybinding editor
+    pub fn select_keybinding(&mut self, delta: isize) {
+        if let Mode::Keybindings {
+            ref mut selected, ..
+        } = self.mode
+        {
+            *selected = selected
+                .saturating_add_signed(delta)
+                .min(Action::ALL.len() - 1);
+        }
+    }
+
+    /// Start or stop waiting for a new key for the highlighted action
+    pub fn capture_keybinding(&mut self, capture: bool) {
+        if let Mode::Keybindings {
+            ref mut capturing,
+            ref mut conflict,
+            ..
+        } = self.mode
+        {
+            *capturing = capture;
+            *conflict = None;
+        }
+    }
+
+    /// Bind the highlighted action to `key` and save it to the config file
+    ///
+    /// A key used by another action is only taken when pressed twice in a
+    /// row, and that action then gets the highlighted action's old key.
+    pub fn rebind_selected(&mut self, key: char) {
+        let Mode::Keybindings {
+            selected,
+            ref mut capturing,
+            ref mut conflict,
+        } = self.mode
+        else {
+            return;
+        };
+        let action = Action::ALL[selected];
+        if let Some(custom) = self.config.actions.iter().find(|a| a.key == Some(key)) {
+            self.error = Some(trf!("'{}' runs the custom action '{}'", key, custom.name));
+            return;
+        }
+        let old_key = self.config.keys.key(action);
+        let mut bindings = vec![(action, key)];
+        if let Some(other) = self.config.keys.conflict(action, key) {
+            if *conflict != Some(key) {
+                *conflict = Some(key);
+                return;
+            }
+            bindings.push((other, old_key));
+        }
+        *capturing = false;
+        *conflict = None;
+        self.apply_bindings(&bindings);
+    }
+
+    /// Restore the highlighted action's default key
+    ///
+    /// An action that took the default key over gets the highlighted
+    /// action's current key instead.
+    pub fn reset_selected_binding(&mut self) {
+        let Mode::Keybindings { selected, .. } = self.mode else {
+            return;
+        };
+        let action = Action::ALL[selected];
+        let key = action.default_key();
+        if let Some(custom) = self.config.actions.iter().find(|a| a.key == Some(key)) {
+            self.error = Some(trf!("'{}' runs the custom action '{}'", key, custom.name));
+            return;
+        }
+        let mut bindings = vec![(action, key)];
+        if let Some(other) = self.config.keys.conflict(action, key) {
+            bindings.push((other, self.config.keys.key(action)));
+        }
+        self.apply_bindings(&bindings);
+    }
+
+    fn apply_bindings(&mut self, bindings: &[(Action, char)]) {
+        if let Err(e) = save_bindings(&Config::path(), bindings) {
+            self.error = Some(trf!("Failed to save keybindings: {}", format!("{:#}", e)));
+            return;
+        }
+        for (action, key) in bindings {
+            self.config.keys.set(*action, *key);
+        }
+        self.message = Some(trf!("Saved to {}", Config::path().display()));
+    }
+
     // =========================================================================
     // Status and statistics
     // =========================================================================
@@ -1169,7 +5888,7 @@ impl App {
                 ClaudeCodeStatus::Working => working += 1,
                 ClaudeCodeStatus::WaitingInput => waiting += 1,
                 ClaudeCodeStatus::Idle => idle += 1,
-                ClaudeCodeStatus::Unknown => {}
+                ClaudeCodeStatus::Crashed | ClaudeCodeStatus::Unknown => {}
             }
         }
 
@@ -1296,6 +6015,63 @@ impl App {
         self.update_new_session_path_suggestions();
     }
 
+    /// Update path suggestions for SendFile mode
+    pub fn update_send_file_path_suggestions(&mut self) {
+        if let Mode::SendFile {
+            ref path,
+            ref mut path_suggestions,
+            ref mut path_selected,
+            ..
+        } = self.mode
+        {
+            *path_suggestions = crate::completion::complete_path(path).suggestions;
+            if path_selected.is_some_and(|idx| idx >= path_suggestions.len()) {
+                *path_selected = path_suggestions.len().checked_sub(1);
+            }
+        }
+    }
+
+    /// Move the SendFile path suggestion selection by `delta` (wrapping)
+    pub fn select_send_file_path(&mut self, delta: isize) {
+        if let Mode::SendFile {
+            ref path_suggestions,
+            ref mut path_selected,
+            ..
+        } = self.mode
+        {
+            if path_suggestions.is_empty() {
+                return;
+            }
+            let len = path_suggestions.len() as isize;
+            let next = match *path_selected {
+                Some(i) => i as isize + delta,
+                None if delta < 0 => len - 1,
+                None => 0,
+            };
+            *path_selected = Some(next.rem_euclid(len) as usize);
+        }
+    }
+
+    /// Accept the current path completion in SendFile mode
+    pub fn accept_send_file_path_completion(&mut self) {
+        if let Mode::SendFile {
+            ref mut path,
+            ref path_suggestions,
+            ref mut path_selected,
+            ..
+        } = self.mode
+        {
+            if let Some(suggestion) = path_selected
+                .and_then(|idx| path_suggestions.get(idx))
+                .or(path_suggestions.first())
+            {
+                *path = suggestion.clone();
+                *path_selected = None;
+            }
+        }
+        self.update_send_file_path_suggestions();
+    }
+
     /// Select previous path suggestion in NewWorktree mode
     pub fn select_prev_worktree_path(&mut self) {
         if let Mode::NewWorktree {
@@ -1364,6 +6140,62 @@ impl App {
         self.update_worktree_path_suggestions();
     }
 
+    /// Branch the NewWorktree dialog would create a worktree for, as
+    /// (name, is new, remote branch it tracks)
+    pub fn worktree_branch(&self) -> Option<(String, bool, Option<String>)> {
+        let Mode::NewWorktree {
+            ref all_branches,
+            ref remote_branches,
+            ref branch_input,
+            selected_branch,
+            ..
+        } = self.mode
+        else {
+            return None;
+        };
+        let filtered = self.filtered_branches();
+        let choice = selected_branch
+            .and_then(|idx| filtered.get(idx).copied())
+            .unwrap_or(branch_input);
+        if choice.is_empty() {
+            return None;
+        }
+        Some(resolve_worktree_branch(
+            choice,
+            all_branches,
+            remote_branches,
+        ))
+    }
+
+    /// Upstream suggested for a new branch in NewWorktree mode, while the
+    /// upstream is empty: the branch of the same name on the default remote
+    pub fn upstream_suggestion(&self) -> Option<String> {
+        let Mode::NewWorktree {
+            ref upstream,
+            ref default_remote,
+            ..
+        } = self.mode
+        else {
+            return None;
+        };
+        let (branch_name, is_new_branch, _) = self.worktree_branch()?;
+        let remote = default_remote.as_ref()?;
+        (upstream.is_empty() && is_new_branch).then(|| format!("{}/{}", remote, branch_name))
+    }
+
+    /// Accept the suggested upstream in NewWorktree mode
+    pub fn accept_upstream_completion(&mut self) {
+        let Some(suggestion) = self.upstream_suggestion() else {
+            return;
+        };
+        if let Mode::NewWorktree {
+            ref mut upstream, ..
+        } = self.mode
+        {
+            *upstream = suggestion;
+        }
+    }
+
     /// Accept the current branch completion in NewWorktree mode
     pub fn accept_branch_completion(&mut self) {
         let selected_branch_name = if let Mode::NewWorktree {
@@ -1427,8 +6259,9 @@ impl App {
 
         match self.mode {
             Mode::ActionMenu => {
-                // Count items before selected instance (1 row each)
-                let mut index = self.selected;
+                // Count items before selected instance (1 row each, plus
+                // group headers)
+                let mut index = self.selected + self.group_headers_through(self.selected);
 
                 // Add 1 for the selected instance row itself
                 index += 1;
@@ -1436,6 +6269,14 @@ impl App {
                 // Add 1 for metadata row (always present when expanded)
                 index += 1;
 
+                // Add 1 for the directories row if Claude's differs from the pane's
+                if self
+                    .selected_instance()
+                    .is_some_and(|inst| inst.pane_path.is_some())
+                {
+                    index += 1;
+                }
+
                 // Add 1 for git info row if present
                 if self
                     .selected_instance()
@@ -1443,6 +6284,15 @@ impl App {
                 {
                     index += 1;
 
+                    // Add 1 for last commit row if present
+                    if self
+                        .selected_instance()
+                        .and_then(|inst| inst.git_context.as_ref())
+                        .is_some_and(|git| git.last_commit.is_some())
+                    {
+                        index += 1;
+                    }
+
                     // Add 1 for PR info row if present
                     if self.pr_info.is_some() {
                         index += 1;
@@ -1458,8 +6308,9 @@ impl App {
                 index
             }
             _ => {
-                // In non-ActionMenu modes, just the instance index
-                self.selected
+                // In non-ActionMenu modes, just the instance index and the
+                // group headers above it
+                self.selected + self.group_headers_through(self.selected)
             }
         }
     }
@@ -1475,23 +6326,38 @@ impl App {
 
         match self.mode {
             Mode::ActionMenu => {
-                // Base: one row per instance
-                let mut total = filtered_count;
+                // Base: one row per instance and group header
+                let mut total = filtered_count + self.group_headers_through(filtered_count - 1);
 
                 // Add expanded content for selected instance:
                 // - 1 metadata row
+                // - 1 directories row (if the pane moved)
                 // - 1 git info row (if git context)
+                // - 1 last commit row (if the branch has a commit)
                 // - 1 PR info row (if pr_info)
                 // - 1 separator
                 // - N action rows
                 // - 1 end separator
                 total += 1; // metadata row
+                if self
+                    .selected_instance()
+                    .is_some_and(|inst| inst.pane_path.is_some())
+                {
+                    total += 1; // directories row
+                }
 
                 if self
                     .selected_instance()
                     .is_some_and(|inst| inst.git_context.is_some())
                 {
                     total += 1; // git info row
+                    if self
+                        .selected_instance()
+                        .and_then(|inst| inst.git_context.as_ref())
+                        .is_some_and(|git| git.last_commit.is_some())
+                    {
+                        total += 1; // last commit row
+                    }
                     if self.pr_info.is_some() {
                         total += 1; // PR info row
                     }
@@ -1503,7 +6369,7 @@ impl App {
 
                 total
             }
-            _ => filtered_count,
+            _ => filtered_count + self.group_headers_through(filtered_count - 1),
         }
     }
 }
@@ -5,6 +5,19 @@
 
 use std::path::PathBuf;
 
+use super::palette::Command;
+use super::GitOperation;
+use crate::archive::ArchivedSession;
+use crate::git::{ConflictedFile, DiffLine, Issue, PendingOperation, SyncOutcome};
+use crate::i18n::tr;
+use crate::review::ReviewComment;
+use crate::schedule::ScheduledJob;
+use crate::search::SearchMatch;
+use crate::session::{PaneEntry, Session};
+use crate::snapshot::Snapshot;
+use crate::stats::Stats;
+use crate::worktime::BranchTotal;
+
 /// The current mode/state of the application
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum Mode {
@@ -28,18 +41,87 @@ pub enum Mode {
     },
     /// Renaming a session
     Rename { old_name: String, new_name: String },
+    /// Editing the tags of an instance's working directory
+    EditTags { path: PathBuf, input: String },
     /// Entering commit message
     Commit { message: String },
+    /// Choosing the base of an interactive rebase
+    Rebase {
+        /// Agent pane the rebase opens beside
+        pane_id: String,
+        /// Working directory the rebase runs in
+        path: PathBuf,
+        /// Branch being rebased
+        branch: String,
+        /// Commit or ref to rebase onto, `@{upstream}` by default
+        base: String,
+    },
+    /// Picking an issue to start a branch, worktree and session for
+    BranchFromIssue {
+        /// The main repository the worktree is added to
+        source_repo: PathBuf,
+        /// Open issues of the repository (empty without gh)
+        issues: Vec<Issue>,
+        /// Issue number, URL or ticket reference, also filtering `issues`
+        input: String,
+        /// Highlighted issue among the ones matching `input`
+        selected: usize,
+        /// Whether the issue is sent to Claude as its first prompt
+        send_prompt: bool,
+    },
+    /// Replicating a session's windows and panes into a new session
+    CloneLayout {
+        /// Session whose layout is copied
+        source_session: String,
+        /// Checkout the selected instance runs in, if it is a git repository
+        source_root: Option<PathBuf>,
+        /// Main repository new worktrees are created from
+        main_repo: Option<PathBuf>,
+        /// Name of the new session
+        name: String,
+        /// Branch for a new worktree; empty to reuse the same directories
+        branch: String,
+        /// Which field is active
+        field: CloneLayoutField,
+    },
+    /// Picking how to rearrange the panes of an instance's window
+    ArrangePanes {
+        /// Pane of the instance; window commands target its window
+        pane_id: String,
+        /// Highlighted entry of `PaneArrangement::ALL`
+        selected: usize,
+    },
+    /// Picking the session to move or link a window to
+    MoveWindow {
+        /// Window to move (`session:index`)
+        window: String,
+        /// Link the window instead of moving it
+        link: bool,
+        /// Sessions other than the window's own
+        sessions: Vec<String>,
+        /// Filter, or the name of a session to create
+        input: String,
+        /// Selected index among the filtered sessions
+        selected: usize,
+    },
     /// Creating a new session from a worktree
     NewWorktree {
         /// The source repository path (from selected session)
         source_repo: PathBuf,
-        /// All branches in the repository
+        /// All branches in the repository, local ones first, then
+        /// `remote_branches`
         all_branches: Vec<String>,
+        /// Remote-tracking branches without a local branch, as
+        /// (`remote/branch`, branch name)
+        remote_branches: Vec<(String, String)>,
         /// Branch name input (may be new or existing)
         branch_input: String,
         /// Selected index in filtered branches (None = creating new branch)
         selected_branch: Option<usize>,
+        /// Upstream to set on the branch (`remote/branch`, empty for none)
+        upstream: String,
+        /// Remote suggested for the upstream of a new branch
+        default_remote: Option<String>,
         /// Worktree path
         worktree_path: String,
         /// Session name
@@ -62,8 +144,218 @@ pub enum Mode {
         /// Which field is active
         field: CreatePullRequestField,
     },
+    /// Picking a slash command to send to the selected instance
+    SlashCommandMenu {
+        /// Currently highlighted command
+        selected: usize,
+    },
+    /// Scheduling a prompt for the selected instance
+    SchedulePrompt {
+        /// Prompt text to send
+        prompt: String,
+        /// When to send it (e.g., "9am", "15m")
+        when: String,
+        /// Which field is active
+        field: SchedulePromptField,
+    },
+    /// Picking a prompt template to send to the selected instance
+    TemplatePicker {
+        /// Currently highlighted template
+        selected: usize,
+    },
+    /// Picking a workspace to start
+    Workspaces {
+        /// Currently highlighted workspace
+        selected: usize,
+    },
+    /// Sending a file or the clipboard to the selected instance
+    SendFile {
+        /// File to send (empty = clipboard)
+        path: String,
+        /// Template to wrap the content in (index into config templates)
+        template: Option<usize>,
+        /// Path completion suggestions
+        path_suggestions: Vec<String>,
+        /// Currently selected path suggestion index
+        path_selected: Option<usize>,
+    },
+    /// Reviewing the selected instance's changes and writing comments
+    Review {
+        /// Flattened working tree diff
+        lines: Vec<DiffLine>,
+        /// Highlighted diff line
+        cursor: usize,
+        /// Comments written so far
+        comments: Vec<ReviewComment>,
+        /// Comment being edited for the cursor line, if any
+        input: Option<String>,
+    },
+    /// Viewing scheduled prompts waiting to be sent
+    PendingJobs {
+        /// Jobs loaded when the view was opened
+        jobs: Vec<ScheduledJob>,
+        /// Currently highlighted job
+        selected: usize,
+    },
+    /// Resolving the conflicts of a stopped merge or rebase
+    Conflicts {
+        /// Root of the checkout
+        root: PathBuf,
+        /// Files still conflicted
+        files: Vec<ConflictedFile>,
+        /// Currently highlighted file
+        selected: usize,
+        /// The stopped operation, continued once no conflicts remain
+        operation: Option<PendingOperation>,
+    },
+    /// Viewing archived sessions, to restore one
+    Archived {
+        /// Sessions loaded when the view was opened, most recent first
+        sessions: Vec<ArchivedSession>,
+        /// Currently highlighted session
+        selected: usize,
+    },
+    /// Viewing the pane snapshots taken when agents started waiting or
+    /// crashed
+    Snapshots {
+        /// Snapshots loaded when the view was opened, most recent first
+        snapshots: Vec<Snapshot>,
+        /// Currently highlighted snapshot
+        selected: usize,
+        /// Lines of the snapshot being read, None while picking one
+        content: Option<Vec<String>>,
+        /// Lines hidden below the view (0 shows the last)
+        scroll: usize,
+    },
+    /// Viewing the output of a custom action (`[[actions]]`)
+    ActionOutput {
+        /// Name of the action
+        name: String,
+        /// Instance it ran for (`session:window.pane`)
+        target: String,
+        /// Output, standard error mixed in
+        lines: Vec<String>,
+        state: ActionRun,
+        /// Lines hidden below the view (0 shows the last)
+        scroll: usize,
+    },
+    /// Viewing recent log lines
+    Logs {
+        /// Lines logged by this process, oldest first
+        lines: Vec<String>,
+        /// Lines hidden below the view (0 shows the newest)
+        scroll: usize,
+    },
+    /// Viewing how long agents worked on each branch
+    WorkTime {
+        /// Totals loaded when the view was opened, most recent first
+        totals: Vec<BranchTotal>,
+        /// Currently highlighted branch
+        selected: usize,
+    },
+    /// Viewing the usage statistics
+    Stats {
+        /// Statistics loaded when the view was opened
+        stats: Stats,
+    },
+    /// Inspecting and rebinding the keys of the session list
+    Keybindings {
+        /// Highlighted action, an index into `Action::ALL`
+        selected: usize,
+        /// Waiting for the new key of the highlighted action
+        capturing: bool,
+        /// Key pressed while capturing that another action already uses;
+        /// pressing it again swaps the two bindings
+        conflict: Option<char>,
+    },
+    /// Searching the content of every Claude pane
+    Search {
+        /// Text to look for
+        query: String,
+        /// Instances matching the last search, `None` until Enter runs it
+        results: Option<Vec<SearchMatch>>,
+        /// Currently highlighted result
+        selected: usize,
+    },
+    /// Picking any action by name in the command palette
+    Palette {
+        /// Text typed to narrow the commands down
+        input: String,
+        /// Commands offered when the palette was opened
+        commands: Vec<Command>,
+        /// Highlighted match, an index into the matches of `input`
+        selected: usize,
+    },
+    /// Viewing every tmux pane, to start Claude in one
+    AllPanes {
+        /// Panes listed when the view was opened
+        panes: Vec<PaneEntry>,
+        /// Currently highlighted pane
+        selected: usize,
+    },
+    /// Viewing every tmux session, to switch to, kill or rename one
+    Sessions {
+        /// Sessions listed when the view was opened
+        sessions: Vec<Session>,
+        /// Currently highlighted session
+        selected: usize,
+        /// Waiting for the highlighted session's kill to be confirmed
+        confirm_kill: bool,
+        /// New name being typed for the highlighted session
+        rename: Option<String>,
+        /// Quit instead of going back to the list when closed
+        /// (`claude-tmux sessions`)
+        quit_on_close: bool,
+    },
+    /// Asking for HTTPS credentials to retry a push, pull, or fetch
+    Credentials {
+        /// Operation to retry
+        operation: GitOperation,
+        /// Session the operation was started from
+        session: String,
+        /// Repository to run it in
+        path: PathBuf,
+        /// Remote URL that asked for authentication
+        url: String,
+        username: String,
+        /// Password or token, shown masked
+        password: String,
+        /// Save the credentials with `git credential approve` once they work
+        store: bool,
+        /// Which field is active
+        field: CredentialsField,
+        /// Whether the previously entered credentials were rejected
+        rejected: bool,
+    },
+    /// Asking for an SSH key passphrase to retry a push, pull, or fetch
+    Passphrase {
+        /// Operation to retry
+        operation: GitOperation,
+        /// Session the operation was started from
+        session: String,
+        /// Repository to run it in
+        path: PathBuf,
+        /// Encrypted private key file
+        key: PathBuf,
+        /// Passphrase, shown masked
+        passphrase: String,
+        /// Whether the previously entered passphrase was wrong
+        rejected: bool,
+    },
     /// Showing help
-    Help,
+    Help {
+        /// Lines scrolled past at the top
+        scroll: usize,
+    },
+    /// Showing why the config file could not be reloaded
+    ConfigError { error: String },
+    /// Syncing checkouts with their upstreams, then showing the results
+    Sync {
+        /// One row per checkout, in list order
+        rows: Vec<SyncRow>,
+        /// Currently highlighted row
+        selected: usize,
+    },
 }
 
 /// An action that can be performed on a session
@@ -76,28 +368,92 @@ pub enum SessionAction {
     Rename,
     /// Create a new session from a worktree
     NewWorktree,
+    /// Create a worktree and session for an issue
+    BranchFromIssue,
+    /// Replicate this session's layout into a new session
+    CloneLayout,
+    /// Swap, rotate, or re-layout the panes of this window
+    ArrangePanes,
+    /// Move this window to another session
+    MoveWindow,
+    /// Link this window into another session as well
+    LinkWindow,
+    /// Review uncommitted changes and send comments to the agent
+    Review,
     /// Stage all changes
     Stage,
     /// Commit staged changes
     Commit,
+    /// Open `git rebase -i` in a split beside the pane
+    InteractiveRebase,
     /// Push commits to remote
     Push,
     /// Push and set upstream branch
     PushSetUpstream,
     /// Fetch from remote (update tracking branches)
     Fetch,
+    /// Turn background fetches of the repository on (true) or off (false)
+    ToggleAutoFetch(bool),
     /// Pull commits from remote
     Pull,
+    /// Replace the remote branch with a rewritten one (with lease)
+    ForcePush,
+    /// Rebase local commits onto a diverged upstream
+    RebaseOnUpstream,
+    /// Merge a diverged upstream into the branch
+    MergeUpstream,
+    /// List conflicted files to take a side, edit, or mark them resolved
+    ResolveConflicts,
+    /// Continue a merge or rebase once its conflicts are resolved
+    ContinueOperation(PendingOperation),
+    /// Abort a merge or rebase, restoring the branch
+    AbortOperation(PendingOperation),
+    /// Answer a waiting agent or tell an idle one to continue
+    Continue,
+    /// Interrupt a working agent (Escape)
+    Interrupt,
+    /// Restart a crashed agent with `--continue`
+    Restart,
+    /// Stop tracking a crashed agent (e.g., Claude was exited on purpose)
+    DismissCrash,
+    /// Restart the pane in the main repository after its worktree was deleted
+    RespawnInMainRepo,
+    /// Send a Claude Code slash command
+    SlashCommand,
+    /// Send a prompt from the template library
+    SendTemplate,
+    /// Schedule a prompt to be sent later
+    SchedulePrompt,
+    /// Send a file or the clipboard as a prompt
+    SendFile,
+    /// Start another Claude in the same directory, in a new window
+    DuplicateWindow,
+    /// Start another Claude in the same directory, in a split
+    DuplicateSplit,
+    /// Open a shell in a split beside the pane, in its working directory
+    SplitShell,
+    /// Open the editor in a split beside the pane, in its working directory
+    SplitEditor,
     /// Create a pull request
     CreatePullRequest,
     /// View pull request in browser
     ViewPullRequest,
+    /// View the pull request's CI checks in browser
+    ViewChecks,
     /// Close pull request without merging
     ClosePullRequest,
     /// Merge pull request
     MergePullRequest,
     /// Merge PR, delete branch, remove worktree, kill session
     MergePullRequestAndClose,
+    /// Pull the pane into a split of the current window
+    JoinPane,
+    /// Return a joined pane to the window it came from
+    SendPaneBack,
+    /// Start (true) or stop (false) logging the pane's output to a file
+    TogglePaneLog(bool),
+    /// Record this session in the archive, then kill it
+    Archive,
     /// Kill this session
     Kill,
     /// Kill session and delete its worktree
@@ -108,34 +464,70 @@ impl SessionAction {
     /// Returns the display label for this action
     pub fn label(&self) -> &'static str {
         match self {
-            Self::SwitchTo => "Switch to session",
-            Self::Rename => "Rename session",
-            Self::NewWorktree => "New session from worktree",
-            Self::Stage => "Stage all changes",
-            Self::Commit => "Commit staged changes",
-            Self::Push => "Push to remote",
-            Self::PushSetUpstream => "Push and set upstream",
-            Self::Fetch => "Fetch from remote",
-            Self::Pull => "Pull from remote",
-            Self::CreatePullRequest => "Create pull request",
-            Self::ViewPullRequest => "View pull request",
-            Self::ClosePullRequest => "Close pull request",
-            Self::MergePullRequest => "Merge pull request",
-            Self::MergePullRequestAndClose => "Merge PR + close session",
-            Self::Kill => "Kill session",
-            Self::KillAndDeleteWorktree => "Kill session + delete worktree",
+            Self::SwitchTo => tr("Switch to session"),
+            Self::Rename => tr("Rename session"),
+            Self::NewWorktree => tr("New session from worktree"),
+            Self::BranchFromIssue => tr("New session from issue"),
+            Self::CloneLayout => tr("Clone session layout"),
+            Self::ArrangePanes => tr("Rearrange panes"),
+            Self::MoveWindow => tr("Move window to session"),
+            Self::LinkWindow => tr("Link window into session"),
+            Self::Review => tr("Review changes"),
+            Self::Stage => tr("Stage all changes"),
+            Self::Commit => tr("Commit staged changes"),
+            Self::InteractiveRebase => tr("Interactive rebase"),
+            Self::Push => tr("Push to remote"),
+            Self::PushSetUpstream => tr("Push and set upstream"),
+            Self::Fetch => tr("Fetch from remote"),
+            Self::ToggleAutoFetch(true) => tr("Turn on auto-fetch"),
+            Self::ToggleAutoFetch(false) => tr("Turn off auto-fetch"),
+            Self::Pull => tr("Pull from remote"),
+            Self::ForcePush => tr("Force push"),
+            Self::RebaseOnUpstream => tr("Rebase onto upstream"),
+            Self::MergeUpstream => tr("Merge upstream"),
+            Self::ResolveConflicts => tr("Resolve conflicts"),
+            Self::ContinueOperation(PendingOperation::Merge) => tr("Continue merge"),
+            Self::ContinueOperation(PendingOperation::Rebase) => tr("Continue rebase"),
+            Self::AbortOperation(PendingOperation::Merge) => tr("Abort merge"),
+            Self::AbortOperation(PendingOperation::Rebase) => tr("Abort rebase"),
+            Self::Continue => tr("Continue"),
+            Self::Interrupt => tr("Interrupt"),
+            Self::Restart => tr("Restart with --continue"),
+            Self::DismissCrash => tr("Dismiss (exited on purpose)"),
+            Self::RespawnInMainRepo => tr("Respawn pane in main repo"),
+            Self::SlashCommand => tr("Send slash command"),
+            Self::SendTemplate => tr("Send prompt template"),
+            Self::SchedulePrompt => tr("Schedule prompt"),
+            Self::SendFile => tr("Send file or clipboard"),
+            Self::DuplicateWindow => tr("Duplicate agent in new window"),
+            Self::DuplicateSplit => tr("Duplicate agent in split"),
+            Self::SplitShell => tr("Open shell in split"),
+            Self::SplitEditor => tr("Open editor in split"),
+            Self::CreatePullRequest => tr("Create pull request"),
+            Self::ViewPullRequest => tr("View pull request"),
+            Self::ViewChecks => tr("View CI checks"),
+            Self::ClosePullRequest => tr("Close pull request"),
+            Self::MergePullRequest => tr("Merge pull request"),
+            Self::MergePullRequestAndClose => tr("Merge PR + close session"),
+            Self::JoinPane => tr("Join pane into current window"),
+            Self::SendPaneBack => tr("Send pane back"),
+            Self::TogglePaneLog(true) => tr("Start logging pane to file"),
+            Self::TogglePaneLog(false) => tr("Stop logging pane"),
+            Self::Archive => tr("Archive session"),
+            Self::Kill => tr("Kill session"),
+            Self::KillAndDeleteWorktree => tr("Kill session + delete worktree"),
         }
     }
 
-    /// Whether this action requires confirmation
+    /// Whether this action requires confirmation whatever `[confirm]` says
     pub fn requires_confirmation(&self) -> bool {
         matches!(
             self,
-            Self::Kill
-                | Self::KillAndDeleteWorktree
+            Self::Archive
                 | Self::ClosePullRequest
                 | Self::MergePullRequest
                 | Self::MergePullRequestAndClose
+                | Self::AbortOperation(_)
         )
     }
 }
@@ -147,10 +539,54 @@ pub enum NewSessionField {
     Path,
 }
 
+/// A way to rearrange the panes of a window
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PaneArrangement {
+    /// Swap the pane with the next one (`swap-pane -D`)
+    SwapNext,
+    /// Swap the pane with the previous one (`swap-pane -U`)
+    SwapPrevious,
+    /// Rotate the window's panes (`rotate-window`)
+    Rotate,
+    /// Apply a preset layout (`select-layout`)
+    Layout(&'static str),
+}
+
+impl PaneArrangement {
+    /// Every arrangement, in picker order
+    pub const ALL: [Self; 8] = [
+        Self::SwapNext,
+        Self::SwapPrevious,
+        Self::Rotate,
+        Self::Layout("even-horizontal"),
+        Self::Layout("even-vertical"),
+        Self::Layout("main-horizontal"),
+        Self::Layout("main-vertical"),
+        Self::Layout("tiled"),
+    ];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            Self::SwapNext => tr("Swap with next pane"),
+            Self::SwapPrevious => tr("Swap with previous pane"),
+            Self::Rotate => tr("Rotate panes"),
+            Self::Layout(layout) => layout,
+        }
+    }
+}
+
+/// Which field is active in the clone layout dialog
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CloneLayoutField {
+    Name,
+    Branch,
+}
+
 /// Which field is active in the new worktree dialog
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum NewWorktreeField {
     Branch,
+    Upstream,
     Path,
     SessionName,
 }
@@ -162,3 +598,62 @@ pub enum CreatePullRequestField {
     Body,
     BaseBranch,
 }
+
+/// Which field is active in the credentials dialog
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CredentialsField {
+    Username,
+    Password,
+    Store,
+}
+
+impl CredentialsField {
+    /// The field Tab moves to
+    pub fn next(self) -> Self {
+        match self {
+            Self::Username => Self::Password,
+            Self::Password => Self::Store,
+            Self::Store => Self::Username,
+        }
+    }
+}
+
+/// Which field is active in the schedule prompt dialog
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SchedulePromptField {
+    Prompt,
+    When,
+}
+
+/// A checkout in the sync summary
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SyncRow {
+    /// Session of the first instance listed in the checkout
+    pub session: String,
+    /// Working directory of the checkout
+    pub path: PathBuf,
+    /// Shortened path and branch
+    pub label: String,
+    pub state: SyncState,
+}
+
+/// Progress or result of syncing one checkout
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SyncState {
+    /// Waiting for its turn, or being synced
+    Pending,
+    /// Left alone, with the reason why
+    Skipped(String),
+    Done(SyncOutcome),
+    Failed(String),
+}
+
+/// Progress of a custom action
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ActionRun {
+    Running,
+    /// Exited with this status code
+    Exited(i32),
+    /// Could not be started, or was killed by a signal
+    Failed(String),
+}
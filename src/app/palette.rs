@@ -0,0 +1,204 @@
+//! Command palette registry
+//!
+//! The palette offers every action of the session list, plus the action
+//! menu entries available for the selected instance, under one fuzzy
+//! search. Keyed actions that the menu also offers are listed once, as the
+//! menu entry.
+
+use crate::config::Action;
+
+use super::SessionAction;
+
+/// An entry of the command palette
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Command {
+    /// A session list action, run as if its key had been pressed
+    Key(Action),
+    /// An action menu entry for the selected instance
+    Menu(SessionAction),
+}
+
+impl Command {
+    /// Returns the display label for this command
+    pub fn label(&self) -> &'static str {
+        match self {
+            Self::Key(action) => action.label(),
+            Self::Menu(action) => action.label(),
+        }
+    }
+}
+
+/// Every command offered for an instance with the `menu` actions available
+pub fn commands(menu: &[SessionAction]) -> Vec<Command> {
+    let mut commands: Vec<Command> = menu.iter().cloned().map(Command::Menu).collect();
+    commands.extend(
+        Action::ALL
+            .into_iter()
+            .filter(|&action| !is_navigation(action))
+            .filter(|&action| !offered_by_menu(action, menu))
+            .map(Command::Key),
+    );
+    commands
+}
+
+/// Keys that move around the list or open a menu, which make no sense as
+/// commands
+fn is_navigation(action: Action) -> bool {
+    matches!(
+        action,
+        Action::SelectNext
+            | Action::SelectPrev
+            | Action::OpenMenu
+            | Action::Upstream
+            | Action::Palette
+    )
+}
+
+/// Whether the menu offers what a keyed action does
+fn offered_by_menu(action: Action, menu: &[SessionAction]) -> bool {
+    match menu_equivalent(action) {
+        // Pushing a branch without upstream sets it
+        Some(SessionAction::Push) => menu
+            .iter()
+            .any(|m| matches!(m, SessionAction::Push | SessionAction::PushSetUpstream)),
+        Some(equivalent) => menu.contains(&equivalent),
+        None => false,
+    }
+}
+
+/// Action menu entry doing what a keyed action does
+fn menu_equivalent(action: Action) -> Option<SessionAction> {
+    Some(match action {
+        Action::Switch => SessionAction::SwitchTo,
+        Action::Kill => SessionAction::Kill,
+        Action::Rename => SessionAction::Rename,
+        Action::Duplicate => SessionAction::DuplicateWindow,
+        Action::Continue => SessionAction::Continue,
+        Action::Interrupt => SessionAction::Interrupt,
+        Action::SlashCommand => SessionAction::SlashCommand,
+        Action::Template => SessionAction::SendTemplate,
+        Action::SendFile => SessionAction::SendFile,
+        Action::Schedule => SessionAction::SchedulePrompt,
+        Action::Review => SessionAction::Review,
+        Action::Stage => SessionAction::Stage,
+        Action::Commit => SessionAction::Commit,
+        Action::Push => SessionAction::Push,
+        Action::Pull => SessionAction::Pull,
+        Action::Fetch => SessionAction::Fetch,
+        Action::NewWorktree => SessionAction::NewWorktree,
+        _ => return None,
+    })
+}
+
+/// Commands matching `query`, best first, with the positions of the
+/// matched characters in their labels
+///
+/// Ties keep the order of `commands`, so an empty query lists them as is.
+pub fn matches<'a>(query: &str, commands: &'a [Command]) -> Vec<(&'a Command, Vec<usize>)> {
+    let mut found: Vec<_> = commands
+        .iter()
+        .filter_map(|command| {
+            fuzzy_match(query, command.label())
+                .map(|(score, positions)| (score, command, positions))
+        })
+        .collect();
+    found.sort_by_key(|&(score, _, _)| std::cmp::Reverse(score));
+    found
+        .into_iter()
+        .map(|(_, command, positions)| (command, positions))
+        .collect()
+}
+
+/// Fuzzy match of `query` against `text`, ignoring case
+///
+/// Every character of the query must appear in order. Returns a score
+/// (higher is better), which favors consecutive characters and word
+/// starts, and the positions of the matched characters in `text`.
+pub fn fuzzy_match(query: &str, text: &str) -> Option<(i64, Vec<usize>)> {
+    let text: Vec<char> = text.chars().collect();
+    let mut positions = Vec::new();
+    let mut score = 0;
+    let mut next = 0;
+
+    for q in query.chars().filter(|c| !c.is_whitespace()) {
+        let q = q.to_lowercase().next().unwrap_or(q);
+        let found = (next..text.len()).find(|&i| text[i].to_lowercase().next() == Some(q))?;
+
+        let word_start = found == 0 || !text[found - 1].is_alphanumeric();
+        let consecutive = positions.last().is_some_and(|&last| last + 1 == found);
+        score += 1;
+        if word_start {
+            score += 8;
+        }
+        if consecutive {
+            score += 5;
+        }
+        // Gaps cost a little, so tighter matches rank first
+        score -= (found - next).min(10) as i64;
+
+        positions.push(found);
+        next = found + 1;
+    }
+
+    Some((score, positions))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_fuzzy_match() {
+        assert_eq!(fuzzy_match("", "Push to remote"), Some((0, vec![])));
+        assert_eq!(
+            fuzzy_match("psh", "Push to remote").map(|(_, p)| p),
+            Some(vec![0, 2, 3])
+        );
+        assert_eq!(fuzzy_match("PUSH", "push").map(|(_, p)| p.len()), Some(4));
+        assert_eq!(fuzzy_match("hsup", "Push"), None);
+
+        // Word starts and consecutive letters rank higher
+        let score = |q, t| fuzzy_match(q, t).unwrap().0;
+        assert!(score("nw", "New session from worktree") > score("nw", "Rename window"));
+        assert!(score("st", "Stage all changes") > score("st", "Restart with --continue"));
+    }
+
+    #[test]
+    fn test_matches() {
+        let commands = [
+            Command::Key(Action::Help),
+            Command::Menu(SessionAction::Push),
+            Command::Menu(SessionAction::Pull),
+        ];
+        let labels = |query| -> Vec<&str> {
+            matches(query, &commands)
+                .into_iter()
+                .map(|(command, _)| command.label())
+                .collect()
+        };
+
+        assert_eq!(labels("").len(), 3);
+        assert_eq!(labels("pul"), ["Pull from remote"]);
+        assert!(labels("zzz").is_empty());
+    }
+
+    #[test]
+    fn test_commands() {
+        let menu = [SessionAction::SwitchTo, SessionAction::PushSetUpstream];
+        let commands = commands(&menu);
+
+        assert_eq!(
+            commands[..2],
+            [
+                Command::Menu(menu[0].clone()),
+                Command::Menu(menu[1].clone())
+            ]
+        );
+        assert!(!commands.contains(&Command::Key(Action::Switch)));
+        assert!(!commands.contains(&Command::Key(Action::Push)));
+        assert!(!commands.contains(&Command::Key(Action::SelectNext)));
+        // Keyed actions the menu doesn't offer right now stay available
+        assert!(commands.contains(&Command::Key(Action::Pull)));
+        assert!(commands.contains(&Command::Key(Action::Help)));
+    }
+}
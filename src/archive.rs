@@ -0,0 +1,280 @@
+//! Archived sessions
+//!
+//! Archiving records what is needed to bring a session back (its name,
+//! working directory, branch, the flags Claude was started with, and the id of
+//! its latest conversation transcript) in a tab-separated file in the data
+//! directory, then kills the session. Restoring starts a new session in the
+//! same directory with `claude --resume <transcript>`.
+//!
+//! The summary Claude writes into a transcript is also read from here, to
+//! suggest pull request titles.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use anyhow::Result;
+
+use crate::config::shell_quote;
+use crate::json::json_field;
+use crate::store::{self, data_dir, escape_field, now_unix, unescape_field};
+
+/// A session that was archived and can be restored
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ArchivedSession {
+    /// Unique ID (archive timestamp in milliseconds)
+    pub id: u64,
+    /// Unix timestamp (seconds) when the session was archived
+    pub archived_at: u64,
+    /// Session name
+    pub name: String,
+    /// Working directory of the Claude pane
+    pub path: PathBuf,
+    /// Branch checked out at archive time
+    pub branch: Option<String>,
+    /// Claude's command-line flags, shell-quoted (without `--resume`/`--continue`)
+    pub flags: String,
+    /// Conversation transcript to resume
+    pub transcript: Option<String>,
+}
+
+impl ArchivedSession {
+    /// Shell command that restarts Claude in this session's conversation
+    pub fn restore_command(&self) -> String {
+        let mut command = "claude".to_string();
+        if !self.flags.is_empty() {
+            command.push(' ');
+            command.push_str(&self.flags);
+        }
+        match &self.transcript {
+            Some(id) => command.push_str(&format!(" --resume {}", shell_quote(id))),
+            None => command.push_str(" --continue"),
+        }
+        command
+    }
+}
+
+fn archive_path() -> PathBuf {
+    data_dir().join("archive.tsv")
+}
+
+/// Load archived sessions, most recent first
+pub fn load_archived() -> Vec<ArchivedSession> {
+    let Ok(content) = fs::read_to_string(archive_path()) else {
+        return Vec::new();
+    };
+
+    let mut sessions: Vec<ArchivedSession> =
+        content.lines().filter_map(parse_archive_line).collect();
+    sessions.sort_by_key(|s| std::cmp::Reverse((s.archived_at, s.id)));
+    sessions
+}
+
+fn save_archived(sessions: &[ArchivedSession]) -> Result<()> {
+    let content: String = sessions.iter().map(format_archive_line).collect();
+    store::write_atomic(&archive_path(), &content, "archive")
+}
+
+/// Record a session in the archive
+pub fn add(session: ArchivedSession) -> Result<()> {
+    let mut sessions = load_archived();
+    sessions.push(session);
+    save_archived(&sessions)
+}
+
+/// Remove an archived session by ID
+pub fn remove(id: u64) -> Result<()> {
+    let mut sessions = load_archived();
+    let before = sessions.len();
+    sessions.retain(|s| s.id != id);
+    if sessions.len() == before {
+        anyhow::bail!("Archived session {} not found", id);
+    }
+    save_archived(&sessions)
+}
+
+/// Build the archive entry for a session whose Claude runs in `path` with
+/// command line `claude_args`
+pub fn describe(
+    name: &str,
+    path: &Path,
+    branch: Option<String>,
+    claude_args: &[String],
+) -> ArchivedSession {
+    let now = now_unix();
+    ArchivedSession {
+        id: std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .map(|d| d.as_millis() as u64)
+            .unwrap_or(now * 1000),
+        archived_at: now,
+        name: name.to_string(),
+        path: path.to_path_buf(),
+        branch,
+        flags: restore_flags(claude_args),
+        transcript: latest_transcript(path),
+    }
+}
+
+/// The flags of a Claude command line (`node …/bin/claude --model opus -c`),
+/// shell-quoted, without the ones choosing which conversation to resume
+fn restore_flags(args: &[String]) -> String {
+    let mut flags = Vec::new();
+    let mut rest = args
+        .iter()
+        .skip_while(|a| !a.contains("bin/claude"))
+        .skip(1)
+        .peekable();
+    while let Some(arg) = rest.next() {
+        match arg.as_str() {
+            "-c" | "--continue" => {}
+            "-r" | "--resume" => {
+                // The conversation id is optional
+                rest.next_if(|next| !next.starts_with('-'));
+            }
+            _ if arg.starts_with("--resume=") => {}
+            _ => flags.push(shell_quote(arg)),
+        }
+    }
+    flags.join(" ")
+}
+
+/// Directory where Claude keeps the transcripts of conversations started in
+/// `path`: `~/.claude/projects/` plus the path with every character other
+/// than letters and digits replaced by `-`
+fn transcripts_dir(path: &Path) -> Option<PathBuf> {
+    let config_dir = std::env::var_os("CLAUDE_CONFIG_DIR")
+        .map(PathBuf::from)
+        .or_else(|| dirs::home_dir().map(|h| h.join(".claude")))?;
+    Some(config_dir.join("projects").join(project_dir_name(path)))
+}
+
+fn project_dir_name(path: &Path) -> String {
+    path.to_string_lossy()
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() { c } else { '-' })
+        .collect()
+}
+
+/// Id of the most recently written transcript for `path`
+fn latest_transcript(path: &Path) -> Option<String> {
+    latest_transcript_path(path)
+        .and_then(|path| Some(path.file_stem()?.to_string_lossy().to_string()))
+}
+
+fn latest_transcript_path(path: &Path) -> Option<PathBuf> {
+    fs::read_dir(transcripts_dir(path)?)
+        .ok()?
+        .flatten()
+        .filter(|e| e.path().extension().is_some_and(|ext| ext == "jsonl"))
+        .filter_map(|e| Some((e.metadata().ok()?.modified().ok()?, e.path())))
+        .max_by_key(|(modified, _)| *modified)
+        .map(|(_, path)| path)
+}
+
+/// Summary Claude wrote for the latest conversation in `path`, if any
+///
+/// Transcripts are JSON lines; summaries are lines of type `summary`.
+pub fn transcript_summary(path: &Path) -> Option<String> {
+    let content = fs::read_to_string(latest_transcript_path(path)?).ok()?;
+    content
+        .lines()
+        .rev()
+        .filter(|line| line.contains("\"type\":\"summary\""))
+        .find_map(|line| json_field(line, "summary"))
+}
+
+fn format_archive_line(session: &ArchivedSession) -> String {
+    format!(
+        "{}\t{}\t{}\t{}\t{}\t{}\t{}\n",
+        session.id,
+        session.archived_at,
+        escape_field(&session.name),
+        escape_field(&session.path.to_string_lossy()),
+        escape_field(session.branch.as_deref().unwrap_or("")),
+        session.transcript.as_deref().unwrap_or(""),
+        escape_field(&session.flags)
+    )
+}
+
+fn parse_archive_line(line: &str) -> Option<ArchivedSession> {
+    let parts: Vec<&str> = line.splitn(7, '\t').collect();
+    if parts.len() < 7 {
+        return None;
+    }
+    let optional = |s: &str| (!s.is_empty()).then(|| unescape_field(s));
+    Some(ArchivedSession {
+        id: parts[0].parse().ok()?,
+        archived_at: parts[1].parse().ok()?,
+        name: unescape_field(parts[2]),
+        path: PathBuf::from(unescape_field(parts[3])),
+        branch: optional(parts[4]),
+        transcript: optional(parts[5]),
+        flags: unescape_field(parts[6]),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(s: &str) -> Vec<String> {
+        s.split(' ').map(str::to_string).collect()
+    }
+
+    #[test]
+    fn test_restore_flags() {
+        assert_eq!(
+            restore_flags(&args(
+                "node /usr/lib/node_modules/bin/claude --model opus -c --append-system-prompt be\tterse"
+            )),
+            "--model opus --append-system-prompt 'be\tterse'"
+        );
+        assert_eq!(
+            restore_flags(&args(
+                "/home/u/.local/bin/claude --resume abc-123 --verbose"
+            )),
+            "--verbose"
+        );
+        assert_eq!(restore_flags(&args("/bin/claude -r --debug")), "--debug");
+        assert_eq!(restore_flags(&args("bash")), "");
+    }
+
+    #[test]
+    fn test_project_dir_name() {
+        assert_eq!(
+            project_dir_name(Path::new("/home/u/src/my.app_v2")),
+            "-home-u-src-my-app-v2"
+        );
+    }
+
+    #[test]
+    fn test_archive_line_roundtrip() {
+        let session = ArchivedSession {
+            id: 1_704_096_000_123,
+            archived_at: 1_704_096_000,
+            name: "api\tfix".to_string(),
+            path: PathBuf::from("/work/api"),
+            branch: Some("feature/login".to_string()),
+            flags: "--model opus".to_string(),
+            transcript: Some("0b4f-uuid".to_string()),
+        };
+        let line = format_archive_line(&session);
+        assert_eq!(
+            parse_archive_line(line.trim_end_matches('\n')),
+            Some(session.clone())
+        );
+
+        let bare = ArchivedSession {
+            branch: None,
+            transcript: None,
+            flags: String::new(),
+            ..session
+        };
+        let line = format_archive_line(&bare);
+        assert_eq!(
+            parse_archive_line(line.trim_end_matches('\n')),
+            Some(bare.clone())
+        );
+        assert_eq!(bare.restore_command(), "claude --continue");
+    }
+}
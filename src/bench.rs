@@ -0,0 +1,160 @@
+//! Refresh benchmark (`claude-tmux bench [iterations]`)
+//!
+//! Runs the refresh pipeline against the live tmux server and reports how
+//! long each stage takes:
+//!
+//! - `list`: list panes and find the ones running Claude
+//! - `status`: capture those panes and detect their status
+//! - `git`: detect the git context of each working directory
+//!
+//! The first iteration includes connecting the control-mode client and
+//! filling the process cache, so look at the median for steady-state cost.
+
+use std::collections::HashSet;
+use std::path::PathBuf;
+use std::time::{Duration, Instant};
+
+use anyhow::{Context, Result};
+
+use crate::config;
+use crate::git::GitContext;
+use crate::session::ClaudeCodeStatus;
+use crate::tmux::Tmux;
+
+/// Iterations run when none are given
+const DEFAULT_ITERATIONS: usize = 10;
+
+/// Timings of one stage across iterations
+#[derive(Debug)]
+struct Stage {
+    name: &'static str,
+    samples: Vec<Duration>,
+}
+
+impl Stage {
+    fn new(name: &'static str) -> Self {
+        Self {
+            name,
+            samples: Vec::new(),
+        }
+    }
+
+    /// (min, median, max) of the samples
+    fn summary(&self) -> Option<(Duration, Duration, Duration)> {
+        let mut sorted = self.samples.clone();
+        sorted.sort();
+        let min = *sorted.first()?;
+        let max = *sorted.last()?;
+        Some((min, sorted[sorted.len() / 2], max))
+    }
+}
+
+/// Run the benchmark; `args` are the arguments after `bench`
+pub fn run(args: &[String]) -> Result<()> {
+    let iterations = match args.first() {
+        None => DEFAULT_ITERATIONS,
+        Some(arg) => arg
+            .parse::<usize>()
+            .ok()
+            .filter(|n| *n > 0)
+            .with_context(|| format!("Invalid iteration count '{}'", arg))?,
+    };
+
+    let config = config::load_for_subcommand("bench");
+
+    let mut list = Stage::new("list");
+    let mut status = Stage::new("status");
+    let mut git = Stage::new("git");
+    let mut total = Stage::new("total");
+    let mut instance_count = 0;
+    let mut repo_count = 0;
+
+    for _ in 0..iterations {
+        let started = Instant::now();
+        let instances = Tmux::list_claude_panes()?;
+        list.samples.push(started.elapsed());
+
+        let stage_started = Instant::now();
+        let pane_ids: Vec<String> = instances
+            .iter()
+            .filter(|i| i.status != ClaudeCodeStatus::Crashed)
+            .map(|i| i.pane_id.clone())
+            .collect();
+        Tmux::capture_statuses(&pane_ids);
+        status.samples.push(stage_started.elapsed());
+
+        let stage_started = Instant::now();
+        let dirs: HashSet<PathBuf> = instances
+            .iter()
+            .map(|i| i.working_directory.clone())
+            .collect();
+        repo_count = dirs
+            .iter()
+            .filter(|dir| GitContext::detect(dir).is_some())
+            .count();
+        git.samples.push(stage_started.elapsed());
+
+        total.samples.push(started.elapsed());
+        instance_count = instances.len();
+    }
+
+    println!(
+        "{} iterations, {} instances, {} git repositories, control mode {}",
+        iterations,
+        instance_count,
+        repo_count,
+        if config.tmux_control_mode {
+            "on"
+        } else {
+            "off"
+        }
+    );
+    println!();
+    println!(
+        "{:<8} {:>10} {:>10} {:>10}",
+        "stage", "min", "median", "max"
+    );
+    for stage in [&list, &status, &git, &total] {
+        if let Some((min, median, max)) = stage.summary() {
+            println!(
+                "{:<8} {:>10} {:>10} {:>10}",
+                stage.name,
+                format_duration(min),
+                format_duration(median),
+                format_duration(max)
+            );
+        }
+    }
+
+    Ok(())
+}
+
+/// Format a duration in milliseconds with two decimals, e.g. "12.34ms"
+fn format_duration(d: Duration) -> String {
+    format!("{:.2}ms", d.as_secs_f64() * 1000.0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_stage_summary() {
+        let mut stage = Stage::new("list");
+        assert!(stage.summary().is_none());
+
+        stage.samples = [30, 10, 20, 50]
+            .iter()
+            .map(|ms| Duration::from_millis(*ms))
+            .collect();
+        assert_eq!(
+            stage.summary(),
+            Some((
+                Duration::from_millis(10),
+                Duration::from_millis(30),
+                Duration::from_millis(50)
+            ))
+        );
+        assert_eq!(format_duration(Duration::from_micros(12_345)), "12.35ms");
+    }
+}
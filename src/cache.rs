@@ -0,0 +1,96 @@
+//! Last-known instance list
+//!
+//! The instance list is saved after each full load so that the next start
+//! can paint it immediately, before tmux is queried. Cached rows are replaced
+//! as soon as fresh data arrives.
+
+use std::fs;
+use std::path::PathBuf;
+
+use anyhow::Result;
+
+use crate::session::{ClaudeCodeStatus, ClaudeInstance};
+use crate::store::{self, data_dir, escape_field, unescape_field};
+
+fn cache_path() -> PathBuf {
+    data_dir().join("instances.tsv")
+}
+
+/// Load the instances saved by the previous run (empty if none)
+pub fn load_instances() -> Vec<ClaudeInstance> {
+    let Ok(content) = fs::read_to_string(cache_path()) else {
+        return Vec::new();
+    };
+    content.lines().filter_map(parse_instance_line).collect()
+}
+
+/// Save the instance list for the next start
+pub fn save_instances(instances: &[ClaudeInstance]) -> Result<()> {
+    let content: String = instances.iter().map(format_instance_line).collect();
+
+    store::write_atomic(&cache_path(), &content, "instance cache")
+}
+
+fn format_instance_line(instance: &ClaudeInstance) -> String {
+    format!(
+        "{}\t{}\t{}\t{}\t{}\t{}\t{}\t{}\n",
+        instance.pane_id,
+        escape_field(&instance.session_name),
+        u8::from(instance.session_attached),
+        instance.window_index,
+        escape_field(&instance.window_name),
+        instance.pane_index,
+        instance.status.label(),
+        escape_field(&instance.working_directory.to_string_lossy()),
+    )
+}
+
+fn parse_instance_line(line: &str) -> Option<ClaudeInstance> {
+    let parts: Vec<&str> = line.splitn(8, '\t').collect();
+    if parts.len() < 8 {
+        return None;
+    }
+    Some(ClaudeInstance {
+        pane_id: parts[0].to_string(),
+        session_name: unescape_field(parts[1]),
+        session_attached: parts[2] == "1",
+        window_index: parts[3].parse().ok()?,
+        window_name: unescape_field(parts[4]),
+        pane_index: parts[5].parse().ok()?,
+        status: ClaudeCodeStatus::from_label(parts[6]),
+        working_directory: PathBuf::from(unescape_field(parts[7])),
+        pane_path: None,
+        git_context: None,
+        logging: false,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::session::test_instance;
+
+    #[test]
+    fn test_instance_line_roundtrip() {
+        let instance = ClaudeInstance {
+            session_attached: true,
+            window_index: 2,
+            window_name: "claude\tmain".to_string(),
+            pane_index: 1,
+            working_directory: PathBuf::from("/work/api"),
+            ..test_instance("%7", ClaudeCodeStatus::WaitingInput)
+        };
+
+        let parsed = parse_instance_line(format_instance_line(&instance).trim_end()).unwrap();
+        assert_eq!(parsed.pane_id, "%7");
+        assert_eq!(parsed.session_name, "api");
+        assert!(parsed.session_attached);
+        assert_eq!(parsed.window_index, 2);
+        assert_eq!(parsed.window_name, "claude\tmain");
+        assert_eq!(parsed.pane_index, 1);
+        assert_eq!(parsed.status, ClaudeCodeStatus::WaitingInput);
+        assert_eq!(parsed.working_directory, PathBuf::from("/work/api"));
+
+        assert!(parse_instance_line("%1\tapi").is_none());
+    }
+}
@@ -0,0 +1,38 @@
+//! System clipboard access
+//!
+//! Shells out to whichever clipboard tool is available, falling back to the
+//! most recent tmux paste buffer when none is installed.
+
+use std::process::Command;
+
+use anyhow::Result;
+
+use crate::tmux::Tmux;
+
+/// Clipboard readers tried in order: (program, args)
+const READERS: &[(&str, &[&str])] = &[
+    ("pbpaste", &[]),
+    ("wl-paste", &["--no-newline"]),
+    ("xclip", &["-selection", "clipboard", "-o"]),
+    ("xsel", &["--clipboard", "--output"]),
+];
+
+/// Read the clipboard contents as text
+pub fn read() -> Result<String> {
+    for (program, args) in READERS {
+        let Ok(output) = Command::new(program).args(*args).output() else {
+            continue; // Not installed
+        };
+        if !output.status.success() {
+            continue;
+        }
+        return String::from_utf8(output.stdout)
+            .map_err(|_| anyhow::anyhow!("Clipboard does not contain text"));
+    }
+
+    if let Some(text) = Tmux::show_buffer() {
+        return Ok(text);
+    }
+
+    anyhow::bail!("No clipboard tool found (tried pbpaste, wl-paste, xclip, xsel, tmux)")
+}
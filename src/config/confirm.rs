@@ -0,0 +1,53 @@
+//! Confirmation of destructive actions
+//!
+//! Killing a session, deleting a worktree and force-pushing each ask for a
+//! yes/no confirmation first. Each prompt can be turned off in the
+//! `[confirm]` table:
+//!
+//! ```toml
+//! [confirm]
+//! kill = false
+//! ```
+
+use anyhow::Result;
+
+use super::{optional_bool, Table};
+
+/// Which destructive actions ask for confirmation (`[confirm]`)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Confirmations {
+    /// Killing a session (`kill`)
+    pub kill: bool,
+    /// Killing a session and deleting its worktree (`delete_worktree`)
+    pub delete_worktree: bool,
+    /// Force-pushing a branch (`force_push`)
+    pub force_push: bool,
+}
+
+impl Default for Confirmations {
+    fn default() -> Self {
+        Self {
+            kill: true,
+            delete_worktree: true,
+            force_push: true,
+        }
+    }
+}
+
+impl Confirmations {
+    const KEYS: [&'static str; 3] = ["kill", "delete_worktree", "force_push"];
+
+    /// Parse the `[confirm]` table, confirming what it doesn't mention
+    pub(super) fn from_table(table: &Table) -> Result<Self> {
+        if let Some(key) = table.keys().find(|k| !Self::KEYS.contains(&k.as_str())) {
+            anyhow::bail!("unknown action '{}'", key);
+        }
+        let confirm =
+            |key: &str| Ok::<_, anyhow::Error>(optional_bool(table, key)?.unwrap_or(true));
+        Ok(Self {
+            kill: confirm("kill")?,
+            delete_worktree: confirm("delete_worktree")?,
+            force_push: confirm("force_push")?,
+        })
+    }
+}
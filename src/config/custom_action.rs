@@ -0,0 +1,97 @@
+//! Shell commands bound to keys of the session list
+//!
+//! ```toml
+//! [[actions]]
+//! name = "Run tests"
+//! key = "X"
+//! command = "cd {path} && cargo test"
+//! ```
+
+use anyhow::{Context, Result};
+
+use super::keys::parse_key;
+use super::profile::shell_quote;
+use super::{expand_placeholders, optional_str, required_str, Table};
+
+/// A shell command run against the selected instance (`[[actions]]`)
+#[derive(Debug, Clone, PartialEq)]
+pub struct CustomAction {
+    /// Shown in the result dialog and the help
+    pub name: String,
+    /// Key of the session list that runs it, if any
+    pub key: Option<char>,
+    /// Command run with `sh -c` in the instance's directory, with
+    /// placeholders
+    pub command: String,
+}
+
+/// Values of the placeholders of a custom action's command
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ActionContext {
+    /// Working directory
+    pub path: String,
+    /// Checked out branch, empty outside a repository
+    pub branch: String,
+    /// `session:window.pane`
+    pub target: String,
+    /// tmux pane ID, e.g. `%3`
+    pub pane_id: String,
+    /// tmux session name
+    pub session: String,
+}
+
+impl CustomAction {
+    /// Parse an `[[actions]]` entry
+    pub(super) fn from_table(table: &Table) -> Result<Self> {
+        let key = optional_str(table, "key")?
+            .map(|value| parse_key(&value).context("'key' must be a single character"))
+            .transpose()?;
+        Ok(Self {
+            name: required_str(table, "name")?,
+            key,
+            command: required_str(table, "command")?,
+        })
+    }
+
+    /// The command with its placeholders replaced by shell-quoted values
+    ///
+    /// Unknown placeholders are left as they are.
+    pub fn expand(&self, ctx: &ActionContext) -> String {
+        expand_placeholders(&self.command, |name| {
+            let value = match name {
+                "path" => &ctx.path,
+                "branch" => &ctx.branch,
+                "target" => &ctx.target,
+                "pane_id" => &ctx.pane_id,
+                "session" => &ctx.session,
+                _ => return None,
+            };
+            Some(shell_quote(value))
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_expand() {
+        let action = CustomAction {
+            name: "Open ticket".to_string(),
+            key: Some('X'),
+            command: "cd {path} && open-ticket {branch} {target} {pane_id} {other}".to_string(),
+        };
+        let ctx = ActionContext {
+            path: "/home/me/my repo".to_string(),
+            branch: "feat/x".to_string(),
+            target: "api:1.0".to_string(),
+            pane_id: "%3".to_string(),
+            session: "api".to_string(),
+        };
+        assert_eq!(
+            action.expand(&ctx),
+            "cd '/home/me/my repo' && open-ticket feat/x api:1.0 '%3' {other}"
+        );
+    }
+}
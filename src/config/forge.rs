@@ -0,0 +1,51 @@
+//! Settings of the forge client
+//!
+//! ```toml
+//! [forge]
+//! cache_seconds = 30
+//! github_token_command = "pass show github/token"
+//! ```
+
+use std::time::Duration;
+
+use anyhow::Result;
+
+use super::{optional_interval, optional_str, Table};
+
+/// Default `cache_seconds`
+const DEFAULT_CACHE_TTL: Duration = Duration::from_secs(30);
+
+/// How pull requests, CI checks and issues are requested (`[forge]`)
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ForgeSettings {
+    /// How long answers are reused before asking again (`cache_seconds`)
+    pub cache_ttl: Duration,
+    /// Shell command printing the token given to gh, instead of its own
+    /// login (`github_token_command`)
+    pub github_token_command: Option<String>,
+}
+
+impl Default for ForgeSettings {
+    fn default() -> Self {
+        Self {
+            cache_ttl: DEFAULT_CACHE_TTL,
+            github_token_command: None,
+        }
+    }
+}
+
+impl ForgeSettings {
+    const KEYS: [&'static str; 2] = ["cache_seconds", "github_token_command"];
+
+    /// Parse the `[forge]` table; keys left out keep their default
+    pub(super) fn from_table(table: &Table) -> Result<Self> {
+        if let Some(key) = table.keys().find(|k| !Self::KEYS.contains(&k.as_str())) {
+            anyhow::bail!("unknown setting '{}'", key);
+        }
+        Ok(Self {
+            cache_ttl: optional_interval(table, "cache_seconds")?.unwrap_or(DEFAULT_CACHE_TTL),
+            github_token_command: optional_str(table, "github_token_command")?
+                .filter(|c| !c.trim().is_empty()),
+        })
+    }
+}
@@ -0,0 +1,110 @@
+//! Commands run after actions in matching repositories
+//!
+//! ```toml
+//! [[hooks]]
+//! after = "commit"
+//! path = "~/work/api"
+//! command = "cargo fmt --check"
+//! ```
+
+use std::path::Path;
+
+use anyhow::Result;
+
+use super::profile::path_matches;
+use super::{optional_str, required_str, Table};
+
+/// Action a hook runs after
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HookEvent {
+    Stage,
+    Commit,
+    Push,
+    Pull,
+    Fetch,
+    CreatePullRequest,
+    MergePullRequest,
+}
+
+impl HookEvent {
+    pub const ALL: [Self; 7] = [
+        Self::Stage,
+        Self::Commit,
+        Self::Push,
+        Self::Pull,
+        Self::Fetch,
+        Self::CreatePullRequest,
+        Self::MergePullRequest,
+    ];
+
+    /// Name used in `after` and `CLAUDE_TMUX_EVENT`
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::Stage => "stage",
+            Self::Commit => "commit",
+            Self::Push => "push",
+            Self::Pull => "pull",
+            Self::Fetch => "fetch",
+            Self::CreatePullRequest => "create_pr",
+            Self::MergePullRequest => "merge_pr",
+        }
+    }
+}
+
+/// A shell command run after an action (`[[hooks]]`)
+#[derive(Debug, Clone, PartialEq)]
+pub struct Hook {
+    /// Action the hook follows
+    pub after: HookEvent,
+    /// Path glob of the repositories it applies to, all when `None`
+    pub path: Option<String>,
+    /// Command run with `sh -c` in the working directory
+    pub command: String,
+}
+
+impl Hook {
+    /// Parse a `[[hooks]]` entry
+    pub(super) fn from_table(table: &Table) -> Result<Self> {
+        let after = required_str(table, "after")?;
+        let after = HookEvent::ALL
+            .into_iter()
+            .find(|e| e.name() == after)
+            .ok_or_else(|| {
+                let names: Vec<&str> = HookEvent::ALL.iter().map(|e| e.name()).collect();
+                anyhow::anyhow!("'after' must be one of: {}", names.join(", "))
+            })?;
+        Ok(Self {
+            after,
+            path: optional_str(table, "path")?,
+            command: required_str(table, "command")?,
+        })
+    }
+
+    /// Whether the hook runs after `event` in `dir`
+    pub fn applies(&self, event: HookEvent, dir: &Path) -> bool {
+        self.after == event && self.path.as_ref().is_none_or(|p| path_matches(p, dir))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn hook(source: &str) -> Result<Hook> {
+        Hook::from_table(&source.parse::<Table>().unwrap())
+    }
+
+    #[test]
+    fn test_hook() {
+        let push = hook("after = \"push\"\npath = \"/work/**\"\ncommand = \"ping-ci\"").unwrap();
+        assert!(push.applies(HookEvent::Push, Path::new("/work/api/src")));
+        assert!(!push.applies(HookEvent::Pull, Path::new("/work/api")));
+        assert!(!push.applies(HookEvent::Push, Path::new("/home/api")));
+
+        let anywhere = hook("after = \"merge_pr\"\ncommand = \"true\"").unwrap();
+        assert!(anywhere.applies(HookEvent::MergePullRequest, Path::new("/tmp")));
+
+        assert!(hook("after = \"deploy\"\ncommand = \"true\"").is_err());
+        assert!(hook("after = \"push\"").is_err());
+    }
+}
@@ -0,0 +1,490 @@
+//! Rebindable keys of the session list
+//!
+//! Each action of the session list has a single-character key, overridable
+//! in the `[keys]` table. The down and up keys also move through the lists
+//! of dialogs, which the quit key closes:
+//!
+//! ```toml
+//! [keys]
+//! kill = "X"
+//! interrupt = "z"
+//! ```
+//!
+//! Arrow keys, `Enter`, `Esc`, `Ctrl+c` and `Ctrl+p` keep their meaning
+//! whatever the bindings. The keybinding editor writes its changes back to
+//! the same table with `save_bindings`, leaving the rest of the file
+//! untouched.
+
+use std::fs;
+use std::path::Path;
+
+use anyhow::{Context, Result};
+
+use toml_edit::{DocumentMut, Item};
+
+use super::{optional_str, Table};
+use crate::i18n::tr;
+
+/// An action of the session list that can be bound to a key
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Action {
+    SelectNext,
+    SelectPrev,
+    OpenMenu,
+    Switch,
+    SwitchBack,
+    NewSession,
+    Kill,
+    Rename,
+    Duplicate,
+    Filter,
+    Continue,
+    Interrupt,
+    SlashCommand,
+    Template,
+    SendFile,
+    Schedule,
+    ScheduledPrompts,
+    Archived,
+    Logs,
+    Snapshots,
+    WorkTime,
+    Stats,
+    AllPanes,
+    Sessions,
+    Workspace,
+    Search,
+    Palette,
+    Review,
+    Stage,
+    Commit,
+    Push,
+    Pull,
+    Fetch,
+    Upstream,
+    NewWorktree,
+    Mark,
+    Sync,
+    Tag,
+    GroupByTag,
+    Sort,
+    PreviewLarger,
+    PreviewSmaller,
+    Refresh,
+    ReloadConfig,
+    Keybindings,
+    Help,
+    Quit,
+}
+
+impl Action {
+    /// Every action, in the order the keybinding editor lists them
+    pub const ALL: [Self; 47] = [
+        Self::SelectNext,
+        Self::SelectPrev,
+        Self::OpenMenu,
+        Self::Switch,
+        Self::SwitchBack,
+        Self::NewSession,
+        Self::Kill,
+        Self::Rename,
+        Self::Duplicate,
+        Self::Filter,
+        Self::Continue,
+        Self::Interrupt,
+        Self::SlashCommand,
+        Self::Template,
+        Self::SendFile,
+        Self::Schedule,
+        Self::ScheduledPrompts,
+        Self::Archived,
+        Self::Logs,
+        Self::Snapshots,
+        Self::WorkTime,
+        Self::Stats,
+        Self::AllPanes,
+        Self::Sessions,
+        Self::Workspace,
+        Self::Search,
+        Self::Palette,
+        Self::Review,
+        Self::Stage,
+        Self::Commit,
+        Self::Push,
+        Self::Pull,
+        Self::Fetch,
+        Self::Upstream,
+        Self::NewWorktree,
+        Self::Mark,
+        Self::Sync,
+        Self::Tag,
+        Self::GroupByTag,
+        Self::Sort,
+        Self::PreviewLarger,
+        Self::PreviewSmaller,
+        Self::Refresh,
+        Self::ReloadConfig,
+        Self::Keybindings,
+        Self::Help,
+        Self::Quit,
+    ];
+
+    /// Name of the action in the `[keys]` table
+    pub fn id(self) -> &'static str {
+        match self {
+            Self::SelectNext => "down",
+            Self::SelectPrev => "up",
+            Self::OpenMenu => "actions",
+            Self::Switch => "switch",
+            Self::SwitchBack => "switch_back",
+            Self::NewSession => "new_session",
+            Self::Kill => "kill",
+            Self::Rename => "rename",
+            Self::Duplicate => "duplicate",
+            Self::Filter => "filter",
+            Self::Continue => "continue",
+            Self::Interrupt => "interrupt",
+            Self::SlashCommand => "slash_command",
+            Self::Template => "template",
+            Self::SendFile => "send_file",
+            Self::Schedule => "schedule",
+            Self::ScheduledPrompts => "scheduled_prompts",
+            Self::Archived => "archived",
+            Self::Logs => "logs",
+            Self::Snapshots => "snapshots",
+            Self::WorkTime => "work_time",
+            Self::Stats => "stats",
+            Self::AllPanes => "all_panes",
+            Self::Sessions => "sessions",
+            Self::Workspace => "workspace",
+            Self::Search => "search",
+            Self::Palette => "palette",
+            Self::Review => "review",
+            Self::Stage => "stage",
+            Self::Commit => "commit",
+            Self::Push => "push",
+            Self::Pull => "pull",
+            Self::Fetch => "fetch",
+            Self::Upstream => "upstream",
+            Self::NewWorktree => "new_worktree",
+            Self::Mark => "mark",
+            Self::Sync => "sync",
+            Self::Tag => "tag",
+            Self::GroupByTag => "group_by_tag",
+            Self::Sort => "sort",
+            Self::PreviewLarger => "preview_larger",
+            Self::PreviewSmaller => "preview_smaller",
+            Self::Refresh => "refresh",
+            Self::ReloadConfig => "reload_config",
+            Self::Keybindings => "keybindings",
+            Self::Help => "help",
+            Self::Quit => "quit",
+        }
+    }
+
+    /// Key used when the config doesn't bind the action
+    pub fn default_key(self) -> char {
+        match self {
+            Self::SelectNext => 'j',
+            Self::SelectPrev => 'k',
+            Self::OpenMenu => 'l',
+            Self::Switch => 'o',
+            Self::SwitchBack => ';',
+            Self::NewSession => 'n',
+            Self::Kill => 'K',
+            Self::Rename => 'r',
+            Self::Duplicate => 'D',
+            Self::Filter => '/',
+            Self::Continue => 'c',
+            Self::Interrupt => 'x',
+            Self::SlashCommand => 's',
+            Self::Template => 'p',
+            Self::SendFile => 'f',
+            Self::Schedule => 'S',
+            Self::ScheduledPrompts => 'J',
+            Self::Archived => 'A',
+            Self::Logs => 'L',
+            Self::Snapshots => 'H',
+            Self::WorkTime => 'T',
+            Self::Stats => 'U',
+            Self::AllPanes => 'w',
+            Self::Sessions => 'E',
+            Self::Workspace => 'e',
+            Self::Search => 'g',
+            Self::Palette => ':',
+            Self::Review => 'v',
+            Self::Stage => 'a',
+            Self::Commit => 'i',
+            Self::Push => 'P',
+            Self::Pull => 'u',
+            Self::Fetch => 'F',
+            Self::Upstream => 'b',
+            Self::NewWorktree => 'W',
+            Self::Mark => 'm',
+            Self::Sync => 'Y',
+            Self::Tag => 't',
+            Self::GroupByTag => 'G',
+            Self::Sort => 'O',
+            Self::PreviewLarger => '+',
+            Self::PreviewSmaller => '-',
+            Self::Refresh => 'R',
+            Self::ReloadConfig => 'C',
+            Self::Keybindings => ',',
+            Self::Help => '?',
+            Self::Quit => 'q',
+        }
+    }
+
+    /// What the action does, as shown in the help and the keybinding editor
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::SelectNext => tr("Move down"),
+            Self::SelectPrev => tr("Move up"),
+            Self::OpenMenu => tr("Open action menu"),
+            Self::Switch => tr("Switch to session"),
+            Self::SwitchBack => tr("Switch back to the previous instance"),
+            Self::NewSession => tr("New session"),
+            Self::Kill => tr("Kill session"),
+            Self::Rename => tr("Rename session"),
+            Self::Duplicate => tr("Duplicate agent (new window)"),
+            Self::Filter => tr("Filter sessions"),
+            Self::Continue => tr("Continue (Enter / \"continue\" / restart)"),
+            Self::Interrupt => tr("Interrupt (Escape)"),
+            Self::SlashCommand => tr("Send slash command"),
+            Self::Template => tr("Send prompt template"),
+            Self::SendFile => tr("Send file or clipboard"),
+            Self::Schedule => tr("Schedule prompt"),
+            Self::ScheduledPrompts => tr("Scheduled prompts"),
+            Self::Archived => tr("Archived sessions"),
+            Self::Logs => tr("Recent log lines"),
+            Self::Snapshots => tr("Snapshots of agents that stopped"),
+            Self::WorkTime => tr("Working time per branch"),
+            Self::Stats => tr("Usage statistics"),
+            Self::AllPanes => tr("All panes (start Claude in one)"),
+            Self::Sessions => tr("All sessions (switch, kill, rename)"),
+            Self::Workspace => tr("Start a workspace"),
+            Self::Search => tr("Search pane contents"),
+            Self::Palette => tr("Command palette"),
+            Self::Review => tr("Review changes"),
+            Self::Stage => tr("Stage all changes"),
+            Self::Commit => tr("Commit staged changes"),
+            Self::Push => tr("Push to remote"),
+            Self::Pull => tr("Pull from remote"),
+            Self::Fetch => tr("Fetch from remote"),
+            Self::Upstream => tr("Pull, push, rebase or merge as ahead/behind calls for"),
+            Self::NewWorktree => tr("New session from worktree"),
+            Self::Mark => tr("Mark instance for syncing"),
+            Self::Sync => tr("Sync marked checkouts with upstream"),
+            Self::Tag => tr("Edit tags"),
+            Self::GroupByTag => tr("Group by tag"),
+            Self::Sort => tr("Change sort order"),
+            Self::PreviewLarger => tr("Enlarge preview"),
+            Self::PreviewSmaller => tr("Shrink preview"),
+            Self::Refresh => tr("Refresh list"),
+            Self::ReloadConfig => tr("Reload config file"),
+            Self::Keybindings => tr("Edit keybindings"),
+            Self::Help => tr("Show this help"),
+            Self::Quit => tr("Quit"),
+        }
+    }
+}
+
+/// Key bound to each action (`[keys]`)
+#[derive(Debug, Clone, PartialEq)]
+pub struct Keymap {
+    /// Keys in `Action::ALL` order
+    keys: Vec<char>,
+}
+
+impl Default for Keymap {
+    fn default() -> Self {
+        Self {
+            keys: Action::ALL.iter().map(|a| a.default_key()).collect(),
+        }
+    }
+}
+
+impl Keymap {
+    /// Parse the `[keys]` table, keeping defaults for unbound actions
+    pub(super) fn from_table(table: &Table) -> Result<Self> {
+        let mut keymap = Self::default();
+        for key in table.keys() {
+            if !Action::ALL.iter().any(|a| a.id() == key) {
+                anyhow::bail!("unknown action '{}'", key);
+            }
+        }
+        for action in Action::ALL {
+            if let Some(value) = optional_str(table, action.id())? {
+                let key = parse_key(&value)
+                    .with_context(|| format!("'{}' must be a single character", action.id()))?;
+                keymap.set(action, key);
+            }
+        }
+        for action in Action::ALL {
+            if let Some(other) = keymap.conflict(action, keymap.key(action)) {
+                anyhow::bail!(
+                    "'{}' is bound to both '{}' and '{}'",
+                    keymap.key(action),
+                    action.id(),
+                    other.id()
+                );
+            }
+        }
+        Ok(keymap)
+    }
+
+    fn index(action: Action) -> usize {
+        Action::ALL.iter().position(|a| *a == action).unwrap_or(0)
+    }
+
+    /// Key bound to `action`
+    pub fn key(&self, action: Action) -> char {
+        self.keys[Self::index(action)]
+    }
+
+    /// Action bound to `key`, if any
+    pub fn action(&self, key: char) -> Option<Action> {
+        let index = self.keys.iter().position(|k| *k == key)?;
+        Some(Action::ALL[index])
+    }
+
+    /// Another action already bound to `key`
+    pub fn conflict(&self, action: Action, key: char) -> Option<Action> {
+        self.action(key).filter(|other| *other != action)
+    }
+
+    pub fn set(&mut self, action: Action, key: char) {
+        self.keys[Self::index(action)] = key;
+    }
+}
+
+/// A binding: exactly one non-whitespace character
+pub(super) fn parse_key(value: &str) -> Option<char> {
+    let mut chars = value.chars();
+    match (chars.next(), chars.next()) {
+        (Some(c), None) if !c.is_whitespace() && !c.is_control() => Some(c),
+        _ => None,
+    }
+}
+
+/// Write bindings into the `[keys]` table of the config file at `path`
+///
+/// Existing entries for the actions are replaced in place and new ones are
+/// added at the end of the table, which is created if needed; comments and
+/// other settings are kept as they are.
+pub fn save_bindings(path: &Path, bindings: &[(Action, char)]) -> Result<()> {
+    let source = match fs::read_to_string(path) {
+        Ok(source) => source,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => String::new(),
+        Err(e) => return Err(e).with_context(|| format!("Failed to read {}", path.display())),
+    };
+    let mut document: DocumentMut = source
+        .parse()
+        .with_context(|| format!("Failed to parse {}", path.display()))?;
+    for (action, key) in bindings {
+        set_binding(&mut document, action.id(), *key)?;
+    }
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir)?;
+    }
+    fs::write(path, document.to_string())
+        .with_context(|| format!("Failed to write {}", path.display()))
+}
+
+/// Set `id = "<key>"` in the `[keys]` table of `document`, keeping the
+/// spelling of an entry it replaces and the comment after it
+fn set_binding(document: &mut DocumentMut, id: &str, key: char) -> Result<()> {
+    let keys = document
+        .entry("keys")
+        .or_insert_with(toml_edit::table)
+        .as_table_like_mut()
+        .context("keys is not a table")?;
+    let mut binding = toml_edit::Value::from(key.to_string());
+    match keys.get_mut(id) {
+        Some(Item::Value(existing)) => {
+            *binding.decor_mut() = existing.decor().clone();
+            *existing = binding;
+        }
+        _ => {
+            keys.insert(id, Item::Value(binding));
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn keymap(source: &str) -> Result<Keymap> {
+        let root = source.parse::<Table>().unwrap();
+        Keymap::from_table(root.get("keys").unwrap().as_table().unwrap())
+    }
+
+    #[test]
+    fn test_keymap() {
+        let keys = keymap("[keys]\nkill = \"X\"\ninterrupt = \"K\"\n").unwrap();
+        assert_eq!(keys.key(Action::Kill), 'X');
+        assert_eq!(keys.action('K'), Some(Action::Interrupt));
+        assert_eq!(keys.action('x'), None);
+        assert_eq!(keys.action('j'), Some(Action::SelectNext));
+        assert_eq!(keys.conflict(Action::Kill, 'j'), Some(Action::SelectNext));
+        assert_eq!(keys.conflict(Action::Kill, 'X'), None);
+
+        assert!(keymap("[keys]\nkill = \"x\"\n").is_err());
+        assert!(keymap("[keys]\nkill = \"XY\"\n").is_err());
+        assert!(keymap("[keys]\nexplode = \"e\"\n").is_err());
+        // Menu actions have keys of their own, checked like the others
+        assert!(keymap("[keys]\npush = \"p\"\n").is_err());
+        let keys = keymap("[keys]\npush = \"p\"\ntemplate = \"T\"\nwork_time = \"V\"\n").unwrap();
+        assert_eq!(keys.action('p'), Some(Action::Push));
+    }
+
+    #[test]
+    fn test_default_keys_are_distinct() {
+        let keymap = Keymap::default();
+        for action in Action::ALL {
+            assert_eq!(keymap.conflict(action, action.default_key()), None);
+        }
+    }
+
+    fn set(source: &str, id: &str, key: char) -> String {
+        let mut document: DocumentMut = source.parse().unwrap();
+        set_binding(&mut document, id, key).unwrap();
+        document.to_string()
+    }
+
+    #[test]
+    fn test_set_binding() {
+        assert_eq!(set("", "kill", 'X'), "[keys]\nkill = \"X\"\n");
+        assert_eq!(
+            set("locale = \"fr\"\n", "kill", '"'),
+            "locale = \"fr\"\n\n[keys]\nkill = '\"'\n"
+        );
+
+        let source = "# mine\n[keys]\nkill = \"X\" # careful\n\n[palette]\ndim = \"white\"\n";
+        assert_eq!(
+            set(source, "kill", 'Z'),
+            "# mine\n[keys]\nkill = \"Z\" # careful\n\n[palette]\ndim = \"white\"\n"
+        );
+        assert_eq!(
+            set(source, "interrupt", 'i'),
+            "# mine\n[keys]\nkill = \"X\" # careful\ninterrupt = \"i\"\n\n[palette]\ndim = \"white\"\n"
+        );
+        // `kill_all` is not `kill`
+        assert_eq!(
+            set("[keys]\nkill_all = \"Q\"\n", "kill", 'X'),
+            "[keys]\nkill_all = \"Q\"\nkill = \"X\"\n"
+        );
+        // Headers and keys are read as TOML, comments and quotes included
+        assert_eq!(
+            set("[keys] # bindings\n\"kill\" = \"X\"\n", "kill", 'Z'),
+            "[keys] # bindings\n\"kill\" = \"Z\"\n"
+        );
+
+        let written = set(source, "interrupt", '\\');
+        let root = &written.parse::<Table>().unwrap();
+        let keys = Keymap::from_table(root.get("keys").unwrap().as_table().unwrap()).unwrap();
+        assert_eq!(keys.key(Action::Interrupt), '\\');
+    }
+}
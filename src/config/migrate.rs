@@ -0,0 +1,257 @@
+//! Config schema versions and migrations
+//!
+//! The config's top-level `version` is the schema it was written for; a file
+//! without one predates versioning and counts as version 0. Settings renamed,
+//! moved into another section or dropped in later versions are rewritten in
+//! the parsed table before the config is read, with a warning for each so the
+//! user can update the file, instead of the old keys failing to parse or
+//! being silently ignored.
+//!
+//! When a setting changes, bump `CURRENT_VERSION` and append a `Migration`
+//! for the new version listing its changes.
+
+use anyhow::Result;
+
+use super::{Table, Value};
+
+/// Schema version this build reads
+pub const CURRENT_VERSION: i64 = 1;
+
+/// A change between two schema versions
+// No setting has changed since versioning was introduced
+#[allow(dead_code)]
+#[derive(Debug)]
+pub(super) enum Change {
+    /// A key or section renamed or moved; paths are dotted (`section.key`)
+    Move {
+        from: &'static str,
+        to: &'static str,
+    },
+    /// A setting that no longer exists, and what replaces it
+    Remove {
+        key: &'static str,
+        note: &'static str,
+    },
+}
+
+/// Changes made to the schema in `version`
+#[derive(Debug)]
+pub(super) struct Migration {
+    pub version: i64,
+    pub changes: &'static [Change],
+}
+
+/// Every schema version after 0, oldest first
+const MIGRATIONS: &[Migration] = &[
+    // Version 1 introduced the `version` key itself
+    Migration {
+        version: 1,
+        changes: &[],
+    },
+];
+
+/// Upgrade `root` to `CURRENT_VERSION`, returning a warning per change made
+pub fn migrate(root: &mut Table) -> Result<Vec<String>> {
+    apply(root, MIGRATIONS, CURRENT_VERSION)
+}
+
+pub(super) fn apply(
+    root: &mut Table,
+    migrations: &[Migration],
+    current: i64,
+) -> Result<Vec<String>> {
+    let version = match root.remove("version") {
+        None => 0,
+        Some(Value::Integer(version)) if version >= 0 => version,
+        Some(other) => anyhow::bail!(
+            "'version' must be a non-negative integer, found {}",
+            other.type_str()
+        ),
+    };
+
+    let mut warnings = Vec::new();
+    if version > current {
+        warnings.push(format!(
+            "config version {} is newer than this claude-tmux supports ({}); unknown settings are ignored",
+            version, current
+        ));
+    }
+    for migration in migrations.iter().filter(|m| m.version > version) {
+        for change in migration.changes {
+            match change {
+                Change::Move { from, to } => {
+                    let Some(value) = take(root, from) else {
+                        continue;
+                    };
+                    if get(root, to).is_some() {
+                        warnings.push(format!(
+                            "'{}' is now '{}'; both are set, so '{}' is ignored",
+                            from, to, from
+                        ));
+                    } else {
+                        insert(root, to, value)?;
+                        warnings.push(format!("'{}' is now '{}'", from, to));
+                    }
+                }
+                Change::Remove { key, note } => {
+                    if take(root, key).is_some() {
+                        warnings.push(format!("'{}' is no longer used: {}", key, note));
+                    }
+                }
+            }
+        }
+    }
+    Ok(warnings)
+}
+
+/// Split `section.key` into its section, if any, and key
+fn split(path: &str) -> (Option<&str>, &str) {
+    match path.split_once('.') {
+        Some((section, key)) => (Some(section), key),
+        None => (None, path),
+    }
+}
+
+fn get<'a>(root: &'a Table, path: &str) -> Option<&'a Value> {
+    match split(path) {
+        (None, key) => root.get(key),
+        (Some(section), key) => root.get(section)?.as_table()?.get(key),
+    }
+}
+
+fn take(root: &mut Table, path: &str) -> Option<Value> {
+    match split(path) {
+        (None, key) => root.remove(key),
+        (Some(section), key) => match root.get_mut(section)? {
+            Value::Table(table) => {
+                let value = table.remove(key);
+                if table.is_empty() {
+                    root.remove(section);
+                }
+                value
+            }
+            _ => None,
+        },
+    }
+}
+
+fn insert(root: &mut Table, path: &str, value: Value) -> Result<()> {
+    match split(path) {
+        (None, key) => {
+            root.insert(key.to_string(), value);
+        }
+        (Some(section), key) => match root
+            .entry(section.to_string())
+            .or_insert_with(|| Value::Table(Table::new()))
+        {
+            Value::Table(table) => {
+                table.insert(key.to_string(), value);
+            }
+            other => anyhow::bail!("'{}' must be a table, found {}", section, other.type_str()),
+        },
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TEST_MIGRATIONS: &[Migration] = &[
+        Migration {
+            version: 1,
+            changes: &[Change::Move {
+                from: "restart",
+                to: "auto_restart",
+            }],
+        },
+        Migration {
+            version: 2,
+            changes: &[
+                Change::Move {
+                    from: "auto_restart",
+                    to: "daemon.auto_restart",
+                },
+                Change::Move {
+                    from: "colors",
+                    to: "palette",
+                },
+                Change::Remove {
+                    key: "legacy_poll",
+                    note: "use capture_budget",
+                },
+            ],
+        },
+    ];
+
+    fn migrated(source: &str) -> (Table, Vec<String>) {
+        let mut root = source.parse::<Table>().unwrap();
+        let warnings = apply(&mut root, TEST_MIGRATIONS, 2).unwrap();
+        (root, warnings)
+    }
+
+    #[test]
+    fn test_migrate_chain() {
+        let (root, warnings) =
+            migrated("restart = true\nlegacy_poll = 3\n\n[colors]\ndim = \"white\"\n");
+        assert_eq!(
+            get(&root, "daemon.auto_restart"),
+            Some(&Value::Boolean(true))
+        );
+        assert_eq!(
+            get(&root, "palette.dim"),
+            Some(&Value::String("white".to_string()))
+        );
+        assert!(!root.contains_key("restart"));
+        assert!(!root.contains_key("auto_restart"));
+        assert!(!root.contains_key("legacy_poll"));
+        assert_eq!(
+            warnings,
+            vec![
+                "'restart' is now 'auto_restart'",
+                "'auto_restart' is now 'daemon.auto_restart'",
+                "'colors' is now 'palette'",
+                "'legacy_poll' is no longer used: use capture_budget",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_migrate_from_version() {
+        // Changes up to the file's version are already applied
+        let (root, warnings) = migrated("version = 1\nauto_restart = true\nrestart = 5\n");
+        assert_eq!(
+            get(&root, "daemon.auto_restart"),
+            Some(&Value::Boolean(true))
+        );
+        assert_eq!(root.get("restart"), Some(&Value::Integer(5)));
+        assert!(!root.contains_key("version"));
+        assert_eq!(warnings.len(), 1);
+
+        let (_, warnings) = migrated("version = 2\n");
+        assert!(warnings.is_empty());
+
+        let (_, warnings) = migrated("version = 7\n");
+        assert!(warnings[0].starts_with("config version 7 is newer"));
+    }
+
+    #[test]
+    fn test_migrate_conflict() {
+        let (root, warnings) =
+            migrated("version = 1\nauto_restart = true\n\n[daemon]\nauto_restart = false\n");
+        assert_eq!(
+            get(&root, "daemon.auto_restart"),
+            Some(&Value::Boolean(false))
+        );
+        assert!(!root.contains_key("auto_restart"));
+        assert!(warnings[0].ends_with("so 'auto_restart' is ignored"));
+    }
+
+    #[test]
+    fn test_invalid_version() {
+        let mut root = "version = \"1\"\n".parse::<Table>().unwrap();
+        assert!(migrate(&mut root).is_err());
+        let mut root = "version = -1\n".parse::<Table>().unwrap();
+        assert!(migrate(&mut root).is_err());
+    }
+}
@@ -0,0 +1,1214 @@
+//! User configuration
+//!
+//! Loaded once at startup from `~/.config/claude-tmux/config.toml`
+//! (or `$XDG_CONFIG_HOME/claude-tmux/config.toml`). A missing file yields the
+//! default configuration.
+//!
+//! - `confirm`: Confirmation of destructive actions
+//! - `custom_action`: Shell commands bound to keys of the session list
+//! - `forge`: Settings of the forge client (caching, tokens)
+//! - `hook`: Commands run after actions in matching repositories
+//! - `keys`: Rebindable keys of the session list
+//! - `migrate`: Schema versions and upgrades of older configs
+//! - `palette`: Colors used in accessibility mode, and theme overrides
+//! - `profile`: Per-project claude launch profiles
+//! - `workspace`: Named sets of directories started together
+
+mod confirm;
+mod custom_action;
+mod forge;
+mod hook;
+mod keys;
+mod migrate;
+mod palette;
+mod profile;
+mod workspace;
+
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use anyhow::{Context, Result};
+use tracing::level_filters::LevelFilter;
+
+use crate::i18n::Locale;
+use crate::poller::DEFAULT_CAPTURE_BUDGET;
+use crate::preview::DEFAULT_PREVIEW_BUFFER_KB;
+use crate::session::SortOrder;
+use crate::tmux::{ClaudeProcess, Tmux};
+use crate::ui::Theme;
+
+pub use confirm::Confirmations;
+pub use custom_action::{ActionContext, CustomAction};
+pub use forge::ForgeSettings;
+pub use hook::{Hook, HookEvent};
+pub use keys::{save_bindings, Action, Keymap};
+pub use palette::Palette;
+pub use profile::{shell_quote, LaunchProfile};
+pub use toml::{Table, Value};
+pub use workspace::Workspace;
+
+/// Default `window_name_template`
+const DEFAULT_WINDOW_NAME_TEMPLATE: &str = "{repo}:{branch}";
+
+/// Default `worktree_path`: next to the main repository
+const DEFAULT_WORKTREE_PATH: &str = "../{repo}-{branch}";
+
+/// Default `session_name_template`
+const DEFAULT_SESSION_NAME_TEMPLATE: &str = "{repo}-{branch}";
+
+/// Default `issue_branch_template`
+const DEFAULT_ISSUE_BRANCH_TEMPLATE: &str = "issue-{issue}-{title}";
+
+/// Default `preview_lines`
+const DEFAULT_PREVIEW_LINES: usize = 15;
+
+/// Default `diff_stat_interval`
+const DEFAULT_DIFF_STAT_INTERVAL: Duration = Duration::from_secs(30);
+
+/// Default `pr_checks_interval`
+const DEFAULT_PR_CHECKS_INTERVAL: Duration = Duration::from_secs(60);
+
+/// Default `auto_fetch_interval`
+const DEFAULT_AUTO_FETCH_INTERVAL: Duration = Duration::from_secs(300);
+
+/// A reusable prompt with `{placeholder}` substitution
+#[derive(Debug, Clone, PartialEq)]
+pub struct PromptTemplate {
+    /// Name shown in the picker
+    pub name: String,
+    /// Prompt text, may contain `{branch}`, `{repo}`, `{path}`, `{last_commit}`, `{session}`,
+    /// and `{content}` when used to wrap a sent file or clipboard
+    pub prompt: String,
+}
+
+/// A Claude Code slash command offered in the quick menu
+#[derive(Debug, Clone, PartialEq)]
+pub struct SlashCommand {
+    /// Command text sent to the pane (e.g., "/compact")
+    pub command: String,
+    /// Short description shown next to the command
+    pub description: String,
+}
+
+impl SlashCommand {
+    fn new(command: &str, description: &str) -> Self {
+        Self {
+            command: command.to_string(),
+            description: description.to_string(),
+        }
+    }
+}
+
+/// Built-in slash commands, always listed before user-defined ones
+fn builtin_slash_commands() -> Vec<SlashCommand> {
+    vec![
+        SlashCommand::new("/compact", "Compact conversation history"),
+        SlashCommand::new("/clear", "Clear conversation history"),
+        SlashCommand::new("/cost", "Show token usage and cost"),
+        SlashCommand::new("/resume", "Resume a previous conversation"),
+    ]
+}
+
+/// Parsed user configuration
+#[derive(Debug, Clone)]
+pub struct Config {
+    /// Prompt template library (`[[templates]]`)
+    pub templates: Vec<PromptTemplate>,
+    /// User-defined slash commands (`[[slash_commands]]`)
+    pub slash_commands: Vec<SlashCommand>,
+    /// Launch profiles (`[[profiles]]`), first match wins
+    pub profiles: Vec<LaunchProfile>,
+    /// Sets of directories started together (`[[workspaces]]`)
+    pub workspaces: Vec<Workspace>,
+    /// Commands run after actions (`[[hooks]]`)
+    pub hooks: Vec<Hook>,
+    /// Shell commands run against the selected instance (`[[actions]]`)
+    pub actions: Vec<CustomAction>,
+    /// Let the daemon restart agents that exited while working with
+    /// `--continue` (`auto_restart`)
+    pub auto_restart: bool,
+    /// Let the daemon label agent panes in their borders (`pane_border_labels`)
+    pub pane_border_labels: bool,
+    /// Let the daemon name agent windows after their checkout (`rename_windows`)
+    pub rename_windows: bool,
+    /// Window name for `rename_windows`, with template placeholders (`window_name_template`)
+    pub window_name_template: String,
+    /// Directory pane logs are written to (`pane_log_dir`), the data
+    /// directory's `pane-logs` when unset
+    pub pane_log_dir: Option<String>,
+    /// Record how long agents work on each branch (`track_work_time`)
+    pub track_work_time: bool,
+    /// Record local usage statistics (`usage_stats`)
+    pub usage_stats: bool,
+    /// Save the pane of agents that start waiting or crash (`status_snapshots`)
+    pub status_snapshots: bool,
+    /// Show the age of each checkout's last commit in the session list (`show_last_commit`)
+    pub show_last_commit: bool,
+    /// Poll the CI checks of branches with open pull requests (`ci_checks`)
+    pub ci_checks: bool,
+    /// Fetch repositories in the background to keep ahead/behind current
+    /// (`auto_fetch`), unless a repository's own setting says otherwise
+    pub auto_fetch: bool,
+    /// Maximum pane captures per second for status polling (`capture_budget`)
+    pub capture_budget: f64,
+    /// Multiplex tmux commands over one control-mode client (`tmux_control_mode`)
+    pub tmux_control_mode: bool,
+    /// How Claude processes are recognized (`[claude_process]`)
+    pub claude_process: ClaudeProcess,
+    /// Size cap of each pane's preview buffer in KiB (`preview_buffer_kb`)
+    pub preview_buffer_kb: usize,
+    /// Lines of the selected pane captured for the preview (`preview_lines`)
+    pub preview_lines: usize,
+    /// Order of the session list (`sort_order`)
+    pub sort_order: SortOrder,
+    /// Where new worktrees go, with template placeholders, relative to the
+    /// main repository (`worktree_path`)
+    pub worktree_path: String,
+    /// Days deleted worktrees stay in the trash, 0 to delete them at once
+    /// (`worktree_trash_days`)
+    pub worktree_trash_days: u64,
+    /// Name of new sessions, with `{repo}` and `{branch}` placeholders
+    /// (`session_name_template`)
+    pub session_name_template: String,
+    /// Name of branches created from an issue, with `{issue}` and `{title}`
+    /// placeholders (`issue_branch_template`)
+    pub issue_branch_template: String,
+    /// How often diffstats are recomputed (`diff_stat_interval`, seconds)
+    pub diff_stat_interval: Duration,
+    /// How often CI checks are polled (`pr_checks_interval`, seconds)
+    pub pr_checks_interval: Duration,
+    /// How often repositories are auto-fetched (`auto_fetch_interval`, seconds)
+    pub auto_fetch_interval: Duration,
+    /// Interface language (`locale`)
+    pub locale: Locale,
+    /// Avoid relying on color and Unicode glyphs (`accessibility`)
+    pub accessibility: bool,
+    /// Draw glyphs with ASCII only (`ascii`, on by default when the locale
+    /// isn't UTF-8)
+    pub ascii: bool,
+    /// Capture the mouse for clicks and wheel scrolling (`mouse`)
+    pub mouse: bool,
+    /// Show how many agents wait, crashed or work in the terminal title and
+    /// progress indicator (`terminal_title`)
+    pub terminal_title: bool,
+    /// Answer other tools on a Unix socket while the TUI runs (`ipc_socket`)
+    pub ipc_socket: bool,
+    /// Draw without colors, set by a non-empty `NO_COLOR` in the environment
+    pub no_color: bool,
+    /// Colors used in accessibility mode (`[palette]`)
+    pub palette: Palette,
+    /// Colors used otherwise (`theme`, with `[colors]` overrides)
+    pub theme: Theme,
+    /// Most verbose messages written to the log file (`log_level`)
+    pub log_level: LevelFilter,
+    /// Outdated settings that were upgraded while loading (`version`)
+    pub warnings: Vec<String>,
+    /// Keys of the session list actions (`[keys]`)
+    pub keys: Keymap,
+    /// Destructive actions that ask for confirmation (`[confirm]`)
+    pub confirm: Confirmations,
+    /// Caching and tokens of forge requests (`[forge]`)
+    pub forge: ForgeSettings,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Self {
+            templates: Vec::new(),
+            slash_commands: Vec::new(),
+            profiles: Vec::new(),
+            workspaces: Vec::new(),
+            hooks: Vec::new(),
+            actions: Vec::new(),
+            auto_restart: false,
+            pane_border_labels: false,
+            rename_windows: false,
+            window_name_template: DEFAULT_WINDOW_NAME_TEMPLATE.to_string(),
+            pane_log_dir: None,
+            track_work_time: true,
+            usage_stats: true,
+            status_snapshots: true,
+            show_last_commit: false,
+            ci_checks: true,
+            auto_fetch: false,
+            capture_budget: DEFAULT_CAPTURE_BUDGET,
+            tmux_control_mode: true,
+            claude_process: ClaudeProcess::default(),
+            preview_buffer_kb: DEFAULT_PREVIEW_BUFFER_KB,
+            preview_lines: DEFAULT_PREVIEW_LINES,
+            sort_order: SortOrder::default(),
+            worktree_path: DEFAULT_WORKTREE_PATH.to_string(),
+            worktree_trash_days: 0,
+            session_name_template: DEFAULT_SESSION_NAME_TEMPLATE.to_string(),
+            issue_branch_template: DEFAULT_ISSUE_BRANCH_TEMPLATE.to_string(),
+            diff_stat_interval: DEFAULT_DIFF_STAT_INTERVAL,
+            pr_checks_interval: DEFAULT_PR_CHECKS_INTERVAL,
+            auto_fetch_interval: DEFAULT_AUTO_FETCH_INTERVAL,
+            locale: Locale::default(),
+            accessibility: false,
+            ascii: !utf8_locale(),
+            mouse: true,
+            terminal_title: false,
+            ipc_socket: false,
+            no_color: no_color_requested(),
+            palette: Palette::default(),
+            theme: Theme::default(),
+            log_level: LevelFilter::INFO,
+            warnings: Vec::new(),
+            keys: Keymap::default(),
+            confirm: Confirmations::default(),
+            forge: ForgeSettings::default(),
+        }
+    }
+}
+
+impl Config {
+    /// Path of the config file
+    pub fn path() -> PathBuf {
+        std::env::var_os("XDG_CONFIG_HOME")
+            .map(PathBuf::from)
+            .filter(|p| p.is_absolute())
+            .or_else(|| dirs::home_dir().map(|h| h.join(".config")))
+            .unwrap_or_default()
+            .join("claude-tmux")
+            .join("config.toml")
+    }
+
+    /// Load the config file, falling back to defaults when it doesn't exist
+    pub fn load() -> Result<Self> {
+        let path = Self::path();
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+        let content = fs::read_to_string(&path)
+            .with_context(|| format!("Failed to read {}", path.display()))?;
+        Self::parse(&content).with_context(|| format!("Invalid config {}", path.display()))
+    }
+
+    /// Parse config from TOML source
+    ///
+    /// Configs written for an older schema `version` are upgraded first,
+    /// with a warning in `warnings` for each setting that changed.
+    pub fn parse(content: &str) -> Result<Self> {
+        let mut root: Table = content.parse()?;
+        let warnings = migrate::migrate(&mut root)?;
+        if let Some(budget) = optional_float(&root, "capture_budget")? {
+            if budget <= 0.0 {
+                anyhow::bail!("'capture_budget' must be positive");
+            }
+        }
+        let locale = match optional_str(&root, "locale")? {
+            Some(code) => Locale::parse(&code).ok_or_else(|| {
+                let codes: Vec<&str> = Locale::ALL.iter().map(|l| l.code()).collect();
+                anyhow::anyhow!("'locale' must be one of: {}", codes.join(", "))
+            })?,
+            None => Locale::default(),
+        };
+        let sort_order = match optional_str(&root, "sort_order")? {
+            Some(order) => SortOrder::parse(&order).ok_or_else(|| {
+                let orders: Vec<&str> = SortOrder::ALL.iter().map(|o| o.code()).collect();
+                anyhow::anyhow!("'sort_order' must be one of: {}", orders.join(", "))
+            })?,
+            None => SortOrder::default(),
+        };
+        let theme = match optional_str(&root, "theme")? {
+            Some(name) => Theme::named(&name).ok_or_else(|| {
+                anyhow::anyhow!("'theme' must be one of: {}", Theme::NAMES.join(", "))
+            })?,
+            None => Theme::default(),
+        };
+        if optional_size(&root, "preview_lines")? == Some(0) {
+            anyhow::bail!("'preview_lines' must be positive");
+        }
+        let log_level = match optional_str(&root, "log_level")? {
+            Some(level) => level.parse().map_err(|_| {
+                anyhow::anyhow!("'log_level' must be one of: off, error, warn, info, debug, trace")
+            })?,
+            None => LevelFilter::INFO,
+        };
+        let mut config = Self {
+            auto_restart: optional_bool(&root, "auto_restart")?.unwrap_or(false),
+            pane_border_labels: optional_bool(&root, "pane_border_labels")?.unwrap_or(false),
+            rename_windows: optional_bool(&root, "rename_windows")?.unwrap_or(false),
+            window_name_template: optional_str(&root, "window_name_template")?
+                .unwrap_or_else(|| DEFAULT_WINDOW_NAME_TEMPLATE.to_string()),
+            pane_log_dir: optional_str(&root, "pane_log_dir")?,
+            track_work_time: optional_bool(&root, "track_work_time")?.unwrap_or(true),
+            usage_stats: optional_bool(&root, "usage_stats")?.unwrap_or(true),
+            status_snapshots: optional_bool(&root, "status_snapshots")?.unwrap_or(true),
+            show_last_commit: optional_bool(&root, "show_last_commit")?.unwrap_or(false),
+            ci_checks: optional_bool(&root, "ci_checks")?.unwrap_or(true),
+            auto_fetch: optional_bool(&root, "auto_fetch")?.unwrap_or(false),
+            capture_budget: optional_float(&root, "capture_budget")?
+                .unwrap_or(DEFAULT_CAPTURE_BUDGET),
+            tmux_control_mode: optional_bool(&root, "tmux_control_mode")?.unwrap_or(true),
+            claude_process: match root.get("claude_process") {
+                None => ClaudeProcess::default(),
+                Some(Value::Table(table)) => claude_process(table).context("claude_process")?,
+                Some(other) => anyhow::bail!(
+                    "'claude_process' must be a table, found {}",
+                    other.type_str()
+                ),
+            },
+            preview_buffer_kb: optional_size(&root, "preview_buffer_kb")?
+                .unwrap_or(DEFAULT_PREVIEW_BUFFER_KB),
+            preview_lines: optional_size(&root, "preview_lines")?.unwrap_or(DEFAULT_PREVIEW_LINES),
+            sort_order,
+            worktree_path: optional_str(&root, "worktree_path")?
+                .unwrap_or_else(|| DEFAULT_WORKTREE_PATH.to_string()),
+            worktree_trash_days: optional_size(&root, "worktree_trash_days")?.unwrap_or(0) as u64,
+            session_name_template: optional_str(&root, "session_name_template")?
+                .unwrap_or_else(|| DEFAULT_SESSION_NAME_TEMPLATE.to_string()),
+            issue_branch_template: optional_str(&root, "issue_branch_template")?
+                .unwrap_or_else(|| DEFAULT_ISSUE_BRANCH_TEMPLATE.to_string()),
+            diff_stat_interval: optional_interval(&root, "diff_stat_interval")?
+                .unwrap_or(DEFAULT_DIFF_STAT_INTERVAL),
+            pr_checks_interval: optional_interval(&root, "pr_checks_interval")?
+                .unwrap_or(DEFAULT_PR_CHECKS_INTERVAL),
+            auto_fetch_interval: optional_interval(&root, "auto_fetch_interval")?
+                .unwrap_or(DEFAULT_AUTO_FETCH_INTERVAL),
+            locale,
+            log_level,
+            warnings,
+            accessibility: optional_bool(&root, "accessibility")?.unwrap_or(false),
+            ascii: optional_bool(&root, "ascii")?.unwrap_or_else(|| !utf8_locale()),
+            mouse: optional_bool(&root, "mouse")?.unwrap_or(true),
+            terminal_title: optional_bool(&root, "terminal_title")?.unwrap_or(false),
+            ipc_socket: optional_bool(&root, "ipc_socket")?.unwrap_or(false),
+            no_color: no_color_requested(),
+            palette: match root.get("palette") {
+                None => Palette::default(),
+                Some(Value::Table(table)) => Palette::from_table(table).context("palette")?,
+                Some(other) => {
+                    anyhow::bail!("'palette' must be a table, found {}", other.type_str())
+                }
+            },
+            theme: match root.get("colors") {
+                None => theme,
+                Some(Value::Table(table)) => {
+                    palette::theme_colors(theme, table).context("colors")?
+                }
+                Some(other) => {
+                    anyhow::bail!("'colors' must be a table, found {}", other.type_str())
+                }
+            },
+            keys: match root.get("keys") {
+                None => Keymap::default(),
+                Some(Value::Table(table)) => Keymap::from_table(table).context("keys")?,
+                Some(other) => {
+                    anyhow::bail!("'keys' must be a table, found {}", other.type_str())
+                }
+            },
+            confirm: match root.get("confirm") {
+                None => Confirmations::default(),
+                Some(Value::Table(table)) => Confirmations::from_table(table).context("confirm")?,
+                Some(other) => {
+                    anyhow::bail!("'confirm' must be a table, found {}", other.type_str())
+                }
+            },
+            forge: match root.get("forge") {
+                None => ForgeSettings::default(),
+                Some(Value::Table(table)) => ForgeSettings::from_table(table).context("forge")?,
+                Some(other) => {
+                    anyhow::bail!("'forge' must be a table, found {}", other.type_str())
+                }
+            },
+            ..Self::default()
+        };
+
+        for (i, entry) in array_of_tables(&root, "templates")?.iter().enumerate() {
+            let name = required_str(entry, "name").with_context(|| format!("templates[{}]", i))?;
+            let prompt =
+                required_str(entry, "prompt").with_context(|| format!("templates[{}]", i))?;
+            config.templates.push(PromptTemplate { name, prompt });
+        }
+
+        for (i, entry) in array_of_tables(&root, "slash_commands")?.iter().enumerate() {
+            let command =
+                required_str(entry, "command").with_context(|| format!("slash_commands[{}]", i))?;
+            if !command.starts_with('/') {
+                anyhow::bail!("slash_commands[{}]: command must start with '/'", i);
+            }
+            let description = optional_str(entry, "description")
+                .with_context(|| format!("slash_commands[{}]", i))?
+                .unwrap_or_default();
+            config.slash_commands.push(SlashCommand {
+                command,
+                description,
+            });
+        }
+
+        for (i, entry) in array_of_tables(&root, "profiles")?.iter().enumerate() {
+            let profile =
+                LaunchProfile::from_table(entry).with_context(|| format!("profiles[{}]", i))?;
+            config.profiles.push(profile);
+        }
+
+        for (i, entry) in array_of_tables(&root, "workspaces")?.iter().enumerate() {
+            let workspace =
+                Workspace::from_table(entry).with_context(|| format!("workspaces[{}]", i))?;
+            if config.workspaces.iter().any(|w| w.name == workspace.name) {
+                anyhow::bail!("workspaces[{}]: duplicate name '{}'", i, workspace.name);
+            }
+            config.workspaces.push(workspace);
+        }
+
+        for (i, entry) in array_of_tables(&root, "hooks")?.iter().enumerate() {
+            let hook = Hook::from_table(entry).with_context(|| format!("hooks[{}]", i))?;
+            config.hooks.push(hook);
+        }
+
+        for (i, entry) in array_of_tables(&root, "actions")?.iter().enumerate() {
+            let action =
+                CustomAction::from_table(entry).with_context(|| format!("actions[{}]", i))?;
+            if let Some(key) = action.key {
+                if let Some(bound) = config.keys.action(key) {
+                    anyhow::bail!("actions[{}]: '{}' is bound to '{}'", i, key, bound.id());
+                }
+                if config.actions.iter().any(|a| a.key == Some(key)) {
+                    anyhow::bail!("actions[{}]: '{}' is bound to another action", i, key);
+                }
+            }
+            config.actions.push(action);
+        }
+
+        Ok(config)
+    }
+
+    /// Command that starts claude in `dir`
+    ///
+    /// Uses the first profile matching any of `dirs` (e.g. a worktree and its
+    /// source repository), or bare `claude` when none does.
+    pub fn claude_command(&self, dirs: &[&Path]) -> String {
+        self.profiles
+            .iter()
+            .find(|p| dirs.iter().any(|d| p.matches(d)))
+            .map(|p| p.command())
+            .unwrap_or_else(|| "claude".to_string())
+    }
+
+    /// Built-in slash commands followed by user-defined ones
+    ///
+    /// A user entry with the same command as a built-in replaces it in place.
+    pub fn all_slash_commands(&self) -> Vec<SlashCommand> {
+        let mut commands = builtin_slash_commands();
+        for custom in &self.slash_commands {
+            match commands.iter_mut().find(|c| c.command == custom.command) {
+                Some(existing) => *existing = custom.clone(),
+                None => commands.push(custom.clone()),
+            }
+        }
+        commands
+    }
+
+    /// Whether glyphs are drawn with ASCII only, which accessibility mode also does
+    pub fn ascii_glyphs(&self) -> bool {
+        self.ascii || self.accessibility
+    }
+}
+
+/// Get an array of tables (`[[key]]`), or an empty list if absent
+fn array_of_tables<'a>(table: &'a Table, key: &str) -> Result<Vec<&'a Table>> {
+    match table.get(key) {
+        None => Ok(Vec::new()),
+        Some(Value::Array(items)) => items
+            .iter()
+            .map(|v| {
+                v.as_table()
+                    .ok_or_else(|| anyhow::anyhow!("'{}' must contain tables", key))
+            })
+            .collect(),
+        Some(other) => anyhow::bail!("'{}' must be an array, found {}", key, other.type_str()),
+    }
+}
+
+/// Get an optional array of strings, or an empty list if absent
+fn string_list(table: &Table, key: &str) -> Result<Vec<String>> {
+    match table.get(key) {
+        None => Ok(Vec::new()),
+        Some(Value::Array(items)) => items
+            .iter()
+            .map(|v| {
+                v.as_str()
+                    .map(str::to_string)
+                    .ok_or_else(|| anyhow::anyhow!("'{}' must contain strings", key))
+            })
+            .collect(),
+        Some(other) => anyhow::bail!("'{}' must be an array, found {}", key, other.type_str()),
+    }
+}
+
+/// Parse the `[claude_process]` table; keys left out keep their default
+fn claude_process(table: &Table) -> Result<ClaudeProcess> {
+    let default = ClaudeProcess::default();
+    let list = |key| -> Result<Option<Vec<String>>> {
+        if table.get(key).is_none() {
+            return Ok(None);
+        }
+        let items = string_list(table, key)?;
+        if items.iter().any(|s| s.is_empty()) {
+            anyhow::bail!("'{}' must not contain empty strings", key);
+        }
+        Ok(Some(items))
+    };
+    let rules = ClaudeProcess {
+        patterns: list("patterns")?.unwrap_or(default.patterns),
+        args: list("args")?.unwrap_or(default.args),
+        exclude: list("exclude")?.unwrap_or(default.exclude),
+    };
+    if rules.patterns.is_empty() && rules.args.is_empty() {
+        anyhow::bail!("'patterns' and 'args' can't both be empty");
+    }
+    Ok(rules)
+}
+
+/// Get a required string value
+fn required_str(table: &Table, key: &str) -> Result<String> {
+    match table.get(key) {
+        Some(Value::String(s)) => Ok(s.clone()),
+        Some(other) => anyhow::bail!("'{}' must be a string, found {}", key, other.type_str()),
+        None => anyhow::bail!("missing '{}'", key),
+    }
+}
+
+/// Get an optional string value
+fn optional_str(table: &Table, key: &str) -> Result<Option<String>> {
+    match table.get(key) {
+        Some(Value::String(s)) => Ok(Some(s.clone())),
+        Some(other) => anyhow::bail!("'{}' must be a string, found {}", key, other.type_str()),
+        None => Ok(None),
+    }
+}
+
+/// Get an optional boolean value
+fn optional_bool(table: &Table, key: &str) -> Result<Option<bool>> {
+    match table.get(key) {
+        Some(Value::Boolean(b)) => Ok(Some(*b)),
+        Some(other) => anyhow::bail!("'{}' must be a boolean, found {}", key, other.type_str()),
+        None => Ok(None),
+    }
+}
+
+/// Whether the locale's character set is UTF-8, following the same
+/// `LC_ALL`, `LC_CTYPE`, `LANG` precedence as tmux
+///
+/// With none of them set, Unicode is assumed so that the interface doesn't
+/// change for terminals that never set a locale.
+fn utf8_locale() -> bool {
+    ["LC_ALL", "LC_CTYPE", "LANG"]
+        .iter()
+        .filter_map(std::env::var_os)
+        .find(|v| !v.is_empty())
+        .is_none_or(|v| {
+            let v = v.to_string_lossy().to_ascii_lowercase();
+            v.contains("utf-8") || v.contains("utf8")
+        })
+}
+
+/// Whether colors are turned off, as described on https://no-color.org
+fn no_color_requested() -> bool {
+    std::env::var_os("NO_COLOR").is_some_and(|v| !v.is_empty())
+}
+
+/// Get an optional non-negative integer
+fn optional_size(table: &Table, key: &str) -> Result<Option<usize>> {
+    match table.get(key) {
+        None => Ok(None),
+        Some(value) => match value.as_integer() {
+            Some(i) if i >= 0 => Ok(Some(i as usize)),
+            Some(_) => anyhow::bail!("'{}' must not be negative", key),
+            None => anyhow::bail!("'{}' must be an integer, found {}", key, value.type_str()),
+        },
+    }
+}
+
+/// Get an optional positive number of seconds
+fn optional_interval(table: &Table, key: &str) -> Result<Option<Duration>> {
+    match optional_size(table, key)? {
+        Some(0) => anyhow::bail!("'{}' must be positive", key),
+        secs => Ok(secs.map(|s| Duration::from_secs(s as u64))),
+    }
+}
+
+/// Get an optional number (integer or float)
+fn optional_float(table: &Table, key: &str) -> Result<Option<f64>> {
+    match table.get(key) {
+        None => Ok(None),
+        Some(value) => match value.as_float().or(value.as_integer().map(|i| i as f64)) {
+            Some(f) => Ok(Some(f)),
+            None => anyhow::bail!("'{}' must be a number, found {}", key, value.type_str()),
+        },
+    }
+}
+
+/// Load the config for a subcommand and apply its tmux settings
+///
+/// A broken config is reported on stderr, prefixed with `claude-tmux
+/// <name>`, and replaced by the defaults.
+pub fn load_for_subcommand(name: &str) -> Config {
+    let config = Config::load().unwrap_or_else(|e| {
+        eprintln!("claude-tmux {}: {:#}", name, e);
+        Config::default()
+    });
+    Tmux::set_control_mode(config.tmux_control_mode);
+    Tmux::set_claude_process(config.claude_process.clone());
+    config
+}
+
+/// Values available for `{placeholder}` substitution in templates
+#[derive(Debug, Clone, Default)]
+pub struct TemplateContext {
+    pub branch: Option<String>,
+    /// Repository directory name, the same for all of its worktrees
+    pub repo: Option<String>,
+    pub path: String,
+    pub last_commit: Option<String>,
+    pub session: String,
+}
+
+/// Expand `{placeholder}`s in a template
+///
+/// Unknown placeholders are left untouched so literal braces in prompts
+/// (code snippets, JSON) survive expansion.
+pub fn expand_template(template: &str, ctx: &TemplateContext) -> String {
+    expand_placeholders(template, |name| match name {
+        "branch" => Some(ctx.branch.clone().unwrap_or_default()),
+        "repo" => Some(ctx.repo.clone().unwrap_or_default()),
+        "path" => Some(ctx.path.clone()),
+        "last_commit" => Some(ctx.last_commit.clone().unwrap_or_default()),
+        "session" => Some(ctx.session.clone()),
+        _ => None,
+    })
+}
+
+/// Replace each `{name}` in a template by `value(name)`, leaving the
+/// placeholder as it is when that returns `None`
+fn expand_placeholders(template: &str, value: impl Fn(&str) -> Option<String>) -> String {
+    let mut out = String::with_capacity(template.len());
+    let mut rest = template;
+
+    while let Some(start) = rest.find('{') {
+        out.push_str(&rest[..start]);
+        let after = &rest[start + 1..];
+        let Some(end) = after.find('}') else {
+            out.push_str(&rest[start..]);
+            return out;
+        };
+        match value(&after[..end]) {
+            Some(v) => out.push_str(&v),
+            None => out.push_str(&rest[start..start + end + 2]),
+        }
+        rest = &after[end + 1..];
+    }
+
+    out.push_str(rest);
+    out
+}
+
+/// Wrap sent content in an (already expanded) template
+///
+/// `{content}` in the template is replaced by the content; templates without
+/// the placeholder get the content appended after a blank line.
+pub fn wrap_content(template: &str, content: &str) -> String {
+    if template.contains("{content}") {
+        template.replace("{content}", content)
+    } else {
+        format!("{}\n\n{}", template.trim_end(), content)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_templates() {
+        let config = Config::parse(
+            r#"
+            [[templates]]
+            name = "Tests"
+            prompt = "Run the tests on {branch}"
+            "#,
+        )
+        .unwrap();
+        assert_eq!(config.templates.len(), 1);
+        assert_eq!(config.templates[0].name, "Tests");
+
+        assert!(Config::parse("[[templates]]\nname = \"x\"\n").is_err());
+        assert!(Config::parse("").unwrap().templates.is_empty());
+    }
+
+    #[test]
+    fn test_slash_commands() {
+        let config = Config::parse(
+            r#"
+            [[slash_commands]]
+            command = "/review"
+            description = "Review the current diff"
+
+            [[slash_commands]]
+            command = "/compact"
+            description = "Compact, keep the plan"
+            "#,
+        )
+        .unwrap();
+        let all = config.all_slash_commands();
+        assert_eq!(all[0].command, "/compact");
+        assert_eq!(all[0].description, "Compact, keep the plan");
+        assert_eq!(all.last().unwrap().command, "/review");
+        assert_eq!(all.len(), builtin_slash_commands().len() + 1);
+
+        assert!(Config::parse("[[slash_commands]]\ncommand = \"review\"\n").is_err());
+    }
+
+    #[test]
+    fn test_claude_command() {
+        let config = Config::parse(
+            r#"
+            [[profiles]]
+            path = "/work/*"
+            model = "opus"
+
+            [[profiles]]
+            path = "/**"
+            args = ["--verbose"]
+            "#,
+        )
+        .unwrap();
+        assert_eq!(
+            config.claude_command(&[Path::new("/tmp/api-fix"), Path::new("/work/api")]),
+            "claude --model opus"
+        );
+        assert_eq!(
+            config.claude_command(&[Path::new("/tmp")]),
+            "claude --verbose"
+        );
+        assert_eq!(
+            Config::default().claude_command(&[Path::new("/")]),
+            "claude"
+        );
+    }
+
+    #[test]
+    fn test_auto_restart() {
+        assert!(!Config::parse("").unwrap().auto_restart);
+        assert!(Config::parse("auto_restart = true").unwrap().auto_restart);
+        assert!(Config::parse("auto_restart = \"yes\"").is_err());
+    }
+
+    #[test]
+    fn test_claude_process() {
+        let config = Config::parse("").unwrap();
+        assert_eq!(config.claude_process, ClaudeProcess::default());
+
+        let config = Config::parse(
+            r#"
+            [claude_process]
+            args = ["claude"]
+            exclude = ["claude-monitor"]
+            "#,
+        )
+        .unwrap();
+        assert_eq!(config.claude_process.patterns, ["bin/claude"]);
+        assert_eq!(config.claude_process.args, ["claude"]);
+        assert_eq!(config.claude_process.exclude, ["claude-monitor"]);
+
+        assert!(Config::parse("[claude_process]\npatterns = []").is_err());
+        assert!(Config::parse("[claude_process]\npatterns = [\"\"]").is_err());
+        assert!(Config::parse("[claude_process]\nexclude = \"node\"").is_err());
+        assert!(Config::parse("claude_process = \"claude\"").is_err());
+    }
+
+    #[test]
+    fn test_pane_border_labels() {
+        assert!(!Config::parse("").unwrap().pane_border_labels);
+        assert!(
+            Config::parse("pane_border_labels = true")
+                .unwrap()
+                .pane_border_labels
+        );
+    }
+
+    #[test]
+    fn test_rename_windows() {
+        let config = Config::parse("").unwrap();
+        assert!(!config.rename_windows);
+        assert_eq!(config.window_name_template, "{repo}:{branch}");
+
+        let config =
+            Config::parse("rename_windows = true\nwindow_name_template = \"{repo}@{branch}\"")
+                .unwrap();
+        assert!(config.rename_windows);
+        assert_eq!(config.window_name_template, "{repo}@{branch}");
+    }
+
+    #[test]
+    fn test_track_work_time() {
+        assert!(Config::parse("").unwrap().track_work_time);
+        assert!(
+            !Config::parse("track_work_time = false")
+                .unwrap()
+                .track_work_time
+        );
+    }
+
+    #[test]
+    fn test_usage_stats() {
+        assert!(Config::parse("").unwrap().usage_stats);
+        assert!(!Config::parse("usage_stats = false").unwrap().usage_stats);
+    }
+
+    #[test]
+    fn test_status_snapshots() {
+        assert!(Config::parse("").unwrap().status_snapshots);
+        let config = Config::parse("status_snapshots = false").unwrap();
+        assert!(!config.status_snapshots);
+    }
+
+    #[test]
+    fn test_terminal_title() {
+        assert!(!Config::parse("").unwrap().terminal_title);
+        assert!(
+            Config::parse("terminal_title = true")
+                .unwrap()
+                .terminal_title
+        );
+        assert!(!Config::parse("").unwrap().ipc_socket);
+        assert!(Config::parse("ipc_socket = true").unwrap().ipc_socket);
+    }
+
+    #[test]
+    fn test_show_last_commit() {
+        assert!(!Config::parse("").unwrap().show_last_commit);
+        assert!(
+            Config::parse("show_last_commit = true")
+                .unwrap()
+                .show_last_commit
+        );
+    }
+
+    #[test]
+    fn test_pane_log_dir() {
+        assert_eq!(Config::parse("").unwrap().pane_log_dir, None);
+        assert_eq!(
+            Config::parse("pane_log_dir = \"~/agent-logs\"")
+                .unwrap()
+                .pane_log_dir
+                .as_deref(),
+            Some("~/agent-logs")
+        );
+    }
+
+    #[test]
+    fn test_ci_checks() {
+        assert!(Config::parse("").unwrap().ci_checks);
+        assert!(!Config::parse("ci_checks = false").unwrap().ci_checks);
+    }
+
+    #[test]
+    fn test_auto_fetch() {
+        assert!(!Config::parse("").unwrap().auto_fetch);
+        assert!(Config::parse("auto_fetch = true").unwrap().auto_fetch);
+    }
+
+    #[test]
+    fn test_locale() {
+        assert_eq!(Config::parse("").unwrap().locale, Locale::En);
+        assert_eq!(
+            Config::parse("locale = \"fr_FR.UTF-8\"").unwrap().locale,
+            Locale::Fr
+        );
+        assert!(Config::parse("locale = \"de\"").is_err());
+    }
+
+    #[test]
+    fn test_accessibility() {
+        let config = Config::parse("").unwrap();
+        assert!(!config.accessibility);
+        assert_eq!(config.palette, Palette::default());
+
+        let config = Config::parse(
+            r##"
+            accessibility = true
+
+            [palette]
+            waiting = "#ffd700"
+            dim = "white"
+            "##,
+        )
+        .unwrap();
+        assert!(config.accessibility);
+        assert_eq!(
+            config.palette.waiting,
+            ratatui::style::Color::Rgb(255, 215, 0)
+        );
+        assert_eq!(config.palette.dim, ratatui::style::Color::White);
+        assert_eq!(config.palette.working, Palette::default().working);
+
+        assert!(Config::parse("[palette]\nidle = \"no-such-color\"").is_err());
+        assert!(Config::parse("palette = \"dark\"").is_err());
+    }
+
+    #[test]
+    fn test_ascii() {
+        assert!(Config::parse("ascii = true").unwrap().ascii);
+        assert!(!Config::parse("ascii = false").unwrap().ascii);
+        assert!(Config::parse("ascii = \"yes\"").is_err());
+
+        let config = Config::parse("accessibility = true\nascii = false").unwrap();
+        assert!(config.ascii_glyphs());
+    }
+
+    #[test]
+    fn test_workspaces() {
+        let config = Config::parse(
+            "[[workspaces]]\nname = \"product\"\npaths = [\"/src/api\", \"/src/web/\"]",
+        )
+        .unwrap();
+        assert_eq!(config.workspaces.len(), 1);
+        assert_eq!(
+            config.workspaces[0].dirs(),
+            vec![PathBuf::from("/src/api"), PathBuf::from("/src/web")]
+        );
+
+        assert!(Config::parse("[[workspaces]]\nname = \"product\"").is_err());
+        assert!(Config::parse("[[workspaces]]\npaths = [\"/src\"]").is_err());
+        let duplicate = "[[workspaces]]\nname = \"a\"\npaths = [\"/x\"]\n";
+        assert!(Config::parse(&duplicate.repeat(2)).is_err());
+    }
+
+    #[test]
+    fn test_custom_actions() {
+        let config = Config::parse(
+            "[[actions]]\nname = \"Tests\"\nkey = \"X\"\ncommand = \"cargo test\"\n\
+             [[actions]]\nname = \"Ticket\"\ncommand = \"open {branch}\"",
+        )
+        .unwrap();
+        assert_eq!(config.actions.len(), 2);
+        assert_eq!(config.actions[0].key, Some('X'));
+        assert_eq!(config.actions[1].key, None);
+
+        assert!(Config::parse("[[actions]]\nname = \"x\"").is_err());
+        assert!(Config::parse("[[actions]]\nname = \"x\"\nkey = \"ab\"\ncommand = \"y\"").is_err());
+        // Keys of built-in actions and of other custom actions are taken
+        assert!(Config::parse("[[actions]]\nname = \"x\"\nkey = \"K\"\ncommand = \"y\"").is_err());
+        let rebound =
+            "[keys]\nkill = \"Z\"\n[[actions]]\nname = \"x\"\nkey = \"K\"\ncommand = \"y\"";
+        assert!(Config::parse(rebound).is_ok());
+        let twice = "[[actions]]\nname = \"x\"\nkey = \"X\"\ncommand = \"y\"\n";
+        assert!(Config::parse(&twice.repeat(2)).is_err());
+    }
+
+    #[test]
+    fn test_confirm() {
+        assert_eq!(Config::parse("").unwrap().confirm, Confirmations::default());
+
+        let config = Config::parse("[confirm]\nkill = false\nforce_push = false").unwrap();
+        assert!(!config.confirm.kill);
+        assert!(config.confirm.delete_worktree);
+        assert!(!config.confirm.force_push);
+
+        assert!(Config::parse("[confirm]\narchive = false").is_err());
+        assert!(Config::parse("[confirm]\nkill = \"no\"").is_err());
+        assert!(Config::parse("confirm = false").is_err());
+    }
+
+    #[test]
+    fn test_forge() {
+        assert_eq!(Config::parse("").unwrap().forge, ForgeSettings::default());
+
+        let config =
+            Config::parse("[forge]\ncache_seconds = 120\ngithub_token_command = \"pass gh\"")
+                .unwrap();
+        assert_eq!(config.forge.cache_ttl, Duration::from_secs(120));
+        assert_eq!(
+            config.forge.github_token_command.as_deref(),
+            Some("pass gh")
+        );
+
+        assert!(Config::parse("[forge]\ncache_seconds = 0").is_err());
+        assert!(Config::parse("[forge]\ntoken = \"x\"").is_err());
+        assert!(Config::parse("forge = 1").is_err());
+    }
+
+    #[test]
+    fn test_mouse() {
+        assert!(Config::parse("").unwrap().mouse);
+        assert!(!Config::parse("mouse = false").unwrap().mouse);
+        assert!(Config::parse("mouse = 1").is_err());
+    }
+
+    #[test]
+    fn test_theme() {
+        use ratatui::style::Color;
+
+        assert_eq!(Config::parse("").unwrap().theme, Theme::default());
+
+        let config = Config::parse(
+            r##"
+            theme = "light"
+
+            [colors]
+            waiting = "#b8860b"
+            selection = "blue"
+            "##,
+        )
+        .unwrap();
+        let light = Theme::named("light").unwrap();
+        assert_eq!(config.theme.waiting, Color::Rgb(184, 134, 11));
+        assert_eq!(config.theme.selection, Color::Blue);
+        assert_eq!(config.theme.working, light.working);
+
+        for name in Theme::NAMES {
+            assert!(Config::parse(&format!("theme = \"{}\"", name)).is_ok());
+        }
+        assert!(Config::parse("theme = \"neon\"").is_err());
+        assert!(Config::parse("[colors]\nstash = \"no-such-color\"").is_err());
+        assert!(Config::parse("colors = \"dark\"").is_err());
+    }
+
+    #[test]
+    fn test_log_level() {
+        assert_eq!(Config::parse("").unwrap().log_level, LevelFilter::INFO);
+        assert_eq!(
+            Config::parse("log_level = \"DEBUG\"").unwrap().log_level,
+            LevelFilter::DEBUG
+        );
+        assert!(Config::parse("log_level = \"verbose\"").is_err());
+    }
+
+    #[test]
+    fn test_version() {
+        let config = Config::parse("version = 1\nauto_restart = true").unwrap();
+        assert!(config.auto_restart);
+        assert!(config.warnings.is_empty());
+        assert!(Config::parse("auto_restart = true")
+            .unwrap()
+            .warnings
+            .is_empty());
+        assert_eq!(Config::parse("version = 99").unwrap().warnings.len(), 1);
+        assert!(Config::parse("version = \"one\"").is_err());
+    }
+
+    #[test]
+    fn test_capture_budget() {
+        assert_eq!(
+            Config::parse("").unwrap().capture_budget,
+            DEFAULT_CAPTURE_BUDGET
+        );
+        assert_eq!(
+            Config::parse("capture_budget = 2").unwrap().capture_budget,
+            2.0
+        );
+        assert!(Config::parse("capture_budget = 0").is_err());
+    }
+
+    #[test]
+    fn test_preview_buffer_kb() {
+        assert_eq!(
+            Config::parse("").unwrap().preview_buffer_kb,
+            DEFAULT_PREVIEW_BUFFER_KB
+        );
+        assert_eq!(
+            Config::parse("preview_buffer_kb = 16")
+                .unwrap()
+                .preview_buffer_kb,
+            16
+        );
+        assert!(Config::parse("preview_buffer_kb = -1").is_err());
+        assert!(Config::parse("preview_buffer_kb = 1.5").is_err());
+    }
+
+    #[test]
+    fn test_preview_lines_and_sort_order() {
+        let config = Config::parse("").unwrap();
+        assert_eq!(config.preview_lines, DEFAULT_PREVIEW_LINES);
+        assert_eq!(config.sort_order, SortOrder::Attached);
+
+        let config = Config::parse("preview_lines = 40\nsort_order = \"path\"").unwrap();
+        assert_eq!(config.preview_lines, 40);
+        assert_eq!(config.sort_order, SortOrder::Path);
+        assert!(Config::parse("preview_lines = 0").is_err());
+        assert!(Config::parse("sort_order = \"status\"").is_err());
+    }
+
+    #[test]
+    fn test_intervals() {
+        let config = Config::parse("").unwrap();
+        assert_eq!(config.diff_stat_interval, DEFAULT_DIFF_STAT_INTERVAL);
+        assert_eq!(config.pr_checks_interval, DEFAULT_PR_CHECKS_INTERVAL);
+        assert_eq!(config.auto_fetch_interval, DEFAULT_AUTO_FETCH_INTERVAL);
+
+        let config = Config::parse("auto_fetch_interval = 900").unwrap();
+        assert_eq!(config.auto_fetch_interval, Duration::from_secs(900));
+        assert!(Config::parse("diff_stat_interval = 0").is_err());
+        assert!(Config::parse("pr_checks_interval = \"1m\"").is_err());
+    }
+
+    #[test]
+    fn test_worktree_path() {
+        assert_eq!(
+            Config::parse("").unwrap().worktree_path,
+            "../{repo}-{branch}"
+        );
+        assert_eq!(
+            Config::parse("worktree_path = \"~/worktrees/{repo}/{branch}\"")
+                .unwrap()
+                .worktree_path,
+            "~/worktrees/{repo}/{branch}"
+        );
+        assert_eq!(
+            Config::parse("issue_branch_template = \"fix/{issue}\"")
+                .unwrap()
+                .issue_branch_template,
+            "fix/{issue}"
+        );
+        assert_eq!(
+            Config::parse("session_name_template = \"{branch}\"")
+                .unwrap()
+                .session_name_template,
+            "{branch}"
+        );
+        assert_eq!(Config::parse("").unwrap().worktree_trash_days, 0);
+        assert_eq!(
+            Config::parse("worktree_trash_days = 7")
+                .unwrap()
+                .worktree_trash_days,
+            7
+        );
+    }
+
+    #[test]
+    fn test_expand_template() {
+        let ctx = TemplateContext {
+            branch: Some("feature/login".to_string()),
+            repo: Some("app".to_string()),
+            path: "~/src/app".to_string(),
+            last_commit: Some("Add login form".to_string()),
+            session: "app".to_string(),
+        };
+        assert_eq!(
+            expand_template("Rebase {branch} in {path} after '{last_commit}'", &ctx),
+            "Rebase feature/login in ~/src/app after 'Add login form'"
+        );
+        assert_eq!(
+            expand_template("{repo}:{branch}", &ctx),
+            "app:feature/login"
+        );
+        // Unknown placeholders and unbalanced braces are preserved
+        assert_eq!(
+            expand_template("{\"a\": {unknown}} {", &ctx),
+            "{\"a\": {unknown}} {"
+        );
+    }
+
+    #[test]
+    fn test_wrap_content() {
+        assert_eq!(
+            wrap_content("Review this:\n```\n{content}\n```", "fn main() {}"),
+            "Review this:\n```\nfn main() {}\n```"
+        );
+        assert_eq!(wrap_content("Explain\n", "x = 1"), "Explain\n\nx = 1");
+    }
+}
@@ -0,0 +1,129 @@
+//! Colors used in accessibility mode, and overrides of the theme's colors
+//!
+//! Every role defaults to a basic ANSI color that stays readable on dark and
+//! light terminals; any of them can be overridden with a color name
+//! (`"yellow"`, `"light-red"`), an ANSI index (`"208"`) or `"#rrggbb"`:
+//!
+//! ```toml
+//! accessibility = true
+//!
+//! [palette]
+//! waiting = "#ffd700"
+//! dim = "white"
+//! ```
+
+use std::str::FromStr;
+
+use anyhow::Result;
+use ratatui::style::Color;
+
+use super::{optional_str, Table};
+use crate::ui::Theme;
+
+/// Colors by role, used when `accessibility` is on
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Palette {
+    /// Agents that are working
+    pub working: Color,
+    /// Agents waiting for input
+    pub waiting: Color,
+    /// Idle agents
+    pub idle: Color,
+    /// Crashed agents and errors
+    pub crashed: Color,
+    /// Agents whose status is unknown
+    pub unknown: Color,
+    /// Header, branches and borders
+    pub accent: Color,
+    /// Hints and secondary text
+    pub dim: Color,
+}
+
+impl Default for Palette {
+    fn default() -> Self {
+        Self {
+            working: Color::Green,
+            waiting: Color::Yellow,
+            idle: Color::White,
+            crashed: Color::LightRed,
+            unknown: Color::Gray,
+            accent: Color::Cyan,
+            dim: Color::Gray,
+        }
+    }
+}
+
+impl Palette {
+    /// Parse the `[palette]` table, keeping defaults for missing roles
+    pub(super) fn from_table(table: &Table) -> Result<Self> {
+        let defaults = Self::default();
+        let color = |key: &str, default: Color| color(table, key, default);
+        Ok(Self {
+            working: color("working", defaults.working)?,
+            waiting: color("waiting", defaults.waiting)?,
+            idle: color("idle", defaults.idle)?,
+            crashed: color("crashed", defaults.crashed)?,
+            unknown: color("unknown", defaults.unknown)?,
+            accent: color("accent", defaults.accent)?,
+            dim: color("dim", defaults.dim)?,
+        })
+    }
+}
+
+/// `theme` with the colors set in the `[colors]` table
+pub(super) fn theme_colors(theme: Theme, table: &Table) -> Result<Theme> {
+    let color = |key: &str, default: Color| color(table, key, default);
+    Ok(Theme {
+        working: color("working", theme.working)?,
+        waiting: color("waiting", theme.waiting)?,
+        idle: color("idle", theme.idle)?,
+        crashed: color("crashed", theme.crashed)?,
+        unknown: color("unknown", theme.unknown)?,
+        selection: color("selection", theme.selection)?,
+        selection_text: color("selection_text", theme.selection_text)?,
+        accent: color("accent", theme.accent)?,
+        staged: color("staged", theme.staged)?,
+        unstaged: color("unstaged", theme.unstaged)?,
+        insertions: color("insertions", theme.insertions)?,
+        deletions: color("deletions", theme.deletions)?,
+        ahead: color("ahead", theme.ahead)?,
+        stash: color("stash", theme.stash)?,
+        conflict: color("conflict", theme.conflict)?,
+        warning: color("warning", theme.warning)?,
+        dim: color("dim", theme.dim)?,
+        text: color("text", theme.text)?,
+        dialog: color("dialog", theme.dialog)?,
+        highlight: color("highlight", theme.highlight)?,
+        error: color("error", theme.error)?,
+        success: color("success", theme.success)?,
+        special: color("special", theme.special)?,
+    })
+}
+
+/// Color set for `key`, or `default`
+fn color(table: &Table, key: &str, default: Color) -> Result<Color> {
+    match optional_str(table, key)? {
+        Some(value) => parse_color(&value)
+            .ok_or_else(|| anyhow::anyhow!("'{}' is not a color: {}", key, value)),
+        None => Ok(default),
+    }
+}
+
+/// Parse a color name, ANSI index or `#rrggbb`
+fn parse_color(value: &str) -> Option<Color> {
+    Color::from_str(value.trim()).ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_color() {
+        assert_eq!(parse_color("yellow"), Some(Color::Yellow));
+        assert_eq!(parse_color("light-red"), Some(Color::LightRed));
+        assert_eq!(parse_color("#ffd700"), Some(Color::Rgb(255, 215, 0)));
+        assert_eq!(parse_color("208"), Some(Color::Indexed(208)));
+        assert_eq!(parse_color("chartreuse-ish"), None);
+    }
+}
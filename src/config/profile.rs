@@ -0,0 +1,165 @@
+//! Per-project Claude launch profiles
+//!
+//! A profile maps repositories (by path glob) to the flags `claude` is started
+//! with:
+//!
+//! ```toml
+//! [[profiles]]
+//! path = "~/work/**"
+//! model = "opus"
+//! permission_mode = "acceptEdits"
+//! mcp_config = "~/work/.mcp.json"
+//! args = ["--verbose"]
+//! ```
+
+use std::path::Path;
+
+use anyhow::Result;
+
+use super::{optional_str, required_str, string_list, Table};
+
+/// Launch flags for sessions started in matching directories
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct LaunchProfile {
+    /// Path glob (`*`, `**`, `?`; leading `~` expands to home)
+    pub path: String,
+    /// `--model`
+    pub model: Option<String>,
+    /// `--permission-mode`
+    pub permission_mode: Option<String>,
+    /// `--mcp-config`
+    pub mcp_config: Option<String>,
+    /// Extra arguments appended verbatim
+    pub args: Vec<String>,
+}
+
+impl LaunchProfile {
+    /// Parse a `[[profiles]]` entry
+    pub(super) fn from_table(table: &Table) -> Result<Self> {
+        Ok(Self {
+            path: required_str(table, "path")?,
+            model: optional_str(table, "model")?,
+            permission_mode: optional_str(table, "permission_mode")?,
+            mcp_config: optional_str(table, "mcp_config")?,
+            args: string_list(table, "args")?,
+        })
+    }
+
+    /// Whether this profile applies to `dir` (or one of its ancestors)
+    pub fn matches(&self, dir: &Path) -> bool {
+        path_matches(&self.path, dir)
+    }
+
+    /// Shell command line that starts claude with this profile's flags
+    pub fn command(&self) -> String {
+        let mut parts = vec!["claude".to_string()];
+        let flags = [
+            ("--model", &self.model),
+            ("--permission-mode", &self.permission_mode),
+            ("--mcp-config", &self.mcp_config),
+        ];
+        for (flag, value) in flags {
+            if let Some(value) = value {
+                parts.push(flag.to_string());
+                parts.push(shell_quote(value));
+            }
+        }
+        parts.extend(self.args.iter().map(|a| shell_quote(a)));
+        parts.join(" ")
+    }
+}
+
+/// Whether `dir` or one of its ancestors matches a path glob
+pub(super) fn path_matches(glob: &str, dir: &Path) -> bool {
+    let pattern = expand_home(glob.trim_end_matches('/'));
+    dir.ancestors()
+        .any(|p| glob_match(&pattern, &p.to_string_lossy()))
+}
+
+/// Expand a leading `~` to the home directory
+pub(super) fn expand_home(path: &str) -> String {
+    match (path.strip_prefix('~'), dirs::home_dir()) {
+        (Some(rest), Some(home)) if rest.is_empty() || rest.starts_with('/') => {
+            format!("{}{}", home.display(), rest)
+        }
+        _ => path.to_string(),
+    }
+}
+
+/// Match a path against a glob: `?` and `*` stay within a path component,
+/// `**` crosses `/`
+fn glob_match(pattern: &str, text: &str) -> bool {
+    let p: Vec<char> = pattern.chars().collect();
+    let t: Vec<char> = text.chars().collect();
+    glob_match_from(&p, &t)
+}
+
+fn glob_match_from(p: &[char], t: &[char]) -> bool {
+    match p.first() {
+        None => t.is_empty(),
+        Some('*') if p.get(1) == Some(&'*') => {
+            let rest = &p[2..];
+            (0..=t.len()).any(|i| glob_match_from(rest, &t[i..]))
+        }
+        Some('*') => {
+            let rest = &p[1..];
+            let limit = t.iter().position(|&c| c == '/').unwrap_or(t.len());
+            (0..=limit).any(|i| glob_match_from(rest, &t[i..]))
+        }
+        Some('?') => t.first().is_some_and(|&c| c != '/') && glob_match_from(&p[1..], &t[1..]),
+        Some(&c) => t.first() == Some(&c) && glob_match_from(&p[1..], &t[1..]),
+    }
+}
+
+/// Quote a word for `sh` if it contains anything beyond safe characters
+pub fn shell_quote(word: &str) -> String {
+    let safe = !word.is_empty()
+        && word
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || "-_./=:@,+~".contains(c));
+    if safe {
+        word.to_string()
+    } else {
+        format!("'{}'", word.replace('\'', r"'\''"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_glob_match() {
+        assert!(glob_match("/src/*", "/src/app"));
+        assert!(!glob_match("/src/*", "/src/app/sub"));
+        assert!(glob_match("/src/**", "/src/app/sub"));
+        assert!(glob_match("/src/app-?", "/src/app-2"));
+        assert!(!glob_match("/src/app", "/src/application"));
+    }
+
+    #[test]
+    fn test_matches_ancestors() {
+        let profile = LaunchProfile {
+            path: "/work/*".to_string(),
+            ..Default::default()
+        };
+        assert!(profile.matches(Path::new("/work/api")));
+        assert!(profile.matches(Path::new("/work/api/crates/core")));
+        assert!(!profile.matches(Path::new("/home/me/api")));
+    }
+
+    #[test]
+    fn test_command() {
+        let profile = LaunchProfile {
+            path: "/work/*".to_string(),
+            model: Some("opus".to_string()),
+            permission_mode: Some("acceptEdits".to_string()),
+            mcp_config: None,
+            args: vec!["--append-system-prompt".to_string(), "Be terse".to_string()],
+        };
+        assert_eq!(
+            profile.command(),
+            "claude --model opus --permission-mode acceptEdits --append-system-prompt 'Be terse'"
+        );
+    }
+}
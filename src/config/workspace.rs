@@ -0,0 +1,48 @@
+//! Workspaces: named sets of directories started together
+//!
+//! Starting a workspace opens a Claude session in each of its directories
+//! that has no agent yet:
+//!
+//! ```toml
+//! [[workspaces]]
+//! name = "product"
+//! paths = ["~/repos/api", "~/repos/web", "~/repos/docs"]
+//! ```
+
+use std::path::PathBuf;
+
+use anyhow::Result;
+
+use super::profile::expand_home;
+use super::{required_str, string_list, Table};
+
+/// A named set of directories (`[[workspaces]]`)
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Workspace {
+    pub name: String,
+    /// Directories as written, `~` included
+    pub paths: Vec<String>,
+}
+
+impl Workspace {
+    /// Parse a `[[workspaces]]` entry
+    pub(super) fn from_table(table: &Table) -> Result<Self> {
+        let name = required_str(table, "name")?;
+        if name.trim().is_empty() {
+            anyhow::bail!("'name' must not be empty");
+        }
+        let paths = string_list(table, "paths")?;
+        if paths.is_empty() {
+            anyhow::bail!("'paths' must list at least one directory");
+        }
+        Ok(Self { name, paths })
+    }
+
+    /// The directories, with `~` expanded
+    pub fn dirs(&self) -> Vec<PathBuf> {
+        self.paths
+            .iter()
+            .map(|p| PathBuf::from(expand_home(p.trim_end_matches('/'))))
+            .collect()
+    }
+}
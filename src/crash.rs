@@ -0,0 +1,200 @@
+//! Crash reports
+//!
+//! When claude-tmux panics, or the TUI exits with an error, a report is
+//! written to `crash-<time>.txt` in the data directory: the error and
+//! backtrace, versions, a summary of the app state and the last log lines.
+//! Its path is printed once the terminal has been restored, so it can be
+//! attached to a bug report.
+
+use std::backtrace::Backtrace;
+use std::fs;
+use std::io::{stdout, Write};
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Mutex;
+
+use crossterm::{
+    event::DisableMouseCapture,
+    terminal::{disable_raw_mode, LeaveAlternateScreen},
+    ExecutableCommand,
+};
+
+use crate::i18n::trf;
+use crate::logging;
+use crate::store::{data_dir, now_unix};
+use crate::tmux::Tmux;
+
+/// Log lines included in a report
+const LOG_TAIL: usize = 50;
+
+/// Latest app state summary, recorded by the TUI after each draw
+static STATE: Mutex<String> = Mutex::new(String::new());
+
+/// Whether the terminal is in raw mode on the alternate screen
+static TERMINAL_ACTIVE: AtomicBool = AtomicBool::new(false);
+
+/// Whether the TUI draws on `/dev/tty` rather than stdout (`--pick`)
+static ON_TTY: AtomicBool = AtomicBool::new(false);
+
+/// Write a crash report whenever a thread panics
+///
+/// A panic on the main thread also restores the terminal before the panic
+/// message is printed; one on a background thread is only logged, since the
+/// TUI keeps running.
+pub fn install() {
+    let default_hook = std::panic::take_hook();
+    std::panic::set_hook(Box::new(move |info| {
+        let on_main_thread = std::thread::current().name() == Some("main");
+        tracing::error!("{}", info);
+        let path = write_report(&info.to_string(), &Backtrace::force_capture().to_string());
+        if !on_main_thread {
+            if let Some(path) = &path {
+                tracing::error!("crash report written to {}", path.display());
+            }
+            return;
+        }
+        restore_terminal();
+        default_hook(info);
+        print_report_path(path);
+    }));
+}
+
+/// Record whether the TUI currently owns the terminal, and whether it draws
+/// on `/dev/tty`
+pub fn set_terminal_active(active: bool, on_tty: bool) {
+    ON_TTY.store(on_tty, Ordering::Relaxed);
+    TERMINAL_ACTIVE.store(active, Ordering::Relaxed);
+}
+
+/// Remember a summary of the app state for the next report
+pub fn record_state(summary: String) {
+    if let Ok(mut state) = STATE.lock() {
+        *state = summary;
+    }
+}
+
+/// Write a report for an error the TUI exited with and print its path
+pub fn report_error(error: &anyhow::Error) {
+    tracing::error!("exiting: {:#}", error);
+    // anyhow's debug format includes the cause chain, and a backtrace when
+    // RUST_BACKTRACE or RUST_LIB_BACKTRACE is set
+    print_report_path(write_report(&format!("{:?}", error), ""));
+}
+
+fn restore_terminal() {
+    if TERMINAL_ACTIVE.swap(false, Ordering::Relaxed) {
+        let _ = disable_raw_mode();
+        // Where the TUI drew: when picking, stdout is the captured result
+        let output: Option<Box<dyn Write>> = if ON_TTY.load(Ordering::Relaxed) {
+            fs::OpenOptions::new()
+                .write(true)
+                .open("/dev/tty")
+                .ok()
+                .map(|tty| Box::new(tty) as Box<dyn Write>)
+        } else {
+            Some(Box::new(stdout()))
+        };
+        if let Some(mut output) = output {
+            let _ = output.execute(DisableMouseCapture);
+            let _ = output.execute(LeaveAlternateScreen);
+        }
+    }
+}
+
+fn print_report_path(path: Option<PathBuf>) {
+    if let Some(path) = path {
+        eprintln!(
+            "{}",
+            trf!(
+                "claude-tmux crashed; a report was written to {}",
+                path.display()
+            )
+        );
+    }
+}
+
+/// Write a report to the data directory, returning its path
+fn write_report(error: &str, backtrace: &str) -> Option<PathBuf> {
+    let time = logging::format_local(now_unix());
+    // Panics can happen while the state is being recorded
+    let state = STATE
+        .try_lock()
+        .map(|s| s.clone())
+        .unwrap_or_else(|_| "(unavailable)".to_string());
+    let log_lines = logging::recent_lines();
+    let log_tail = &log_lines[log_lines.len().saturating_sub(LOG_TAIL)..];
+    let report = render_report(&time, error, backtrace, &versions(), &state, log_tail);
+
+    let dir = data_dir();
+    fs::create_dir_all(&dir).ok()?;
+    let name = format!(
+        "crash-{}.txt",
+        time.replace(['-', ':'], "").replace(' ', "-")
+    );
+    let path = dir.join(name);
+    fs::write(&path, report).ok()?;
+    Some(path)
+}
+
+/// claude-tmux, tmux and OS versions
+fn versions() -> String {
+    let tmux = Tmux::version().unwrap_or_else(|| "tmux not found".to_string());
+    format!(
+        "claude-tmux {}\n{}\n{} {}",
+        env!("CARGO_PKG_VERSION"),
+        tmux,
+        std::env::consts::OS,
+        std::env::consts::ARCH
+    )
+}
+
+fn render_report(
+    time: &str,
+    error: &str,
+    backtrace: &str,
+    versions: &str,
+    state: &str,
+    log_tail: &[String],
+) -> String {
+    let mut report = format!(
+        "claude-tmux crash report, {}\n\n## Error\n\n{}\n",
+        time, error
+    );
+    if !backtrace.is_empty() {
+        report.push_str(&format!("\n## Backtrace\n\n{}\n", backtrace.trim_end()));
+    }
+    report.push_str(&format!("\n## Versions\n\n{}\n", versions));
+    if !state.is_empty() {
+        report.push_str(&format!("\n## State\n\n{}\n", state));
+    }
+    report.push_str(&format!("\n## Last {} log lines\n\n", log_tail.len()));
+    for line in log_tail {
+        report.push_str(line);
+        report.push('\n');
+    }
+    report
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_render_report() {
+        let report = render_report(
+            "2026-01-02 03:04:05",
+            "panicked at src/app/mod.rs:1:1:\nboom",
+            "",
+            "claude-tmux 0.1.0\ntmux 3.4\nlinux x86_64",
+            "mode: Normal",
+            &["2026-01-02 03:04:04 INFO  daemon: started".to_string()],
+        );
+        assert!(report.starts_with("claude-tmux crash report, 2026-01-02 03:04:05\n"));
+        assert!(report.contains("## Error\n\npanicked at src/app/mod.rs:1:1:\nboom\n"));
+        assert!(!report.contains("## Backtrace"));
+        assert!(report.contains("## State\n\nmode: Normal\n"));
+        assert!(
+            report.ends_with("## Last 1 log lines\n\n2026-01-02 03:04:04 INFO  daemon: started\n")
+        );
+    }
+}
@@ -0,0 +1,300 @@
+//! Background watcher (`claude-tmux daemon`)
+//!
+//! Runs outside the TUI and dispatches scheduled prompts when they become due.
+//! With `auto_restart = true` in the config, it also restarts agents that
+//! exited while working with `claude --continue`, and with `pane_border_labels = true` it keeps each
+//! agent pane's border title showing its status and branch. With
+//! `rename_windows = true` it names agent windows after their repository and
+//! branch. Unless `track_work_time = false`, it records how long agents work
+//! on each branch, and unless `usage_stats = false`, how they wait for input
+//! and the scheduled prompts it sends. Unless `status_snapshots = false`, it
+//! saves the pane of agents that start waiting or crash.
+//! Start it once per tmux server, e.g. from `~/.tmux.conf`:
+//!
+//! ```text
+//! run-shell -b "~/.cargo/bin/claude-tmux daemon"
+//! ```
+
+use std::collections::{HashMap, HashSet};
+use std::fs;
+use std::path::PathBuf;
+use std::process::Command;
+use std::thread;
+use std::time::Duration;
+
+use anyhow::{Context, Result};
+
+use crate::config::{self, expand_template, Config, TemplateContext};
+use crate::git::GitContext;
+use crate::logging;
+use crate::schedule::{self};
+use crate::session::{ClaudeCodeStatus, ClaudeInstance};
+use crate::snapshot::SnapshotWatch;
+use crate::stats::{self, Counter, InputWatch};
+use crate::store::{data_dir, now_unix};
+use crate::tmux::Tmux;
+use crate::worktime::Tracker;
+
+/// How often the daemon checks for due jobs
+const TICK: Duration = Duration::from_secs(5);
+
+/// Restarts allowed per pane within `RESTART_WINDOW_SECS` before giving up
+const MAX_RESTARTS: usize = 3;
+const RESTART_WINDOW_SECS: u64 = 600;
+
+fn pid_path() -> PathBuf {
+    data_dir().join("daemon.pid")
+}
+
+/// Returns true if a daemon process is currently running
+pub fn is_running() -> bool {
+    let Ok(content) = fs::read_to_string(pid_path()) else {
+        return false;
+    };
+    let Ok(pid) = content.trim().parse::<u32>() else {
+        return false;
+    };
+    Command::new("kill")
+        .args(["-0", &pid.to_string()])
+        .output()
+        .map(|o| o.status.success())
+        .unwrap_or(false)
+}
+
+/// Run the daemon loop (blocks forever)
+pub fn run() -> Result<()> {
+    if is_running() {
+        anyhow::bail!("claude-tmux daemon is already running");
+    }
+
+    fs::create_dir_all(data_dir()).context("Failed to create data directory")?;
+    fs::write(pid_path(), std::process::id().to_string()).context("Failed to write pid file")?;
+
+    let config = config::load_for_subcommand("daemon");
+    logging::init(config.log_level);
+    tracing::info!("daemon started (pid {})", std::process::id());
+    for warning in &config.warnings {
+        tracing::warn!("config: {}", warning);
+    }
+    let mut restarts: HashMap<String, Vec<u64>> = HashMap::new();
+    let mut last_status: HashMap<String, ClaudeCodeStatus> = HashMap::new();
+    let mut labels: HashMap<String, String> = HashMap::new();
+    let mut tracker = Tracker::new();
+    let mut input_watch = InputWatch::default();
+    let mut snapshot_watch = SnapshotWatch::default();
+
+    loop {
+        dispatch_due_jobs(config.usage_stats);
+        let sampling = config.track_work_time || config.usage_stats || config.status_snapshots;
+        if config.auto_restart || config.pane_border_labels || config.rename_windows || sampling {
+            let with_status = config.auto_restart || config.pane_border_labels || sampling;
+            if let Some(instances) = agent_instances(with_status) {
+                if config.auto_restart {
+                    record_statuses(&instances, &mut last_status);
+                    restart_crashed(&config, &mut restarts, &mut last_status);
+                }
+                if config.pane_border_labels {
+                    update_labels(&instances, config.ascii_glyphs(), &mut labels);
+                }
+                if config.rename_windows {
+                    rename_windows(&instances, &config.window_name_template);
+                }
+                if config.track_work_time {
+                    tracker.sample(&instances, now_unix());
+                }
+                if config.usage_stats {
+                    input_watch.sample(&instances, now_unix());
+                }
+                if config.status_snapshots {
+                    snapshot_watch.sample(&instances);
+                }
+            }
+        }
+        thread::sleep(TICK);
+    }
+}
+
+/// Remember the status each agent last had while running, by pane ID
+fn record_statuses(
+    instances: &[ClaudeInstance],
+    last_status: &mut HashMap<String, ClaudeCodeStatus>,
+) {
+    last_status.retain(|pane_id, _| instances.iter().any(|i| &i.pane_id == pane_id));
+    for instance in instances {
+        if instance.status != ClaudeCodeStatus::Crashed {
+            last_status.insert(instance.pane_id.clone(), instance.status);
+        }
+    }
+}
+
+/// Restart claude in panes where it crashed, with a per-pane crash-loop limit
+///
+/// Claude is quit at its prompt, so one that exited while working crashed;
+/// any other exit is left alone. The pane must be back at its shell, not
+/// running another program.
+fn restart_crashed(
+    config: &Config,
+    restarts: &mut HashMap<String, Vec<u64>>,
+    last_status: &mut HashMap<String, ClaudeCodeStatus>,
+) {
+    let panes = match Tmux::list_crashed_panes() {
+        Ok(panes) => panes,
+        Err(e) => {
+            tracing::warn!("{}", e);
+            return;
+        }
+    };
+
+    let now = now_unix();
+    for pane in panes {
+        if last_status.get(&pane.id) != Some(&ClaudeCodeStatus::Working) {
+            continue; // Quit, or not seen working: leave it for the user
+        }
+        if !pane.is_shell() {
+            tracing::debug!(
+                "{} runs {}, not restarting it",
+                pane.id,
+                pane.current_command
+            );
+            continue;
+        }
+        let history = restarts.entry(pane.id.clone()).or_default();
+        history.retain(|&t| now.saturating_sub(t) < RESTART_WINDOW_SECS);
+        if history.len() >= MAX_RESTARTS {
+            tracing::debug!("{} keeps crashing, not restarting it", pane.id);
+            continue; // Crash loop: leave it for the user
+        }
+
+        let command = config.claude_command(&[&pane.current_path]);
+        match Tmux::restart_claude(&pane.id, &command) {
+            Ok(_) => {
+                tracing::info!("restarted claude in {} ({})", pane.id, command);
+                history.push(now);
+                // Until it is seen working again
+                last_status.remove(&pane.id);
+            }
+            Err(e) => tracing::warn!("{}", e),
+        }
+    }
+}
+
+/// Agent instances with their git context, and their status when
+/// `with_status` is set (it costs a pane capture each)
+fn agent_instances(with_status: bool) -> Option<Vec<ClaudeInstance>> {
+    let mut instances = match Tmux::list_claude_panes() {
+        Ok(instances) => instances,
+        Err(e) => {
+            tracing::warn!("{}", e);
+            return None;
+        }
+    };
+
+    if with_status {
+        let running: Vec<String> = instances
+            .iter()
+            .filter(|i| i.status != ClaudeCodeStatus::Crashed)
+            .map(|i| i.pane_id.clone())
+            .collect();
+        let statuses = Tmux::capture_statuses(&running);
+        for instance in &mut instances {
+            if let Some(&status) = statuses.get(&instance.pane_id) {
+                instance.status = status;
+            }
+        }
+    }
+
+    let mut git_contexts: HashMap<PathBuf, Option<GitContext>> = HashMap::new();
+    for instance in &mut instances {
+        instance.git_context = git_contexts
+            .entry(instance.working_directory.clone())
+            .or_insert_with_key(|path| GitContext::detect(path))
+            .clone();
+    }
+    Some(instances)
+}
+
+/// Set the border title of every agent pane whose label changed
+///
+/// `labels` holds the last title set per pane, so unchanged panes cost no
+/// tmux command.
+fn update_labels(instances: &[ClaudeInstance], ascii: bool, labels: &mut HashMap<String, String>) {
+    for instance in instances {
+        let label = instance.border_label(ascii);
+        if labels.get(&instance.pane_id) == Some(&label) {
+            continue;
+        }
+        match Tmux::set_pane_label(&instance.pane_id, &label) {
+            Ok(_) => {
+                labels.insert(instance.pane_id.clone(), label);
+            }
+            Err(e) => tracing::warn!("{}", e),
+        }
+    }
+
+    labels.retain(|pane_id, _| instances.iter().any(|i| &i.pane_id == pane_id));
+}
+
+/// Rename windows containing agents after `template`, using the first agent
+/// pane of each window; windows outside a git repository keep their name
+fn rename_windows(instances: &[ClaudeInstance], template: &str) {
+    let mut renamed = HashSet::new();
+    for instance in instances {
+        if !renamed.insert((&instance.session_name, instance.window_index)) {
+            continue;
+        }
+        let Some(git) = &instance.git_context else {
+            continue;
+        };
+
+        let ctx = TemplateContext {
+            branch: Some(git.branch.clone()),
+            repo: GitContext::repo_name(&instance.working_directory),
+            path: instance.display_path(),
+            last_commit: None,
+            session: instance.session_name.clone(),
+        };
+        let name = expand_template(template, &ctx);
+        if name.is_empty() || name == instance.window_name {
+            continue;
+        }
+        match Tmux::rename_window(&instance.pane_id, &name) {
+            Ok(_) => tracing::debug!("renamed window of {} to {}", instance.pane_id, name),
+            Err(e) => tracing::warn!("{}", e),
+        }
+    }
+}
+
+/// Send every job that is due and drop it from the schedule, counting the
+/// sent prompts in the statistics when `count` is set
+fn dispatch_due_jobs(count: bool) {
+    let now = now_unix();
+    let due: Vec<_> = schedule::load_jobs()
+        .into_iter()
+        .filter(|j| j.due <= now)
+        .collect();
+    if due.is_empty() {
+        return;
+    }
+
+    let due_ids: Vec<u64> = due.iter().map(|j| j.id).collect();
+
+    // Remove first so a crash mid-dispatch never sends a prompt twice
+    if let Err(e) = schedule::remove_jobs(&due_ids) {
+        tracing::warn!("{}", e);
+        return;
+    }
+
+    let mut sent = 0;
+    for job in due {
+        match Tmux::send_prompt(&job.pane_id, &job.prompt) {
+            Ok(_) => {
+                tracing::info!("sent scheduled prompt {} to {}", job.id, job.label);
+                sent += 1;
+            }
+            Err(e) => tracing::warn!("failed to send job {} to {}: {}", job.id, job.label, e),
+        }
+    }
+    if count && sent > 0 {
+        stats::record(&[(Counter::PromptsSent, sent)]);
+    }
+}
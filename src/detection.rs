@@ -2,31 +2,47 @@ use crate::session::ClaudeCodeStatus;
 
 /// Detect Claude Code status from pane content.
 pub fn detect_status(content: &str) -> ClaudeCodeStatus {
+    classify(content).0
+}
+
+/// The rule `detect_status` decides by for this content, for the log.
+pub fn status_reason(content: &str) -> &'static str {
+    classify(content).1
+}
+
+/// Status of the pane content, along with the rule that decided it.
+fn classify(content: &str) -> (ClaudeCodeStatus, &'static str) {
     // Step 1: Detect input field by its visual structure
     if has_input_field(content) {
         // Step 2: Check if interruptable
         if content.contains("ctrl+c") && content.contains("to interrupt") {
-            return ClaudeCodeStatus::Working;
+            return (
+                ClaudeCodeStatus::Working,
+                "input field with \"ctrl+c to interrupt\"",
+            );
         }
-        return ClaudeCodeStatus::Idle;
+        return (ClaudeCodeStatus::Idle, "input field, not interruptible");
     }
 
     // No input field - check for permission prompt (legacy format)
     if content.contains("[y/n]") || content.contains("[Y/n]") {
-        return ClaudeCodeStatus::WaitingInput;
+        return (ClaudeCodeStatus::WaitingInput, "[y/n] prompt");
     }
 
     // Detect permission prompt (new format)
     if content.contains("Do you want to proceed?") {
-        return ClaudeCodeStatus::WaitingInput;
+        return (
+            ClaudeCodeStatus::WaitingInput,
+            "\"Do you want to proceed?\"",
+        );
     }
 
     // Detect AskUserQuestion menu interface
     if content.contains("Enter to select") && content.contains("to navigate") {
-        return ClaudeCodeStatus::WaitingInput;
+        return (ClaudeCodeStatus::WaitingInput, "question menu");
     }
 
-    ClaudeCodeStatus::Unknown
+    (ClaudeCodeStatus::Unknown, "no input field or prompt")
 }
 
 /// Detect input field: prompt line (❯) with border directly above it.
@@ -93,4 +109,14 @@ mod tests {
         let content = "random stuff";
         assert_eq!(detect_status(content), ClaudeCodeStatus::Unknown);
     }
+
+    #[test]
+    fn test_status_reason() {
+        let content = "* (ctrl+c to interrupt)\n─────\n❯ hello";
+        assert_eq!(
+            status_reason(content),
+            "input field with \"ctrl+c to interrupt\""
+        );
+        assert_eq!(status_reason("random stuff"), "no input field or prompt");
+    }
 }
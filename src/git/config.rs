@@ -0,0 +1,119 @@
+//! Effective git configuration of a working directory
+//!
+//! libgit2 reads per-worktree config (`extensions.worktreeConfig`) and
+//! `gitdir:`/`onbranch:` includes, but silently skips `hasconfig:` includes,
+//! a common way to switch identity between work and personal remotes. The
+//! values are therefore read from `git config --list` run in the working
+//! directory, which applies every rule exactly as git does, with libgit2's
+//! view as the fallback when git is not installed.
+
+use std::collections::HashMap;
+use std::path::Path;
+use std::process::Command;
+
+use git2::Repository;
+
+/// Config values as seen from one working directory
+#[derive(Debug, Default)]
+pub struct EffectiveConfig {
+    /// Values by normalized key; the last occurrence wins, as in git
+    values: HashMap<String, String>,
+}
+
+impl EffectiveConfig {
+    /// Load the config that applies in `path` (a worktree or subdirectory)
+    pub fn load(path: &Path, repo: &Repository) -> Self {
+        Self::from_git(path).unwrap_or_else(|| Self::from_libgit2(repo))
+    }
+
+    fn from_git(path: &Path) -> Option<Self> {
+        let output = Command::new("git")
+            .arg("-C")
+            .arg(path)
+            .args(["config", "--list", "-z"])
+            .output()
+            .ok()?;
+        output
+            .status
+            .success()
+            .then(|| Self::parse(&String::from_utf8_lossy(&output.stdout)))
+    }
+
+    fn from_libgit2(repo: &Repository) -> Self {
+        let mut config = Self::default();
+        let Ok(snapshot) = repo.config().and_then(|mut c| c.snapshot()) else {
+            return config;
+        };
+        if let Ok(mut entries) = snapshot.entries(None) {
+            while let Some(Ok(entry)) = entries.next() {
+                if let (Some(name), Some(value)) = (entry.name(), entry.value()) {
+                    config.values.insert(normalize_key(name), value.to_string());
+                }
+            }
+        }
+        config
+    }
+
+    /// Parse `git config --list -z` output: NUL-terminated entries, each a
+    /// key optionally followed by a newline and its value
+    fn parse(output: &str) -> Self {
+        let values = output
+            .split('\0')
+            .filter(|entry| !entry.is_empty())
+            .map(|entry| match entry.split_once('\n') {
+                Some((key, value)) => (normalize_key(key), value.to_string()),
+                // A key without a value is a boolean `true`
+                None => (normalize_key(entry), "true".to_string()),
+            })
+            .collect();
+        Self { values }
+    }
+
+    pub fn get(&self, key: &str) -> Option<&str> {
+        self.values.get(&normalize_key(key)).map(String::as_str)
+    }
+
+    /// A boolean value, using git's spellings of true and false
+    pub fn get_bool(&self, key: &str) -> Option<bool> {
+        match self.get(key)?.to_ascii_lowercase().as_str() {
+            "true" | "yes" | "on" | "1" => Some(true),
+            "false" | "no" | "off" | "0" | "" => Some(false),
+            _ => None,
+        }
+    }
+}
+
+/// Lowercase the section and variable names of a key, keeping the
+/// case-sensitive subsection (`branch.Feature.pushRemote` → `branch.Feature.pushremote`)
+fn normalize_key(key: &str) -> String {
+    let (Some(first), Some(last)) = (key.find('.'), key.rfind('.')) else {
+        return key.to_ascii_lowercase();
+    };
+    format!(
+        "{}{}{}",
+        key[..first].to_ascii_lowercase(),
+        &key[first..last],
+        key[last..].to_ascii_lowercase()
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_config_list() {
+        let config = EffectiveConfig::parse(
+            "user.name\nPersonal\0user.name\nWork\0commit.gpgsign\0\
+             branch.Feature.pushremote\nfork\0core.bare\nfalse\0",
+        );
+
+        assert_eq!(config.get("user.name"), Some("Work"));
+        assert_eq!(config.get("User.Name"), Some("Work"));
+        assert_eq!(config.get_bool("commit.gpgSign"), Some(true));
+        assert_eq!(config.get_bool("core.bare"), Some(false));
+        assert_eq!(config.get("branch.Feature.pushRemote"), Some("fork"));
+        assert_eq!(config.get("branch.feature.pushRemote"), None);
+        assert_eq!(config.get("user.email"), None);
+    }
+}
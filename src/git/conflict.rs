@@ -0,0 +1,181 @@
+//! Conflicted files of a stopped merge or rebase
+//!
+//! Conflicts are read from the index, where each conflicted path has up to
+//! three stages: the common ancestor, "ours" and "theirs". A missing side
+//! means that side deleted the file. Taking a side writes its version to the
+//! working tree (or deletes the file) and records it in the index, which
+//! resolves the conflict like `git checkout --ours/--theirs` then `git add`.
+//!
+//! During a rebase the sides are swapped as in git: "ours" is the branch being
+//! rebased onto, "theirs" the commit being replayed.
+
+use std::fs;
+use std::path::Path;
+
+use anyhow::{Context, Result};
+use git2::{IndexConflict, Repository};
+
+/// A path with unresolved conflicts
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ConflictedFile {
+    /// Path relative to the repository root
+    pub path: String,
+    /// Whether our side still has the file
+    pub ours: bool,
+    /// Whether their side still has the file
+    pub theirs: bool,
+}
+
+/// Which version of a conflicted file to keep
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ConflictSide {
+    Ours,
+    Theirs,
+}
+
+/// Paths with unresolved conflicts, sorted
+pub fn conflicted_files(path: &Path) -> Result<Vec<ConflictedFile>> {
+    let repo = Repository::discover(path).context("Failed to open repository")?;
+    let index = repo.index().context("Failed to get index")?;
+    let mut files = Vec::new();
+    for conflict in index.conflicts().context("Failed to read conflicts")? {
+        let conflict = conflict.context("Failed to read conflict")?;
+        let Some(entry) = conflict_entry(&conflict) else {
+            continue;
+        };
+        files.push(ConflictedFile {
+            path: String::from_utf8_lossy(&entry.path).into_owned(),
+            ours: conflict.our.is_some(),
+            theirs: conflict.their.is_some(),
+        });
+    }
+    files.sort_by(|a, b| a.path.cmp(&b.path));
+    Ok(files)
+}
+
+/// Resolve a conflicted file by keeping one side's version
+pub fn take_side(path: &Path, file: &str, side: ConflictSide) -> Result<()> {
+    let repo = Repository::discover(path).context("Failed to open repository")?;
+    let workdir = repo.workdir().context("Repository has no working tree")?;
+    let mut index = repo.index().context("Failed to get index")?;
+    let conflict = index
+        .conflicts()
+        .context("Failed to read conflicts")?
+        .filter_map(|c| c.ok())
+        .find(|c| conflict_entry(c).is_some_and(|e| e.path == file.as_bytes()))
+        .with_context(|| format!("{} has no conflict", file))?;
+    let entry = match side {
+        ConflictSide::Ours => conflict.our,
+        ConflictSide::Theirs => conflict.their,
+    };
+
+    let target = workdir.join(file);
+    match entry {
+        Some(entry) => {
+            let blob = repo
+                .find_blob(entry.id)
+                .with_context(|| format!("Failed to read {}", file))?;
+            if let Some(parent) = target.parent() {
+                fs::create_dir_all(parent)
+                    .with_context(|| format!("Failed to create {}", parent.display()))?;
+            }
+            fs::write(&target, blob.content())
+                .with_context(|| format!("Failed to write {}", file))?;
+        }
+        // That side deleted the file
+        None => {
+            if target.exists() {
+                fs::remove_file(&target).with_context(|| format!("Failed to delete {}", file))?;
+            }
+        }
+    }
+    stage_path(&repo, &mut index, file)
+}
+
+/// Mark a conflicted file resolved with its working tree content (`git add`)
+pub fn mark_resolved(path: &Path, file: &str) -> Result<()> {
+    let repo = Repository::discover(path).context("Failed to open repository")?;
+    let mut index = repo.index().context("Failed to get index")?;
+    stage_path(&repo, &mut index, file)
+}
+
+/// Record the working tree state of `file`, clearing its conflict
+fn stage_path(repo: &Repository, index: &mut git2::Index, file: &str) -> Result<()> {
+    let workdir = repo.workdir().context("Repository has no working tree")?;
+    if workdir.join(file).exists() {
+        index
+            .add_path(Path::new(file))
+            .with_context(|| format!("Failed to stage {}", file))?;
+    } else {
+        index
+            .remove_path(Path::new(file))
+            .with_context(|| format!("Failed to stage {}", file))?;
+    }
+    index.write().context("Failed to write index")
+}
+
+/// Any stage of a conflict, for its path
+fn conflict_entry(conflict: &IndexConflict) -> Option<&git2::IndexEntry> {
+    conflict
+        .our
+        .as_ref()
+        .or(conflict.their.as_ref())
+        .or(conflict.ancestor.as_ref())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::git::scratch::ScratchRepo;
+
+    #[test]
+    fn test_resolve_conflicts() {
+        let repo = ScratchRepo::new("conflict");
+        for name in ["a.txt", "b.txt", "c.txt"] {
+            repo.write(name, "base\n");
+        }
+        repo.git(&["add", "."]);
+        repo.git(&["commit", "--quiet", "-m", "base"]);
+        repo.git(&["branch", "other"]);
+        for name in ["a.txt", "b.txt", "c.txt"] {
+            repo.write(name, "main\n");
+        }
+        repo.git(&["commit", "--quiet", "-am", "main"]);
+        repo.git(&["checkout", "--quiet", "other"]);
+        repo.write("a.txt", "other\n");
+        repo.write("b.txt", "other\n");
+        repo.git(&["rm", "--quiet", "c.txt"]);
+        repo.git(&["commit", "--quiet", "-am", "other"]);
+        repo.git(&["merge", "--quiet", "main"]);
+        let path = repo.path.as_path();
+
+        let before = conflicted_files(path).unwrap();
+        take_side(path, "a.txt", ConflictSide::Theirs).unwrap();
+        take_side(path, "c.txt", ConflictSide::Ours).unwrap();
+        fs::write(path.join("b.txt"), "both\n").unwrap();
+        mark_resolved(path, "b.txt").unwrap();
+        let after = conflicted_files(path).unwrap();
+        let contents = (
+            fs::read_to_string(path.join("a.txt")).unwrap(),
+            path.join("c.txt").exists(),
+        );
+        let unknown = take_side(path, "a.txt", ConflictSide::Ours);
+
+        let file = |path: &str, ours: bool, theirs: bool| ConflictedFile {
+            path: path.to_string(),
+            ours,
+            theirs,
+        };
+        assert_eq!(
+            before,
+            vec![
+                file("a.txt", true, true),
+                file("b.txt", true, true),
+                file("c.txt", false, true),
+            ]
+        );
+        assert!(after.is_empty());
+        assert_eq!(contents, ("main\n".to_string(), false));
+        assert!(unknown.is_err());
+    }
+}
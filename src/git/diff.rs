@@ -0,0 +1,174 @@
+//! Working tree diff for the review viewer and the list's diffstat
+//!
+//! Flattens `HEAD..working tree` (staged, unstaged, and untracked changes)
+//! into display lines, or sums it up in a diffstat.
+
+use std::path::Path;
+
+use anyhow::{Context, Result};
+use git2::{Diff, DiffFormat, DiffOptions, Repository};
+
+/// Diffs longer than this are truncated
+const MAX_DIFF_LINES: usize = 5000;
+
+/// Kind of a line in a flattened diff
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DiffLineKind {
+    /// File header (path)
+    File,
+    /// Hunk header (`@@ -1,3 +1,4 @@`)
+    Hunk,
+    Added,
+    Removed,
+    Context,
+}
+
+/// A single line of a flattened diff
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DiffLine {
+    pub kind: DiffLineKind,
+    /// Line content without the trailing newline
+    pub text: String,
+    /// Path of the file this line belongs to
+    pub file: String,
+    /// Line number in the old file (removed/context lines)
+    pub old_lineno: Option<u32>,
+    /// Line number in the new file (added/context lines)
+    pub new_lineno: Option<u32>,
+}
+
+/// Files changed, lines added and lines removed in a diff
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DiffStat {
+    pub files: usize,
+    pub insertions: usize,
+    pub deletions: usize,
+}
+
+/// The working tree (including the index and untracked files) against HEAD
+fn head_to_working_tree(repo: &Repository) -> Result<Diff<'_>> {
+    let head_tree = repo.head().ok().and_then(|h| h.peel_to_tree().ok());
+
+    let mut opts = DiffOptions::new();
+    opts.include_untracked(true)
+        .recurse_untracked_dirs(true)
+        .show_untracked_content(true);
+
+    repo.diff_tree_to_workdir_with_index(head_tree.as_ref(), Some(&mut opts))
+        .context("Failed to compute diff")
+}
+
+/// Diffstat of the working tree against HEAD, `None` when there are no changes
+pub fn working_tree_diff_stat(path: &Path) -> Result<Option<DiffStat>> {
+    let repo = Repository::discover(path).context("Failed to open repository")?;
+    let stats = head_to_working_tree(&repo)?
+        .stats()
+        .context("Failed to compute diffstat")?;
+    Ok((stats.files_changed() > 0).then(|| DiffStat {
+        files: stats.files_changed(),
+        insertions: stats.insertions(),
+        deletions: stats.deletions(),
+    }))
+}
+
+/// Diff of the working tree (including the index and untracked files) against HEAD
+pub fn working_tree_diff(path: &Path) -> Result<Vec<DiffLine>> {
+    let repo = Repository::discover(path).context("Failed to open repository")?;
+    let diff = head_to_working_tree(&repo)?;
+
+    let mut lines: Vec<DiffLine> = Vec::new();
+    let mut current_file: Option<String> = None;
+
+    // The callback aborts the walk (returns false) once the limit is hit,
+    // which surfaces as an error we ignore.
+    let _ = diff.print(DiffFormat::Patch, |delta, _hunk, line| {
+        let file = delta
+            .new_file()
+            .path()
+            .or_else(|| delta.old_file().path())
+            .map(|p| p.display().to_string())
+            .unwrap_or_default();
+
+        if current_file.as_deref() != Some(file.as_str()) {
+            current_file = Some(file.clone());
+            lines.push(DiffLine {
+                kind: DiffLineKind::File,
+                text: file.clone(),
+                file: file.clone(),
+                old_lineno: None,
+                new_lineno: None,
+            });
+        }
+
+        let kind = match line.origin() {
+            '+' | '>' => DiffLineKind::Added,
+            '-' | '<' => DiffLineKind::Removed,
+            ' ' | '=' => DiffLineKind::Context,
+            'H' => DiffLineKind::Hunk,
+            _ => return true, // File headers and binary markers
+        };
+
+        let text = String::from_utf8_lossy(line.content())
+            .trim_end_matches(['\n', '\r'])
+            .to_string();
+        lines.push(DiffLine {
+            kind,
+            text,
+            file,
+            old_lineno: line.old_lineno(),
+            new_lineno: line.new_lineno(),
+        });
+
+        lines.len() < MAX_DIFF_LINES
+    });
+
+    Ok(lines)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_untracked_file_in_diff() {
+        let dir = std::env::temp_dir().join(format!("claude-tmux-diff-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        Repository::init(&dir).unwrap();
+        std::fs::write(dir.join("notes.txt"), "one\ntwo\n").unwrap();
+
+        let lines = working_tree_diff(&dir).unwrap();
+        let _ = std::fs::remove_dir_all(&dir);
+
+        assert_eq!(lines[0].kind, DiffLineKind::File);
+        assert_eq!(lines[0].file, "notes.txt");
+        let added: Vec<_> = lines
+            .iter()
+            .filter(|l| l.kind == DiffLineKind::Added)
+            .map(|l| (l.text.as_str(), l.new_lineno))
+            .collect();
+        assert_eq!(added, vec![("one", Some(1)), ("two", Some(2))]);
+    }
+
+    #[test]
+    fn test_diff_stat() {
+        let dir = std::env::temp_dir().join(format!("claude-tmux-stat-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        Repository::init(&dir).unwrap();
+        let clean = working_tree_diff_stat(&dir).unwrap();
+        std::fs::write(dir.join("a.txt"), "one\ntwo\n").unwrap();
+        std::fs::write(dir.join("b.txt"), "three\n").unwrap();
+
+        let stat = working_tree_diff_stat(&dir).unwrap();
+        let _ = std::fs::remove_dir_all(&dir);
+
+        assert_eq!(clean, None);
+        assert_eq!(
+            stat,
+            Some(DiffStat {
+                files: 2,
+                insertions: 3,
+                deletions: 0
+            })
+        );
+    }
+}
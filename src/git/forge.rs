@@ -0,0 +1,499 @@
+//! Forge integrations behind one shared client
+//!
+//! Pull request status, CI checks, pull request creation and issues go
+//! through `client()`, which picks the provider hosting the repository's
+//! first remote (GitHub for now) and keeps its use of the API in check:
+//! - reads are cached per repository and branch for `cache_seconds`, so the
+//!   action menu, the details and the CI poll share one answer
+//! - after a rate limit response, nothing is sent until the limit resets;
+//!   reads answer from the cache meanwhile, even when it is stale
+//! - changes (creating, merging, closing) drop the cached answers of their
+//!   repository
+//!
+//! A provider implements `Forge`; adding GitLab means implementing it and
+//! listing it in `Client::new`.
+
+use std::collections::HashMap;
+use std::fmt;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex, MutexGuard};
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+
+use anyhow::Result;
+use git2::Repository;
+
+use super::github::{get_remote_url, GitHub};
+use crate::config::ForgeSettings;
+
+/// Wait after a rate limit response that doesn't say when it resets,
+/// doubled after each one in a row
+const BACKOFF_BASE: Duration = Duration::from_secs(60);
+/// Longest wait after a rate limit response
+const BACKOFF_MAX: Duration = Duration::from_secs(3600);
+
+/// Result of creating a pull request
+#[derive(Debug)]
+pub struct PullRequestResult {
+    /// The URL of the created pull request
+    pub url: String,
+}
+
+/// Information about an existing pull request
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PullRequestInfo {
+    /// PR number
+    pub number: u64,
+    /// PR state (OPEN, CLOSED, MERGED)
+    pub state: String,
+    /// Whether the PR is mergeable (MERGEABLE, CONFLICTING, UNKNOWN)
+    pub mergeable: String,
+}
+
+/// An issue of the repository
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Issue {
+    pub number: u64,
+    pub title: String,
+    /// Description, empty when not fetched
+    pub body: String,
+}
+
+/// Outcome of a CI check, grouped as `gh pr checks` does
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum CheckBucket {
+    /// Failed or cancelled
+    Fail,
+    Pending,
+    Pass,
+    Skipping,
+}
+
+/// One CI check of a pull request
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CheckRun {
+    pub name: String,
+    pub bucket: CheckBucket,
+    /// Page of the check run
+    pub link: String,
+}
+
+/// CI checks of a branch's pull request, failing first
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct PullRequestChecks {
+    pub runs: Vec<CheckRun>,
+}
+
+impl PullRequestChecks {
+    /// Failing if any check failed, else pending if any is running, else passing
+    ///
+    /// None when the pull request has no checks.
+    pub fn overall(&self) -> Option<CheckBucket> {
+        self.runs
+            .iter()
+            .map(|r| r.bucket)
+            .filter(|b| *b != CheckBucket::Skipping)
+            .min()
+    }
+
+    pub fn count(&self, bucket: CheckBucket) -> usize {
+        self.runs.iter().filter(|r| r.bucket == bucket).count()
+    }
+
+    pub fn failing(&self) -> impl Iterator<Item = &CheckRun> {
+        self.runs.iter().filter(|r| r.bucket == CheckBucket::Fail)
+    }
+}
+
+/// Error returned when a forge refuses requests until its rate limit resets
+#[derive(Debug, Clone)]
+pub struct RateLimited {
+    /// Name of the forge
+    pub forge: &'static str,
+    /// When the limit resets, in seconds since the epoch, if known
+    pub reset: Option<u64>,
+}
+
+impl fmt::Display for RateLimited {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} API rate limit reached", self.forge)
+    }
+}
+
+impl std::error::Error for RateLimited {}
+
+/// A code forge hosting repositories: pull requests, CI checks and issues
+///
+/// Each method works on the repository containing `path` and, for pull
+/// requests, its checked out branch.
+pub trait Forge: Send + Sync {
+    /// Name shown in messages
+    fn name(&self) -> &'static str;
+    /// Whether a remote URL points to this forge
+    fn hosts(&self, remote_url: &str) -> bool;
+    /// Whether requests can be made (tooling installed and authenticated)
+    fn is_available(&self) -> bool;
+
+    /// The branch's pull request, None when it has none
+    fn pull_request(&self, path: &Path) -> Result<Option<PullRequestInfo>>;
+    /// CI checks of the branch's pull request, None when it has none
+    fn checks(&self, path: &Path) -> Result<Option<PullRequestChecks>>;
+    /// Open a pull request of the branch into `base_branch`
+    fn create_pull_request(
+        &self,
+        path: &Path,
+        title: &str,
+        body: &str,
+        base_branch: &str,
+    ) -> Result<PullRequestResult>;
+    /// Open the branch's pull request in the browser
+    fn view_pull_request(&self, path: &Path) -> Result<()>;
+    /// Open the checks of the branch's pull request in the browser
+    fn view_checks(&self, path: &Path) -> Result<()>;
+    /// Merge the branch's pull request
+    fn merge_pull_request(&self, path: &Path, delete_branch: bool) -> Result<()>;
+    /// Close the branch's pull request without merging
+    fn close_pull_request(&self, path: &Path) -> Result<()>;
+
+    /// Open issues, most recent first, without their bodies
+    fn list_issues(&self, path: &Path) -> Result<Vec<Issue>>;
+    /// An issue with its description
+    fn issue(&self, path: &Path, number: u64) -> Result<Issue>;
+}
+
+/// The client shared by every forge feature
+static CLIENT: Mutex<Option<Arc<Client>>> = Mutex::new(None);
+
+/// The shared client, with default settings until `configure` is called
+pub fn client() -> Arc<Client> {
+    lock(&CLIENT)
+        .get_or_insert_with(|| Arc::new(Client::new(&ForgeSettings::default())))
+        .clone()
+}
+
+/// Replace the shared client, e.g. after the config was reloaded
+///
+/// Its cache starts empty, and the token command runs again on first use.
+pub fn configure(settings: &ForgeSettings) {
+    *lock(&CLIENT) = Some(Arc::new(Client::new(settings)));
+}
+
+/// Repository and branch whose answers are cached together
+type CacheKey = (PathBuf, String);
+
+/// Answers of a forge, by repository and branch, with when they were fetched
+struct Cache<T> {
+    entries: HashMap<CacheKey, (Instant, T)>,
+}
+
+impl<T: Clone> Cache<T> {
+    fn new() -> Self {
+        Self {
+            entries: HashMap::new(),
+        }
+    }
+
+    /// The answer for `key`, if fetched less than `ttl` ago
+    fn fresh(&self, key: &CacheKey, ttl: Duration) -> Option<T> {
+        self.entries
+            .get(key)
+            .filter(|(at, _)| at.elapsed() < ttl)
+            .map(|(_, value)| value.clone())
+    }
+
+    /// The answer for `key`, however old
+    fn stale(&self, key: &CacheKey) -> Option<T> {
+        self.entries.get(key).map(|(_, value)| value.clone())
+    }
+
+    fn insert(&mut self, key: CacheKey, value: T) {
+        self.entries.insert(key, (Instant::now(), value));
+    }
+
+    /// Drop the answers about a repository
+    fn invalidate(&mut self, repo: &Path) {
+        self.entries.retain(|(path, _), _| path != repo);
+    }
+}
+
+/// Pause of requests after rate limit responses
+#[derive(Debug, Default)]
+struct Backoff {
+    /// No request is sent before this
+    until: Option<Instant>,
+    /// Rate limit responses in a row
+    strikes: u32,
+}
+
+impl Backoff {
+    /// Time left before requests may be sent again
+    fn remaining(&self) -> Option<Duration> {
+        self.until
+            .map(|until| until.saturating_duration_since(Instant::now()))
+            .filter(|left| !left.is_zero())
+    }
+
+    /// Pause after a rate limit response, until `reset` when known
+    fn strike(&mut self, reset: Option<u64>) -> Duration {
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.as_secs())
+            .unwrap_or(0);
+        let wait = backoff_delay(reset.map(|reset| reset.saturating_sub(now)), self.strikes);
+        self.strikes += 1;
+        self.until = Some(Instant::now() + wait);
+        wait
+    }
+
+    /// A request went through
+    fn clear(&mut self) {
+        self.until = None;
+        self.strikes = 0;
+    }
+}
+
+/// How long to pause after a rate limit response: until the reset when it
+/// is known, else longer with each response in a row
+fn backoff_delay(reset_in: Option<u64>, strikes: u32) -> Duration {
+    let wait = match reset_in {
+        // A second of margin, as the reset time is rounded down
+        Some(secs) => Duration::from_secs(secs + 1),
+        None => BACKOFF_BASE.saturating_mul(2u32.saturating_pow(strikes.min(16))),
+    };
+    wait.min(BACKOFF_MAX)
+}
+
+/// Forge requests of the whole program, cached and paced
+pub struct Client {
+    providers: Vec<Box<dyn Forge>>,
+    cache_ttl: Duration,
+    pull_requests: Mutex<Cache<Option<PullRequestInfo>>>,
+    checks: Mutex<Cache<Option<PullRequestChecks>>>,
+    backoff: Mutex<Backoff>,
+}
+
+impl Client {
+    fn new(settings: &ForgeSettings) -> Self {
+        Self {
+            providers: vec![Box::new(GitHub::new(settings.github_token_command.clone()))],
+            cache_ttl: settings.cache_ttl,
+            pull_requests: Mutex::new(Cache::new()),
+            checks: Mutex::new(Cache::new()),
+            backoff: Mutex::new(Backoff::default()),
+        }
+    }
+
+    /// The available forge hosting the repository containing `path`
+    pub fn forge(&self, path: &Path) -> Option<&dyn Forge> {
+        let url = get_remote_url(path)?;
+        self.providers
+            .iter()
+            .map(|p| p.as_ref())
+            .find(|p| p.hosts(&url))
+            .filter(|p| p.is_available())
+    }
+
+    /// Whether the repository containing `path` is on an available forge
+    pub fn supports(&self, path: &Path) -> bool {
+        self.forge(path).is_some()
+    }
+
+    /// The branch's pull request, None when it has none or the forge can't
+    /// be reached
+    pub fn pull_request(&self, path: &Path) -> Option<PullRequestInfo> {
+        self.read(&self.pull_requests, path, |forge| forge.pull_request(path))
+            .flatten()
+    }
+
+    /// CI checks of the branch's pull request, None when it has none or the
+    /// forge can't be reached
+    pub fn checks(&self, path: &Path) -> Option<PullRequestChecks> {
+        self.read(&self.checks, path, |forge| forge.checks(path))
+            .flatten()
+    }
+
+    pub fn create_pull_request(
+        &self,
+        path: &Path,
+        title: &str,
+        body: &str,
+        base_branch: &str,
+    ) -> Result<PullRequestResult> {
+        let result = self.send(path, |forge| {
+            forge.create_pull_request(path, title, body, base_branch)
+        });
+        self.invalidate(path);
+        result
+    }
+
+    pub fn view_pull_request(&self, path: &Path) -> Result<()> {
+        self.send(path, |forge| forge.view_pull_request(path))
+    }
+
+    pub fn view_checks(&self, path: &Path) -> Result<()> {
+        self.send(path, |forge| forge.view_checks(path))
+    }
+
+    pub fn merge_pull_request(&self, path: &Path, delete_branch: bool) -> Result<()> {
+        let result = self.send(path, |forge| forge.merge_pull_request(path, delete_branch));
+        self.invalidate(path);
+        result
+    }
+
+    pub fn close_pull_request(&self, path: &Path) -> Result<()> {
+        let result = self.send(path, |forge| forge.close_pull_request(path));
+        self.invalidate(path);
+        result
+    }
+
+    pub fn list_issues(&self, path: &Path) -> Result<Vec<Issue>> {
+        self.send(path, |forge| forge.list_issues(path))
+    }
+
+    pub fn issue(&self, path: &Path, number: u64) -> Result<Issue> {
+        self.send(path, |forge| forge.issue(path, number))
+    }
+
+    /// A cached answer, or a fresh one unless rate limited
+    ///
+    /// Failures other than rate limits are not cached, so they are retried
+    /// on the next read.
+    fn read<T: Clone>(
+        &self,
+        cache: &Mutex<Cache<T>>,
+        path: &Path,
+        fetch: impl FnOnce(&dyn Forge) -> Result<T>,
+    ) -> Option<T> {
+        let key = cache_key(path);
+        if let Some(value) = lock(cache).fresh(&key, self.cache_ttl) {
+            return Some(value);
+        }
+        match self.send(path, fetch) {
+            Ok(value) => {
+                lock(cache).insert(key, value.clone());
+                Some(value)
+            }
+            Err(e) => {
+                if e.downcast_ref::<RateLimited>().is_none() {
+                    tracing::debug!("forge: {}: {:#}", path.display(), e);
+                }
+                lock(cache).stale(&key)
+            }
+        }
+    }
+
+    /// Send a request to the repository's forge unless rate limited
+    fn send<T>(&self, path: &Path, request: impl FnOnce(&dyn Forge) -> Result<T>) -> Result<T> {
+        let Some(forge) = self.forge(path) else {
+            anyhow::bail!(
+                "No forge available for this repository (GitHub needs gh, authenticated)"
+            );
+        };
+        if let Some(left) = lock(&self.backoff).remaining() {
+            anyhow::bail!(
+                "{} API rate limit reached, try again in {}",
+                forge.name(),
+                format_wait(left)
+            );
+        }
+
+        let result = request(forge);
+        match result
+            .as_ref()
+            .err()
+            .and_then(|e| e.downcast_ref::<RateLimited>())
+        {
+            Some(limited) => {
+                let wait = lock(&self.backoff).strike(limited.reset);
+                tracing::warn!(
+                    "forge: {} rate limited, pausing requests for {}",
+                    forge.name(),
+                    format_wait(wait)
+                );
+            }
+            None => lock(&self.backoff).clear(),
+        }
+        result
+    }
+
+    /// Drop the cached answers about the repository containing `path`
+    fn invalidate(&self, path: &Path) {
+        let (repo, _) = cache_key(path);
+        lock(&self.pull_requests).invalidate(&repo);
+        lock(&self.checks).invalidate(&repo);
+    }
+}
+
+/// Lock a mutex, taking over a poisoned one: its data stays valid
+fn lock<T>(mutex: &Mutex<T>) -> MutexGuard<'_, T> {
+    mutex.lock().unwrap_or_else(|e| e.into_inner())
+}
+
+/// Repository root and checked out branch of `path`
+///
+/// Worktrees have roots of their own, so each keeps its own answers.
+fn cache_key(path: &Path) -> CacheKey {
+    let Ok(repo) = Repository::discover(path) else {
+        return (path.to_path_buf(), String::new());
+    };
+    let root = repo.workdir().unwrap_or(path).to_path_buf();
+    let branch = repo
+        .head()
+        .ok()
+        .and_then(|head| head.shorthand().map(str::to_string))
+        .unwrap_or_default();
+    (root, branch)
+}
+
+/// "45s", "3 min"
+fn format_wait(wait: Duration) -> String {
+    match wait.as_secs() {
+        secs if secs < 60 => format!("{}s", secs.max(1)),
+        secs => format!("{} min", secs.div_ceil(60)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_backoff_delay() {
+        assert_eq!(backoff_delay(Some(120), 0), Duration::from_secs(121));
+        assert_eq!(backoff_delay(Some(0), 3), Duration::from_secs(1));
+        assert_eq!(backoff_delay(None, 0), BACKOFF_BASE);
+        assert_eq!(backoff_delay(None, 2), BACKOFF_BASE * 4);
+        assert_eq!(backoff_delay(None, 40), BACKOFF_MAX);
+        assert_eq!(backoff_delay(Some(86_400), 0), BACKOFF_MAX);
+
+        let mut backoff = Backoff::default();
+        assert!(backoff.remaining().is_none());
+        backoff.strike(None);
+        assert!(backoff.remaining().is_some());
+        backoff.clear();
+        assert!(backoff.remaining().is_none());
+    }
+
+    #[test]
+    fn test_cache() {
+        let key = (PathBuf::from("/src/api"), "main".to_string());
+        let other = (PathBuf::from("/src/web"), "main".to_string());
+        let mut cache = Cache::new();
+        cache.insert(key.clone(), 1);
+        cache.insert(other.clone(), 2);
+
+        assert_eq!(cache.fresh(&key, Duration::from_secs(30)), Some(1));
+        assert_eq!(cache.fresh(&key, Duration::ZERO), None);
+        assert_eq!(cache.stale(&key), Some(1));
+
+        cache.invalidate(Path::new("/src/api"));
+        assert_eq!(cache.stale(&key), None);
+        assert_eq!(cache.stale(&other), Some(2));
+    }
+
+    #[test]
+    fn test_format_wait() {
+        assert_eq!(format_wait(Duration::from_millis(300)), "1s");
+        assert_eq!(format_wait(Duration::from_secs(45)), "45s");
+        assert_eq!(format_wait(Duration::from_secs(61)), "2 min");
+    }
+}
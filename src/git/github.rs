@@ -1,60 +1,280 @@
-//! GitHub CLI (gh) operations
+//! GitHub through its CLI (gh)
 //!
-//! Provides pull request management through the GitHub CLI tool.
+//! The GitHub provider of the forge client (see `forge`): pull requests, CI
+//! checks and issues through `gh`, which also handles authentication. A
+//! `github_token_command` in `[forge]` gives gh a token of its own instead,
+//! e.g. from a password manager. Also home to the repository helpers that pull
+//! requests build on: the remote, the default branch and suggested contents.
 
 use std::path::Path;
-use std::process::Command;
+use std::process::{Command, Output};
 use std::sync::OnceLock;
 
 use anyhow::{Context, Result};
 use git2::Repository;
+use serde_json::Value;
+
+use super::forge::{
+    CheckBucket, CheckRun, Forge, Issue, PullRequestChecks, PullRequestInfo, PullRequestResult,
+    RateLimited,
+};
+
+/// Most open issues listed by `list_issues`
+const MAX_LISTED_ISSUES: usize = 50;
+
+/// GitHub, through gh
+pub struct GitHub {
+    /// Shell command printing the token given to gh (`github_token_command`)
+    token_command: Option<String>,
+    /// Output of `token_command`, run on first use
+    token: OnceLock<Option<String>>,
+    /// Whether gh is installed and authenticated, checked on first use
+    available: OnceLock<bool>,
+}
 
-/// Cached result of gh CLI availability check
-static GH_AVAILABLE: OnceLock<bool> = OnceLock::new();
+impl GitHub {
+    pub fn new(token_command: Option<String>) -> Self {
+        Self {
+            token_command,
+            token: OnceLock::new(),
+            available: OnceLock::new(),
+        }
+    }
 
-/// Result of creating a pull request
-#[derive(Debug)]
-pub struct PullRequestResult {
-    /// The URL of the created pull request
-    pub url: String,
-}
+    /// A gh command running in `path`, with the configured token
+    fn gh(&self, path: &Path) -> Command {
+        let mut cmd = Command::new("gh");
+        cmd.current_dir(path);
+        if let Some(token) = self.token() {
+            cmd.env("GH_TOKEN", token);
+        }
+        cmd
+    }
+
+    fn token(&self) -> Option<&str> {
+        self.token
+            .get_or_init(|| {
+                let command = self.token_command.as_deref()?;
+                match Command::new("sh").args(["-c", command]).output() {
+                    Ok(output) if output.status.success() => {
+                        let token = String::from_utf8_lossy(&output.stdout).trim().to_string();
+                        (!token.is_empty()).then_some(token)
+                    }
+                    Ok(output) => {
+                        tracing::warn!(
+                            "github: github_token_command failed: {}",
+                            String::from_utf8_lossy(&output.stderr).trim()
+                        );
+                        None
+                    }
+                    Err(e) => {
+                        tracing::warn!("github: github_token_command failed: {}", e);
+                        None
+                    }
+                }
+            })
+            .as_deref()
+    }
+
+    /// Run a gh command that changes or opens something, failing with its
+    /// error output
+    fn run(&self, path: &Path, args: &[&str]) -> Result<Output> {
+        let name = args.iter().take(2).copied().collect::<Vec<_>>().join(" ");
+        let output = self
+            .gh(path)
+            .args(args)
+            .output()
+            .with_context(|| format!("Failed to execute gh {}", name))?;
+        if !output.status.success() {
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            self.check_rate_limit(path, &stderr)?;
+            anyhow::bail!("gh {} failed: {}", name, stderr.trim())
+        }
+        Ok(output)
+    }
 
-/// Information about an existing pull request
-#[derive(Debug, Clone)]
-pub struct PullRequestInfo {
-    /// PR number
-    pub number: u64,
-    /// PR state (OPEN, CLOSED, MERGED)
-    pub state: String,
-    /// Whether the PR is mergeable (MERGEABLE, CONFLICTING, UNKNOWN)
-    pub mergeable: String,
+    /// Fail with `RateLimited` when gh's error output says so
+    fn check_rate_limit(&self, path: &Path, stderr: &str) -> Result<()> {
+        if !is_rate_limit_error(stderr) {
+            return Ok(());
+        }
+        // Asking when the limit resets doesn't count against it
+        let reset = self
+            .gh(path)
+            .args(["api", "rate_limit", "--jq", ".resources.graphql.reset"])
+            .output()
+            .ok()
+            .filter(|output| output.status.success())
+            .and_then(|output| String::from_utf8_lossy(&output.stdout).trim().parse().ok());
+        Err(RateLimited {
+            forge: "GitHub",
+            reset,
+        }
+        .into())
+    }
 }
 
-/// Check if the GitHub CLI (gh) is available and authenticated.
-/// Result is cached for the lifetime of the program.
-pub fn is_gh_available() -> bool {
-    *GH_AVAILABLE.get_or_init(|| {
-        // Check if gh is installed
-        let version_check = Command::new("gh").arg("--version").output();
+impl Forge for GitHub {
+    fn name(&self) -> &'static str {
+        "GitHub"
+    }
+
+    fn hosts(&self, remote_url: &str) -> bool {
+        remote_url.contains("github.com")
+    }
 
-        if version_check.is_err() || !version_check.unwrap().status.success() {
-            return false;
+    fn is_available(&self) -> bool {
+        *self.available.get_or_init(|| {
+            let installed = Command::new("gh")
+                .arg("--version")
+                .output()
+                .is_ok_and(|output| output.status.success());
+            if !installed {
+                return false;
+            }
+            // A token of our own needs no `gh auth login`
+            if self.token().is_some() {
+                return true;
+            }
+            Command::new("gh")
+                .args(["auth", "status"])
+                .output()
+                .is_ok_and(|output| output.status.success())
+        })
+    }
+
+    fn pull_request(&self, path: &Path) -> Result<Option<PullRequestInfo>> {
+        let output = self
+            .gh(path)
+            .args(["pr", "view", "--json", "number,url,state,mergeable"])
+            .output()
+            .context("Failed to execute gh pr view")?;
+
+        if !output.status.success() {
+            // Most often: no pull request for this branch
+            self.check_rate_limit(path, &String::from_utf8_lossy(&output.stderr))?;
+            return Ok(None);
         }
 
-        // Check if gh is authenticated
-        let auth_check = Command::new("gh").args(["auth", "status"]).output();
+        // Format: {"number":123,"state":"OPEN","mergeable":"MERGEABLE"}
+        let Ok(json) = serde_json::from_slice::<Value>(&output.stdout) else {
+            return Ok(None);
+        };
+        let (Some(number), Some(state)) = (json["number"].as_u64(), json["state"].as_str()) else {
+            return Ok(None);
+        };
+        let state = state.to_string();
+        let mergeable = json["mergeable"].as_str().unwrap_or("UNKNOWN").to_string();
+
+        Ok(Some(PullRequestInfo {
+            number,
+            state,
+            mergeable,
+        }))
+    }
 
-        auth_check
-            .map(|output| output.status.success())
-            .unwrap_or(false)
-    })
+    fn checks(&self, path: &Path) -> Result<Option<PullRequestChecks>> {
+        // Exits non-zero while checks fail or are pending, but still prints them
+        let output = self
+            .gh(path)
+            .args(["pr", "checks", "--json", "name,bucket,link"])
+            .output()
+            .context("Failed to execute gh pr checks")?;
+        let json = String::from_utf8_lossy(&output.stdout);
+        if !json.trim_start().starts_with('[') {
+            self.check_rate_limit(path, &String::from_utf8_lossy(&output.stderr))?;
+            return Ok(None);
+        }
+        Ok(Some(parse_checks(&json)))
+    }
+
+    fn create_pull_request(
+        &self,
+        path: &Path,
+        title: &str,
+        body: &str,
+        base_branch: &str,
+    ) -> Result<PullRequestResult> {
+        let output = self.run(
+            path,
+            &[
+                "pr",
+                "create",
+                "--title",
+                title,
+                "--base",
+                base_branch,
+                "--body",
+                body,
+            ],
+        )?;
+        let url = String::from_utf8_lossy(&output.stdout).trim().to_string();
+        Ok(PullRequestResult { url })
+    }
+
+    fn view_pull_request(&self, path: &Path) -> Result<()> {
+        self.run(path, &["pr", "view", "--web"]).map(drop)
+    }
+
+    fn view_checks(&self, path: &Path) -> Result<()> {
+        self.run(path, &["pr", "checks", "--web"]).map(drop)
+    }
+
+    fn merge_pull_request(&self, path: &Path, delete_branch: bool) -> Result<()> {
+        // Use merge commit strategy
+        let mut args = vec!["pr", "merge", "--merge"];
+        if delete_branch {
+            args.push("--delete-branch");
+        }
+        self.run(path, &args).map(drop)
+    }
+
+    fn close_pull_request(&self, path: &Path) -> Result<()> {
+        self.run(path, &["pr", "close"]).map(drop)
+    }
+
+    fn list_issues(&self, path: &Path) -> Result<Vec<Issue>> {
+        // A template instead of JSON, so that titles need no unescaping
+        let limit = MAX_LISTED_ISSUES.to_string();
+        let output = self.run(
+            path,
+            &[
+                "issue",
+                "list",
+                "--state",
+                "open",
+                "--limit",
+                &limit,
+                "--json",
+                "number,title",
+                "--template",
+                "{{range .}}{{.number}}\t{{.title}}\n{{end}}",
+            ],
+        )?;
+        Ok(parse_issue_list(&String::from_utf8_lossy(&output.stdout)))
+    }
+
+    fn issue(&self, path: &Path, number: u64) -> Result<Issue> {
+        let output = self.run(
+            path,
+            &[
+                "issue",
+                "view",
+                &number.to_string(),
+                "--json",
+                "number,title,body",
+                "--template",
+                "{{.number}}\t{{.title}}\n{{.body}}",
+            ],
+        )?;
+        parse_issue(&String::from_utf8_lossy(&output.stdout))
+            .with_context(|| format!("Unexpected output for issue #{}", number))
+    }
 }
 
-/// Check if the remote URL points to GitHub
-pub fn is_github_remote(path: &Path) -> bool {
-    get_remote_url(path)
-        .map(|url| url.contains("github.com"))
-        .unwrap_or(false)
+/// Whether gh failed on a primary or secondary rate limit
+fn is_rate_limit_error(stderr: &str) -> bool {
+    stderr.to_ascii_lowercase().contains("rate limit")
 }
 
 /// Get the remote URL for the repository (first remote, usually "origin")
@@ -99,152 +319,213 @@ pub fn get_default_branch(path: &Path) -> Option<String> {
     Some("main".to_string())
 }
 
-/// Create a pull request using the GitHub CLI
-pub fn create_pull_request(
+/// Most commits listed in a suggested pull request body
+const MAX_LISTED_COMMITS: usize = 50;
+
+/// Suggested title and body for a pull request of the current branch into
+/// `base_branch`, like `gh pr create --fill`
+///
+/// A single commit gives its message. Several commits are listed in the body,
+/// under `summary` (e.g., of the agent's conversation) or else the branch
+/// name as title.
+pub fn suggest_pull_request(
     path: &Path,
-    title: &str,
-    body: &str,
     base_branch: &str,
-) -> Result<PullRequestResult> {
-    if !is_gh_available() {
-        anyhow::bail!("GitHub CLI (gh) is not available or not authenticated");
-    }
-
-    let mut cmd = Command::new("gh");
-    cmd.current_dir(path);
-    cmd.args(["pr", "create"]);
-    cmd.args(["--title", title]);
-    cmd.args(["--base", base_branch]);
-
-    if !body.is_empty() {
-        cmd.args(["--body", body]);
-    } else {
-        cmd.args(["--body", ""]);
-    }
-
-    let output = cmd.output().context("Failed to execute gh pr create")?;
-
-    if output.status.success() {
-        let url = String::from_utf8_lossy(&output.stdout).trim().to_string();
-        Ok(PullRequestResult { url })
-    } else {
-        let stderr = String::from_utf8_lossy(&output.stderr);
-        anyhow::bail!("gh pr create failed: {}", stderr.trim())
-    }
+    summary: Option<&str>,
+) -> (String, String) {
+    let repo = Repository::discover(path).ok();
+    let branch = repo
+        .as_ref()
+        .and_then(|r| r.head().ok())
+        .and_then(|h| h.shorthand().map(str::to_string))
+        .unwrap_or_default();
+    let commits = repo
+        .as_ref()
+        .and_then(|r| branch_commits(r, base_branch))
+        .unwrap_or_default();
+    compose_pull_request(&branch, &commits, summary)
 }
 
-/// Get information about a PR for the current branch (if one exists)
-pub fn get_pull_request_info(path: &Path) -> Option<PullRequestInfo> {
-    if !is_gh_available() {
-        return None;
-    }
-
-    let output = Command::new("gh")
-        .current_dir(path)
-        .args(["pr", "view", "--json", "number,url,state,mergeable"])
-        .output()
-        .ok()?;
+/// Summary and body of the commits on HEAD but not on `base_branch`, oldest first
+fn branch_commits(repo: &Repository, base_branch: &str) -> Option<Vec<(String, String)>> {
+    let remote = repo.remotes().ok()?.get(0).map(str::to_string);
+    let base = remote
+        .iter()
+        .map(|r| format!("refs/remotes/{}/{}", r, base_branch))
+        .chain([format!("refs/heads/{}", base_branch)])
+        .find_map(|name| repo.refname_to_id(&name).ok())?;
+    let mut walk = repo.revwalk().ok()?;
+    walk.push_head().ok()?;
+    walk.hide(base).ok()?;
+    let mut commits: Vec<(String, String)> = walk
+        .flatten()
+        .take(MAX_LISTED_COMMITS)
+        .filter_map(|oid| repo.find_commit(oid).ok())
+        .map(|c| {
+            (
+                c.summary().unwrap_or_default().to_string(),
+                c.body().unwrap_or_default().trim().to_string(),
+            )
+        })
+        .collect();
+    commits.reverse();
+    Some(commits)
+}
 
-    if !output.status.success() {
-        return None;
+fn compose_pull_request(
+    branch: &str,
+    commits: &[(String, String)],
+    summary: Option<&str>,
+) -> (String, String) {
+    if let [(title, body)] = commits {
+        return (title.clone(), body.clone());
     }
-
-    let json_str = String::from_utf8_lossy(&output.stdout);
-
-    // Simple JSON parsing without adding a dependency
-    // Format: {"number":123,"state":"OPEN","mergeable":"MERGEABLE"}
-    let number = extract_json_u64(&json_str, "number")?;
-    let state = extract_json_string(&json_str, "state")?;
-    let mergeable =
-        extract_json_string(&json_str, "mergeable").unwrap_or_else(|| "UNKNOWN".to_string());
-
-    Some(PullRequestInfo {
-        number,
-        state,
-        mergeable,
-    })
+    let title = match summary.map(str::trim).filter(|s| !s.is_empty()) {
+        Some(summary) => summary.to_string(),
+        None => humanize_branch(branch),
+    };
+    let body = commits
+        .iter()
+        .map(|(summary, _)| format!("- {}", summary))
+        .collect::<Vec<_>>()
+        .join("\n");
+    (title, body)
 }
 
-/// Open the PR for the current branch in the browser
-pub fn view_pull_request(path: &Path) -> Result<()> {
-    if !is_gh_available() {
-        anyhow::bail!("GitHub CLI (gh) is not available or not authenticated");
+/// "fix-login_page" -> "Fix login page"
+fn humanize_branch(branch: &str) -> String {
+    let words = branch.replace(['-', '_'], " ");
+    let mut chars = words.chars();
+    match chars.next() {
+        Some(first) => first.to_uppercase().chain(chars).collect(),
+        None => String::new(),
     }
+}
 
-    let output = Command::new("gh")
-        .current_dir(path)
-        .args(["pr", "view", "--web"])
-        .output()
-        .context("Failed to execute gh pr view")?;
+/// Parse the `gh pr checks --json name,bucket,link` array
+fn parse_checks(json: &str) -> PullRequestChecks {
+    let checks: Vec<Value> = serde_json::from_str(json).unwrap_or_default();
+    let mut runs: Vec<CheckRun> = checks
+        .iter()
+        .filter_map(|check| {
+            let bucket = match check["bucket"].as_str()? {
+                "pass" => CheckBucket::Pass,
+                "fail" | "cancel" => CheckBucket::Fail,
+                "skipping" => CheckBucket::Skipping,
+                _ => CheckBucket::Pending,
+            };
+            Some(CheckRun {
+                name: check["name"].as_str()?.to_string(),
+                bucket,
+                link: check["link"].as_str().unwrap_or_default().to_string(),
+            })
+        })
+        .collect();
+    runs.sort_by(|a, b| a.bucket.cmp(&b.bucket).then_with(|| a.name.cmp(&b.name)));
+    PullRequestChecks { runs }
+}
 
-    if output.status.success() {
-        Ok(())
-    } else {
-        let stderr = String::from_utf8_lossy(&output.stderr);
-        anyhow::bail!("gh pr view failed: {}", stderr.trim())
-    }
+/// Parse the `number<TAB>title` lines printed for `list_issues`
+fn parse_issue_list(output: &str) -> Vec<Issue> {
+    output
+        .lines()
+        .filter_map(|line| {
+            let (number, title) = line.split_once('\t')?;
+            Some(Issue {
+                number: number.trim().parse().ok()?,
+                title: title.trim().to_string(),
+                body: String::new(),
+            })
+        })
+        .collect()
 }
 
-/// Merge the PR for the current branch
-pub fn merge_pull_request(path: &Path, delete_branch: bool) -> Result<()> {
-    if !is_gh_available() {
-        anyhow::bail!("GitHub CLI (gh) is not available or not authenticated");
-    }
+/// Parse the `number<TAB>title` line and body printed for `issue`
+fn parse_issue(output: &str) -> Option<Issue> {
+    let (first, body) = output.split_once('\n').unwrap_or((output, ""));
+    let mut issue = parse_issue_list(first).pop()?;
+    issue.body = body.trim().to_string();
+    Some(issue)
+}
 
-    let mut cmd = Command::new("gh");
-    cmd.current_dir(path);
-    cmd.args(["pr", "merge", "--merge"]); // Use merge commit strategy
+#[cfg(test)]
+mod tests {
+    use super::*;
 
-    if delete_branch {
-        cmd.arg("--delete-branch");
+    fn commit(summary: &str, body: &str) -> (String, String) {
+        (summary.to_string(), body.to_string())
     }
 
-    let output = cmd.output().context("Failed to execute gh pr merge")?;
-
-    if output.status.success() {
-        Ok(())
-    } else {
-        let stderr = String::from_utf8_lossy(&output.stderr);
-        anyhow::bail!("gh pr merge failed: {}", stderr.trim())
+    #[test]
+    fn test_parse_checks() {
+        let checks = parse_checks(
+            r#"[{"bucket":"pass","link":"https://ci/1","name":"build"},{"bucket":"fail","link":"https://ci/2","name":"lint"},{"bucket":"pending","link":"","name":"e2e"},{"bucket":"skipping","link":"","name":"deploy"}]"#,
+        );
+        let names: Vec<&str> = checks.runs.iter().map(|r| r.name.as_str()).collect();
+        assert_eq!(names, vec!["lint", "e2e", "build", "deploy"]);
+        assert_eq!(checks.runs[0].link, "https://ci/2");
+        assert_eq!(checks.overall(), Some(CheckBucket::Fail));
+        assert_eq!(checks.count(CheckBucket::Pass), 1);
+        assert_eq!(checks.failing().count(), 1);
+
+        let checks = parse_checks(
+            r#"[{"bucket":"pass","link":"","name":"build"},{"bucket":"skipping","link":"","name":"deploy"}]"#,
+        );
+        assert_eq!(checks.overall(), Some(CheckBucket::Pass));
+        assert_eq!(parse_checks("[]").overall(), None);
     }
-}
 
-/// Close the PR for the current branch without merging
-pub fn close_pull_request(path: &Path) -> Result<()> {
-    if !is_gh_available() {
-        anyhow::bail!("GitHub CLI (gh) is not available or not authenticated");
+    #[test]
+    fn test_is_rate_limit_error() {
+        assert!(is_rate_limit_error(
+            "GraphQL: API rate limit exceeded for user ID 42."
+        ));
+        assert!(is_rate_limit_error(
+            "HTTP 403: You have exceeded a secondary rate limit."
+        ));
+        assert!(!is_rate_limit_error(
+            "no pull requests found for branch \"x\""
+        ));
     }
 
-    let output = Command::new("gh")
-        .current_dir(path)
-        .args(["pr", "close"])
-        .output()
-        .context("Failed to execute gh pr close")?;
-
-    if output.status.success() {
-        Ok(())
-    } else {
-        let stderr = String::from_utf8_lossy(&output.stderr);
-        anyhow::bail!("gh pr close failed: {}", stderr.trim())
+    #[test]
+    fn test_parse_issues() {
+        let issues = parse_issue_list("42\tFix login redirect\n7\tDark mode: {theme}\nnoise\n");
+        assert_eq!(issues.len(), 2);
+        assert_eq!(issues[0].number, 42);
+        assert_eq!(issues[1].title, "Dark mode: {theme}");
+
+        let issue =
+            parse_issue("42\tFix login redirect\nIt loops.\n\nSteps:\n1. Log in\n").unwrap();
+        assert_eq!(issue.title, "Fix login redirect");
+        assert_eq!(issue.body, "It loops.\n\nSteps:\n1. Log in");
+        assert_eq!(parse_issue("7\tNo body").unwrap().body, "");
+        assert!(parse_issue("").is_none());
     }
-}
-
-/// Simple helper to extract a string value from JSON
-fn extract_json_string(json: &str, key: &str) -> Option<String> {
-    let pattern = format!("\"{}\":\"", key);
-    let start = json.find(&pattern)? + pattern.len();
-    let rest = &json[start..];
-    let end = rest.find('"')?;
-    Some(rest[..end].to_string())
-}
 
-/// Simple helper to extract a u64 value from JSON
-fn extract_json_u64(json: &str, key: &str) -> Option<u64> {
-    let pattern = format!("\"{}\":", key);
-    let start = json.find(&pattern)? + pattern.len();
-    let rest = &json[start..].trim_start();
-    let end = rest
-        .find(|c: char| !c.is_ascii_digit())
-        .unwrap_or(rest.len());
-    rest[..end].parse().ok()
+    #[test]
+    fn test_compose_pull_request() {
+        let single = [commit("Fix login redirect", "It looped.")];
+        assert_eq!(
+            compose_pull_request("fix-login", &single, Some("ignored")),
+            ("Fix login redirect".to_string(), "It looped.".to_string())
+        );
+
+        let several = [commit("Add form", ""), commit("Validate email", "")];
+        assert_eq!(
+            compose_pull_request("feat/signup_form", &several, None),
+            (
+                "Feat/signup form".to_string(),
+                "- Add form\n- Validate email".to_string()
+            )
+        );
+        assert_eq!(
+            compose_pull_request("feat/signup_form", &several, Some("Signup form")).0,
+            "Signup form"
+        );
+        assert_eq!(
+            compose_pull_request("", &[], None),
+            (String::new(), String::new())
+        );
+    }
 }
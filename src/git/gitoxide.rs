@@ -0,0 +1,103 @@
+//! Read-only queries through gitoxide (`--features gix`)
+//!
+//! libgit2 computes status on one thread, which takes seconds in large
+//! monorepos; gix checks the index, the working tree and `HEAD` in
+//! parallel. Built with the `gix` feature, status, ahead/behind counts and
+//! the local branch list are read through gix, falling back to libgit2 when
+//! it fails. Everything that writes stays on libgit2.
+
+use std::path::Path;
+
+use gix::bstr::BString;
+use gix::status::index_worktree::iter::Summary;
+use gix::status::Item;
+
+/// Whether the checkout at `path` has (staged, unstaged) changes, untracked
+/// files counting as unstaged
+///
+/// Submodules are skipped, as with libgit2.
+pub fn status(path: &Path) -> Option<(bool, bool)> {
+    let repo = gix::discover(path).ok()?;
+    let items = repo
+        .status(gix::progress::Discard)
+        .ok()?
+        .index_worktree_submodules(None)
+        .into_iter(Vec::<BString>::new())
+        .ok()?;
+
+    let (mut staged, mut unstaged) = (false, false);
+    for item in items {
+        match item.ok()? {
+            Item::TreeIndex(_) => staged = true,
+            // Entries whose stat changed but content didn't have no summary
+            Item::IndexWorktree(item) => {
+                unstaged |= item.summary().is_some_and(|s| s != Summary::Conflict);
+            }
+        }
+        if staged && unstaged {
+            break;
+        }
+    }
+    Some((staged, unstaged))
+}
+
+/// Commits `local` has that `upstream` lacks, and the other way round
+pub fn ahead_behind(path: &Path, local: git2::Oid, upstream: git2::Oid) -> Option<(usize, usize)> {
+    let repo = gix::discover(path).ok()?;
+    let local = gix::ObjectId::try_from(local.as_bytes()).ok()?;
+    let upstream = gix::ObjectId::try_from(upstream.as_bytes()).ok()?;
+    let only_in = |tip: gix::ObjectId, hidden: gix::ObjectId| {
+        repo.rev_walk([tip])
+            .with_hidden([hidden])
+            .all()
+            .ok()
+            .map(|walk| walk.filter(Result::is_ok).count())
+    };
+    Some((only_in(local, upstream)?, only_in(upstream, local)?))
+}
+
+/// Names of the local branches of the repository at `path`, unsorted
+pub fn local_branches(path: &Path) -> Option<Vec<String>> {
+    let repo = gix::discover(path).ok()?;
+    let references = repo.references().ok()?;
+    let branches = references
+        .local_branches()
+        .ok()?
+        .filter_map(Result::ok)
+        .map(|reference| reference.name().shorten().to_string())
+        .collect();
+    Some(branches)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::git::scratch::ScratchRepo;
+
+    #[test]
+    fn test_matches_libgit2() {
+        let repo = ScratchRepo::new("gix");
+        let path = repo.path.as_path();
+        repo.commit("a.txt", "base\n");
+        let clean = status(path);
+        repo.git(&["checkout", "--quiet", "-b", "feature"]);
+        repo.git(&["commit", "--quiet", "--allow-empty", "-m", "one"]);
+        repo.write("b.txt", "new\n");
+        let untracked = status(path);
+        repo.git(&["add", "b.txt"]);
+        let staged = status(path);
+
+        let git2_repo = git2::Repository::open(path).unwrap();
+        let main = git2_repo.refname_to_id("refs/heads/main").unwrap();
+        let feature = git2_repo.refname_to_id("refs/heads/feature").unwrap();
+        let counts = ahead_behind(path, feature, main);
+        let mut branches = local_branches(path).unwrap();
+        branches.sort();
+
+        assert_eq!(clean, Some((false, false)));
+        assert_eq!(untracked, Some((false, true)));
+        assert_eq!(staged, Some((true, false)));
+        assert_eq!(counts, Some((1, 0)));
+        assert_eq!(branches, ["feature", "main"]);
+    }
+}
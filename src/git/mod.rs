@@ -2,23 +2,48 @@
 //!
 //! This module provides git functionality through libgit2 and GitHub CLI:
 //! - `GitContext`: Detects and caches git state for a working directory
-//! - `github`: GitHub CLI operations (PR management)
+//! - `conflict`: Conflicted files of a stopped merge or rebase
+//! - `config`: Effective config of a working directory (worktree config, includes)
+//! - `diff`: Working tree diff for the review viewer and diffstats
+//! - `forge`: Forge client shared by pull requests, CI checks and issues
+//! - `github`: GitHub provider through its CLI, and pull request helpers
+//! - `gitoxide`: Read-only queries through gix (`gix` feature)
 //! - `operations`: Core git operations (push, pull, fetch, commit, stage)
+//! - `queue`: One operation at a time per repository
 //! - `worktree`: Worktree and branch management
 
+mod config;
+mod conflict;
+mod diff;
+mod forge;
 mod github;
+#[cfg(feature = "gix")]
+mod gitoxide;
 mod operations;
+mod queue;
+#[cfg(test)]
+mod scratch;
 mod worktree;
 
 use std::path::{Path, PathBuf};
+use std::time::UNIX_EPOCH;
 
-use git2::{Repository, StatusOptions};
+use anyhow::{Context, Result};
+use git2::{ConfigLevel, Oid, Repository, StatusOptions};
 
 // Re-export public API
-pub use github::{
-    close_pull_request, create_pull_request, get_default_branch, get_pull_request_info,
-    is_gh_available, is_github_remote, merge_pull_request, view_pull_request, PullRequestInfo,
+pub use conflict::{conflicted_files, mark_resolved, take_side, ConflictSide, ConflictedFile};
+pub use diff::{working_tree_diff, working_tree_diff_stat, DiffLine, DiffLineKind, DiffStat};
+pub use forge::{
+    client as forge, configure as configure_forge, CheckBucket, Issue, PullRequestChecks,
+    PullRequestInfo,
+};
+pub use github::{get_default_branch, suggest_pull_request};
+pub use operations::{
+    approve_credentials, CancelToken, Credentials, CredentialsRequired, PassphraseRequired,
+    SyncOutcome,
 };
+pub use queue::{lock_repo, queued_operations, try_lock_repo, QueuedOperation};
 
 /// Git context for a session's working directory
 #[derive(Debug, Clone)]
@@ -41,6 +66,91 @@ pub struct GitContext {
     pub ahead: usize,
     /// Commits behind upstream
     pub behind: usize,
+    /// Default branch the current one is compared to (None on that branch itself)
+    pub default_branch: Option<String>,
+    /// Commits not yet on the default branch
+    pub ahead_of_default: usize,
+    /// Number of stashed changes
+    pub stash_count: usize,
+    /// When the repository was last fetched (unix seconds), if ever
+    pub last_fetched: Option<i64>,
+    /// This repository's auto-fetch setting (`None` to follow `auto_fetch`)
+    pub auto_fetch: Option<bool>,
+    /// The HEAD commit (None in an empty repository)
+    pub last_commit: Option<CommitInfo>,
+    /// Merge or rebase stopped halfway (e.g., on conflicts)
+    pub in_progress: Option<PendingOperation>,
+    /// Whether the index has unresolved conflicts
+    pub has_conflicts: bool,
+}
+
+/// A multi-step git operation waiting to be continued or aborted
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PendingOperation {
+    Merge,
+    Rebase,
+}
+
+impl PendingOperation {
+    fn from_state(state: git2::RepositoryState) -> Option<Self> {
+        use git2::RepositoryState;
+        match state {
+            RepositoryState::Merge => Some(Self::Merge),
+            RepositoryState::Rebase
+            | RepositoryState::RebaseInteractive
+            | RepositoryState::RebaseMerge => Some(Self::Rebase),
+            _ => None,
+        }
+    }
+
+    /// The git subcommand that continues or aborts it
+    pub fn command(self) -> &'static str {
+        match self {
+            Self::Merge => "merge",
+            Self::Rebase => "rebase",
+        }
+    }
+}
+
+/// Summary of a commit
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CommitInfo {
+    /// First line of the message
+    pub summary: String,
+    pub author: String,
+    /// Commit time (unix seconds)
+    pub time: i64,
+}
+
+impl CommitInfo {
+    fn from_commit(commit: &git2::Commit) -> Self {
+        Self {
+            summary: commit.summary().unwrap_or_default().to_string(),
+            author: commit.author().name().unwrap_or_default().to_string(),
+            time: commit.time().seconds(),
+        }
+    }
+
+    /// How long ago the commit was made, as of `now` (e.g., "5m", "3h", "2d")
+    pub fn age(&self, now: u64) -> String {
+        format_age(now.saturating_sub(self.time.max(0) as u64))
+    }
+}
+
+/// Format an elapsed time compactly in its largest unit ("<1m", "5m", "3h", "2d", "6w", "1y")
+pub fn format_age(secs: u64) -> String {
+    const UNITS: [(u64, &str); 5] = [
+        (365 * 86_400, "y"),
+        (7 * 86_400, "w"),
+        (86_400, "d"),
+        (3_600, "h"),
+        (60, "m"),
+    ];
+    UNITS
+        .iter()
+        .find(|(unit, _)| secs >= *unit)
+        .map(|(unit, suffix)| format!("{}{}", secs / unit, suffix))
+        .unwrap_or_else(|| "<1m".to_string())
 }
 
 impl GitContext {
@@ -74,43 +184,7 @@ impl GitContext {
         };
 
         // Check staged/unstaged state
-        let mut status_opts = StatusOptions::new();
-        status_opts
-            .include_untracked(true)
-            .include_ignored(false)
-            .exclude_submodules(true);
-
-        let (has_staged, has_unstaged) = repo
-            .statuses(Some(&mut status_opts))
-            .map(|statuses| {
-                let mut staged = false;
-                let mut unstaged = false;
-                for entry in statuses.iter() {
-                    let s = entry.status();
-                    // Index (staged) changes
-                    if s.intersects(
-                        git2::Status::INDEX_NEW
-                            | git2::Status::INDEX_MODIFIED
-                            | git2::Status::INDEX_DELETED
-                            | git2::Status::INDEX_RENAMED
-                            | git2::Status::INDEX_TYPECHANGE,
-                    ) {
-                        staged = true;
-                    }
-                    // Worktree (unstaged) changes
-                    if s.intersects(
-                        git2::Status::WT_NEW
-                            | git2::Status::WT_MODIFIED
-                            | git2::Status::WT_DELETED
-                            | git2::Status::WT_RENAMED
-                            | git2::Status::WT_TYPECHANGE,
-                    ) {
-                        unstaged = true;
-                    }
-                }
-                (staged, unstaged)
-            })
-            .unwrap_or((false, false));
+        let (has_staged, has_unstaged) = status(&repo);
 
         // Check if worktree
         let is_worktree = repo.is_worktree();
@@ -126,6 +200,39 @@ impl GitContext {
         // Check if upstream is configured and get ahead/behind
         let (has_upstream, ahead, behind) = get_upstream_info(&repo);
 
+        let (default_branch, ahead_of_default) = match get_default_branch_ahead(&repo, &branch) {
+            Some((name, ahead)) => (Some(name), ahead),
+            None => (None, 0),
+        };
+
+        // Each stash is an entry in the reflog of refs/stash
+        let stash_count = repo.reflog("refs/stash").map(|r| r.len()).unwrap_or(0);
+
+        // Every fetch rewrites FETCH_HEAD, in the worktree's or the common git dir
+        let last_fetched = [repo.path(), repo.commondir()]
+            .iter()
+            .filter_map(|dir| dir.join("FETCH_HEAD").metadata().ok()?.modified().ok())
+            .max()
+            .and_then(|time| time.duration_since(UNIX_EPOCH).ok())
+            .map(|since| since.as_secs() as i64);
+
+        let auto_fetch = repo
+            .config()
+            .ok()
+            .and_then(|config| config.get_bool(AUTO_FETCH_KEY).ok());
+
+        let last_commit = repo
+            .head()
+            .and_then(|head| head.peel_to_commit())
+            .ok()
+            .map(|commit| CommitInfo::from_commit(&commit));
+
+        let in_progress = PendingOperation::from_state(repo.state());
+        let has_conflicts = repo
+            .index()
+            .map(|index| index.has_conflicts())
+            .unwrap_or(false);
+
         Some(GitContext {
             branch,
             has_staged,
@@ -136,10 +243,91 @@ impl GitContext {
             has_remote,
             ahead,
             behind,
+            default_branch,
+            ahead_of_default,
+            stash_count,
+            last_fetched,
+            auto_fetch,
+            last_commit,
+            in_progress,
+            has_conflicts,
         })
     }
 }
 
+/// Git config key of a repository's auto-fetch setting
+const AUTO_FETCH_KEY: &str = "claude-tmux.autoFetch";
+
+/// Turn background fetches on or off for the repository of `path`
+///
+/// Stored in the repository's own config, so every worktree follows it.
+pub fn set_auto_fetch(path: &Path, enabled: bool) -> Result<()> {
+    let repo = Repository::discover(path).context("Failed to open repository")?;
+    repo.config()
+        .and_then(|config| config.open_level(ConfigLevel::Local))
+        .and_then(|mut config| config.set_bool(AUTO_FETCH_KEY, enabled))
+        .context("Failed to write repository config")
+}
+
+/// Summary line of the HEAD commit, if any
+pub fn last_commit_summary(path: &Path) -> Option<String> {
+    let repo = Repository::discover(path).ok()?;
+    let commit = repo.head().ok()?.peel_to_commit().ok()?;
+    commit.summary().map(|s| s.to_string())
+}
+
+/// Name of the checked-out branch, `None` on a detached HEAD
+pub fn current_branch(path: &Path) -> Option<String> {
+    let repo = Repository::discover(path).ok()?;
+    let head = repo.head().ok()?;
+    if !head.is_branch() {
+        return None;
+    }
+    head.shorthand().map(|s| s.to_string())
+}
+
+/// How many commits of the checkouts at `paths` no remote-tracking branch
+/// has, counting their HEADs and every local branch
+///
+/// Unlike ahead counts, this ignores which upstream is configured: a
+/// commit is safe once any remote has it. Checkouts of the same repository
+/// are counted together, and repositories without remotes count 0, as
+/// there's nowhere to push to.
+pub fn unpushed_commits<P: AsRef<Path>>(paths: &[P]) -> usize {
+    // HEADs to walk by repository, as worktrees share their branches
+    let mut repos: Vec<(PathBuf, Repository, Vec<Oid>)> = Vec::new();
+    for path in paths {
+        let Ok(repo) = Repository::discover(path) else {
+            continue;
+        };
+        if repo.remotes().map_or(true, |remotes| remotes.is_empty()) {
+            continue;
+        }
+        let head = repo.head().ok().and_then(|h| h.target());
+        let common = repo.commondir().to_path_buf();
+        match repos.iter_mut().find(|(dir, _, _)| *dir == common) {
+            Some((_, _, heads)) => heads.extend(head),
+            None => repos.push((common, repo, head.into_iter().collect())),
+        }
+    }
+
+    repos
+        .iter()
+        .map(|(_, repo, heads)| {
+            repo.revwalk()
+                .and_then(|mut walk| {
+                    for &head in heads {
+                        walk.push(head)?;
+                    }
+                    walk.push_glob("refs/heads/*")?;
+                    walk.hide_glob("refs/remotes/*")?;
+                    Ok(walk.count())
+                })
+                .unwrap_or(0)
+        })
+        .sum()
+}
+
 /// Get upstream info: (has_upstream, ahead, behind)
 fn get_upstream_info(repo: &Repository) -> (bool, usize, usize) {
     let head = match repo.head() {
@@ -163,7 +351,7 @@ fn get_upstream_info(repo: &Repository) -> (bool, usize, usize) {
 
     let upstream = match local_branch.upstream() {
         Ok(u) => u,
-        Err(_) => return (false, 0, 0), // No upstream configured
+        Err(_) => return unpushed_upstream_info(repo, branch_name, head.target()),
     };
 
     // Has upstream, now get ahead/behind
@@ -177,14 +365,130 @@ fn get_upstream_info(repo: &Repository) -> (bool, usize, usize) {
         None => return (true, 0, 0),
     };
 
-    match repo.graph_ahead_behind(local_oid, upstream_oid) {
-        Ok((ahead, behind)) => (true, ahead, behind),
-        Err(_) => (true, 0, 0),
+    match ahead_behind(repo, local_oid, upstream_oid) {
+        Some((ahead, behind)) => (true, ahead, behind),
+        None => (true, 0, 0),
+    }
+}
+
+/// Whether the checkout has (staged, unstaged) changes
+fn status(repo: &Repository) -> (bool, bool) {
+    #[cfg(feature = "gix")]
+    if let Some(status) = repo.workdir().and_then(gitoxide::status) {
+        return status;
+    }
+
+    let mut status_opts = StatusOptions::new();
+    status_opts
+        .include_untracked(true)
+        .include_ignored(false)
+        .exclude_submodules(true);
+
+    repo.statuses(Some(&mut status_opts))
+        .map(|statuses| {
+            let mut staged = false;
+            let mut unstaged = false;
+            for entry in statuses.iter() {
+                let s = entry.status();
+                // Index (staged) changes
+                if s.intersects(
+                    git2::Status::INDEX_NEW
+                        | git2::Status::INDEX_MODIFIED
+                        | git2::Status::INDEX_DELETED
+                        | git2::Status::INDEX_RENAMED
+                        | git2::Status::INDEX_TYPECHANGE,
+                ) {
+                    staged = true;
+                }
+                // Worktree (unstaged) changes
+                if s.intersects(
+                    git2::Status::WT_NEW
+                        | git2::Status::WT_MODIFIED
+                        | git2::Status::WT_DELETED
+                        | git2::Status::WT_RENAMED
+                        | git2::Status::WT_TYPECHANGE,
+                ) {
+                    unstaged = true;
+                }
+            }
+            (staged, unstaged)
+        })
+        .unwrap_or((false, false))
+}
+
+/// Commits `local` has that `upstream` lacks, and the other way round
+fn ahead_behind(
+    repo: &Repository,
+    local: git2::Oid,
+    upstream: git2::Oid,
+) -> Option<(usize, usize)> {
+    #[cfg(feature = "gix")]
+    if let Some(counts) = gitoxide::ahead_behind(repo.path(), local, upstream) {
+        return Some(counts);
+    }
+    repo.graph_ahead_behind(local, upstream).ok()
+}
+
+/// Upstream info of a branch whose upstream is configured but not pushed
+/// yet: it counts as having an upstream, ahead by the commits no branch of
+/// that remote has
+fn unpushed_upstream_info(
+    repo: &Repository,
+    branch_name: &str,
+    head: Option<git2::Oid>,
+) -> (bool, usize, usize) {
+    let remote = repo
+        .config()
+        .and_then(|config| config.get_string(&format!("branch.{}.remote", branch_name)));
+    let (Ok(remote), Some(head)) = (remote, head) else {
+        return (false, 0, 0); // No upstream configured
+    };
+    let ahead = repo
+        .revwalk()
+        .and_then(|mut walk| {
+            walk.push(head)?;
+            walk.hide_glob(&format!("refs/remotes/{}/*", remote))?;
+            Ok(walk.count())
+        })
+        .unwrap_or(0);
+    (true, ahead, 0)
+}
+
+/// Default branch and how many commits HEAD has that it lacks
+///
+/// The remote's default branch is preferred, else a local main or master.
+/// None when on the default branch or when it can't be found.
+fn get_default_branch_ahead(repo: &Repository, branch: &str) -> Option<(String, usize)> {
+    let head = repo.head().ok()?.target()?;
+    let remote = repo.remotes().ok()?.get(0).map(str::to_string);
+    let mut candidates = Vec::new();
+    if let Some(remote) = &remote {
+        if let Ok(reference) = repo.find_reference(&format!("refs/remotes/{}/HEAD", remote)) {
+            if let Ok(resolved) = reference.resolve() {
+                candidates.extend(resolved.name().map(str::to_string));
+            }
+        }
+        candidates.push(format!("refs/remotes/{}/main", remote));
+        candidates.push(format!("refs/remotes/{}/master", remote));
+    }
+    candidates.push("refs/heads/main".to_string());
+    candidates.push("refs/heads/master".to_string());
+
+    let (name, base) = candidates.iter().find_map(|refname| {
+        let oid = repo.refname_to_id(refname).ok()?;
+        let name = refname.rsplit('/').next()?.to_string();
+        Some((name, oid))
+    })?;
+    if name == branch {
+        return None;
     }
+    let (ahead, _) = ahead_behind(repo, head, base)?;
+    Some((name, ahead))
 }
 
 #[cfg(test)]
 mod tests {
+    use super::scratch::ScratchRepo;
     use super::*;
 
     #[test]
@@ -194,4 +498,91 @@ mod tests {
         // but we can't guarantee it, so just test the function doesn't panic
         let _ = GitContext::detect(&dir);
     }
+
+    #[test]
+    fn test_stash_and_ahead_of_default() {
+        let repo = ScratchRepo::new("ahead");
+        repo.commit("a.txt", "base\n");
+        let on_main = GitContext::detect(&repo.path).unwrap();
+
+        repo.git(&["checkout", "--quiet", "-b", "feature"]);
+        for message in ["one", "two"] {
+            repo.write("a.txt", message);
+            repo.git(&["commit", "--quiet", "-am", message]);
+        }
+        for change in ["x", "y"] {
+            repo.write("a.txt", change);
+            repo.git(&["stash", "--quiet"]);
+        }
+        let on_feature = GitContext::detect(&repo.path).unwrap();
+
+        assert_eq!((on_main.default_branch, on_main.stash_count), (None, 0));
+        assert_eq!(on_feature.default_branch.as_deref(), Some("main"));
+        assert_eq!(on_feature.ahead_of_default, 2);
+        assert_eq!(on_feature.stash_count, 2);
+    }
+
+    #[test]
+    fn test_unpushed_commits() {
+        let repo = ScratchRepo::new("unpushed");
+        for message in ["one", "two", "three"] {
+            repo.git(&["commit", "--quiet", "--allow-empty", "-m", message]);
+        }
+        let without_remote = unpushed_commits(&[&repo.path]);
+        repo.git(&["remote", "add", "origin", "https://example.com/repo.git"]);
+        let never_pushed = unpushed_commits(&[&repo.path]);
+        // Pushed to some other branch of the remote
+        repo.git(&["update-ref", "refs/remotes/origin/wip", "HEAD~1"]);
+        let partly_pushed = unpushed_commits(&[&repo.path]);
+        // A branch that isn't checked out counts too, its commits once
+        repo.git(&["checkout", "--quiet", "-b", "side"]);
+        repo.git(&["commit", "--quiet", "--allow-empty", "-m", "four"]);
+        repo.git(&["checkout", "--quiet", "main"]);
+        let other_branch = unpushed_commits(&[&repo.path, &repo.path]);
+
+        assert_eq!(without_remote, 0);
+        assert_eq!(never_pushed, 3);
+        assert_eq!(partly_pushed, 1);
+        assert_eq!(other_branch, 2);
+    }
+
+    #[test]
+    fn test_auto_fetch_setting() {
+        let path =
+            std::env::temp_dir().join(format!("claude-tmux-autofetch-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&path);
+        std::fs::create_dir_all(&path).unwrap();
+        Repository::init(&path).unwrap();
+        let before = GitContext::detect(&path).unwrap();
+        set_auto_fetch(&path, true).unwrap();
+        let enabled = GitContext::detect(&path).unwrap();
+        set_auto_fetch(&path, false).unwrap();
+        let disabled = GitContext::detect(&path).unwrap();
+        let _ = std::fs::remove_dir_all(&path);
+
+        assert_eq!(before.auto_fetch, None);
+        assert_eq!(before.last_fetched, None);
+        assert_eq!(enabled.auto_fetch, Some(true));
+        assert_eq!(disabled.auto_fetch, Some(false));
+    }
+
+    #[test]
+    fn test_format_age() {
+        assert_eq!(format_age(0), "<1m");
+        assert_eq!(format_age(59), "<1m");
+        assert_eq!(format_age(300), "5m");
+        assert_eq!(format_age(3 * 3_600 + 59 * 60), "3h");
+        assert_eq!(format_age(2 * 86_400), "2d");
+        assert_eq!(format_age(45 * 86_400), "6w");
+        assert_eq!(format_age(400 * 86_400), "1y");
+
+        let commit = CommitInfo {
+            summary: "Fix login".to_string(),
+            author: "Ada".to_string(),
+            time: 1_000,
+        };
+        assert_eq!(commit.age(4_600), "1h");
+        // Clock skew: commits from the future are just made
+        assert_eq!(commit.age(500), "<1m");
+    }
 }
@@ -0,0 +1,247 @@
+//! One git operation at a time per repository
+//!
+//! Pushes, pulls and fetches started from the TUI, auto-fetches and syncs
+//! run on worker threads; two of them in the same repository could
+//! interleave their updates of refs, the index or the working tree. Each
+//! takes the repository's lock first with `lock_repo`, waiting in line
+//! behind the operation holding it. Worktrees share the lock of their
+//! repository, as they share its refs.
+//!
+//! Operations run on the UI thread (staging, committing, worktrees) don't
+//! wait: `try_lock_repo` fails at once while another operation holds the
+//! lock. `queued_operations` lists the operations waiting, for the status
+//! bar.
+
+use std::path::{Path, PathBuf};
+use std::sync::{Condvar, Mutex, MutexGuard};
+use std::time::Duration;
+
+use anyhow::Result;
+use git2::Repository;
+
+use super::CancelToken;
+use crate::i18n::trf;
+
+/// How often a waiting operation checks whether it was cancelled
+const CANCEL_CHECK: Duration = Duration::from_millis(200);
+
+/// An operation holding or waiting for a repository's lock
+#[derive(Debug)]
+struct Entry {
+    id: u64,
+    /// Repository's common git directory
+    repo: PathBuf,
+    /// What the operation does, e.g. "pulling api"
+    operation: String,
+}
+
+#[derive(Debug)]
+struct Queue {
+    next_id: u64,
+    running: Vec<Entry>,
+    /// In arrival order
+    waiting: Vec<Entry>,
+}
+
+static QUEUE: Mutex<Queue> = Mutex::new(Queue {
+    next_id: 0,
+    running: Vec::new(),
+    waiting: Vec::new(),
+});
+static RELEASED: Condvar = Condvar::new();
+
+/// An operation waiting for another one in its repository
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct QueuedOperation {
+    /// What the operation does, e.g. "pulling api"
+    pub operation: String,
+    /// The operation it waits for
+    pub behind: String,
+}
+
+/// A repository's lock, released when dropped
+#[derive(Debug)]
+pub struct RepoLock {
+    id: u64,
+}
+
+impl Drop for RepoLock {
+    fn drop(&mut self) {
+        lock(&QUEUE).running.retain(|e| e.id != self.id);
+        RELEASED.notify_all();
+    }
+}
+
+/// Take the lock of the repository of `path`, waiting for the operations
+/// before it in that repository
+///
+/// Fails if `cancel` is cancelled while waiting.
+pub fn lock_repo(path: &Path, operation: &str, cancel: &CancelToken) -> Result<RepoLock> {
+    let repo = repo_key(path);
+    let mut queue = lock(&QUEUE);
+    let id = queue.next_id;
+    queue.next_id += 1;
+    queue.waiting.push(Entry {
+        id,
+        repo: repo.clone(),
+        operation: operation.to_string(),
+    });
+
+    loop {
+        let first = queue.waiting.iter().find(|e| e.repo == repo).map(|e| e.id);
+        if first == Some(id) && !queue.running.iter().any(|e| e.repo == repo) {
+            let position = queue.waiting.iter().position(|e| e.id == id);
+            if let Some(entry) = position.map(|i| queue.waiting.remove(i)) {
+                queue.running.push(entry);
+            }
+            return Ok(RepoLock { id });
+        }
+        if cancel.is_cancelled() {
+            queue.waiting.retain(|e| e.id != id);
+            drop(queue);
+            // The next in line may be able to go
+            RELEASED.notify_all();
+            anyhow::bail!("cancelled");
+        }
+        queue = RELEASED
+            .wait_timeout(queue, CANCEL_CHECK)
+            .map(|(guard, _)| guard)
+            .unwrap_or_else(|e| e.into_inner().0);
+    }
+}
+
+/// Take the lock of the repository of `path` if no other operation holds
+/// it or waits for it
+pub fn try_lock_repo(path: &Path, operation: &str) -> Result<RepoLock> {
+    let repo = repo_key(path);
+    let mut queue = lock(&QUEUE);
+    if let Some(busy) = queue
+        .running
+        .iter()
+        .chain(&queue.waiting)
+        .find(|e| e.repo == repo)
+    {
+        anyhow::bail!(trf!(
+            "wait for {} to finish in this repository",
+            busy.operation
+        ));
+    }
+    let id = queue.next_id;
+    queue.next_id += 1;
+    queue.running.push(Entry {
+        id,
+        repo,
+        operation: operation.to_string(),
+    });
+    Ok(RepoLock { id })
+}
+
+/// Operations waiting for their repository, in arrival order
+pub fn queued_operations() -> Vec<QueuedOperation> {
+    let queue = lock(&QUEUE);
+    queue
+        .waiting
+        .iter()
+        .filter_map(|entry| {
+            let behind = queue
+                .running
+                .iter()
+                .chain(&queue.waiting)
+                .find(|e| e.repo == entry.repo && e.id != entry.id)?;
+            Some(QueuedOperation {
+                operation: entry.operation.clone(),
+                behind: behind.operation.clone(),
+            })
+        })
+        .collect()
+}
+
+/// The common git directory of the repository of `path`, shared by its
+/// worktrees, or `path` itself outside a repository
+fn repo_key(path: &Path) -> PathBuf {
+    let dir = Repository::discover(path)
+        .map(|repo| repo.commondir().to_path_buf())
+        .unwrap_or_else(|_| path.to_path_buf());
+    dir.canonicalize().unwrap_or(dir)
+}
+
+fn lock<T>(mutex: &Mutex<T>) -> MutexGuard<'_, T> {
+    mutex.lock().unwrap_or_else(|e| e.into_inner())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::mpsc;
+    use std::thread;
+
+    use super::*;
+
+    /// A directory of its own per test, outside any repository
+    fn repo(name: &str) -> PathBuf {
+        PathBuf::from(format!("/nonexistent/claude-tmux-queue-{}", name))
+    }
+
+    fn queued_in(path: &Path) -> Vec<QueuedOperation> {
+        let name = path.file_name().unwrap().to_string_lossy().to_string();
+        queued_operations()
+            .into_iter()
+            .filter(|q| q.operation.ends_with(&name))
+            .collect()
+    }
+
+    #[test]
+    fn test_lock_waits_in_line() {
+        let path = repo("line");
+        let held = lock_repo(&path, "fetching queue-line", &CancelToken::new()).unwrap();
+        assert!(try_lock_repo(&path, "committing").is_err());
+        assert!(try_lock_repo(&repo("other"), "committing").is_ok());
+
+        let (tx, rx) = mpsc::channel();
+        let worker_path = path.clone();
+        let worker = thread::spawn(move || {
+            let _lock = lock_repo(
+                &worker_path,
+                "pulling claude-tmux-queue-line",
+                &CancelToken::new(),
+            )
+            .unwrap();
+            tx.send(()).unwrap();
+        });
+
+        while queued_in(&path).is_empty() {
+            thread::sleep(Duration::from_millis(10));
+        }
+        assert_eq!(
+            queued_in(&path),
+            vec![QueuedOperation {
+                operation: "pulling claude-tmux-queue-line".to_string(),
+                behind: "fetching queue-line".to_string(),
+            }]
+        );
+        assert!(rx.try_recv().is_err());
+
+        drop(held);
+        rx.recv_timeout(Duration::from_secs(5)).unwrap();
+        worker.join().unwrap();
+        assert!(queued_in(&path).is_empty());
+        assert!(try_lock_repo(&path, "committing").is_ok());
+    }
+
+    #[test]
+    fn test_lock_cancelled() {
+        let path = repo("cancel");
+        let _held = lock_repo(&path, "fetching", &CancelToken::new()).unwrap();
+        let cancel = CancelToken::new();
+        let token = cancel.clone();
+        let worker_path = path.clone();
+        let worker = thread::spawn(move || {
+            lock_repo(&worker_path, "pulling claude-tmux-queue-cancel", &token).is_err()
+        });
+        while queued_in(&path).is_empty() {
+            thread::sleep(Duration::from_millis(10));
+        }
+        cancel.cancel();
+        assert!(worker.join().unwrap());
+        assert!(queued_in(&path).is_empty());
+    }
+}
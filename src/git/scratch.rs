@@ -0,0 +1,79 @@
+//! Scratch repositories for tests
+//!
+//! Each repository lives in its own directory of the system temp dir and is
+//! removed when dropped, including when an assertion fails.
+
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+/// A git repository on `main`, committing as `test`
+pub struct ScratchRepo {
+    pub path: PathBuf,
+}
+
+impl ScratchRepo {
+    /// Initialize an empty repository, `name` keeping concurrent tests apart
+    pub fn new(name: &str) -> Self {
+        let repo = Self::empty(name);
+        repo.git(&["init", "--quiet", "--initial-branch=main"]);
+        repo.set_identity();
+        repo
+    }
+
+    /// Clone `origin` into a new scratch directory
+    pub fn clone_of(origin: &ScratchRepo, name: &str) -> Self {
+        let repo = Self::empty(name);
+        run(
+            &repo.path,
+            &["clone", "--quiet", &origin.path.to_string_lossy(), "."],
+        );
+        repo.set_identity();
+        repo
+    }
+
+    fn empty(name: &str) -> Self {
+        let path =
+            std::env::temp_dir().join(format!("claude-tmux-{}-{}", name, std::process::id()));
+        let _ = std::fs::remove_dir_all(&path);
+        std::fs::create_dir_all(&path).unwrap();
+        Self { path }
+    }
+
+    fn set_identity(&self) {
+        self.git(&["config", "user.name", "test"]);
+        self.git(&["config", "user.email", "test@example.com"]);
+    }
+
+    /// Run git in the repository, returning whether it succeeded
+    pub fn git(&self, args: &[&str]) -> bool {
+        run(&self.path, args)
+    }
+
+    /// Write `content` to `file` of the working tree
+    pub fn write(&self, file: &str, content: &str) {
+        std::fs::write(self.path.join(file), content).unwrap();
+    }
+
+    /// Write `content` to `file` and commit it, with the content as message
+    pub fn commit(&self, file: &str, content: &str) -> bool {
+        self.write(file, content);
+        self.git(&["add", file]) && self.git(&["commit", "--quiet", "-m", content])
+    }
+}
+
+impl Drop for ScratchRepo {
+    fn drop(&mut self) {
+        let _ = std::fs::remove_dir_all(&self.path);
+    }
+}
+
+fn run(dir: &Path, args: &[&str]) -> bool {
+    Command::new("git")
+        .arg("-C")
+        .arg(dir)
+        .args(args)
+        .output()
+        .unwrap()
+        .status
+        .success()
+}
@@ -2,26 +2,37 @@
 //!
 //! Provides operations for listing branches and managing worktrees.
 
-use std::path::Path;
+use std::fs;
+use std::path::{Path, PathBuf};
 use std::process::Command;
 
 use anyhow::{Context, Result};
-use git2::Repository;
+use git2::{Repository, WorktreeLockStatus, WorktreePruneOptions};
 
 use super::GitContext;
+use crate::trash;
 
 impl GitContext {
     /// List all local branch names in the repository
     pub fn list_branches(repo_path: &Path) -> Result<Vec<String>> {
-        let repo = Repository::discover(repo_path).context("Failed to open repository")?;
-        let mut branches = Vec::new();
-
-        for branch_result in repo.branches(Some(git2::BranchType::Local))? {
-            let (branch, _) = branch_result?;
-            if let Ok(Some(name)) = branch.name() {
-                branches.push(name.to_string());
+        #[cfg(feature = "gix")]
+        let listed = super::gitoxide::local_branches(repo_path);
+        #[cfg(not(feature = "gix"))]
+        let listed = None;
+        let mut branches = match listed {
+            Some(branches) => branches,
+            None => {
+                let repo = Repository::discover(repo_path).context("Failed to open repository")?;
+                let mut branches = Vec::new();
+                for branch_result in repo.branches(Some(git2::BranchType::Local))? {
+                    let (branch, _) = branch_result?;
+                    if let Ok(Some(name)) = branch.name() {
+                        branches.push(name.to_string());
+                    }
+                }
+                branches
             }
-        }
+        };
 
         // Sort with main/master first, then alphabetically
         branches.sort_by(|a, b| {
@@ -37,14 +48,72 @@ impl GitContext {
         Ok(branches)
     }
 
+    /// List remote-tracking branches that have no local branch of the same
+    /// name, as (`origin/feature`, `feature`), sorted
+    pub fn list_remote_branches(repo_path: &Path) -> Result<Vec<(String, String)>> {
+        let repo = Repository::discover(repo_path).context("Failed to open repository")?;
+        let mut branches = Vec::new();
+
+        for branch_result in repo.branches(Some(git2::BranchType::Remote))? {
+            let (branch, _) = branch_result?;
+            // Skip `origin/HEAD`, which only points at another branch
+            if branch.get().symbolic_target().is_some() {
+                continue;
+            }
+            let Ok(Some(name)) = branch.name() else {
+                continue;
+            };
+            let Some((_, short)) = split_remote_branch(&repo, name) else {
+                continue;
+            };
+            if repo.find_branch(short, git2::BranchType::Local).is_err() {
+                branches.push((name.to_string(), short.to_string()));
+            }
+        }
+
+        branches.sort();
+        Ok(branches)
+    }
+
+    /// The remote-tracking branch a new local branch named `branch_name`
+    /// would track, as `git worktree add` guesses it: the branch of that
+    /// name on exactly one remote
+    pub fn guess_remote_branch(repo_path: &Path, branch_name: &str) -> Option<String> {
+        let repo = Repository::discover(repo_path).ok()?;
+        let remotes = repo.remotes().ok()?;
+        let mut found = remotes.iter().flatten().filter_map(|remote| {
+            let name = format!("{}/{}", remote, branch_name);
+            repo.find_branch(&name, git2::BranchType::Remote)
+                .is_ok()
+                .then_some(name)
+        });
+        match (found.next(), found.next()) {
+            (Some(name), None) => Some(name),
+            _ => None,
+        }
+    }
+
+    /// The remote new branches are pushed to by default: `origin` if it
+    /// exists, otherwise the first remote
+    pub fn default_remote_name(repo_path: &Path) -> Option<String> {
+        let repo = Repository::discover(repo_path).ok()?;
+        super::operations::default_remote(&repo).ok()
+    }
+
     /// Create a new worktree for a branch
-    /// - If `is_new_branch` is true: creates a new branch from HEAD
+    /// - If `is_new_branch` is true: creates a new branch from HEAD, or from
+    ///   `upstream` when it names an existing remote-tracking branch
     /// - If `is_new_branch` is false: uses an existing branch
+    ///
+    /// `upstream` (`remote/branch`) is recorded as the branch's upstream,
+    /// like `git branch --set-upstream-to`, even when the remote branch does
+    /// not exist yet so the first push goes there.
     pub fn create_worktree(
         repo_path: &Path,
         worktree_path: &Path,
         branch_name: &str,
         is_new_branch: bool,
+        upstream: Option<&str>,
     ) -> Result<()> {
         let repo = Repository::discover(repo_path).context("Failed to open repository")?;
 
@@ -55,15 +124,37 @@ impl GitContext {
         if worktree_path.exists() {
             anyhow::bail!("Path '{}' already exists", worktree_path.display());
         }
+        let upstream_parts = upstream
+            .map(|upstream| {
+                split_remote_branch(&repo, upstream)
+                    .with_context(|| format!("'{}' does not name a remote branch", upstream))
+            })
+            .transpose()?;
 
         if is_new_branch {
-            // Create new branch from HEAD, then create worktree
-            let head = repo.head().context("Failed to get HEAD")?;
-            let commit = head.peel_to_commit().context("Failed to get HEAD commit")?;
+            // Start from the remote branch being tracked (`--track`), if it
+            // exists, otherwise from HEAD
+            let remote_ref = upstream.and_then(|upstream| {
+                repo.find_reference(&format!("refs/remotes/{}", upstream))
+                    .ok()
+            });
+            let commit = match remote_ref {
+                Some(reference) => reference
+                    .peel_to_commit()
+                    .context("Failed to get remote branch commit")?,
+                None => repo
+                    .head()
+                    .context("Failed to get HEAD")?
+                    .peel_to_commit()
+                    .context("Failed to get HEAD commit")?,
+            };
 
             // Create the branch first
             repo.branch(branch_name, &commit, false)
                 .with_context(|| format!("Failed to create branch '{}'", branch_name))?;
+            if let Some((remote, merge)) = upstream_parts {
+                set_branch_upstream(&repo, branch_name, remote, merge)?;
+            }
 
             // Now create the worktree for this branch
             let refname = format!("refs/heads/{}", branch_name);
@@ -104,6 +195,9 @@ impl GitContext {
                     }
                 }
             }
+            if let Some((remote, merge)) = upstream_parts {
+                set_branch_upstream(&repo, branch_name, remote, merge)?;
+            }
 
             repo.worktree(
                 &worktree_name,
@@ -123,6 +217,27 @@ impl GitContext {
         Ok(())
     }
 
+    /// Top-level directory of the checkout (main or worktree) containing `path`
+    pub fn checkout_root(path: &Path) -> Option<PathBuf> {
+        let repo = Repository::discover(path).ok()?;
+        repo.workdir()
+            .map(|dir| dir.canonicalize().unwrap_or_else(|_| dir.to_path_buf()))
+    }
+
+    /// Name of the repository containing `path`: the main checkout's
+    /// directory name, also for its worktrees
+    pub fn repo_name(path: &Path) -> Option<String> {
+        let repo = Repository::discover(path).ok()?;
+        let common = repo.commondir();
+        let dir = if common.file_name()? == ".git" {
+            common.parent()?
+        } else {
+            common // Bare repository, e.g. `project.git`
+        };
+        let name = dir.file_name()?.to_string_lossy();
+        Some(name.strip_suffix(".git").unwrap_or(&name).to_string())
+    }
+
     /// Delete the worktree at the given path using `git worktree remove`
     /// Returns an error if the worktree has uncommitted changes (unless force=true)
     pub fn delete_worktree(worktree_path: &Path, force: bool) -> Result<()> {
@@ -168,4 +283,244 @@ impl GitContext {
             anyhow::bail!("git worktree remove failed: {}.{}", stderr.trim(), hint)
         }
     }
+
+    /// Move the worktree at the given path to the trash and drop it from
+    /// git, keeping its branch; returns where its directory went
+    pub fn trash_worktree(worktree_path: &Path, force: bool) -> Result<PathBuf> {
+        let repo = Repository::discover(worktree_path).context("Failed to open repository")?;
+        if !repo.is_worktree() {
+            anyhow::bail!(
+                "'{}' is not a worktree (it may be the main repository)",
+                worktree_path.display()
+            );
+        }
+        let root = repo
+            .workdir()
+            .context("Worktree has no working directory")?
+            .to_path_buf();
+        if !force && Self::detect(&root).is_some_and(|git| git.is_dirty()) {
+            anyhow::bail!(
+                "'{}' contains modified or untracked files. Commit or stash your changes first, or use force delete.",
+                root.display()
+            );
+        }
+
+        // Its administrative files are in `<common dir>/worktrees/<name>`
+        let name = repo
+            .path()
+            .file_name()
+            .context("Failed to find the worktree's name")?
+            .to_string_lossy()
+            .to_string();
+        let main = Repository::open(repo.commondir()).context("Failed to open main repository")?;
+        let worktree = main
+            .find_worktree(&name)
+            .context("Failed to find worktree")?;
+        if let Ok(WorktreeLockStatus::Locked(_)) = worktree.is_locked() {
+            anyhow::bail!(
+                "'{}' is locked. Unlock it first with: git worktree unlock {}",
+                root.display(),
+                root.display()
+            );
+        }
+
+        let trashed = trash::move_to_trash(&root)?;
+        worktree
+            .prune(Some(
+                WorktreePruneOptions::new().valid(true).working_tree(false),
+            ))
+            .with_context(|| {
+                format!(
+                    "Moved to {} but failed to prune the worktree",
+                    trashed.display()
+                )
+            })?;
+        Ok(trashed)
+    }
+
+    /// Find the main repository of a worktree whose directory was deleted
+    ///
+    /// git keeps `.git/worktrees/<name>/gitdir` in the main repository until
+    /// the worktree is pruned. Repositories in the nearest existing ancestor
+    /// of `worktree_path` (and that ancestor itself) are searched for an
+    /// entry pointing at it.
+    pub fn find_main_repo_of_missing(worktree_path: &Path) -> Option<PathBuf> {
+        let base = worktree_path.ancestors().skip(1).find(|dir| dir.is_dir())?;
+        let expected = worktree_path.join(".git");
+
+        let siblings = fs::read_dir(base).ok()?.flatten().map(|entry| entry.path());
+        std::iter::once(base.to_path_buf())
+            .chain(siblings)
+            .find(|candidate| {
+                let Ok(entries) = fs::read_dir(candidate.join(".git").join("worktrees")) else {
+                    return false;
+                };
+                entries.flatten().any(|entry| {
+                    fs::read_to_string(entry.path().join("gitdir"))
+                        .is_ok_and(|gitdir| Path::new(gitdir.trim()) == expected)
+                })
+            })
+    }
+}
+
+/// Split `remote/branch` at the longest configured remote name, since both
+/// halves may contain slashes
+fn split_remote_branch<'a>(repo: &Repository, name: &'a str) -> Option<(&'a str, &'a str)> {
+    let remotes = repo.remotes().ok()?;
+    remotes
+        .iter()
+        .flatten()
+        .filter_map(|remote| {
+            let branch = name.strip_prefix(remote)?.strip_prefix('/')?;
+            (!branch.is_empty()).then(|| (&name[..remote.len()], branch))
+        })
+        .max_by_key(|(remote, _)| remote.len())
+}
+
+/// Record `remote`'s branch `merge` as the upstream of a local branch
+fn set_branch_upstream(
+    repo: &Repository,
+    branch_name: &str,
+    remote: &str,
+    merge: &str,
+) -> Result<()> {
+    let mut config = repo.config().context("Failed to open git config")?;
+    config
+        .set_str(&format!("branch.{}.remote", branch_name), remote)
+        .and_then(|_| {
+            config.set_str(
+                &format!("branch.{}.merge", branch_name),
+                &format!("refs/heads/{}", merge),
+            )
+        })
+        .with_context(|| format!("Failed to set upstream of '{}'", branch_name))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_find_main_repo_of_missing() {
+        let base = std::env::temp_dir().join(format!("claude-tmux-missing-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&base);
+        let repo = base.join("project");
+        let worktree = base.join("project-feature");
+        let entry = repo.join(".git").join("worktrees").join("feature");
+        fs::create_dir_all(&entry).unwrap();
+        fs::write(
+            entry.join("gitdir"),
+            format!("{}\n", worktree.join(".git").display()),
+        )
+        .unwrap();
+
+        let found = GitContext::find_main_repo_of_missing(&worktree);
+        let unrelated = GitContext::find_main_repo_of_missing(&base.join("other"));
+        let _ = fs::remove_dir_all(&base);
+
+        assert_eq!(found, Some(repo));
+        assert_eq!(unrelated, None);
+    }
+
+    #[test]
+    fn test_repo_name() {
+        let base = std::env::temp_dir().join(format!("claude-tmux-name-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&base);
+        let repo_path = base.join("project");
+        let worktree = base.join("project-feature");
+        fs::create_dir_all(repo_path.join("src")).unwrap();
+
+        let repo = Repository::init(&repo_path).unwrap();
+        let sig = git2::Signature::now("t", "t@t").unwrap();
+        let tree = repo
+            .find_tree(repo.index().unwrap().write_tree().unwrap())
+            .unwrap();
+        repo.commit(Some("HEAD"), &sig, &sig, "init", &tree, &[])
+            .unwrap();
+        repo.worktree("feature", &worktree, None).unwrap();
+
+        let names = (
+            GitContext::repo_name(&repo_path.join("src")),
+            GitContext::repo_name(&worktree),
+        );
+        let _ = fs::remove_dir_all(&base);
+
+        assert_eq!(names.0.as_deref(), Some("project"));
+        assert_eq!(names.1.as_deref(), Some("project"));
+    }
+
+    #[test]
+    fn test_create_worktree_with_upstream() {
+        let base =
+            std::env::temp_dir().join(format!("claude-tmux-upstream-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&base);
+        let repo_path = base.join("project");
+        fs::create_dir_all(&repo_path).unwrap();
+
+        let repo = Repository::init(&repo_path).unwrap();
+        let sig = git2::Signature::now("t", "t@t").unwrap();
+        let tree = repo
+            .find_tree(repo.index().unwrap().write_tree().unwrap())
+            .unwrap();
+        let base_commit = repo
+            .commit(Some("HEAD"), &sig, &sig, "base", &tree, &[])
+            .unwrap();
+        let parent = repo.find_commit(base_commit).unwrap();
+        repo.commit(Some("HEAD"), &sig, &sig, "local", &tree, &[&parent])
+            .unwrap();
+        // A remote whose only branch is at the base commit
+        repo.remote("origin", "https://example.com/project.git")
+            .unwrap();
+        repo.reference("refs/remotes/origin/feature", base_commit, false, "")
+            .unwrap();
+
+        let remote_branches = GitContext::list_remote_branches(&repo_path).unwrap();
+        let guessed = GitContext::guess_remote_branch(&repo_path, "feature");
+        let tracking = base.join("project-feature");
+        GitContext::create_worktree(
+            &repo_path,
+            &tracking,
+            "feature",
+            true,
+            Some("origin/feature"),
+        )
+        .unwrap();
+        let unpushed = base.join("project-topic");
+        GitContext::create_worktree(&repo_path, &unpushed, "topic", true, Some("origin/topic"))
+            .unwrap();
+        let bad = GitContext::create_worktree(
+            &repo_path,
+            &base.join("project-bad"),
+            "bad",
+            true,
+            Some("upstream/bad"),
+        );
+        let contexts = (
+            GitContext::detect(&tracking).unwrap(),
+            GitContext::detect(&unpushed).unwrap(),
+        );
+        let branches = GitContext::list_branches(&repo_path).unwrap();
+        let merge = repo
+            .config()
+            .unwrap()
+            .get_string("branch.topic.merge")
+            .unwrap();
+        let _ = fs::remove_dir_all(&base);
+
+        assert_eq!(
+            remote_branches,
+            vec![("origin/feature".to_string(), "feature".to_string())]
+        );
+        assert_eq!(guessed.as_deref(), Some("origin/feature"));
+        // Starts from the remote branch it tracks
+        let (tracking, unpushed) = contexts;
+        assert!(tracking.has_upstream);
+        assert_eq!((tracking.ahead, tracking.behind), (0, 0));
+        // Starts from HEAD, and the first push goes to the configured branch
+        assert_eq!(merge, "refs/heads/topic");
+        assert!(unpushed.has_upstream);
+        assert_eq!(unpushed.ahead, 1);
+        assert!(bad.is_err());
+        assert!(!branches.contains(&"bad".to_string()));
+    }
 }
@@ -0,0 +1,119 @@
+//! Running `[[hooks]]` after actions
+//!
+//! Hooks matching an action and repository run one after another on a
+//! background thread, with `sh -c` in the working directory. They get the
+//! context in environment variables:
+//!
+//! - `CLAUDE_TMUX_EVENT`: the action, e.g. `push`
+//! - `CLAUDE_TMUX_SESSION`: tmux session the action was run from
+//! - `CLAUDE_TMUX_PATH`: working directory
+//! - `CLAUDE_TMUX_REPO`, `CLAUDE_TMUX_BRANCH`, `CLAUDE_TMUX_LAST_COMMIT`:
+//!   repository name, branch and last commit summary, empty outside a repo
+//!
+//! Each hook's output and exit status go to the log.
+
+use std::path::Path;
+use std::process::Command;
+use std::thread;
+
+use crate::config::{Hook, HookEvent};
+use crate::git::{self, GitContext};
+
+/// Output lines logged per hook
+const MAX_OUTPUT_LINES: usize = 200;
+
+/// Start the hooks that apply to `event` in `path`, if any
+pub fn run_after(hooks: &[Hook], event: HookEvent, session: &str, path: &Path) {
+    let commands: Vec<String> = hooks
+        .iter()
+        .filter(|h| h.applies(event, path))
+        .map(|h| h.command.clone())
+        .collect();
+    if commands.is_empty() {
+        return;
+    }
+    let session = session.to_string();
+    let path = path.to_path_buf();
+    thread::spawn(move || {
+        let env = environment(event, &session, &path);
+        for command in commands {
+            run(event, &command, &path, &env);
+        }
+    });
+}
+
+fn environment(event: HookEvent, session: &str, path: &Path) -> Vec<(&'static str, String)> {
+    let git_context = GitContext::detect(path);
+    vec![
+        ("CLAUDE_TMUX_EVENT", event.name().to_string()),
+        ("CLAUDE_TMUX_SESSION", session.to_string()),
+        ("CLAUDE_TMUX_PATH", path.display().to_string()),
+        (
+            "CLAUDE_TMUX_REPO",
+            git_context
+                .as_ref()
+                .and_then(|_| GitContext::repo_name(path))
+                .unwrap_or_default(),
+        ),
+        (
+            "CLAUDE_TMUX_BRANCH",
+            git_context.map(|g| g.branch).unwrap_or_default(),
+        ),
+        (
+            "CLAUDE_TMUX_LAST_COMMIT",
+            git::last_commit_summary(path).unwrap_or_default(),
+        ),
+    ]
+}
+
+fn run(event: HookEvent, command: &str, path: &Path, env: &[(&'static str, String)]) {
+    if !path.is_dir() {
+        tracing::warn!(
+            "{} hook `{}` skipped: {} no longer exists",
+            event.name(),
+            command,
+            path.display()
+        );
+        return;
+    }
+    tracing::info!(
+        "{} hook `{}` started in {}",
+        event.name(),
+        command,
+        path.display()
+    );
+    let output = match Command::new("sh")
+        .arg("-c")
+        .arg(command)
+        .current_dir(path)
+        .envs(env.iter().map(|(k, v)| (k, v)))
+        .output()
+    {
+        Ok(output) => output,
+        Err(e) => {
+            tracing::warn!("{} hook `{}` failed to start: {}", event.name(), command, e);
+            return;
+        }
+    };
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    for line in stdout
+        .lines()
+        .chain(stderr.lines())
+        .filter(|l| !l.trim().is_empty())
+        .take(MAX_OUTPUT_LINES)
+    {
+        tracing::info!("{} hook `{}` | {}", event.name(), command, line);
+    }
+    if output.status.success() {
+        tracing::info!("{} hook `{}` finished", event.name(), command);
+    } else {
+        tracing::warn!(
+            "{} hook `{}` failed ({})",
+            event.name(),
+            command,
+            output.status
+        );
+    }
+}
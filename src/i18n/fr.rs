@@ -0,0 +1,656 @@
+//! French catalog
+
+/// English interface strings and their French translations
+pub const MESSAGES: &[(&str, &str)] = &[
+    (" Confirm ", " Confirmer "),
+    ("AND delete worktree at:", "ET supprimer le worktree :"),
+    ("⚠ This will permanently delete the directory!", "⚠ Le dossier sera supprimé définitivement !"),
+    ("⚠ This is your current session - tmux will exit!", "⚠ C'est votre session actuelle - tmux va se fermer !"),
+    ("[Y]es  [n]o", "[Y] oui  [n] non"),
+    (" Close Pull Request ", " Fermer la pull request "),
+    ("Close this pull request without merging?\n\n[Y]es  [n]o", "Fermer cette pull request sans la fusionner ?\n\n[Y] oui  [n] non"),
+    (" Merge Pull Request ", " Fusionner la pull request "),
+    ("Merge this pull request?\n\n[Y]es  [n]o", "Fusionner cette pull request ?\n\n[Y] oui  [n] non"),
+    (" Merge PR + Close ", " Fusionner la PR + fermer "),
+    ("This will:", "Cette action va :"),
+    ("  • Merge the pull request", "  • Fusionner la pull request"),
+    ("  • Remove the local worktree", "  • Supprimer le worktree local"),
+    (" New Session ", " Nouvelle session "),
+    ("Name: ", "Nom : "),
+    ("Path: ", "Chemin : "),
+    ("Tab switch  ↑↓ select  → accept  Enter create  Esc cancel", "Tab changer  ↑↓ choisir  → accepter  Entrée créer  Échap annuler"),
+    (" Commit ", " Commit "),
+    ("Message: ", "Message : "),
+    ("Press Enter to commit", "Entrée pour committer"),
+    (" Create Pull Request ", " Créer une pull request "),
+    ("Title: ", "Titre: "),
+    ("Body:  ", "Corps: "),
+    ("(optional)", "(facultatif)"),
+    ("Base:  ", "Base:  "),
+    ("[Tab] Next field  [Enter] Create PR  [Esc] Cancel", "[Tab] Champ suivant  [Entrée] Créer la PR  [Échap] Annuler"),
+    (" New Session from Worktree ", " Nouvelle session depuis un worktree "),
+    (" (new)", " (nouvelle)"),
+    (" (existing)", " (existante)"),
+    ("Branch:  ", "Branche: "),
+    ("Path:    ", "Chemin:  "),
+    ("Session: ", "Session: "),
+    ("New name: ", "Nouveau nom : "),
+    ("Press Enter to confirm", "Entrée pour confirmer"),
+    ("Prompt: ", "Prompt: "),
+    ("When:   ", "Quand:  "),
+    ("e.g. 9am, 14:30, 15m, 1h30m", "ex. 9am, 14:30, 15m, 1h30m"),
+    ("[Tab] Next field  [Enter] Schedule  [Esc] Cancel", "[Tab] Champ suivant  [Entrée] Planifier  [Échap] Annuler"),
+    (" Credentials ", " Identifiants "),
+    ("Credentials rejected by ", "Identifiants refusés par "),
+    ("Authentication required for ", "Authentification requise pour "),
+    ("Username: ", "Utilisateur : "),
+    ("Password: ", "Mot de passe : "),
+    ("Save with the git credential helper", "Enregistrer avec le credential helper de git"),
+    ("Use a personal access token as the password where required", "Utilisez un jeton d'accès personnel comme mot de passe si nécessaire"),
+    ("[Tab] Next field  [Space] Toggle save  [Enter] Retry  [Esc] Cancel", "[Tab] Champ suivant  [Espace] Enregistrer ou non  [Entrée] Réessayer  [Échap] Annuler"),
+    (" Clone Session Layout ", " Cloner la disposition de la session "),
+    ("Empty branch: same directories; otherwise a new worktree", "Branche vide : mêmes dossiers ; sinon un nouveau worktree"),
+    ("Not a git repository: the same directories are used", "Pas un dépôt git : les mêmes dossiers sont utilisés"),
+    ("Windows and panes of ", "Fenêtres et panneaux de "),
+    ("Session name: ", "Nom de la session : "),
+    ("Worktree branch: ", "Branche du worktree : "),
+    ("[Tab] Next field  [Enter] Create  [Esc] Cancel", "[Tab] Champ suivant  [Entrée] Créer  [Échap] Annuler"),
+    (" Rearrange Panes ", " Réorganiser les panneaux "),
+    ("Window of ", "Fenêtre de "),
+    ("layout  ", "layout  "),
+    ("jk navigate  Enter apply  Esc close", "jk naviguer  Entrée appliquer  Échap fermer"),
+    (" Link Window ", " Lier la fenêtre "),
+    (" Move Window ", " Déplacer la fenêtre "),
+    ("Link ", "Lier "),
+    ("Move ", "Déplacer "),
+    (" into:", " dans :"),
+    (" to:", " vers :"),
+    ("No other sessions; type a name to create one", "Aucune autre session ; saisissez un nom pour en créer une"),
+    ("Type to filter  [↑↓] Select  [Enter] Confirm  [Esc] Cancel", "Tapez pour filtrer  [↑↓] Choisir  [Entrée] Confirmer  [Échap] Annuler"),
+    (" SSH Passphrase ", " Phrase de passe SSH "),
+    ("Wrong passphrase for ", "Phrase de passe incorrecte pour "),
+    ("Passphrase required for ", "Phrase de passe requise pour "),
+    ("Passphrase: ", "Phrase de passe : "),
+    ("[Enter] Retry  [Esc] Cancel", "[Entrée] Réessayer  [Échap] Annuler"),
+    (" Archived Sessions ", " Sessions archivées "),
+    ("No archived sessions", "Aucune session archivée"),
+    ("jk navigate  Enter restore  d delete  esc close", "jk naviguer  Entrée restaurer  d supprimer  Échap fermer"),
+    (" Scheduled Prompts ", " Prompts planifiés "),
+    ("No scheduled prompts", "Aucun prompt planifié"),
+    ("Daemon not running - start `claude-tmux daemon` to dispatch", "Le démon ne tourne pas - lancez `claude-tmux daemon` pour l'envoi"),
+    ("jk navigate  d cancel job  esc close", "jk naviguer  d annuler la tâche  Échap fermer"),
+    (" All Panes ", " Tous les panneaux "),
+    ("No tmux panes", "Aucun panneau tmux"),
+    ("jk navigate  Enter switch  c start claude here  v start in a split  esc close", "jk naviguer  Entrée basculer  c lancer claude ici  v lancer dans un split  Échap fermer"),
+    (" Slash Commands ", " Commandes slash "),
+    ("jk navigate  Enter send  Esc cancel", "jk naviguer  Entrée envoyer  Échap annuler"),
+    (" Prompt Templates ", " Modèles de prompt "),
+    ("File:     ", "Fichier:  "),
+    (" (empty: send clipboard)", " (vide : envoyer le presse-papiers)"),
+    ("Template: ", "Modèle:   "),
+    ("none (send as-is)", "aucun (envoi tel quel)"),
+    ("[Enter] Send  [Tab] Template  [→] Accept  [Ctrl+U] Clear  [Esc] Cancel", "[Entrée] Envoyer  [Tab] Modèle  [→] Accepter  [Ctrl+U] Effacer  [Échap] Annuler"),
+    ("Kill session '{}'", "Tuer la session '{}'"),
+    ("  • Kill session '{}'", "  • Tuer la session '{}'"),
+    ("      ... and {} more", "      ... et {} de plus"),
+    ("         ... and {} more", "         ... et {} de plus"),
+    (" Rename '{}' ", " Renommer '{}' "),
+    (" Schedule Prompt for {} ", " Planifier un prompt pour {} "),
+    ("Enter creates session '{}'", "Entrée crée la session '{}'"),
+    (" Send to {} ", " Envoyer à {} "),
+    ("          ... and {} more", "          ... et {} de plus"),
+    (" Help ", " Aide "),
+    ("Navigation", "Navigation"),
+    ("Move down", "Descendre"),
+    ("Move up", "Monter"),
+    ("Open action menu", "Ouvrir le menu d'actions"),
+    ("Switch to session", "Basculer vers la session"),
+    ("Actions", "Actions"),
+    ("New session", "Nouvelle session"),
+    ("Kill session", "Tuer la session"),
+    ("Rename session", "Renommer la session"),
+    ("Duplicate agent (new window)", "Dupliquer l'agent (nouvelle fenêtre)"),
+    ("Filter sessions", "Filtrer les sessions"),
+    ("Continue (Enter / \"continue\" / restart)", "Continuer (Entrée / \"continue\" / relance)"),
+    ("Interrupt (Escape)", "Interrompre (Échap)"),
+    ("Send slash command", "Envoyer une commande slash"),
+    ("Send prompt template", "Envoyer un modèle de prompt"),
+    ("Send file or clipboard", "Envoyer un fichier ou le presse-papiers"),
+    ("Schedule prompt", "Planifier un prompt"),
+    ("Scheduled prompts", "Prompts planifiés"),
+    ("Archived sessions", "Sessions archivées"),
+    ("All panes (start Claude in one)", "Tous les panneaux (y lancer Claude)"),
+    ("Recent log lines", "Dernières lignes du journal"),
+    ("Refresh list", "Actualiser la liste"),
+    ("Action Menu", "Menu d'actions"),
+    ("Go back", "Revenir"),
+    ("Execute action", "Exécuter l'action"),
+    ("Other", "Divers"),
+    ("Show this help", "Afficher cette aide"),
+    ("Quit", "Quitter"),
+    ("Loading Claude Code instances...", "Chargement des instances Claude Code..."),
+    ("No Claude Code instances found. Press '{}' to create a new session.", "Aucune instance Claude Code. Appuyez sur '{}' pour créer une session."),
+    ("No instances match the filter.", "Aucune instance ne correspond au filtre."),
+    (" [missing dir]", " [dossier absent]"),
+    ("yes", "oui"),
+    ("no", "non"),
+    ("session: ", "session : "),
+    ("window: ", "fenêtre : "),
+    ("pane: ", "panneau : "),
+    ("attached: ", "attachée : "),
+    ("branch: ", "branche : "),
+    ("staged: ", "indexé : "),
+    ("unstaged: ", "non indexé : "),
+    ("worktree: ", "worktree : "),
+    ("open", "ouverte"),
+    ("closed", "fermée"),
+    ("merged", "fusionnée"),
+    ("ready to merge", "prête à fusionner"),
+    ("has conflicts", "en conflit"),
+    ("merge status unknown", "fusion : état inconnu"),
+    ("  No preview available", "  Aucun aperçu disponible"),
+    ("loading...", "chargement..."),
+    ("detecting status...", "détection des états..."),
+    ("loading git info...", "chargement des infos git..."),
+    ("  {} help  {}{} navigate  {} actions  ⏎ switch  {} new  {} kill  {} reload  {} filter  {} quit", "  {} aide  {}{} naviguer  {} actions  ⏎ basculer  {} nouvelle  {} tuer  {} recharger  {} filtrer  {} quitter"),
+    ("  {}{} navigate  ⏎/{} select  h/esc back  {} quit", "  {}{} naviguer  ⏎/{} choisir  h/Échap retour  {} quitter"),
+    ("  ⏎ apply  esc cancel", "  ⏎ appliquer  Échap annuler"),
+    ("  y/⏎ confirm  n/esc cancel", "  y/⏎ confirmer  n/Échap annuler"),
+    ("  ⏎ create  tab switch  ↑↓ select  → accept  esc cancel", "  ⏎ créer  tab changer  ↑↓ choisir  → accepter  Échap annuler"),
+    ("  ⏎ confirm  esc cancel", "  ⏎ confirmer  Échap annuler"),
+    ("  ⏎ commit  esc cancel", "  ⏎ committer  Échap annuler"),
+    ("  ⏎ create PR  tab switch  esc cancel", "  ⏎ créer la PR  tab changer  Échap annuler"),
+    ("  jk navigate  ⏎ send  esc cancel", "  jk naviguer  ⏎ envoyer  Échap annuler"),
+    ("  ⏎ schedule  tab switch  esc cancel", "  ⏎ planifier  tab changer  Échap annuler"),
+    ("  ⏎ send  tab template  ↑↓ select  → accept  esc cancel", "  ⏎ envoyer  tab modèle  ↑↓ choisir  → accepter  Échap annuler"),
+    ("  ⏎ save comment  esc discard", "  ⏎ enregistrer le commentaire  Échap abandonner"),
+    ("  jk move  [] file  c comment  d delete  s send to agent  esc close", "  jk déplacer  [] fichier  c commenter  d supprimer  s envoyer à l'agent  Échap fermer"),
+    ("  jk navigate  d cancel job  esc close", "  jk naviguer  d annuler la tâche  Échap fermer"),
+    ("  jk navigate  ⏎ restore  d delete  esc close", "  jk naviguer  ⏎ restaurer  d supprimer  Échap fermer"),
+    ("  ⏎ create  tab switch  esc cancel", "  ⏎ créer  tab changer  Échap annuler"),
+    ("  ↑↓ select  ⏎ confirm  esc cancel", "  ↑↓ choisir  ⏎ confirmer  Échap annuler"),
+    ("  jk navigate  ⏎ apply  esc close", "  jk naviguer  ⏎ appliquer  Échap fermer"),
+    ("  jk navigate  ⏎ switch  c start claude  v split  esc close", "  jk naviguer  ⏎ basculer  c lancer claude  v split  Échap fermer"),
+    ("  ⏎ retry  tab switch  space toggle save  esc cancel", "  ⏎ réessayer  tab changer  espace enregistrer ou non  Échap annuler"),
+    ("  ⏎ retry  esc cancel", "  ⏎ réessayer  Échap annuler"),
+    (" attached: {} ", " attaché : {} "),
+    ("{} {}–{} of {} {} ", "{} {}–{} sur {} {} "),
+    ("{} instances", "{} instances"),
+    ("{} working", "{} au travail"),
+    ("{} awaiting input", "{} en attente de saisie"),
+    ("{} crashed", "{} en échec"),
+    ("{}... (^C cancel)", "{}... (^C annuler)"),
+    (" │ filter: \"{}\"", " │ filtre : \"{}\""),
+    ("Comment: ", "Commentaire : "),
+    (" Review {} — 1 comment ", " Relecture de {} — 1 commentaire "),
+    (" Review {} — {} comments ", " Relecture de {} — {} commentaires "),
+    ("pushing", "push de"),
+    ("fetching", "fetch de"),
+    ("pulling", "pull de"),
+    ("Pushed to remote", "Push vers le dépôt distant effectué"),
+    ("Pushed and set upstream", "Push effectué et upstream défini"),
+    ("Fetched from remote", "Fetch depuis le dépôt distant effectué"),
+    ("Pulled from remote", "Pull depuis le dépôt distant effectué"),
+    ("Push failed", "Échec du push"),
+    ("Fetch failed", "Échec du fetch"),
+    ("Pull failed", "Échec du pull"),
+    ("Error: {}", "Erreur : {}"),
+    (" Log — {} ", " Journal — {} "),
+    ("No log messages yet", "Aucun message dans le journal"),
+    (
+        "  jk scroll  g/G oldest/newest  r reload  esc close",
+        "  jk défiler  g/G plus anciens/récents  r recharger  Échap fermer",
+    ),
+    ("Refreshed", "Actualisé"),
+    ("Staged all changes", "Toutes les modifications indexées"),
+    ("Opened PR in browser", "PR ouverte dans le navigateur"),
+    ("Closed pull request", "Pull request fermée"),
+    ("Merged pull request", "Pull request fusionnée"),
+    ("Merged PR, removed worktree, and closed session", "PR fusionnée, worktree supprimé et session fermée"),
+    ("Merged PR and closed session", "PR fusionnée et session fermée"),
+    ("Username and password are required", "Utilisateur et mot de passe requis"),
+    ("Passphrase is required", "Phrase de passe requise"),
+    ("Commit message cannot be empty", "Le message de commit ne peut pas être vide"),
+    ("Committed changes", "Modifications committées"),
+    ("Session name cannot be empty", "Le nom de la session ne peut pas être vide"),
+    ("Branch name cannot be empty", "Le nom de la branche ne peut pas être vide"),
+    ("Worktree path cannot be empty", "Le chemin du worktree ne peut pas être vide"),
+    ("A worktree needs a git repository", "Un worktree nécessite un dépôt git"),
+    ("PR title cannot be empty", "Le titre de la PR ne peut pas être vide"),
+    ("Main repository not found", "Dépôt principal introuvable"),
+    ("clipboard", "presse-papiers"),
+    ("No changes to review", "Aucune modification à relire"),
+    ("No comments to send (press c to comment)", "Aucun commentaire à envoyer (c pour commenter)"),
+    ("Prompt cannot be empty", "Le prompt ne peut pas être vide"),
+    (" - start `claude-tmux daemon` to dispatch it", " - lancez `claude-tmux daemon` pour l'envoyer"),
+    ("Cancelled scheduled prompt", "Prompt planifié annulé"),
+    ("Failed to refresh: {}", "Échec de l'actualisation : {}"),
+    ("Failed to switch: {}", "Échec du basculement : {}"),
+    ("Stage failed: {}", "Échec de l'indexation : {}"),
+    ("Failed to open PR: {}", "Impossible d'ouvrir la PR : {}"),
+    ("Failed to close PR: {}", "Impossible de fermer la PR : {}"),
+    ("Failed to merge PR: {}", "Impossible de fusionner la PR : {}"),
+    ("PR merged but failed to delete worktree: {}", "PR fusionnée mais impossible de supprimer le worktree : {}"),
+    ("PR merged but failed to kill session: {}", "PR fusionnée mais impossible de tuer la session : {}"),
+    ("Failed to join pane: {}", "Impossible de joindre le panneau : {}"),
+    ("Sent pane back to {}", "Panneau renvoyé vers {}"),
+    ("Failed to send pane back: {}", "Impossible de renvoyer le panneau : {}"),
+    ("Killed session '{}'", "Session '{}' tuée"),
+    ("Failed to kill: {}", "Impossible de tuer : {}"),
+    ("Deleted worktree and killed session '{}'", "Worktree supprimé et session '{}' tuée"),
+    ("Worktree deleted but failed to kill session: {}", "Worktree supprimé mais impossible de tuer la session : {}"),
+    ("Failed to delete worktree: {}", "Impossible de supprimer le worktree : {}"),
+    ("Already {} {}; press Ctrl-C to cancel it", "{} {} déjà en cours ; Ctrl-C pour l'annuler"),
+    ("Credentials not saved: {}", "Identifiants non enregistrés : {}"),
+    ("Cancelled {} {}", "Annulé : {} {}"),
+    ("Renamed '{}' to '{}'", "'{}' renommée en '{}'"),
+    ("Failed to rename: {}", "Impossible de renommer : {}"),
+    ("Commit failed: {}", "Échec du commit : {}"),
+    ("Created session '{}'", "Session '{}' créée"),
+    ("Failed to create session: {}", "Impossible de créer la session : {}"),
+    ("Failed to list branches: {}", "Impossible de lister les branches : {}"),
+    ("Created worktree '{}' and session '{}'", "Worktree '{}' et session '{}' créés"),
+    ("Worktree created but session creation failed: {}", "Worktree créé mais échec de la création de la session : {}"),
+    ("Failed to create worktree: {}", "Impossible de créer le worktree : {}"),
+    ("Failed to read layout: {}", "Impossible de lire la disposition : {}"),
+    ("Cloned layout of '{}' into '{}'", "Disposition de '{}' clonée dans '{}'"),
+    ("Cloned layout of '{}' into '{}' on worktree '{}'", "Disposition de '{}' clonée dans '{}' sur le worktree '{}'"),
+    ("Failed to clone layout: {}", "Impossible de cloner la disposition : {}"),
+    ("Applied: {}", "Appliqué : {}"),
+    ("Failed to rearrange panes: {}", "Impossible de réorganiser les panneaux : {}"),
+    ("Failed to list sessions: {}", "Impossible de lister les sessions : {}"),
+    ("Linked window {} into '{}'", "Fenêtre {} liée dans '{}'"),
+    ("Moved window {} to '{}'", "Fenêtre {} déplacée vers '{}'"),
+    ("Failed to move window: {}", "Impossible de déplacer la fenêtre : {}"),
+    ("Created PR: {}", "PR créée : {}"),
+    ("Failed to create PR: {}", "Impossible de créer la PR : {}"),
+    ("{} is already working", "{} travaille déjà"),
+    ("{} is not waiting for input", "{} n'attend pas de saisie"),
+    ("Continued {}", "Reprise de {}"),
+    ("Failed to continue: {}", "Impossible de continuer : {}"),
+    ("Interrupted {}", "{} interrompu"),
+    ("Failed to interrupt: {}", "Impossible d'interrompre : {}"),
+    ("Restarted claude in {}", "claude redémarré dans {}"),
+    ("Failed to restart: {}", "Impossible de redémarrer : {}"),
+    ("Failed to dismiss: {}", "Impossible d'ignorer : {}"),
+    ("Respawned {} in {}", "{} relancé dans {}"),
+    ("Failed to respawn: {}", "Impossible de relancer : {}"),
+    ("Sent {} to {}", "{} envoyé à {}"),
+    ("Failed to send {}: {}", "Impossible d'envoyer {} : {}"),
+    ("No prompt templates defined in {}", "Aucun modèle de prompt défini dans {}"),
+    ("Sent prompt to {}", "Prompt envoyé à {}"),
+    ("Failed to send prompt: {}", "Impossible d'envoyer le prompt : {}"),
+    ("Nothing to send: {} is empty", "Rien à envoyer : {} est vide"),
+    ("{} is too large to send ({} KiB, max {} KiB)", "{} est trop volumineux ({} Kio, max {} Kio)"),
+    ("Failed to read {}: {}", "Impossible de lire {} : {}"),
+    ("Failed to load diff: {}", "Impossible de charger le diff : {}"),
+    ("Sent review to {}", "Relecture envoyée à {}"),
+    ("Failed to send review: {}", "Impossible d'envoyer la relecture : {}"),
+    ("Scheduled prompt for {} ({})", "Prompt planifié pour {} ({})"),
+    ("Failed to schedule prompt: {}", "Impossible de planifier le prompt : {}"),
+    ("Failed to cancel: {}", "Impossible d'annuler : {}"),
+    ("{} no longer exists", "{} n'existe plus"),
+    ("Started {} in {}", "{} lancé dans {}"),
+    ("Failed to duplicate agent: {}", "Impossible de dupliquer l'agent : {}"),
+    ("Failed to archive: {}", "Impossible d'archiver : {}"),
+    ("Archived session '{}'", "Session '{}' archivée"),
+    ("Archived, but failed to kill: {}", "Archivée, mais impossible de tuer : {}"),
+    ("Restored, but failed to update archive: {}", "Restaurée, mais impossible de mettre à jour l'archive : {}"),
+    ("Restored session '{}'", "Session '{}' restaurée"),
+    ("Failed to restore: {}", "Impossible de restaurer : {}"),
+    ("Removed '{}' from the archive", "'{}' retirée de l'archive"),
+    ("Failed to remove: {}", "Impossible de retirer : {}"),
+    ("Failed to list panes: {}", "Impossible de lister les panneaux : {}"),
+    ("Claude already runs in {}", "Claude tourne déjà dans {}"),
+    ("{} is running {}; press v to start Claude in a split", "{} exécute {} ; v pour lancer Claude dans un split"),
+    ("Started Claude next to {}", "Claude lancé à côté de {}"),
+    ("Started Claude in {}", "Claude lancé dans {}"),
+    ("Failed to start Claude: {}", "Impossible de lancer Claude : {}"),
+    ("New session from worktree", "Nouvelle session depuis un worktree"),
+    ("Clone session layout", "Cloner la disposition de la session"),
+    ("Rearrange panes", "Réorganiser les panneaux"),
+    ("Move window to session", "Déplacer la fenêtre vers une session"),
+    ("Link window into session", "Lier la fenêtre dans une session"),
+    ("Review changes", "Relire les modifications"),
+    ("Stage all changes", "Indexer toutes les modifications"),
+    ("Commit staged changes", "Committer les modifications indexées"),
+    ("Push to remote", "Push vers le dépôt distant"),
+    ("Push and set upstream", "Push et définir l'upstream"),
+    ("Fetch from remote", "Fetch depuis le dépôt distant"),
+    ("Pull from remote", "Pull depuis le dépôt distant"),
+    ("Continue", "Continuer"),
+    ("Interrupt", "Interrompre"),
+    ("Restart with --continue", "Redémarrer avec --continue"),
+    ("Dismiss (exited on purpose)", "Ignorer (arrêt volontaire)"),
+    ("Respawn pane in main repo", "Relancer le panneau dans le dépôt principal"),
+    ("Duplicate agent in new window", "Dupliquer l'agent dans une nouvelle fenêtre"),
+    ("Duplicate agent in split", "Dupliquer l'agent dans un split"),
+    ("Create pull request", "Créer une pull request"),
+    ("View pull request", "Voir la pull request"),
+    ("Close pull request", "Fermer la pull request"),
+    ("Merge pull request", "Fusionner la pull request"),
+    ("Merge PR + close session", "Fusionner la PR + fermer la session"),
+    ("Join pane into current window", "Joindre le panneau à la fenêtre actuelle"),
+    ("Send pane back", "Renvoyer le panneau"),
+    ("Archive session", "Archiver la session"),
+    ("Kill session + delete worktree", "Tuer la session + supprimer le worktree"),
+    ("Swap with next pane", "Échanger avec le panneau suivant"),
+    ("Swap with previous pane", "Échanger avec le panneau précédent"),
+    ("Rotate panes", "Faire tourner les panneaux"),
+    ("idle", "inactif"),
+    ("working", "actif"),
+    ("input", "saisie"),
+    ("crashed", "planté"),
+    ("unknown", "inconnu"),
+    ("claude-tmux crashed; a report was written to {}", "claude-tmux a planté ; un rapport a été écrit dans {}"),
+    ("Outdated config: {}", "Configuration obsolète : {}"),
+    ("Edit keybindings", "Modifier les raccourcis"),
+    (" Keybindings ", " Raccourcis "),
+    ("  (default {})", "  (par défaut {})"),
+    ("'{}' is used by \"{}\"; press it again to swap", "'{}' est utilisé par « {} » ; appuyez à nouveau pour échanger"),
+    ("Press the new key for \"{}\" (esc cancels)", "Appuyez sur la nouvelle touche pour « {} » (Échap annule)"),
+    ("jk navigate  Enter rebind  d default  esc close", "jk naviguer  Entrée modifier  d défaut  Échap fermer"),
+    ("  jk navigate  ⏎ rebind  d default  esc close", "  jk naviguer  ⏎ modifier  d défaut  Échap fermer"),
+    ("  press a key  esc cancel", "  appuyez sur une touche  Échap annuler"),
+    ("Failed to save keybindings: {}", "Échec de l'enregistrement des raccourcis : {}"),
+    ("Saved to {}", "Enregistré dans {}"),
+    ("Working time per branch", "Temps de travail par branche"),
+    (" Working Time ", " Temps de travail "),
+    ("Branch", "Branche"),
+    ("Today", "Aujourd'hui"),
+    ("7 days", "7 jours"),
+    ("Total", "Total"),
+    ("Last", "Dernier"),
+    ("No working time recorded yet", "Aucun temps de travail enregistré"),
+    ("Time agents spent working; jk navigate  esc close", "Temps de travail des agents ; jk naviguer  Échap fermer"),
+    ("  jk navigate  esc close", "  jk naviguer  Échap fermer"),
+    ("worked: ", "travail : "),
+    ("{} today, {} total", "{} aujourd'hui, {} au total"),
+    ("Usage statistics", "Statistiques d'utilisation"),
+    (" Usage Statistics ", " Statistiques d'utilisation "),
+    ("Since {}", "Depuis le {}"),
+    ("Nothing recorded yet", "Rien d'enregistré pour l'instant"),
+    ("Sessions created", "Sessions créées"),
+    ("Prompts sent", "Prompts envoyés"),
+    ("Commits", "Commits"),
+    ("Pushes", "Pushs"),
+    ("Times agents asked for input", "Demandes de saisie des agents"),
+    ("Average time to answer", "Temps moyen de réponse"),
+    ("Recording is off (usage_stats = false)", "Enregistrement désactivé (usage_stats = false)"),
+    ("r reload  esc close", "r recharger  Échap fermer"),
+    ("  r reload  esc close", "  r recharger  Échap fermer"),
+    ("1 file", "1 fichier"),
+    ("{} files", "{} fichiers"),
+    ("last commit: ", "dernier commit : "),
+    ("by ", "par "),
+    ("{} ago", "il y a {}"),
+    ("Continue merge", "Poursuivre la fusion"),
+    ("Continue rebase", "Poursuivre le rebase"),
+    ("Abort merge", "Annuler la fusion"),
+    ("Abort rebase", "Annuler le rebase"),
+    ("Merge completed", "Fusion terminée"),
+    ("Rebase continued", "Rebase poursuivi"),
+    ("Continue failed: {}", "Échec de la poursuite : {}"),
+    ("Merge aborted", "Fusion annulée"),
+    ("Rebase aborted", "Rebase annulé"),
+    ("Abort failed: {}", "Échec de l'annulation : {}"),
+    ("Abort the merge in progress?", "Annuler la fusion en cours ?"),
+    ("Abort the rebase in progress?", "Annuler le rebase en cours ?"),
+    ("Conflict resolutions made so far are lost.", "Les conflits déjà résolus seront perdus."),
+    ("MERGING", "FUSION"),
+    ("REBASING", "REBASE"),
+    (" (conflicts)", " (conflits)"),
+    ("Rebase completed", "Rebase terminé"),
+    ("Resolve conflicts", "Résoudre les conflits"),
+    ("Not a git checkout", "Pas un dépôt git"),
+    ("Failed to list conflicts: {}", "Impossible de lister les conflits : {}"),
+    ("Resolved {}", "{} résolu"),
+    ("Failed to resolve: {}", "Échec de la résolution : {}"),
+    ("Failed to open editor: {}", "Impossible d'ouvrir l'éditeur : {}"),
+    ("Resolve every conflict first", "Résolvez d'abord tous les conflits"),
+    (
+        "  jk navigate  e edit  o/t ours/theirs  s resolved  c continue  esc close",
+        "  jk naviguer  e éditer  o/t nôtre/leur  s résolu  c poursuivre  Échap fermer",
+    ),
+    (" Conflicts ", " Conflits "),
+    (
+        "All conflicts resolved, c continues the merge",
+        "Conflits résolus, c poursuit la fusion",
+    ),
+    (
+        "All conflicts resolved, c continues the rebase",
+        "Conflits résolus, c poursuit le rebase",
+    ),
+    ("All conflicts resolved", "Conflits résolus"),
+    ("deleted by us", "supprimé de notre côté"),
+    ("deleted by them", "supprimé de leur côté"),
+    ("both modified", "modifié des deux côtés"),
+    (
+        "e edit  o ours  t theirs  s mark resolved  c continue  esc close",
+        "e éditer  o nôtre  t leur  s marquer résolu  c poursuivre  Échap fermer",
+    ),
+    (
+        "Wait for the running git operation first",
+        "Attendez la fin de l'opération git en cours",
+    ),
+    ("View CI checks", "Voir les checks CI"),
+    ("Opened CI checks in browser", "Checks CI ouverts dans le navigateur"),
+    ("Failed to open CI checks: {}", "Échec de l'ouverture des checks CI : {}"),
+    ("CI:", "CI :"),
+    ("{} failing ({})", "{} en échec ({})"),
+    ("{} pending", "{} en attente"),
+    ("{} passed", "{} réussis"),
+    ("ahead of {}: ", "en avance sur {} : "),
+    ("stashes: ", "stashs : "),
+    ("Turn on auto-fetch", "Activer la récupération automatique"),
+    ("Turn off auto-fetch", "Désactiver la récupération automatique"),
+    ("Auto-fetch turned on for this repository", "Récupération automatique activée pour ce dépôt"),
+    ("Auto-fetch turned off for this repository", "Récupération automatique désactivée pour ce dépôt"),
+    ("Failed to change auto-fetch: {}", "Échec du changement de la récupération automatique : {}"),
+    ("fetched: ", "récupéré : "),
+    ("never", "jamais"),
+    ("(auto)", "(auto)"),
+    ("Search pane contents", "Rechercher dans le contenu des panneaux"),
+    (" Search Panes ", " Recherche dans les panneaux "),
+    ("Search: ", "Recherche : "),
+    ("Enter searches the content of every Claude pane", "Entrée recherche dans le contenu de chaque panneau Claude"),
+    ("No pane contains it", "Aucun panneau ne le contient"),
+    ("Type a pattern  [Enter] Search / switch  [↑↓] Select  [Esc] Close", "Saisir un motif  [Entrée] Rechercher / basculer  [↑↓] Choisir  [Échap] Fermer"),
+    ("  ⏎ search  esc close", "  ⏎ rechercher  Échap fermer"),
+    ("  ↑↓ select  ⏎ switch  esc close", "  ↑↓ choisir  ⏎ basculer  Échap fermer"),
+    ("Start logging pane to file", "Journaliser le panneau dans un fichier"),
+    ("Stop logging pane", "Arrêter la journalisation du panneau"),
+    ("Stopped logging the pane", "Journalisation du panneau arrêtée"),
+    ("Failed to stop logging: {}", "Échec de l'arrêt de la journalisation : {}"),
+    ("Logging the pane to {}", "Journalisation du panneau dans {}"),
+    ("Failed to start logging: {}", "Échec du démarrage de la journalisation : {}"),
+    (" [log]", " [journal]"),
+    ("Open shell in split", "Ouvrir un shell dans une division"),
+    ("Open editor in split", "Ouvrir l'éditeur dans une division"),
+    ("Failed to split pane: {}", "Échec de la division du panneau : {}"),
+    ("Upstream: ", "Amont:   "),
+    ("(none)", "(aucune)"),
+    (" (tracks {})", " (suit {})"),
+    ("No Claude instance at '{}'", "Aucune instance Claude à '{}'"),
+    (" [pane moved]", " [panneau déplacé]"),
+    ("claude in: ", "claude dans : "),
+    ("pane in: ", "panneau dans : "),
+    ("  ⏎ close", "  ⏎ fermer"),
+    (" Config not reloaded ", " Configuration non rechargée "),
+    ("The previous settings stay in effect.", "Les réglages précédents restent en vigueur."),
+    ("Enter close  {} retry", "Entrée fermer  {} réessayer"),
+    ("Reload config file", "Recharger le fichier de configuration"),
+    ("Config reloaded", "Configuration rechargée"),
+    ("Config not reloaded: {}", "Configuration non rechargée : {}"),
+    ("Mark instance for syncing", "Marquer l'instance pour la synchronisation"),
+    ("Sync marked checkouts with upstream", "Synchroniser les copies marquées avec l'amont"),
+    ("{} marked", "{} marquées"),
+    ("agent is working", "l'agent travaille"),
+    ("not a git repository", "pas un dépôt git"),
+    ("merge or rebase in progress", "fusion ou rebase en cours"),
+    ("no upstream branch", "pas de branche amont"),
+    ("No instances to sync", "Aucune instance à synchroniser"),
+    ("Sync stopped", "Synchronisation arrêtée"),
+    (" Sync ", " Synchronisation "),
+    ("pending", "en attente"),
+    ("failed", "échec"),
+    ("up to date", "à jour"),
+    (", local changes kept", ", modifications locales conservées"),
+    ("skipped: {}", "ignorée : {}"),
+    ("fast-forwarded {} commits", "avance rapide de {} commits"),
+    ("rebased onto {} new commits", "rebasée sur {} nouveaux commits"),
+    ("{} of {} synced; esc stop", "{} sur {} synchronisées ; Échap arrêter"),
+    (
+        "{} updated, {} failed, {} skipped; esc close",
+        "{} mises à jour, {} en échec, {} ignorées ; Échap fermer",
+    ),
+    ("  jk navigate  esc stop", "  jk naviguer  Échap arrêter"),
+    ("Edit tags", "Modifier les étiquettes"),
+    ("Group by tag", "Grouper par étiquette"),
+    (" Tags of {} ", " Étiquettes de {} "),
+    ("Tags: ", "Étiquettes : "),
+    (
+        "Separate tags with spaces or commas; leave empty to remove them",
+        "Séparez les étiquettes par des espaces ou des virgules ; laissez vide pour les retirer",
+    ),
+    ("  ⏎ save  esc cancel", "  ⏎ enregistrer  Échap annuler"),
+    ("grouped by tag", "groupé par étiquette"),
+    ("untagged", "sans étiquette"),
+    ("Failed to save tags: {}", "Échec de l'enregistrement des étiquettes : {}"),
+    ("{} isn't available for this instance", "{} n'est pas disponible pour cette instance"),
+    ("Git", "Git"),
+    ("All sessions (switch, kill, rename)", "Toutes les sessions (basculer, tuer, renommer)"),
+    (" Sessions ", " Sessions "),
+    ("No tmux sessions", "Aucune session tmux"),
+    ("1 window", "1 fenêtre"),
+    ("{} windows", "{} fenêtres"),
+    ("attached", "attachée"),
+    (
+        "Kill '{}' and everything running in it? y/Enter confirms",
+        "Tuer '{}' et tout ce qui y tourne ? y/Entrée confirme",
+    ),
+    (
+        "jk navigate  Enter switch  {} kill  {} rename  esc close",
+        "jk naviguer  Entrée basculer  {} tuer  {} renommer  Échap fermer",
+    ),
+    (
+        "  jk navigate  ⏎ switch  {} kill  {} rename  esc close",
+        "  jk naviguer  ⏎ basculer  {} tuer  {} renommer  Échap fermer",
+    ),
+    ("  ⏎ rename  esc cancel", "  ⏎ renommer  Échap annuler"),
+    ("Session list", "Liste des sessions"),
+    ("Lists and dialogs", "Listes et dialogues"),
+    ("Select or confirm", "Choisir ou confirmer"),
+    ("Close", "Fermer"),
+    ("Filter", "Filtre"),
+    ("Apply filter", "Appliquer le filtre"),
+    ("Cancel", "Annuler"),
+    ("All sessions", "Toutes les sessions"),
+    ("Help", "Aide"),
+    ("Scroll", "Défiler"),
+    ("Scroll a page", "Défiler d'une page"),
+    (" {}-{} of {} ", " {}-{} sur {} "),
+    ("  jk scroll  {} close", "  jk défiler  {} fermer"),
+    ("Command palette", "Palette de commandes"),
+    (" Command Palette ", " Palette de commandes "),
+    ("No matching command", "Aucune commande correspondante"),
+    ("Type to filter  [Enter] Run  [↑↓] Select  [Esc] Close", "Saisir pour filtrer  [Entrée] Lancer  [↑↓] Choisir  [Échap] Fermer"),
+    ("  ↑↓ select  ⏎ run  esc close", "  ↑↓ choisir  ⏎ lancer  Échap fermer"),
+    ("Interactive rebase", "Rebase interactif"),
+    (" Interactive Rebase of {} ", " Rebase interactif de {} "),
+    ("Onto: ", "Sur : "),
+    ("Any branch, tag or commit. Enter opens git rebase -i in a split", "Toute branche, étiquette ou commit. Entrée ouvre git rebase -i dans une division"),
+    ("  ⏎ rebase  esc cancel", "  ⏎ rebaser  Échap annuler"),
+    ("Press Enter to close", "Appuyer sur Entrée pour fermer"),
+    ("Rebasing onto {} in a split", "Rebase sur {} dans une division"),
+    ("Failed to start rebase: {}", "Échec du démarrage du rebase : {}"),
+    ("Rebase stopped in {}", "Rebase interrompu dans {}"),
+    ("Rebase finished in {}", "Rebase terminé dans {}"),
+    ("New session from issue", "Nouvelle session depuis un ticket"),
+    ("Failed to list issues: {}", "Échec de la liste des tickets : {}"),
+    (" New Session from Issue ", " Nouvelle session depuis un ticket "),
+    ("Issue: ", "Ticket : "),
+    ("Branch: ", "Branche : "),
+    ("[{}] Send the issue as the first prompt", "[{}] Envoyer le ticket comme premier prompt"),
+    ("Number, URL or ticket  [↑↓] Select  [Tab] Prompt  [Enter] Create  [Esc] Cancel", "Numéro, URL ou ticket  [↑↓] Choisir  [Tab] Prompt  [Entrée] Créer  [Échap] Annuler"),
+    ("  ↑↓ select  tab prompt  ⏎ create  esc cancel", "  ↑↓ choisir  tab prompt  ⏎ créer  Échap annuler"),
+    ("rebasing", "rebase de"),
+    ("merging", "merge de"),
+    ("Rebased onto upstream", "Rebase sur la branche amont effectué"),
+    ("Merged upstream", "Merge de la branche amont effectué"),
+    ("Rebase failed", "Échec du rebase"),
+    ("Merge failed", "Échec du merge"),
+    ("No upstream branch to compare with", "Aucune branche amont à comparer"),
+    ("Up to date with upstream", "À jour avec la branche amont"),
+    ("Commit or stash changes before pulling", "Committez ou remisez les modifications avant le pull"),
+    ("Rebase onto upstream", "Rebase sur la branche amont"),
+    ("Merge upstream", "Merger la branche amont"),
+    ("Pull, push, rebase or merge as ahead/behind calls for", "Pull, push, rebase ou merge selon l'avance/le retard"),
+    ("attached first", "sessions attachées d'abord"),
+    ("name", "nom"),
+    ("path", "chemin"),
+    ("Sorted by {}", "Tri par {}"),
+    ("Change sort order", "Changer l'ordre de tri"),
+    ("Enlarge preview", "Agrandir l'aperçu"),
+    ("Shrink preview", "Réduire l'aperçu"),
+    ("Force-pushed to remote", "Push forcé vers le dépôt distant effectué"),
+    ("Force push", "Push forcé"),
+    ("Force-push '{}'?", "Forcer le push de '{}' ?"),
+    ("Commits only on the remote branch are dropped from it.", "Les commits présents uniquement sur la branche distante en sont retirés."),
+    ("Start a workspace", "Démarrer un espace de travail"),
+    (" Workspaces ", " Espaces de travail "),
+    ("not running", "arrêté"),
+    ("directory not found", "répertoire introuvable"),
+    ("jk navigate  Enter start  Esc cancel", "jk naviguer  Entrée démarrer  Échap annuler"),
+    ("  jk navigate  ⏎ start  esc cancel", "  jk naviguer  ⏎ démarrer  Échap annuler"),
+    ("No workspaces defined in {}", "Aucun espace de travail défini dans {}"),
+    ("Workspace {}: {} started, {} already running", "Espace de travail {} : {} démarrés, {} déjà actifs"),
+    ("Workspace {}: could not start {}", "Espace de travail {} : impossible de démarrer {}"),
+    ("node: ", "node : "),
+    ("mcp: ", "mcp : "),
+    ("Custom actions", "Actions personnalisées"),
+    ("  jk scroll  g/G top/bottom  r run again  esc close", "  jk défiler  g/G début/fin  r relancer  Échap fermer"),
+    ("running…", "en cours…"),
+    ("done", "terminé"),
+    ("No output", "Aucune sortie"),
+    ("exit status {}", "code de sortie {}"),
+    ("failed: {}", "échec : {}"),
+    ("killed by a signal", "tué par un signal"),
+    ("'{}' is still running", "'{}' est encore en cours"),
+    ("'{}' finished", "'{}' terminé"),
+    ("'{}' exited with status {}", "'{}' s'est terminé avec le code {}"),
+    ("'{}' failed: {}", "échec de '{}' : {}"),
+    ("'{}' runs the custom action '{}'", "'{}' lance l'action personnalisée '{}'"),
+    ("Switch back to the previous instance", "Revenir à l'instance précédente"),
+    ("No previous instance to switch back to", "Aucune instance précédente vers laquelle revenir"),
+    ("  jk navigate  ⏎ view  d delete  esc close", "  jk naviguer  ⏎ afficher  d supprimer  Échap fermer"),
+    ("  jk scroll  g/G top/bottom  esc back", "  jk défiler  g/G début/fin  Échap retour"),
+    (" Status Snapshots ", " Instantanés de statut "),
+    ("No snapshots yet: they are taken when an agent starts waiting or crashes", "Aucun instantané : ils sont pris quand un agent attend une réponse ou plante"),
+    ("jk navigate  Enter view  d delete  esc close", "jk naviguer  Entrée afficher  d supprimer  Échap fermer"),
+    ("The pane was empty", "Le panneau était vide"),
+    ("Snapshots of agents that stopped", "Instantanés des agents arrêtés"),
+    ("Deleted snapshot", "Instantané supprimé"),
+    ("Failed to open snapshot: {}", "Échec de l'ouverture de l'instantané : {}"),
+    ("Failed to delete snapshot: {}", "Échec de la suppression de l'instantané : {}"),
+    ("auto-fetching {}", "fetch automatique de {}"),
+    ("syncing {}", "synchronisation de {}"),
+    ("staging", "l'indexation"),
+    ("committing", "le commit"),
+    ("continuing", "la reprise"),
+    ("aborting", "l'abandon"),
+    ("removing a worktree", "la suppression d'un worktree"),
+    ("creating a worktree", "la création d'un worktree"),
+    ("wait for {} to finish in this repository", "attendez la fin de {} dans ce dépôt"),
+    ("{} queued behind {}", "{} en attente derrière {}"),
+    ("{} queued behind {} (^C cancel)", "{} en attente derrière {} (^C annuler)"),
+    ("  {}{} navigate  ⏎ pick  {} filter  esc cancel", "  {}{} naviguer  ⏎ choisir  {} filtrer  Échap annuler"),
+    ("No agents in {}. Press ⏎ to start one here.", "Aucun agent dans {}. Appuyez sur ⏎ pour en démarrer un ici."),
+    ("  {} help  ⏎ start agent here  {} new  {} reload  {} quit", "  {} aide  ⏎ démarrer un agent ici  {} nouvelle  {} recharger  {} quitter"),
+    (" (empty: named from the path)", " (vide : nommée d'après le chemin)"),
+    ("Failed to open the socket: {}", "Échec de l'ouverture du socket : {}"),
+    ("Moved worktree to the trash and killed session '{}'", "Worktree déplacé dans la corbeille et session '{}' tuée"),
+    ("The directory goes to the trash for {} days.", "Le dossier est gardé {} jours dans la corbeille."),
+    ("⚠ Commits on no remote: {}", "⚠ Commits absents de tout dépôt distant : {}"),
+    ("Looking for commits on no remote…", "Recherche des commits absents de tout dépôt distant…"),
+];
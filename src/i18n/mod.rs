@@ -0,0 +1,247 @@
+//! Translated interface text
+//!
+//! Interface strings are written in English in the source and passed through
+//! [`tr`], or [`trf!`] when they have `{}` placeholders. The English text is the
+//! key into each locale's catalog, and anything missing from a catalog is
+//! shown in English. Output from git, tmux and the OS is never translated.
+//!
+//! - `fr`: French catalog
+
+mod fr;
+
+use std::fmt::Display;
+use std::sync::atomic::{AtomicU8, Ordering};
+
+/// Interface language (`locale` in the config)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Locale {
+    #[default]
+    En,
+    Fr,
+}
+
+impl Locale {
+    /// Every supported locale
+    pub const ALL: [Self; 2] = [Self::En, Self::Fr];
+
+    /// Language code used in the config
+    pub fn code(self) -> &'static str {
+        match self {
+            Self::En => "en",
+            Self::Fr => "fr",
+        }
+    }
+
+    /// Parse a language code, ignoring region and encoding (`fr_FR.UTF-8`)
+    pub fn parse(value: &str) -> Option<Self> {
+        let language = value
+            .split(['_', '-', '.'])
+            .next()
+            .unwrap_or_default()
+            .to_ascii_lowercase();
+        Self::ALL.into_iter().find(|l| l.code() == language)
+    }
+
+    /// Translations from English, empty for English itself
+    fn catalog(self) -> &'static [(&'static str, &'static str)] {
+        match self {
+            Self::En => &[],
+            Self::Fr => fr::MESSAGES,
+        }
+    }
+}
+
+/// Locale used by `tr`, as an index into `Locale::ALL`
+static LOCALE: AtomicU8 = AtomicU8::new(0);
+
+/// Select the interface language
+pub fn set_locale(locale: Locale) {
+    let index = Locale::ALL.iter().position(|l| *l == locale).unwrap_or(0);
+    LOCALE.store(index as u8, Ordering::Relaxed);
+}
+
+fn current() -> Locale {
+    Locale::ALL
+        .get(LOCALE.load(Ordering::Relaxed) as usize)
+        .copied()
+        .unwrap_or_default()
+}
+
+/// Translate an interface string into the selected language
+pub fn tr(text: &'static str) -> &'static str {
+    translate(current(), text)
+}
+
+/// An interface string in English and in the selected language, for text
+/// whose English form is read too, such as footer hints naming keys
+pub fn tr_pair(text: &'static str) -> (&'static str, &'static str) {
+    (text, tr(text))
+}
+
+/// Translate an interface string and fill its `{}` placeholders in order,
+/// like `format!`
+macro_rules! trf {
+    ($text:literal, $($arg:expr),+ $(,)?) => {
+        $crate::i18n::fill(
+            $crate::i18n::tr($text),
+            &[$(&$arg as &dyn std::fmt::Display),+],
+        )
+    };
+}
+pub(crate) use trf;
+
+fn translate(locale: Locale, text: &'static str) -> &'static str {
+    locale
+        .catalog()
+        .iter()
+        .find(|(english, _)| *english == text)
+        .map_or(text, |(_, translated)| translated)
+}
+
+/// Fill the `{}` placeholders of `template` in order (used by `trf!`)
+pub fn fill(template: &str, args: &[&dyn Display]) -> String {
+    let mut result = String::with_capacity(template.len());
+    let mut args = args.iter();
+    let mut pieces = template.split("{}");
+    result.push_str(pieces.next().unwrap_or_default());
+    for piece in pieces {
+        if let Some(arg) = args.next() {
+            result.push_str(&arg.to_string());
+        }
+        result.push_str(piece);
+    }
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::Path;
+
+    #[test]
+    fn test_parse_locale() {
+        assert_eq!(Locale::parse("fr"), Some(Locale::Fr));
+        assert_eq!(Locale::parse("fr_FR.UTF-8"), Some(Locale::Fr));
+        assert_eq!(Locale::parse("EN-gb"), Some(Locale::En));
+        assert_eq!(Locale::parse("de"), None);
+        assert_eq!(Locale::parse(""), None);
+    }
+
+    #[test]
+    fn test_translate() {
+        assert_eq!(translate(Locale::En, "Kill session"), "Kill session");
+        assert_eq!(translate(Locale::Fr, "Kill session"), "Tuer la session");
+        assert_eq!(
+            translate(Locale::Fr, "not in any catalog"),
+            "not in any catalog"
+        );
+        assert_eq!(
+            fill(translate(Locale::Fr, "Killed session '{}'"), &[&"api"]),
+            "Session 'api' tuée"
+        );
+        assert_eq!(fill("{} of {}", &[&1]), "1 of ");
+    }
+
+    #[test]
+    fn test_catalogs_are_consistent() {
+        for locale in Locale::ALL {
+            let catalog = locale.catalog();
+            for (i, (english, translated)) in catalog.iter().enumerate() {
+                assert!(
+                    !catalog[..i].iter().any(|(other, _)| other == english),
+                    "{}: duplicate entry {:?}",
+                    locale.code(),
+                    english
+                );
+                assert!(
+                    !translated.is_empty(),
+                    "{}: empty {:?}",
+                    locale.code(),
+                    english
+                );
+                assert_eq!(
+                    english.matches("{}").count(),
+                    translated.matches("{}").count(),
+                    "{}: placeholders differ for {:?}",
+                    locale.code(),
+                    english
+                );
+                // Footer hints are matched to their English keys in order
+                assert!(
+                    !english.starts_with("  ")
+                        || english.split("  ").count() == translated.split("  ").count(),
+                    "{}: hints differ for {:?}",
+                    locale.code(),
+                    english
+                );
+            }
+        }
+    }
+
+    /// Every string literal passed to `tr`/`trf!` in the sources
+    fn source_strings(dir: &Path, strings: &mut Vec<String>) {
+        for entry in std::fs::read_dir(dir).unwrap().flatten() {
+            let path = entry.path();
+            if path.is_dir() {
+                source_strings(&path, strings);
+            } else if path.extension().is_some_and(|e| e == "rs") && !path.ends_with("i18n/mod.rs")
+            {
+                let source = std::fs::read_to_string(&path).unwrap();
+                for call in ["tr(", "trf!(", "tr_pair("] {
+                    for (start, _) in source.match_indices(call) {
+                        let preceded_by_ident = source[..start]
+                            .chars()
+                            .next_back()
+                            .is_some_and(|c| c.is_alphanumeric() || c == '_');
+                        let rest = source[start + call.len()..].trim_start();
+                        if preceded_by_ident || !rest.starts_with('"') {
+                            continue;
+                        }
+                        strings.push(string_literal(&rest[1..]));
+                    }
+                }
+            }
+        }
+    }
+
+    /// Value of the string literal whose body starts `source`
+    fn string_literal(source: &str) -> String {
+        let mut value = String::new();
+        let mut chars = source.chars();
+        while let Some(c) = chars.next() {
+            match c {
+                '"' => break,
+                '\\' => match chars.next() {
+                    Some('n') => value.push('\n'),
+                    Some('t') => value.push('\t'),
+                    Some(other) => value.push(other),
+                    None => break,
+                },
+                _ => value.push(c),
+            }
+        }
+        value
+    }
+
+    #[test]
+    fn test_catalogs_are_complete() {
+        let mut strings = Vec::new();
+        source_strings(
+            &Path::new(env!("CARGO_MANIFEST_DIR")).join("src"),
+            &mut strings,
+        );
+        assert!(!strings.is_empty());
+        for locale in Locale::ALL.into_iter().filter(|l| *l != Locale::En) {
+            let missing: Vec<&String> = strings
+                .iter()
+                .filter(|s| !locale.catalog().iter().any(|(english, _)| english == s))
+                .collect();
+            assert!(
+                missing.is_empty(),
+                "{}: missing {:?}",
+                locale.code(),
+                missing
+            );
+        }
+    }
+}
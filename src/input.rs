@@ -1,6 +1,11 @@
-use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers, MouseButton, MouseEvent, MouseEventKind};
 
-use crate::app::{App, CreatePullRequestField, Mode, NewSessionField, NewWorktreeField};
+use crate::app::{
+    App, CloneLayoutField, Command, CreatePullRequestField, CredentialsField, Mode,
+    NewSessionField, NewWorktreeField, SchedulePromptField, SessionAction,
+};
+use crate::config::Action;
+use crate::git::ConflictSide;
 
 /// Handle a key event and update the application state
 pub fn handle_key(app: &mut App, key: KeyEvent) {
@@ -8,36 +13,131 @@ pub fn handle_key(app: &mut App, key: KeyEvent) {
     app.clear_messages();
 
     match &app.mode {
+        Mode::Normal if app.pick.is_some() => handle_pick_mode(app, key),
         Mode::Normal => handle_normal_mode(app, key),
         Mode::ActionMenu => handle_action_menu_mode(app, key),
         Mode::Filter { .. } => handle_filter_mode(app, key),
         Mode::ConfirmAction => handle_confirm_action_mode(app, key),
         Mode::NewSession { .. } => handle_new_session_mode(app, key),
         Mode::Rename { .. } => handle_rename_mode(app, key),
+        Mode::EditTags { .. } => handle_edit_tags_mode(app, key),
         Mode::Commit { .. } => handle_commit_mode(app, key),
+        Mode::Rebase { .. } => handle_rebase_mode(app, key),
         Mode::NewWorktree { .. } => handle_new_worktree_mode(app, key),
+        Mode::BranchFromIssue { .. } => handle_branch_from_issue_mode(app, key),
+        Mode::CloneLayout { .. } => handle_clone_layout_mode(app, key),
+        Mode::MoveWindow { .. } => handle_move_window_mode(app, key),
+        Mode::ArrangePanes { .. } => handle_arrange_panes_mode(app, key),
         Mode::CreatePullRequest { .. } => handle_create_pr_mode(app, key),
-        Mode::Help => handle_help_mode(app, key),
+        Mode::SlashCommandMenu { .. } => handle_slash_command_mode(app, key),
+        Mode::TemplatePicker { .. } => handle_template_picker_mode(app, key),
+        Mode::Workspaces { .. } => handle_workspaces_mode(app, key),
+        Mode::SchedulePrompt { .. } => handle_schedule_prompt_mode(app, key),
+        Mode::SendFile { .. } => handle_send_file_mode(app, key),
+        Mode::Review { .. } => handle_review_mode(app, key),
+        Mode::PendingJobs { .. } => handle_pending_jobs_mode(app, key),
+        Mode::Conflicts { .. } => handle_conflicts_mode(app, key),
+        Mode::Archived { .. } => handle_archived_mode(app, key),
+        Mode::Logs { .. } => handle_logs_mode(app, key),
+        Mode::Snapshots { content: None, .. } => handle_snapshots_mode(app, key),
+        Mode::Snapshots { .. } => handle_snapshot_mode(app, key),
+        Mode::ActionOutput { .. } => handle_action_output_mode(app, key),
+        Mode::WorkTime { .. } => handle_work_time_mode(app, key),
+        Mode::Stats { .. } => handle_stats_mode(app, key),
+        Mode::Keybindings { .. } => handle_keybindings_mode(app, key),
+        Mode::Search { .. } => handle_search_mode(app, key),
+        Mode::Palette { .. } => handle_palette_mode(app, key),
+        Mode::AllPanes { .. } => handle_all_panes_mode(app, key),
+        Mode::Sessions { .. } => handle_sessions_mode(app, key),
+        Mode::Credentials { .. } => handle_credentials_mode(app, key),
+        Mode::Passphrase { .. } => handle_passphrase_mode(app, key),
+        Mode::Help { .. } => handle_help_mode(app, key),
+        Mode::ConfigError { .. } => handle_config_error_mode(app, key),
+        Mode::Sync { .. } => handle_sync_mode(app, key),
+    }
+}
+
+/// Handle a mouse event: clicks on footer buttons and list rows, and the
+/// wheel over the preview, the list or the help screen
+pub fn handle_mouse(app: &mut App, mouse: MouseEvent) {
+    let (column, row) = (mouse.column, mouse.row);
+    match mouse.kind {
+        MouseEventKind::Down(MouseButton::Left) => {
+            let button = app
+                .footer_buttons
+                .iter()
+                .find(|button| button.area.contains((column, row).into()));
+            if let Some(button) = button {
+                handle_key(app, KeyEvent::from(button.key));
+            } else if matches!(app.mode, Mode::Normal) {
+                if let Some(index) = app.scroll_state.instance_at(column, row) {
+                    app.clear_messages();
+                    app.select_index(index);
+                }
+            }
+        }
+        MouseEventKind::ScrollUp | MouseEventKind::ScrollDown => {
+            let up = mouse.kind == MouseEventKind::ScrollUp;
+            match app.mode {
+                Mode::Help { .. } => app.scroll_help(if up { -3 } else { 3 }),
+                Mode::Normal if app.scroll_state.in_preview(column, row) => {
+                    app.scroll_preview(if up { 3 } else { -3 });
+                }
+                Mode::Normal if app.scroll_state.instance_at(column, row).is_some() => {
+                    if up {
+                        app.select_prev();
+                    } else {
+                        app.select_next();
+                    }
+                }
+                _ => {}
+            }
+        }
+        _ => {}
     }
 }
 
 fn handle_normal_mode(app: &mut App, key: KeyEvent) {
+    // Rebindable single-character keys (`[keys]` in the config)
+    if let Some(action) = bound_action(app, key) {
+        run_bound_action(app, action);
+        return;
+    }
+
+    // Shell commands of `[[actions]]`
+    if let Some(index) = custom_action(app, key) {
+        app.run_custom_action(index);
+        return;
+    }
+
     match key.code {
         // Quit
-        KeyCode::Char('q') | KeyCode::Esc => {
+        KeyCode::Esc => {
             app.should_quit = true;
         }
 
         // Navigation
-        KeyCode::Char('j') | KeyCode::Down => {
+        KeyCode::Down => {
             app.select_next();
         }
-        KeyCode::Char('k') | KeyCode::Up => {
+        KeyCode::Up => {
             app.select_prev();
         }
+        KeyCode::PageDown => {
+            app.select_page(true);
+        }
+        KeyCode::PageUp => {
+            app.select_page(false);
+        }
+        KeyCode::Home => {
+            app.select_index(0);
+        }
+        KeyCode::End => {
+            app.select_index(usize::MAX);
+        }
 
         // Enter action menu
-        KeyCode::Char('l') | KeyCode::Right => {
+        KeyCode::Right => {
             app.enter_action_menu();
         }
 
@@ -46,42 +146,151 @@ fn handle_normal_mode(app: &mut App, key: KeyEvent) {
             app.switch_to_selected();
         }
 
-        // New session
-        KeyCode::Char('n') => {
-            app.start_new_session();
+        // Cancel a running push/pull/fetch
+        KeyCode::Char('c')
+            if key.modifiers.contains(KeyModifiers::CONTROL) && app.git_job.is_some() =>
+        {
+            app.cancel_git_job();
         }
 
-        // Kill session (capital K to avoid accidents)
-        KeyCode::Char('K') => {
-            app.start_kill();
+        // Clear filter
+        KeyCode::Char('c') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+            app.clear_filter();
         }
 
-        // Rename session
-        KeyCode::Char('r') => {
-            app.start_rename();
+        // Command palette
+        KeyCode::Char('p') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+            app.start_palette();
         }
 
-        // Filter
-        KeyCode::Char('/') => {
-            app.start_filter();
-        }
+        _ => {}
+    }
+}
 
-        // Clear filter
-        KeyCode::Char('c') if key.modifiers.contains(KeyModifiers::CONTROL) => {
-            app.clear_filter();
-        }
+/// The instance list alone, with `--pick`: choose an instance or cancel
+fn handle_pick_mode(app: &mut App, key: KeyEvent) {
+    match bound_action(app, key) {
+        Some(Action::SelectNext) => app.select_next(),
+        Some(Action::SelectPrev) => app.select_prev(),
+        Some(Action::Switch) => app.switch_to_selected(),
+        Some(Action::Filter) => app.start_filter(),
+        Some(Action::Quit) => app.should_quit = true,
+        Some(_) => {}
+        None => match key.code {
+            KeyCode::Esc => app.should_quit = true,
+            KeyCode::Char('c') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                app.should_quit = true;
+            }
+            KeyCode::Down => app.select_next(),
+            KeyCode::Up => app.select_prev(),
+            KeyCode::PageDown => app.select_page(true),
+            KeyCode::PageUp => app.select_page(false),
+            KeyCode::Home => app.select_index(0),
+            KeyCode::End => app.select_index(usize::MAX),
+            KeyCode::Enter => app.switch_to_selected(),
+            _ => {}
+        },
+    }
+}
+
+/// Run a session list action, as its key or the command palette asks
+fn run_bound_action(app: &mut App, action: Action) {
+    match action {
+        Action::SelectNext => app.select_next(),
+        Action::SelectPrev => app.select_prev(),
+        Action::OpenMenu => app.enter_action_menu(),
+        Action::Switch => app.switch_to_selected(),
+        Action::SwitchBack => app.switch_back(),
+        Action::NewSession => app.start_new_session(),
+        Action::Kill => app.start_kill(),
+        Action::Rename => app.start_rename(),
+        // Duplicate agent into a new window
+        Action::Duplicate => app.duplicate_selected(false),
+        Action::Filter => app.start_filter(),
+        // Continue a waiting/idle agent
+        Action::Continue => app.continue_selected(),
+        // Interrupt a working agent
+        Action::Interrupt => app.interrupt_selected(),
+        Action::SlashCommand => app.start_slash_command_menu(),
+        Action::Template => app.start_template_picker(),
+        // Send a file or the clipboard
+        Action::SendFile => app.start_send_file(),
+        // Schedule a prompt for later
+        Action::Schedule => app.start_schedule_prompt(),
+        Action::ScheduledPrompts => app.show_pending_jobs(),
+        Action::Archived => app.show_archived(),
+        Action::Logs => app.show_logs(),
+        Action::Snapshots => app.show_snapshots(),
+        Action::WorkTime => app.show_work_time(),
+        Action::Stats => app.show_stats(),
+        // List every pane
+        Action::AllPanes => app.show_all_panes(),
+        // List every tmux session
+        Action::Sessions => app.show_sessions(false),
+        Action::Workspace => app.start_workspace_picker(),
+        Action::Search => app.start_search(),
+        Action::Palette => app.start_palette(),
+        // Menu actions, when the menu would offer them
+        Action::Review => app.run_action(SessionAction::Review),
+        Action::Stage => app.run_action(SessionAction::Stage),
+        Action::Commit => app.run_action(SessionAction::Commit),
+        Action::Push => app.run_action(SessionAction::Push),
+        Action::Pull => app.run_action(SessionAction::Pull),
+        Action::Fetch => app.run_action(SessionAction::Fetch),
+        Action::Upstream => app.offer_upstream_actions(),
+        Action::Sort => app.cycle_sort_order(),
+        Action::PreviewLarger => app.resize_preview(true),
+        Action::PreviewSmaller => app.resize_preview(false),
+        Action::NewWorktree => app.run_action(SessionAction::NewWorktree),
+        Action::Mark => app.toggle_mark(),
+        // Pull the marked checkouts
+        Action::Sync => app.start_sync(),
+        Action::Tag => app.start_edit_tags(),
+        Action::GroupByTag => app.toggle_group_by_tag(),
+        Action::Refresh => app.refresh(),
+        Action::ReloadConfig => app.reload_config(),
+        Action::Keybindings => app.show_keybindings(),
+        Action::Help => app.show_help(),
+        Action::Quit => app.should_quit = true,
+    }
+}
 
-        // Refresh
-        KeyCode::Char('R') => {
-            app.refresh();
+/// Action bound to a key pressed without Ctrl or Alt
+fn bound_action(app: &App, key: KeyEvent) -> Option<Action> {
+    match key.code {
+        KeyCode::Char(c)
+            if !key
+                .modifiers
+                .intersects(KeyModifiers::CONTROL | KeyModifiers::ALT) =>
+        {
+            app.config.keys.action(c)
         }
+        _ => None,
+    }
+}
 
-        // Help
-        KeyCode::Char('?') => {
-            app.show_help();
+/// Index of the custom action bound to a key pressed without Ctrl or Alt
+fn custom_action(app: &App, key: KeyEvent) -> Option<usize> {
+    match key.code {
+        KeyCode::Char(c)
+            if !key
+                .modifiers
+                .intersects(KeyModifiers::CONTROL | KeyModifiers::ALT) =>
+        {
+            app.config.actions.iter().position(|a| a.key == Some(c))
         }
+        _ => None,
+    }
+}
 
-        _ => {}
+/// Key code of a list or dialog, with the bound down, up and quit keys read
+/// as the arrows and Esc
+fn list_key(app: &App, key: KeyEvent) -> KeyCode {
+    match bound_action(app, key) {
+        Some(Action::SelectNext) => KeyCode::Down,
+        Some(Action::SelectPrev) => KeyCode::Up,
+        Some(Action::Quit) => KeyCode::Esc,
+        _ => key.code,
     }
 }
 
@@ -108,31 +317,32 @@ fn handle_filter_mode(app: &mut App, key: KeyEvent) {
 }
 
 fn handle_action_menu_mode(app: &mut App, key: KeyEvent) {
-    match key.code {
+    match bound_action(app, key) {
         // Navigate actions
-        KeyCode::Char('j') | KeyCode::Down => {
-            app.select_next_action();
-        }
-        KeyCode::Char('k') | KeyCode::Up => {
-            app.select_prev_action();
-        }
-
+        Some(Action::SelectNext) => app.select_next_action(),
+        Some(Action::SelectPrev) => app.select_prev_action(),
         // Execute selected action
-        KeyCode::Enter | KeyCode::Char('l') | KeyCode::Right => {
-            app.execute_selected_action();
-        }
-
-        // Back to session list
-        KeyCode::Char('h') | KeyCode::Left | KeyCode::Esc => {
-            app.cancel();
-        }
-
+        Some(Action::OpenMenu) => app.execute_selected_action(),
         // Quit entirely
-        KeyCode::Char('q') => {
-            app.should_quit = true;
-        }
+        Some(Action::Quit) => app.should_quit = true,
+        _ => match key.code {
+            KeyCode::Down => {
+                app.select_next_action();
+            }
+            KeyCode::Up => {
+                app.select_prev_action();
+            }
+            KeyCode::Enter | KeyCode::Right => {
+                app.execute_selected_action();
+            }
 
-        _ => {}
+            // Back to session list
+            KeyCode::Char('h') | KeyCode::Left | KeyCode::Esc => {
+                app.cancel();
+            }
+
+            _ => {}
+        },
     }
 }
 
@@ -236,6 +446,99 @@ fn handle_new_session_mode(app: &mut App, key: KeyEvent) {
     }
 }
 
+fn handle_branch_from_issue_mode(app: &mut App, key: KeyEvent) {
+    match key.code {
+        KeyCode::Esc => {
+            app.cancel();
+        }
+        KeyCode::Enter => {
+            app.confirm_branch_from_issue();
+        }
+        KeyCode::Down => {
+            app.select_issue(1);
+        }
+        KeyCode::Up => {
+            app.select_issue(-1);
+        }
+        KeyCode::Tab => {
+            if let Mode::BranchFromIssue {
+                ref mut send_prompt,
+                ..
+            } = app.mode
+            {
+                *send_prompt = !*send_prompt;
+            }
+        }
+        KeyCode::Backspace => {
+            if let Mode::BranchFromIssue {
+                ref mut input,
+                ref mut selected,
+                ..
+            } = app.mode
+            {
+                input.pop();
+                *selected = 0;
+            }
+        }
+        KeyCode::Char(c) => {
+            if let Mode::BranchFromIssue {
+                ref mut input,
+                ref mut selected,
+                ..
+            } = app.mode
+            {
+                input.push(c);
+                *selected = 0;
+            }
+        }
+        _ => {}
+    }
+}
+
+fn handle_rebase_mode(app: &mut App, key: KeyEvent) {
+    match key.code {
+        KeyCode::Esc => {
+            app.cancel();
+        }
+        KeyCode::Enter => {
+            app.confirm_rebase();
+        }
+        KeyCode::Backspace => {
+            if let Mode::Rebase { ref mut base, .. } = app.mode {
+                base.pop();
+            }
+        }
+        KeyCode::Char(c) => {
+            if let Mode::Rebase { ref mut base, .. } = app.mode {
+                base.push(c);
+            }
+        }
+        _ => {}
+    }
+}
+
+fn handle_edit_tags_mode(app: &mut App, key: KeyEvent) {
+    match key.code {
+        KeyCode::Esc => {
+            app.cancel();
+        }
+        KeyCode::Enter => {
+            app.confirm_tags();
+        }
+        KeyCode::Backspace => {
+            if let Mode::EditTags { ref mut input, .. } = app.mode {
+                input.pop();
+            }
+        }
+        KeyCode::Char(c) => {
+            if let Mode::EditTags { ref mut input, .. } = app.mode {
+                input.push(c);
+            }
+        }
+        _ => {}
+    }
+}
+
 fn handle_rename_mode(app: &mut App, key: KeyEvent) {
     match key.code {
         KeyCode::Esc => {
@@ -289,6 +592,112 @@ fn handle_commit_mode(app: &mut App, key: KeyEvent) {
     }
 }
 
+fn handle_clone_layout_mode(app: &mut App, key: KeyEvent) {
+    match key.code {
+        KeyCode::Esc => {
+            app.cancel();
+        }
+        KeyCode::Tab | KeyCode::BackTab => {
+            if let Mode::CloneLayout { ref mut field, .. } = app.mode {
+                *field = match field {
+                    CloneLayoutField::Name => CloneLayoutField::Branch,
+                    CloneLayoutField::Branch => CloneLayoutField::Name,
+                };
+            }
+        }
+        KeyCode::Enter => {
+            app.confirm_clone_layout();
+        }
+        KeyCode::Backspace => {
+            if let Mode::CloneLayout {
+                ref mut name,
+                ref mut branch,
+                field,
+                ..
+            } = app.mode
+            {
+                match field {
+                    CloneLayoutField::Name => name.pop(),
+                    CloneLayoutField::Branch => branch.pop(),
+                };
+            }
+        }
+        KeyCode::Char(c) => {
+            if let Mode::CloneLayout {
+                ref mut name,
+                ref mut branch,
+                field,
+                ..
+            } = app.mode
+            {
+                match field {
+                    CloneLayoutField::Name => name.push(c),
+                    CloneLayoutField::Branch => branch.push(c),
+                }
+            }
+        }
+        _ => {}
+    }
+}
+
+fn handle_arrange_panes_mode(app: &mut App, key: KeyEvent) {
+    match list_key(app, key) {
+        KeyCode::Down => {
+            app.select_arrangement(1);
+        }
+        KeyCode::Up => {
+            app.select_arrangement(-1);
+        }
+        KeyCode::Enter => {
+            app.apply_arrangement();
+        }
+        KeyCode::Esc => {
+            app.cancel();
+        }
+        _ => {}
+    }
+}
+
+fn handle_move_window_mode(app: &mut App, key: KeyEvent) {
+    match key.code {
+        KeyCode::Esc => {
+            app.cancel();
+        }
+        KeyCode::Down | KeyCode::Tab => {
+            app.select_move_session(1);
+        }
+        KeyCode::Up | KeyCode::BackTab => {
+            app.select_move_session(-1);
+        }
+        KeyCode::Enter => {
+            app.confirm_move_window();
+        }
+        KeyCode::Backspace => {
+            if let Mode::MoveWindow {
+                ref mut input,
+                ref mut selected,
+                ..
+            } = app.mode
+            {
+                input.pop();
+                *selected = 0;
+            }
+        }
+        KeyCode::Char(c) => {
+            if let Mode::MoveWindow {
+                ref mut input,
+                ref mut selected,
+                ..
+            } = app.mode
+            {
+                input.push(c);
+                *selected = 0;
+            }
+        }
+        _ => {}
+    }
+}
+
 fn handle_new_worktree_mode(app: &mut App, key: KeyEvent) {
     // Get current field to determine behavior
     let current_field = if let Mode::NewWorktree { field, .. } = &app.mode {
@@ -305,7 +714,8 @@ fn handle_new_worktree_mode(app: &mut App, key: KeyEvent) {
             // Cycle through fields
             if let Mode::NewWorktree { ref mut field, .. } = app.mode {
                 *field = match field {
-                    NewWorktreeField::Branch => NewWorktreeField::Path,
+                    NewWorktreeField::Branch => NewWorktreeField::Upstream,
+                    NewWorktreeField::Upstream => NewWorktreeField::Path,
                     NewWorktreeField::Path => NewWorktreeField::SessionName,
                     NewWorktreeField::SessionName => NewWorktreeField::Branch,
                 };
@@ -316,7 +726,8 @@ fn handle_new_worktree_mode(app: &mut App, key: KeyEvent) {
             if let Mode::NewWorktree { ref mut field, .. } = app.mode {
                 *field = match field {
                     NewWorktreeField::Branch => NewWorktreeField::SessionName,
-                    NewWorktreeField::Path => NewWorktreeField::Branch,
+                    NewWorktreeField::Upstream => NewWorktreeField::Branch,
+                    NewWorktreeField::Path => NewWorktreeField::Upstream,
                     NewWorktreeField::SessionName => NewWorktreeField::Path,
                 };
             }
@@ -327,6 +738,7 @@ fn handle_new_worktree_mode(app: &mut App, key: KeyEvent) {
         KeyCode::Backspace => {
             if let Mode::NewWorktree {
                 ref mut branch_input,
+                ref mut upstream,
                 ref mut worktree_path,
                 ref mut session_name,
                 ref mut path_selected,
@@ -338,6 +750,9 @@ fn handle_new_worktree_mode(app: &mut App, key: KeyEvent) {
                     NewWorktreeField::Branch => {
                         branch_input.pop();
                     }
+                    NewWorktreeField::Upstream => {
+                        upstream.pop();
+                    }
                     NewWorktreeField::Path => {
                         worktree_path.pop();
                         *path_selected = None; // Reset selection on edit
@@ -357,6 +772,7 @@ fn handle_new_worktree_mode(app: &mut App, key: KeyEvent) {
         KeyCode::Char(c) => {
             if let Mode::NewWorktree {
                 ref mut branch_input,
+                ref mut upstream,
                 ref mut worktree_path,
                 ref mut session_name,
                 ref mut path_selected,
@@ -368,6 +784,9 @@ fn handle_new_worktree_mode(app: &mut App, key: KeyEvent) {
                     NewWorktreeField::Branch => {
                         branch_input.push(c);
                     }
+                    NewWorktreeField::Upstream => {
+                        upstream.push(c);
+                    }
                     NewWorktreeField::Path => {
                         worktree_path.push(c);
                         *path_selected = None; // Reset selection on edit
@@ -434,6 +853,10 @@ fn handle_new_worktree_mode(app: &mut App, key: KeyEvent) {
             app.select_prev_worktree_path();
         }
         // Accept path completion with Right arrow
+        // Accept the suggested upstream with Right arrow
+        KeyCode::Right if current_field == NewWorktreeField::Upstream => {
+            app.accept_upstream_completion();
+        }
         KeyCode::Right if current_field == NewWorktreeField::Path => {
             app.accept_worktree_path_completion();
         }
@@ -518,9 +941,827 @@ fn handle_create_pr_mode(app: &mut App, key: KeyEvent) {
     }
 }
 
-fn handle_help_mode(app: &mut App, key: KeyEvent) {
+fn handle_slash_command_mode(app: &mut App, key: KeyEvent) {
+    match list_key(app, key) {
+        KeyCode::Down => {
+            app.select_slash_command(1);
+        }
+        KeyCode::Up => {
+            app.select_slash_command(-1);
+        }
+        KeyCode::Enter => {
+            app.confirm_slash_command();
+        }
+        KeyCode::Esc => {
+            app.cancel();
+        }
+        _ => {}
+    }
+}
+
+fn handle_template_picker_mode(app: &mut App, key: KeyEvent) {
+    match list_key(app, key) {
+        KeyCode::Down => {
+            app.select_template(1);
+        }
+        KeyCode::Up => {
+            app.select_template(-1);
+        }
+        KeyCode::Enter => {
+            app.confirm_template();
+        }
+        KeyCode::Esc => {
+            app.cancel();
+        }
+        _ => {}
+    }
+}
+
+fn handle_workspaces_mode(app: &mut App, key: KeyEvent) {
+    match list_key(app, key) {
+        KeyCode::Down => {
+            app.select_workspace(1);
+        }
+        KeyCode::Up => {
+            app.select_workspace(-1);
+        }
+        KeyCode::Enter => {
+            app.confirm_workspace();
+        }
+        KeyCode::Esc => {
+            app.cancel();
+        }
+        _ => {}
+    }
+}
+
+fn handle_send_file_mode(app: &mut App, key: KeyEvent) {
     match key.code {
-        KeyCode::Char('q') | KeyCode::Esc | KeyCode::Char('?') => {
+        KeyCode::Esc => {
+            app.cancel();
+        }
+        KeyCode::Enter => {
+            app.confirm_send_file();
+        }
+        KeyCode::Tab => {
+            app.cycle_send_file_template();
+        }
+        KeyCode::Up => {
+            app.select_send_file_path(-1);
+        }
+        KeyCode::Down => {
+            app.select_send_file_path(1);
+        }
+        KeyCode::Right => {
+            app.accept_send_file_path_completion();
+        }
+        // Clear the path (an empty path sends the clipboard)
+        KeyCode::Char('u') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+            if let Mode::SendFile {
+                ref mut path,
+                ref mut path_selected,
+                ..
+            } = app.mode
+            {
+                path.clear();
+                *path_selected = None;
+            }
+            app.update_send_file_path_suggestions();
+        }
+        KeyCode::Backspace => {
+            if let Mode::SendFile {
+                ref mut path,
+                ref mut path_selected,
+                ..
+            } = app.mode
+            {
+                path.pop();
+                *path_selected = None;
+            }
+            app.update_send_file_path_suggestions();
+        }
+        KeyCode::Char(c) => {
+            if let Mode::SendFile {
+                ref mut path,
+                ref mut path_selected,
+                ..
+            } = app.mode
+            {
+                path.push(c);
+                *path_selected = None;
+            }
+            app.update_send_file_path_suggestions();
+        }
+        _ => {}
+    }
+}
+
+fn handle_review_mode(app: &mut App, key: KeyEvent) {
+    let editing = matches!(app.mode, Mode::Review { input: Some(_), .. });
+
+    if editing {
+        match key.code {
+            KeyCode::Esc => {
+                if let Mode::Review { ref mut input, .. } = app.mode {
+                    *input = None;
+                }
+            }
+            KeyCode::Enter => {
+                app.review_confirm_comment();
+            }
+            KeyCode::Backspace => {
+                if let Mode::Review {
+                    input: Some(ref mut text),
+                    ..
+                } = app.mode
+                {
+                    text.pop();
+                }
+            }
+            KeyCode::Char(c) => {
+                if let Mode::Review {
+                    input: Some(ref mut text),
+                    ..
+                } = app.mode
+                {
+                    text.push(c);
+                }
+            }
+            _ => {}
+        }
+        return;
+    }
+
+    match list_key(app, key) {
+        KeyCode::Down => {
+            app.review_move(1);
+        }
+        KeyCode::Up => {
+            app.review_move(-1);
+        }
+        KeyCode::PageDown | KeyCode::Char(' ') => {
+            app.review_move(20);
+        }
+        KeyCode::PageUp => {
+            app.review_move(-20);
+        }
+        KeyCode::Char(']') => {
+            app.review_jump_file(true);
+        }
+        KeyCode::Char('[') => {
+            app.review_jump_file(false);
+        }
+        KeyCode::Char('c') | KeyCode::Enter => {
+            app.review_start_comment();
+        }
+        KeyCode::Char('d') => {
+            app.review_delete_comment();
+        }
+        KeyCode::Char('s') => {
+            app.send_review();
+        }
+        KeyCode::Esc => {
+            app.cancel();
+        }
+        _ => {}
+    }
+}
+
+fn handle_schedule_prompt_mode(app: &mut App, key: KeyEvent) {
+    match key.code {
+        KeyCode::Esc => {
+            app.cancel();
+        }
+        KeyCode::Tab | KeyCode::BackTab => {
+            if let Mode::SchedulePrompt { ref mut field, .. } = app.mode {
+                *field = match field {
+                    SchedulePromptField::Prompt => SchedulePromptField::When,
+                    SchedulePromptField::When => SchedulePromptField::Prompt,
+                };
+            }
+        }
+        KeyCode::Enter => {
+            app.confirm_schedule_prompt();
+        }
+        KeyCode::Backspace => {
+            if let Mode::SchedulePrompt {
+                ref mut prompt,
+                ref mut when,
+                field,
+            } = app.mode
+            {
+                match field {
+                    SchedulePromptField::Prompt => {
+                        prompt.pop();
+                    }
+                    SchedulePromptField::When => {
+                        when.pop();
+                    }
+                }
+            }
+        }
+        KeyCode::Char(c) => {
+            if let Mode::SchedulePrompt {
+                ref mut prompt,
+                ref mut when,
+                field,
+            } = app.mode
+            {
+                match field {
+                    SchedulePromptField::Prompt => prompt.push(c),
+                    SchedulePromptField::When => when.push(c),
+                }
+            }
+        }
+        _ => {}
+    }
+}
+
+fn handle_credentials_mode(app: &mut App, key: KeyEvent) {
+    match key.code {
+        KeyCode::Esc => {
+            app.cancel();
+        }
+        KeyCode::Tab | KeyCode::BackTab => {
+            if let Mode::Credentials { ref mut field, .. } = app.mode {
+                *field = field.next();
+            }
+        }
+        KeyCode::Enter => {
+            app.confirm_credentials();
+        }
+        KeyCode::Backspace => {
+            if let Mode::Credentials {
+                ref mut username,
+                ref mut password,
+                field,
+                ..
+            } = app.mode
+            {
+                match field {
+                    CredentialsField::Username => {
+                        username.pop();
+                    }
+                    CredentialsField::Password => {
+                        password.pop();
+                    }
+                    CredentialsField::Store => {}
+                }
+            }
+        }
+        KeyCode::Char(c) => {
+            if let Mode::Credentials {
+                ref mut username,
+                ref mut password,
+                ref mut store,
+                field,
+                ..
+            } = app.mode
+            {
+                match field {
+                    CredentialsField::Username => username.push(c),
+                    CredentialsField::Password => password.push(c),
+                    CredentialsField::Store => {
+                        if c == ' ' {
+                            *store = !*store;
+                        }
+                    }
+                }
+            }
+        }
+        _ => {}
+    }
+}
+
+fn handle_passphrase_mode(app: &mut App, key: KeyEvent) {
+    match key.code {
+        KeyCode::Esc => {
+            app.cancel();
+        }
+        KeyCode::Enter => {
+            app.confirm_passphrase();
+        }
+        KeyCode::Backspace => {
+            if let Mode::Passphrase {
+                ref mut passphrase, ..
+            } = app.mode
+            {
+                passphrase.pop();
+            }
+        }
+        KeyCode::Char(c) => {
+            if let Mode::Passphrase {
+                ref mut passphrase, ..
+            } = app.mode
+            {
+                passphrase.push(c);
+            }
+        }
+        _ => {}
+    }
+}
+
+fn handle_pending_jobs_mode(app: &mut App, key: KeyEvent) {
+    match list_key(app, key) {
+        KeyCode::Down => {
+            app.select_pending_job(1);
+        }
+        KeyCode::Up => {
+            app.select_pending_job(-1);
+        }
+        KeyCode::Char('d') | KeyCode::Delete => {
+            app.cancel_selected_job();
+        }
+        KeyCode::Esc => {
+            app.cancel();
+        }
+        _ if bound_action(app, key) == Some(Action::ScheduledPrompts) => {
+            app.cancel();
+        }
+        _ => {}
+    }
+}
+
+fn handle_conflicts_mode(app: &mut App, key: KeyEvent) {
+    match list_key(app, key) {
+        KeyCode::Down => {
+            app.select_conflict(1);
+        }
+        KeyCode::Up => {
+            app.select_conflict(-1);
+        }
+        KeyCode::Char('e') | KeyCode::Enter => {
+            app.edit_conflict();
+        }
+        KeyCode::Char('o') => {
+            app.resolve_conflict(Some(ConflictSide::Ours));
+        }
+        KeyCode::Char('t') => {
+            app.resolve_conflict(Some(ConflictSide::Theirs));
+        }
+        // Mark resolved (stage)
+        KeyCode::Char('s') => {
+            app.resolve_conflict(None);
+        }
+        KeyCode::Char('c') => {
+            app.continue_after_conflicts();
+        }
+        KeyCode::Esc => {
+            app.cancel();
+        }
+        _ => {}
+    }
+}
+
+fn handle_archived_mode(app: &mut App, key: KeyEvent) {
+    match list_key(app, key) {
+        KeyCode::Down => {
+            app.select_archived(1);
+        }
+        KeyCode::Up => {
+            app.select_archived(-1);
+        }
+        KeyCode::Enter => {
+            app.restore_archived();
+        }
+        KeyCode::Char('d') | KeyCode::Delete => {
+            app.delete_archived();
+        }
+        KeyCode::Esc => {
+            app.cancel();
+        }
+        _ if bound_action(app, key) == Some(Action::Archived) => {
+            app.cancel();
+        }
+        _ => {}
+    }
+}
+
+fn handle_snapshots_mode(app: &mut App, key: KeyEvent) {
+    match list_key(app, key) {
+        KeyCode::Down => {
+            app.select_snapshot(1);
+        }
+        KeyCode::Up => {
+            app.select_snapshot(-1);
+        }
+        KeyCode::Enter => {
+            app.open_snapshot();
+        }
+        KeyCode::Char('d') | KeyCode::Delete => {
+            app.delete_snapshot();
+        }
+        KeyCode::Esc => {
+            app.cancel();
+        }
+        _ if bound_action(app, key) == Some(Action::Snapshots) => {
+            app.cancel();
+        }
+        _ => {}
+    }
+}
+
+fn handle_snapshot_mode(app: &mut App, key: KeyEvent) {
+    match list_key(app, key) {
+        KeyCode::Down => {
+            app.scroll_snapshot(-1);
+        }
+        KeyCode::Up => {
+            app.scroll_snapshot(1);
+        }
+        KeyCode::PageDown => {
+            app.scroll_snapshot(-10);
+        }
+        KeyCode::PageUp => {
+            app.scroll_snapshot(10);
+        }
+        KeyCode::Char('g') | KeyCode::Home => {
+            app.scroll_snapshot(isize::MAX);
+        }
+        KeyCode::Char('G') | KeyCode::End => {
+            app.scroll_snapshot(isize::MIN);
+        }
+        // Back to the list
+        KeyCode::Esc => {
+            app.close_snapshot();
+        }
+        _ if bound_action(app, key) == Some(Action::Snapshots) => {
+            app.close_snapshot();
+        }
+        _ => {}
+    }
+}
+
+fn handle_action_output_mode(app: &mut App, key: KeyEvent) {
+    match list_key(app, key) {
+        KeyCode::Down => {
+            app.scroll_action_output(-1);
+        }
+        KeyCode::Up => {
+            app.scroll_action_output(1);
+        }
+        KeyCode::PageDown => {
+            app.scroll_action_output(-10);
+        }
+        KeyCode::PageUp => {
+            app.scroll_action_output(10);
+        }
+        KeyCode::Char('g') | KeyCode::Home => {
+            app.scroll_action_output(isize::MAX);
+        }
+        KeyCode::Char('G') | KeyCode::End => {
+            app.scroll_action_output(isize::MIN);
+        }
+        // Run again
+        KeyCode::Char('r') => {
+            app.rerun_custom_action();
+        }
+        KeyCode::Esc | KeyCode::Enter => {
+            app.cancel();
+        }
+        _ => {}
+    }
+}
+
+fn handle_logs_mode(app: &mut App, key: KeyEvent) {
+    match list_key(app, key) {
+        KeyCode::Down => {
+            app.scroll_logs(-1);
+        }
+        KeyCode::Up => {
+            app.scroll_logs(1);
+        }
+        KeyCode::PageDown => {
+            app.scroll_logs(-10);
+        }
+        KeyCode::PageUp => {
+            app.scroll_logs(10);
+        }
+        KeyCode::Char('g') | KeyCode::Home => {
+            app.scroll_logs(isize::MAX);
+        }
+        KeyCode::Char('G') | KeyCode::End => {
+            app.scroll_logs(isize::MIN);
+        }
+        // Reload
+        KeyCode::Char('r') => {
+            app.show_logs();
+        }
+        KeyCode::Esc => {
+            app.cancel();
+        }
+        _ if bound_action(app, key) == Some(Action::Logs) => {
+            app.cancel();
+        }
+        _ => {}
+    }
+}
+
+fn handle_work_time_mode(app: &mut App, key: KeyEvent) {
+    match list_key(app, key) {
+        KeyCode::Down => {
+            app.select_work_time(1);
+        }
+        KeyCode::Up => {
+            app.select_work_time(-1);
+        }
+        KeyCode::Esc => {
+            app.cancel();
+        }
+        _ if bound_action(app, key) == Some(Action::WorkTime) => {
+            app.cancel();
+        }
+        _ => {}
+    }
+}
+
+fn handle_sync_mode(app: &mut App, key: KeyEvent) {
+    match list_key(app, key) {
+        KeyCode::Down => {
+            app.select_sync_row(1);
+        }
+        KeyCode::Up => {
+            app.select_sync_row(-1);
+        }
+        KeyCode::Esc | KeyCode::Enter => {
+            app.close_sync();
+        }
+        _ => {}
+    }
+}
+
+fn handle_stats_mode(app: &mut App, key: KeyEvent) {
+    match list_key(app, key) {
+        // Reload
+        KeyCode::Char('r') => {
+            app.show_stats();
+        }
+        KeyCode::Esc => {
+            app.cancel();
+        }
+        _ if bound_action(app, key) == Some(Action::Stats) => {
+            app.cancel();
+        }
+        _ => {}
+    }
+}
+
+fn handle_keybindings_mode(app: &mut App, key: KeyEvent) {
+    let Mode::Keybindings { capturing, .. } = app.mode else {
+        return;
+    };
+    if capturing {
+        match key.code {
+            KeyCode::Esc => app.capture_keybinding(false),
+            KeyCode::Char(c)
+                if !c.is_whitespace()
+                    && !key
+                        .modifiers
+                        .intersects(KeyModifiers::CONTROL | KeyModifiers::ALT) =>
+            {
+                app.rebind_selected(c);
+            }
+            _ => {}
+        }
+        return;
+    }
+    match list_key(app, key) {
+        KeyCode::Down => {
+            app.select_keybinding(1);
+        }
+        KeyCode::Up => {
+            app.select_keybinding(-1);
+        }
+        // Rebind
+        KeyCode::Enter => {
+            app.capture_keybinding(true);
+        }
+        // Reset to the default key
+        KeyCode::Char('d') => {
+            app.reset_selected_binding();
+        }
+        KeyCode::Esc => {
+            app.cancel();
+        }
+        _ => {}
+    }
+}
+
+fn handle_all_panes_mode(app: &mut App, key: KeyEvent) {
+    match list_key(app, key) {
+        KeyCode::Down => {
+            app.select_listed_pane(1);
+        }
+        KeyCode::Up => {
+            app.select_listed_pane(-1);
+        }
+        KeyCode::Enter => {
+            app.switch_to_listed_pane();
+        }
+        KeyCode::Char('c') => {
+            app.start_claude_in_listed_pane(false);
+        }
+        KeyCode::Char('v') => {
+            app.start_claude_in_listed_pane(true);
+        }
+        KeyCode::Esc => {
+            app.cancel();
+        }
+        _ if bound_action(app, key) == Some(Action::AllPanes) => {
+            app.cancel();
+        }
+        _ => {}
+    }
+}
+
+fn handle_sessions_mode(app: &mut App, key: KeyEvent) {
+    let Mode::Sessions {
+        ref rename,
+        confirm_kill,
+        ..
+    } = app.mode
+    else {
+        return;
+    };
+    if rename.is_some() {
+        match key.code {
+            KeyCode::Esc => app.cancel_session_edit(),
+            KeyCode::Enter => app.confirm_session_rename(),
+            KeyCode::Backspace => {
+                if let Mode::Sessions {
+                    rename: Some(ref mut name),
+                    ..
+                } = app.mode
+                {
+                    name.pop();
+                }
+            }
+            KeyCode::Char(c) => {
+                if let Mode::Sessions {
+                    rename: Some(ref mut name),
+                    ..
+                } = app.mode
+                {
+                    name.push(c);
+                }
+            }
+            _ => {}
+        }
+        return;
+    }
+    if confirm_kill {
+        match key.code {
+            KeyCode::Enter | KeyCode::Char('y') | KeyCode::Char('Y') => app.kill_listed_session(),
+            _ => app.cancel_session_edit(),
+        }
+        return;
+    }
+
+    match list_key(app, key) {
+        KeyCode::Down => {
+            app.select_listed_session(1);
+        }
+        KeyCode::Up => {
+            app.select_listed_session(-1);
+        }
+        KeyCode::Enter => {
+            app.switch_to_listed_session();
+        }
+        KeyCode::Esc => {
+            app.close_sessions();
+        }
+        _ => match bound_action(app, key) {
+            Some(Action::Kill) => app.start_kill_listed_session(),
+            Some(Action::Rename) => app.start_rename_listed_session(),
+            Some(Action::Sessions) => app.close_sessions(),
+            _ => {}
+        },
+    }
+}
+
+fn handle_search_mode(app: &mut App, key: KeyEvent) {
+    match key.code {
+        KeyCode::Esc => {
+            app.cancel();
+        }
+        KeyCode::Down | KeyCode::Tab => {
+            app.select_search_result(1);
+        }
+        KeyCode::Up | KeyCode::BackTab => {
+            app.select_search_result(-1);
+        }
+        KeyCode::Enter => {
+            app.confirm_search();
+        }
+        KeyCode::Backspace => {
+            if let Mode::Search {
+                ref mut query,
+                ref mut results,
+                ..
+            } = app.mode
+            {
+                query.pop();
+                *results = None;
+            }
+        }
+        KeyCode::Char(c) => {
+            if let Mode::Search {
+                ref mut query,
+                ref mut results,
+                ..
+            } = app.mode
+            {
+                query.push(c);
+                *results = None;
+            }
+        }
+        _ => {}
+    }
+}
+
+fn handle_palette_mode(app: &mut App, key: KeyEvent) {
+    match key.code {
+        KeyCode::Esc => {
+            app.cancel();
+        }
+        KeyCode::Char('p') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+            app.cancel();
+        }
+        KeyCode::Down | KeyCode::Tab => {
+            app.select_palette_command(1);
+        }
+        KeyCode::Up | KeyCode::BackTab => {
+            app.select_palette_command(-1);
+        }
+        KeyCode::Enter => match app.confirm_palette() {
+            Some(Command::Key(action)) => run_bound_action(app, action),
+            Some(Command::Menu(action)) => app.run_action(action),
+            None => {}
+        },
+        KeyCode::Backspace => {
+            if let Mode::Palette {
+                ref mut input,
+                ref mut selected,
+                ..
+            } = app.mode
+            {
+                input.pop();
+                *selected = 0;
+            }
+        }
+        KeyCode::Char(c) => {
+            if let Mode::Palette {
+                ref mut input,
+                ref mut selected,
+                ..
+            } = app.mode
+            {
+                input.push(c);
+                *selected = 0;
+            }
+        }
+        _ => {}
+    }
+}
+
+fn handle_config_error_mode(app: &mut App, key: KeyEvent) {
+    match list_key(app, key) {
+        KeyCode::Esc | KeyCode::Enter => {
+            app.cancel();
+        }
+        // Try again once the file is fixed
+        _ if bound_action(app, key) == Some(Action::ReloadConfig) => {
+            app.cancel();
+            app.reload_config();
+        }
+        _ => {}
+    }
+}
+
+fn handle_help_mode(app: &mut App, key: KeyEvent) {
+    match list_key(app, key) {
+        KeyCode::Down => {
+            app.scroll_help(1);
+        }
+        KeyCode::Up => {
+            app.scroll_help(-1);
+        }
+        KeyCode::PageDown => {
+            app.scroll_help(10);
+        }
+        KeyCode::PageUp => {
+            app.scroll_help(-10);
+        }
+        KeyCode::Home => {
+            app.scroll_help(isize::MIN);
+        }
+        KeyCode::End => {
+            app.scroll_help(isize::MAX);
+        }
+        KeyCode::Esc => {
+            app.cancel();
+        }
+        _ if bound_action(app, key) == Some(Action::Help) => {
             app.cancel();
         }
         _ => {}
@@ -0,0 +1,347 @@
+//! Socket for other tools (`ipc_socket = true`)
+//!
+//! While the TUI runs, it answers requests on a Unix socket,
+//! `$XDG_RUNTIME_DIR/claude-tmux.sock` (the data directory without
+//! `XDG_RUNTIME_DIR`), so status bars and editor plugins get the state it
+//! already follows without detecting it again. A request is one line and
+//! gets one line of JSON back; a connection may send several:
+//!
+//! ```text
+//! instances          -> {"ok":true,"result":[{"target":"api:1.0",...}]}
+//! status <pane>      -> {"ok":true,"result":"input"}
+//! switch <pane>      -> {"ok":true,"result":null}
+//! ```
+//!
+//! `<pane>` is a `session:window.pane` target or a pane ID such as `%3`.
+//! `switch` switches the TUI's client to the pane and leaves the TUI
+//! running. Failed requests get `{"ok":false,"error":"..."}`.
+//!
+//! Requests are answered from the main loop, so within its poll interval.
+
+use std::fs;
+use std::io::{self, Read, Write};
+use std::os::unix::fs::{DirBuilderExt, PermissionsExt};
+use std::os::unix::net::{UnixListener, UnixStream};
+use std::path::PathBuf;
+
+use anyhow::{Context, Result};
+use serde_json::{json, Value};
+
+use crate::app::App;
+use crate::i18n::trf;
+use crate::list::instance_json;
+use crate::session::ClaudeInstance;
+use crate::store::data_dir;
+
+/// Longest request accepted; longer ones close the connection
+const MAX_REQUEST: usize = 4096;
+
+/// Most reply bytes kept for a client that doesn't read; more closes the
+/// connection
+const MAX_UNSENT: usize = 1 << 20;
+
+/// Where the socket is created
+pub fn socket_path() -> PathBuf {
+    dirs::runtime_dir()
+        .unwrap_or_else(data_dir)
+        .join("claude-tmux.sock")
+}
+
+/// A request from a client
+#[derive(Debug, PartialEq, Eq)]
+enum Request {
+    /// Every instance, as `claude-tmux list --json` prints them
+    Instances,
+    /// Status of one instance
+    Status(String),
+    /// Switch the TUI's client to an instance
+    Switch(String),
+}
+
+impl Request {
+    fn parse(line: &str) -> Result<Self> {
+        let mut words = line.split_whitespace();
+        let request = match (words.next(), words.next()) {
+            (Some("instances"), None) => Self::Instances,
+            (Some("status"), Some(pane)) => Self::Status(pane.to_string()),
+            (Some("switch"), Some(pane)) => Self::Switch(pane.to_string()),
+            (Some(command @ ("status" | "switch")), None) => {
+                anyhow::bail!("{} needs a pane", command)
+            }
+            (Some(command), _) => anyhow::bail!("unknown request: {}", command),
+            (None, _) => anyhow::bail!("empty request"),
+        };
+        if words.next().is_some() {
+            anyhow::bail!("too many arguments");
+        }
+        Ok(request)
+    }
+}
+
+/// The socket, opened while `ipc_socket` is on
+#[derive(Debug, Default)]
+pub struct Server {
+    listening: Option<Listening>,
+    /// Opening the socket failed; not retried until the setting changes
+    failed: bool,
+}
+
+impl Server {
+    /// Follow the `ipc_socket` setting and answer pending requests
+    pub fn update(&mut self, app: &mut App) {
+        if !app.config.ipc_socket {
+            self.listening = None;
+            self.failed = false;
+            return;
+        }
+        if self.listening.is_none() && !self.failed {
+            match Listening::bind(socket_path()) {
+                Ok(listening) => {
+                    tracing::info!("listening on {}", listening.path.display());
+                    self.listening = Some(listening);
+                }
+                Err(e) => {
+                    tracing::warn!("socket not opened: {:#}", e);
+                    app.error = Some(trf!("Failed to open the socket: {}", format!("{:#}", e)));
+                    app.needs_redraw = true;
+                    self.failed = true;
+                }
+            }
+        }
+        if let Some(listening) = self.listening.as_mut() {
+            listening.poll(|line| respond(app, line));
+        }
+    }
+}
+
+/// A bound socket and its connected clients
+#[derive(Debug)]
+struct Listening {
+    path: PathBuf,
+    listener: UnixListener,
+    clients: Vec<Client>,
+}
+
+#[derive(Debug)]
+struct Client {
+    stream: UnixStream,
+    /// Received bytes not yet ending with a newline
+    pending: Vec<u8>,
+    /// Replies the client hasn't read yet
+    unsent: Vec<u8>,
+}
+
+impl Listening {
+    /// Create the socket at `path`, replacing one left by a TUI that's gone
+    fn bind(path: PathBuf) -> Result<Self> {
+        if path.exists() {
+            if UnixStream::connect(&path).is_ok() {
+                anyhow::bail!("{} is used by another claude-tmux", path.display());
+            }
+            fs::remove_file(&path)
+                .with_context(|| format!("Failed to remove {}", path.display()))?;
+        }
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        // Only this user may ask for switches: the socket is bound in a
+        // directory no one else can enter, made private, then moved into place
+        let private = path.with_extension(format!("{}.tmp", std::process::id()));
+        let _ = fs::remove_dir_all(&private);
+        fs::DirBuilder::new()
+            .mode(0o700)
+            .create(&private)
+            .with_context(|| format!("Failed to create {}", private.display()))?;
+        let bound = private.join("sock");
+        let listener = UnixListener::bind(&bound)
+            .with_context(|| format!("Failed to bind {}", path.display()))
+            .and_then(|listener| {
+                fs::set_permissions(&bound, fs::Permissions::from_mode(0o600))?;
+                fs::rename(&bound, &path)
+                    .with_context(|| format!("Failed to create {}", path.display()))?;
+                Ok(listener)
+            });
+        let _ = fs::remove_dir_all(&private);
+        let listener = listener?;
+        listener.set_nonblocking(true)?;
+        Ok(Self {
+            path,
+            listener,
+            clients: Vec::new(),
+        })
+    }
+
+    /// Accept new clients and answer their complete requests with `answer`
+    fn poll(&mut self, mut answer: impl FnMut(&str) -> String) {
+        while let Ok((stream, _)) = self.listener.accept() {
+            if stream.set_nonblocking(true).is_ok() {
+                self.clients.push(Client {
+                    stream,
+                    pending: Vec::new(),
+                    unsent: Vec::new(),
+                });
+            }
+        }
+        self.clients
+            .retain_mut(|client| client.serve(&mut answer).is_ok());
+    }
+}
+
+impl Drop for Listening {
+    fn drop(&mut self) {
+        let _ = fs::remove_file(&self.path);
+    }
+}
+
+impl Client {
+    /// Answer the requests received so far, sending what the client is
+    /// ready to read; fails once the client is gone
+    fn serve(&mut self, answer: &mut impl FnMut(&str) -> String) -> io::Result<()> {
+        let mut buf = [0; 1024];
+        let closed = loop {
+            match self.stream.read(&mut buf) {
+                Ok(0) => break true,
+                Ok(n) => self.pending.extend_from_slice(&buf[..n]),
+                Err(e) if e.kind() == io::ErrorKind::WouldBlock => break false,
+                Err(e) if e.kind() == io::ErrorKind::Interrupted => {}
+                Err(e) => return Err(e),
+            }
+        };
+
+        while let Some(end) = self.pending.iter().position(|&b| b == b'\n') {
+            let line: Vec<u8> = self.pending.drain(..=end).collect();
+            let reply = answer(String::from_utf8_lossy(&line).trim());
+            self.unsent.extend_from_slice(reply.as_bytes());
+            self.unsent.push(b'\n');
+        }
+        self.flush()?;
+        if closed || self.pending.len() > MAX_REQUEST || self.unsent.len() > MAX_UNSENT {
+            return Err(io::ErrorKind::UnexpectedEof.into());
+        }
+        Ok(())
+    }
+
+    /// Write unsent replies until the client stops reading
+    fn flush(&mut self) -> io::Result<()> {
+        while !self.unsent.is_empty() {
+            match self.stream.write(&self.unsent) {
+                Ok(0) => return Err(io::ErrorKind::WriteZero.into()),
+                Ok(n) => {
+                    self.unsent.drain(..n);
+                }
+                Err(e) if e.kind() == io::ErrorKind::WouldBlock => break,
+                Err(e) if e.kind() == io::ErrorKind::Interrupted => {}
+                Err(e) => return Err(e),
+            }
+        }
+        Ok(())
+    }
+}
+
+/// The reply to one request line
+fn respond(app: &mut App, line: &str) -> String {
+    let result = Request::parse(line).and_then(|request| match request {
+        Request::Instances => Ok(Value::Array(
+            app.instances.iter().map(instance_json).collect(),
+        )),
+        Request::Status(pane) => {
+            let instance = find(&app.instances, &pane)?;
+            Ok(json!(instance.status.label()))
+        }
+        Request::Switch(pane) => {
+            let target = find(&app.instances, &pane)?.tmux_target();
+            app.switch_for_client(&target)?;
+            Ok(Value::Null)
+        }
+    });
+    match result {
+        Ok(value) => json!({ "ok": true, "result": value }),
+        Err(e) => json!({ "ok": false, "error": format!("{:#}", e) }),
+    }
+    .to_string()
+}
+
+/// The instance a `session:window.pane` target or pane ID names
+fn find<'a>(instances: &'a [ClaudeInstance], pane: &str) -> Result<&'a ClaudeInstance> {
+    instances
+        .iter()
+        .find(|i| i.tmux_target() == pane || i.pane_id == pane)
+        .with_context(|| format!("no instance at {}", pane))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::{BufRead, BufReader};
+    use std::time::Duration;
+
+    use super::*;
+
+    #[test]
+    fn test_parse() {
+        assert_eq!(Request::parse("instances").unwrap(), Request::Instances);
+        assert_eq!(
+            Request::parse(" status %3 ").unwrap(),
+            Request::Status("%3".to_string())
+        );
+        assert_eq!(
+            Request::parse("switch api:1.0").unwrap(),
+            Request::Switch("api:1.0".to_string())
+        );
+        assert!(Request::parse("").is_err());
+        assert!(Request::parse("status").is_err());
+        assert!(Request::parse("instances now").is_err());
+        assert!(Request::parse("kill api").is_err());
+    }
+
+    #[test]
+    fn test_socket() {
+        let path =
+            std::env::temp_dir().join(format!("claude-tmux-ipc-{}.sock", std::process::id()));
+        let mut listening = Listening::bind(path.clone()).unwrap();
+        assert!(Listening::bind(path.clone()).is_err());
+        let mode = fs::metadata(&path).unwrap().permissions().mode();
+        assert_eq!(mode & 0o777, 0o600);
+
+        let mut client = UnixStream::connect(&path).unwrap();
+        client.write_all(b"instances\nstat").unwrap();
+        let mut requests = Vec::new();
+        listening.poll(|line| {
+            requests.push(line.to_string());
+            "[]".to_string()
+        });
+        client.write_all(b"us %3\n").unwrap();
+        listening.poll(|line| {
+            requests.push(line.to_string());
+            "\"idle\"".to_string()
+        });
+        assert_eq!(requests, ["instances", "status %3"]);
+
+        let mut replies = BufReader::new(&client).lines();
+        assert_eq!(replies.next().unwrap().unwrap(), "[]");
+        assert_eq!(replies.next().unwrap().unwrap(), "\"idle\"");
+
+        // A reply larger than the socket buffer goes out as the client reads
+        let long = "x".repeat(1 << 18);
+        client.write_all(b"instances\n").unwrap();
+        listening.poll(|_| long.clone());
+        assert!(!listening.clients[0].unsent.is_empty());
+        let reader = std::thread::spawn(move || {
+            let mut line = String::new();
+            BufReader::new(&client).read_line(&mut line).unwrap();
+            (client, line)
+        });
+        while !listening.clients[0].unsent.is_empty() {
+            listening.poll(|_| unreachable!());
+            std::thread::sleep(Duration::from_millis(1));
+        }
+        let (client, line) = reader.join().unwrap();
+        assert_eq!(line.trim_end(), long);
+
+        drop(client);
+        listening.poll(|_| unreachable!());
+        assert!(listening.clients.is_empty());
+
+        drop(listening);
+        assert!(!path.exists());
+    }
+}
@@ -0,0 +1,25 @@
+//! Reading JSON written by other programs (the GitHub API, Claude
+//! transcripts)
+
+/// Value of the string field `key` of the JSON object `json`
+pub fn json_field(json: &str, key: &str) -> Option<String> {
+    let object: serde_json::Value = serde_json::from_str(json).ok()?;
+    object.get(key)?.as_str().map(str::to_string)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_json_field() {
+        let line = r#"{"type":"summary","summary":"Fix \"login\" \u00e9\\n","leafUuid":"x"}"#;
+        assert_eq!(
+            json_field(line, "summary").as_deref(),
+            Some("Fix \"login\" é\\n")
+        );
+        assert_eq!(json_field(line, "missing"), None);
+        assert_eq!(json_field(r#"{"summary":"cut"#, "summary"), None);
+        assert_eq!(json_field(r#"{"summary":1}"#, "summary"), None);
+    }
+}
@@ -0,0 +1,123 @@
+//! Listing instances without the TUI (`claude-tmux list [--json]`)
+//!
+//! Prints every detected Claude instance, in the configured `sort_order`:
+//! one tab-separated line per instance (target, status, directory, branch),
+//! or with `--json` an array of objects for scripts and launchers:
+//!
+//! ```json
+//! [{"target": "api:1.0", "session": "api", "window": 1, "window_name": "claude",
+//!   "pane": 0, "pane_id": "%3", "attached": true, "status": "working",
+//!   "cwd": "/home/me/api", "pane_path": null,
+//!   "git": {"branch": "main", "ahead": 0, "behind": 2, ...}}]
+//! ```
+//!
+//! `git` is `null` outside a repository. Statuses are `idle`, `working`,
+//! `input`, `crashed` and `unknown`.
+
+use anyhow::Result;
+use serde_json::{json, Value};
+
+use crate::config;
+use crate::git::{GitContext, PendingOperation};
+use crate::session::ClaudeInstance;
+use crate::tmux::Tmux;
+
+/// List the instances; `args` are the arguments after `list`
+pub fn run(args: &[String]) -> Result<()> {
+    let json = match args {
+        [] => false,
+        [flag] if flag == "--json" => true,
+        _ => anyhow::bail!("Usage: claude-tmux list [--json]"),
+    };
+
+    let config = config::load_for_subcommand("list");
+
+    let mut instances = Tmux::list_claude_instances()?;
+    config.sort_order.sort(&mut instances);
+
+    if json {
+        let objects: Vec<Value> = instances.iter().map(instance_json).collect();
+        println!("{}", Value::Array(objects));
+    } else {
+        for instance in &instances {
+            println!(
+                "{}\t{}\t{}\t{}",
+                instance.tmux_target(),
+                instance.status.label(),
+                instance.working_directory.display(),
+                instance
+                    .git_context
+                    .as_ref()
+                    .map(|g| g.branch.as_str())
+                    .unwrap_or_default()
+            );
+        }
+    }
+    Ok(())
+}
+
+/// An instance as a JSON object
+pub fn instance_json(instance: &ClaudeInstance) -> Value {
+    json!({
+        "target": instance.tmux_target(),
+        "session": instance.session_name,
+        "window": instance.window_index,
+        "window_name": instance.window_name,
+        "pane": instance.pane_index,
+        "pane_id": instance.pane_id,
+        "attached": instance.session_attached,
+        "status": instance.status.label(),
+        "cwd": instance.working_directory.to_string_lossy(),
+        "pane_path": instance.pane_path.as_ref().map(|p| p.to_string_lossy()),
+        "git": instance.git_context.as_ref().map(git_json),
+    })
+}
+
+/// The git context of an instance as a JSON object
+fn git_json(git: &GitContext) -> Value {
+    let in_progress = git.in_progress.map(|op| match op {
+        PendingOperation::Merge => "merge",
+        PendingOperation::Rebase => "rebase",
+    });
+    json!({
+        "branch": git.branch,
+        "has_upstream": git.has_upstream,
+        "ahead": git.ahead,
+        "behind": git.behind,
+        "default_branch": git.default_branch,
+        "ahead_of_default": git.ahead_of_default,
+        "staged": git.has_staged,
+        "unstaged": git.has_unstaged,
+        "stashes": git.stash_count,
+        "worktree": git.is_worktree,
+        "main_repo": git.main_repo_path.as_ref().map(|p| p.to_string_lossy()),
+        "in_progress": in_progress,
+        "conflicts": git.has_conflicts,
+        "last_commit": git.last_commit.as_ref().map(|c| json!({
+            "summary": c.summary,
+            "author": c.author,
+            "time": c.time,
+        })),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::session::{test_instance, ClaudeCodeStatus};
+
+    #[test]
+    fn test_instance_json() {
+        let instance = ClaudeInstance {
+            session_attached: true,
+            ..test_instance("%3", ClaudeCodeStatus::WaitingInput)
+        };
+        assert_eq!(
+            instance_json(&instance).to_string(),
+            "{\"target\":\"api:1.0\",\"session\":\"api\",\"window\":1,\
+             \"window_name\":\"claude\",\"pane\":0,\"pane_id\":\"%3\",\
+             \"attached\":true,\"status\":\"input\",\"cwd\":\"/src/api\",\
+             \"pane_path\":null,\"git\":null}"
+        );
+    }
+}
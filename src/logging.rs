@@ -0,0 +1,160 @@
+//! Log files and recent log lines
+//!
+//! Events recorded through `tracing` (`tracing::debug!`, `tracing::warn!`,
+//! …) by our own modules are appended to a daily file in the `logs`
+//! directory of the data directory, through `tracing-appender`; files older
+//! than `MAX_LOG_FILES` days are removed. The TUI and the daemon share the
+//! files. The most recent lines are also kept in memory for the in-app log
+//! viewer.
+
+use std::collections::VecDeque;
+use std::fmt;
+use std::io;
+use std::path::PathBuf;
+use std::sync::{Mutex, OnceLock};
+
+use tracing::level_filters::LevelFilter;
+use tracing_appender::rolling::{RollingFileAppender, Rotation};
+use tracing_subscriber::filter::Targets;
+use tracing_subscriber::fmt::format::Writer;
+use tracing_subscriber::fmt::time::FormatTime;
+use tracing_subscriber::layer::SubscriberExt;
+use tracing_subscriber::{reload, Registry};
+
+use crate::store::{data_dir, local_utc_offset, now_unix};
+
+/// Daily log files kept
+const MAX_LOG_FILES: usize = 7;
+
+/// Lines kept in memory for the log viewer
+const RECENT_LINES: usize = 500;
+
+/// Level of the installed subscriber, to change it on config reloads
+static FILTER: OnceLock<reload::Handle<Targets, Registry>> = OnceLock::new();
+
+static RECENT: Mutex<VecDeque<String>> = Mutex::new(VecDeque::new());
+
+/// Directory of the log files
+pub fn log_dir() -> PathBuf {
+    data_dir().join("logs")
+}
+
+/// Start logging at `level` (`log_level` in the config)
+///
+/// May be called again to change the level; the subscriber is installed once.
+pub fn init(level: LevelFilter) {
+    let targets = Targets::new().with_target("claude_tmux", level);
+    if let Some(handle) = FILTER.get() {
+        let _ = handle.reload(targets);
+        return;
+    }
+
+    let (filter, handle) = reload::Layer::new(targets);
+    let timer = LocalTime;
+    let file = RollingFileAppender::builder()
+        .rotation(Rotation::DAILY)
+        .filename_prefix("claude-tmux")
+        .filename_suffix("log")
+        .max_log_files(MAX_LOG_FILES)
+        .build(log_dir())
+        .ok()
+        .map(|file| {
+            tracing_subscriber::fmt::layer()
+                .with_writer(file)
+                .with_ansi(false)
+                .with_timer(timer)
+        });
+    let recent = tracing_subscriber::fmt::layer()
+        .with_writer(|| RecentLines)
+        .with_ansi(false)
+        .with_timer(timer);
+    let subscriber = tracing_subscriber::registry()
+        .with(filter)
+        .with(file)
+        .with(recent);
+    // Fails only if a subscriber is already installed
+    if tracing::subscriber::set_global_default(subscriber).is_ok() {
+        let _ = FILTER.set(handle);
+    }
+}
+
+/// The most recent log lines of this process, oldest first
+pub fn recent_lines() -> Vec<String> {
+    lock_recent().iter().cloned().collect()
+}
+
+fn lock_recent() -> std::sync::MutexGuard<'static, VecDeque<String>> {
+    RECENT.lock().unwrap_or_else(|e| e.into_inner())
+}
+
+/// Writer keeping formatted events in `RECENT`
+struct RecentLines;
+
+impl io::Write for RecentLines {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        let text = String::from_utf8_lossy(buf);
+        let mut recent = lock_recent();
+        for line in text.lines() {
+            if recent.len() == RECENT_LINES {
+                recent.pop_front();
+            }
+            recent.push_back(line.to_string());
+        }
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+/// Timestamps in local time, as `format_local` writes them
+#[derive(Clone, Copy)]
+struct LocalTime;
+
+impl FormatTime for LocalTime {
+    fn format_time(&self, w: &mut Writer<'_>) -> fmt::Result {
+        write!(w, "{}", format_local(now_unix()))
+    }
+}
+
+/// Format a unix timestamp in local time as `YYYY-MM-DD HH:MM:SS`
+pub fn format_local(unix: u64) -> String {
+    format_timestamp(unix as i64 + local_utc_offset(unix))
+}
+
+/// Format local seconds since the epoch as `YYYY-MM-DD HH:MM:SS`
+pub fn format_timestamp(secs: i64) -> String {
+    let (days, time) = (secs.div_euclid(86_400), secs.rem_euclid(86_400));
+    // Civil date from days since 1970-01-01 (Howard Hinnant's algorithm)
+    let z = days + 719_468;
+    let era = z.div_euclid(146_097);
+    let doe = z.rem_euclid(146_097);
+    let yoe = (doe - doe / 1460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = yoe + era * 400 + i64::from(month <= 2);
+    format!(
+        "{:04}-{:02}-{:02} {:02}:{:02}:{:02}",
+        year,
+        month,
+        day,
+        time / 3600,
+        time % 3600 / 60,
+        time % 60
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_format_timestamp() {
+        assert_eq!(format_timestamp(0), "1970-01-01 00:00:00");
+        assert_eq!(format_timestamp(1_704_096_000), "2024-01-01 08:00:00");
+        assert_eq!(format_timestamp(951_825_599), "2000-02-29 11:59:59");
+    }
+}
@@ -1,56 +1,357 @@
+mod agent_env;
 mod app;
+mod archive;
+mod bench;
+mod cache;
+mod clipboard;
 mod completion;
+mod config;
+mod crash;
+mod daemon;
 mod detection;
 mod git;
+mod hooks;
+mod i18n;
 mod input;
+mod ipc;
+mod json;
+mod list;
+mod logging;
+mod manage;
+mod mcp;
+mod poller;
+mod popup;
+mod preview;
+mod review;
+mod schedule;
 mod scroll_state;
+mod search;
 mod session;
+mod snapshot;
+mod stats;
+mod status;
+mod store;
+mod switch;
+mod tags;
+mod title;
 mod tmux;
+mod trash;
 mod ui;
+mod ui_state;
+mod update;
+mod watch;
+mod workspace;
+mod worktime;
 
-use std::io::{self, stdout};
+use std::io::{stdout, Write};
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
 
-use anyhow::Result;
+use anyhow::{Context, Result};
 use crossterm::{
-    event::{self, Event},
+    event::{self, DisableMouseCapture, EnableMouseCapture, Event},
     terminal::{disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen},
     ExecutableCommand,
 };
 use ratatui::prelude::*;
 
-use crate::app::App;
+use crate::app::{App, PickOutput};
 
 fn main() -> Result<()> {
-    // Set up terminal
+    // Subcommands that run without the TUI
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    if let Some(command) = args.first() {
+        match command.as_str() {
+            "daemon" => {
+                crash::install();
+                return daemon::run();
+            }
+            "bench" => return bench::run(&args[1..]),
+            // Kill a session by its exact name, from scripts
+            "kill" => return manage::run_kill(&args[1..]),
+            // Print the instances for scripts, without the TUI
+            "list" => return list::run(&args[1..]),
+            // Tools for a supervising Claude, over MCP on stdio
+            "mcp" => return mcp::run(&args[1..]),
+            // Create a session in a directory, e.g. from a bootstrap script
+            "new" => return manage::run_new(&args[1..]),
+            "self-update" => return update::run(&args[1..]),
+            // One short line for the tmux status bar
+            "status" => return status::run(&args[1..]),
+            // Switch back to the last instance, e.g. from a tmux binding
+            "switch" => return switch::run(&args[1..]),
+            // Stream status changes as JSON lines for automation
+            "watch" => return watch::run(&args[1..]),
+            "workspace" => return workspace::run(&args[1..]),
+            _ => {}
+        }
+    }
+
+    let options = Options::parse(&args)?;
+    // From a binding, open in a popup with the same options
+    if options.once && popup::opens_popup() {
+        return popup::run(&args);
+    }
+    let pick = options.pick;
+
+    crash::install();
+
+    // Set up terminal; when picking, stdout is for the result, as in
+    // `target=$(claude-tmux --pick)`
+    let mut output: Box<dyn Write> = if pick.is_some() {
+        Box::new(
+            std::fs::OpenOptions::new()
+                .read(true)
+                .write(true)
+                .open("/dev/tty")?,
+        )
+    } else {
+        Box::new(stdout())
+    };
     enable_raw_mode()?;
-    stdout().execute(EnterAlternateScreen)?;
+    output.execute(EnterAlternateScreen)?;
+    crash::set_terminal_active(true, pick.is_some());
 
-    let backend = CrosstermBackend::new(stdout());
+    let backend = CrosstermBackend::new(output);
     let mut terminal = Terminal::new(backend)?;
 
     // Run the app
-    let result = run(&mut terminal);
+    let result = run(&mut terminal, options);
 
-    // Restore terminal
+    // Restore terminal, whether or not the app quit normally; mouse
+    // capture may still be on, as the `mouse` setting leaves it
+    crash::set_terminal_active(false, pick.is_some());
     disable_raw_mode()?;
-    stdout().execute(LeaveAlternateScreen)?;
+    terminal.backend_mut().execute(DisableMouseCapture)?;
+    terminal.backend_mut().execute(LeaveAlternateScreen)?;
+
+    if let Err(e) = &result {
+        crash::report_error(e);
+    }
+
+    match result? {
+        Some(target) if pick.is_some() => println!("{}", target),
+        // Nothing picked: fail, so scripts can tell
+        None if pick.is_some() => std::process::exit(1),
+        // Outside tmux, switching means attaching in this terminal
+        Some(target) => tmux::Tmux::attach_to_pane(&target)?,
+        None => {}
+    }
+
+    Ok(())
+}
+
+/// How the TUI opens, from the command line
+#[derive(Debug, Default, PartialEq)]
+struct Options {
+    /// Instance to open with selected (`focus`, `--select`)
+    select: Option<String>,
+    /// Open on the list of every tmux session, as a session picker (`sessions`)
+    sessions: bool,
+    /// Print the chosen instance, or its directory, instead of switching
+    /// to it (`--pick`, `--print-cwd`)
+    pick: Option<PickOutput>,
+    /// Quit after a switch or launch (`--once`)
+    once: bool,
+    /// Only the instances under this directory (`--cwd`)
+    scope: Option<PathBuf>,
+}
+
+impl Options {
+    /// Parse every argument; `focus` and `sessions` come first, like
+    /// subcommands, and the flags may follow in any order
+    fn parse(args: &[String]) -> Result<Self> {
+        let mut options = Self::default();
+        let mut args = args.iter().peekable();
+        match args.peek().map(|arg| arg.as_str()) {
+            Some("focus") => {
+                args.next();
+                options.set_select(select_target("focus", args.next().map(String::as_str))?)?;
+            }
+            Some("sessions") => {
+                args.next();
+                options.sessions = true;
+            }
+            _ => {}
+        }
+        while let Some(arg) = args.next() {
+            match arg.as_str() {
+                "--select" => options
+                    .set_select(select_target("--select", args.next().map(String::as_str))?)?,
+                "--pick" => options.set_pick(PickOutput::Target)?,
+                "--print-cwd" => options.set_pick(PickOutput::Directory)?,
+                "--once" => options.once = true,
+                "--cwd" => {
+                    let path = args.next_if(|arg| !arg.starts_with('-'));
+                    options.scope = Some(scope_dir(path)?);
+                }
+                _ => match arg.strip_prefix("--select=") {
+                    Some(target) => options.set_select(select_target("--select", Some(target))?)?,
+                    None => anyhow::bail!("Unknown argument '{}'", arg),
+                },
+            }
+        }
+        Ok(options)
+    }
+
+    fn set_select(&mut self, target: String) -> Result<()> {
+        if self.select.replace(target).is_some() {
+            anyhow::bail!("Only one instance can be selected");
+        }
+        Ok(())
+    }
+
+    fn set_pick(&mut self, output: PickOutput) -> Result<()> {
+        if self.pick.replace(output).is_some() {
+            anyhow::bail!("--pick and --print-cwd can't be combined");
+        }
+        Ok(())
+    }
+}
+
+/// The `session:window.pane` argument of `focus` or `--select`
+fn select_target(command: &str, target: Option<&str>) -> Result<String> {
+    match target {
+        Some(target) if !target.is_empty() && !target.starts_with('-') => Ok(target.to_string()),
+        _ => anyhow::bail!("{} needs a target (session:window.pane)", command),
+    }
+}
+
+/// The directory of `--cwd [path]`, the current one without a path
+fn scope_dir(path: Option<&String>) -> Result<PathBuf> {
+    let dir = match path {
+        None => std::env::current_dir().context("Failed to read the current directory")?,
+        Some(path) => app::expand_path(path),
+    };
+    if !dir.is_dir() {
+        anyhow::bail!("Directory not found: {}", dir.display());
+    }
+    Ok(dir.canonicalize().unwrap_or(dir))
+}
 
-    result
+/// Set `reload` on each SIGHUP, or exit when it means the terminal went
+/// away (the popup or pane was closed), as without a handler
+///
+/// The terminal check runs on its own thread: once the terminal is gone,
+/// the main loop may never get back from waiting for input.
+#[cfg(unix)]
+fn watch_hangups(reload: Arc<AtomicBool>) -> Result<()> {
+    use signal_hook::consts::SIGHUP;
+
+    let mut signals = signal_hook::iterator::Signals::new([SIGHUP])?;
+    std::thread::spawn(move || {
+        for _ in signals.forever() {
+            if std::fs::File::open("/dev/tty").is_err() {
+                std::process::exit(128 + SIGHUP);
+            }
+            reload.store(true, Ordering::Relaxed);
+        }
+    });
+    Ok(())
 }
 
-fn run(terminal: &mut Terminal<CrosstermBackend<io::Stdout>>) -> Result<()> {
+/// Run the TUI, returning the pane to attach to after exit, if any
+///
+/// With `sessions`, the TUI opens on the list of every session and quits
+/// when it is closed. With `pick`, it shows the instance list alone and
+/// returns the instance chosen, or its directory, instead of switching to
+/// it. With `once`, it also switches to the sessions and agents it
+/// launches, and so quits.
+fn run<W: Write>(
+    terminal: &mut Terminal<CrosstermBackend<W>>,
+    options: Options,
+) -> Result<Option<String>> {
     // Fast initialization - UI appears immediately
     let mut app = App::new_fast()?;
+    if let Some(target) = options.select {
+        app.select_on_load(target);
+    }
+    if options.sessions {
+        app.show_sessions(true);
+    }
+    app.pick = options.pick;
+    app.once = options.once;
+    app.scope = options.scope;
+
+    // `kill -HUP` reloads the config
+    let reload = Arc::new(AtomicBool::new(false));
+    #[cfg(unix)]
+    watch_hangups(Arc::clone(&reload))?;
 
     // Start background loading of instances and git contexts
     app.start_background_loading();
 
+    let mut mouse_captured = false;
+    let mut terminal_title = title::TerminalTitle::default();
+    let mut ipc_server = ipc::Server::default();
     loop {
         // Poll for background loading updates (non-blocking)
         app.poll_loading();
 
-        // Draw the UI
-        terminal.draw(|frame| ui::render(frame, &mut app))?;
+        // Pick up the result of a background push/pull/fetch
+        app.poll_git_job();
+
+        // Show git operations waiting for their repository
+        app.poll_repo_queue();
+
+        // Record checkouts synced in the background
+        app.poll_sync_job();
+
+        // Show the output of a custom action once it exits
+        app.poll_action_job();
+
+        // Refresh git context once an interactive rebase split closes
+        app.poll_rebases();
+
+        // Pick up pane captures, and re-detect status of panes that are due
+        app.poll_statuses();
+
+        // Show how many commits a kill would leave on no remote
+        app.poll_unpushed();
+
+        // Size up each checkout's pending changes now and then
+        app.poll_diff_stats();
+
+        // Follow the CI checks of branches with pull requests
+        app.poll_pr_checks();
+
+        // Keep ahead/behind current for repositories with auto-fetch on
+        app.poll_auto_fetch();
+
+        // Record working time and input waits
+        app.sample_agents();
+
+        if reload.swap(false, Ordering::Relaxed) {
+            app.reload_config();
+        }
+
+        // Follow the `mouse` setting, which a reload may change
+        if app.config.mouse != mouse_captured {
+            mouse_captured = app.config.mouse;
+            if mouse_captured {
+                terminal.backend_mut().execute(EnableMouseCapture)?;
+            } else {
+                terminal.backend_mut().execute(DisableMouseCapture)?;
+            }
+        }
+
+        // Show the agents' state in the title when `terminal_title` is on
+        if app.config.terminal_title {
+            terminal_title.update(terminal.backend_mut(), &app.instances)?;
+        } else {
+            terminal_title.restore(terminal.backend_mut())?;
+        }
+
+        // Answer other tools on the socket when `ipc_socket` is on
+        ipc_server.update(&mut app);
+
+        // Draw the UI only when something changed
+        if app.needs_redraw {
+            terminal.draw(|frame| ui::render(frame, &mut app))?;
+            app.needs_redraw = false;
+            crash::record_state(app.state_summary());
+        }
 
         // Check if we should quit
         if app.should_quit {
@@ -63,11 +364,54 @@ fn run(terminal: &mut Terminal<CrosstermBackend<io::Stdout>>) -> Result<()> {
 
         // Handle events
         if event::poll(std::time::Duration::from_millis(poll_ms))? {
-            if let Event::Key(key) = event::read()? {
-                input::handle_key(&mut app, key);
+            match event::read()? {
+                Event::Key(key) => {
+                    input::handle_key(&mut app, key);
+                    app.needs_redraw = true;
+                }
+                Event::Mouse(mouse) => {
+                    input::handle_mouse(&mut app, mouse);
+                    app.needs_redraw = true;
+                }
+                Event::Resize(..) => app.needs_redraw = true,
+                _ => {}
             }
         }
     }
 
-    Ok(())
+    terminal_title.restore(terminal.backend_mut())?;
+    if let Err(e) = app.save_ui_state() {
+        tracing::warn!("UI state not saved: {:#}", e);
+    }
+    Ok(app.attach_on_exit.take())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(args: &[&str]) -> Result<Options> {
+        let args: Vec<String> = args.iter().map(|arg| arg.to_string()).collect();
+        Options::parse(&args)
+    }
+
+    #[test]
+    fn test_parse_options() {
+        assert_eq!(parse(&[]).unwrap(), Options::default());
+        let options = parse(&["--pick", "--cwd", "/", "--once"]).unwrap();
+        assert_eq!(options.pick, Some(PickOutput::Target));
+        assert_eq!(options.scope, Some(PathBuf::from("/")));
+        assert!(options.once);
+        let options = parse(&["focus", "api:1.0", "--print-cwd"]).unwrap();
+        assert_eq!(options.select.as_deref(), Some("api:1.0"));
+        assert_eq!(options.pick, Some(PickOutput::Directory));
+        assert!(parse(&["sessions", "--select=web"]).unwrap().sessions);
+
+        // Nothing is dropped
+        assert!(parse(&["sessions", "foo"]).is_err());
+        assert!(parse(&["--once", "sessions"]).is_err());
+        assert!(parse(&["--pick", "--print-cwd"]).is_err());
+        assert!(parse(&["focus", "api", "--select", "web"]).is_err());
+        assert!(parse(&["--select", "--pick"]).is_err());
+    }
 }
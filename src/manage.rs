@@ -0,0 +1,206 @@
+//! Managing sessions from the command line (`claude-tmux new` and `kill`)
+//!
+//! For project bootstrap scripts that would otherwise call tmux directly:
+//!
+//! ```text
+//! claude-tmux new ~/src/api [--name <session>] [--no-claude] [--switch]
+//! claude-tmux kill api [--force]
+//! ```
+//!
+//! `new` creates a session in a directory running Claude with the launch
+//! profile of that directory, as the TUI's new session dialog does, and
+//! prints its name. Without `--name` the session is named by the
+//! `session_name_template`, with a number added when that name is taken.
+//! `--switch` then switches to it like `claude-tmux switch`.
+//!
+//! `kill` refuses to kill a session whose checkouts have commits on no
+//! remote, which the TUI would warn about, unless given `--force`.
+
+use std::path::PathBuf;
+
+use anyhow::{Context, Result};
+
+use crate::app::expand_path;
+use crate::config;
+use crate::git;
+use crate::stats::{self, Counter};
+use crate::tmux::Tmux;
+use crate::{switch, workspace};
+
+const NEW_USAGE: &str = "Usage: claude-tmux new <dir> [--name <session>] [--no-claude] [--switch]";
+
+const KILL_USAGE: &str = "Usage: claude-tmux kill <session> [--force]";
+
+/// What `new` was asked for
+#[derive(Debug, PartialEq, Eq)]
+struct NewArgs {
+    dir: PathBuf,
+    name: Option<String>,
+    start_claude: bool,
+    switch: bool,
+}
+
+/// Create a session; `args` are the arguments after `new`
+pub fn run_new(args: &[String]) -> Result<()> {
+    let args = parse_new_args(args)?;
+    if !args.dir.is_dir() {
+        anyhow::bail!("Directory not found: {}", args.dir.display());
+    }
+    let dir = args.dir.canonicalize().unwrap_or(args.dir);
+
+    let config = config::load_for_subcommand("new");
+
+    let taken = Tmux::list_sessions().unwrap_or_default();
+    let name = match args.name {
+        Some(name) if taken.contains(&name) => {
+            anyhow::bail!("A session named '{}' already exists", name)
+        }
+        Some(name) => name,
+        None => workspace::session_name(&config.session_name_template, &dir, &taken),
+    };
+    let command = args.start_claude.then(|| config.claude_command(&[&dir]));
+    Tmux::new_session(&name, &dir, command.as_deref())?;
+    if config.usage_stats {
+        stats::record(&[(Counter::SessionsCreated, 1)]);
+    }
+    println!("{}", name);
+
+    if args.switch {
+        switch::run(&[name])?;
+    }
+    Ok(())
+}
+
+fn parse_new_args(args: &[String]) -> Result<NewArgs> {
+    let mut dir = None;
+    let mut name = None;
+    let mut start_claude = true;
+    let mut switch = false;
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--name" => {
+                let value = args.next().context(NEW_USAGE)?;
+                if value.is_empty() {
+                    anyhow::bail!("Session name cannot be empty");
+                }
+                name = Some(value.clone());
+            }
+            "--no-claude" => start_claude = false,
+            "--switch" => switch = true,
+            _ if arg.starts_with("--") || dir.is_some() => anyhow::bail!(NEW_USAGE),
+            _ => dir = Some(expand_path(arg)),
+        }
+    }
+    Ok(NewArgs {
+        dir: dir.context(NEW_USAGE)?,
+        name,
+        start_claude,
+        switch,
+    })
+}
+
+/// Kill a session; `args` are the arguments after `kill`
+pub fn run_kill(args: &[String]) -> Result<()> {
+    let (session, force) = parse_kill_args(args)?;
+
+    config::load_for_subcommand("kill");
+
+    // Exact names only: tmux would otherwise kill the first session
+    // starting with the name
+    let Some(listed) = Tmux::list_every_session()?
+        .into_iter()
+        .find(|s| s.name == session)
+    else {
+        anyhow::bail!("No session named '{}'", session);
+    };
+    if !force {
+        let paths: Vec<PathBuf> = listed
+            .panes
+            .iter()
+            .map(|pane| pane.current_path.clone())
+            .collect();
+        let unpushed = git::unpushed_commits(&paths);
+        if unpushed > 0 {
+            anyhow::bail!(
+                "'{}' has {} commits on no remote; pass --force to kill it anyway",
+                session,
+                unpushed
+            );
+        }
+    }
+    Tmux::kill_session(&session)
+}
+
+/// Session name and whether `--force` was given
+fn parse_kill_args(args: &[String]) -> Result<(String, bool)> {
+    let mut session = None;
+    let mut force = false;
+    for arg in args {
+        match arg.as_str() {
+            "--force" => force = true,
+            _ if arg.starts_with("--") || session.is_some() => anyhow::bail!(KILL_USAGE),
+            _ => session = Some(arg.clone()),
+        }
+    }
+    Ok((session.context(KILL_USAGE)?, force))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(args: &[&str]) -> Vec<String> {
+        args.iter().map(|a| a.to_string()).collect()
+    }
+
+    #[test]
+    fn test_parse_new_args() {
+        assert_eq!(
+            parse_new_args(&args(&["/src/api"])).unwrap(),
+            NewArgs {
+                dir: PathBuf::from("/src/api"),
+                name: None,
+                start_claude: true,
+                switch: false,
+            }
+        );
+        assert_eq!(
+            parse_new_args(&args(&[
+                "--no-claude",
+                "/src/api",
+                "--name",
+                "api-2",
+                "--switch"
+            ]))
+            .unwrap(),
+            NewArgs {
+                dir: PathBuf::from("/src/api"),
+                name: Some("api-2".to_string()),
+                start_claude: false,
+                switch: true,
+            }
+        );
+
+        assert!(parse_new_args(&args(&[])).is_err());
+        assert!(parse_new_args(&args(&["/src/api", "/src/web"])).is_err());
+        assert!(parse_new_args(&args(&["/src/api", "--name"])).is_err());
+        assert!(parse_new_args(&args(&["/src/api", "--detach"])).is_err());
+    }
+
+    #[test]
+    fn test_parse_kill_args() {
+        assert_eq!(
+            parse_kill_args(&args(&["api"])).unwrap(),
+            ("api".to_string(), false)
+        );
+        assert_eq!(
+            parse_kill_args(&args(&["--force", "api"])).unwrap(),
+            ("api".to_string(), true)
+        );
+
+        assert!(parse_kill_args(&args(&[])).is_err());
+        assert!(parse_kill_args(&args(&["api", "web"])).is_err());
+        assert!(parse_kill_args(&args(&["api", "--all"])).is_err());
+    }
+}
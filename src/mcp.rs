@@ -0,0 +1,325 @@
+//! Model Context Protocol server (`claude-tmux mcp`)
+//!
+//! Lets a supervising Claude inspect and coordinate the other agents. It
+//! speaks MCP over stdio, one JSON-RPC message per line, and offers three
+//! tools:
+//!
+//! - `list_instances`: the agents, as `claude-tmux list --json` prints them
+//! - `get_pane_output`: the end of an agent's pane as plain text
+//! - `send_prompt`: a prompt typed and submitted in an agent's pane
+//!
+//! Register it with Claude Code:
+//!
+//! ```text
+//! claude mcp add claude-tmux -- claude-tmux mcp
+//! ```
+//!
+//! Agents are named by their `session:window.pane` target or pane ID. An
+//! agent can't send a prompt to its own pane.
+
+use std::io::{self, BufRead, Write};
+
+use anyhow::{Context, Result};
+use serde_json::{json, Value};
+
+use crate::config::{self, Config};
+use crate::list::instance_json;
+use crate::session::ClaudeInstance;
+use crate::stats::{self, Counter};
+use crate::tmux::Tmux;
+
+/// Protocol versions understood, latest first
+const PROTOCOL_VERSIONS: [&str; 3] = ["2025-06-18", "2025-03-26", "2024-11-05"];
+
+/// Lines `get_pane_output` reads unless asked for another number
+const DEFAULT_OUTPUT_LINES: u64 = 100;
+
+/// Most lines `get_pane_output` reads
+const MAX_OUTPUT_LINES: u64 = 2000;
+
+/// JSON-RPC error codes
+const PARSE_ERROR: i64 = -32700;
+const METHOD_NOT_FOUND: i64 = -32601;
+const INVALID_PARAMS: i64 = -32602;
+
+/// Serve MCP on stdin and stdout; `args` are the arguments after `mcp`
+pub fn run(args: &[String]) -> Result<()> {
+    if !args.is_empty() {
+        anyhow::bail!("Usage: claude-tmux mcp");
+    }
+
+    let config = config::load_for_subcommand("mcp");
+
+    let mut stdout = io::stdout();
+    for line in io::stdin().lock().lines() {
+        let line = line.context("Failed to read request")?;
+        if line.trim().is_empty() {
+            continue;
+        }
+        let Some(reply) = handle(&config, &line) else {
+            continue;
+        };
+        match writeln!(stdout, "{}", reply).and_then(|_| stdout.flush()) {
+            Ok(()) => {}
+            // The client went away
+            Err(e) if e.kind() == io::ErrorKind::BrokenPipe => return Ok(()),
+            Err(e) => return Err(e).context("Failed to write reply"),
+        }
+    }
+    Ok(())
+}
+
+/// The reply to one message, `None` for notifications
+fn handle(config: &Config, line: &str) -> Option<String> {
+    let message: Value = match serde_json::from_str(line) {
+        Ok(message) => message,
+        Err(e) => return Some(failure(Value::Null, PARSE_ERROR, &e.to_string()).to_string()),
+    };
+    // Notifications and replies have no ID or no method
+    let id = message.get("id")?.clone();
+    let method = message.get("method")?.as_str()?;
+    let params = message.get("params");
+
+    let result = match method {
+        "initialize" => Ok(initialize(params)),
+        "ping" => Ok(json!({})),
+        "tools/list" => Ok(tools_list()),
+        "tools/call" => call_tool(config, params),
+        _ => Err((METHOD_NOT_FOUND, format!("Method not found: {}", method))),
+    };
+    let reply = match result {
+        Ok(result) => json!({ "jsonrpc": "2.0", "id": id, "result": result }),
+        Err((code, message)) => failure(id, code, &message),
+    };
+    Some(reply.to_string())
+}
+
+fn failure(id: Value, code: i64, message: &str) -> Value {
+    json!({
+        "jsonrpc": "2.0",
+        "id": id,
+        "error": { "code": code, "message": message },
+    })
+}
+
+/// Result of `initialize`, in the client's protocol version when known
+fn initialize(params: Option<&Value>) -> Value {
+    let requested = params
+        .and_then(|p| p.get("protocolVersion"))
+        .and_then(Value::as_str);
+    let version = PROTOCOL_VERSIONS
+        .into_iter()
+        .find(|v| Some(*v) == requested)
+        .unwrap_or(PROTOCOL_VERSIONS[0]);
+    json!({
+        "protocolVersion": version,
+        "capabilities": { "tools": {} },
+        "serverInfo": {
+            "name": "claude-tmux",
+            "version": env!("CARGO_PKG_VERSION"),
+        },
+    })
+}
+
+/// Result of `tools/list`
+fn tools_list() -> Value {
+    let target = json!({
+        "type": "string",
+        "description": "The agent's session:window.pane target or pane ID, from list_instances",
+    });
+    json!({
+        "tools": [
+            {
+                "name": "list_instances",
+                "description": "List the Claude Code agents running in tmux with their target, \
+                                status (idle, working, input, crashed), directory and git branch",
+                "inputSchema": { "type": "object", "properties": {}, "required": [] },
+            },
+            {
+                "name": "get_pane_output",
+                "description": "Read the end of an agent's pane, scrollback included, as plain text",
+                "inputSchema": {
+                    "type": "object",
+                    "properties": {
+                        "target": target,
+                        "lines": {
+                            "type": "integer",
+                            "description": "Lines to read, 100 by default, at most 2000",
+                        },
+                    },
+                    "required": ["target"],
+                },
+            },
+            {
+                "name": "send_prompt",
+                "description": "Send a prompt to an agent, typed in its pane and submitted",
+                "inputSchema": {
+                    "type": "object",
+                    "properties": {
+                        "target": target,
+                        "prompt": { "type": "string", "description": "The prompt to send" },
+                    },
+                    "required": ["target", "prompt"],
+                },
+            },
+        ],
+    })
+}
+
+/// Result of `tools/call`; failures of the tool itself are results too
+fn call_tool(config: &Config, params: Option<&Value>) -> Result<Value, (i64, String)> {
+    let name = params
+        .and_then(|p| p.get("name"))
+        .and_then(Value::as_str)
+        .ok_or((INVALID_PARAMS, "Missing tool name".to_string()))?;
+    let args = params.and_then(|p| p.get("arguments"));
+    let output = match name {
+        "list_instances" => list_instances(),
+        "get_pane_output" => get_pane_output(args),
+        "send_prompt" => send_prompt(config, args),
+        _ => return Err((INVALID_PARAMS, format!("Unknown tool: {}", name))),
+    };
+
+    let (text, is_error) = match output {
+        Ok(text) => (text, false),
+        Err(e) => (format!("{:#}", e), true),
+    };
+    Ok(json!({
+        "content": [{ "type": "text", "text": text }],
+        "isError": is_error,
+    }))
+}
+
+fn list_instances() -> Result<String> {
+    let objects: Vec<Value> = Tmux::list_claude_instances()?
+        .iter()
+        .map(instance_json)
+        .collect();
+    Ok(Value::Array(objects).to_string())
+}
+
+fn get_pane_output(args: Option<&Value>) -> Result<String> {
+    let instance = find_instance(string_arg(args, "target")?)?;
+    let lines = match args.and_then(|a| a.get("lines")) {
+        None => DEFAULT_OUTPUT_LINES,
+        Some(lines) => lines
+            .as_u64()
+            .filter(|n| *n > 0)
+            .context("lines must be a positive integer")?,
+    };
+    let output = Tmux::capture_history(&instance.pane_id, lines.min(MAX_OUTPUT_LINES) as usize)?;
+    Ok(output.trim_end().to_string())
+}
+
+fn send_prompt(config: &Config, args: Option<&Value>) -> Result<String> {
+    let instance = find_instance(string_arg(args, "target")?)?;
+    let prompt = string_arg(args, "prompt")?;
+    if prompt.trim().is_empty() {
+        anyhow::bail!("The prompt is empty");
+    }
+    // The supervising agent runs in the pane this server was started from
+    if std::env::var("TMUX_PANE").is_ok_and(|pane| pane == instance.pane_id) {
+        anyhow::bail!("{} is the calling agent's own pane", instance.tmux_target());
+    }
+    Tmux::send_prompt(&instance.pane_id, prompt)?;
+    if config.usage_stats {
+        stats::record(&[(Counter::PromptsSent, 1)]);
+    }
+    Ok(format!("Sent the prompt to {}", instance.tmux_target()))
+}
+
+/// The agent a `session:window.pane` target or pane ID names
+fn find_instance(target: &str) -> Result<ClaudeInstance> {
+    Tmux::list_claude_instances()?
+        .into_iter()
+        .find(|i| i.pane_id == target || i.tmux_target() == target)
+        .with_context(|| format!("No agent at {}; list_instances lists them", target))
+}
+
+fn string_arg<'a>(args: Option<&'a Value>, name: &str) -> Result<&'a str> {
+    args.and_then(|a| a.get(name))
+        .and_then(Value::as_str)
+        .with_context(|| format!("Missing string argument: {}", name))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_handle() {
+        let config = Config::default();
+        let reply = handle(
+            &config,
+            r#"{"jsonrpc":"2.0","id":1,"method":"initialize","params":{"protocolVersion":"2025-03-26"}}"#,
+        )
+        .unwrap();
+        assert!(reply.starts_with(
+            "{\"jsonrpc\":\"2.0\",\"id\":1,\"result\":{\"protocolVersion\":\"2025-03-26\""
+        ));
+
+        // Notifications get no reply
+        assert_eq!(
+            handle(
+                &config,
+                r#"{"jsonrpc":"2.0","method":"notifications/initialized"}"#
+            ),
+            None
+        );
+
+        let reply = handle(
+            &config,
+            r#"{"jsonrpc":"2.0","id":"a","method":"tools/list"}"#,
+        )
+        .unwrap();
+        for tool in ["list_instances", "get_pane_output", "send_prompt"] {
+            assert!(reply.contains(&format!("\"name\":\"{}\"", tool)));
+        }
+
+        assert_eq!(
+            handle(
+                &config,
+                r#"{"jsonrpc":"2.0","id":2,"method":"resources/list"}"#
+            )
+            .unwrap(),
+            "{\"jsonrpc\":\"2.0\",\"id\":2,\"error\":{\"code\":-32601,\
+             \"message\":\"Method not found: resources/list\"}}"
+        );
+        assert!(handle(&config, "{not json")
+            .unwrap()
+            .contains("\"id\":null,\"error\":{\"code\":-32700"));
+
+        // Tool failures are results
+        let reply = handle(
+            &config,
+            r#"{"jsonrpc":"2.0","id":3,"method":"tools/call","params":{"name":"send_prompt","arguments":{}}}"#,
+        )
+        .unwrap();
+        assert_eq!(
+            reply,
+            "{\"jsonrpc\":\"2.0\",\"id\":3,\"result\":{\"content\":[{\"type\":\"text\",\
+             \"text\":\"Missing string argument: target\"}],\"isError\":true}}"
+        );
+        assert!(handle(
+            &config,
+            r#"{"jsonrpc":"2.0","id":4,"method":"tools/call","params":{"name":"rm"}}"#
+        )
+        .unwrap()
+        .contains("\"code\":-32602"));
+
+        // Escaped surrogate pairs decode, and lone halves are rejected
+        assert!(handle(
+            &config,
+            r#"{"jsonrpc":"2.0","id":5,"method":"ping","params":{"note":"\ud83d\ude00"}}"#
+        )
+        .unwrap()
+        .contains("\"result\":{}"));
+        for note in [r"\ud83d", r"\ud83dx", r"\ud83d\u0041", r"\ude00"] {
+            let line = format!(
+                r#"{{"jsonrpc":"2.0","id":6,"method":"ping","params":{{"note":"{}"}}}}"#,
+                note
+            );
+            assert!(handle(&config, &line).unwrap().contains("\"code\":-32700"));
+        }
+    }
+}
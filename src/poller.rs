@@ -0,0 +1,197 @@
+//! Adaptive status polling
+//!
+//! Decides which panes to capture on each tick. Panes whose status changed
+//! recently (or that are working) are polled often; panes that stay the same
+//! back off exponentially. A token bucket caps the total number of captures
+//! per second, since each one spawns a tmux subprocess, and each tick runs at
+//! most `MAX_CAPTURES_PER_TICK` of them so the UI stays responsive with
+//! hundreds of panes. Panes visible in the list go first.
+
+use std::collections::{HashMap, HashSet};
+use std::time::{Duration, Instant};
+
+use crate::session::ClaudeCodeStatus;
+
+/// Poll interval right after a status change
+const MIN_INTERVAL: Duration = Duration::from_secs(1);
+/// Longest interval for panes that never change
+const MAX_INTERVAL: Duration = Duration::from_secs(30);
+
+/// Most captures run in one tick, whatever the budget
+const MAX_CAPTURES_PER_TICK: usize = 8;
+
+/// Default capture budget (captures per second)
+pub const DEFAULT_CAPTURE_BUDGET: f64 = 4.0;
+
+#[derive(Debug, Clone)]
+struct PaneState {
+    status: ClaudeCodeStatus,
+    interval: Duration,
+    next_due: Instant,
+}
+
+/// Per-pane capture scheduling with a global rate limit
+#[derive(Debug)]
+pub struct StatusPoller {
+    panes: HashMap<String, PaneState>,
+    /// Captures allowed per second
+    budget: f64,
+    /// Available captures (token bucket, at most `budget` but never below one
+    /// so budgets under 1/s still make progress)
+    tokens: f64,
+    last_refill: Instant,
+    /// Panes currently shown in the list, polled before the others
+    visible: HashSet<String>,
+}
+
+impl StatusPoller {
+    pub fn new(budget: f64, now: Instant) -> Self {
+        let budget = budget.max(0.1);
+        Self {
+            panes: HashMap::new(),
+            budget,
+            tokens: budget,
+            last_refill: now,
+            visible: HashSet::new(),
+        }
+    }
+
+    /// Change the captures allowed per second, e.g. after a config reload
+    pub fn set_budget(&mut self, budget: f64) {
+        self.budget = budget.max(0.1);
+        self.tokens = self.tokens.min(self.budget.max(1.0));
+    }
+
+    /// Set the panes currently shown in the list
+    pub fn set_visible(&mut self, pane_ids: HashSet<String>) {
+        self.visible = pane_ids;
+    }
+
+    /// Pick the panes to capture now within the budget: visible panes first,
+    /// then most overdue first
+    ///
+    /// `panes` are the currently known (pane id, status) pairs; panes that
+    /// disappeared are forgotten and new ones are due immediately.
+    pub fn due(&mut self, panes: &[(&str, ClaudeCodeStatus)], now: Instant) -> Vec<String> {
+        let known: HashSet<&str> = panes.iter().map(|(pane_id, _)| *pane_id).collect();
+        self.panes.retain(|id, _| known.contains(id.as_str()));
+        for (id, status) in panes {
+            self.panes
+                .entry(id.to_string())
+                .or_insert_with(|| PaneState {
+                    status: *status,
+                    interval: MIN_INTERVAL,
+                    next_due: now,
+                });
+        }
+
+        let elapsed = now
+            .saturating_duration_since(self.last_refill)
+            .as_secs_f64();
+        self.tokens = (self.tokens + elapsed * self.budget).min(self.budget.max(1.0));
+        self.last_refill = now;
+
+        let mut due: Vec<(&String, Instant)> = self
+            .panes
+            .iter()
+            .filter(|(_, state)| state.next_due <= now)
+            .map(|(id, state)| (id, state.next_due))
+            .collect();
+        due.sort_by_key(|(id, next_due)| (!self.visible.contains(*id), *next_due));
+
+        let allowed = (self.tokens.floor() as usize).min(MAX_CAPTURES_PER_TICK);
+        let picked: Vec<String> = due
+            .into_iter()
+            .take(allowed)
+            .map(|(id, _)| id.clone())
+            .collect();
+        self.tokens -= picked.len() as f64;
+        picked
+    }
+
+    /// Record a capture result and schedule the pane's next capture
+    pub fn record(&mut self, pane_id: &str, status: ClaudeCodeStatus, now: Instant) {
+        let Some(state) = self.panes.get_mut(pane_id) else {
+            return;
+        };
+
+        state.interval = if status != state.status || status == ClaudeCodeStatus::Working {
+            MIN_INTERVAL
+        } else {
+            (state.interval * 2).min(MAX_INTERVAL)
+        };
+        state.status = status;
+        state.next_due = now + state.interval;
+    }
+
+    /// Poll a pane again as soon as possible (e.g. after sending it input)
+    pub fn wake(&mut self, pane_id: &str, now: Instant) {
+        if let Some(state) = self.panes.get_mut(pane_id) {
+            state.interval = MIN_INTERVAL;
+            state.next_due = now;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_backoff_and_ramp_up() {
+        let start = Instant::now();
+        let mut poller = StatusPoller::new(100.0, start);
+        let panes = [("%1", ClaudeCodeStatus::Idle)];
+
+        assert_eq!(poller.due(&panes, start), vec!["%1".to_string()]);
+        poller.record("%1", ClaudeCodeStatus::Idle, start);
+        assert_eq!(poller.panes["%1"].interval, MIN_INTERVAL * 2);
+
+        // Unchanged status keeps doubling up to the cap
+        for _ in 0..10 {
+            poller.record("%1", ClaudeCodeStatus::Idle, start);
+        }
+        assert_eq!(poller.panes["%1"].interval, MAX_INTERVAL);
+        assert!(poller.due(&panes, start + MIN_INTERVAL).is_empty());
+
+        // A status change resets to the minimum interval
+        poller.record("%1", ClaudeCodeStatus::Working, start);
+        assert_eq!(poller.panes["%1"].interval, MIN_INTERVAL);
+    }
+
+    #[test]
+    fn test_visible_panes_first_and_tick_cap() {
+        let start = Instant::now();
+        let mut poller = StatusPoller::new(100.0, start);
+        let ids: Vec<String> = (0..20).map(|n| format!("%{}", n)).collect();
+        let panes: Vec<(&str, ClaudeCodeStatus)> = ids
+            .iter()
+            .map(|id| (id.as_str(), ClaudeCodeStatus::Idle))
+            .collect();
+        poller.set_visible(["%17".to_string(), "%18".to_string()].into());
+
+        let due = poller.due(&panes, start);
+        assert_eq!(due.len(), MAX_CAPTURES_PER_TICK);
+        assert!(due[..2].contains(&"%17".to_string()));
+        assert!(due[..2].contains(&"%18".to_string()));
+    }
+
+    #[test]
+    fn test_budget_limits_captures() {
+        let start = Instant::now();
+        let mut poller = StatusPoller::new(2.0, start);
+        let panes = [
+            ("%1", ClaudeCodeStatus::Idle),
+            ("%2", ClaudeCodeStatus::Idle),
+            ("%3", ClaudeCodeStatus::Idle),
+        ];
+
+        assert_eq!(poller.due(&panes, start).len(), 2);
+        // No tokens left until time passes
+        assert!(poller.due(&panes, start).is_empty());
+        assert_eq!(
+            poller.due(&panes, start + Duration::from_millis(500)).len(),
+            1
+        );
+    }
+}
@@ -0,0 +1,80 @@
+//! Popup mode (`claude-tmux --once`)
+//!
+//! With `--once`, the TUI quits after any switch or launch: creating a
+//! session or worktree, starting a workspace or an agent, cloning a layout
+//! or restoring an archived session switches to what it started and exits,
+//! so a popup closes on its own.
+//!
+//! Run without a terminal, as from a key binding's `run-shell`, it opens
+//! itself in a `display-popup` sized for the instances instead, and waits
+//! for the popup to close; `-b` keeps tmux responsive meanwhile:
+//!
+//! ```text
+//! bind-key C-c run-shell -b "claude-tmux --once"
+//! ```
+
+use std::io::IsTerminal;
+
+use anyhow::{Context, Result};
+
+use crate::config::{self, shell_quote};
+use crate::tmux::Tmux;
+use crate::ui;
+use crate::ui_state;
+
+/// Columns of the popup, enough for the instance rows and their badges
+const WIDTH: u16 = 110;
+
+/// Instance rows shown at most before the list scrolls
+const MAX_ROWS: u16 = 20;
+
+/// Whether `--once` should open the popup rather than run the TUI here:
+/// inside tmux, with no terminal to draw on
+pub fn opens_popup() -> bool {
+    Tmux::inside_tmux() && !std::io::stdin().is_terminal()
+}
+
+/// Open `claude-tmux` with `args` (including `--once`) in a popup sized for
+/// the instances
+pub fn run(args: &[String]) -> Result<()> {
+    config::load_for_subcommand("--once");
+
+    let instances = Tmux::list_claude_instances().unwrap_or_default().len();
+    let height = height(instances, ui_state::load().preview_percent);
+    let exe = std::env::current_exe().context("Failed to find the claude-tmux executable")?;
+    let mut command = shell_quote(&exe.to_string_lossy());
+    for arg in args {
+        command.push(' ');
+        command.push_str(&shell_quote(arg));
+    }
+    let dir = std::env::current_dir().unwrap_or_else(|_| dirs::home_dir().unwrap_or_default());
+    Tmux::display_popup(WIDTH, height, &dir, &command)
+}
+
+/// Lines of a popup listing `instances` rows above a preview given
+/// `preview_percent` of the space, borders included
+fn height(instances: usize, preview_percent: u16) -> u16 {
+    let rows = (instances as u16).clamp(3, MAX_ROWS);
+    // Lines for the list and the preview, as the UI splits them
+    let available = (rows..)
+        .find(|&available| available >= rows + ui::preview_share(available, preview_percent))
+        .unwrap_or(rows);
+    // Header, spacing, status bar, footer, and the popup's two borders
+    available + 6
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_height() {
+        // The preview keeps its 8 lines at least
+        assert_eq!(height(4, 50), 18);
+        assert_eq!(height(0, 50), 17);
+        // Longer lists scroll
+        assert_eq!(height(100, 50), 45);
+        assert_eq!(height(4, 0), 10);
+        assert_eq!(height(4, 75), 22);
+    }
+}
@@ -0,0 +1,157 @@
+//! Per-pane preview buffers
+//!
+//! Each captured pane keeps its lines in a ring buffer capped in bytes. A new
+//! capture only replaces the lines of the previous screen that changed; lines
+//! that scrolled off the top stay in the buffer as history until the cap
+//! evicts them, oldest first.
+
+use std::collections::VecDeque;
+
+/// Default cap per preview buffer, in KiB (`preview_buffer_kb`)
+pub const DEFAULT_PREVIEW_BUFFER_KB: usize = 64;
+
+/// Captured lines of one pane
+#[derive(Debug, Clone)]
+pub struct PreviewBuffer {
+    lines: VecDeque<String>,
+    /// Total length of `lines` in bytes
+    bytes: usize,
+    max_bytes: usize,
+    /// Number of trailing lines that belong to the latest capture
+    screen_len: usize,
+}
+
+impl PreviewBuffer {
+    pub fn new(max_bytes: usize) -> Self {
+        Self {
+            lines: VecDeque::new(),
+            bytes: 0,
+            max_bytes,
+            screen_len: 0,
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.lines.is_empty()
+    }
+
+    /// Merge a new capture of the pane's screen, returning whether anything changed
+    ///
+    /// If the first captured line (when not blank) appears in the previous
+    /// screen, everything above it is treated as scrolled into history and
+    /// kept. Otherwise the screen was redrawn and the previous screen is
+    /// replaced.
+    pub fn update(&mut self, capture: &str) -> bool {
+        let new: Vec<&str> = capture.lines().collect();
+        let screen_start = self.lines.len() - self.screen_len;
+
+        let scrolled = new
+            .first()
+            .filter(|first| !first.trim().is_empty())
+            .and_then(|first| {
+                self.lines
+                    .range(screen_start..)
+                    .position(|line| line == first)
+            })
+            .unwrap_or(0);
+        let old_screen = screen_start + scrolled..self.lines.len();
+        let overlap = old_screen.len().min(new.len());
+
+        // Lines scrolled into history stay, unchanged lines are reused
+        let mut changed = scrolled > 0 || old_screen.len() != new.len();
+        for (offset, text) in new.iter().take(overlap).enumerate() {
+            let line = &mut self.lines[old_screen.start + offset];
+            if line != text {
+                self.bytes = self.bytes - line.len() + text.len();
+                *line = text.to_string();
+                changed = true;
+            }
+        }
+        while self.lines.len() > old_screen.start + overlap {
+            if let Some(line) = self.lines.pop_back() {
+                self.bytes -= line.len();
+            }
+        }
+        for text in &new[overlap..] {
+            self.bytes += text.len();
+            self.lines.push_back(text.to_string());
+        }
+        self.screen_len = new.len();
+
+        self.evict();
+        changed
+    }
+
+    /// Number of lines kept, history included
+    pub fn len(&self) -> usize {
+        self.lines.len()
+    }
+
+    /// `count` lines ending `back` lines before the last one, joined with
+    /// newlines; `back` stops at the oldest line
+    pub fn window(&self, count: usize, back: usize) -> String {
+        let end = self
+            .lines
+            .len()
+            .saturating_sub(back)
+            .max(count.min(self.lines.len()));
+        let start = end.saturating_sub(count);
+        self.lines
+            .range(start..end)
+            .map(String::as_str)
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    /// Drop the oldest lines until the buffer fits its cap
+    ///
+    /// The latest screen is always kept, even when it alone exceeds the cap.
+    fn evict(&mut self) {
+        while self.bytes > self.max_bytes && self.lines.len() > self.screen_len {
+            if let Some(line) = self.lines.pop_front() {
+                self.bytes -= line.len();
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn lines(buffer: &PreviewBuffer) -> Vec<&str> {
+        buffer.lines.iter().map(String::as_str).collect()
+    }
+
+    #[test]
+    fn test_scrolled_lines_become_history() {
+        let mut buffer = PreviewBuffer::new(1024);
+        assert!(buffer.update("a\nb\nc\n> "));
+        assert!(!buffer.update("a\nb\nc\n> "));
+
+        // Two lines scrolled off, the prompt line changed
+        assert!(buffer.update("c\nd\ne\n> hi"));
+        assert_eq!(lines(&buffer), vec!["a", "b", "c", "d", "e", "> hi"]);
+        assert_eq!(buffer.window(2, 0), "e\n> hi");
+        assert_eq!(buffer.window(2, 3), "b\nc");
+        assert_eq!(buffer.window(2, 99), "a\nb");
+
+        // A redraw with no common first line replaces the screen only
+        assert!(buffer.update("x\ny"));
+        assert_eq!(lines(&buffer), vec!["a", "b", "x", "y"]);
+    }
+
+    #[test]
+    fn test_cap_evicts_oldest_history() {
+        let mut buffer = PreviewBuffer::new(6);
+        buffer.update("11\n22");
+        buffer.update("22\n33");
+        buffer.update("33\n44");
+        assert_eq!(lines(&buffer), vec!["22", "33", "44"]);
+        assert_eq!(buffer.bytes, 6);
+
+        // The current screen survives even if it is over the cap
+        buffer.update("5555\n6666");
+        assert_eq!(lines(&buffer), vec!["5555", "6666"]);
+    }
+}
@@ -0,0 +1,112 @@
+//! Review comments on an agent's changes
+//!
+//! Comments are anchored to lines of the flattened working tree diff and
+//! turned into a single structured prompt sent back to the agent.
+
+use crate::git::{DiffLine, DiffLineKind};
+
+/// A review comment on one diff line
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ReviewComment {
+    /// Index into the diff lines
+    pub line: usize,
+    pub text: String,
+}
+
+/// Human-readable location of a diff line, e.g. "src/app.rs:42"
+fn location(line: &DiffLine) -> String {
+    match line.kind {
+        DiffLineKind::File => format!("{} (whole file)", line.file),
+        DiffLineKind::Hunk => format!("{} {}", line.file, line.text),
+        DiffLineKind::Removed => match line.old_lineno {
+            Some(n) => format!("{}:{} (removed line)", line.file, n),
+            None => line.file.clone(),
+        },
+        DiffLineKind::Added | DiffLineKind::Context => match line.new_lineno {
+            Some(n) => format!("{}:{}", line.file, n),
+            None => line.file.clone(),
+        },
+    }
+}
+
+/// Build the prompt asking the agent to address review comments
+///
+/// Comments are ordered by their position in the diff and quote the line
+/// they refer to.
+pub fn build_review_prompt(lines: &[DiffLine], comments: &[ReviewComment]) -> String {
+    let mut sorted: Vec<&ReviewComment> = comments.iter().collect();
+    sorted.sort_by_key(|c| c.line);
+
+    let mut prompt = String::from(
+        "I reviewed your uncommitted changes. Please address each of these comments:\n",
+    );
+
+    for (i, comment) in sorted.iter().enumerate() {
+        prompt.push('\n');
+        let Some(line) = lines.get(comment.line) else {
+            prompt.push_str(&format!("{}. {}\n", i + 1, comment.text));
+            continue;
+        };
+
+        prompt.push_str(&format!("{}. {}\n", i + 1, location(line)));
+        let marker = match line.kind {
+            DiffLineKind::Added => Some('+'),
+            DiffLineKind::Removed => Some('-'),
+            DiffLineKind::Context => Some(' '),
+            DiffLineKind::File | DiffLineKind::Hunk => None,
+        };
+        if let Some(marker) = marker {
+            prompt.push_str(&format!("   > {}{}\n", marker, line.text));
+        }
+        for text_line in comment.text.lines() {
+            prompt.push_str(&format!("   {}\n", text_line));
+        }
+    }
+
+    prompt.trim_end().to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn line(kind: DiffLineKind, text: &str, old: Option<u32>, new: Option<u32>) -> DiffLine {
+        DiffLine {
+            kind,
+            text: text.to_string(),
+            file: "src/app.rs".to_string(),
+            old_lineno: old,
+            new_lineno: new,
+        }
+    }
+
+    #[test]
+    fn test_build_review_prompt() {
+        let lines = vec![
+            line(DiffLineKind::File, "src/app.rs", None, None),
+            line(DiffLineKind::Hunk, "@@ -10,2 +10,2 @@", None, None),
+            line(DiffLineKind::Removed, "let x = 1;", Some(10), None),
+            line(DiffLineKind::Added, "let x = compute();", None, Some(10)),
+        ];
+        let comments = vec![
+            ReviewComment {
+                line: 3,
+                text: "compute() can fail, handle the error".to_string(),
+            },
+            ReviewComment {
+                line: 0,
+                text: "Add tests for this module".to_string(),
+            },
+        ];
+
+        let prompt = build_review_prompt(&lines, &comments);
+        assert_eq!(
+            prompt,
+            "I reviewed your uncommitted changes. Please address each of these comments:\n\
+             \n\
+             1. src/app.rs (whole file)\n   Add tests for this module\n\
+             \n\
+             2. src/app.rs:10\n   > +let x = compute();\n   compute() can fail, handle the error"
+        );
+    }
+}
@@ -0,0 +1,328 @@
+//! Scheduled prompt dispatch
+//!
+//! Prompts can be scheduled for a specific time ("9am", "14:30") or after a
+//! delay ("15m", "1h30m"). Jobs are persisted to a small tab-separated file in
+//! the data directory so that the `claude-tmux daemon` watcher can pick them up
+//! and send them to the target pane when they become due.
+
+use std::fs::{self, File};
+use std::path::PathBuf;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use anyhow::{Context, Result};
+
+use crate::store::{self, data_dir, escape_field, unescape_field};
+
+/// A prompt waiting to be sent to a pane
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ScheduledJob {
+    /// Unique job ID (creation timestamp in milliseconds)
+    pub id: u64,
+    /// Unix timestamp (seconds) when the prompt should be sent
+    pub due: u64,
+    /// Target pane ID (e.g., "%3"), stable across window/pane reordering
+    pub pane_id: String,
+    /// Human-readable target (session:window.pane at scheduling time)
+    pub label: String,
+    /// Prompt text to send
+    pub prompt: String,
+}
+
+fn schedule_path() -> PathBuf {
+    data_dir().join("schedule.tsv")
+}
+
+/// Load all pending jobs, sorted by due time
+pub fn load_jobs() -> Vec<ScheduledJob> {
+    let Ok(content) = fs::read_to_string(schedule_path()) else {
+        return Vec::new();
+    };
+
+    let mut jobs: Vec<ScheduledJob> = content.lines().filter_map(parse_job_line).collect();
+    jobs.sort_by_key(|j| (j.due, j.id));
+    jobs
+}
+
+/// Persist the given jobs, replacing the current schedule
+///
+/// Callers updating the schedule hold its lock (`lock_schedule`) from
+/// loading it until it is saved.
+fn save_jobs(jobs: &[ScheduledJob]) -> Result<()> {
+    let content: String = jobs.iter().map(format_job_line).collect();
+    store::write_atomic(&schedule_path(), &content, "schedule")
+}
+
+/// Lock the schedule against other processes updating it
+fn lock_schedule() -> Result<File> {
+    store::lock(&schedule_path()).context("Failed to lock the schedule")
+}
+
+/// Schedule a new prompt and return the created job
+pub fn add_job(due: u64, pane_id: &str, label: &str, prompt: &str) -> Result<ScheduledJob> {
+    let id = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_millis() as u64)
+        .unwrap_or(0);
+
+    let job = ScheduledJob {
+        id,
+        due,
+        pane_id: pane_id.to_string(),
+        label: label.to_string(),
+        prompt: prompt.to_string(),
+    };
+
+    let _lock = lock_schedule()?;
+    let mut jobs = load_jobs();
+    jobs.push(job.clone());
+    save_jobs(&jobs)?;
+    Ok(job)
+}
+
+/// Remove a pending job by ID
+pub fn cancel_job(id: u64) -> Result<()> {
+    let _lock = lock_schedule()?;
+    let mut jobs = load_jobs();
+    let before = jobs.len();
+    jobs.retain(|j| j.id != id);
+    if jobs.len() == before {
+        anyhow::bail!("Job {} not found", id);
+    }
+    save_jobs(&jobs)
+}
+
+/// Remove the given jobs from the schedule (used after dispatching)
+///
+/// Re-reads the schedule so jobs added since the caller loaded it are kept.
+pub fn remove_jobs(ids: &[u64]) -> Result<()> {
+    let _lock = lock_schedule()?;
+    let mut jobs = load_jobs();
+    jobs.retain(|j| !ids.contains(&j.id));
+    save_jobs(&jobs)
+}
+
+fn format_job_line(job: &ScheduledJob) -> String {
+    format!(
+        "{}\t{}\t{}\t{}\t{}\n",
+        job.id,
+        job.due,
+        job.pane_id,
+        escape_field(&job.label),
+        escape_field(&job.prompt)
+    )
+}
+
+fn parse_job_line(line: &str) -> Option<ScheduledJob> {
+    let parts: Vec<&str> = line.splitn(5, '\t').collect();
+    if parts.len() < 5 {
+        return None;
+    }
+    Some(ScheduledJob {
+        id: parts[0].parse().ok()?,
+        due: parts[1].parse().ok()?,
+        pane_id: parts[2].to_string(),
+        label: unescape_field(parts[3]),
+        prompt: unescape_field(parts[4]),
+    })
+}
+
+/// Parse a schedule expression into a unix timestamp
+///
+/// Accepts delays ("30s", "15m", "2h", "1h30m", optionally prefixed with "in ")
+/// and clock times ("9am", "9:30pm", "14:30", optionally prefixed with "at ").
+/// Clock times refer to the next occurrence in local time, `utc_offset`
+/// giving the offset of local time at a timestamp.
+pub fn parse_when(input: &str, now: u64, utc_offset: impl Fn(u64) -> i64) -> Result<u64> {
+    let input = input.trim().to_lowercase();
+    if input.is_empty() {
+        anyhow::bail!("Time cannot be empty");
+    }
+
+    if let Some(rest) = input.strip_prefix("in ") {
+        return parse_delay(rest.trim()).and_then(|secs| after(now, secs));
+    }
+    if let Some(rest) = input.strip_prefix("at ") {
+        return parse_clock(rest.trim(), now, utc_offset);
+    }
+
+    parse_delay(&input)
+        .and_then(|secs| after(now, secs))
+        .or_else(|_| parse_clock(&input, now, utc_offset))
+}
+
+/// The timestamp `secs` after `now`
+fn after(now: u64, secs: u64) -> Result<u64> {
+    now.checked_add(secs)
+        .ok_or_else(|| anyhow::anyhow!("Delay of {}s is too long", secs))
+}
+
+/// Parse a delay like "90s", "15m", "1h30m" into seconds
+fn parse_delay(input: &str) -> Result<u64> {
+    let mut total = 0u64;
+    let mut number = String::new();
+    let mut saw_unit = false;
+
+    for c in input.chars() {
+        if c.is_ascii_digit() {
+            number.push(c);
+            continue;
+        }
+        let multiplier = match c {
+            's' => 1,
+            'm' => 60,
+            'h' => 3600,
+            'd' => 86400,
+            ' ' => continue,
+            _ => anyhow::bail!("Invalid delay '{}'", input),
+        };
+        let value: u64 = number
+            .parse()
+            .map_err(|_| anyhow::anyhow!("Invalid delay '{}'", input))?;
+        total = value
+            .checked_mul(multiplier)
+            .and_then(|secs| total.checked_add(secs))
+            .ok_or_else(|| anyhow::anyhow!("Delay '{}' is too long", input))?;
+        number.clear();
+        saw_unit = true;
+    }
+
+    if !number.is_empty() || !saw_unit {
+        anyhow::bail!("Invalid delay '{}' (use e.g. 15m, 2h, 1h30m)", input);
+    }
+
+    Ok(total)
+}
+
+/// Parse a clock time like "9am", "9:30pm", "14:30" into the next matching timestamp
+fn parse_clock(input: &str, now: u64, utc_offset: impl Fn(u64) -> i64) -> Result<u64> {
+    let (time_part, pm) = if let Some(t) = input.strip_suffix("am") {
+        (t.trim(), Some(false))
+    } else if let Some(t) = input.strip_suffix("pm") {
+        (t.trim(), Some(true))
+    } else {
+        (input, None)
+    };
+
+    let (hour_str, minute_str) = time_part.split_once(':').unwrap_or((time_part, "0"));
+    let mut hour: u64 = hour_str
+        .parse()
+        .map_err(|_| anyhow::anyhow!("Invalid time '{}'", input))?;
+    let minute: u64 = minute_str
+        .parse()
+        .map_err(|_| anyhow::anyhow!("Invalid time '{}'", input))?;
+
+    match pm {
+        Some(is_pm) => {
+            if hour == 0 || hour > 12 {
+                anyhow::bail!("Invalid time '{}'", input);
+            }
+            hour %= 12;
+            if is_pm {
+                hour += 12;
+            }
+        }
+        None if !time_part.contains(':') => {
+            anyhow::bail!("Invalid time '{}' (use e.g. 9am, 14:30)", input);
+        }
+        None => {}
+    }
+
+    if hour > 23 || minute > 59 {
+        anyhow::bail!("Invalid time '{}'", input);
+    }
+
+    // Work in local time, then convert back to UTC
+    let local_now = now as i64 + utc_offset(now);
+    let day_start = local_now - local_now.rem_euclid(86400);
+    let mut target = day_start + (hour * 3600 + minute * 60) as i64;
+    if target <= local_now {
+        target += 86400;
+    }
+
+    // Convert with the offset at the target, which differs from the current
+    // one across a daylight saving change
+    let estimate = target - utc_offset(now);
+    Ok((target - utc_offset(estimate as u64)) as u64)
+}
+
+/// Format a due time relative to now (e.g., "in 5m", "in 2h10m", "overdue")
+pub fn format_due(due: u64, now: u64) -> String {
+    if due <= now {
+        return "overdue".to_string();
+    }
+    let secs = due - now;
+    let (hours, minutes) = (secs / 3600, (secs % 3600) / 60);
+    if hours > 0 {
+        format!("in {}h{:02}m", hours, minutes)
+    } else if minutes > 0 {
+        format!("in {}m", minutes)
+    } else {
+        format!("in {}s", secs)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // 2024-01-01 08:00:00 UTC
+    const NOW: u64 = 1_704_096_000;
+
+    #[test]
+    fn test_parse_delay() {
+        assert_eq!(parse_when("15m", NOW, |_| 0).unwrap(), NOW + 900);
+        assert_eq!(parse_when("in 1h30m", NOW, |_| 0).unwrap(), NOW + 5400);
+        assert_eq!(parse_when("45s", NOW, |_| 0).unwrap(), NOW + 45);
+        assert!(parse_when("15", NOW, |_| 0).is_err());
+        assert!(parse_when("soon", NOW, |_| 0).is_err());
+        assert!(parse_when("99999999999999999d", NOW, |_| 0).is_err());
+        assert!(parse_when("213503982334601d", NOW, |_| 0).is_err());
+    }
+
+    #[test]
+    fn test_parse_clock() {
+        // 9am is later today
+        assert_eq!(parse_when("9am", NOW, |_| 0).unwrap(), NOW + 3600);
+        // 7:30 has passed, so it's tomorrow
+        assert_eq!(
+            parse_when("at 7:30", NOW, |_| 0).unwrap(),
+            NOW + 23 * 3600 + 1800
+        );
+        assert_eq!(parse_when("12pm", NOW, |_| 0).unwrap(), NOW + 4 * 3600);
+        assert_eq!(parse_when("12am", NOW, |_| 0).unwrap(), NOW + 16 * 3600);
+        assert!(parse_when("25:00", NOW, |_| 0).is_err());
+        assert!(parse_when("13pm", NOW, |_| 0).is_err());
+    }
+
+    #[test]
+    fn test_parse_clock_with_offset() {
+        // Local time is UTC+2 (10:00 local), so 11am local is one hour away
+        assert_eq!(parse_when("11am", NOW, |_| 7200).unwrap(), NOW + 3600);
+        // Clocks go forward an hour (UTC+1 to UTC+2) at 09:00 UTC, before
+        // 11am local
+        let offset = |unix: u64| if unix < NOW + 3600 { 3600 } else { 7200 };
+        assert_eq!(parse_when("11am", NOW, offset).unwrap(), NOW + 3600);
+    }
+
+    #[test]
+    fn test_job_line_roundtrip() {
+        let job = ScheduledJob {
+            id: 1,
+            due: NOW,
+            pane_id: "%3".to_string(),
+            label: "work:1.0".to_string(),
+            prompt: "resume the migration\n\tthen run tests \\o/".to_string(),
+        };
+        let line = format_job_line(&job);
+        assert_eq!(line.matches('\n').count(), 1);
+        assert_eq!(parse_job_line(line.trim_end_matches('\n')), Some(job));
+    }
+
+    #[test]
+    fn test_format_due() {
+        assert_eq!(format_due(NOW - 1, NOW), "overdue");
+        assert_eq!(format_due(NOW + 30, NOW), "in 30s");
+        assert_eq!(format_due(NOW + 300, NOW), "in 5m");
+        assert_eq!(format_due(NOW + 3900, NOW), "in 1h05m");
+    }
+}
@@ -3,13 +3,33 @@
 //! As the user scrolls down, the selection stays centered in the visible area
 //! until the bottom items become visible, then the selection moves to the
 //! bottom portion of the view.
+//!
+//! Only the visible window of rows is built and rendered, so the list stays
+//! cheap with hundreds of panes: `update` returns the first visible row and
+//! `window_state` gives the ListState for the rows of that window.
+//!
+//! For the mouse, it also remembers where the list rows and the preview were
+//! drawn, and how far the preview is scrolled back into the pane's history.
 
+use ratatui::layout::{Position, Rect};
 use ratatui::widgets::ListState;
 
 /// Manages scroll state for a list with center-locked scrolling.
 pub struct ScrollState {
-    /// The underlying ratatui ListState
+    /// The underlying ratatui ListState, relative to the visible window
     list_state: ListState,
+    /// First visible row
+    offset: usize,
+    /// Rows visible at the last update
+    page_size: usize,
+    /// Where the visible rows were drawn
+    list_area: Rect,
+    /// Instance shown on each visible row, `None` for group headers
+    rows: Vec<Option<usize>>,
+    /// Where the preview content was drawn
+    preview_area: Rect,
+    /// Pane the preview is scrolled for, and by how many lines back
+    preview_scroll: Option<(String, usize)>,
 }
 
 impl Default for ScrollState {
@@ -22,33 +42,94 @@ impl ScrollState {
     pub fn new() -> Self {
         Self {
             list_state: ListState::default(),
+            offset: 0,
+            page_size: 0,
+            list_area: Rect::default(),
+            rows: Vec::new(),
+            preview_area: Rect::default(),
+            preview_scroll: None,
         }
     }
 
-    /// Update the scroll state given the current selection and list dimensions.
+    /// Update the scroll position given the current selection and list dimensions.
     ///
-    /// Returns a mutable reference to the underlying ListState for rendering.
+    /// Returns the index of the first visible row.
     ///
     /// # Arguments
     /// * `selected` - The index of the currently selected item
     /// * `total_items` - Total number of items in the list
     /// * `visible_height` - Height of the visible area in rows
-    pub fn update(
-        &mut self,
-        selected: usize,
-        total_items: usize,
-        visible_height: usize,
-    ) -> &mut ListState {
-        // Set the selected item
-        self.list_state.select(Some(selected));
-
-        // Compute and set centered offset
-        let offset = Self::compute_centered_offset(selected, total_items, visible_height);
-        *self.list_state.offset_mut() = offset;
+    pub fn update(&mut self, selected: usize, total_items: usize, visible_height: usize) -> usize {
+        self.offset = Self::compute_centered_offset(selected, total_items, visible_height);
+        self.page_size = visible_height;
+        self.offset
+    }
 
+    /// ListState for rendering only the visible rows, starting at the offset
+    /// returned by `update`.
+    pub fn window_state(&mut self, selected: usize) -> &mut ListState {
+        self.list_state
+            .select(Some(selected.saturating_sub(self.offset)));
+        *self.list_state.offset_mut() = 0;
         &mut self.list_state
     }
 
+    /// First visible row at the last update
+    pub fn offset(&self) -> usize {
+        self.offset
+    }
+
+    /// Rows visible at the last update
+    pub fn page_size(&self) -> usize {
+        self.page_size
+    }
+
+    /// Record the instance shown on each row drawn in `area`
+    pub fn set_rows(&mut self, area: Rect, rows: Vec<Option<usize>>) {
+        self.list_area = area;
+        self.rows = rows;
+    }
+
+    /// Instance drawn at a screen position, if any
+    pub fn instance_at(&self, column: u16, row: u16) -> Option<usize> {
+        if !self.list_area.contains(Position::new(column, row)) {
+            return None;
+        }
+        self.rows
+            .get((row - self.list_area.y) as usize)
+            .copied()
+            .flatten()
+    }
+
+    /// Record where the preview content was drawn
+    pub fn set_preview_area(&mut self, area: Rect) {
+        self.preview_area = area;
+    }
+
+    /// Whether a screen position is on the preview
+    pub fn in_preview(&self, column: u16, row: u16) -> bool {
+        self.preview_area.contains(Position::new(column, row))
+    }
+
+    /// Lines the preview of `pane_id` is scrolled back from its last line
+    pub fn preview_offset(&self, pane_id: &str) -> usize {
+        match &self.preview_scroll {
+            Some((pane, back)) if pane == pane_id => *back,
+            _ => 0,
+        }
+    }
+
+    /// Scroll the preview of `pane_id` back into history by `delta` lines
+    /// (forward when negative), until its first of `lines` lines shows
+    pub fn scroll_preview(&mut self, pane_id: &str, delta: isize, lines: usize) {
+        let max = lines.saturating_sub(self.preview_area.height as usize);
+        let back = self
+            .preview_offset(pane_id)
+            .saturating_add_signed(delta)
+            .min(max);
+        self.preview_scroll = Some((pane_id.to_string(), back));
+    }
+
     /// Compute the scroll offset to keep selection centered.
     ///
     /// Behavior:
@@ -111,6 +192,28 @@ mod tests {
         assert_eq!(ScrollState::compute_centered_offset(19, 20, 10), 10);
     }
 
+    #[test]
+    fn test_mouse_targets() {
+        let mut state = ScrollState::new();
+        state.set_rows(Rect::new(0, 2, 80, 5), vec![None, Some(3), Some(4)]);
+        assert_eq!(state.instance_at(10, 3), Some(3));
+        assert_eq!(state.instance_at(10, 2), None);
+        assert_eq!(state.instance_at(10, 5), None);
+        assert_eq!(state.instance_at(90, 3), None);
+
+        // Preview scrolling is kept per pane, and stops once the first of
+        // its 15 lines shows in 5 rows
+        state.set_preview_area(Rect::new(0, 8, 80, 5));
+        assert!(state.in_preview(0, 12));
+        assert!(!state.in_preview(0, 13));
+        state.scroll_preview("%1", 3, 15);
+        state.scroll_preview("%1", 20, 15);
+        assert_eq!(state.preview_offset("%1"), 10);
+        assert_eq!(state.preview_offset("%2"), 0);
+        state.scroll_preview("%1", -20, 15);
+        assert_eq!(state.preview_offset("%1"), 0);
+    }
+
     #[test]
     fn test_edge_cases() {
         // Empty list
@@ -0,0 +1,112 @@
+//! Search across the content of every Claude pane
+//!
+//! Each instance's pane is captured as plain text, including up to
+//! `HISTORY_LINES` of scrollback, and searched for a fixed string. As in
+//! vim's smartcase, the search ignores case unless the pattern has an
+//! uppercase letter. Instances are listed in session list order with their
+//! most recent matching line.
+
+use crate::session::ClaudeInstance;
+use crate::tmux::Tmux;
+
+/// Lines of scrollback captured per pane
+const HISTORY_LINES: usize = 2000;
+
+/// Longest excerpt kept of a matching line, in characters
+const MAX_LINE_CHARS: usize = 200;
+
+/// An instance whose pane contains the pattern
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SearchMatch {
+    /// Pane to switch to (`session:window.pane`)
+    pub target: String,
+    /// Checkout the instance works in, shortened for display
+    pub path: String,
+    /// Most recent matching line, trimmed
+    pub line: String,
+    /// Number of matching lines
+    pub count: usize,
+}
+
+/// Instances whose pane content contains `pattern`
+///
+/// Panes that cannot be captured are skipped.
+pub fn search_panes(instances: &[ClaudeInstance], pattern: &str) -> Vec<SearchMatch> {
+    instances
+        .iter()
+        .filter_map(|instance| {
+            let content = match Tmux::capture_history(&instance.pane_id, HISTORY_LINES) {
+                Ok(content) => content,
+                Err(e) => {
+                    tracing::debug!("search: {}: {:#}", instance.pane_id, e);
+                    return None;
+                }
+            };
+            let lines = matching_lines(&content, pattern);
+            let line = lines.last()?;
+            Some(SearchMatch {
+                target: instance.tmux_target(),
+                path: instance.display_path(),
+                line: excerpt(line),
+                count: lines.len(),
+            })
+        })
+        .collect()
+}
+
+/// Lines of `content` containing `pattern`, smartcase
+fn matching_lines<'a>(content: &'a str, pattern: &str) -> Vec<&'a str> {
+    let pattern = pattern.trim();
+    if pattern.is_empty() {
+        return Vec::new();
+    }
+    let ignore_case = !pattern.chars().any(char::is_uppercase);
+    let needle = if ignore_case {
+        pattern.to_lowercase()
+    } else {
+        pattern.to_string()
+    };
+    content
+        .lines()
+        .filter(|line| {
+            if ignore_case {
+                line.to_lowercase().contains(&needle)
+            } else {
+                line.contains(&needle)
+            }
+        })
+        .collect()
+}
+
+/// A line without surrounding whitespace or box borders, shortened
+fn excerpt(line: &str) -> String {
+    let line = line.trim_matches(|c: char| c.is_whitespace() || matches!(c, '│' | '┃' | '|'));
+    if line.chars().count() > MAX_LINE_CHARS {
+        let mut short: String = line.chars().take(MAX_LINE_CHARS - 1).collect();
+        short.push('…');
+        short
+    } else {
+        line.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_matching_lines() {
+        let content = "Editing src/billing/invoice.rs\nRunning tests\n│ Billing totals fixed │";
+        assert_eq!(
+            matching_lines(content, "billing"),
+            vec!["Editing src/billing/invoice.rs", "│ Billing totals fixed │"]
+        );
+        assert_eq!(
+            matching_lines(content, "Billing"),
+            vec!["│ Billing totals fixed │"]
+        );
+        assert!(matching_lines(content, "  ").is_empty());
+        assert_eq!(excerpt("│ Billing totals fixed │"), "Billing totals fixed");
+        assert_eq!(excerpt(&"x".repeat(300)).chars().count(), MAX_LINE_CHARS);
+    }
+}
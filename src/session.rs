@@ -1,6 +1,7 @@
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
 use crate::git::GitContext;
+use crate::i18n::tr;
 
 /// Status of a Claude Code instance in a pane
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
@@ -11,6 +12,8 @@ pub enum ClaudeCodeStatus {
     Working,
     /// Awaiting user confirmation/input (y/n prompt, etc.)
     WaitingInput,
+    /// Claude exited but its pane is still open
+    Crashed,
     /// Cannot determine status
     #[default]
     Unknown,
@@ -23,6 +26,18 @@ impl ClaudeCodeStatus {
             ClaudeCodeStatus::Idle => "○",
             ClaudeCodeStatus::Working => "●",
             ClaudeCodeStatus::WaitingInput => "◐",
+            ClaudeCodeStatus::Crashed => "✗",
+            ClaudeCodeStatus::Unknown => "?",
+        }
+    }
+
+    /// Returns the symbol for terminals without Unicode glyphs
+    pub fn ascii_symbol(&self) -> &'static str {
+        match self {
+            ClaudeCodeStatus::Idle => "o",
+            ClaudeCodeStatus::Working => "*",
+            ClaudeCodeStatus::WaitingInput => "!",
+            ClaudeCodeStatus::Crashed => "x",
             ClaudeCodeStatus::Unknown => "?",
         }
     }
@@ -33,13 +48,25 @@ impl ClaudeCodeStatus {
             ClaudeCodeStatus::Idle => "idle",
             ClaudeCodeStatus::Working => "working",
             ClaudeCodeStatus::WaitingInput => "input",
+            ClaudeCodeStatus::Crashed => "crashed",
             ClaudeCodeStatus::Unknown => "unknown",
         }
     }
+
+    /// Parse a label produced by `label()`, defaulting to `Unknown`
+    pub fn from_label(label: &str) -> Self {
+        match label {
+            "idle" => ClaudeCodeStatus::Idle,
+            "working" => ClaudeCodeStatus::Working,
+            "input" => ClaudeCodeStatus::WaitingInput,
+            "crashed" => ClaudeCodeStatus::Crashed,
+            _ => ClaudeCodeStatus::Unknown,
+        }
+    }
 }
 
 /// A tmux pane within a session
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub struct Pane {
     /// Pane ID (e.g., "%0")
     pub id: String,
@@ -53,6 +80,65 @@ pub struct Pane {
     pub window_index: usize,
     /// Window name
     pub window_name: String,
+    /// Whether claude-tmux has seen Claude running in this pane before
+    pub agent_marked: bool,
+    /// Name of the foreground command (e.g. "zsh", "vim")
+    pub current_command: String,
+    /// Whether the pane's output is piped to a command (`pipe-pane`)
+    pub piped: bool,
+}
+
+impl Pane {
+    /// Whether the pane sits at a shell prompt
+    pub fn is_shell(&self) -> bool {
+        is_shell(&self.current_command)
+    }
+}
+
+/// Any pane on the server, as listed in the all-panes view
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PaneEntry {
+    pub session_name: String,
+    pub pane: Pane,
+    /// Whether Claude runs in or below the pane's process
+    pub has_claude: bool,
+}
+
+/// Commands treated as an idle shell, safe to type a command into
+const SHELLS: &[&str] = &[
+    "bash", "zsh", "fish", "sh", "dash", "ksh", "mksh", "tcsh", "csh", "nu", "elvish", "xonsh",
+];
+
+/// Whether `command` (a pane's `pane_current_command`) is a shell; login
+/// shells show as `-zsh`
+pub fn is_shell(command: &str) -> bool {
+    SHELLS.contains(&command.trim_start_matches('-'))
+}
+
+impl PaneEntry {
+    /// `session:window.pane`, as for `ClaudeInstance`
+    pub fn display_name(&self) -> String {
+        format!(
+            "{}:{}.{}",
+            self.session_name, self.pane.window_index, self.pane.pane_index
+        )
+    }
+
+    /// Whether the pane sits at a shell prompt
+    pub fn is_shell(&self) -> bool {
+        self.pane.is_shell()
+    }
+}
+
+/// A path with the home directory replaced by `~`
+pub fn short_path(path: &Path) -> String {
+    if let Some(home) = dirs::home_dir() {
+        if let Ok(stripped) = path.strip_prefix(&home) {
+            return format!("~/{}", stripped.display());
+        }
+    }
+
+    path.display().to_string()
 }
 
 /// A Claude Code instance running in a tmux pane
@@ -77,12 +163,35 @@ pub struct ClaudeInstance {
     pub pane_index: usize,
 
     // Claude info
-    /// Working directory of the pane
+    /// Working directory of the Claude process, or of the pane if it
+    /// can't be read
     pub working_directory: PathBuf,
+    /// The pane's current path, when it differs from `working_directory`
+    pub pane_path: Option<PathBuf>,
     /// Status of Claude Code
     pub status: ClaudeCodeStatus,
     /// Git context, if the working directory is a git repository
     pub git_context: Option<GitContext>,
+    /// Whether the pane's output is being logged (`pipe-pane`)
+    pub logging: bool,
+}
+
+/// An instance at `api:1.0` in `/src/api`, for tests
+#[cfg(test)]
+pub(crate) fn test_instance(pane_id: &str, status: ClaudeCodeStatus) -> ClaudeInstance {
+    ClaudeInstance {
+        session_name: "api".to_string(),
+        session_attached: false,
+        window_index: 1,
+        window_name: "claude".to_string(),
+        pane_id: pane_id.to_string(),
+        pane_index: 0,
+        working_directory: PathBuf::from("/src/api"),
+        pane_path: None,
+        status,
+        git_context: None,
+        logging: false,
+    }
 }
 
 impl ClaudeInstance {
@@ -96,16 +205,52 @@ impl ClaudeInstance {
 
     /// Returns a shortened version of the working directory for display
     pub fn display_path(&self) -> String {
-        let path = &self.working_directory;
+        short_path(&self.working_directory)
+    }
 
-        // Try to replace home directory with ~
-        if let Some(home) = dirs::home_dir() {
-            if let Ok(stripped) = path.strip_prefix(&home) {
-                return format!("~/{}", stripped.display());
-            }
+    /// Whether the pane's working directory no longer exists (e.g. a deleted worktree)
+    pub fn is_directory_missing(&self) -> bool {
+        !self.working_directory.as_os_str().is_empty() && !self.working_directory.exists()
+    }
+
+    /// Label for the pane's border, e.g. "claude: working ● feature/x ↑2",
+    /// or "claude: working * feature/x ^2" with `ascii`
+    pub fn border_label(&self, ascii: bool) -> String {
+        let symbol = self.status_symbol(ascii);
+        let label = format!("claude: {} {}", self.status.label(), symbol);
+        self.with_branch(label, ascii)
+    }
+
+    /// Compact status for a tmux status line, e.g. "● working feature/x ↑2",
+    /// or "* working feature/x ^2" with `ascii`
+    pub fn status_line(&self, ascii: bool) -> String {
+        let symbol = self.status_symbol(ascii);
+        let label = format!("{} {}", symbol, self.status.label());
+        self.with_branch(label, ascii)
+    }
+
+    fn status_symbol(&self, ascii: bool) -> &'static str {
+        if ascii {
+            self.status.ascii_symbol()
+        } else {
+            self.status.symbol()
         }
+    }
 
-        path.display().to_string()
+    /// `label` followed by the branch and how far it is ahead and behind
+    fn with_branch(&self, mut label: String, ascii: bool) -> String {
+        let (up, down) = if ascii { ("^", "v") } else { ("↑", "↓") };
+        if let Some(git) = &self.git_context {
+            label.push(' ');
+            label.push_str(&git.branch);
+            if git.ahead > 0 {
+                label.push_str(&format!(" {}{}", up, git.ahead));
+            }
+            if git.behind > 0 {
+                label.push_str(&format!(" {}{}", down, git.behind));
+            }
+        }
+        label
     }
 
     /// Returns the tmux target for this pane (for switch-client, send-keys, etc.)
@@ -117,10 +262,65 @@ impl ClaudeInstance {
     }
 }
 
-/// A tmux session that may contain a Claude Code instance
-#[derive(Debug, Clone)]
-#[allow(dead_code)]
+/// Order of the session list (`sort_order` in the config)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum SortOrder {
+    /// Attached sessions first, then by session name
+    #[default]
+    Attached,
+    /// By session name
+    Name,
+    /// By working directory, keeping agents of a repository together
+    Path,
+}
+
+impl SortOrder {
+    /// Every order
+    pub const ALL: [Self; 3] = [Self::Attached, Self::Name, Self::Path];
+
+    /// Name used in the config
+    pub fn code(self) -> &'static str {
+        match self {
+            Self::Attached => "attached",
+            Self::Name => "name",
+            Self::Path => "path",
+        }
+    }
+
+    pub fn parse(value: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|o| o.code() == value)
+    }
+
+    /// What the list is sorted by, as shown after changing the order
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Attached => tr("attached first"),
+            Self::Name => tr("name"),
+            Self::Path => tr("path"),
+        }
+    }
+
+    /// Sort instances, then by window and pane within a session
+    pub fn sort(self, instances: &mut [ClaudeInstance]) {
+        instances.sort_by(|a, b| {
+            let first = match self {
+                Self::Attached => b.session_attached.cmp(&a.session_attached),
+                Self::Name => std::cmp::Ordering::Equal,
+                Self::Path => a.working_directory.cmp(&b.working_directory),
+            };
+            first
+                .then_with(|| a.session_name.cmp(&b.session_name))
+                .then_with(|| a.window_index.cmp(&b.window_index))
+                .then_with(|| a.pane_index.cmp(&b.pane_index))
+        });
+    }
+}
+
+/// A tmux session, with or without Claude, as listed in the sessions view
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub struct Session {
+    /// Session ID (e.g., "$0"), kept across renames
+    pub id: String,
     /// Session name
     pub name: String,
     /// Unix timestamp when session was created
@@ -135,8 +335,11 @@ pub struct Session {
     pub panes: Vec<Pane>,
     /// Pane ID containing Claude Code, if any
     pub claude_code_pane: Option<String>,
-    /// Status of Claude Code in this session
-    pub claude_code_status: ClaudeCodeStatus,
-    /// Git context, if the working directory is a git repository
-    pub git_context: Option<GitContext>,
+}
+
+impl Session {
+    /// Seconds since the session was created, as of `now`
+    pub fn duration(&self, now: u64) -> u64 {
+        now.saturating_sub(self.created.max(0) as u64)
+    }
 }
@@ -0,0 +1,271 @@
+//! Pane snapshots taken when an agent stops for the user
+//!
+//! When an agent starts waiting for input or crashes, the end of its pane
+//! is saved in the data directory, so what it asked or printed can be read
+//! later even if the pane has since scrolled, been cleared or closed. The
+//! daemon watches for these changes, or the TUI while no daemon runs; turn
+//! it off with `status_snapshots = false`.
+//!
+//! `snapshots.tsv` lists the snapshots, one per line; the text of each is
+//! in `snapshots/<id>.txt`. Only the latest `MAX_SNAPSHOTS` are kept.
+
+use std::collections::HashMap;
+use std::fs;
+use std::path::PathBuf;
+
+use anyhow::{Context, Result};
+
+use crate::session::{ClaudeCodeStatus, ClaudeInstance};
+use crate::store::{self, data_dir, escape_field, now_unix, unescape_field};
+use crate::tmux::Tmux;
+
+/// Snapshots kept; older ones are deleted when a new one is taken
+const MAX_SNAPSHOTS: usize = 200;
+
+/// Lines of the pane saved, scrollback included
+const SNAPSHOT_LINES: usize = 300;
+
+/// The end of an agent's pane when its status changed
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Snapshot {
+    /// Unique ID (timestamp in milliseconds)
+    pub id: u64,
+    /// Unix timestamp (seconds) when it was taken
+    pub taken_at: u64,
+    /// Status the agent changed to
+    pub status: ClaudeCodeStatus,
+    /// Pane as `session:window.pane`
+    pub target: String,
+    /// tmux pane ID, e.g. `%3`
+    pub pane_id: String,
+    /// Working directory of the agent
+    pub path: PathBuf,
+    /// Branch checked out then
+    pub branch: Option<String>,
+}
+
+fn index_path() -> PathBuf {
+    data_dir().join("snapshots.tsv")
+}
+
+fn content_path(id: u64) -> PathBuf {
+    data_dir().join("snapshots").join(format!("{}.txt", id))
+}
+
+/// Load the snapshots, most recent first
+pub fn load() -> Vec<Snapshot> {
+    let Ok(content) = fs::read_to_string(index_path()) else {
+        return Vec::new();
+    };
+    let mut snapshots: Vec<Snapshot> = content.lines().filter_map(parse_line).collect();
+    snapshots.sort_by_key(|s| std::cmp::Reverse((s.taken_at, s.id)));
+    snapshots
+}
+
+/// Text of a snapshot
+pub fn content(snapshot: &Snapshot) -> Result<String> {
+    fs::read_to_string(content_path(snapshot.id)).context("Failed to read snapshot")
+}
+
+/// Delete a snapshot and its text
+pub fn remove(id: u64) -> Result<()> {
+    let mut snapshots = load();
+    let before = snapshots.len();
+    snapshots.retain(|s| s.id != id);
+    if snapshots.len() == before {
+        anyhow::bail!("Snapshot {} not found", id);
+    }
+    save_index(&snapshots)?;
+    let _ = fs::remove_file(content_path(id));
+    Ok(())
+}
+
+/// Capture the pane of `instance` and save it as a snapshot
+pub fn take(instance: &ClaudeInstance) -> Result<Snapshot> {
+    let text = Tmux::capture_history(&instance.pane_id, SNAPSHOT_LINES)?;
+    let now = now_unix();
+    let snapshot = Snapshot {
+        id: std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .map(|d| d.as_millis() as u64)
+            .unwrap_or(now * 1000),
+        taken_at: now,
+        status: instance.status,
+        target: instance.tmux_target(),
+        pane_id: instance.pane_id.clone(),
+        path: instance.working_directory.clone(),
+        branch: instance.git_context.as_ref().map(|g| g.branch.clone()),
+    };
+
+    let path = content_path(snapshot.id);
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent).context("Failed to create snapshot directory")?;
+    }
+    fs::write(&path, text.trim_end()).context("Failed to write snapshot")?;
+
+    let mut snapshots = load();
+    snapshots.insert(0, snapshot.clone());
+    for old in snapshots.drain(MAX_SNAPSHOTS.min(snapshots.len())..) {
+        let _ = fs::remove_file(content_path(old.id));
+    }
+    save_index(&snapshots)?;
+    Ok(snapshot)
+}
+
+fn save_index(snapshots: &[Snapshot]) -> Result<()> {
+    let content: String = snapshots.iter().map(format_line).collect();
+    store::write_atomic(&index_path(), &content, "snapshot list")
+}
+
+fn format_line(snapshot: &Snapshot) -> String {
+    format!(
+        "{}\t{}\t{}\t{}\t{}\t{}\t{}\n",
+        snapshot.id,
+        snapshot.taken_at,
+        snapshot.status.label(),
+        escape_field(&snapshot.target),
+        escape_field(&snapshot.pane_id),
+        escape_field(&snapshot.path.to_string_lossy()),
+        escape_field(snapshot.branch.as_deref().unwrap_or_default()),
+    )
+}
+
+fn parse_line(line: &str) -> Option<Snapshot> {
+    let fields: Vec<&str> = line.split('\t').collect();
+    let [id, taken_at, status, target, pane_id, path, branch] = fields[..] else {
+        return None;
+    };
+    let branch = unescape_field(branch);
+    Some(Snapshot {
+        id: id.parse().ok()?,
+        taken_at: taken_at.parse().ok()?,
+        status: ClaudeCodeStatus::from_label(status),
+        target: unescape_field(target),
+        pane_id: unescape_field(pane_id),
+        path: PathBuf::from(unescape_field(path)),
+        branch: (!branch.is_empty()).then_some(branch),
+    })
+}
+
+/// Watches agents for the status changes that take a snapshot
+#[derive(Debug, Default)]
+pub struct SnapshotWatch {
+    /// Last known status of each pane
+    statuses: HashMap<String, ClaudeCodeStatus>,
+}
+
+impl SnapshotWatch {
+    /// Snapshot the agents that started waiting or crashed since the last
+    /// sample
+    pub fn sample(&mut self, instances: &[ClaudeInstance]) {
+        for instance in self.observe(instances) {
+            match take(instance) {
+                Ok(_) => tracing::info!(
+                    "snapshot of {} ({})",
+                    instance.tmux_target(),
+                    instance.status.label()
+                ),
+                Err(e) => tracing::warn!("snapshot of {}: {:#}", instance.tmux_target(), e),
+            }
+        }
+    }
+
+    /// Agents whose status changed to waiting or crashed
+    ///
+    /// Panes seen for the first time only record their status, so starting
+    /// the watch doesn't snapshot every agent already waiting.
+    fn observe<'a>(&mut self, instances: &'a [ClaudeInstance]) -> Vec<&'a ClaudeInstance> {
+        let mut changed = Vec::new();
+        for instance in instances {
+            // Unknown while a capture failed; keep the last status
+            if instance.status == ClaudeCodeStatus::Unknown {
+                continue;
+            }
+            let previous = self
+                .statuses
+                .insert(instance.pane_id.clone(), instance.status);
+            let stopped = matches!(
+                instance.status,
+                ClaudeCodeStatus::WaitingInput | ClaudeCodeStatus::Crashed
+            );
+            if stopped && previous.is_some_and(|p| p != instance.status) {
+                changed.push(instance);
+            }
+        }
+        self.statuses
+            .retain(|pane_id, _| instances.iter().any(|i| &i.pane_id == pane_id));
+        changed
+    }
+
+    /// Forget the statuses seen (another process is watching meanwhile)
+    pub fn pause(&mut self) {
+        self.statuses.clear();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::session::test_instance;
+
+    fn observed(watch: &mut SnapshotWatch, instances: &[ClaudeInstance]) -> Vec<String> {
+        watch
+            .observe(instances)
+            .iter()
+            .map(|i| i.pane_id.clone())
+            .collect()
+    }
+
+    #[test]
+    fn test_observe() {
+        let mut watch = SnapshotWatch::default();
+        let first = [
+            test_instance("%1", ClaudeCodeStatus::WaitingInput),
+            test_instance("%2", ClaudeCodeStatus::Working),
+        ];
+        assert!(observed(&mut watch, &first).is_empty());
+
+        let second = [
+            test_instance("%1", ClaudeCodeStatus::WaitingInput),
+            test_instance("%2", ClaudeCodeStatus::WaitingInput),
+        ];
+        assert_eq!(observed(&mut watch, &second), vec!["%2"]);
+
+        // A failed capture doesn't count as a change
+        let third = [
+            test_instance("%1", ClaudeCodeStatus::Unknown),
+            test_instance("%2", ClaudeCodeStatus::Crashed),
+        ];
+        assert_eq!(observed(&mut watch, &third), vec!["%2"]);
+        let fourth = [test_instance("%1", ClaudeCodeStatus::WaitingInput)];
+        assert!(observed(&mut watch, &fourth).is_empty());
+    }
+
+    #[test]
+    fn test_line_round_trip() {
+        let snapshot = Snapshot {
+            id: 1_704_096_000_123,
+            taken_at: 1_704_096_000,
+            status: ClaudeCodeStatus::WaitingInput,
+            target: "api:1.0".to_string(),
+            pane_id: "%3".to_string(),
+            path: PathBuf::from("/src/my\tapi"),
+            branch: Some("main".to_string()),
+        };
+        let line = format_line(&snapshot);
+        assert_eq!(
+            parse_line(line.trim_end_matches('\n')),
+            Some(snapshot.clone())
+        );
+
+        let detached = Snapshot {
+            branch: None,
+            ..snapshot
+        };
+        assert_eq!(
+            parse_line(format_line(&detached).trim_end_matches('\n')),
+            Some(detached)
+        );
+        assert_eq!(parse_line("garbage"), None);
+    }
+}
@@ -0,0 +1,256 @@
+//! Local usage statistics
+//!
+//! Counters of what was done through claude-tmux (sessions created, prompts
+//! sent, commits and pushes) and of how agents waited for input, kept in a
+//! small tab-separated file in the data directory. Nothing leaves the
+//! machine. Input waits are seen by the same sampling as the working time
+//! (see `worktime`): an agent entering the waiting-for-input state counts as
+//! a request, and the time until it leaves that state as the response time.
+
+use std::collections::HashMap;
+use std::fs;
+use std::path::PathBuf;
+
+use anyhow::Result;
+
+use crate::session::{ClaudeCodeStatus, ClaudeInstance};
+use crate::store::{self, data_dir, now_unix};
+
+/// Something counted in the statistics
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Counter {
+    SessionsCreated,
+    PromptsSent,
+    Commits,
+    Pushes,
+    /// Times an agent started waiting for input
+    InputRequests,
+    /// Input waits that ended with an answer
+    InputResponses,
+    /// Seconds spent waiting by the answered input requests
+    InputWaitSeconds,
+}
+
+impl Counter {
+    const ALL: [Self; 7] = [
+        Self::SessionsCreated,
+        Self::PromptsSent,
+        Self::Commits,
+        Self::Pushes,
+        Self::InputRequests,
+        Self::InputResponses,
+        Self::InputWaitSeconds,
+    ];
+
+    /// Name in the statistics file
+    fn key(self) -> &'static str {
+        match self {
+            Self::SessionsCreated => "sessions_created",
+            Self::PromptsSent => "prompts_sent",
+            Self::Commits => "commits",
+            Self::Pushes => "pushes",
+            Self::InputRequests => "input_requests",
+            Self::InputResponses => "input_responses",
+            Self::InputWaitSeconds => "input_wait_seconds",
+        }
+    }
+}
+
+/// Recorded statistics
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Stats {
+    /// Values in `Counter::ALL` order
+    values: [u64; 7],
+    /// When the first value was recorded (unix seconds)
+    pub since: Option<u64>,
+}
+
+impl Stats {
+    pub fn get(&self, counter: Counter) -> u64 {
+        self.values[Counter::ALL.iter().position(|c| *c == counter).unwrap_or(0)]
+    }
+
+    fn add(&mut self, counter: Counter, amount: u64) {
+        let index = Counter::ALL.iter().position(|c| *c == counter).unwrap_or(0);
+        self.values[index] += amount;
+    }
+
+    /// Average seconds agents waited for an answer, if any was answered
+    pub fn average_response(&self) -> Option<u64> {
+        let responses = self.get(Counter::InputResponses);
+        (responses > 0).then(|| self.get(Counter::InputWaitSeconds) / responses)
+    }
+}
+
+fn stats_path() -> PathBuf {
+    data_dir().join("stats.tsv")
+}
+
+/// Load the recorded statistics
+pub fn load() -> Stats {
+    fs::read_to_string(stats_path())
+        .map(|content| parse(&content))
+        .unwrap_or_default()
+}
+
+/// Add to counters, logging a failure to save them
+pub fn record(counts: &[(Counter, u64)]) {
+    if let Err(e) = save_counts(counts) {
+        tracing::warn!("{:#}", e);
+    }
+}
+
+fn save_counts(counts: &[(Counter, u64)]) -> Result<()> {
+    let _lock = store::lock(&stats_path())?;
+    let mut stats = load();
+    for (counter, amount) in counts {
+        stats.add(*counter, *amount);
+    }
+    stats.since.get_or_insert_with(now_unix);
+
+    store::write_atomic(&stats_path(), &format(&stats), "statistics")
+}
+
+fn format(stats: &Stats) -> String {
+    let mut content = String::new();
+    if let Some(since) = stats.since {
+        content.push_str(&format!("since\t{}\n", since));
+    }
+    for (counter, value) in Counter::ALL.iter().zip(stats.values) {
+        content.push_str(&format!("{}\t{}\n", counter.key(), value));
+    }
+    content
+}
+
+/// Parse the statistics file, ignoring unknown or malformed lines
+fn parse(content: &str) -> Stats {
+    let mut stats = Stats::default();
+    for line in content.lines() {
+        let Some((key, value)) = line.split_once('\t') else {
+            continue;
+        };
+        let Ok(value) = value.parse() else {
+            continue;
+        };
+        if key == "since" {
+            stats.since = Some(value);
+        } else if let Some(counter) = Counter::ALL.into_iter().find(|c| c.key() == key) {
+            stats.add(counter, value);
+        }
+    }
+    stats
+}
+
+/// Follows agents waiting for input across samples
+#[derive(Default)]
+pub struct InputWatch {
+    /// When each waiting pane started waiting
+    waiting_since: HashMap<String, u64>,
+}
+
+impl InputWatch {
+    /// Record agents that started or stopped waiting since the last sample
+    pub fn sample(&mut self, instances: &[ClaudeInstance], now: u64) {
+        let counts = self.observe(instances, now);
+        if !counts.is_empty() {
+            record(&counts);
+        }
+    }
+
+    fn observe(&mut self, instances: &[ClaudeInstance], now: u64) -> Vec<(Counter, u64)> {
+        let mut counts = Vec::new();
+        for instance in instances {
+            let waiting = instance.status == ClaudeCodeStatus::WaitingInput;
+            match self.waiting_since.get(&instance.pane_id) {
+                None if waiting => {
+                    self.waiting_since.insert(instance.pane_id.clone(), now);
+                    counts.push((Counter::InputRequests, 1));
+                }
+                // A crashed agent was not answered
+                Some(_) if instance.status == ClaudeCodeStatus::Crashed => {
+                    self.waiting_since.remove(&instance.pane_id);
+                }
+                // Unknown while a capture failed; keep waiting
+                Some(&since) if !waiting && instance.status != ClaudeCodeStatus::Unknown => {
+                    self.waiting_since.remove(&instance.pane_id);
+                    counts.push((Counter::InputResponses, 1));
+                    counts.push((Counter::InputWaitSeconds, now.saturating_sub(since)));
+                }
+                _ => {}
+            }
+        }
+        // Panes that were closed while waiting
+        self.waiting_since
+            .retain(|pane_id, _| instances.iter().any(|i| &i.pane_id == pane_id));
+        counts
+    }
+
+    /// Forget the agents seen waiting (another process is watching meanwhile)
+    pub fn pause(&mut self) {
+        self.waiting_since.clear();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::session::test_instance;
+
+    #[test]
+    fn test_roundtrip() {
+        let mut stats = Stats::default();
+        stats.add(Counter::Commits, 3);
+        stats.add(Counter::InputResponses, 2);
+        stats.add(Counter::InputWaitSeconds, 90);
+        stats.since = Some(1_704_096_000);
+        let parsed = parse(&format(&stats));
+        assert_eq!(parsed, stats);
+        assert_eq!(parsed.average_response(), Some(45));
+        assert_eq!(Stats::default().average_response(), None);
+
+        let parsed = parse("commits\t2\nexplosions\t9\npushes\tmany\n");
+        assert_eq!(parsed.get(Counter::Commits), 2);
+        assert_eq!(parsed.get(Counter::Pushes), 0);
+        assert_eq!(parsed.since, None);
+    }
+
+    #[test]
+    fn test_input_watch() {
+        use ClaudeCodeStatus::*;
+        let mut watch = InputWatch::default();
+        let counts = watch.observe(
+            &[
+                test_instance("%1", WaitingInput),
+                test_instance("%2", Working),
+            ],
+            100,
+        );
+        assert_eq!(counts, vec![(Counter::InputRequests, 1)]);
+
+        // Still waiting, then a failed capture: nothing new
+        assert!(watch
+            .observe(&[test_instance("%1", WaitingInput)], 110)
+            .is_empty());
+        assert!(watch
+            .observe(&[test_instance("%1", Unknown)], 115)
+            .is_empty());
+
+        let counts = watch.observe(&[test_instance("%1", Working)], 130);
+        assert_eq!(
+            counts,
+            vec![
+                (Counter::InputResponses, 1),
+                (Counter::InputWaitSeconds, 30)
+            ]
+        );
+
+        // Closed or crashed while waiting: not an answer
+        watch.observe(&[test_instance("%1", WaitingInput)], 140);
+        assert!(watch.observe(&[], 150).is_empty());
+        watch.observe(&[test_instance("%1", WaitingInput)], 160);
+        assert!(watch
+            .observe(&[test_instance("%1", Crashed)], 170)
+            .is_empty());
+        assert!(watch.waiting_since.is_empty());
+    }
+}
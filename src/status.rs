@@ -0,0 +1,148 @@
+//! Status for the tmux status line (`claude-tmux status`)
+//!
+//! Prints one short line, meant for `status-right` or `status-left`:
+//!
+//! ```text
+//! set -g status-right '#(claude-tmux status --pane #{pane_id})'
+//! ```
+//!
+//! `--pane <id>` gives the status of the agent in a pane, with its branch,
+//! e.g. `● working main ↑2`, and nothing when no agent runs there. `--all`
+//! counts the agents of every session by status, e.g. `◐1 ●2 ○3`, those
+//! waiting for input first.
+
+use anyhow::Result;
+
+use crate::config;
+use crate::git::GitContext;
+use crate::session::{ClaudeCodeStatus, ClaudeInstance};
+use crate::tmux::Tmux;
+
+/// Order of the counts printed by `--all`: what needs attention first
+const SUMMARY_ORDER: [ClaudeCodeStatus; 5] = [
+    ClaudeCodeStatus::WaitingInput,
+    ClaudeCodeStatus::Crashed,
+    ClaudeCodeStatus::Working,
+    ClaudeCodeStatus::Idle,
+    ClaudeCodeStatus::Unknown,
+];
+
+/// What to print
+enum Scope {
+    /// The agent in this pane (`%id` or `session:window.pane`)
+    Pane(String),
+    /// Every agent
+    All,
+}
+
+/// Print the status line; `args` are the arguments after `status`
+pub fn run(args: &[String]) -> Result<()> {
+    let scope = match args {
+        [flag, pane] if flag == "--pane" => Scope::Pane(pane.clone()),
+        [flag] if flag == "--all" => Scope::All,
+        _ => anyhow::bail!("Usage: claude-tmux status --pane <id> | --all"),
+    };
+
+    // A broken config must not leave the status line empty
+    let config = config::load_for_subcommand("status");
+    let ascii = config.ascii_glyphs();
+
+    // Listing panes is cheap; only the panes shown are captured
+    let mut instances = Tmux::list_claude_panes()?;
+    if let Scope::Pane(pane) = &scope {
+        instances.retain(|i| &i.pane_id == pane || &i.tmux_target() == pane);
+    }
+    detect_statuses(&mut instances);
+
+    let line = match scope {
+        Scope::Pane(_) => match instances.first_mut() {
+            Some(instance) => {
+                instance.git_context = GitContext::detect(&instance.working_directory);
+                instance.status_line(ascii)
+            }
+            None => String::new(),
+        },
+        Scope::All => summary(&instances, ascii),
+    };
+    println!("{}", line);
+    Ok(())
+}
+
+/// Capture the running instances to detect their status
+pub fn detect_statuses(instances: &mut [ClaudeInstance]) {
+    let running: Vec<String> = instances
+        .iter()
+        .filter(|i| i.status != ClaudeCodeStatus::Crashed)
+        .map(|i| i.pane_id.clone())
+        .collect();
+    let statuses = Tmux::capture_statuses(&running);
+    for instance in instances {
+        if let Some(status) = statuses.get(&instance.pane_id) {
+            instance.status = *status;
+        }
+    }
+}
+
+/// Count of instances per status, e.g. "◐1 ●2 ○3", skipping empty ones
+fn summary(instances: &[ClaudeInstance], ascii: bool) -> String {
+    SUMMARY_ORDER
+        .iter()
+        .filter_map(|status| {
+            let count = instances.iter().filter(|i| i.status == *status).count();
+            let symbol = if ascii {
+                status.ascii_symbol()
+            } else {
+                status.symbol()
+            };
+            (count > 0).then(|| format!("{}{}", symbol, count))
+        })
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::session::test_instance;
+
+    #[test]
+    fn test_summary() {
+        let instances = [
+            test_instance("%3", ClaudeCodeStatus::Idle),
+            test_instance("%3", ClaudeCodeStatus::Working),
+            test_instance("%3", ClaudeCodeStatus::WaitingInput),
+            test_instance("%3", ClaudeCodeStatus::Working),
+        ];
+        assert_eq!(summary(&instances, false), "◐1 ●2 ○1");
+        assert_eq!(summary(&instances, true), "!1 *2 o1");
+        assert_eq!(summary(&[], false), "");
+    }
+
+    #[test]
+    fn test_status_line() {
+        let mut instance = test_instance("%3", ClaudeCodeStatus::Working);
+        assert_eq!(instance.status_line(false), "● working");
+
+        instance.git_context = Some(GitContext {
+            branch: "main".to_string(),
+            has_staged: false,
+            has_unstaged: false,
+            is_worktree: false,
+            main_repo_path: None,
+            has_upstream: true,
+            has_remote: true,
+            ahead: 2,
+            behind: 1,
+            default_branch: Some("main".to_string()),
+            ahead_of_default: 0,
+            stash_count: 0,
+            last_fetched: None,
+            auto_fetch: None,
+            last_commit: None,
+            in_progress: None,
+            has_conflicts: false,
+        });
+        assert_eq!(instance.status_line(false), "● working main ↑2 ↓1");
+        assert_eq!(instance.status_line(true), "* working main ^2 v1");
+    }
+}
@@ -0,0 +1,139 @@
+//! Files in the data directory
+//!
+//! Schedule, statistics, archive, tags and the other small stores are
+//! tab-separated files in the data directory. They are replaced whole: the
+//! content is written to a temporary file named after the process, then
+//! renamed over the store so readers never see a partial file. Stores that
+//! both the TUI and the daemon update take a lock file around the
+//! read-modify-write so neither loses the other's changes.
+
+use std::fs::{self, File};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use anyhow::{Context, Result};
+use chrono::{Local, Offset, TimeZone};
+
+/// Directory holding claude-tmux runtime data (schedule, daemon pid)
+pub fn data_dir() -> PathBuf {
+    dirs::data_local_dir()
+        .or_else(dirs::home_dir)
+        .unwrap_or_else(std::env::temp_dir)
+        .join("claude-tmux")
+}
+
+/// Current unix time in seconds
+pub fn now_unix() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0)
+}
+
+/// Offset of local time from UTC at the unix timestamp `unix`, in seconds
+///
+/// Looked up for each timestamp, so times on either side of a daylight
+/// saving change get their own offset.
+pub fn local_utc_offset(unix: u64) -> i64 {
+    Local
+        .timestamp_opt(unix as i64, 0)
+        .single()
+        .map(|time| time.offset().fix().local_minus_utc() as i64)
+        .unwrap_or(0)
+}
+
+/// Replace the file at `path` with `content`, creating the data directory
+///
+/// `what` names the store in errors ("Failed to write schedule").
+pub fn write_atomic(path: &Path, content: &str, what: &str) -> Result<()> {
+    static WRITES: AtomicU64 = AtomicU64::new(0);
+
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent).context("Failed to create data directory")?;
+    }
+    // Unique per process and per write, so concurrent writers never share it
+    let mut tmp = path.as_os_str().to_owned();
+    tmp.push(format!(
+        ".{}.{}.tmp",
+        std::process::id(),
+        WRITES.fetch_add(1, Ordering::Relaxed)
+    ));
+    let tmp = PathBuf::from(tmp);
+    if let Err(e) = fs::write(&tmp, content) {
+        let _ = fs::remove_file(&tmp);
+        return Err(e).with_context(|| format!("Failed to write {}", what));
+    }
+    fs::rename(&tmp, path).with_context(|| format!("Failed to replace {}", what))
+}
+
+/// Hold the lock of the store at `path` until the returned file is dropped
+///
+/// Blocks while another process holds it. The lock lives in a separate
+/// `.lock` file, as the store itself is replaced on every write.
+pub fn lock(path: &Path) -> Result<File> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent).context("Failed to create data directory")?;
+    }
+    let file = File::create(path.with_extension("lock")).context("Failed to open lock file")?;
+    file.lock().context("Failed to lock")?;
+    Ok(file)
+}
+
+/// Escape tabs, newlines and backslashes so a field fits on one TSV line
+pub fn escape_field(s: &str) -> String {
+    s.replace('\\', "\\\\")
+        .replace('\t', "\\t")
+        .replace('\n', "\\n")
+}
+
+pub fn unescape_field(s: &str) -> String {
+    let mut out = String::with_capacity(s.len());
+    let mut chars = s.chars();
+    while let Some(c) = chars.next() {
+        if c == '\\' {
+            match chars.next() {
+                Some('t') => out.push('\t'),
+                Some('n') => out.push('\n'),
+                Some(other) => out.push(other),
+                None => out.push('\\'),
+            }
+        } else {
+            out.push(c);
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_write_atomic_and_lock() {
+        let dir = std::env::temp_dir().join(format!("claude-tmux-store-{}", std::process::id()));
+        let path = dir.join("store.tsv");
+        let lock = lock(&path).unwrap();
+        write_atomic(&path, "one\n", "store").unwrap();
+        write_atomic(&path, "two\n", "store").unwrap();
+        drop(lock);
+        let content = fs::read_to_string(&path).unwrap();
+        let mut files: Vec<_> = fs::read_dir(&dir)
+            .unwrap()
+            .map(|e| e.unwrap().file_name().into_string().unwrap())
+            .collect();
+        files.sort();
+        let _ = fs::remove_dir_all(&dir);
+
+        assert_eq!(content, "two\n");
+        // No temporary file is left behind
+        assert_eq!(files, ["store.lock", "store.tsv"]);
+    }
+
+    #[test]
+    fn test_escape_field_roundtrip() {
+        let field = "a\tb\nc \\o/";
+        assert!(!escape_field(field).contains(['\t', '\n']));
+        assert_eq!(unescape_field(&escape_field(field)), field);
+    }
+}
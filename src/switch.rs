@@ -0,0 +1,45 @@
+//! Switching from the command line (`claude-tmux switch <target> | --last`)
+//!
+//! `--last` goes back to the instance switched away from last, as the TUI's
+//! switch back key does, so it can be bound in tmux without opening the TUI:
+//!
+//! ```text
+//! bind-key L run-shell "claude-tmux switch --last"
+//! ```
+//!
+//! Switching records the pane being left, so running it again toggles
+//! between the two instances.
+
+use anyhow::{Context, Result};
+
+use crate::config;
+use crate::tmux::Tmux;
+use crate::ui_state;
+
+/// Switch to an instance; `args` are the arguments after `switch`
+pub fn run(args: &[String]) -> Result<()> {
+    let mut state = ui_state::load();
+    let target = match args {
+        [flag] if flag == "--last" => state
+            .previous
+            .clone()
+            .context("No previous instance to switch back to")?,
+        [target] if !target.starts_with('-') => target.clone(),
+        _ => anyhow::bail!("Usage: claude-tmux switch <target> | --last"),
+    };
+
+    config::load_for_subcommand("switch");
+
+    let current = Tmux::current_pane().ok().flatten();
+    if current.as_ref().is_some_and(|c| *c != target) {
+        state.previous = current;
+    }
+
+    if Tmux::inside_tmux() {
+        Tmux::switch_to_pane(&target)?;
+        ui_state::save(&state)
+    } else {
+        ui_state::save(&state)?;
+        Tmux::attach_to_pane(&target)
+    }
+}
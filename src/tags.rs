@@ -0,0 +1,97 @@
+//! Tags assigned to instances
+//!
+//! Tags ("frontend", "urgent") group instances independently of session
+//! names. They are stored by working directory, so they survive tmux
+//! restarts and renamed sessions, and are shared by the agents of a checkout.
+
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use anyhow::Result;
+
+use crate::store::{self, data_dir, escape_field, unescape_field};
+
+/// Tags of each tagged working directory
+pub type Tags = HashMap<PathBuf, Vec<String>>;
+
+fn tags_path() -> PathBuf {
+    data_dir().join("tags.tsv")
+}
+
+/// Load the saved tags (empty if none)
+pub fn load() -> Tags {
+    let Ok(content) = fs::read_to_string(tags_path()) else {
+        return Tags::new();
+    };
+    content.lines().filter_map(parse_line).collect()
+}
+
+/// Save the tags, replacing the file
+pub fn save(tags: &Tags) -> Result<()> {
+    let mut entries: Vec<_> = tags.iter().filter(|(_, t)| !t.is_empty()).collect();
+    entries.sort();
+    let content: String = entries
+        .into_iter()
+        .map(|(path, tags)| format_line(path, tags))
+        .collect();
+
+    store::write_atomic(&tags_path(), &content, "tags")
+}
+
+/// Split typed tags on commas and whitespace, dropping a leading `#` and
+/// duplicates
+pub fn parse_tags(input: &str) -> Vec<String> {
+    let mut tags: Vec<String> = Vec::new();
+    for tag in input
+        .split(|c: char| c == ',' || c.is_whitespace())
+        .map(|t| t.trim_start_matches('#'))
+        .filter(|t| !t.is_empty())
+    {
+        if !tags.iter().any(|t| t == tag) {
+            tags.push(tag.to_string());
+        }
+    }
+    tags
+}
+
+fn format_line(path: &Path, tags: &[String]) -> String {
+    format!(
+        "{}\t{}\n",
+        escape_field(&path.to_string_lossy()),
+        tags.join(" ")
+    )
+}
+
+fn parse_line(line: &str) -> Option<(PathBuf, Vec<String>)> {
+    let (path, tags) = line.split_once('\t')?;
+    let tags = parse_tags(tags);
+    if tags.is_empty() {
+        return None;
+    }
+    Some((PathBuf::from(unescape_field(path)), tags))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_tags() {
+        assert_eq!(
+            parse_tags("frontend, #urgent  frontend,,api"),
+            vec!["frontend", "urgent", "api"]
+        );
+        assert!(parse_tags(" , # ").is_empty());
+    }
+
+    #[test]
+    fn test_line_round_trip() {
+        let path = PathBuf::from("/home/me/my\tapp");
+        let tags = vec!["frontend".to_string(), "urgent".to_string()];
+        let line = format_line(&path, &tags);
+
+        assert_eq!(parse_line(line.trim_end()), Some((path, tags)));
+        assert_eq!(parse_line("/home/me/app\t"), None);
+    }
+}
@@ -0,0 +1,172 @@
+//! Fleet state in the terminal title (`terminal_title = true`)
+//!
+//! While the TUI runs, the title of its terminal tab says how many agents
+//! wait for input, crashed or work, e.g. `claude-tmux: 2 awaiting input`,
+//! so the tab shows it even when another one is in front. Terminals that
+//! support the OSC 9;4 progress indicator (Windows Terminal, ConEmu, iTerm2,
+//! Ghostty) also show a paused state while agents wait, an error when one
+//! crashed, and a busy one while any works. Inside tmux the title is the
+//! pane's, shown by the outer terminal with `set -g set-titles on`.
+//!
+//! The previous title is saved with the xterm title stack and restored
+//! when the TUI exits or the setting is turned off.
+
+use std::io::{self, Write};
+
+use crate::i18n::trf;
+use crate::session::{ClaudeCodeStatus, ClaudeInstance};
+
+/// What the progress indicator shows
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Progress {
+    /// No indicator
+    Hidden,
+    /// An agent works
+    Busy,
+    /// An agent waits for input
+    Paused,
+    /// An agent crashed
+    Error,
+}
+
+impl Progress {
+    /// OSC 9;4 sequence showing it
+    fn sequence(self) -> &'static str {
+        match self {
+            Self::Hidden => "\x1b]9;4;0;0\x07",
+            Self::Busy => "\x1b]9;4;3;0\x07",
+            Self::Paused => "\x1b]9;4;4;100\x07",
+            Self::Error => "\x1b]9;4;2;100\x07",
+        }
+    }
+}
+
+/// Title and progress currently shown
+#[derive(Debug, Default)]
+pub struct TerminalTitle {
+    /// What was written last, `None` while the previous title is shown
+    shown: Option<(String, Progress)>,
+}
+
+impl TerminalTitle {
+    /// Show the state of `instances`, writing only when it changed
+    pub fn update(&mut self, out: &mut impl Write, instances: &[ClaudeInstance]) -> io::Result<()> {
+        let state = (title(instances), progress(instances));
+        if self.shown.as_ref() == Some(&state) {
+            return Ok(());
+        }
+        if self.shown.is_none() {
+            // Save the title to restore
+            write!(out, "\x1b[22;0t")?;
+        }
+        write!(out, "\x1b]0;{}\x07{}", state.0, state.1.sequence())?;
+        out.flush()?;
+        self.shown = Some(state);
+        Ok(())
+    }
+
+    /// Hide the progress indicator and restore the previous title
+    pub fn restore(&mut self, out: &mut impl Write) -> io::Result<()> {
+        if self.shown.take().is_some() {
+            write!(out, "{}\x1b[23;0t", Progress::Hidden.sequence())?;
+            out.flush()?;
+        }
+        Ok(())
+    }
+}
+
+/// Title for `instances`, e.g. "claude-tmux: 2 awaiting input, 1 working"
+fn title(instances: &[ClaudeInstance]) -> String {
+    let count = |status| instances.iter().filter(|i| i.status == status).count();
+    let (waiting, crashed, working) = (
+        count(ClaudeCodeStatus::WaitingInput),
+        count(ClaudeCodeStatus::Crashed),
+        count(ClaudeCodeStatus::Working),
+    );
+
+    let mut parts = Vec::new();
+    if waiting > 0 {
+        parts.push(trf!("{} awaiting input", waiting));
+    }
+    if crashed > 0 {
+        parts.push(trf!("{} crashed", crashed));
+    }
+    if working > 0 {
+        parts.push(trf!("{} working", working));
+    }
+    if parts.is_empty() {
+        "claude-tmux".to_string()
+    } else {
+        format!("claude-tmux: {}", parts.join(", "))
+    }
+}
+
+/// Progress for `instances`: what needs attention first
+fn progress(instances: &[ClaudeInstance]) -> Progress {
+    let any = |status| instances.iter().any(|i| i.status == status);
+    if any(ClaudeCodeStatus::Crashed) {
+        Progress::Error
+    } else if any(ClaudeCodeStatus::WaitingInput) {
+        Progress::Paused
+    } else if any(ClaudeCodeStatus::Working) {
+        Progress::Busy
+    } else {
+        Progress::Hidden
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::session::test_instance;
+
+    #[test]
+    fn test_title() {
+        assert_eq!(title(&[]), "claude-tmux");
+        assert_eq!(
+            title(&[test_instance("%3", ClaudeCodeStatus::Idle)]),
+            "claude-tmux"
+        );
+        let instances = [
+            test_instance("%3", ClaudeCodeStatus::Working),
+            test_instance("%3", ClaudeCodeStatus::WaitingInput),
+            test_instance("%3", ClaudeCodeStatus::WaitingInput),
+        ];
+        assert_eq!(
+            title(&instances),
+            "claude-tmux: 2 awaiting input, 1 working"
+        );
+        assert_eq!(progress(&instances), Progress::Paused);
+        assert_eq!(
+            progress(&[test_instance("%3", ClaudeCodeStatus::Crashed)]),
+            Progress::Error
+        );
+        assert_eq!(
+            progress(&[test_instance("%3", ClaudeCodeStatus::Idle)]),
+            Progress::Hidden
+        );
+    }
+
+    #[test]
+    fn test_update() {
+        let mut title = TerminalTitle::default();
+        let mut out = Vec::new();
+        let working = [test_instance("%3", ClaudeCodeStatus::Working)];
+        title.update(&mut out, &working).unwrap();
+        assert_eq!(
+            String::from_utf8_lossy(&out),
+            "\x1b[22;0t\x1b]0;claude-tmux: 1 working\x07\x1b]9;4;3;0\x07"
+        );
+
+        // Unchanged: nothing written
+        out.clear();
+        title.update(&mut out, &working).unwrap();
+        assert!(out.is_empty());
+
+        title.restore(&mut out).unwrap();
+        assert_eq!(String::from_utf8_lossy(&out), "\x1b]9;4;0;0\x07\x1b[23;0t");
+        out.clear();
+        title.restore(&mut out).unwrap();
+        assert!(out.is_empty());
+    }
+}
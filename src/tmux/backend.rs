@@ -0,0 +1,85 @@
+//! Where tmux commands are executed
+//!
+//! Every tmux command goes through a [`TmuxBackend`]. The system backend
+//! runs the real `tmux`, over the control channel when possible; tests swap
+//! in a [`MockTmux`](super::mock::MockTmux) for the current thread, so that
+//! the app and its rendering can be exercised without a tmux server.
+
+use std::cell::RefCell;
+use std::process::Command;
+use std::sync::Arc;
+
+use anyhow::{Context, Result};
+
+use super::control::{self, CommandOutput};
+
+/// Executes tmux commands
+pub trait TmuxBackend: Send + Sync {
+    /// Run a command, over the control channel when possible
+    fn run(&self, args: &[&str]) -> Result<CommandOutput>;
+
+    /// Run a command in its own process, writing `input` to its stdin
+    ///
+    /// Needed by commands that act on "the current client", which the
+    /// control client must not stand in for.
+    fn spawn(&self, args: &[&str], input: Option<&[u8]>) -> Result<CommandOutput>;
+
+    /// Run a command on this terminal, returning whether it succeeded
+    ///
+    /// Used by `attach-session`, which blocks until the client detaches,
+    /// and `display-popup -E`, until the popup closes.
+    fn run_in_terminal(&self, args: &[&str]) -> Result<bool>;
+}
+
+/// The real tmux
+pub struct SystemTmux;
+
+impl TmuxBackend for SystemTmux {
+    fn run(&self, args: &[&str]) -> Result<CommandOutput> {
+        control::execute(args)
+    }
+
+    fn spawn(&self, args: &[&str], input: Option<&[u8]>) -> Result<CommandOutput> {
+        control::spawn_process(args, input)
+    }
+
+    fn run_in_terminal(&self, args: &[&str]) -> Result<bool> {
+        let status = Command::new("tmux")
+            .args(args)
+            .status()
+            .with_context(|| format!("Failed to execute tmux {}", args.first().unwrap_or(&"")))?;
+        Ok(status.success())
+    }
+}
+
+thread_local! {
+    /// Backend replacing the system one on this thread (see `set_thread_backend`)
+    static THREAD_BACKEND: RefCell<Option<Arc<dyn TmuxBackend>>> = const { RefCell::new(None) };
+}
+
+/// Backend for commands issued from the current thread
+pub fn current() -> Arc<dyn TmuxBackend> {
+    THREAD_BACKEND
+        .with(|backend| backend.borrow().clone())
+        .unwrap_or_else(|| Arc::new(SystemTmux))
+}
+
+/// Send the tmux commands of the current thread to `backend`, or back to
+/// the system tmux with `None`
+///
+/// Threads spawned afterwards still use the system tmux, except those
+/// started with [`spawn_worker`].
+#[cfg(test)]
+pub fn set_thread_backend(backend: Option<Arc<dyn TmuxBackend>>) {
+    THREAD_BACKEND.with(|current| *current.borrow_mut() = backend);
+}
+
+/// Run `job` on a new thread whose tmux commands go to the current
+/// thread's backend
+pub fn spawn_worker(job: impl FnOnce() + Send + 'static) {
+    let backend = THREAD_BACKEND.with(|backend| backend.borrow().clone());
+    std::thread::spawn(move || {
+        THREAD_BACKEND.with(|current| *current.borrow_mut() = backend);
+        job();
+    });
+}
@@ -0,0 +1,228 @@
+//! Bounded, deduplicated pane captures for status detection
+//!
+//! A refresh with many Claude panes would otherwise run one `capture-pane`
+//! per pane back to back, or (with the control channel disabled) spawn a
+//! tmux process for each. Captures go through a small pool instead: at most
+//! `MAX_CONCURRENT_CAPTURES` run at once across all threads, and a pane that
+//! is already being captured is not captured again; later callers wait for
+//! and share the in-flight result.
+
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Condvar, Mutex, OnceLock};
+use std::thread;
+
+use crate::detection::{detect_status, status_reason};
+use crate::session::ClaudeCodeStatus;
+
+use super::Tmux;
+
+/// Captures allowed to run at the same time
+const MAX_CONCURRENT_CAPTURES: usize = 4;
+
+/// Non-empty lines captured for status detection
+const STATUS_LINES: usize = 15;
+
+static POOL: OnceLock<CapturePool<Option<ClaudeCodeStatus>>> = OnceLock::new();
+
+/// Detect the status of each pane, capturing through the shared pool
+///
+/// Duplicate IDs are captured once. Panes that cannot be captured, as when
+/// tmux times out, are left out, so that callers keep their last status.
+pub fn capture_statuses(pane_ids: &[String]) -> HashMap<String, ClaudeCodeStatus> {
+    let pool = POOL.get_or_init(|| CapturePool::new(MAX_CONCURRENT_CAPTURES));
+    let statuses = pool.run_all(pane_ids, |pane_id| {
+        match Tmux::capture_pane(pane_id, STATUS_LINES, true) {
+            Ok(content) => {
+                let status = detect_status(&content);
+                tracing::debug!(
+                    "{}: {} ({})",
+                    pane_id,
+                    status.label(),
+                    status_reason(&content)
+                );
+                tracing::trace!("{} capture:\n{}", pane_id, content);
+                Some(status)
+            }
+            Err(e) => {
+                tracing::debug!("{}: capture failed: {}", pane_id, e);
+                None
+            }
+        }
+    });
+    statuses
+        .into_iter()
+        .filter_map(|(pane_id, status)| Some((pane_id, status?)))
+        .collect()
+}
+
+/// Result slot shared by everyone waiting on the same pane
+struct Pending<T> {
+    result: Mutex<Option<T>>,
+    done: Condvar,
+}
+
+/// Runs keyed jobs with a concurrency limit and per-key deduplication
+struct CapturePool<T> {
+    limit: usize,
+    running: Mutex<usize>,
+    slot_freed: Condvar,
+    in_flight: Mutex<HashMap<String, Arc<Pending<T>>>>,
+}
+
+impl<T: Clone + Send + Sync> CapturePool<T> {
+    fn new(limit: usize) -> Self {
+        Self {
+            limit: limit.max(1),
+            running: Mutex::new(0),
+            slot_freed: Condvar::new(),
+            in_flight: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Run `job` for each distinct key on up to `limit` worker threads
+    fn run_all<F>(&self, keys: &[String], job: F) -> HashMap<String, T>
+    where
+        F: Fn(&str) -> T + Sync,
+    {
+        let mut unique: Vec<&str> = keys.iter().map(String::as_str).collect();
+        unique.sort_unstable();
+        unique.dedup();
+
+        let next = AtomicUsize::new(0);
+        let results = Mutex::new(HashMap::with_capacity(unique.len()));
+        let workers = self.limit.min(unique.len());
+
+        thread::scope(|scope| {
+            for _ in 0..workers {
+                scope.spawn(|| {
+                    while let Some(key) = unique.get(next.fetch_add(1, Ordering::Relaxed)) {
+                        let value = self.run(key, || job(key));
+                        lock(&results).insert(key.to_string(), value);
+                    }
+                });
+            }
+        });
+
+        results.into_inner().unwrap_or_else(|e| e.into_inner())
+    }
+
+    /// Run `job` for `key`, or wait for the run already in flight
+    fn run(&self, key: &str, job: impl FnOnce() -> T) -> T {
+        let (pending, owner) = {
+            let mut in_flight = lock(&self.in_flight);
+            match in_flight.get(key) {
+                Some(pending) => (Arc::clone(pending), false),
+                None => {
+                    let pending = Arc::new(Pending {
+                        result: Mutex::new(None),
+                        done: Condvar::new(),
+                    });
+                    in_flight.insert(key.to_string(), Arc::clone(&pending));
+                    (pending, true)
+                }
+            }
+        };
+
+        if !owner {
+            let mut result = lock(&pending.result);
+            loop {
+                if let Some(value) = result.as_ref() {
+                    return value.clone();
+                }
+                result = pending.done.wait(result).unwrap_or_else(|e| e.into_inner());
+            }
+        }
+
+        let value = {
+            let _slot = self.acquire_slot();
+            job()
+        };
+
+        *lock(&pending.result) = Some(value.clone());
+        pending.done.notify_all();
+        lock(&self.in_flight).remove(key);
+        value
+    }
+
+    fn acquire_slot(&self) -> SlotGuard<'_, T> {
+        let mut running = lock(&self.running);
+        while *running >= self.limit {
+            running = self
+                .slot_freed
+                .wait(running)
+                .unwrap_or_else(|e| e.into_inner());
+        }
+        *running += 1;
+        SlotGuard { pool: self }
+    }
+}
+
+/// Releases a pool slot when dropped
+struct SlotGuard<'a, T> {
+    pool: &'a CapturePool<T>,
+}
+
+impl<T> Drop for SlotGuard<'_, T> {
+    fn drop(&mut self) {
+        *lock(&self.pool.running) -= 1;
+        self.pool.slot_freed.notify_one();
+    }
+}
+
+fn lock<T>(mutex: &Mutex<T>) -> std::sync::MutexGuard<'_, T> {
+    mutex.lock().unwrap_or_else(|e| e.into_inner())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    #[test]
+    fn test_limit_and_dedup() {
+        let pool = CapturePool::new(2);
+        let calls = AtomicUsize::new(0);
+        let active = AtomicUsize::new(0);
+        let peak = AtomicUsize::new(0);
+
+        let keys: Vec<String> = ["%1", "%2", "%3", "%1", "%4", "%5", "%2"]
+            .iter()
+            .map(|k| k.to_string())
+            .collect();
+        let results = pool.run_all(&keys, |key| {
+            calls.fetch_add(1, Ordering::SeqCst);
+            let now = active.fetch_add(1, Ordering::SeqCst) + 1;
+            peak.fetch_max(now, Ordering::SeqCst);
+            thread::sleep(Duration::from_millis(20));
+            active.fetch_sub(1, Ordering::SeqCst);
+            format!("captured {}", key)
+        });
+
+        assert_eq!(calls.load(Ordering::SeqCst), 5);
+        assert!(peak.load(Ordering::SeqCst) <= 2);
+        assert_eq!(results.len(), 5);
+        assert_eq!(results["%3"], "captured %3");
+    }
+
+    #[test]
+    fn test_concurrent_callers_share_capture() {
+        let pool = CapturePool::new(4);
+        let calls = AtomicUsize::new(0);
+        let job = || {
+            calls.fetch_add(1, Ordering::SeqCst);
+            thread::sleep(Duration::from_millis(50));
+            7
+        };
+
+        let results: Vec<i32> = thread::scope(|scope| {
+            let handles: Vec<_> = (0..3)
+                .map(|_| scope.spawn(|| pool.run("%1", job)))
+                .collect();
+            handles.into_iter().map(|h| h.join().unwrap()).collect()
+        });
+
+        assert_eq!(results, vec![7, 7, 7]);
+        assert_eq!(calls.load(Ordering::SeqCst), 1);
+    }
+}
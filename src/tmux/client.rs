@@ -0,0 +1,122 @@
+//! Resolving which tmux client to switch
+//!
+//! `switch-client` without `-c` acts on "the current client", which tmux
+//! guesses when the command does not come from a client (as with a spawned
+//! `tmux` process): usually the most recently active client of the server.
+//! With several clients attached (another terminal, an outer tmux whose pane
+//! runs `ssh host tmux attach`), that guess can move a different terminal
+//! than the one showing this TUI.
+//!
+//! The server is the one named by `$TMUX`, which the innermost tmux sets for
+//! the processes it runs, so commands already reach the right layer. The
+//! client is resolved explicitly: the one attached to the session this TUI
+//! runs in (from `$TMUX_PANE`, or the session field of `$TMUX` in a popup),
+//! preferring the most recently active one. Control-mode clients, including
+//! our own control channel, are never picked.
+
+use super::control;
+
+/// A client line from `list-clients`
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct ClientInfo {
+    name: String,
+    session_id: String,
+    activity: u64,
+    control_mode: bool,
+}
+
+const LIST_CLIENTS_FORMAT: &str =
+    "#{client_name}\t#{session_id}\t#{client_activity}\t#{client_control_mode}";
+
+/// Whether this process runs inside tmux (`$TMUX` is set)
+pub fn inside_tmux() -> bool {
+    std::env::var("TMUX").is_ok_and(|value| !value.is_empty())
+}
+
+/// Name of the client displaying this process, if it can be determined
+pub fn own_client() -> Option<String> {
+    let session_id = own_session_id()?;
+    let output = control::run(&["list-clients", "-F", LIST_CLIENTS_FORMAT]).ok()?;
+    if !output.success {
+        return None;
+    }
+    let clients: Vec<ClientInfo> = output.stdout.lines().filter_map(parse_client).collect();
+    pick_client(&clients, &session_id)
+}
+
+/// Active pane of the client displaying this process
+pub fn own_client_pane() -> Option<String> {
+    let client = own_client()?;
+    let output = control::run(&["display-message", "-p", "-c", &client, "#{pane_id}"]).ok()?;
+    let pane = output.stdout.trim();
+    (output.success && !pane.is_empty()).then(|| pane.to_string())
+}
+
+/// ID (`$N`) of the session this process runs in
+fn own_session_id() -> Option<String> {
+    if let Ok(pane) = std::env::var("TMUX_PANE") {
+        let output = control::run(&["display-message", "-p", "-t", &pane, "#{session_id}"]).ok()?;
+        let id = output.stdout.trim();
+        if output.success && !id.is_empty() {
+            return Some(id.to_string());
+        }
+    }
+    std::env::var("TMUX")
+        .ok()
+        .and_then(|v| session_from_env(&v))
+}
+
+/// Session ID from a `$TMUX` value (`socket,server pid,session number`)
+fn session_from_env(value: &str) -> Option<String> {
+    let number = value.rsplit(',').next()?.trim();
+    number.parse::<u32>().ok().map(|n| format!("${}", n))
+}
+
+fn parse_client(line: &str) -> Option<ClientInfo> {
+    let mut parts = line.split('\t');
+    Some(ClientInfo {
+        name: parts.next()?.to_string(),
+        session_id: parts.next()?.to_string(),
+        activity: parts.next()?.parse().unwrap_or(0),
+        control_mode: parts.next()? == "1",
+    })
+}
+
+/// The most recently active non-control client attached to `session_id`
+fn pick_client(clients: &[ClientInfo], session_id: &str) -> Option<String> {
+    clients
+        .iter()
+        .filter(|c| !c.control_mode && c.session_id == session_id)
+        .max_by_key(|c| c.activity)
+        .map(|c| c.name.clone())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_pick_client() {
+        let clients: Vec<ClientInfo> = [
+            "/dev/pts/1\t$0\t100\t0",
+            "/dev/pts/4\t$2\t300\t0",
+            "client-99\t$2\t900\t1",
+            "/dev/pts/7\t$2\t200\t0",
+            "garbage",
+        ]
+        .iter()
+        .filter_map(|line| parse_client(line))
+        .collect();
+
+        assert_eq!(clients.len(), 4);
+        assert_eq!(pick_client(&clients, "$2").as_deref(), Some("/dev/pts/4"));
+        assert_eq!(pick_client(&clients, "$0").as_deref(), Some("/dev/pts/1"));
+        assert_eq!(pick_client(&clients, "$5"), None);
+
+        assert_eq!(
+            session_from_env("/tmp/tmux-1000/default,4242,2").as_deref(),
+            Some("$2")
+        );
+        assert_eq!(session_from_env(""), None);
+    }
+}
@@ -0,0 +1,405 @@
+//! Persistent tmux control-mode connection
+//!
+//! Spawning a `tmux` process per command dominates refresh time with many
+//! panes. Instead, one `tmux -C` client is kept attached and commands are
+//! written to its stdin; each reply arrives as a `%begin` ... `%end` (or
+//! `%error`) block on stdout.
+//!
+//! The control client attaches with `no-output,ignore-size` so it neither
+//! receives pane output nor affects window sizes. Commands that act on "the
+//! current client" (switch-client, display-message) must not go through it.
+//!
+//! Every command is bounded by `COMMAND_TIMEOUT`, whether it goes over the
+//! channel or runs as its own process, so a wedged server or a slow capture
+//! surfaces as an error instead of freezing the UI. Output is read on
+//! separate threads while waiting; a timed-out process is killed, and a
+//! timed-out channel is dropped and reconnected on the next command.
+
+use std::io::{BufRead, BufReader, Read, Write};
+use std::process::{Child, ChildStdin, Command, Stdio};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc::{self, Receiver, RecvTimeoutError};
+use std::sync::{Mutex, OnceLock};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use anyhow::{Context, Result};
+
+use super::backend;
+
+/// Longest a single tmux command may take
+const COMMAND_TIMEOUT: Duration = Duration::from_secs(3);
+
+/// Interval between checks on a spawned tmux process
+const WAIT_INTERVAL: Duration = Duration::from_millis(5);
+
+/// Error returned when a tmux command exceeds `COMMAND_TIMEOUT`
+#[derive(Debug)]
+pub struct TimedOut {
+    command: String,
+    after: Duration,
+}
+
+impl std::fmt::Display for TimedOut {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "tmux {} timed out after {:.1}s",
+            self.command,
+            self.after.as_secs_f64()
+        )
+    }
+}
+
+impl std::error::Error for TimedOut {}
+
+/// Output of a tmux command, whether run over the channel or spawned
+#[derive(Debug, Clone, Default)]
+pub struct CommandOutput {
+    pub success: bool,
+    pub stdout: String,
+    pub stderr: String,
+}
+
+/// Whether commands may use the control channel (see `set_enabled`)
+static ENABLED: AtomicBool = AtomicBool::new(true);
+
+/// The shared connection, created on first use
+static CHANNEL: OnceLock<Mutex<Option<ControlChannel>>> = OnceLock::new();
+
+/// Enable or disable the control channel (`tmux_control_mode` in the config)
+pub fn set_enabled(enabled: bool) {
+    ENABLED.store(enabled, Ordering::Relaxed);
+    if !enabled {
+        if let Some(channel) = CHANNEL.get() {
+            *channel.lock().unwrap_or_else(|e| e.into_inner()) = None;
+        }
+    }
+}
+
+/// ID (`$N`) of the session the control client is attached to, if connected
+///
+/// That session's `session_attached` count includes the control client.
+pub fn attached_session_id() -> Option<String> {
+    let channel = CHANNEL.get()?.lock().ok()?;
+    channel.as_ref().map(|c| c.session_id.clone())
+}
+
+/// Run a tmux command, over the control channel when possible
+///
+/// Goes through the current [`TmuxBackend`](super::backend::TmuxBackend),
+/// which for the system tmux is `execute`.
+pub fn run(args: &[&str]) -> Result<CommandOutput> {
+    backend::current().run(args)
+}
+
+/// Run a tmux command in its own process
+pub fn spawn(args: &[&str]) -> Result<CommandOutput> {
+    spawn_with_input(args, None)
+}
+
+/// Run a tmux command in its own process, writing `input` to its stdin
+pub fn spawn_with_input(args: &[&str], input: Option<&[u8]>) -> Result<CommandOutput> {
+    backend::current().spawn(args, input)
+}
+
+/// Run a command on the system tmux, over the control channel when possible
+///
+/// Falls back to spawning `tmux` when the channel is disabled or cannot be
+/// established (no server, no session to attach to, tmux older than 3.2).
+/// A channel that breaks mid-command is dropped and reconnected next time.
+/// A command (or connection) that times out on the channel is not retried:
+/// the server is most likely stuck, and a spawned command would only wait
+/// again.
+pub(super) fn execute(args: &[&str]) -> Result<CommandOutput> {
+    if ENABLED.load(Ordering::Relaxed) {
+        let mut guard = CHANNEL
+            .get_or_init(|| Mutex::new(None))
+            .lock()
+            .unwrap_or_else(|e| e.into_inner());
+
+        if guard.is_none() {
+            match ControlChannel::connect() {
+                Ok(channel) => *guard = Some(channel),
+                Err(e) if e.is::<TimedOut>() => {
+                    tracing::warn!("control channel: {}", e);
+                    return Err(e);
+                }
+                Err(e) => tracing::debug!("control channel unavailable, spawning tmux: {}", e),
+            }
+        }
+        if let Some(channel) = guard.as_mut() {
+            match channel.execute(args) {
+                Ok(output) => return Ok(output),
+                Err(e) => {
+                    tracing::warn!("control channel dropped on {:?}: {}", args.first(), e);
+                    *guard = None;
+                    if e.is::<TimedOut>() {
+                        return Err(e);
+                    }
+                }
+            }
+        }
+    }
+
+    spawn_process(args, None)
+}
+
+/// Run a command on the system tmux in its own process, writing `input` to
+/// its stdin
+pub(super) fn spawn_process(args: &[&str], input: Option<&[u8]>) -> Result<CommandOutput> {
+    let mut command = Command::new("tmux");
+    command.args(args);
+    output_with_timeout(
+        command,
+        args.first().copied().unwrap_or(""),
+        input,
+        COMMAND_TIMEOUT,
+    )
+}
+
+/// Run a command to completion, killing it after `timeout`
+fn output_with_timeout(
+    mut command: Command,
+    name: &str,
+    input: Option<&[u8]>,
+    timeout: Duration,
+) -> Result<CommandOutput> {
+    let mut child = command
+        .stdin(if input.is_some() {
+            Stdio::piped()
+        } else {
+            Stdio::null()
+        })
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .with_context(|| format!("Failed to execute tmux {}", name))?;
+
+    let stdout = read_in_background(child.stdout.take());
+    let stderr = read_in_background(child.stderr.take());
+    if let (Some(input), Some(mut stdin)) = (input, child.stdin.take()) {
+        // Written from a thread too: a stuck server may never drain the pipe
+        let input = input.to_vec();
+        thread::spawn(move || {
+            let _ = stdin.write_all(&input);
+        });
+    }
+
+    let deadline = Instant::now() + timeout;
+    let status = loop {
+        if let Some(status) = child.try_wait()? {
+            break status;
+        }
+        if Instant::now() >= deadline {
+            let _ = child.kill();
+            let _ = child.wait();
+            return Err(TimedOut {
+                command: name.to_string(),
+                after: timeout,
+            }
+            .into());
+        }
+        thread::sleep(WAIT_INTERVAL);
+    };
+
+    Ok(CommandOutput {
+        success: status.success(),
+        stdout: stdout.join().unwrap_or_default(),
+        stderr: stderr.join().unwrap_or_default(),
+    })
+}
+
+/// Read a pipe to the end on its own thread
+fn read_in_background(pipe: Option<impl Read + Send + 'static>) -> thread::JoinHandle<String> {
+    thread::spawn(move || {
+        let mut bytes = Vec::new();
+        if let Some(mut pipe) = pipe {
+            let _ = pipe.read_to_end(&mut bytes);
+        }
+        String::from_utf8_lossy(&bytes).into_owned()
+    })
+}
+
+struct ControlChannel {
+    child: Child,
+    stdin: ChildStdin,
+    /// Raw output lines, read by a background thread
+    lines: Receiver<Vec<u8>>,
+    session_id: String,
+}
+
+impl ControlChannel {
+    fn connect() -> Result<Self> {
+        let mut child = Command::new("tmux")
+            .args(["-C", "attach-session", "-f", "no-output,ignore-size"])
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::null())
+            .spawn()
+            .context("Failed to start tmux control client")?;
+
+        let stdin = child.stdin.take().context("No stdin")?;
+        let mut stdout = BufReader::new(child.stdout.take().context("No stdout")?);
+        let (sender, lines) = mpsc::channel();
+        thread::spawn(move || loop {
+            let mut raw = Vec::new();
+            match stdout.read_until(b'\n', &mut raw) {
+                Ok(0) | Err(_) => break,
+                Ok(_) => {
+                    if sender.send(raw).is_err() {
+                        break;
+                    }
+                }
+            }
+        });
+        let mut channel = Self {
+            child,
+            stdin,
+            lines,
+            session_id: String::new(),
+        };
+
+        // The attach itself is answered with a block
+        let attach = channel.read_block("attach-session")?;
+        if !attach.success {
+            anyhow::bail!("tmux control client failed to attach");
+        }
+
+        let session = channel.execute(&["display-message", "-p", "#{session_id}"])?;
+        channel.session_id = session.stdout.trim().to_string();
+        Ok(channel)
+    }
+
+    fn execute(&mut self, args: &[&str]) -> Result<CommandOutput> {
+        let line = args.iter().map(|a| quote(a)).collect::<Vec<_>>().join(" ");
+        writeln!(self.stdin, "{}", line).context("tmux control client closed")?;
+        self.stdin.flush()?;
+        self.read_block(args.first().copied().unwrap_or(""))
+    }
+
+    /// Read the next reply block to `command`, skipping notifications
+    fn read_block(&mut self, command: &str) -> Result<CommandOutput> {
+        let mut number: Option<String> = None;
+        let mut body: Vec<String> = Vec::new();
+        let deadline = Instant::now() + COMMAND_TIMEOUT;
+
+        loop {
+            let timeout = deadline.saturating_duration_since(Instant::now());
+            let raw = match self.lines.recv_timeout(timeout) {
+                Ok(raw) => raw,
+                Err(RecvTimeoutError::Timeout) => {
+                    return Err(TimedOut {
+                        command: command.to_string(),
+                        after: COMMAND_TIMEOUT,
+                    }
+                    .into())
+                }
+                Err(RecvTimeoutError::Disconnected) => {
+                    anyhow::bail!("tmux control client closed")
+                }
+            };
+            let line = String::from_utf8_lossy(&raw);
+            let line = line.trim_end_matches(['\n', '\r']);
+
+            match &number {
+                None => {
+                    // Outside a block: only notifications until %begin
+                    if let Some(rest) = line.strip_prefix("%begin ") {
+                        number = rest.split(' ').nth(1).map(str::to_string);
+                    }
+                }
+                Some(n) => {
+                    let closes = |prefix: &str| {
+                        line.strip_prefix(prefix)
+                            .is_some_and(|rest| rest.split(' ').nth(1) == Some(n.as_str()))
+                    };
+                    if closes("%end ") || closes("%error ") {
+                        let success = line.starts_with("%end ");
+                        let text = body.join("\n");
+                        let (stdout, stderr) = if success {
+                            (text, String::new())
+                        } else {
+                            (String::new(), text)
+                        };
+                        return Ok(CommandOutput {
+                            success,
+                            stdout: if stdout.is_empty() {
+                                stdout
+                            } else {
+                                stdout + "\n"
+                            },
+                            stderr,
+                        });
+                    }
+                    body.push(line.to_string());
+                }
+            }
+        }
+    }
+}
+
+impl Drop for ControlChannel {
+    fn drop(&mut self) {
+        let _ = self.child.kill();
+        let _ = self.child.wait();
+    }
+}
+
+/// Quote an argument for the tmux command parser
+///
+/// Double quotes with escapes keep newlines, `$` and `~` literal.
+fn quote(arg: &str) -> String {
+    let mut out = String::with_capacity(arg.len() + 2);
+    out.push('"');
+    for c in arg.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '$' => out.push_str("\\$"),
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            '\t' => out.push_str("\\t"),
+            c => out.push(c),
+        }
+    }
+    out.push('"');
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_output_with_timeout() {
+        let mut echo = Command::new("sh");
+        echo.args(["-c", "cat; echo err >&2"]);
+        let output =
+            output_with_timeout(echo, "echo", Some(b"hello"), Duration::from_secs(5)).unwrap();
+        assert!(output.success);
+        assert_eq!(output.stdout, "hello");
+        assert_eq!(output.stderr, "err\n");
+
+        let mut sleep = Command::new("sleep");
+        sleep.arg("10");
+        let started = Instant::now();
+        let error =
+            output_with_timeout(sleep, "sleep", None, Duration::from_millis(100)).unwrap_err();
+        assert!(error.is::<TimedOut>());
+        assert!(started.elapsed() < Duration::from_secs(5));
+    }
+
+    #[test]
+    fn test_quote() {
+        assert_eq!(quote("list-panes"), "\"list-panes\"");
+        assert_eq!(
+            quote("say \"hi\" to $USER\nnow"),
+            "\"say \\\"hi\\\" to \\$USER\\nnow\""
+        );
+        assert_eq!(
+            quote("#{pane_id}\t#{pane_pid}"),
+            "\"#{pane_id}\\t#{pane_pid}\""
+        );
+    }
+}
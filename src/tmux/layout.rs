@@ -0,0 +1,261 @@
+//! Capturing a session's window/pane layout and recreating it
+//!
+//! A layout is the session's windows, in order, each with its tmux layout
+//! string (`window_layout`, the geometry `select-layout` accepts) and the
+//! working directory of every pane. Recreating it creates each window, splits
+//! it once per extra pane, then applies the layout string, so pane sizes and
+//! arrangement match the original.
+
+use std::path::PathBuf;
+
+use anyhow::{Context, Result};
+
+use super::{control, pane_path, process};
+
+/// A session's windows, as captured from tmux
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SessionLayout {
+    pub windows: Vec<WindowLayout>,
+}
+
+/// One window of a captured session
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WindowLayout {
+    pub name: String,
+    /// Layout string, e.g. `b25d,200x50,0,0{100x50,0,0,1,99x50,101,0,2}`
+    pub layout: String,
+    pub width: u32,
+    pub height: u32,
+    /// Whether this is the session's current window
+    pub active: bool,
+    pub panes: Vec<PaneLayout>,
+}
+
+/// One pane of a captured window
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PaneLayout {
+    pub path: PathBuf,
+    /// Whether Claude ran in the pane when it was captured
+    pub has_claude: bool,
+    /// Command to type into the recreated pane, if any
+    pub command: Option<String>,
+}
+
+const LAYOUT_FORMAT: &str = "#{window_index}\t#{window_name}\t#{window_layout}\t#{window_width}\t#{window_height}\t#{window_active}\t#{pane_pid}\t#{pane_current_path}";
+
+/// Capture the windows and panes of `session`
+pub fn capture(session: &str) -> Result<SessionLayout> {
+    let target = format!("={}", session);
+    let output = control::run(&["list-panes", "-s", "-t", &target, "-F", LAYOUT_FORMAT])
+        .context("Failed to list panes")?;
+    if !output.success {
+        anyhow::bail!(
+            "Failed to list panes of {}: {}",
+            session,
+            output.stderr.trim()
+        );
+    }
+
+    let (mut layout, pids) = parse_layout(&output.stdout);
+    let with_claude = process::panes_with_claude(&pids);
+    for (pane, pid) in layout
+        .windows
+        .iter_mut()
+        .flat_map(|w| w.panes.iter_mut())
+        .zip(pids)
+    {
+        pane.has_claude = with_claude.contains(&pid);
+    }
+
+    if layout.windows.is_empty() {
+        anyhow::bail!("Session {} has no panes", session);
+    }
+    Ok(layout)
+}
+
+/// Parse `list-panes` output in `LAYOUT_FORMAT`; also returns the pane PIDs,
+/// in the order of the panes
+fn parse_layout(output: &str) -> (SessionLayout, Vec<u32>) {
+    let mut windows: Vec<(String, WindowLayout)> = Vec::new();
+    let mut pids = Vec::new();
+
+    for line in output.lines() {
+        let parts: Vec<&str> = line.split('\t').collect();
+        if parts.len() < 8 {
+            continue;
+        }
+        let pane = PaneLayout {
+            path: pane_path(parts[7]),
+            has_claude: false,
+            command: None,
+        };
+        match windows.last_mut() {
+            Some((index, window)) if index == parts[0] => window.panes.push(pane),
+            _ => windows.push((
+                parts[0].to_string(),
+                WindowLayout {
+                    name: parts[1].to_string(),
+                    layout: parts[2].to_string(),
+                    width: parts[3].parse().unwrap_or(80),
+                    height: parts[4].parse().unwrap_or(24),
+                    active: parts[5] == "1",
+                    panes: vec![pane],
+                },
+            )),
+        }
+        pids.push(parts[6].parse().unwrap_or(0));
+    }
+
+    let windows = windows.into_iter().map(|(_, window)| window).collect();
+    (SessionLayout { windows }, pids)
+}
+
+/// Create session `name` with the windows and panes of `layout`
+///
+/// Each pane starts in its directory and runs its `command`, if any.
+pub fn create(name: &str, layout: &SessionLayout) -> Result<()> {
+    let mut active_pane = None;
+
+    for (i, window) in layout.windows.iter().enumerate() {
+        let Some((first, rest)) = window.panes.split_first() else {
+            continue;
+        };
+        let dir = first.path.to_string_lossy();
+
+        // The first window comes with the session
+        let first_pane = if i == 0 {
+            let (width, height) = (window.width.to_string(), window.height.to_string());
+            print_id(
+                control::spawn(&[
+                    "new-session",
+                    "-d",
+                    "-P",
+                    "-F",
+                    "#{pane_id}",
+                    "-s",
+                    name,
+                    "-n",
+                    &window.name,
+                    "-x",
+                    &width,
+                    "-y",
+                    &height,
+                    "-c",
+                    &dir,
+                ]),
+                "create session",
+            )?
+        } else {
+            let target = format!("={}:", name);
+            print_id(
+                control::run(&[
+                    "new-window",
+                    "-d",
+                    "-P",
+                    "-F",
+                    "#{pane_id}",
+                    "-t",
+                    &target,
+                    "-n",
+                    &window.name,
+                    "-c",
+                    &dir,
+                ]),
+                "create window",
+            )?
+        };
+
+        // Splitting the last pane each time keeps tmux's pane order, which
+        // the layout string's cells are matched against
+        let mut pane_ids = vec![first_pane.clone()];
+        for pane in rest {
+            let last = pane_ids.last().cloned().unwrap_or_default();
+            let dir = pane.path.to_string_lossy();
+            let id = print_id(
+                control::run(&[
+                    "split-window",
+                    "-d",
+                    "-P",
+                    "-F",
+                    "#{pane_id}",
+                    "-t",
+                    &last,
+                    "-c",
+                    &dir,
+                ]),
+                "split window",
+            )?;
+            // Even out the panes so the next split has room
+            let _ = control::run(&["select-layout", "-t", &first_pane, "tiled"]);
+            pane_ids.push(id);
+        }
+
+        let output = control::run(&["select-layout", "-t", &first_pane, &window.layout])
+            .context("Failed to apply layout")?;
+        if !output.success {
+            anyhow::bail!("Failed to apply layout: {}", output.stderr.trim());
+        }
+
+        for (pane, id) in window.panes.iter().zip(&pane_ids) {
+            if let Some(command) = &pane.command {
+                let _ = control::run(&["send-keys", "-t", id, command, "Enter"]);
+            }
+        }
+
+        if window.active {
+            active_pane = Some(first_pane);
+        }
+    }
+
+    if let Some(pane) = active_pane {
+        let _ = control::run(&["select-window", "-t", &pane]);
+    }
+
+    Ok(())
+}
+
+/// The ID printed by a command run with `-P -F '#{pane_id}'`
+fn print_id(output: Result<control::CommandOutput>, what: &str) -> Result<String> {
+    let output = output.with_context(|| format!("Failed to {}", what))?;
+    let id = output.stdout.trim();
+    if !output.success || id.is_empty() {
+        anyhow::bail!("Failed to {}: {}", what, output.stderr.trim());
+    }
+    Ok(id.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_layout() {
+        let output = "\
+1\tagent\tb25d,200x50,0,0{100x50,0,0,1,99x50,101,0,2}\t200\t50\t1\t100\t/work/api
+1\tagent\tb25d,200x50,0,0{100x50,0,0,1,99x50,101,0,2}\t200\t50\t1\t101\t/work/api/web
+3\tserver\t5e1f,200x50,0,0,3\t200\t50\t0\t102\t/work/api (deleted)
+garbage";
+
+        let (layout, pids) = parse_layout(output);
+        assert_eq!(pids, vec![100, 101, 102]);
+        assert_eq!(layout.windows.len(), 2);
+
+        let agent = &layout.windows[0];
+        assert_eq!(agent.name, "agent");
+        assert_eq!((agent.width, agent.height), (200, 50));
+        assert!(agent.active);
+        assert_eq!(
+            agent
+                .panes
+                .iter()
+                .map(|p| p.path.clone())
+                .collect::<Vec<_>>(),
+            vec![PathBuf::from("/work/api"), PathBuf::from("/work/api/web")]
+        );
+
+        let server = &layout.windows[1];
+        assert_eq!(server.layout, "5e1f,200x50,0,0,3");
+        assert!(!server.active);
+        assert_eq!(server.panes[0].path, PathBuf::from("/work/api"));
+    }
+}
@@ -0,0 +1,134 @@
+//! Scripted tmux replies for tests
+//!
+//! A [`MockTmux`] answers commands from a list of replies, each matched on
+//! the start of the command line, and records every command it receives.
+//! Commands without a reply fail the way they do when no server is running.
+
+use std::sync::{Arc, Mutex};
+
+use anyhow::Result;
+
+use super::backend::{self, TmuxBackend};
+use super::control::CommandOutput;
+
+/// A tmux stand-in answering from scripted replies
+#[derive(Default)]
+pub struct MockTmux {
+    /// Command line prefixes and their output, the longest match winning
+    replies: Vec<(String, CommandOutput)>,
+    /// Command lines received, arguments joined by spaces
+    calls: Mutex<Vec<String>>,
+}
+
+impl MockTmux {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Reply to commands starting with `prefix` (e.g. "display-message -p")
+    pub fn reply(mut self, prefix: &str, stdout: &str) -> Self {
+        self.replies.push((
+            prefix.to_string(),
+            CommandOutput {
+                success: true,
+                stdout: stdout.to_string(),
+                stderr: String::new(),
+            },
+        ));
+        self
+    }
+
+    /// Fail commands starting with `prefix` with `stderr`
+    pub fn fail(mut self, prefix: &str, stderr: &str) -> Self {
+        self.replies.push((
+            prefix.to_string(),
+            CommandOutput {
+                success: false,
+                stdout: String::new(),
+                stderr: stderr.to_string(),
+            },
+        ));
+        self
+    }
+
+    /// Handle tmux commands of the current thread with this mock
+    pub fn install(self) -> Arc<Self> {
+        let mock = Arc::new(self);
+        backend::set_thread_backend(Some(mock.clone()));
+        mock
+    }
+
+    /// Command lines received so far
+    pub fn calls(&self) -> Vec<String> {
+        self.calls.lock().unwrap().clone()
+    }
+
+    fn answer(&self, args: &[&str]) -> CommandOutput {
+        let line = args.join(" ");
+        self.calls.lock().unwrap().push(line.clone());
+        self.replies
+            .iter()
+            .filter(|(prefix, _)| line.starts_with(prefix.as_str()))
+            .max_by_key(|(prefix, _)| prefix.len())
+            .map(|(_, output)| output.clone())
+            .unwrap_or_else(|| CommandOutput {
+                success: false,
+                stdout: String::new(),
+                stderr: "no server running".to_string(),
+            })
+    }
+}
+
+impl TmuxBackend for MockTmux {
+    fn run(&self, args: &[&str]) -> Result<CommandOutput> {
+        Ok(self.answer(args))
+    }
+
+    fn spawn(&self, args: &[&str], _input: Option<&[u8]>) -> Result<CommandOutput> {
+        Ok(self.answer(args))
+    }
+
+    fn run_in_terminal(&self, args: &[&str]) -> Result<bool> {
+        Ok(self.answer(args).success)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tmux::Tmux;
+
+    #[test]
+    fn test_mock_tmux() {
+        let mock = MockTmux::new()
+            .reply("display-message", "other:0.0\n")
+            .reply("display-message -p #{session_name}", "main:1.2\n")
+            .reply("-V", "tmux 3.4\n")
+            .install();
+
+        assert_eq!(Tmux::current_pane().unwrap().as_deref(), Some("main:1.2"));
+        assert_eq!(Tmux::version().as_deref(), Some("tmux 3.4"));
+        assert_eq!(
+            mock.calls(),
+            [
+                "display-message -p #{session_name}:#{window_index}.#{pane_index}",
+                "-V"
+            ]
+        );
+
+        // Unscripted commands fail as without a server
+        assert!(Tmux::capture_pane("%1", 10, false).is_err());
+
+        let mock = MockTmux::new()
+            .fail("select-window", "can't find window: 9")
+            .install();
+        let error = Tmux::attach_to_pane("main:9").unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "Failed to select main:9: can't find window: 9"
+        );
+        assert_eq!(mock.calls(), ["select-window -t main:9"]);
+
+        backend::set_thread_backend(None);
+    }
+}
@@ -0,0 +1,1600 @@
+//! tmux command wrapper
+//!
+//! - `backend`: Where commands are executed: the system tmux, or a mock in tests
+//! - `capture`: Bounded, deduplicated pane captures for status detection
+//! - `client`: Resolving which tmux client to switch
+//! - `control`: Persistent control-mode connection commands are multiplexed over
+//! - `layout`: Capturing a session's window/pane layout and recreating it
+//! - `mock`: Scripted tmux replies for tests
+//! - `process`: Process tree lookups under pane PIDs
+
+mod backend;
+mod capture;
+mod client;
+mod control;
+mod layout;
+#[cfg(test)]
+pub mod mock;
+mod process;
+
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result};
+
+use crate::config::shell_quote;
+use crate::git::GitContext;
+use crate::session::{
+    is_shell, ClaudeCodeStatus, ClaudeInstance, Pane, PaneEntry, Session, SortOrder,
+};
+
+pub use backend::TmuxBackend;
+pub use layout::SessionLayout;
+pub use process::{command_line, environment, executable, ClaudeProcess};
+
+/// Pane option set on panes where Claude has been seen running
+const AGENT_OPTION: &str = "@claude_tmux_agent";
+
+/// Pane option recording where a pane joined into another window came from
+const HOME_OPTION: &str = "@claude_tmux_home";
+
+/// Format of `HOME_OPTION` values; session names never contain `:`, and the
+/// window name, which may, comes last
+const HOME_FORMAT: &str = "#{session_name}:#{window_id}:#{window_index}:#{window_name}";
+
+/// Texts longer than this are pasted through a tmux buffer instead of send-keys
+const SEND_KEYS_LIMIT: usize = 1024;
+
+/// Whether a session has a client attached, not counting our control client
+fn is_attached(session_attached: &str, session_id: &str) -> bool {
+    let clients: usize = session_attached.parse().unwrap_or(0);
+    let own = control::attached_session_id().is_some_and(|id| id == session_id);
+    clients > usize::from(own)
+}
+
+/// Path from `#{pane_current_path}`
+///
+/// On Linux tmux reads the path from `/proc/<pid>/cwd`, which gets a
+/// " (deleted)" suffix once the directory is removed.
+fn pane_path(value: &str) -> PathBuf {
+    PathBuf::from(value.strip_suffix(" (deleted)").unwrap_or(value))
+}
+
+/// Directory an agent works in, and the pane's current path if it differs
+///
+/// The Claude process's own working directory wins over the pane's. Paths
+/// are compared with symlinks resolved, as the process's is a real path.
+fn claude_directory(
+    current_path: PathBuf,
+    claude_cwd: Option<&PathBuf>,
+) -> (PathBuf, Option<PathBuf>) {
+    let Some(cwd) = claude_cwd.map(|cwd| pane_path(&cwd.to_string_lossy())) else {
+        return (current_path, None);
+    };
+    if current_path == cwd || current_path.canonicalize().is_ok_and(|path| path == cwd) {
+        (current_path, None)
+    } else {
+        (cwd, Some(current_path))
+    }
+}
+
+/// Window a joined pane came from, parsed from `HOME_OPTION`
+#[derive(Debug, PartialEq, Eq)]
+struct PaneHome {
+    session: String,
+    window_id: String,
+    window_index: String,
+    window_name: String,
+}
+
+impl PaneHome {
+    fn parse(value: &str) -> Option<Self> {
+        let mut parts = value.trim_end_matches('\n').splitn(4, ':');
+        Some(Self {
+            session: parts.next().filter(|s| !s.is_empty())?.to_string(),
+            window_id: parts.next()?.to_string(),
+            window_index: parts.next()?.to_string(),
+            window_name: parts.next()?.to_string(),
+        })
+    }
+}
+
+/// Keep one entry per pane, preferring attached sessions
+///
+/// A window linked into several sessions (`link-window`) is listed once per
+/// session; its panes are only reported under the first one.
+fn dedupe_linked(panes: &mut Vec<(String, bool, Pane)>) {
+    panes.sort_by_key(|(_, attached, _)| !attached);
+    let mut seen = HashSet::new();
+    panes.retain(|(_, _, pane)| seen.insert(pane.id.clone()));
+}
+
+/// Wrapper for tmux command execution
+pub struct Tmux;
+
+impl Tmux {
+    /// Enable or disable the persistent control-mode connection
+    pub fn set_control_mode(enabled: bool) {
+        control::set_enabled(enabled);
+    }
+
+    /// Recognize Claude processes with `rules` (`[claude_process]`)
+    pub fn set_claude_process(rules: ClaudeProcess) {
+        process::set_claude_process(rules);
+    }
+
+    /// List all Claude Code instances across all tmux sessions
+    pub fn list_claude_instances() -> Result<Vec<ClaudeInstance>> {
+        // Get list of sessions
+        let output = control::run(&[
+            "list-sessions",
+            "-F",
+            "#{session_name}\t#{session_attached}\t#{session_id}",
+        ])
+        .context("Failed to execute tmux list-sessions")?;
+
+        if !output.success {
+            let stderr = &output.stderr;
+            if stderr.contains("no server running") || stderr.contains("no sessions") {
+                return Ok(Vec::new());
+            }
+            anyhow::bail!("tmux list-sessions failed: {}", stderr);
+        }
+
+        let stdout = &output.stdout;
+
+        // Collect all panes from all sessions
+        let mut all_panes: Vec<(String, bool, Pane)> = Vec::new(); // (session_name, attached, pane)
+
+        for line in stdout.lines() {
+            let parts: Vec<&str> = line.split('\t').collect();
+            if parts.len() >= 3 {
+                let session_name = parts[0].to_string();
+                let attached = is_attached(parts[1], parts[2]);
+
+                // Get all panes for this session
+                let panes = Self::list_panes(&session_name).unwrap_or_default();
+                for pane in panes {
+                    all_panes.push((session_name.clone(), attached, pane));
+                }
+            }
+        }
+
+        dedupe_linked(&mut all_panes);
+
+        // Collect all pane PIDs
+        let all_pane_pids: Vec<u32> = all_panes
+            .iter()
+            .filter(|(_, _, pane)| pane.pid > 0)
+            .map(|(_, _, pane)| pane.pid)
+            .collect();
+
+        // Find which panes have Claude Code running
+        let panes_with_claude = process::panes_with_claude(&all_pane_pids);
+
+        // Build ClaudeInstance for each pane with Claude
+        let mut instances: Vec<ClaudeInstance> = Vec::new();
+
+        let statuses = Self::capture_claude_panes(&all_panes, &panes_with_claude);
+        let claude_pids: Vec<u32> = panes_with_claude.iter().copied().collect();
+        let claude_cwds = process::claude_cwds(&claude_pids);
+
+        for (session_name, attached, pane) in all_panes {
+            let has_claude = panes_with_claude.contains(&pane.pid);
+            if let Some(status) = Self::agent_status(&pane, has_claude, &statuses) {
+                let (working_directory, pane_path) =
+                    claude_directory(pane.current_path, claude_cwds.get(&pane.pid));
+                // Detect git context
+                let git_context = GitContext::detect(&working_directory);
+
+                instances.push(ClaudeInstance {
+                    session_name,
+                    session_attached: attached,
+                    window_index: pane.window_index,
+                    window_name: pane.window_name,
+                    pane_id: pane.id,
+                    pane_index: pane.pane_index,
+                    working_directory,
+                    pane_path,
+                    status,
+                    git_context,
+                    logging: pane.piped,
+                });
+            }
+        }
+
+        // Attached sessions first; the app applies the configured order
+        SortOrder::default().sort(&mut instances);
+
+        Ok(instances)
+    }
+
+    /// List all panes in a session (across all windows)
+    fn list_panes(session: &str) -> Result<Vec<Pane>> {
+        let output = control::run(&[
+                "list-panes",
+                "-t",
+                session,
+                "-s", // List all panes in all windows
+                "-F",
+                "#{pane_id}\t#{pane_index}\t#{pane_pid}\t#{pane_current_path}\t#{window_index}\t#{window_name}\t#{@claude_tmux_agent}\t#{pane_current_command}\t#{pane_pipe}",
+            ])
+            .context("Failed to execute tmux list-panes")?;
+
+        if !output.success {
+            return Ok(Vec::new());
+        }
+
+        let stdout = &output.stdout;
+        let mut panes = Vec::new();
+
+        for line in stdout.lines() {
+            let parts: Vec<&str> = line.split('\t').collect();
+            if parts.len() >= 6 {
+                panes.push(Pane {
+                    id: parts[0].to_string(),
+                    pane_index: parts[1].parse().unwrap_or(0),
+                    pid: parts[2].parse().unwrap_or(0),
+                    current_path: pane_path(parts[3]),
+                    window_index: parts[4].parse().unwrap_or(0),
+                    window_name: parts[5].to_string(),
+                    agent_marked: parts.get(6) == Some(&"1"),
+                    current_command: parts.get(7).unwrap_or(&"").to_string(),
+                    piped: parts.get(8) == Some(&"1"),
+                });
+            }
+        }
+
+        Ok(panes)
+    }
+
+    /// List all panes across all sessions in a single tmux call
+    ///
+    /// This is more efficient than calling list_panes() for each session separately.
+    fn list_all_panes() -> Result<Vec<(String, bool, Pane)>> {
+        let output = control::run(&[
+                "list-panes",
+                "-a", // All sessions, all windows
+                "-F",
+                "#{session_name}\t#{session_attached}\t#{pane_id}\t#{pane_index}\t#{pane_pid}\t#{pane_current_path}\t#{window_index}\t#{window_name}\t#{@claude_tmux_agent}\t#{session_id}\t#{pane_current_command}\t#{pane_pipe}",
+            ])
+            .context("Failed to execute tmux list-panes -a")?;
+
+        if !output.success {
+            let stderr = &output.stderr;
+            if stderr.contains("no server running") || stderr.contains("no sessions") {
+                return Ok(Vec::new());
+            }
+            anyhow::bail!("tmux list-panes -a failed: {}", stderr);
+        }
+
+        let stdout = &output.stdout;
+        let mut all_panes = Vec::new();
+
+        for line in stdout.lines() {
+            let parts: Vec<&str> = line.split('\t').collect();
+            if parts.len() >= 10 {
+                let session_name = parts[0].to_string();
+                let attached = is_attached(parts[1], parts[9]);
+                let pane = Pane {
+                    id: parts[2].to_string(),
+                    pane_index: parts[3].parse().unwrap_or(0),
+                    pid: parts[4].parse().unwrap_or(0),
+                    current_path: pane_path(parts[5]),
+                    window_index: parts[6].parse().unwrap_or(0),
+                    window_name: parts[7].to_string(),
+                    agent_marked: parts.get(8) == Some(&"1"),
+                    current_command: parts.get(10).unwrap_or(&"").to_string(),
+                    piped: parts.get(11) == Some(&"1"),
+                };
+                all_panes.push((session_name, attached, pane));
+            }
+        }
+
+        dedupe_linked(&mut all_panes);
+        Ok(all_panes)
+    }
+
+    /// List Claude instances without capturing panes or detecting git context
+    ///
+    /// Used for fast initial loading. Running instances get status `Unknown`;
+    /// use `capture_statuses` to detect them, and `GitContext::detect()` for
+    /// git context. Only one `list-panes` call and a process scan are needed.
+    pub fn list_claude_panes() -> Result<Vec<ClaudeInstance>> {
+        // Get all panes in a single tmux call
+        let all_panes = Self::list_all_panes()?;
+
+        // Collect all pane PIDs
+        let all_pane_pids: Vec<u32> = all_panes
+            .iter()
+            .filter(|(_, _, pane)| pane.pid > 0)
+            .map(|(_, _, pane)| pane.pid)
+            .collect();
+
+        // Find which panes have Claude Code running
+        let panes_with_claude = process::panes_with_claude(&all_pane_pids);
+
+        // Build ClaudeInstance for each pane with Claude (without git context)
+        let mut instances: Vec<ClaudeInstance> = Vec::new();
+        let no_statuses = HashMap::new();
+        let claude_pids: Vec<u32> = panes_with_claude.iter().copied().collect();
+        let claude_cwds = process::claude_cwds(&claude_pids);
+
+        for (session_name, attached, pane) in all_panes {
+            let has_claude = panes_with_claude.contains(&pane.pid);
+            if let Some(status) = Self::agent_status(&pane, has_claude, &no_statuses) {
+                let (working_directory, pane_path) =
+                    claude_directory(pane.current_path, claude_cwds.get(&pane.pid));
+                instances.push(ClaudeInstance {
+                    session_name,
+                    session_attached: attached,
+                    window_index: pane.window_index,
+                    window_name: pane.window_name,
+                    pane_id: pane.id,
+                    pane_index: pane.pane_index,
+                    working_directory,
+                    pane_path,
+                    status,
+                    git_context: None, // Will be loaded separately
+                    logging: pane.piped,
+                });
+            }
+        }
+
+        // Attached sessions first; the app applies the configured order
+        SortOrder::default().sort(&mut instances);
+
+        Ok(instances)
+    }
+
+    /// List every pane on the server, with or without Claude
+    ///
+    /// Sorted by session, window, and pane, for the all-panes view.
+    pub fn list_every_pane() -> Result<Vec<PaneEntry>> {
+        let all_panes = Self::list_all_panes()?;
+        let pids: Vec<u32> = all_panes
+            .iter()
+            .filter(|(_, _, pane)| pane.pid > 0)
+            .map(|(_, _, pane)| pane.pid)
+            .collect();
+        let panes_with_claude = process::panes_with_claude(&pids);
+
+        let mut entries: Vec<PaneEntry> = all_panes
+            .into_iter()
+            .map(|(session_name, _, pane)| PaneEntry {
+                has_claude: panes_with_claude.contains(&pane.pid),
+                session_name,
+                pane,
+            })
+            .collect();
+        entries.sort_by(|a, b| {
+            a.session_name
+                .cmp(&b.session_name)
+                .then_with(|| a.pane.window_index.cmp(&b.pane.window_index))
+                .then_with(|| a.pane.pane_index.cmp(&b.pane.pane_index))
+        });
+
+        Ok(entries)
+    }
+
+    /// List every session on the server, with or without Claude
+    ///
+    /// Sorted by name, for the sessions view.
+    pub fn list_every_session() -> Result<Vec<Session>> {
+        let output = control::run(&[
+            "list-sessions",
+            "-F",
+            "#{session_id}\t#{session_name}\t#{session_created}\t#{session_attached}\t#{session_windows}\t#{session_path}",
+        ])
+        .context("Failed to list sessions")?;
+        if !output.success {
+            let stderr = &output.stderr;
+            if stderr.contains("no server running") || stderr.contains("no sessions") {
+                return Ok(Vec::new());
+            }
+            anyhow::bail!("Failed to list sessions: {}", stderr.trim());
+        }
+
+        let all_panes = Self::list_all_panes()?;
+        let pids: Vec<u32> = all_panes
+            .iter()
+            .filter(|(_, _, pane)| pane.pid > 0)
+            .map(|(_, _, pane)| pane.pid)
+            .collect();
+        let panes_with_claude = process::panes_with_claude(&pids);
+        let mut panes_by_session: HashMap<String, Vec<Pane>> = HashMap::new();
+        for (session_name, _, pane) in all_panes {
+            panes_by_session.entry(session_name).or_default().push(pane);
+        }
+
+        let mut sessions: Vec<Session> = output
+            .stdout
+            .lines()
+            .filter_map(|line| {
+                let parts: Vec<&str> = line.split('\t').collect();
+                if parts.len() < 6 {
+                    return None;
+                }
+                let panes = panes_by_session.remove(parts[1]).unwrap_or_default();
+                let claude_pane = panes
+                    .iter()
+                    .find(|pane| panes_with_claude.contains(&pane.pid));
+                let working_directory = claude_pane
+                    .or(panes.first())
+                    .map(|pane| pane.current_path.clone())
+                    .unwrap_or_else(|| PathBuf::from(parts[5]));
+                Some(Session {
+                    id: parts[0].to_string(),
+                    name: parts[1].to_string(),
+                    created: parts[2].parse().unwrap_or(0),
+                    attached: is_attached(parts[3], parts[0]),
+                    working_directory,
+                    window_count: parts[4].parse().unwrap_or(0),
+                    claude_code_pane: claude_pane.map(|pane| pane.id.clone()),
+                    panes,
+                })
+            })
+            .collect();
+        sessions.sort_by(|a, b| a.name.cmp(&b.name));
+
+        Ok(sessions)
+    }
+
+    /// Status of a pane, or None if Claude has never run in it
+    ///
+    /// Panes running Claude are marked with a pane option so that, once the
+    /// process is gone, the pane can still be recognized and reported as
+    /// crashed.
+    fn agent_status(
+        pane: &Pane,
+        has_claude: bool,
+        statuses: &HashMap<String, ClaudeCodeStatus>,
+    ) -> Option<ClaudeCodeStatus> {
+        if has_claude {
+            if !pane.agent_marked {
+                let _ = Self::set_agent_marker(&pane.id, true);
+            }
+            let status = statuses
+                .get(&pane.id)
+                .copied()
+                .unwrap_or(ClaudeCodeStatus::Unknown);
+            Some(status)
+        } else if pane.agent_marked {
+            Some(ClaudeCodeStatus::Crashed)
+        } else {
+            None
+        }
+    }
+
+    /// Capture the status of every pane running Claude
+    fn capture_claude_panes(
+        panes: &[(String, bool, Pane)],
+        panes_with_claude: &HashSet<u32>,
+    ) -> HashMap<String, ClaudeCodeStatus> {
+        let pane_ids: Vec<String> = panes
+            .iter()
+            .filter(|(_, _, pane)| panes_with_claude.contains(&pane.pid))
+            .map(|(_, _, pane)| pane.id.clone())
+            .collect();
+        capture::capture_statuses(&pane_ids)
+    }
+
+    /// Detect the status of the given panes through the bounded capture pool
+    pub fn capture_statuses(pane_ids: &[String]) -> HashMap<String, ClaudeCodeStatus> {
+        capture::capture_statuses(pane_ids)
+    }
+
+    /// Run `job` on a new thread, sending its tmux commands where this
+    /// thread's go
+    pub fn spawn_worker(job: impl FnOnce() + Send + 'static) {
+        backend::spawn_worker(job)
+    }
+
+    /// List panes where Claude ran before but is no longer running
+    pub fn list_crashed_panes() -> Result<Vec<Pane>> {
+        let all_panes = Self::list_all_panes()?;
+        let marked_pids: Vec<u32> = all_panes
+            .iter()
+            .filter(|(_, _, pane)| pane.agent_marked && pane.pid > 0)
+            .map(|(_, _, pane)| pane.pid)
+            .collect();
+        if marked_pids.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let panes_with_claude = process::panes_with_claude(&marked_pids);
+        Ok(all_panes
+            .into_iter()
+            .map(|(_, _, pane)| pane)
+            .filter(|pane| pane.agent_marked && !panes_with_claude.contains(&pane.pid))
+            .collect())
+    }
+
+    /// PID of the Claude process running in a pane
+    pub fn claude_pid(pane_id: &str) -> Option<u32> {
+        let output = control::run(&["display-message", "-p", "-t", pane_id, "#{pane_pid}"]).ok()?;
+        let pid = output.stdout.trim().parse().ok()?;
+        process::claude_pid(pid)
+    }
+
+    /// Command line of the Claude process running in a pane
+    pub fn claude_args(pane_id: &str) -> Option<Vec<String>> {
+        process::command_line(Self::claude_pid(pane_id)?)
+    }
+
+    /// Set or clear the marker identifying a pane as a Claude pane
+    pub fn set_agent_marker(pane_id: &str, marked: bool) -> Result<()> {
+        let mut args = vec!["set-option", "-p", "-t", pane_id];
+        if marked {
+            args.extend([AGENT_OPTION, "1"]);
+        } else {
+            args.extend(["-u", AGENT_OPTION]);
+        }
+
+        let output = control::run(&args).context("Failed to set pane option")?;
+
+        if !output.success {
+            anyhow::bail!("Failed to update pane option on {}", pane_id);
+        }
+
+        Ok(())
+    }
+
+    /// Set a pane's title and show titles in its window's pane borders
+    ///
+    /// The pane is also stopped from retitling itself through escape
+    /// sequences (`allow-set-title`, tmux 3.4+), which Claude does.
+    pub fn set_pane_label(pane_id: &str, label: &str) -> Result<()> {
+        let _ = control::run(&["set-option", "-p", "-t", pane_id, "allow-set-title", "off"]);
+        let _ = control::run(&[
+            "set-option",
+            "-w",
+            "-t",
+            pane_id,
+            "pane-border-status",
+            "top",
+        ]);
+
+        Self::set_pane_title(pane_id, label)
+    }
+
+    /// Set a pane's title (`select-pane -T`)
+    pub fn set_pane_title(pane_id: &str, title: &str) -> Result<()> {
+        let output = control::run(&["select-pane", "-t", pane_id, "-T", title])
+            .context("Failed to set pane title")?;
+        if !output.success {
+            anyhow::bail!(
+                "Failed to set title of {}: {}",
+                pane_id,
+                output.stderr.trim()
+            );
+        }
+        Ok(())
+    }
+
+    /// Restart Claude in a pane whose process exited, resuming the last conversation
+    ///
+    /// `claude_command` is the launch command (e.g. from a profile);
+    /// `--continue` is appended.
+    pub fn restart_claude(pane_id: &str, claude_command: &str) -> Result<()> {
+        Self::ensure_shell(pane_id)?;
+        Self::exit_pane_mode(pane_id)?;
+
+        let command = format!("{} --continue", claude_command);
+        let output = control::run(&["send-keys", "-t", pane_id, &command, "Enter"])
+            .context("Failed to send keys")?;
+
+        if !output.success {
+            anyhow::bail!("Failed to restart claude in {}", pane_id);
+        }
+
+        Ok(())
+    }
+
+    /// Start Claude in a pane sitting at a shell prompt
+    pub fn start_claude(pane_id: &str, claude_command: &str) -> Result<()> {
+        Self::exit_pane_mode(pane_id)?;
+
+        let output = control::run(&["send-keys", "-t", pane_id, claude_command, "Enter"])
+            .context("Failed to send keys")?;
+
+        if !output.success {
+            anyhow::bail!("Failed to start claude in {}", pane_id);
+        }
+
+        Ok(())
+    }
+
+    /// Split a pane horizontally and start Claude in the new pane, in `dir`
+    ///
+    /// Returns the ID of the new pane.
+    pub fn split_with_claude(
+        pane_id: &str,
+        dir: &std::path::Path,
+        claude_command: &str,
+    ) -> Result<String> {
+        let dir = dir.to_string_lossy();
+        let output = control::run(&[
+            "split-window",
+            "-h",
+            "-d", // Keep focus where it is
+            "-P",
+            "-F",
+            "#{pane_id}",
+            "-t",
+            pane_id,
+            "-c",
+            &dir,
+        ])
+        .context("Failed to split pane")?;
+        if !output.success {
+            anyhow::bail!("Failed to split {}: {}", pane_id, output.stderr.trim());
+        }
+
+        let new_pane = output.stdout.trim().to_string();
+        Self::start_claude(&new_pane, claude_command)?;
+
+        Ok(new_pane)
+    }
+
+    /// Split a pane horizontally and run `command` in the new pane, in `dir`
+    ///
+    /// The default shell runs when `command` is `None`. Returns the ID of the
+    /// new pane.
+    pub fn split_running(
+        pane_id: &str,
+        dir: &std::path::Path,
+        command: Option<&str>,
+    ) -> Result<String> {
+        let dir = dir.to_string_lossy();
+        let mut args = vec![
+            "split-window",
+            "-h",
+            "-d",
+            "-P",
+            "-F",
+            "#{pane_id}",
+            "-t",
+            pane_id,
+            "-c",
+            &dir,
+        ];
+        args.extend(command);
+        let output = control::run(&args).context("Failed to split pane")?;
+        if !output.success {
+            anyhow::bail!("Failed to split {}: {}", pane_id, output.stderr.trim());
+        }
+        Ok(output.stdout.trim().to_string())
+    }
+
+    /// Open a window named `name` in `session`, in `dir`, and start Claude in it
+    ///
+    /// The window opens in the background. Returns the ID of its pane.
+    pub fn new_window_with_claude(
+        session: &str,
+        name: &str,
+        dir: &std::path::Path,
+        claude_command: &str,
+    ) -> Result<String> {
+        let target = format!("={}:", session);
+        let dir = dir.to_string_lossy();
+        let output = control::run(&[
+            "new-window",
+            "-d",
+            "-P",
+            "-F",
+            "#{pane_id}",
+            "-t",
+            &target,
+            "-n",
+            name,
+            "-c",
+            &dir,
+        ])
+        .context("Failed to create window")?;
+        if !output.success {
+            anyhow::bail!(
+                "Failed to create window in {}: {}",
+                session,
+                output.stderr.trim()
+            );
+        }
+
+        let new_pane = output.stdout.trim().to_string();
+        Self::start_claude(&new_pane, claude_command)?;
+
+        Ok(new_pane)
+    }
+
+    /// Open a window named `name` in `session`, in `dir`, running `command`
+    ///
+    /// The window opens in the background and closes when the command exits.
+    /// Returns the ID of its pane.
+    pub fn new_window_running(
+        session: &str,
+        name: &str,
+        dir: &std::path::Path,
+        command: &str,
+    ) -> Result<String> {
+        let target = format!("={}:", session);
+        let dir = dir.to_string_lossy();
+        let output = control::run(&[
+            "new-window",
+            "-d",
+            "-P",
+            "-F",
+            "#{pane_id}",
+            "-t",
+            &target,
+            "-n",
+            name,
+            "-c",
+            &dir,
+            command,
+        ])
+        .context("Failed to create window")?;
+        if !output.success {
+            anyhow::bail!(
+                "Failed to create window in {}: {}",
+                session,
+                output.stderr.trim()
+            );
+        }
+
+        Ok(output.stdout.trim().to_string())
+    }
+
+    /// Replace a pane's process with a fresh shell in `dir` and start Claude in it
+    ///
+    /// Used when the pane's working directory was deleted under it.
+    pub fn respawn_pane(pane_id: &str, dir: &std::path::Path, claude_command: &str) -> Result<()> {
+        let dir = dir.to_string_lossy();
+        let output = control::run(&["respawn-pane", "-k", "-t", pane_id, "-c", &dir])
+            .context("Failed to respawn pane")?;
+        if !output.success {
+            anyhow::bail!("Failed to respawn {}: {}", pane_id, output.stderr.trim());
+        }
+
+        let output = control::run(&["send-keys", "-t", pane_id, claude_command, "Enter"])
+            .context("Failed to send keys")?;
+        if !output.success {
+            anyhow::bail!("Failed to start claude in {}", pane_id);
+        }
+
+        Ok(())
+    }
+
+    /// Capture the last N lines of a pane's content
+    ///
+    /// If `strip_empty` is true, empty lines are filtered out before taking the last N.
+    /// This is useful for status detection. For preview display, use `strip_empty: false`
+    /// to preserve the visual layout.
+    ///
+    /// ANSI escape sequences are always included - the UI handles rendering them.
+    pub fn capture_pane(pane_id: &str, lines: usize, strip_empty: bool) -> Result<String> {
+        let output = control::run(&[
+            "capture-pane",
+            "-t",
+            pane_id,
+            "-p", // Print to stdout
+            "-J", // Join wrapped lines
+            "-e", // Include escape sequences
+        ])
+        .context("Failed to capture pane")?;
+
+        if !output.success {
+            anyhow::bail!("Failed to capture pane {}", pane_id);
+        }
+
+        let content = &output.stdout;
+
+        if strip_empty {
+            // Filter out empty lines, then get last N (for status detection)
+            let non_empty: Vec<&str> = content.lines().filter(|l| !l.trim().is_empty()).collect();
+            let start = non_empty.len().saturating_sub(lines);
+            let last_lines = &non_empty[start..];
+            Ok(last_lines.join("\n"))
+        } else {
+            // Preserve internal empty lines but trim trailing ones (for preview display)
+            let all_lines: Vec<&str> = content.lines().collect();
+
+            // Find last non-empty line
+            let last_non_empty = all_lines
+                .iter()
+                .rposition(|l| !l.trim().is_empty())
+                .map(|i| i + 1)
+                .unwrap_or(0);
+
+            let trimmed = &all_lines[..last_non_empty];
+            let start = trimmed.len().saturating_sub(lines);
+            let last_lines = &trimmed[start..];
+            Ok(last_lines.join("\n"))
+        }
+    }
+
+    /// Append everything the pane outputs from now on to `file` (`pipe-pane`)
+    pub fn start_pane_log(pane_id: &str, file: &Path) -> Result<()> {
+        let command = format!("cat >> {}", shell_quote(&file.to_string_lossy()));
+        let output = control::run(&["pipe-pane", "-t", pane_id, &command])
+            .context("Failed to start logging the pane")?;
+        if !output.success {
+            anyhow::bail!("Failed to log pane {}: {}", pane_id, output.stderr.trim());
+        }
+        Ok(())
+    }
+
+    /// Stop piping the pane's output
+    pub fn stop_pane_log(pane_id: &str) -> Result<()> {
+        let output = control::run(&["pipe-pane", "-t", pane_id])
+            .context("Failed to stop logging the pane")?;
+        if !output.success {
+            anyhow::bail!(
+                "Failed to stop logging pane {}: {}",
+                pane_id,
+                output.stderr.trim()
+            );
+        }
+        Ok(())
+    }
+
+    /// Capture a pane's last `lines` lines of scrollback and screen as plain text
+    pub fn capture_history(pane_id: &str, lines: usize) -> Result<String> {
+        let start = format!("-{}", lines);
+        let output = control::run(&["capture-pane", "-t", pane_id, "-p", "-J", "-S", &start])
+            .context("Failed to capture pane")?;
+        if !output.success {
+            anyhow::bail!("Failed to capture pane {}", pane_id);
+        }
+        Ok(output.stdout)
+    }
+
+    /// Switch the client showing this TUI to a specific pane (target format: session:window.pane)
+    ///
+    /// The client is resolved explicitly so that, with several clients or
+    /// nested tmux layers, the terminal running the TUI is the one that moves.
+    pub fn switch_to_pane(target: &str) -> Result<()> {
+        let client = client::own_client();
+        let mut args = vec!["switch-client"];
+        if let Some(client) = client.as_deref() {
+            args.extend(["-c", client]);
+        }
+        args.extend(["-t", target]);
+
+        let output = control::spawn(&args).context("Failed to switch to pane")?;
+        if !output.success {
+            anyhow::bail!("Failed to switch to pane {}", target);
+        }
+
+        Ok(())
+    }
+
+    /// Run `command` in a popup over the client displaying this process,
+    /// closed when the command exits
+    ///
+    /// The popup is `width` columns by `height` lines, shrunk to fit the
+    /// client. Blocks until the popup closes, so like `attach_to_pane` it
+    /// has no timeout.
+    pub fn display_popup(width: u16, height: u16, dir: &Path, command: &str) -> Result<()> {
+        let client = client::own_client().context("No tmux client to show the popup on")?;
+        Self::display_popup_on(&client, width, height, dir, command)
+    }
+
+    fn display_popup_on(
+        client: &str,
+        width: u16,
+        height: u16,
+        dir: &Path,
+        command: &str,
+    ) -> Result<()> {
+        let size = control::run(&[
+            "display-message",
+            "-p",
+            "-c",
+            client,
+            "#{client_width} #{client_height}",
+        ])?;
+        let (width, height) = match size.stdout.trim().split_once(' ') {
+            Some((w, h)) => (
+                width.min(w.parse().unwrap_or(width)),
+                height.min(h.parse().unwrap_or(height)),
+            ),
+            None => (width, height),
+        };
+
+        let (width, height) = (width.to_string(), height.to_string());
+        let dir = dir.to_string_lossy();
+        let shown = backend::current()
+            .run_in_terminal(&[
+                "display-popup",
+                "-E",
+                "-c",
+                client,
+                "-w",
+                &width,
+                "-h",
+                &height,
+                "-d",
+                &dir,
+                command,
+            ])
+            .context("Failed to open popup")?;
+        if !shown {
+            anyhow::bail!("Failed to open popup on {}", client);
+        }
+        Ok(())
+    }
+
+    /// Whether this process runs inside tmux
+    ///
+    /// Outside tmux there is no client of ours to switch; callers attach to
+    /// the target after exiting instead (see `attach_to_pane`).
+    pub fn inside_tmux() -> bool {
+        client::inside_tmux()
+    }
+
+    /// Select a pane and attach a new client to its session in this terminal
+    ///
+    /// Blocks until the client detaches, so unlike other commands it has no
+    /// timeout.
+    pub fn attach_to_pane(target: &str) -> Result<()> {
+        for command in ["select-window", "select-pane"] {
+            let output = control::spawn(&[command, "-t", target])?;
+            if !output.success {
+                anyhow::bail!("Failed to select {}: {}", target, output.stderr.trim());
+            }
+        }
+
+        let attached = backend::current()
+            .run_in_terminal(&["attach-session", "-t", target])
+            .context("Failed to attach to session")?;
+        if !attached {
+            anyhow::bail!("Failed to attach to {}", target);
+        }
+
+        Ok(())
+    }
+
+    /// Send text to a pane as if typed, followed by Enter
+    ///
+    /// Short single-line text is sent literally (`-l`) so words like "Enter" or
+    /// "C-c" are not interpreted as key names. Long or multi-line text goes
+    /// through a tmux buffer and bracketed paste instead, which avoids
+    /// send-keys length limits and keeps embedded newlines from submitting
+    /// the prompt early. Nothing is sent once Claude has left the pane.
+    pub fn send_prompt(target: &str, text: &str) -> Result<()> {
+        Self::ensure_claude(target)?;
+        Self::exit_pane_mode(target)?;
+
+        if text.len() > SEND_KEYS_LIMIT || text.contains('\n') {
+            Self::paste_text(target, text)?;
+        } else {
+            let output = control::run(&["send-keys", "-t", target, "-l", text])
+                .context("Failed to send keys")?;
+
+            if !output.success {
+                anyhow::bail!("Failed to send prompt to {}", target);
+            }
+        }
+
+        let output =
+            control::run(&["send-keys", "-t", target, "Enter"]).context("Failed to send keys")?;
+
+        if !output.success {
+            anyhow::bail!("Failed to send Enter to {}", target);
+        }
+
+        Ok(())
+    }
+
+    /// Send a single named key (e.g. "Enter", "Escape") to Claude in a pane
+    pub fn send_key(target: &str, key: &str) -> Result<()> {
+        Self::ensure_claude(target)?;
+        Self::exit_pane_mode(target)?;
+
+        let output =
+            control::run(&["send-keys", "-t", target, key]).context("Failed to send keys")?;
+
+        if !output.success {
+            anyhow::bail!("Failed to send {} to {}", key, target);
+        }
+
+        Ok(())
+    }
+
+    /// Fail unless a Claude process still runs in the pane
+    ///
+    /// The list can be a few seconds old: once Claude exits, keys meant for it
+    /// would be typed into the shell it was started from.
+    fn ensure_claude(target: &str) -> Result<()> {
+        let output = control::run(&["display-message", "-p", "-t", target, "#{pane_pid}"])
+            .context("Failed to query pane")?;
+        let pid: u32 = output
+            .stdout
+            .trim()
+            .parse()
+            .with_context(|| format!("Pane {} no longer exists", target))?;
+        if process::panes_with_claude(&[pid]).is_empty() {
+            anyhow::bail!("Claude no longer runs in {}; nothing was sent", target);
+        }
+        Ok(())
+    }
+
+    /// Fail unless the pane sits at a shell prompt
+    ///
+    /// Once Claude exits, the pane may run another program, such as an editor,
+    /// that a command line must not be typed into.
+    fn ensure_shell(pane_id: &str) -> Result<()> {
+        let output = control::run(&[
+            "display-message",
+            "-p",
+            "-t",
+            pane_id,
+            "#{pane_current_command}",
+        ])
+        .context("Failed to query pane")?;
+        let command = output.stdout.trim();
+        if !output.success || command.is_empty() {
+            anyhow::bail!("Pane {} no longer exists", pane_id);
+        }
+        if !is_shell(command) {
+            anyhow::bail!(
+                "{} runs {}, not a shell; nothing was sent",
+                pane_id,
+                command
+            );
+        }
+        Ok(())
+    }
+
+    /// Leave copy-mode (or any other pane mode) so sent keys reach the program
+    ///
+    /// While a pane is in a mode, keys sent to it are consumed by the mode
+    /// and never reach Claude.
+    fn exit_pane_mode(target: &str) -> Result<()> {
+        let output = control::run(&["display-message", "-p", "-t", target, "#{pane_in_mode}"])
+            .context("Failed to query pane mode")?;
+        if output.stdout.trim() != "1" {
+            return Ok(());
+        }
+
+        // `copy-mode -q` cancels every mode; tmux before 3.2 lacks it, so
+        // fall back to the copy-mode `cancel` command
+        let output =
+            control::run(&["copy-mode", "-q", "-t", target]).context("Failed to exit pane mode")?;
+        if !output.success {
+            let output = control::run(&["send-keys", "-X", "-t", target, "cancel"])
+                .context("Failed to exit pane mode")?;
+            if !output.success {
+                anyhow::bail!("Failed to exit copy-mode in {}", target);
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Paste text into a pane via a temporary tmux buffer
+    fn paste_text(target: &str, text: &str) -> Result<()> {
+        let buffer = format!("claude-tmux-{}", std::process::id());
+
+        let output =
+            control::spawn_with_input(&["load-buffer", "-b", &buffer, "-"], Some(text.as_bytes()))
+                .context("Failed to load text into tmux buffer")?;
+        if !output.success {
+            anyhow::bail!("Failed to load text into tmux buffer");
+        }
+
+        // -p: bracketed paste, -d: delete the buffer afterwards
+        let output = control::spawn(&["paste-buffer", "-p", "-d", "-b", &buffer, "-t", target])
+            .context("Failed to paste buffer")?;
+
+        if !output.success {
+            anyhow::bail!("Failed to paste text into {}", target);
+        }
+
+        Ok(())
+    }
+
+    /// Create a new tmux session
+    ///
+    /// `claude_command` (e.g. "claude --model opus") is typed into the new
+    /// session's shell when given.
+    pub fn new_session(
+        name: &str,
+        path: &std::path::Path,
+        claude_command: Option<&str>,
+    ) -> Result<()> {
+        let path_str = path.to_string_lossy();
+
+        let output = control::spawn(&["new-session", "-d", "-s", name, "-c", &path_str])
+            .context("Failed to create new session")?;
+
+        if !output.success {
+            anyhow::bail!("Failed to create session {}", name);
+        }
+
+        if let Some(command) = claude_command {
+            // Send claude command to the new session
+            let _ = control::spawn(&["send-keys", "-t", name, command, "Enter"]);
+        }
+
+        Ok(())
+    }
+
+    /// Capture the windows, pane layouts, and directories of a session
+    pub fn capture_layout(session: &str) -> Result<SessionLayout> {
+        layout::capture(session)
+    }
+
+    /// Create a session replicating a captured layout
+    pub fn new_session_from_layout(name: &str, layout: &SessionLayout) -> Result<()> {
+        layout::create(name, layout)
+    }
+
+    /// Swap a pane with the next (or previous) pane of its window
+    ///
+    /// The active pane stays the same.
+    pub fn swap_pane(pane_id: &str, forward: bool) -> Result<()> {
+        let direction = if forward { "-D" } else { "-U" };
+        Self::run_window_command(&["swap-pane", "-d", direction, "-t", pane_id])
+    }
+
+    /// Rotate the panes of a pane's window
+    pub fn rotate_window(pane_id: &str) -> Result<()> {
+        Self::run_window_command(&["rotate-window", "-t", pane_id])
+    }
+
+    /// Apply a preset layout (e.g. "main-vertical") to a pane's window
+    pub fn select_layout(pane_id: &str, layout: &str) -> Result<()> {
+        Self::run_window_command(&["select-layout", "-t", pane_id, layout])
+    }
+
+    fn run_window_command(args: &[&str]) -> Result<()> {
+        let output = control::run(args).with_context(|| format!("Failed to run {}", args[0]))?;
+        if !output.success {
+            anyhow::bail!("{} failed: {}", args[0], output.stderr.trim());
+        }
+        Ok(())
+    }
+
+    /// Names of the windows of `session`
+    pub fn list_window_names(session: &str) -> Result<Vec<String>> {
+        let target = format!("={}", session);
+        let output = control::run(&["list-windows", "-t", &target, "-F", "#{window_name}"])
+            .context("Failed to list windows")?;
+        if !output.success {
+            anyhow::bail!("Failed to list windows of {}", session);
+        }
+        Ok(output.stdout.lines().map(str::to_string).collect())
+    }
+
+    /// Names of all sessions
+    pub fn list_sessions() -> Result<Vec<String>> {
+        let output = control::run(&["list-sessions", "-F", "#{session_name}"])
+            .context("Failed to list sessions")?;
+        if !output.success {
+            anyhow::bail!("Failed to list sessions: {}", output.stderr.trim());
+        }
+        Ok(output.stdout.lines().map(str::to_string).collect())
+    }
+
+    /// Move a window to the end of another session, creating it if needed
+    ///
+    /// With `link`, the window is linked instead: it stays in its session
+    /// and also appears in `session`.
+    pub fn move_window(window: &str, session: &str, link: bool) -> Result<()> {
+        // A new session comes with a shell window, closed once ours is in
+        let placeholder = if Self::list_sessions()?.iter().any(|s| s == session) {
+            None
+        } else {
+            let output = control::spawn(&[
+                "new-session",
+                "-d",
+                "-P",
+                "-F",
+                "#{window_id}",
+                "-s",
+                session,
+            ])
+            .context("Failed to create session")?;
+            if !output.success {
+                anyhow::bail!("Failed to create session {}", session);
+            }
+            Some(output.stdout.trim().to_string())
+        };
+
+        let destination = format!("={}:", session);
+        let command = if link { "link-window" } else { "move-window" };
+        let output = control::run(&[command, "-s", window, "-t", &destination])
+            .with_context(|| format!("Failed to run {}", command))?;
+        if !output.success {
+            anyhow::bail!("{} failed: {}", command, output.stderr.trim());
+        }
+
+        if let Some(placeholder) = placeholder {
+            let _ = control::run(&["kill-window", "-t", &placeholder]);
+        }
+
+        Ok(())
+    }
+
+    /// Join a pane into the window shown by our client, as a split
+    ///
+    /// The pane's window is recorded so `send_pane_back` can return it.
+    pub fn join_pane(pane_id: &str) -> Result<()> {
+        let Some(destination) = client::own_client_pane() else {
+            anyhow::bail!("No tmux client to join the pane into");
+        };
+        let current = control::run(&["display-message", "-p", "-t", &destination, "#{window_id}"])?;
+        let home = control::run(&["display-message", "-p", "-t", pane_id, HOME_FORMAT])?;
+        if !home.success {
+            anyhow::bail!("Pane {} not found", pane_id);
+        }
+        if PaneHome::parse(&home.stdout).is_some_and(|h| h.window_id == current.stdout.trim()) {
+            anyhow::bail!("The pane is already in the current window");
+        }
+
+        let value = home.stdout.trim_end_matches('\n');
+        let _ = control::run(&["set-option", "-p", "-t", pane_id, HOME_OPTION, value]);
+        let output = control::run(&["join-pane", "-h", "-s", pane_id, "-t", &destination])
+            .context("Failed to join pane")?;
+        if !output.success {
+            let _ = control::run(&["set-option", "-p", "-u", "-t", pane_id, HOME_OPTION]);
+            anyhow::bail!("join-pane failed: {}", output.stderr.trim());
+        }
+        Ok(())
+    }
+
+    /// Whether a pane still exists
+    pub fn pane_exists(pane_id: &str) -> bool {
+        control::run(&["display-message", "-p", "-t", pane_id, "#{pane_id}"])
+            .is_ok_and(|output| output.success && output.stdout.trim() == pane_id)
+    }
+
+    /// Whether a pane was joined into another window by `join_pane`
+    pub fn is_joined(pane_id: &str) -> bool {
+        control::run(&["show-options", "-p", "-v", "-t", pane_id, HOME_OPTION])
+            .is_ok_and(|output| output.success && PaneHome::parse(&output.stdout).is_some())
+    }
+
+    /// Return a pane joined by `join_pane` to the window it came from
+    ///
+    /// When that window closed (the pane was its only one), the pane is
+    /// broken out into a new window with the same name, at the same index if
+    /// it is free, recreating the session if needed. Returns the
+    /// `session:window` it went back to.
+    pub fn send_pane_back(pane_id: &str) -> Result<String> {
+        let output = control::run(&["show-options", "-p", "-v", "-t", pane_id, HOME_OPTION])?;
+        let Some(home) = PaneHome::parse(&output.stdout).filter(|_| output.success) else {
+            anyhow::bail!("The pane was not joined from another window");
+        };
+        let window_alive = control::run(&[
+            "display-message",
+            "-p",
+            "-t",
+            &home.window_id,
+            "#{window_id}",
+        ])
+        .is_ok_and(|output| output.success && output.stdout.trim() == home.window_id);
+        if window_alive {
+            let output = control::run(&[
+                "join-pane",
+                "-d",
+                "-h",
+                "-s",
+                pane_id,
+                "-t",
+                &home.window_id,
+            ])
+            .context("Failed to send pane back")?;
+            if !output.success {
+                anyhow::bail!("join-pane failed: {}", output.stderr.trim());
+            }
+        } else {
+            Self::break_pane_home(pane_id, &home)?;
+        }
+
+        let _ = control::run(&["set-option", "-p", "-u", "-t", pane_id, HOME_OPTION]);
+        Ok(format!("{}:{}", home.session, home.window_name))
+    }
+
+    /// Break a pane out into a new window recreating `home`
+    ///
+    /// The session itself closed if the pane was its last one; it is
+    /// recreated with a placeholder window, closed once the pane is in.
+    fn break_pane_home(pane_id: &str, home: &PaneHome) -> Result<()> {
+        let placeholder = if Self::list_sessions()?.contains(&home.session) {
+            None
+        } else {
+            let output = control::spawn(&[
+                "new-session",
+                "-d",
+                "-P",
+                "-F",
+                "#{window_id}",
+                "-s",
+                &home.session,
+            ])
+            .context("Failed to create session")?;
+            if !output.success {
+                anyhow::bail!("Failed to create session {}", home.session);
+            }
+            Some(output.stdout.trim().to_string())
+        };
+
+        let session = format!("={}:", home.session);
+        let output = control::run(&[
+            "break-pane",
+            "-d",
+            "-P",
+            "-F",
+            "#{window_id}",
+            "-s",
+            pane_id,
+            "-t",
+            &session,
+            "-n",
+            &home.window_name,
+        ])
+        .context("Failed to send pane back")?;
+        if !output.success {
+            anyhow::bail!("break-pane failed: {}", output.stderr.trim());
+        }
+
+        if let Some(placeholder) = placeholder {
+            let _ = control::run(&["kill-window", "-t", &placeholder]);
+        }
+        // Back to the original index, unless another window took it
+        let index = format!("={}:{}", home.session, home.window_index);
+        let _ = control::run(&["move-window", "-s", output.stdout.trim(), "-t", &index]);
+        Ok(())
+    }
+
+    /// Kill a tmux session
+    pub fn kill_session(session: &str) -> Result<()> {
+        let output =
+            control::spawn(&["kill-session", "-t", session]).context("Failed to kill session")?;
+
+        if !output.success {
+            anyhow::bail!("Failed to kill session {}", session);
+        }
+
+        Ok(())
+    }
+
+    /// Rename a tmux session
+    pub fn rename_session(old_name: &str, new_name: &str) -> Result<()> {
+        let output = control::spawn(&["rename-session", "-t", old_name, new_name])
+            .context("Failed to rename session")?;
+
+        if !output.success {
+            anyhow::bail!("Failed to rename session {} to {}", old_name, new_name);
+        }
+
+        Ok(())
+    }
+
+    /// Rename the window containing `target`
+    ///
+    /// This also turns off tmux's `automatic-rename` for the window.
+    pub fn rename_window(target: &str, name: &str) -> Result<()> {
+        let output = control::run(&["rename-window", "-t", target, name])
+            .context("Failed to rename window")?;
+
+        if !output.success {
+            anyhow::bail!("Failed to rename window of {} to {}", target, name);
+        }
+
+        Ok(())
+    }
+
+    /// Contents of the most recent paste buffer, if there is one
+    pub fn show_buffer() -> Option<String> {
+        let output = control::spawn(&["show-buffer"]).ok()?;
+        output.success.then_some(output.stdout)
+    }
+
+    /// Version of tmux, e.g. "tmux 3.4", if it can be run
+    pub fn version() -> Option<String> {
+        let output = control::spawn(&["-V"]).ok()?;
+        output.success.then(|| output.stdout.trim().to_string())
+    }
+
+    /// Get the current pane target (session:window.pane format)
+    pub fn current_pane() -> Result<Option<String>> {
+        let output = control::spawn(&[
+            "display-message",
+            "-p",
+            "#{session_name}:#{window_index}.#{pane_index}",
+        ])
+        .context("Failed to get current pane")?;
+
+        if !output.success {
+            return Ok(None);
+        }
+
+        let target = output.stdout.trim().to_string();
+        if target.is_empty() {
+            Ok(None)
+        } else {
+            Ok(Some(target))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::mock::MockTmux;
+    use super::*;
+
+    fn pane(id: &str) -> Pane {
+        Pane {
+            id: id.to_string(),
+            pane_index: 0,
+            pid: 0,
+            current_path: PathBuf::from("/work"),
+            window_index: 0,
+            window_name: "claude".to_string(),
+            agent_marked: false,
+            current_command: "zsh".to_string(),
+            piped: false,
+        }
+    }
+
+    #[test]
+    fn test_dedupe_linked() {
+        let mut panes = vec![
+            ("api".to_string(), false, pane("%1")),
+            ("web".to_string(), false, pane("%2")),
+            ("shared".to_string(), true, pane("%1")),
+            ("web".to_string(), false, pane("%1")),
+        ];
+        dedupe_linked(&mut panes);
+
+        let kept: Vec<(&str, &str)> = panes
+            .iter()
+            .map(|(session, _, pane)| (session.as_str(), pane.id.as_str()))
+            .collect();
+        assert_eq!(kept, vec![("shared", "%1"), ("web", "%2")]);
+    }
+
+    #[test]
+    fn test_parse_pane_home() {
+        assert_eq!(
+            PaneHome::parse("api:@4:2:agent:logs\n"),
+            Some(PaneHome {
+                session: "api".to_string(),
+                window_id: "@4".to_string(),
+                window_index: "2".to_string(),
+                window_name: "agent:logs".to_string(),
+            })
+        );
+        assert_eq!(PaneHome::parse(""), None);
+        assert_eq!(PaneHome::parse("api:@4"), None);
+    }
+
+    #[test]
+    fn test_claude_directory() {
+        let pane = PathBuf::from("/work/api");
+        let here = std::env::current_dir().unwrap();
+
+        assert_eq!(claude_directory(pane.clone(), None), (pane.clone(), None));
+        assert_eq!(
+            claude_directory(pane.clone(), Some(&PathBuf::from("/work/api"))),
+            (pane.clone(), None)
+        );
+        assert_eq!(
+            claude_directory(pane.clone(), Some(&PathBuf::from("/work/web (deleted)"))),
+            (PathBuf::from("/work/web"), Some(pane))
+        );
+        // An unresolved form of the same directory is no mismatch
+        let unresolved = here.join(".");
+        assert_eq!(
+            claude_directory(unresolved.clone(), Some(&here)),
+            (unresolved, None)
+        );
+    }
+
+    #[test]
+    fn test_display_popup() {
+        let mock = MockTmux::new()
+            .reply("display-message -p -c /dev/pts/3", "80 24\n")
+            .reply("display-popup", "")
+            .install();
+        Tmux::display_popup_on(
+            "/dev/pts/3",
+            110,
+            18,
+            Path::new("/work"),
+            "claude-tmux --once",
+        )
+        .unwrap();
+        // Shrunk to the client's width
+        assert_eq!(
+            mock.calls()[1],
+            "display-popup -E -c /dev/pts/3 -w 80 -h 18 -d /work claude-tmux --once"
+        );
+
+        MockTmux::new()
+            .fail("display-popup", "unknown command")
+            .install();
+        assert!(Tmux::display_popup_on("/dev/pts/3", 110, 18, Path::new("/"), "true").is_err());
+        backend::set_thread_backend(None);
+    }
+
+    #[test]
+    fn test_restart_only_at_shell() {
+        let mock = MockTmux::new()
+            .reply("display-message -p -t %1 #{pane_current_command}", "vim\n")
+            .install();
+        let error = Tmux::restart_claude("%1", "claude").unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "%1 runs vim, not a shell; nothing was sent"
+        );
+        assert_eq!(mock.calls().len(), 1);
+
+        let mock = MockTmux::new()
+            .reply("display-message -p -t %1 #{pane_current_command}", "-zsh\n")
+            .reply("display-message -p -t %1 #{pane_in_mode}", "0\n")
+            .reply("send-keys", "")
+            .install();
+        Tmux::restart_claude("%1", "claude").unwrap();
+        assert_eq!(
+            mock.calls().last().unwrap(),
+            "send-keys -t %1 claude --continue Enter"
+        );
+        backend::set_thread_backend(None);
+    }
+
+    #[test]
+    fn test_send_only_to_claude() {
+        use std::os::unix::process::CommandExt;
+
+        // A process the default rules take for Claude
+        let mut claude = std::process::Command::new("sleep")
+            .arg0("/opt/bin/claude")
+            .arg("5")
+            .spawn()
+            .unwrap();
+        // Its command line shows once exec completes
+        for _ in 0..100 {
+            if process::claude_pid(claude.id()).is_some() {
+                break;
+            }
+            std::thread::sleep(std::time::Duration::from_millis(10));
+        }
+        let pane_pid = claude.id().to_string();
+        let mock = MockTmux::new()
+            .reply("display-message -p -t %1 #{pane_pid}", &pane_pid)
+            .reply("display-message -p -t %1 #{pane_in_mode}", "0\n")
+            .reply("send-keys", "")
+            .install();
+        let sent = Tmux::send_prompt("%1", "hello").and(Tmux::send_key("%1", "Escape"));
+        let calls = mock.calls();
+
+        claude.kill().unwrap();
+        claude.wait().unwrap();
+        let mock = MockTmux::new()
+            .reply("display-message -p -t %1 #{pane_pid}", &pane_pid)
+            .reply("display-message -p -t %1 #{pane_in_mode}", "0\n")
+            .reply("send-keys", "")
+            .install();
+        let gone = [
+            Tmux::send_prompt("%1", "hello").unwrap_err(),
+            Tmux::send_key("%1", "Escape").unwrap_err(),
+        ];
+        let gone_calls = mock.calls();
+        backend::set_thread_backend(None);
+
+        sent.unwrap();
+        assert!(calls.contains(&"send-keys -t %1 -l hello".to_string()));
+        assert!(calls.contains(&"send-keys -t %1 Escape".to_string()));
+        for error in gone {
+            assert_eq!(
+                error.to_string(),
+                "Claude no longer runs in %1; nothing was sent"
+            );
+        }
+        assert!(!gone_calls.iter().any(|call| call.starts_with("send-keys")));
+    }
+}
@@ -0,0 +1,543 @@
+//! Process tree lookups under pane PIDs
+//!
+//! Rather than scanning the whole process table, only the trees below pane
+//! PIDs are walked: on Linux through `/proc/<pid>/task/*/children`, elsewhere
+//! with `pgrep -P`. The Claude process found under a pane is cached, keyed by
+//! the pane PID and its start time (so a reused PID never hits a stale
+//! entry), and reused as long as that Claude process keeps running.
+//!
+//! Claude processes are recognized by their command line, following the
+//! `[claude_process]` rules of the config (by default, `bin/claude`
+//! anywhere in it).
+
+use std::collections::{HashMap, HashSet};
+use std::path::PathBuf;
+use std::process::Command;
+use std::sync::{Mutex, OnceLock, RwLock};
+
+/// Deepest descendant level searched below a pane
+const MAX_DEPTH: usize = 16;
+
+/// Cache entries kept before the cache is reset
+const MAX_CACHE_ENTRIES: usize = 1024;
+
+/// A process identified by PID and start time
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+struct ProcessId {
+    pid: u32,
+    start: String,
+}
+
+impl ProcessId {
+    fn of(pid: u32, matcher: &mut ClaudeMatcher) -> Option<Self> {
+        Some(Self {
+            pid,
+            start: matcher.start_time(pid)?,
+        })
+    }
+
+    /// Whether the same process is still running
+    fn is_alive(&self, matcher: &mut ClaudeMatcher) -> bool {
+        matcher.start_time(self.pid).as_deref() == Some(self.start.as_str())
+    }
+}
+
+/// Claude process found under each pane
+static CACHE: OnceLock<Mutex<HashMap<ProcessId, ProcessId>>> = OnceLock::new();
+
+/// Rules in use (see `set_claude_process`)
+static RULES: OnceLock<RwLock<ClaudeProcess>> = OnceLock::new();
+
+/// How Claude processes are recognized by their command line
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ClaudeProcess {
+    /// Text found anywhere in the command line, arguments joined by spaces
+    pub patterns: Vec<String>,
+    /// Arguments matched exactly, or by their file name (`claude` matches
+    /// `/opt/bin/claude`)
+    pub args: Vec<String>,
+    /// Text that rules a command line out, even if it matches
+    pub exclude: Vec<String>,
+}
+
+impl Default for ClaudeProcess {
+    fn default() -> Self {
+        Self {
+            patterns: vec!["bin/claude".to_string()],
+            args: Vec::new(),
+            exclude: Vec::new(),
+        }
+    }
+}
+
+impl ClaudeProcess {
+    /// Whether a command line is that of a Claude process
+    pub fn matches(&self, argv: &[String]) -> bool {
+        let line = argv.join(" ");
+        if self.exclude.iter().any(|e| line.contains(e.as_str())) {
+            return false;
+        }
+        self.patterns.iter().any(|p| line.contains(p.as_str()))
+            || argv.iter().any(|arg| {
+                let name = arg.rsplit('/').next().unwrap_or(arg);
+                self.args.iter().any(|a| a == arg || a == name)
+            })
+    }
+}
+
+/// Recognize Claude processes with `rules` from now on
+///
+/// Panes already matched are looked up again.
+pub fn set_claude_process(rules: ClaudeProcess) {
+    let lock = RULES.get_or_init(|| RwLock::new(ClaudeProcess::default()));
+    let mut current = lock.write().unwrap_or_else(|e| e.into_inner());
+    if *current != rules {
+        *current = rules;
+        if let Some(cache) = CACHE.get() {
+            cache.lock().unwrap_or_else(|e| e.into_inner()).clear();
+        }
+    }
+}
+
+fn rules() -> ClaudeProcess {
+    RULES
+        .get()
+        .map(|lock| lock.read().unwrap_or_else(|e| e.into_inner()).clone())
+        .unwrap_or_default()
+}
+
+/// Pane PIDs that are, or have as a descendant, a Claude process
+pub fn panes_with_claude(pane_pids: &[u32]) -> HashSet<u32> {
+    let mut cache = CACHE
+        .get_or_init(|| Mutex::new(HashMap::new()))
+        .lock()
+        .unwrap_or_else(|e| e.into_inner());
+    if cache.len() > MAX_CACHE_ENTRIES {
+        cache.clear();
+    }
+
+    let mut matcher = ClaudeMatcher::default();
+    let mut found = HashSet::new();
+
+    for &pane_pid in pane_pids {
+        let Some(pane) = ProcessId::of(pane_pid, &mut matcher) else {
+            continue;
+        };
+
+        if cache
+            .get(&pane)
+            .is_some_and(|claude| claude.is_alive(&mut matcher))
+        {
+            found.insert(pane_pid);
+            continue;
+        }
+
+        let claude = find_claude(pane_pid, &mut matcher);
+        match claude.and_then(|pid| ProcessId::of(pid, &mut matcher)) {
+            Some(claude) => {
+                cache.insert(pane, claude);
+                found.insert(pane_pid);
+            }
+            None => {
+                cache.remove(&pane);
+            }
+        }
+    }
+
+    found
+}
+
+/// Working directory of the Claude process under each pane, by pane PID
+///
+/// Claude keeps the directory it was started in even when the pane's
+/// current path (that of its foreground process) changes. Panes without a
+/// Claude process, or whose directory can't be read, are left out.
+pub fn claude_cwds(pane_pids: &[u32]) -> HashMap<u32, PathBuf> {
+    let claude_pids: Vec<(u32, u32)> = {
+        let cache = CACHE
+            .get_or_init(|| Mutex::new(HashMap::new()))
+            .lock()
+            .unwrap_or_else(|e| e.into_inner());
+        let mut matcher = ClaudeMatcher::default();
+        pane_pids
+            .iter()
+            .filter_map(|&pane_pid| {
+                let cached = ProcessId::of(pane_pid, &mut matcher)
+                    .and_then(|pane| cache.get(&pane).cloned())
+                    .filter(|claude| claude.is_alive(&mut matcher))
+                    .map(|claude| claude.pid);
+                let claude = cached.or_else(|| find_claude(pane_pid, &mut matcher))?;
+                Some((pane_pid, claude))
+            })
+            .collect()
+    };
+    let cwds = process_cwds(&claude_pids.iter().map(|&(_, pid)| pid).collect::<Vec<_>>());
+    claude_pids
+        .into_iter()
+        .filter_map(|(pane_pid, pid)| Some((pane_pid, cwds.get(&pid)?.clone())))
+        .collect()
+}
+
+#[cfg(target_os = "linux")]
+fn process_cwds(pids: &[u32]) -> HashMap<u32, PathBuf> {
+    pids.iter()
+        .filter_map(|&pid| Some((pid, std::fs::read_link(format!("/proc/{}/cwd", pid)).ok()?)))
+        .collect()
+}
+
+/// Working directories of processes, from a single `lsof` call
+#[cfg(not(target_os = "linux"))]
+fn process_cwds(pids: &[u32]) -> HashMap<u32, PathBuf> {
+    if pids.is_empty() {
+        return HashMap::new();
+    }
+    let list: Vec<String> = pids.iter().map(u32::to_string).collect();
+    Command::new("lsof")
+        .args(["-a", "-d", "cwd", "-Fn", "-p", &list.join(",")])
+        .output()
+        .map(|output| parse_lsof_cwds(&String::from_utf8_lossy(&output.stdout)))
+        .unwrap_or_default()
+}
+
+/// Parse `lsof -Fn` output: a `p<pid>` line, then `n<path>` for its file
+#[cfg(any(not(target_os = "linux"), test))]
+fn parse_lsof_cwds(output: &str) -> HashMap<u32, PathBuf> {
+    let mut cwds = HashMap::new();
+    let mut pid = None;
+    for line in output.lines() {
+        if let Some(value) = line.strip_prefix('p') {
+            pid = value.parse().ok();
+        } else if let (Some(path), Some(pid)) = (line.strip_prefix('n'), pid) {
+            cwds.insert(pid, PathBuf::from(path));
+        }
+    }
+    cwds
+}
+
+/// PID of the Claude process at or below `pane_pid`
+pub fn claude_pid(pane_pid: u32) -> Option<u32> {
+    find_claude(pane_pid, &mut ClaudeMatcher::default())
+}
+
+/// Environment variables of a process
+#[cfg(target_os = "linux")]
+pub fn environment(pid: u32) -> Option<Vec<(String, String)>> {
+    let environ = std::fs::read(format!("/proc/{}/environ", pid)).ok()?;
+    Some(parse_environ(&environ))
+}
+
+/// Environment variables of a process; other users' processes and most
+/// systems without `/proc` don't expose them
+#[cfg(not(target_os = "linux"))]
+pub fn environment(_pid: u32) -> Option<Vec<(String, String)>> {
+    None
+}
+
+/// `NAME=value` entries separated by NUL bytes, as in `/proc/<pid>/environ`
+fn parse_environ(environ: &[u8]) -> Vec<(String, String)> {
+    environ
+        .split(|&b| b == 0)
+        .filter_map(|entry| {
+            let entry = String::from_utf8_lossy(entry);
+            let (name, value) = entry.split_once('=')?;
+            (!name.is_empty()).then(|| (name.to_string(), value.to_string()))
+        })
+        .collect()
+}
+
+/// Executable a process runs
+#[cfg(target_os = "linux")]
+pub fn executable(pid: u32) -> Option<PathBuf> {
+    std::fs::read_link(format!("/proc/{}/exe", pid)).ok()
+}
+
+/// Executable a process runs
+#[cfg(not(target_os = "linux"))]
+pub fn executable(pid: u32) -> Option<PathBuf> {
+    let output = Command::new("ps")
+        .args(["-o", "comm=", "-p", &pid.to_string()])
+        .output()
+        .ok()?;
+    let comm = String::from_utf8_lossy(&output.stdout).trim().to_string();
+    (output.status.success() && !comm.is_empty()).then(|| PathBuf::from(comm))
+}
+
+/// Command line of a process
+#[cfg(target_os = "linux")]
+pub fn command_line(pid: u32) -> Option<Vec<String>> {
+    let cmdline = std::fs::read(format!("/proc/{}/cmdline", pid)).ok()?;
+    Some(
+        cmdline
+            .split(|&b| b == 0)
+            .filter(|arg| !arg.is_empty())
+            .map(|arg| String::from_utf8_lossy(arg).to_string())
+            .collect(),
+    )
+}
+
+/// Command line of a process; arguments containing spaces are split apart,
+/// as `ps` doesn't quote them
+#[cfg(not(target_os = "linux"))]
+pub fn command_line(pid: u32) -> Option<Vec<String>> {
+    let output = Command::new("ps")
+        .args(["-o", "args=", "-p", &pid.to_string()])
+        .output()
+        .ok()?;
+    output.status.success().then(|| {
+        String::from_utf8_lossy(&output.stdout)
+            .split_whitespace()
+            .map(str::to_string)
+            .collect()
+    })
+}
+
+/// Breadth-first search for a Claude process at or below `root`
+fn find_claude(root: u32, matcher: &mut ClaudeMatcher) -> Option<u32> {
+    let mut level = vec![root];
+    let mut visited = HashSet::new();
+
+    for _ in 0..=MAX_DEPTH {
+        if level.is_empty() {
+            break;
+        }
+        if let Some(&pid) = level.iter().find(|&&pid| matcher.is_claude(pid)) {
+            return Some(pid);
+        }
+        level = level
+            .iter()
+            .flat_map(|&pid| children(pid))
+            .filter(|pid| visited.insert(*pid))
+            .collect();
+    }
+
+    None
+}
+
+/// Recognizes Claude processes by their command line, with the rules in
+/// use when it was created, and tells processes apart by start time
+struct ClaudeMatcher {
+    rules: ClaudeProcess,
+    /// Every process from `ps`, listed once per lookup where /proc is
+    /// unavailable
+    #[cfg(not(target_os = "linux"))]
+    processes: Option<HashMap<u32, ListedProcess>>,
+}
+
+impl Default for ClaudeMatcher {
+    fn default() -> Self {
+        Self {
+            rules: rules(),
+            #[cfg(not(target_os = "linux"))]
+            processes: None,
+        }
+    }
+}
+
+impl ClaudeMatcher {
+    #[cfg(target_os = "linux")]
+    fn is_claude(&mut self, pid: u32) -> bool {
+        command_line(pid).is_some_and(|argv| self.rules.matches(&argv))
+    }
+
+    #[cfg(target_os = "linux")]
+    fn start_time(&mut self, pid: u32) -> Option<String> {
+        start_time(pid)
+    }
+
+    #[cfg(not(target_os = "linux"))]
+    fn is_claude(&mut self, pid: u32) -> bool {
+        self.processes().get(&pid).is_some_and(|p| p.claude)
+    }
+
+    /// Start time of a process, as `ps` prints it (`lstart`)
+    #[cfg(not(target_os = "linux"))]
+    fn start_time(&mut self, pid: u32) -> Option<String> {
+        self.processes().get(&pid).map(|p| p.start.clone())
+    }
+
+    #[cfg(not(target_os = "linux"))]
+    fn processes(&mut self) -> &HashMap<u32, ListedProcess> {
+        let rules = &self.rules;
+        self.processes.get_or_insert_with(|| {
+            Command::new("ps")
+                .args(["-A", "-o", "pid=,lstart=,args="])
+                .output()
+                .map(|output| parse_ps(&String::from_utf8_lossy(&output.stdout), rules))
+                .unwrap_or_default()
+        })
+    }
+}
+
+/// A process listed by `ps`
+#[cfg(any(not(target_os = "linux"), test))]
+#[derive(Debug, PartialEq, Eq)]
+struct ListedProcess {
+    /// Start time, such as "Mon Jan  8 09:12:44 2024" with spaces collapsed
+    start: String,
+    /// Whether its command line matches the rules
+    claude: bool,
+}
+
+/// Parse `ps -o pid=,lstart=,args=` output; arguments containing spaces are
+/// split apart, as `ps` doesn't quote them
+#[cfg(any(not(target_os = "linux"), test))]
+fn parse_ps(output: &str, rules: &ClaudeProcess) -> HashMap<u32, ListedProcess> {
+    output
+        .lines()
+        .filter_map(|line| {
+            let mut words = line.split_whitespace();
+            let pid = words.next()?.parse().ok()?;
+            // Weekday, month, day, time and year
+            let start: Vec<&str> = words.by_ref().take(5).collect();
+            if start.len() < 5 {
+                return None;
+            }
+            let argv: Vec<String> = words.map(str::to_string).collect();
+            let process = ListedProcess {
+                start: start.join(" "),
+                claude: rules.matches(&argv),
+            };
+            Some((pid, process))
+        })
+        .collect()
+}
+
+/// Direct children of a process
+///
+/// `children` files need a kernel built with `CONFIG_PROC_CHILDREN`; without
+/// them this falls back to `pgrep -P`.
+#[cfg(target_os = "linux")]
+fn children(pid: u32) -> Vec<u32> {
+    let Ok(tasks) = std::fs::read_dir(format!("/proc/{}/task", pid)) else {
+        return Vec::new();
+    };
+    let lists: Vec<String> = tasks
+        .flatten()
+        .filter_map(|task| std::fs::read_to_string(task.path().join("children")).ok())
+        .collect();
+    if lists.is_empty() {
+        return pgrep_children(pid);
+    }
+    lists
+        .iter()
+        .flat_map(|list| list.split_whitespace().filter_map(|p| p.parse().ok()))
+        .collect()
+}
+
+/// Direct children of a process
+#[cfg(not(target_os = "linux"))]
+fn children(pid: u32) -> Vec<u32> {
+    pgrep_children(pid)
+}
+
+fn pgrep_children(pid: u32) -> Vec<u32> {
+    pids_from(Command::new("pgrep").args(["-P", &pid.to_string()]))
+        .into_iter()
+        .collect()
+}
+
+/// Start time of a process, in an OS-specific but stable format
+#[cfg(target_os = "linux")]
+fn start_time(pid: u32) -> Option<String> {
+    let stat = std::fs::read_to_string(format!("/proc/{}/stat", pid)).ok()?;
+    // Fields after the parenthesized command name start at field 3 (state);
+    // starttime is field 22
+    let after_comm = &stat[stat.rfind(')')? + 1..];
+    after_comm.split_whitespace().nth(19).map(str::to_string)
+}
+
+/// PIDs printed one per line by a command such as `pgrep`
+fn pids_from(command: &mut Command) -> HashSet<u32> {
+    let Ok(output) = command.output() else {
+        return HashSet::new();
+    };
+    String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .filter_map(|line| line.trim().parse().ok())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_children_and_start_time() {
+        let mut child = Command::new("sleep").arg("5").spawn().unwrap();
+        let own = std::process::id();
+
+        let kids = children(own);
+        let identity = ProcessId::of(child.id(), &mut ClaudeMatcher::default()).unwrap();
+        let alive = identity.is_alive(&mut ClaudeMatcher::default());
+
+        child.kill().unwrap();
+        child.wait().unwrap();
+
+        assert!(kids.contains(&child.id()));
+        assert!(alive);
+        assert!(!identity.is_alive(&mut ClaudeMatcher::default()));
+    }
+
+    #[test]
+    fn test_parse_environ() {
+        let environ = b"HOME=/home/me\0CLAUDE_CONFIG_DIR=/tmp/a=b\0BROKEN\0=x\0EMPTY=\0";
+        assert_eq!(
+            parse_environ(environ),
+            vec![
+                ("HOME".to_string(), "/home/me".to_string()),
+                ("CLAUDE_CONFIG_DIR".to_string(), "/tmp/a=b".to_string()),
+                ("EMPTY".to_string(), String::new()),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_claude_process_matches() {
+        let argv = |line: &str| -> Vec<String> { line.split(' ').map(str::to_string).collect() };
+
+        let default = ClaudeProcess::default();
+        assert!(default.matches(&argv("/usr/local/bin/claude --continue")));
+        assert!(default.matches(&argv("node /home/me/.npm/bin/claude")));
+        assert!(!default.matches(&argv("claude")));
+        assert!(!default.matches(&argv("vim claude.md")));
+
+        let rules = ClaudeProcess {
+            patterns: vec!["claude-code/cli.js".to_string()],
+            args: vec!["claude".to_string(), "cc-wrapper".to_string()],
+            exclude: vec!["claude-monitor".to_string()],
+        };
+        assert!(rules.matches(&argv("node /opt/claude-code/cli.js")));
+        assert!(rules.matches(&argv("claude --resume")));
+        assert!(rules.matches(&argv("sh /home/me/bin/cc-wrapper")));
+        assert!(!rules.matches(&argv("vim claude.md")));
+        assert!(!rules.matches(&argv("/usr/bin/claude-monitor claude")));
+    }
+
+    #[test]
+    fn test_parse_ps() {
+        let output = "  812 Mon Jan  8 09:12:44 2024 /usr/local/bin/claude --continue\n\
+                      913 Tue Jan  9 10:00:01 2024 -zsh\n\
+                      bogus\n";
+        let processes = parse_ps(output, &ClaudeProcess::default());
+        assert_eq!(
+            processes.get(&812),
+            Some(&ListedProcess {
+                start: "Mon Jan 8 09:12:44 2024".to_string(),
+                claude: true,
+            })
+        );
+        assert_eq!(processes.get(&913).map(|p| p.claude), Some(false));
+        assert_eq!(processes.len(), 2);
+    }
+
+    #[test]
+    fn test_process_cwds() {
+        let own = std::process::id();
+        let cwds = process_cwds(&[own]);
+        assert_eq!(cwds.get(&own), Some(&std::env::current_dir().unwrap()));
+
+        let parsed = parse_lsof_cwds("p812\nfcwd\nn/work/api\np913\nfcwd\nn/work/my app\n");
+        assert_eq!(parsed.get(&812), Some(&PathBuf::from("/work/api")));
+        assert_eq!(parsed.get(&913), Some(&PathBuf::from("/work/my app")));
+    }
+}
@@ -0,0 +1,137 @@
+//! Trash for deleted worktrees (`worktree_trash_days`)
+//!
+//! With `worktree_trash_days` set, deleting a worktree moves its directory
+//! to `trash/` in the data directory instead of deleting it, so uncommitted
+//! and untracked files can still be recovered. Each entry is named
+//! `<unix time>-<directory name>`; entries older than the setting are
+//! deleted whenever another worktree goes to the trash.
+
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result};
+
+use crate::store::{data_dir, now_unix};
+
+const DAY_SECS: u64 = 24 * 60 * 60;
+
+/// Where trashed worktrees are kept
+pub fn dir() -> PathBuf {
+    data_dir().join("trash")
+}
+
+/// Move a directory into the trash, returning its new path
+pub fn move_to_trash(path: &Path) -> Result<PathBuf> {
+    move_into(path, &dir(), now_unix())
+}
+
+/// Delete the entries trashed more than `days` days ago, returning how many
+pub fn purge(days: u64) -> Result<usize> {
+    let expired = expired(&dir(), now_unix(), days);
+    for path in &expired {
+        fs::remove_dir_all(path).with_context(|| format!("Failed to delete {}", path.display()))?;
+    }
+    Ok(expired.len())
+}
+
+fn move_into(path: &Path, trash: &Path, now: u64) -> Result<PathBuf> {
+    let name = path
+        .file_name()
+        .with_context(|| format!("Cannot trash {}", path.display()))?
+        .to_string_lossy();
+    fs::create_dir_all(trash).with_context(|| format!("Failed to create {}", trash.display()))?;
+    let dest = (1..)
+        .map(|n| match n {
+            1 => trash.join(format!("{}-{}", now, name)),
+            n => trash.join(format!("{}-{}-{}", now, name, n)),
+        })
+        .find(|dest| !dest.exists())
+        .unwrap_or_else(|| trash.join(name.as_ref()));
+
+    if fs::rename(path, &dest).is_err() {
+        // Another filesystem: copy, then delete
+        if let Err(e) = copy_dir(path, &dest) {
+            let _ = fs::remove_dir_all(&dest);
+            return Err(e).with_context(|| format!("Failed to copy to {}", dest.display()));
+        }
+        fs::remove_dir_all(path)
+            .with_context(|| format!("Copied to {} but failed to delete", dest.display()))?;
+    }
+    Ok(dest)
+}
+
+/// Entries of `trash` trashed more than `days` days before `now`
+fn expired(trash: &Path, now: u64, days: u64) -> Vec<PathBuf> {
+    let Ok(entries) = fs::read_dir(trash) else {
+        return Vec::new();
+    };
+    let mut expired: Vec<PathBuf> = entries
+        .flatten()
+        .filter(|entry| {
+            let name = entry.file_name();
+            let trashed_at = name
+                .to_string_lossy()
+                .split_once('-')
+                .and_then(|(time, _)| time.parse::<u64>().ok());
+            trashed_at.is_some_and(|t| now.saturating_sub(t) > days * DAY_SECS)
+        })
+        .map(|entry| entry.path())
+        .collect();
+    expired.sort();
+    expired
+}
+
+/// Copy a directory tree, keeping symlinks as links
+fn copy_dir(from: &Path, to: &Path) -> io::Result<()> {
+    fs::create_dir(to)?;
+    for entry in fs::read_dir(from)? {
+        let entry = entry?;
+        let (source, dest) = (entry.path(), to.join(entry.file_name()));
+        let file_type = entry.file_type()?;
+        if file_type.is_symlink() {
+            std::os::unix::fs::symlink(fs::read_link(&source)?, &dest)?;
+        } else if file_type.is_dir() {
+            copy_dir(&source, &dest)?;
+        } else {
+            fs::copy(&source, &dest)?;
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_trash() {
+        let base = std::env::temp_dir().join(format!("claude-tmux-trash-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&base);
+        let trash = base.join("trash");
+        let worktree = base.join("api-login");
+        fs::create_dir_all(worktree.join("src")).unwrap();
+        fs::write(worktree.join("src/wip.rs"), "fn wip() {}").unwrap();
+
+        let moved = move_into(&worktree, &trash, 1_000).unwrap();
+        assert_eq!(moved, trash.join("1000-api-login"));
+        assert!(!worktree.exists());
+        assert_eq!(
+            fs::read_to_string(moved.join("src/wip.rs")).unwrap(),
+            "fn wip() {}"
+        );
+
+        fs::create_dir_all(&worktree).unwrap();
+        assert_eq!(
+            move_into(&worktree, &trash, 1_000).unwrap(),
+            trash.join("1000-api-login-2")
+        );
+        fs::create_dir_all(base.join("copied")).unwrap();
+        copy_dir(&moved, &base.join("copied/api-login")).unwrap();
+        assert!(base.join("copied/api-login/src/wip.rs").exists());
+
+        assert!(expired(&trash, 1_000 + DAY_SECS, 1).is_empty());
+        assert_eq!(expired(&trash, 1_001 + DAY_SECS, 1).len(), 2);
+        let _ = fs::remove_dir_all(&base);
+    }
+}
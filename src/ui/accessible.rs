@@ -0,0 +1,74 @@
+//! Accessibility mode adjustments applied to a rendered frame
+//!
+//! Glyphs that need a Unicode-capable terminal font (status dots, arrows,
+//! box-drawing borders) are replaced by ASCII look-alikes of the same width,
+//! and text in the theme's `dim` and `accent` colors is redrawn in the
+//! palette's. The glyph replacement is also used on its own in ASCII mode, and colors
+//! can be removed entirely for `NO_COLOR`.
+
+use ratatui::{
+    buffer::Buffer,
+    style::{Color, Modifier},
+};
+
+use crate::config::Palette;
+
+use super::Theme;
+
+/// Rewrite every cell of `buffer`, drawn with `theme`, for accessibility mode
+pub fn adapt(buffer: &mut Buffer, palette: &Palette, theme: &Theme) {
+    to_ascii(buffer);
+    for cell in buffer.content.iter_mut() {
+        if cell.fg == theme.dim {
+            cell.fg = palette.dim;
+        } else if cell.fg == theme.accent {
+            cell.fg = palette.accent;
+        }
+        cell.modifier.remove(Modifier::DIM);
+    }
+}
+
+/// Replace every Unicode glyph of `buffer` that has an ASCII look-alike
+pub fn to_ascii(buffer: &mut Buffer) {
+    for cell in buffer.content.iter_mut() {
+        if let Some(ascii) = ascii_glyph(cell.symbol()) {
+            cell.set_symbol(ascii);
+        }
+    }
+}
+
+/// Remove every color of `buffer`
+///
+/// Cells drawn on a background (the selected row, message overlays) are
+/// shown in reverse video instead, so they still stand out.
+pub fn strip_colors(buffer: &mut Buffer) {
+    for cell in buffer.content.iter_mut() {
+        if cell.bg != Color::Reset {
+            cell.modifier.insert(Modifier::REVERSED);
+        }
+        cell.fg = Color::Reset;
+        cell.bg = Color::Reset;
+    }
+}
+
+/// ASCII replacement for a single-width glyph
+fn ascii_glyph(symbol: &str) -> Option<&'static str> {
+    Some(match symbol {
+        "─" | "━" | "—" | "–" | "−" => "-",
+        "│" | "┃" => "|",
+        "┌" | "┐" | "└" | "┘" | "├" | "┤" | "┬" | "┴" | "┼" | "╭" | "╮" | "╰" | "╯" => {
+            "+"
+        }
+        "●" | "•" | "⏺" | "✻" => "*",
+        "○" => "o",
+        "◐" | "⚠" => "!",
+        "✗" => "x",
+        "✓" => "+",
+        "▸" | "▶" | "→" | "↳" | "⎿" => ">",
+        "▾" | "▼" | "↓" => "v",
+        "↑" => "^",
+        "←" => "<",
+        "…" => ".",
+        _ => return None,
+    })
+}
@@ -2,21 +2,67 @@
 //!
 //! Provides rendering for all modal dialogs:
 //! - Confirmation dialogs (kill, merge PR, etc.)
-//! - Input dialogs (new session, rename, commit, new worktree, create PR)
+//! - Input dialogs (new session, rename, commit, new worktree, clone layout, create PR,
+//!   schedule prompt, credentials, SSH passphrase)
+//! - Pickers (prompt templates), the pending scheduled prompts list, and the
+//!   all-panes list
+//! - The keybinding editor
+//! - The sync summary
+
+use std::path::Path;
 
 use ratatui::{
-    layout::Alignment,
-    style::{Color, Modifier, Style},
+    layout::{Alignment, Rect},
+    style::{Modifier, Style},
     text::{Line, Span, Text},
     widgets::{Block, Borders, Clear, Paragraph, Wrap},
     Frame,
 };
 
-use crate::app::{App, CreatePullRequestField, NewSessionField, NewWorktreeField, SessionAction};
+use crate::app::{
+    ActionRun, App, CloneLayoutField, CreatePullRequestField, CredentialsField, NewSessionField,
+    NewWorktreeField, PaneArrangement, SchedulePromptField, SessionAction, SyncRow, SyncState,
+};
+use crate::archive::ArchivedSession;
+use crate::config::{Action, Keymap, PromptTemplate, SlashCommand, Workspace};
+use crate::git::{format_age, ConflictedFile, Issue, PendingOperation, SyncOutcome};
+use crate::i18n::{tr, trf};
+use crate::logging;
+use crate::schedule::{self, ScheduledJob};
+use crate::search::SearchMatch;
+use crate::session::{short_path, ClaudeCodeStatus, PaneEntry, Session};
+use crate::snapshot::Snapshot;
+use crate::stats::{Counter, Stats};
+use crate::store;
+use crate::workspace::{Member, MemberState};
+use crate::worktime::{format_seconds, BranchTotal};
 
 use super::help::centered_rect;
+use super::Theme;
+
+/// Commits a kill would leave on no remote, while they are counted and if
+/// there are any (only counted before killing or deleting a worktree)
+fn unpushed_line(app: &App) -> Option<Line<'static>> {
+    let theme = &super::theme(app);
+    if app.checking_unpushed() {
+        Some(Line::styled(
+            tr("Looking for commits on no remote…"),
+            Style::default().fg(theme.dim),
+        ))
+    } else {
+        (app.unpushed_commits > 0).then(|| {
+            Line::styled(
+                trf!("⚠ Commits on no remote: {}", app.unpushed_commits),
+                Style::default()
+                    .fg(theme.warning)
+                    .add_modifier(Modifier::BOLD),
+            )
+        })
+    }
+}
 
 pub fn render_confirm_action(frame: &mut Frame, app: &App) {
+    let theme = &super::theme(app);
     let instance = app.selected_instance();
     let session_name = instance.map(|i| i.session_name.as_str()).unwrap_or("?");
     let is_worktree = instance
@@ -28,45 +74,60 @@ pub fn render_confirm_action(frame: &mut Frame, app: &App) {
         .as_ref()
         .is_some_and(|c| instance.is_some_and(|i| c == &i.tmux_target()));
 
+    let unpushed = unpushed_line(app);
+
     match &app.pending_action {
         Some(SessionAction::KillAndDeleteWorktree) => {
             let worktree_path = instance
                 .map(|i| i.display_path())
                 .unwrap_or_else(|| "?".to_string());
 
-            let dialog_height = if is_current_pane { 11 } else { 9 };
+            let dialog_height = 9 + is_current_pane as u16 * 2 + unpushed.is_some() as u16;
             let area = centered_rect(55, dialog_height, frame.area());
 
             let block = Block::default()
-                .title(" Confirm ")
+                .title(tr(" Confirm "))
                 .borders(Borders::ALL)
-                .border_style(Style::default().fg(Color::Red));
+                .border_style(Style::default().fg(theme.error));
 
             let mut lines = vec![
-                Line::from(format!("Kill session '{}'", session_name)),
-                Line::from("AND delete worktree at:"),
+                Line::from(trf!("Kill session '{}'", session_name)),
+                Line::from(tr("AND delete worktree at:")),
                 Line::styled(
                     format!("  {}", worktree_path),
-                    Style::default().fg(Color::Yellow),
+                    Style::default().fg(theme.highlight),
                 ),
                 Line::raw(""),
-                Line::styled(
-                    "⚠ This will permanently delete the directory!",
-                    Style::default().fg(Color::Red).add_modifier(Modifier::BOLD),
-                ),
+                if app.config.worktree_trash_days > 0 {
+                    Line::styled(
+                        trf!(
+                            "The directory goes to the trash for {} days.",
+                            app.config.worktree_trash_days
+                        ),
+                        Style::default().fg(theme.highlight),
+                    )
+                } else {
+                    Line::styled(
+                        tr("⚠ This will permanently delete the directory!"),
+                        Style::default()
+                            .fg(theme.error)
+                            .add_modifier(Modifier::BOLD),
+                    )
+                },
             ];
 
+            lines.extend(unpushed);
             if is_current_pane {
                 lines.push(Line::styled(
-                    "⚠ This is your current session - tmux will exit!",
+                    tr("⚠ This is your current session - tmux will exit!"),
                     Style::default()
-                        .fg(Color::Yellow)
+                        .fg(theme.highlight)
                         .add_modifier(Modifier::BOLD),
                 ));
             }
 
             lines.push(Line::raw(""));
-            lines.push(Line::from("[Y]es  [n]o"));
+            lines.push(Line::from(tr("[Y]es  [n]o")));
 
             let paragraph = Paragraph::new(Text::from(lines))
                 .block(block)
@@ -80,11 +141,11 @@ pub fn render_confirm_action(frame: &mut Frame, app: &App) {
             let area = centered_rect(50, 5, frame.area());
 
             let block = Block::default()
-                .title(" Close Pull Request ")
+                .title(tr(" Close Pull Request "))
                 .borders(Borders::ALL)
-                .border_style(Style::default().fg(Color::Yellow));
+                .border_style(Style::default().fg(theme.warning));
 
-            let text = "Close this pull request without merging?\n\n[Y]es  [n]o";
+            let text = tr("Close this pull request without merging?\n\n[Y]es  [n]o");
             let paragraph = Paragraph::new(text)
                 .block(block)
                 .alignment(Alignment::Center)
@@ -97,11 +158,11 @@ pub fn render_confirm_action(frame: &mut Frame, app: &App) {
             let area = centered_rect(50, 5, frame.area());
 
             let block = Block::default()
-                .title(" Merge Pull Request ")
+                .title(tr(" Merge Pull Request "))
                 .borders(Borders::ALL)
-                .border_style(Style::default().fg(Color::Green));
+                .border_style(Style::default().fg(theme.success));
 
-            let text = "Merge this pull request?\n\n[Y]es  [n]o";
+            let text = tr("Merge this pull request?\n\n[Y]es  [n]o");
             let paragraph = Paragraph::new(text)
                 .block(block)
                 .alignment(Alignment::Center)
@@ -115,42 +176,101 @@ pub fn render_confirm_action(frame: &mut Frame, app: &App) {
             let area = centered_rect(58, dialog_height, frame.area());
 
             let block = Block::default()
-                .title(" Merge PR + Close ")
+                .title(tr(" Merge PR + Close "))
                 .borders(Borders::ALL)
-                .border_style(Style::default().fg(Color::Yellow));
+                .border_style(Style::default().fg(theme.warning));
 
             let mut lines = vec![
-                Line::from("This will:"),
+                Line::from(tr("This will:")),
                 Line::styled(
-                    "  • Merge the pull request",
-                    Style::default().fg(Color::Green),
+                    tr("  • Merge the pull request"),
+                    Style::default().fg(theme.success),
                 ),
             ];
 
             if is_worktree {
                 lines.push(Line::styled(
-                    "  • Remove the local worktree",
-                    Style::default().fg(Color::Red),
+                    tr("  • Remove the local worktree"),
+                    Style::default().fg(theme.error),
                 ));
             }
 
             lines.push(Line::styled(
-                format!("  • Kill session '{}'", session_name),
-                Style::default().fg(Color::Red),
+                trf!("  • Kill session '{}'", session_name),
+                Style::default().fg(theme.error),
             ));
 
             if is_current_pane {
                 lines.push(Line::raw(""));
                 lines.push(Line::styled(
-                    "⚠ This is your current session - tmux will exit!",
+                    tr("⚠ This is your current session - tmux will exit!"),
                     Style::default()
-                        .fg(Color::Yellow)
+                        .fg(theme.highlight)
                         .add_modifier(Modifier::BOLD),
                 ));
             }
 
             lines.push(Line::raw(""));
-            lines.push(Line::from("[Y]es  [n]o"));
+            lines.push(Line::from(tr("[Y]es  [n]o")));
+
+            let paragraph = Paragraph::new(Text::from(lines))
+                .block(block)
+                .alignment(Alignment::Center)
+                .wrap(Wrap { trim: true });
+
+            frame.render_widget(Clear, area);
+            frame.render_widget(paragraph, area);
+        }
+        Some(SessionAction::ForcePush) => {
+            let branch = instance
+                .and_then(|i| i.git_context.as_ref())
+                .map_or("?", |g| g.branch.as_str());
+            let area = centered_rect(55, 6, frame.area());
+
+            let block = Block::default()
+                .title(tr(" Confirm "))
+                .borders(Borders::ALL)
+                .border_style(Style::default().fg(theme.error));
+
+            let lines = vec![
+                Line::from(trf!("Force-push '{}'?", branch)),
+                Line::styled(
+                    tr("Commits only on the remote branch are dropped from it."),
+                    Style::default().fg(theme.highlight),
+                ),
+                Line::raw(""),
+                Line::from(tr("[Y]es  [n]o")),
+            ];
+
+            let paragraph = Paragraph::new(Text::from(lines))
+                .block(block)
+                .alignment(Alignment::Center)
+                .wrap(Wrap { trim: true });
+
+            frame.render_widget(Clear, area);
+            frame.render_widget(paragraph, area);
+        }
+        Some(SessionAction::AbortOperation(operation)) => {
+            let area = centered_rect(55, 6, frame.area());
+
+            let block = Block::default()
+                .title(tr(" Confirm "))
+                .borders(Borders::ALL)
+                .border_style(Style::default().fg(theme.error));
+
+            let question = match operation {
+                PendingOperation::Merge => tr("Abort the merge in progress?"),
+                PendingOperation::Rebase => tr("Abort the rebase in progress?"),
+            };
+            let lines = vec![
+                Line::from(question),
+                Line::styled(
+                    tr("Conflict resolutions made so far are lost."),
+                    Style::default().fg(theme.highlight),
+                ),
+                Line::raw(""),
+                Line::from(tr("[Y]es  [n]o")),
+            ];
 
             let paragraph = Paragraph::new(Text::from(lines))
                 .block(block)
@@ -165,13 +285,14 @@ pub fn render_confirm_action(frame: &mut Frame, app: &App) {
             let kills_session = matches!(action, SessionAction::Kill);
             let show_exit_warning = kills_session && is_current_pane;
 
-            let dialog_height = if show_exit_warning { 7 } else { 5 };
+            let dialog_height =
+                if show_exit_warning { 7 } else { 5 } + unpushed.is_some() as u16 * 2;
             let area = centered_rect(55, dialog_height, frame.area());
 
             let block = Block::default()
-                .title(" Confirm ")
+                .title(tr(" Confirm "))
                 .borders(Borders::ALL)
-                .border_style(Style::default().fg(Color::Red));
+                .border_style(Style::default().fg(theme.error));
 
             let mut lines = vec![Line::from(format!(
                 "{} '{}'?",
@@ -179,18 +300,23 @@ pub fn render_confirm_action(frame: &mut Frame, app: &App) {
                 session_name
             ))];
 
+            if let Some(unpushed) = unpushed {
+                lines.push(Line::raw(""));
+                lines.push(unpushed);
+            }
+
             if show_exit_warning {
                 lines.push(Line::raw(""));
                 lines.push(Line::styled(
-                    "⚠ This is your current session - tmux will exit!",
+                    tr("⚠ This is your current session - tmux will exit!"),
                     Style::default()
-                        .fg(Color::Yellow)
+                        .fg(theme.highlight)
                         .add_modifier(Modifier::BOLD),
                 ));
             }
 
             lines.push(Line::raw(""));
-            lines.push(Line::from("[Y]es  [n]o"));
+            lines.push(Line::from(tr("[Y]es  [n]o")));
 
             let paragraph = Paragraph::new(Text::from(lines))
                 .block(block)
@@ -206,6 +332,7 @@ pub fn render_confirm_action(frame: &mut Frame, app: &App) {
 
 pub fn render_new_session_dialog(
     frame: &mut Frame,
+    theme: &Theme,
     name: &str,
     path: &str,
     field: NewSessionField,
@@ -228,13 +355,13 @@ pub fn render_new_session_dialog(
     let area = centered_rect(60, dialog_height, frame.area());
 
     let block = Block::default()
-        .title(" New Session ")
+        .title(tr(" New Session "))
         .borders(Borders::ALL)
-        .border_style(Style::default().fg(Color::Cyan));
+        .border_style(Style::default().fg(theme.dialog));
 
     let name_style = if field == NewSessionField::Name {
         Style::default()
-            .fg(Color::Yellow)
+            .fg(theme.highlight)
             .add_modifier(Modifier::BOLD)
     } else {
         Style::default()
@@ -242,7 +369,7 @@ pub fn render_new_session_dialog(
 
     let path_style = if field == NewSessionField::Path {
         Style::default()
-            .fg(Color::Yellow)
+            .fg(theme.highlight)
             .add_modifier(Modifier::BOLD)
     } else {
         Style::default()
@@ -251,15 +378,22 @@ pub fn render_new_session_dialog(
     let mut lines = Vec::new();
 
     // Name field
-    lines.push(Line::from(vec![
-        Span::styled("Name: ", name_style),
+    let mut name_spans = vec![
+        Span::styled(tr("Name: "), name_style),
         Span::raw(name),
         if field == NewSessionField::Name {
             Span::raw("_")
         } else {
             Span::raw("")
         },
-    ]));
+    ];
+    if name.is_empty() {
+        name_spans.push(Span::styled(
+            tr(" (empty: named from the path)"),
+            Style::default().fg(theme.dim),
+        ));
+    }
+    lines.push(Line::from(name_spans));
 
     lines.push(Line::raw(""));
 
@@ -271,17 +405,15 @@ pub fn render_new_session_dialog(
     };
 
     let mut path_spans = vec![
-        Span::styled("Path: ", path_style),
-        Span::styled(path, Style::default().fg(Color::Yellow)),
+        Span::styled(tr("Path: "), path_style),
+        Span::styled(path, Style::default().fg(theme.highlight)),
     ];
 
     // Add ghost text (completion suffix)
     if let Some(ref ghost) = ghost_text {
         path_spans.push(Span::styled(
             ghost,
-            Style::default()
-                .fg(Color::DarkGray)
-                .add_modifier(Modifier::DIM),
+            Style::default().fg(theme.dim).add_modifier(Modifier::DIM),
         ));
     }
 
@@ -296,7 +428,7 @@ pub fn render_new_session_dialog(
     if field == NewSessionField::Path && !path_suggestions.is_empty() {
         lines.push(Line::styled(
             "      ────────────────────────────────────",
-            Style::default().fg(Color::DarkGray),
+            Style::default().fg(theme.dim),
         ));
 
         for (i, suggestion) in path_suggestions.iter().take(5).enumerate() {
@@ -304,31 +436,31 @@ pub fn render_new_session_dialog(
             let prefix = if is_selected { "    > " } else { "      " };
             let style = if is_selected {
                 Style::default()
-                    .fg(Color::Cyan)
+                    .fg(theme.dialog)
                     .add_modifier(Modifier::BOLD)
             } else {
-                Style::default().fg(Color::DarkGray)
+                Style::default().fg(theme.dim)
             };
             lines.push(Line::styled(format!("{}{}", prefix, suggestion), style));
         }
 
         if path_suggestions.len() > 5 {
             lines.push(Line::styled(
-                format!("      ... and {} more", path_suggestions.len() - 5),
-                Style::default().fg(Color::DarkGray),
+                trf!("      ... and {} more", path_suggestions.len() - 5),
+                Style::default().fg(theme.dim),
             ));
         }
 
         lines.push(Line::styled(
             "      ────────────────────────────────────",
-            Style::default().fg(Color::DarkGray),
+            Style::default().fg(theme.dim),
         ));
     }
 
     lines.push(Line::raw(""));
     lines.push(Line::styled(
-        "Tab switch  ↑↓ select  → accept  Enter create  Esc cancel",
-        Style::default().fg(Color::DarkGray),
+        tr("Tab switch  ↑↓ select  → accept  Enter create  Esc cancel"),
+        Style::default().fg(theme.dim),
     ));
 
     let text = Text::from(lines);
@@ -338,24 +470,121 @@ pub fn render_new_session_dialog(
     frame.render_widget(paragraph, area);
 }
 
-pub fn render_commit_dialog(frame: &mut Frame, message: &str) {
+pub fn render_commit_dialog(frame: &mut Frame, theme: &Theme, message: &str) {
     let area = centered_rect(60, 6, frame.area());
 
     let block = Block::default()
-        .title(" Commit ")
+        .title(tr(" Commit "))
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(theme.dialog));
+
+    let text = Text::from(vec![
+        Line::from(vec![
+            Span::raw(tr("Message: ")),
+            Span::styled(message, Style::default().fg(theme.highlight)),
+            Span::raw("_"),
+        ]),
+        Line::raw(""),
+        Line::styled(tr("Press Enter to commit"), Style::default().fg(theme.dim)),
+    ]);
+
+    let paragraph = Paragraph::new(text).block(block).wrap(Wrap { trim: true });
+
+    frame.render_widget(Clear, area);
+    frame.render_widget(paragraph, area);
+}
+
+pub fn render_branch_from_issue_dialog(
+    frame: &mut Frame,
+    theme: &Theme,
+    input: &str,
+    issues: &[&Issue],
+    selected: usize,
+    branch: Option<&str>,
+    send_prompt: bool,
+) {
+    let visible = issues.len().min(10);
+    let area = centered_rect(70, visible as u16 + 9, frame.area());
+
+    let block = Block::default()
+        .title(tr(" New Session from Issue "))
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(theme.dialog));
+
+    let mut lines = vec![
+        Line::from(vec![
+            Span::raw(tr("Issue: ")),
+            Span::styled(input, Style::default().fg(theme.highlight)),
+            Span::raw("_"),
+        ]),
+        Line::raw(""),
+    ];
+
+    // Keep the selection visible when there are more issues than rows
+    let start = selected.saturating_sub(visible.saturating_sub(1));
+    for (i, issue) in issues.iter().enumerate().skip(start).take(visible) {
+        let is_selected = i == selected;
+        let style = if is_selected {
+            Style::default()
+                .fg(theme.dialog)
+                .add_modifier(Modifier::BOLD)
+        } else {
+            Style::default()
+        };
+        lines.push(Line::from(vec![
+            Span::styled(if is_selected { "> " } else { "  " }, style),
+            Span::styled(
+                format!("{:<6}", format!("#{}", issue.number)),
+                Style::default().fg(theme.highlight),
+            ),
+            Span::styled(issue.title.as_str(), style),
+        ]));
+    }
+    if visible > 0 {
+        lines.push(Line::raw(""));
+    }
+
+    lines.push(Line::from(vec![
+        Span::raw(tr("Branch: ")),
+        Span::styled(
+            branch.unwrap_or(""),
+            Style::default().add_modifier(Modifier::BOLD),
+        ),
+    ]));
+    lines.push(Line::raw(trf!(
+        "[{}] Send the issue as the first prompt",
+        if send_prompt { "x" } else { " " }
+    )));
+    lines.push(Line::raw(""));
+    lines.push(Line::styled(
+        tr("Number, URL or ticket  [↑↓] Select  [Tab] Prompt  [Enter] Create  [Esc] Cancel"),
+        Style::default().fg(theme.dim),
+    ));
+
+    let paragraph = Paragraph::new(Text::from(lines)).block(block);
+
+    frame.render_widget(Clear, area);
+    frame.render_widget(paragraph, area);
+}
+
+pub fn render_rebase_dialog(frame: &mut Frame, theme: &Theme, branch: &str, base: &str) {
+    let area = centered_rect(60, 7, frame.area());
+
+    let block = Block::default()
+        .title(trf!(" Interactive Rebase of {} ", branch))
         .borders(Borders::ALL)
-        .border_style(Style::default().fg(Color::Cyan));
+        .border_style(Style::default().fg(theme.dialog));
 
     let text = Text::from(vec![
         Line::from(vec![
-            Span::raw("Message: "),
-            Span::styled(message, Style::default().fg(Color::Yellow)),
+            Span::raw(tr("Onto: ")),
+            Span::styled(base, Style::default().fg(theme.highlight)),
             Span::raw("_"),
         ]),
         Line::raw(""),
         Line::styled(
-            "Press Enter to commit",
-            Style::default().fg(Color::DarkGray),
+            tr("Any branch, tag or commit. Enter opens git rebase -i in a split"),
+            Style::default().fg(theme.dim),
         ),
     ]);
 
@@ -367,21 +596,27 @@ pub fn render_commit_dialog(frame: &mut Frame, message: &str) {
 
 pub fn render_create_pr_dialog(
     frame: &mut Frame,
+    theme: &Theme,
     title: &str,
     body: &str,
     base_branch: &str,
     field: CreatePullRequestField,
 ) {
-    let area = centered_rect(65, 12, frame.area());
+    // Multi-line bodies (e.g., a suggested commit list) show their last lines,
+    // where typing goes
+    const BODY_LINES: usize = 6;
+    let body_lines: Vec<&str> = body.lines().collect();
+    let shown_lines = body_lines.len().clamp(1, BODY_LINES);
+    let area = centered_rect(65, 11 + shown_lines as u16, frame.area());
 
     let block = Block::default()
-        .title(" Create Pull Request ")
+        .title(tr(" Create Pull Request "))
         .borders(Borders::ALL)
-        .border_style(Style::default().fg(Color::Green));
+        .border_style(Style::default().fg(theme.success));
 
     let title_style = if field == CreatePullRequestField::Title {
         Style::default()
-            .fg(Color::Yellow)
+            .fg(theme.highlight)
             .add_modifier(Modifier::BOLD)
     } else {
         Style::default()
@@ -389,7 +624,7 @@ pub fn render_create_pr_dialog(
 
     let body_style = if field == CreatePullRequestField::Body {
         Style::default()
-            .fg(Color::Yellow)
+            .fg(theme.highlight)
             .add_modifier(Modifier::BOLD)
     } else {
         Style::default()
@@ -397,7 +632,7 @@ pub fn render_create_pr_dialog(
 
     let base_style = if field == CreatePullRequestField::BaseBranch {
         Style::default()
-            .fg(Color::Yellow)
+            .fg(theme.highlight)
             .add_modifier(Modifier::BOLD)
     } else {
         Style::default()
@@ -405,39 +640,61 @@ pub fn render_create_pr_dialog(
 
     let cursor = |active: bool| if active { "_" } else { "" };
 
-    let text = Text::from(vec![
+    let mut lines = vec![
         Line::from(vec![
-            Span::styled("Title: ", title_style),
-            Span::styled(title, Style::default().fg(Color::Yellow)),
+            Span::styled(tr("Title: "), title_style),
+            Span::styled(title, Style::default().fg(theme.highlight)),
             Span::raw(cursor(field == CreatePullRequestField::Title)),
         ]),
         Line::raw(""),
-        Line::from(vec![
-            Span::styled("Body:  ", body_style),
-            Span::styled(
-                if body.is_empty() { "(optional)" } else { body },
-                if body.is_empty() {
-                    Style::default().fg(Color::DarkGray)
-                } else {
-                    Style::default().fg(Color::Yellow)
-                },
-            ),
+    ];
+    if body.is_empty() {
+        lines.push(Line::from(vec![
+            Span::styled(tr("Body:  "), body_style),
+            Span::styled(tr("(optional)"), Style::default().fg(theme.dim)),
             Span::raw(cursor(field == CreatePullRequestField::Body)),
-        ]),
+        ]));
+    } else {
+        let start = body_lines.len().saturating_sub(BODY_LINES);
+        for (i, line) in body_lines.iter().enumerate().skip(start) {
+            let label = if i == start {
+                Span::styled(tr("Body:  "), body_style)
+            } else {
+                Span::raw("       ")
+            };
+            // Older lines scrolled out of view
+            let text = if i == start && start > 0 {
+                format!("… {}", line)
+            } else {
+                line.to_string()
+            };
+            let mut spans = vec![
+                label,
+                Span::styled(text, Style::default().fg(theme.highlight)),
+            ];
+            if i + 1 == body_lines.len() {
+                spans.push(Span::raw(cursor(field == CreatePullRequestField::Body)));
+            }
+            lines.push(Line::from(spans));
+        }
+    }
+    lines.extend([
         Line::raw(""),
         Line::from(vec![
-            Span::styled("Base:  ", base_style),
-            Span::styled(base_branch, Style::default().fg(Color::Cyan)),
+            Span::styled(tr("Base:  "), base_style),
+            Span::styled(base_branch, Style::default().fg(theme.dialog)),
             Span::raw(cursor(field == CreatePullRequestField::BaseBranch)),
         ]),
         Line::raw(""),
         Line::styled(
-            "[Tab] Next field  [Enter] Create PR  [Esc] Cancel",
-            Style::default().fg(Color::DarkGray),
+            tr("[Tab] Next field  [Enter] Create PR  [Esc] Cancel"),
+            Style::default().fg(theme.dim),
         ),
     ]);
 
-    let paragraph = Paragraph::new(text).block(block).wrap(Wrap { trim: false });
+    let paragraph = Paragraph::new(Text::from(lines))
+        .block(block)
+        .wrap(Wrap { trim: false });
 
     frame.render_widget(Clear, area);
     frame.render_widget(paragraph, area);
@@ -449,17 +706,21 @@ pub fn render_new_worktree_dialog(
     app: &App,
     branch_input: &str,
     selected_branch: Option<usize>,
+    upstream: &str,
     worktree_path: &str,
     session_name: &str,
     field: NewWorktreeField,
     path_suggestions: &[String],
     path_selected: Option<usize>,
 ) {
+    let theme = &super::theme(app);
     // Get filtered branches
     let filtered_branches = app.filtered_branches();
     let is_new_branch = selected_branch.is_none()
         && !branch_input.is_empty()
         && !filtered_branches.contains(&branch_input);
+    // A new branch from a remote one starts from it and tracks it
+    let remote_branch = app.worktree_branch().and_then(|(_, _, remote)| remote);
 
     // Calculate dialog height based on suggestions shown
     let branches_to_show = if field == NewWorktreeField::Branch && !filtered_branches.is_empty() {
@@ -483,7 +744,7 @@ pub fn render_new_worktree_dialog(
     } else {
         0
     };
-    let dialog_height = 10
+    let dialog_height = 12
         + branches_to_show as u16
         + branch_extra as u16
         + path_suggestions_to_show as u16
@@ -492,9 +753,9 @@ pub fn render_new_worktree_dialog(
     let area = centered_rect(65, dialog_height, frame.area());
 
     let block = Block::default()
-        .title(" New Session from Worktree ")
+        .title(tr(" New Session from Worktree "))
         .borders(Borders::ALL)
-        .border_style(Style::default().fg(Color::Cyan));
+        .border_style(Style::default().fg(theme.dialog));
 
     // Build the content
     let mut lines = Vec::new();
@@ -502,16 +763,21 @@ pub fn render_new_worktree_dialog(
     // Branch field with ghost text
     let branch_style = if field == NewWorktreeField::Branch {
         Style::default()
-            .fg(Color::Yellow)
+            .fg(theme.highlight)
             .add_modifier(Modifier::BOLD)
     } else {
         Style::default()
     };
 
-    let branch_indicator = if is_new_branch {
-        Span::styled(" (new)", Style::default().fg(Color::Green))
+    let branch_indicator = if let Some(remote) = remote_branch {
+        Span::styled(
+            trf!(" (tracks {})", remote),
+            Style::default().fg(theme.dialog),
+        )
+    } else if is_new_branch {
+        Span::styled(tr(" (new)"), Style::default().fg(theme.success))
     } else if selected_branch.is_some() {
-        Span::styled(" (existing)", Style::default().fg(Color::Cyan))
+        Span::styled(tr(" (existing)"), Style::default().fg(theme.dialog))
     } else {
         Span::raw("")
     };
@@ -524,17 +790,15 @@ pub fn render_new_worktree_dialog(
     };
 
     let mut branch_spans = vec![
-        Span::styled("Branch:  ", branch_style),
-        Span::styled(branch_input, Style::default().fg(Color::Yellow)),
+        Span::styled(tr("Branch:  "), branch_style),
+        Span::styled(branch_input, Style::default().fg(theme.highlight)),
     ];
 
     // Add branch ghost text
     if let Some(ref ghost) = branch_ghost {
         branch_spans.push(Span::styled(
             ghost,
-            Style::default()
-                .fg(Color::DarkGray)
-                .add_modifier(Modifier::DIM),
+            Style::default().fg(theme.dim).add_modifier(Modifier::DIM),
         ));
     }
 
@@ -550,7 +814,7 @@ pub fn render_new_worktree_dialog(
     if field == NewWorktreeField::Branch && !filtered_branches.is_empty() {
         lines.push(Line::styled(
             "         ─────────────────────────────",
-            Style::default().fg(Color::DarkGray),
+            Style::default().fg(theme.dim),
         ));
 
         for (i, branch) in filtered_branches.iter().take(5).enumerate() {
@@ -562,33 +826,61 @@ pub fn render_new_worktree_dialog(
             };
             let style = if is_selected {
                 Style::default()
-                    .fg(Color::Cyan)
+                    .fg(theme.dialog)
                     .add_modifier(Modifier::BOLD)
             } else {
-                Style::default().fg(Color::DarkGray)
+                Style::default().fg(theme.dim)
             };
             lines.push(Line::styled(format!("{}{}", prefix, branch), style));
         }
 
         if filtered_branches.len() > 5 {
             lines.push(Line::styled(
-                format!("         ... and {} more", filtered_branches.len() - 5),
-                Style::default().fg(Color::DarkGray),
+                trf!("         ... and {} more", filtered_branches.len() - 5),
+                Style::default().fg(theme.dim),
             ));
         }
 
         lines.push(Line::styled(
             "         ─────────────────────────────",
-            Style::default().fg(Color::DarkGray),
+            Style::default().fg(theme.dim),
         ));
     }
 
     lines.push(Line::raw(""));
 
+    // Upstream field, with the default remote's branch as ghost text
+    let upstream_style = if field == NewWorktreeField::Upstream {
+        Style::default()
+            .fg(theme.highlight)
+            .add_modifier(Modifier::BOLD)
+    } else {
+        Style::default()
+    };
+    let mut upstream_spans = vec![
+        Span::styled(tr("Upstream: "), upstream_style),
+        Span::styled(upstream, Style::default().fg(theme.highlight)),
+    ];
+    if field == NewWorktreeField::Upstream {
+        if let Some(suggestion) = app.upstream_suggestion() {
+            upstream_spans.push(Span::styled(
+                suggestion,
+                Style::default().fg(theme.dim).add_modifier(Modifier::DIM),
+            ));
+        }
+        upstream_spans.push(Span::raw("_"));
+    }
+    if upstream.is_empty() && field != NewWorktreeField::Upstream {
+        upstream_spans.push(Span::styled(tr("(none)"), Style::default().fg(theme.dim)));
+    }
+    lines.push(Line::from(upstream_spans));
+
+    lines.push(Line::raw(""));
+
     // Path field with ghost text
     let path_style = if field == NewWorktreeField::Path {
         Style::default()
-            .fg(Color::Yellow)
+            .fg(theme.highlight)
             .add_modifier(Modifier::BOLD)
     } else {
         Style::default()
@@ -602,17 +894,15 @@ pub fn render_new_worktree_dialog(
     };
 
     let mut path_spans = vec![
-        Span::styled("Path:    ", path_style),
-        Span::styled(worktree_path, Style::default().fg(Color::Yellow)),
+        Span::styled(tr("Path:    "), path_style),
+        Span::styled(worktree_path, Style::default().fg(theme.highlight)),
     ];
 
     // Add path ghost text
     if let Some(ref ghost) = path_ghost {
         path_spans.push(Span::styled(
             ghost,
-            Style::default()
-                .fg(Color::DarkGray)
-                .add_modifier(Modifier::DIM),
+            Style::default().fg(theme.dim).add_modifier(Modifier::DIM),
         ));
     }
 
@@ -627,7 +917,7 @@ pub fn render_new_worktree_dialog(
     if field == NewWorktreeField::Path && !path_suggestions.is_empty() {
         lines.push(Line::styled(
             "         ────────────────────────────────────",
-            Style::default().fg(Color::DarkGray),
+            Style::default().fg(theme.dim),
         ));
 
         for (i, suggestion) in path_suggestions.iter().take(5).enumerate() {
@@ -639,24 +929,24 @@ pub fn render_new_worktree_dialog(
             };
             let style = if is_selected {
                 Style::default()
-                    .fg(Color::Cyan)
+                    .fg(theme.dialog)
                     .add_modifier(Modifier::BOLD)
             } else {
-                Style::default().fg(Color::DarkGray)
+                Style::default().fg(theme.dim)
             };
             lines.push(Line::styled(format!("{}{}", prefix, suggestion), style));
         }
 
         if path_suggestions.len() > 5 {
             lines.push(Line::styled(
-                format!("         ... and {} more", path_suggestions.len() - 5),
-                Style::default().fg(Color::DarkGray),
+                trf!("         ... and {} more", path_suggestions.len() - 5),
+                Style::default().fg(theme.dim),
             ));
         }
 
         lines.push(Line::styled(
             "         ────────────────────────────────────",
-            Style::default().fg(Color::DarkGray),
+            Style::default().fg(theme.dim),
         ));
     }
 
@@ -665,15 +955,15 @@ pub fn render_new_worktree_dialog(
     // Session name field
     let session_style = if field == NewWorktreeField::SessionName {
         Style::default()
-            .fg(Color::Yellow)
+            .fg(theme.highlight)
             .add_modifier(Modifier::BOLD)
     } else {
         Style::default()
     };
 
     lines.push(Line::from(vec![
-        Span::styled("Session: ", session_style),
-        Span::styled(session_name, Style::default().fg(Color::Yellow)),
+        Span::styled(tr("Session: "), session_style),
+        Span::styled(session_name, Style::default().fg(theme.highlight)),
         if field == NewWorktreeField::SessionName {
             Span::raw("_")
         } else {
@@ -683,8 +973,8 @@ pub fn render_new_worktree_dialog(
 
     lines.push(Line::raw(""));
     lines.push(Line::styled(
-        "Tab switch  ↑↓ select  → accept  Enter create  Esc cancel",
-        Style::default().fg(Color::DarkGray),
+        tr("Tab switch  ↑↓ select  → accept  Enter create  Esc cancel"),
+        Style::default().fg(theme.dim),
     ));
 
     let text = Text::from(lines);
@@ -694,24 +984,49 @@ pub fn render_new_worktree_dialog(
     frame.render_widget(paragraph, area);
 }
 
-pub fn render_rename_dialog(frame: &mut Frame, old_name: &str, new_name: &str) {
-    let area = centered_rect(50, 6, frame.area());
+/// The error that kept the config file from being reloaded
+pub fn render_config_error(frame: &mut Frame, theme: &Theme, error: &str, retry_key: char) {
+    let area = centered_rect(70, 10, frame.area());
+
+    let block = Block::default()
+        .title(tr(" Config not reloaded "))
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(theme.error));
+
+    let text = Text::from(vec![
+        Line::styled(error, Style::default().fg(theme.highlight)),
+        Line::raw(""),
+        Line::from(tr("The previous settings stay in effect.")),
+        Line::raw(""),
+        Line::styled(
+            trf!("Enter close  {} retry", retry_key),
+            Style::default().fg(theme.dim),
+        ),
+    ]);
+    let paragraph = Paragraph::new(text).block(block).wrap(Wrap { trim: false });
+
+    frame.render_widget(Clear, area);
+    frame.render_widget(paragraph, area);
+}
+
+pub fn render_edit_tags_dialog(frame: &mut Frame, theme: &Theme, path: &Path, input: &str) {
+    let area = centered_rect(60, 7, frame.area());
 
     let block = Block::default()
-        .title(format!(" Rename '{}' ", old_name))
+        .title(trf!(" Tags of {} ", short_path(path)))
         .borders(Borders::ALL)
-        .border_style(Style::default().fg(Color::Cyan));
+        .border_style(Style::default().fg(theme.dialog));
 
     let text = Text::from(vec![
         Line::from(vec![
-            Span::raw("New name: "),
-            Span::styled(new_name, Style::default().fg(Color::Yellow)),
+            Span::raw(tr("Tags: ")),
+            Span::styled(input, Style::default().fg(theme.highlight)),
             Span::raw("_"),
         ]),
         Line::raw(""),
         Line::styled(
-            "Press Enter to confirm",
-            Style::default().fg(Color::DarkGray),
+            tr("Separate tags with spaces or commas; leave empty to remove them"),
+            Style::default().fg(theme.dim),
         ),
     ]);
 
@@ -720,3 +1035,1764 @@ pub fn render_rename_dialog(frame: &mut Frame, old_name: &str, new_name: &str) {
     frame.render_widget(Clear, area);
     frame.render_widget(paragraph, area);
 }
+
+pub fn render_rename_dialog(frame: &mut Frame, theme: &Theme, old_name: &str, new_name: &str) {
+    let area = centered_rect(50, 6, frame.area());
+
+    let block = Block::default()
+        .title(trf!(" Rename '{}' ", old_name))
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(theme.dialog));
+
+    let text = Text::from(vec![
+        Line::from(vec![
+            Span::raw(tr("New name: ")),
+            Span::styled(new_name, Style::default().fg(theme.highlight)),
+            Span::raw("_"),
+        ]),
+        Line::raw(""),
+        Line::styled(tr("Press Enter to confirm"), Style::default().fg(theme.dim)),
+    ]);
+
+    let paragraph = Paragraph::new(text).block(block).wrap(Wrap { trim: true });
+
+    frame.render_widget(Clear, area);
+    frame.render_widget(paragraph, area);
+}
+
+pub fn render_schedule_prompt_dialog(
+    frame: &mut Frame,
+    theme: &Theme,
+    target: &str,
+    prompt: &str,
+    when: &str,
+    field: SchedulePromptField,
+) {
+    let area = centered_rect(65, 10, frame.area());
+
+    let block = Block::default()
+        .title(trf!(" Schedule Prompt for {} ", target))
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(theme.dialog));
+
+    let field_style = |active: bool| {
+        if active {
+            Style::default()
+                .fg(theme.highlight)
+                .add_modifier(Modifier::BOLD)
+        } else {
+            Style::default()
+        }
+    };
+    let cursor = |active: bool| if active { "_" } else { "" };
+
+    let text = Text::from(vec![
+        Line::from(vec![
+            Span::styled(
+                tr("Prompt: "),
+                field_style(field == SchedulePromptField::Prompt),
+            ),
+            Span::styled(prompt, Style::default().fg(theme.highlight)),
+            Span::raw(cursor(field == SchedulePromptField::Prompt)),
+        ]),
+        Line::raw(""),
+        Line::from(vec![
+            Span::styled(
+                tr("When:   "),
+                field_style(field == SchedulePromptField::When),
+            ),
+            Span::styled(
+                if when.is_empty() && field != SchedulePromptField::When {
+                    tr("e.g. 9am, 14:30, 15m, 1h30m")
+                } else {
+                    when
+                },
+                if when.is_empty() {
+                    Style::default().fg(theme.dim)
+                } else {
+                    Style::default().fg(theme.highlight)
+                },
+            ),
+            Span::raw(cursor(field == SchedulePromptField::When)),
+        ]),
+        Line::raw(""),
+        Line::styled(
+            tr("[Tab] Next field  [Enter] Schedule  [Esc] Cancel"),
+            Style::default().fg(theme.dim),
+        ),
+    ]);
+
+    let paragraph = Paragraph::new(text).block(block).wrap(Wrap { trim: false });
+
+    frame.render_widget(Clear, area);
+    frame.render_widget(paragraph, area);
+}
+
+#[allow(clippy::too_many_arguments)]
+pub fn render_credentials_dialog(
+    frame: &mut Frame,
+    theme: &Theme,
+    url: &str,
+    username: &str,
+    password: &str,
+    store: bool,
+    field: CredentialsField,
+    rejected: bool,
+) {
+    let area = centered_rect(65, 11, frame.area());
+
+    let block = Block::default()
+        .title(tr(" Credentials "))
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(theme.dialog));
+
+    let field_style = |active: bool| {
+        if active {
+            Style::default()
+                .fg(theme.highlight)
+                .add_modifier(Modifier::BOLD)
+        } else {
+            Style::default()
+        }
+    };
+    let cursor = |active: bool| if active { "_" } else { "" };
+
+    let (notice, notice_color) = if rejected {
+        (tr("Credentials rejected by "), theme.error)
+    } else {
+        (tr("Authentication required for "), theme.text)
+    };
+
+    let text = Text::from(vec![
+        Line::from(vec![
+            Span::styled(notice, Style::default().fg(notice_color)),
+            Span::styled(url, Style::default().fg(theme.dialog)),
+        ]),
+        Line::raw(""),
+        Line::from(vec![
+            Span::styled(
+                tr("Username: "),
+                field_style(field == CredentialsField::Username),
+            ),
+            Span::styled(username, Style::default().fg(theme.highlight)),
+            Span::raw(cursor(field == CredentialsField::Username)),
+        ]),
+        Line::from(vec![
+            Span::styled(
+                tr("Password: "),
+                field_style(field == CredentialsField::Password),
+            ),
+            Span::styled(
+                "•".repeat(password.chars().count()),
+                Style::default().fg(theme.highlight),
+            ),
+            Span::raw(cursor(field == CredentialsField::Password)),
+        ]),
+        Line::from(vec![
+            Span::styled(
+                if store { "[x] " } else { "[ ] " },
+                field_style(field == CredentialsField::Store),
+            ),
+            Span::styled(
+                tr("Save with the git credential helper"),
+                field_style(field == CredentialsField::Store),
+            ),
+        ]),
+        Line::raw(""),
+        Line::styled(
+            tr("Use a personal access token as the password where required"),
+            Style::default().fg(theme.dim),
+        ),
+        Line::styled(
+            tr("[Tab] Next field  [Space] Toggle save  [Enter] Retry  [Esc] Cancel"),
+            Style::default().fg(theme.dim),
+        ),
+    ]);
+
+    let paragraph = Paragraph::new(text).block(block).wrap(Wrap { trim: false });
+
+    frame.render_widget(Clear, area);
+    frame.render_widget(paragraph, area);
+}
+
+pub fn render_clone_layout_dialog(
+    frame: &mut Frame,
+    theme: &Theme,
+    source_session: &str,
+    name: &str,
+    branch: &str,
+    in_repo: bool,
+    field: CloneLayoutField,
+) {
+    let area = centered_rect(60, 10, frame.area());
+
+    let block = Block::default()
+        .title(tr(" Clone Session Layout "))
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(theme.dialog));
+
+    let field_style = |active: bool| {
+        if active {
+            Style::default()
+                .fg(theme.highlight)
+                .add_modifier(Modifier::BOLD)
+        } else {
+            Style::default()
+        }
+    };
+    let cursor = |active: bool| if active { "_" } else { "" };
+
+    let branch_hint = if in_repo {
+        tr("Empty branch: same directories; otherwise a new worktree")
+    } else {
+        tr("Not a git repository: the same directories are used")
+    };
+
+    let text = Text::from(vec![
+        Line::from(vec![
+            Span::raw(tr("Windows and panes of ")),
+            Span::styled(source_session, Style::default().fg(theme.dialog)),
+        ]),
+        Line::raw(""),
+        Line::from(vec![
+            Span::styled(
+                tr("Session name: "),
+                field_style(field == CloneLayoutField::Name),
+            ),
+            Span::styled(name, Style::default().fg(theme.highlight)),
+            Span::raw(cursor(field == CloneLayoutField::Name)),
+        ]),
+        Line::from(vec![
+            Span::styled(
+                tr("Worktree branch: "),
+                field_style(field == CloneLayoutField::Branch),
+            ),
+            Span::styled(branch, Style::default().fg(theme.highlight)),
+            Span::raw(cursor(field == CloneLayoutField::Branch)),
+        ]),
+        Line::raw(""),
+        Line::styled(branch_hint, Style::default().fg(theme.dim)),
+        Line::styled(
+            tr("[Tab] Next field  [Enter] Create  [Esc] Cancel"),
+            Style::default().fg(theme.dim),
+        ),
+    ]);
+
+    let paragraph = Paragraph::new(text).block(block).wrap(Wrap { trim: false });
+
+    frame.render_widget(Clear, area);
+    frame.render_widget(paragraph, area);
+}
+
+/// `hint` with its `jk` showing the bound down and up keys
+pub fn nav_hint(hint: &str, keys: &Keymap) -> String {
+    let nav = format!(
+        "{}{} ",
+        keys.key(Action::SelectNext),
+        keys.key(Action::SelectPrev)
+    );
+    hint.replacen("jk ", &nav, 1)
+}
+
+pub fn render_arrange_panes(
+    frame: &mut Frame,
+    theme: &Theme,
+    target: &str,
+    selected: usize,
+    keys: &Keymap,
+) {
+    let area = centered_rect(50, PaneArrangement::ALL.len() as u16 + 7, frame.area());
+
+    let block = Block::default()
+        .title(tr(" Rearrange Panes "))
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(theme.dialog));
+
+    let mut lines = vec![
+        Line::from(vec![
+            Span::raw(tr("Window of ")),
+            Span::styled(target, Style::default().fg(theme.dialog)),
+        ]),
+        Line::raw(""),
+    ];
+    for (i, arrangement) in PaneArrangement::ALL.iter().enumerate() {
+        let is_selected = i == selected;
+        let style = if is_selected {
+            Style::default()
+                .fg(theme.dialog)
+                .add_modifier(Modifier::BOLD)
+        } else {
+            Style::default()
+        };
+        let kind = match arrangement {
+            PaneArrangement::Layout(_) => tr("layout  "),
+            _ => "        ",
+        };
+        lines.push(Line::from(vec![
+            Span::styled(if is_selected { "> " } else { "  " }, style),
+            Span::styled(kind, Style::default().fg(theme.dim)),
+            Span::styled(arrangement.label(), style),
+        ]));
+    }
+    lines.push(Line::raw(""));
+    lines.push(Line::styled(
+        nav_hint(tr("jk navigate  Enter apply  Esc close"), keys),
+        Style::default().fg(theme.dim),
+    ));
+
+    let paragraph = Paragraph::new(Text::from(lines)).block(block);
+
+    frame.render_widget(Clear, area);
+    frame.render_widget(paragraph, area);
+}
+
+pub fn render_move_window_dialog(
+    frame: &mut Frame,
+    theme: &Theme,
+    window: &str,
+    link: bool,
+    input: &str,
+    sessions: &[&str],
+    selected: usize,
+) {
+    let visible = sessions.len().clamp(1, 8);
+    let area = centered_rect(60, visible as u16 + 7, frame.area());
+
+    let block = Block::default()
+        .title(if link {
+            tr(" Link Window ")
+        } else {
+            tr(" Move Window ")
+        })
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(theme.dialog));
+
+    let mut lines = vec![
+        Line::from(vec![
+            Span::raw(if link { tr("Link ") } else { tr("Move ") }),
+            Span::styled(window, Style::default().fg(theme.dialog)),
+            Span::raw(if link { tr(" into:") } else { tr(" to:") }),
+        ]),
+        Line::from(vec![
+            Span::styled(
+                tr("Session: "),
+                Style::default()
+                    .fg(theme.highlight)
+                    .add_modifier(Modifier::BOLD),
+            ),
+            Span::styled(input, Style::default().fg(theme.highlight)),
+            Span::raw("_"),
+        ]),
+        Line::raw(""),
+    ];
+
+    if sessions.is_empty() {
+        lines.push(Line::styled(
+            if input.trim().is_empty() {
+                tr("No other sessions; type a name to create one").to_string()
+            } else {
+                trf!("Enter creates session '{}'", input.trim())
+            },
+            Style::default().fg(theme.dim),
+        ));
+    } else {
+        // Keep the selection visible when there are more sessions than rows
+        let start = selected.saturating_sub(visible - 1);
+        for (i, session) in sessions.iter().enumerate().skip(start).take(visible) {
+            let is_selected = i == selected;
+            let style = if is_selected {
+                Style::default()
+                    .fg(theme.dialog)
+                    .add_modifier(Modifier::BOLD)
+            } else {
+                Style::default()
+            };
+            lines.push(Line::from(vec![
+                Span::styled(if is_selected { "> " } else { "  " }, style),
+                Span::styled(*session, style),
+            ]));
+        }
+    }
+
+    lines.push(Line::raw(""));
+    lines.push(Line::styled(
+        tr("Type to filter  [↑↓] Select  [Enter] Confirm  [Esc] Cancel"),
+        Style::default().fg(theme.dim),
+    ));
+
+    let paragraph = Paragraph::new(Text::from(lines)).block(block);
+
+    frame.render_widget(Clear, area);
+    frame.render_widget(paragraph, area);
+}
+
+pub fn render_passphrase_dialog(
+    frame: &mut Frame,
+    theme: &Theme,
+    key: &Path,
+    passphrase: &str,
+    rejected: bool,
+) {
+    let area = centered_rect(65, 8, frame.area());
+
+    let block = Block::default()
+        .title(tr(" SSH Passphrase "))
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(theme.dialog));
+
+    let (notice, notice_color) = if rejected {
+        (tr("Wrong passphrase for "), theme.error)
+    } else {
+        (tr("Passphrase required for "), theme.text)
+    };
+
+    let text = Text::from(vec![
+        Line::from(vec![
+            Span::styled(notice, Style::default().fg(notice_color)),
+            Span::styled(key.display().to_string(), Style::default().fg(theme.dialog)),
+        ]),
+        Line::raw(""),
+        Line::from(vec![
+            Span::styled(
+                tr("Passphrase: "),
+                Style::default()
+                    .fg(theme.highlight)
+                    .add_modifier(Modifier::BOLD),
+            ),
+            Span::styled(
+                "•".repeat(passphrase.chars().count()),
+                Style::default().fg(theme.highlight),
+            ),
+            Span::raw("_"),
+        ]),
+        Line::raw(""),
+        Line::styled(
+            tr("[Enter] Retry  [Esc] Cancel"),
+            Style::default().fg(theme.dim),
+        ),
+    ]);
+
+    let paragraph = Paragraph::new(text).block(block).wrap(Wrap { trim: false });
+
+    frame.render_widget(Clear, area);
+    frame.render_widget(paragraph, area);
+}
+
+pub fn render_conflicts(
+    frame: &mut Frame,
+    theme: &Theme,
+    files: &[ConflictedFile],
+    selected: usize,
+    operation: Option<PendingOperation>,
+) {
+    let visible = files.len().clamp(1, 12);
+    let area = centered_rect(70, visible as u16 + 5, frame.area());
+
+    let block = Block::default()
+        .title(tr(" Conflicts "))
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(theme.error));
+
+    let mut lines = Vec::new();
+
+    if files.is_empty() {
+        let text = match operation {
+            Some(PendingOperation::Merge) => tr("All conflicts resolved, c continues the merge"),
+            Some(PendingOperation::Rebase) => tr("All conflicts resolved, c continues the rebase"),
+            None => tr("All conflicts resolved"),
+        };
+        lines.push(Line::styled(text, Style::default().fg(theme.success)));
+    } else {
+        // Keep the selection visible when there are more files than rows
+        let start = selected.saturating_sub(visible - 1);
+        for (i, file) in files.iter().enumerate().skip(start).take(visible) {
+            let is_selected = i == selected;
+            let style = if is_selected {
+                Style::default()
+                    .fg(theme.dialog)
+                    .add_modifier(Modifier::BOLD)
+            } else {
+                Style::default()
+            };
+            // Which side deleted the file, if any
+            let note = match (file.ours, file.theirs) {
+                (false, _) => tr("deleted by us"),
+                (_, false) => tr("deleted by them"),
+                _ => tr("both modified"),
+            };
+            lines.push(Line::from(vec![
+                Span::styled(if is_selected { "> " } else { "  " }, style),
+                Span::styled(format!("{:<48}", file.path), style),
+                Span::styled(note, Style::default().fg(theme.highlight)),
+            ]));
+        }
+    }
+
+    lines.push(Line::raw(""));
+    lines.push(Line::styled(
+        tr("e edit  o ours  t theirs  s mark resolved  c continue  esc close"),
+        Style::default().fg(theme.dim),
+    ));
+
+    let paragraph = Paragraph::new(Text::from(lines)).block(block);
+
+    frame.render_widget(Clear, area);
+    frame.render_widget(paragraph, area);
+}
+
+pub fn render_archived(
+    frame: &mut Frame,
+    theme: &Theme,
+    sessions: &[ArchivedSession],
+    selected: usize,
+    keys: &Keymap,
+) {
+    let visible = sessions.len().clamp(1, 10);
+    let area = centered_rect(70, visible as u16 + 5, frame.area());
+
+    let block = Block::default()
+        .title(tr(" Archived Sessions "))
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(theme.dialog));
+
+    let mut lines = Vec::new();
+
+    if sessions.is_empty() {
+        lines.push(Line::styled(
+            tr("No archived sessions"),
+            Style::default().fg(theme.dim),
+        ));
+    } else {
+        // Keep the selection visible when there are more sessions than rows
+        let start = selected.saturating_sub(visible - 1);
+        for (i, session) in sessions.iter().enumerate().skip(start).take(visible) {
+            let is_selected = i == selected;
+            let style = if is_selected {
+                Style::default()
+                    .fg(theme.dialog)
+                    .add_modifier(Modifier::BOLD)
+            } else {
+                Style::default()
+            };
+            lines.push(Line::from(vec![
+                Span::styled(if is_selected { "> " } else { "  " }, style),
+                Span::styled(format!("{:<16}", session.name), style),
+                Span::styled(
+                    format!("{:<20}", session.branch.as_deref().unwrap_or("-")),
+                    Style::default().fg(theme.special),
+                ),
+                Span::styled(short_path(&session.path), Style::default().fg(theme.dim)),
+            ]));
+        }
+    }
+
+    lines.push(Line::raw(""));
+    lines.push(Line::styled(
+        nav_hint(tr("jk navigate  Enter restore  d delete  esc close"), keys),
+        Style::default().fg(theme.dim),
+    ));
+
+    let paragraph = Paragraph::new(Text::from(lines)).block(block);
+
+    frame.render_widget(Clear, area);
+    frame.render_widget(paragraph, area);
+}
+
+pub fn render_snapshots(
+    frame: &mut Frame,
+    theme: &Theme,
+    snapshots: &[Snapshot],
+    selected: usize,
+    ascii: bool,
+    keys: &Keymap,
+) {
+    let visible = snapshots.len().clamp(1, 12);
+    let area = centered_rect(82, visible as u16 + 5, frame.area());
+
+    let block = Block::default()
+        .title(tr(" Status Snapshots "))
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(theme.dialog));
+
+    let mut lines = Vec::new();
+
+    if snapshots.is_empty() {
+        lines.push(Line::styled(
+            tr("No snapshots yet: they are taken when an agent starts waiting or crashes"),
+            Style::default().fg(theme.dim),
+        ));
+    } else {
+        // Keep the selection visible when there are more snapshots than rows
+        let start = selected.saturating_sub(visible - 1);
+        for (i, snapshot) in snapshots.iter().enumerate().skip(start).take(visible) {
+            let is_selected = i == selected;
+            let style = if is_selected {
+                Style::default()
+                    .fg(theme.dialog)
+                    .add_modifier(Modifier::BOLD)
+            } else {
+                Style::default()
+            };
+            let status_color = match snapshot.status {
+                ClaudeCodeStatus::Crashed => theme.error,
+                _ => theme.highlight,
+            };
+            let symbol = if ascii {
+                snapshot.status.ascii_symbol()
+            } else {
+                snapshot.status.symbol()
+            };
+            let time = logging::format_local(snapshot.taken_at);
+            lines.push(Line::from(vec![
+                Span::styled(if is_selected { "> " } else { "  " }, style),
+                Span::styled(format!("{}  ", &time[..16]), style),
+                Span::styled(
+                    format!("{} {:<9}", symbol, snapshot.status.label()),
+                    Style::default().fg(status_color),
+                ),
+                Span::styled(format!("{:<14}", snapshot.target), style),
+                Span::styled(
+                    format!("{:<20}", snapshot.branch.as_deref().unwrap_or("-")),
+                    Style::default().fg(theme.special),
+                ),
+                Span::styled(short_path(&snapshot.path), Style::default().fg(theme.dim)),
+            ]));
+        }
+    }
+
+    lines.push(Line::raw(""));
+    lines.push(Line::styled(
+        nav_hint(tr("jk navigate  Enter view  d delete  esc close"), keys),
+        Style::default().fg(theme.dim),
+    ));
+
+    let paragraph = Paragraph::new(Text::from(lines)).block(block);
+
+    frame.render_widget(Clear, area);
+    frame.render_widget(paragraph, area);
+}
+
+pub fn render_snapshot(
+    frame: &mut Frame,
+    theme: &Theme,
+    snapshot: &Snapshot,
+    lines: &[String],
+    scroll: usize,
+) {
+    let full = frame.area();
+    // Leave the footer visible
+    let area = Rect {
+        height: full.height.saturating_sub(1),
+        ..full
+    };
+
+    let time = logging::format_local(snapshot.taken_at);
+    let block = Block::default()
+        .title(format!(
+            " {} — {} {} ",
+            snapshot.target,
+            snapshot.status.label(),
+            &time[..16]
+        ))
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(theme.dialog));
+
+    let visible = area.height.saturating_sub(2) as usize;
+    let end = lines.len().saturating_sub(scroll);
+    let start = end.saturating_sub(visible);
+
+    let rows: Vec<Line> = if lines.is_empty() {
+        vec![Line::styled(
+            tr("The pane was empty"),
+            Style::default().fg(theme.dim),
+        )]
+    } else {
+        lines[start..end]
+            .iter()
+            .map(|line| Line::raw(line.as_str()))
+            .collect()
+    };
+
+    let paragraph = Paragraph::new(Text::from(rows)).block(block);
+
+    frame.render_widget(Clear, area);
+    frame.render_widget(paragraph, area);
+}
+
+pub fn render_work_time(
+    frame: &mut Frame,
+    theme: &Theme,
+    totals: &[BranchTotal],
+    selected: usize,
+    keys: &Keymap,
+) {
+    let visible = totals.len().clamp(1, 12);
+    let area = centered_rect(82, visible as u16 + 5, frame.area());
+
+    let block = Block::default()
+        .title(tr(" Working Time "))
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(theme.dialog));
+
+    let header_style = Style::default().fg(theme.dim);
+    let mut lines = vec![Line::styled(
+        format!(
+            "  {:<36}{:>8}{:>9}{:>9}  {}",
+            tr("Branch"),
+            tr("Today"),
+            tr("7 days"),
+            tr("Total"),
+            tr("Last")
+        ),
+        header_style,
+    )];
+
+    if totals.is_empty() {
+        lines.push(Line::styled(
+            tr("No working time recorded yet"),
+            Style::default().fg(theme.dim),
+        ));
+    } else {
+        // Keep the selection visible when there are more branches than rows
+        let start = selected.saturating_sub(visible - 1);
+        for (i, total) in totals.iter().enumerate().skip(start).take(visible) {
+            let is_selected = i == selected;
+            let style = if is_selected {
+                Style::default()
+                    .fg(theme.dialog)
+                    .add_modifier(Modifier::BOLD)
+            } else {
+                Style::default()
+            };
+            lines.push(Line::from(vec![
+                Span::styled(if is_selected { "> " } else { "  " }, style),
+                Span::styled(
+                    format!("{:<36}", format!("{}:{}", total.repo, total.branch)),
+                    style,
+                ),
+                Span::styled(
+                    format!(
+                        "{:>8}{:>9}{:>9}",
+                        format_seconds(total.today),
+                        format_seconds(total.week),
+                        format_seconds(total.total)
+                    ),
+                    Style::default().fg(theme.highlight),
+                ),
+                Span::styled(
+                    format!("  {}", total.last_day),
+                    Style::default().fg(theme.dim),
+                ),
+            ]));
+        }
+    }
+
+    lines.push(Line::raw(""));
+    lines.push(Line::styled(
+        nav_hint(
+            tr("Time agents spent working; jk navigate  esc close"),
+            keys,
+        ),
+        Style::default().fg(theme.dim),
+    ));
+
+    let paragraph = Paragraph::new(Text::from(lines)).block(block);
+
+    frame.render_widget(Clear, area);
+    frame.render_widget(paragraph, area);
+}
+
+pub fn render_sync(
+    frame: &mut Frame,
+    theme: &Theme,
+    rows: &[SyncRow],
+    selected: usize,
+    running: bool,
+) {
+    let visible = rows.len().clamp(1, 12);
+    let area = centered_rect(90, visible as u16 + 7, frame.area());
+
+    let block = Block::default()
+        .title(tr(" Sync "))
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(theme.dialog));
+
+    let mut lines = Vec::new();
+    // Keep the selection visible when there are more checkouts than rows
+    let start = selected.saturating_sub(visible - 1);
+    for (i, row) in rows.iter().enumerate().skip(start).take(visible) {
+        let is_selected = i == selected;
+        let style = if is_selected {
+            Style::default()
+                .fg(theme.dialog)
+                .add_modifier(Modifier::BOLD)
+        } else {
+            Style::default()
+        };
+        // Keep the end of long paths, where the branch is
+        let label: String = if row.label.chars().count() > 36 {
+            let tail: String = row
+                .label
+                .chars()
+                .skip(row.label.chars().count() - 35)
+                .collect();
+            format!("…{}", tail)
+        } else {
+            row.label.clone()
+        };
+        let (result, color) = match &row.state {
+            SyncState::Pending => (tr("pending").to_string(), theme.dim),
+            SyncState::Skipped(reason) => (trf!("skipped: {}", reason), theme.highlight),
+            SyncState::Failed(_) => (tr("failed").to_string(), theme.error),
+            SyncState::Done(SyncOutcome::UpToDate) => (tr("up to date").to_string(), theme.dim),
+            SyncState::Done(SyncOutcome::FastForwarded { commits, stashed }) => (
+                trf!("fast-forwarded {} commits", commits)
+                    + if *stashed {
+                        tr(", local changes kept")
+                    } else {
+                        ""
+                    },
+                theme.success,
+            ),
+            SyncState::Done(SyncOutcome::Rebased { commits, stashed }) => (
+                trf!("rebased onto {} new commits", commits)
+                    + if *stashed {
+                        tr(", local changes kept")
+                    } else {
+                        ""
+                    },
+                theme.success,
+            ),
+        };
+        lines.push(Line::from(vec![
+            Span::styled(if is_selected { "> " } else { "  " }, style),
+            Span::styled(format!("{:<38}", label), style),
+            Span::styled(result, Style::default().fg(color)),
+        ]));
+    }
+
+    let count = |matches: fn(&SyncState) -> bool| rows.iter().filter(|r| matches(&r.state)).count();
+    let hint = if running {
+        trf!(
+            "{} of {} synced; esc stop",
+            count(|s| matches!(s, SyncState::Done(_) | SyncState::Failed(_))),
+            count(|s| !matches!(s, SyncState::Skipped(_)))
+        )
+    } else {
+        trf!(
+            "{} updated, {} failed, {} skipped; esc close",
+            count(|s| matches!(
+                s,
+                SyncState::Done(SyncOutcome::FastForwarded { .. } | SyncOutcome::Rebased { .. })
+            )),
+            count(|s| matches!(s, SyncState::Failed(_))),
+            count(|s| matches!(s, SyncState::Skipped(_)))
+        )
+    };
+    lines.push(Line::raw(""));
+    lines.push(Line::styled(hint, Style::default().fg(theme.dim)));
+
+    // Errors don't fit in the list; show the highlighted one in full below
+    if let Some(SyncState::Failed(error)) = rows.get(selected).map(|row| &row.state) {
+        lines.push(Line::raw(""));
+        lines.push(Line::styled(
+            error.as_str(),
+            Style::default().fg(theme.error),
+        ));
+    }
+
+    let paragraph = Paragraph::new(Text::from(lines))
+        .block(block)
+        .wrap(Wrap { trim: false });
+
+    frame.render_widget(Clear, area);
+    frame.render_widget(paragraph, area);
+}
+
+pub fn render_stats(frame: &mut Frame, theme: &Theme, stats: &Stats, recording: bool) {
+    let area = centered_rect(56, if recording { 12 } else { 13 }, frame.area());
+
+    let block = Block::default()
+        .title(tr(" Usage Statistics "))
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(theme.dialog));
+
+    let label_style = Style::default().fg(theme.dim);
+    let value_style = Style::default().fg(theme.highlight);
+    let row = |label: &str, value: String| {
+        Line::from(vec![
+            Span::styled(format!("  {:<32}", label), label_style),
+            Span::styled(value, value_style),
+        ])
+    };
+
+    let since = match stats.since {
+        Some(since) => trf!("Since {}", logging::format_local(since)[..10].to_string()),
+        None => tr("Nothing recorded yet").to_string(),
+    };
+    let mut lines = vec![
+        Line::styled(since, label_style),
+        Line::raw(""),
+        row(
+            tr("Sessions created"),
+            stats.get(Counter::SessionsCreated).to_string(),
+        ),
+        row(
+            tr("Prompts sent"),
+            stats.get(Counter::PromptsSent).to_string(),
+        ),
+        row(tr("Commits"), stats.get(Counter::Commits).to_string()),
+        row(tr("Pushes"), stats.get(Counter::Pushes).to_string()),
+        row(
+            tr("Times agents asked for input"),
+            stats.get(Counter::InputRequests).to_string(),
+        ),
+        row(
+            tr("Average time to answer"),
+            stats
+                .average_response()
+                .map_or_else(|| "-".to_string(), format_wait),
+        ),
+        Line::raw(""),
+    ];
+    if !recording {
+        lines.push(Line::styled(
+            tr("Recording is off (usage_stats = false)"),
+            Style::default().fg(theme.highlight),
+        ));
+    }
+    lines.push(Line::styled(
+        tr("r reload  esc close"),
+        Style::default().fg(theme.dim),
+    ));
+
+    let paragraph = Paragraph::new(Text::from(lines)).block(block);
+
+    frame.render_widget(Clear, area);
+    frame.render_widget(paragraph, area);
+}
+
+/// Format a wait in seconds (e.g., "45s", "3m20s", "1h05m")
+fn format_wait(secs: u64) -> String {
+    let (hours, minutes, seconds) = (secs / 3600, (secs % 3600) / 60, secs % 60);
+    if hours > 0 {
+        format!("{}h{:02}m", hours, minutes)
+    } else if minutes > 0 {
+        format!("{}m{:02}s", minutes, seconds)
+    } else {
+        format!("{}s", seconds)
+    }
+}
+
+pub fn render_keybindings(
+    frame: &mut Frame,
+    theme: &Theme,
+    keys: &Keymap,
+    selected: usize,
+    capturing: bool,
+    conflict: Option<char>,
+) {
+    let area = centered_rect(64, Action::ALL.len() as u16 + 4, frame.area());
+
+    let block = Block::default()
+        .title(tr(" Keybindings "))
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(theme.dialog));
+
+    // Scroll to the selected action when the list is taller than the screen
+    let visible = (area.height as usize).saturating_sub(4).max(1);
+    let first = selected.saturating_sub(visible - 1);
+
+    let mut lines = Vec::new();
+    for (i, action) in Action::ALL.iter().enumerate().skip(first).take(visible) {
+        let is_selected = i == selected;
+        let style = if is_selected {
+            Style::default()
+                .fg(theme.dialog)
+                .add_modifier(Modifier::BOLD)
+        } else {
+            Style::default()
+        };
+        let key = if is_selected && capturing {
+            "…".to_string()
+        } else {
+            keys.key(*action).to_string()
+        };
+        let mut spans = vec![
+            Span::styled(if is_selected { "> " } else { "  " }, style),
+            Span::styled(format!("{:<4}", key), style.fg(theme.highlight)),
+            Span::styled(action.label(), style),
+        ];
+        if keys.key(*action) != action.default_key() {
+            spans.push(Span::styled(
+                trf!("  (default {})", action.default_key()),
+                Style::default().fg(theme.dim),
+            ));
+        }
+        lines.push(Line::from(spans));
+    }
+
+    lines.push(Line::raw(""));
+    let action = Action::ALL[selected];
+    let hint = match conflict {
+        Some(key) if capturing => Line::styled(
+            trf!(
+                "'{}' is used by \"{}\"; press it again to swap",
+                key,
+                keys.action(key).map_or("", |a| a.label())
+            ),
+            Style::default().fg(theme.highlight),
+        ),
+        None if capturing => Line::styled(
+            trf!("Press the new key for \"{}\" (esc cancels)", action.label()),
+            Style::default().fg(theme.highlight),
+        ),
+        _ => Line::styled(
+            nav_hint(tr("jk navigate  Enter rebind  d default  esc close"), keys),
+            Style::default().fg(theme.dim),
+        ),
+    };
+    lines.push(hint);
+
+    let paragraph = Paragraph::new(Text::from(lines)).block(block);
+
+    frame.render_widget(Clear, area);
+    frame.render_widget(paragraph, area);
+}
+
+pub fn render_logs(frame: &mut Frame, theme: &Theme, lines: &[String], scroll: usize) {
+    let full = frame.area();
+    // Leave the footer visible
+    let area = Rect {
+        height: full.height.saturating_sub(1),
+        ..full
+    };
+
+    let block = Block::default()
+        .title(trf!(" Log — {} ", short_path(&logging::log_dir())))
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(theme.dialog));
+
+    let visible = area.height.saturating_sub(2) as usize;
+    let end = lines.len().saturating_sub(scroll);
+    let start = end.saturating_sub(visible);
+
+    let rows: Vec<Line> = if lines.is_empty() {
+        vec![Line::styled(
+            tr("No log messages yet"),
+            Style::default().fg(theme.dim),
+        )]
+    } else {
+        lines[start..end]
+            .iter()
+            .map(|line| {
+                // The level follows the `YYYY-MM-DD HH:MM:SS ` timestamp;
+                // continuation lines of a multi-line message have none
+                let style = match line.get(20..25).map(str::trim_end) {
+                    Some("ERROR") => Style::default().fg(theme.error),
+                    Some("WARN") => Style::default().fg(theme.highlight),
+                    Some("DEBUG") | Some("TRACE") => Style::default().fg(theme.dim),
+                    _ => Style::default(),
+                };
+                Line::styled(line.as_str(), style)
+            })
+            .collect()
+    };
+
+    let paragraph = Paragraph::new(Text::from(rows)).block(block);
+
+    frame.render_widget(Clear, area);
+    frame.render_widget(paragraph, area);
+}
+
+pub fn render_action_output(
+    frame: &mut Frame,
+    theme: &Theme,
+    name: &str,
+    target: &str,
+    lines: &[String],
+    state: &ActionRun,
+    scroll: usize,
+) {
+    let full = frame.area();
+    // Leave the footer visible
+    let area = Rect {
+        height: full.height.saturating_sub(1),
+        ..full
+    };
+
+    let (status, color) = match state {
+        ActionRun::Running => (tr("running…").to_string(), theme.highlight),
+        ActionRun::Exited(0) => (tr("done").to_string(), theme.success),
+        ActionRun::Exited(code) => (trf!("exit status {}", code), theme.error),
+        ActionRun::Failed(e) => (trf!("failed: {}", e), theme.error),
+    };
+    let block = Block::default()
+        .title(format!(" {} — {} ", name, target))
+        .title_bottom(Line::styled(
+            format!(" {} ", status),
+            Style::default().fg(color),
+        ))
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(color));
+
+    let visible = area.height.saturating_sub(2) as usize;
+    let end = lines.len().saturating_sub(scroll);
+    let start = end.saturating_sub(visible);
+
+    let rows: Vec<Line> = if lines.is_empty() && *state != ActionRun::Running {
+        vec![Line::styled(
+            tr("No output"),
+            Style::default().fg(theme.dim),
+        )]
+    } else {
+        lines[start..end]
+            .iter()
+            .map(|line| Line::raw(line.as_str()))
+            .collect()
+    };
+
+    let paragraph = Paragraph::new(Text::from(rows)).block(block);
+
+    frame.render_widget(Clear, area);
+    frame.render_widget(paragraph, area);
+}
+
+pub fn render_pending_jobs(
+    frame: &mut Frame,
+    theme: &Theme,
+    jobs: &[ScheduledJob],
+    selected: usize,
+    keys: &Keymap,
+) {
+    let visible = jobs.len().clamp(1, 10);
+    let area = centered_rect(70, visible as u16 + 6, frame.area());
+
+    let block = Block::default()
+        .title(tr(" Scheduled Prompts "))
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(theme.dialog));
+
+    let mut lines = Vec::new();
+
+    if jobs.is_empty() {
+        lines.push(Line::styled(
+            tr("No scheduled prompts"),
+            Style::default().fg(theme.dim),
+        ));
+    } else {
+        let now = store::now_unix();
+        // Keep the selection visible when there are more jobs than rows
+        let start = selected.saturating_sub(visible - 1);
+        for (i, job) in jobs.iter().enumerate().skip(start).take(visible) {
+            let is_selected = i == selected;
+            let style = if is_selected {
+                Style::default()
+                    .fg(theme.dialog)
+                    .add_modifier(Modifier::BOLD)
+            } else {
+                Style::default()
+            };
+            let first_line = job.prompt.lines().next().unwrap_or("");
+            lines.push(Line::from(vec![
+                Span::styled(if is_selected { "> " } else { "  " }, style),
+                Span::styled(
+                    format!("{:<10}", schedule::format_due(job.due, now)),
+                    Style::default().fg(theme.highlight),
+                ),
+                Span::styled(format!("{:<14}", job.label), style),
+                Span::styled(first_line.to_string(), style),
+            ]));
+        }
+    }
+
+    lines.push(Line::raw(""));
+    if !crate::daemon::is_running() {
+        lines.push(Line::styled(
+            tr("Daemon not running - start `claude-tmux daemon` to dispatch"),
+            Style::default().fg(theme.error),
+        ));
+    } else {
+        lines.push(Line::raw(""));
+    }
+    lines.push(Line::styled(
+        nav_hint(tr("jk navigate  d cancel job  esc close"), keys),
+        Style::default().fg(theme.dim),
+    ));
+
+    let paragraph = Paragraph::new(Text::from(lines)).block(block);
+
+    frame.render_widget(Clear, area);
+    frame.render_widget(paragraph, area);
+}
+
+pub fn render_search(
+    frame: &mut Frame,
+    theme: &Theme,
+    query: &str,
+    results: Option<&[SearchMatch]>,
+    selected: usize,
+) {
+    let visible = results.map_or(1, |r| r.len().clamp(1, 15));
+    let area = centered_rect(80, visible as u16 + 7, frame.area());
+
+    let block = Block::default()
+        .title(tr(" Search Panes "))
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(theme.dialog));
+
+    let mut lines = vec![
+        Line::from(vec![
+            Span::styled(
+                tr("Search: "),
+                Style::default()
+                    .fg(theme.highlight)
+                    .add_modifier(Modifier::BOLD),
+            ),
+            Span::styled(query, Style::default().fg(theme.highlight)),
+            Span::raw("_"),
+        ]),
+        Line::raw(""),
+    ];
+
+    match results {
+        None => lines.push(Line::styled(
+            tr("Enter searches the content of every Claude pane"),
+            Style::default().fg(theme.dim),
+        )),
+        Some([]) => lines.push(Line::styled(
+            tr("No pane contains it"),
+            Style::default().fg(theme.dim),
+        )),
+        Some(results) => {
+            let target_width = results
+                .iter()
+                .map(|r| r.target.chars().count())
+                .max()
+                .unwrap_or(0)
+                + 2;
+            // Keep the selection visible when there are more results than rows
+            let start = selected.saturating_sub(visible - 1);
+            for (i, result) in results.iter().enumerate().skip(start).take(visible) {
+                let is_selected = i == selected;
+                let style = if is_selected {
+                    Style::default()
+                        .fg(theme.dialog)
+                        .add_modifier(Modifier::BOLD)
+                } else {
+                    Style::default()
+                };
+                lines.push(Line::from(vec![
+                    Span::styled(if is_selected { "> " } else { "  " }, style),
+                    Span::styled(
+                        format!("{:<width$}", result.target, width = target_width),
+                        style,
+                    ),
+                    Span::styled(
+                        format!("{:>5}  ", format!("({})", result.count)),
+                        Style::default().fg(theme.highlight),
+                    ),
+                    Span::raw(result.line.as_str()),
+                    Span::styled(format!("  {}", result.path), Style::default().fg(theme.dim)),
+                ]));
+            }
+        }
+    }
+
+    lines.push(Line::raw(""));
+    lines.push(Line::styled(
+        tr("Type a pattern  [Enter] Search / switch  [↑↓] Select  [Esc] Close"),
+        Style::default().fg(theme.dim),
+    ));
+
+    let paragraph = Paragraph::new(Text::from(lines)).block(block);
+
+    frame.render_widget(Clear, area);
+    frame.render_widget(paragraph, area);
+}
+
+pub fn render_all_panes(
+    frame: &mut Frame,
+    theme: &Theme,
+    panes: &[PaneEntry],
+    selected: usize,
+    keys: &Keymap,
+) {
+    let visible = panes.len().clamp(1, 15);
+    let area = centered_rect(80, visible as u16 + 5, frame.area());
+
+    let block = Block::default()
+        .title(tr(" All Panes "))
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(theme.dialog));
+
+    let mut lines = Vec::new();
+
+    if panes.is_empty() {
+        lines.push(Line::styled(
+            tr("No tmux panes"),
+            Style::default().fg(theme.dim),
+        ));
+    } else {
+        let name_width = panes
+            .iter()
+            .map(|p| p.display_name().chars().count())
+            .max()
+            .unwrap_or(0)
+            + 2;
+        // Keep the selection visible when there are more panes than rows
+        let start = selected.saturating_sub(visible - 1);
+        for (i, entry) in panes.iter().enumerate().skip(start).take(visible) {
+            let is_selected = i == selected;
+            let style = if is_selected {
+                Style::default()
+                    .fg(theme.dialog)
+                    .add_modifier(Modifier::BOLD)
+            } else {
+                Style::default()
+            };
+            let (command, command_color) = if entry.has_claude {
+                ("claude", theme.accent)
+            } else {
+                (entry.pane.current_command.as_str(), theme.highlight)
+            };
+            lines.push(Line::from(vec![
+                Span::styled(if is_selected { "> " } else { "  " }, style),
+                Span::styled(
+                    format!("{:<width$}", entry.display_name(), width = name_width),
+                    style,
+                ),
+                Span::styled(
+                    format!("{:<10} ", command),
+                    Style::default().fg(command_color),
+                ),
+                Span::styled(
+                    short_path(&entry.pane.current_path),
+                    Style::default().fg(theme.dim),
+                ),
+            ]));
+        }
+    }
+
+    lines.push(Line::raw(""));
+    lines.push(Line::styled(
+        nav_hint(
+            tr("jk navigate  Enter switch  c start claude here  v start in a split  esc close"),
+            keys,
+        ),
+        Style::default().fg(theme.dim),
+    ));
+
+    let paragraph = Paragraph::new(Text::from(lines)).block(block);
+
+    frame.render_widget(Clear, area);
+    frame.render_widget(paragraph, area);
+}
+
+pub fn render_sessions(
+    frame: &mut Frame,
+    app: &App,
+    sessions: &[Session],
+    selected: usize,
+    confirm_kill: bool,
+    rename: Option<&str>,
+) {
+    let theme = &super::theme(app);
+    let keys = &app.config.keys;
+    let unpushed = if confirm_kill && rename.is_none() {
+        unpushed_line(app)
+    } else {
+        None
+    };
+    let visible = sessions.len().clamp(1, 15);
+    let area = centered_rect(
+        80,
+        visible as u16 + 5 + unpushed.is_some() as u16,
+        frame.area(),
+    );
+
+    let block = Block::default()
+        .title(tr(" Sessions "))
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(theme.dialog));
+
+    let mut lines = Vec::new();
+
+    if sessions.is_empty() {
+        lines.push(Line::styled(
+            tr("No tmux sessions"),
+            Style::default().fg(theme.dim),
+        ));
+    } else {
+        let name_width = sessions
+            .iter()
+            .map(|s| s.name.chars().count())
+            .max()
+            .unwrap_or(0)
+            + 2;
+        let now = store::now_unix();
+        // Keep the selection visible when there are more sessions than rows
+        let start = selected.saturating_sub(visible - 1);
+        for (i, session) in sessions.iter().enumerate().skip(start).take(visible) {
+            let is_selected = i == selected;
+            let style = if is_selected {
+                Style::default()
+                    .fg(theme.dialog)
+                    .add_modifier(Modifier::BOLD)
+            } else {
+                Style::default()
+            };
+            let windows = if session.window_count == 1 {
+                tr("1 window").to_string()
+            } else {
+                trf!("{} windows", session.window_count)
+            };
+            let mut spans = vec![
+                Span::styled(if is_selected { "> " } else { "  " }, style),
+                Span::styled(
+                    format!("{:<width$}", session.name, width = name_width),
+                    style,
+                ),
+                Span::raw(format!("{:<11}", windows)),
+                Span::styled(
+                    format!("{:>4}  ", format_age(session.duration(now))),
+                    Style::default().fg(theme.dim),
+                ),
+                Span::styled(
+                    if session.claude_code_pane.is_some() {
+                        "claude "
+                    } else {
+                        "       "
+                    },
+                    Style::default().fg(theme.accent),
+                ),
+            ];
+            if session.attached {
+                spans.push(Span::styled(
+                    format!("{} ", tr("attached")),
+                    Style::default().fg(theme.success),
+                ));
+            }
+            spans.push(Span::styled(
+                short_path(&session.working_directory),
+                Style::default().fg(theme.dim),
+            ));
+            lines.push(Line::from(spans));
+        }
+    }
+
+    lines.push(Line::raw(""));
+    lines.extend(unpushed);
+    let name = sessions.get(selected).map_or("", |s| s.name.as_str());
+    lines.push(match rename {
+        Some(new_name) => Line::from(vec![
+            Span::raw(tr("New name: ")),
+            Span::styled(new_name.to_string(), Style::default().fg(theme.highlight)),
+            Span::raw("_"),
+        ]),
+        None if confirm_kill => Line::styled(
+            trf!(
+                "Kill '{}' and everything running in it? y/Enter confirms",
+                name
+            ),
+            Style::default().fg(theme.highlight),
+        ),
+        None => Line::styled(
+            nav_hint(
+                &trf!(
+                    "jk navigate  Enter switch  {} kill  {} rename  esc close",
+                    keys.key(Action::Kill),
+                    keys.key(Action::Rename)
+                ),
+                keys,
+            ),
+            Style::default().fg(theme.dim),
+        ),
+    });
+
+    let paragraph = Paragraph::new(Text::from(lines)).block(block);
+
+    frame.render_widget(Clear, area);
+    frame.render_widget(paragraph, area);
+}
+
+pub fn render_slash_command_menu(
+    frame: &mut Frame,
+    theme: &Theme,
+    commands: &[SlashCommand],
+    selected: usize,
+    keys: &Keymap,
+) {
+    let visible = commands.len().clamp(1, 12);
+    let area = centered_rect(60, visible as u16 + 4, frame.area());
+
+    let block = Block::default()
+        .title(tr(" Slash Commands "))
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(theme.dialog));
+
+    let width = commands.iter().map(|c| c.command.len()).max().unwrap_or(0) + 2;
+
+    let mut lines = Vec::new();
+    let start = selected.saturating_sub(visible - 1);
+    for (i, command) in commands.iter().enumerate().skip(start).take(visible) {
+        let is_selected = i == selected;
+        let style = if is_selected {
+            Style::default()
+                .fg(theme.dialog)
+                .add_modifier(Modifier::BOLD)
+        } else {
+            Style::default()
+        };
+        lines.push(Line::from(vec![
+            Span::styled(if is_selected { "> " } else { "  " }, style),
+            Span::styled(format!("{:<width$}", command.command), style),
+            Span::styled(command.description.clone(), Style::default().fg(theme.dim)),
+        ]));
+    }
+
+    lines.push(Line::raw(""));
+    lines.push(Line::styled(
+        nav_hint(tr("jk navigate  Enter send  Esc cancel"), keys),
+        Style::default().fg(theme.dim),
+    ));
+
+    let paragraph = Paragraph::new(Text::from(lines)).block(block);
+
+    frame.render_widget(Clear, area);
+    frame.render_widget(paragraph, area);
+}
+
+pub fn render_template_picker(
+    frame: &mut Frame,
+    theme: &Theme,
+    templates: &[PromptTemplate],
+    selected: usize,
+    preview: Option<&str>,
+    keys: &Keymap,
+) {
+    let visible = templates.len().clamp(1, 10);
+    let preview_lines: Vec<&str> = preview
+        .map(|p| p.lines().take(6).collect())
+        .unwrap_or_default();
+    let height = visible + preview_lines.len() + 5;
+    let area = centered_rect(70, height as u16, frame.area());
+
+    let block = Block::default()
+        .title(tr(" Prompt Templates "))
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(theme.dialog));
+
+    let mut lines = Vec::new();
+
+    let start = selected.saturating_sub(visible - 1);
+    for (i, template) in templates.iter().enumerate().skip(start).take(visible) {
+        let is_selected = i == selected;
+        let (prefix, style) = if is_selected {
+            (
+                "> ",
+                Style::default()
+                    .fg(theme.dialog)
+                    .add_modifier(Modifier::BOLD),
+            )
+        } else {
+            ("  ", Style::default())
+        };
+        lines.push(Line::styled(format!("{}{}", prefix, template.name), style));
+    }
+
+    lines.push(Line::styled(
+        "────────────────────────────────────",
+        Style::default().fg(theme.dim),
+    ));
+    for line in preview_lines {
+        lines.push(Line::styled(
+            line.to_string(),
+            Style::default().fg(theme.highlight),
+        ));
+    }
+
+    lines.push(Line::raw(""));
+    lines.push(Line::styled(
+        nav_hint(tr("jk navigate  Enter send  Esc cancel"), keys),
+        Style::default().fg(theme.dim),
+    ));
+
+    let paragraph = Paragraph::new(Text::from(lines)).block(block);
+
+    frame.render_widget(Clear, area);
+    frame.render_widget(paragraph, area);
+}
+
+pub fn render_workspace_picker(
+    frame: &mut Frame,
+    theme: &Theme,
+    workspaces: &[Workspace],
+    selected: usize,
+    members: &[Member],
+    keys: &Keymap,
+) {
+    let visible = workspaces.len().clamp(1, 8);
+    let member_lines = members.len().min(10);
+    let height = visible + member_lines + 5;
+    let area = centered_rect(70, height as u16, frame.area());
+
+    let block = Block::default()
+        .title(tr(" Workspaces "))
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(theme.dialog));
+
+    let mut lines = Vec::new();
+
+    let start = selected.saturating_sub(visible - 1);
+    for (i, workspace) in workspaces.iter().enumerate().skip(start).take(visible) {
+        let is_selected = i == selected;
+        let (prefix, style) = if is_selected {
+            (
+                "> ",
+                Style::default()
+                    .fg(theme.dialog)
+                    .add_modifier(Modifier::BOLD),
+            )
+        } else {
+            ("  ", Style::default())
+        };
+        lines.push(Line::styled(format!("{}{}", prefix, workspace.name), style));
+    }
+
+    lines.push(Line::styled(
+        "────────────────────────────────────",
+        Style::default().fg(theme.dim),
+    ));
+    for member in members.iter().take(member_lines) {
+        let (state, color) = match &member.state {
+            MemberState::Running { target, status } => {
+                (format!("{} ({})", target, status.label()), theme.success)
+            }
+            MemberState::Stopped => (tr("not running").to_string(), theme.highlight),
+            MemberState::Missing => (tr("directory not found").to_string(), theme.error),
+        };
+        lines.push(Line::from(vec![
+            Span::raw(format!("  {}  ", short_path(&member.dir))),
+            Span::styled(state, Style::default().fg(color)),
+        ]));
+    }
+
+    lines.push(Line::raw(""));
+    lines.push(Line::styled(
+        nav_hint(tr("jk navigate  Enter start  Esc cancel"), keys),
+        Style::default().fg(theme.dim),
+    ));
+
+    let paragraph = Paragraph::new(Text::from(lines)).block(block);
+
+    frame.render_widget(Clear, area);
+    frame.render_widget(paragraph, area);
+}
+
+pub fn render_send_file_dialog(
+    frame: &mut Frame,
+    theme: &Theme,
+    target: &str,
+    path: &str,
+    template: Option<&str>,
+    path_suggestions: &[String],
+    path_selected: Option<usize>,
+) {
+    let suggestions_to_show = path_suggestions.len().min(5);
+    let suggestion_extra = if suggestions_to_show > 0 {
+        2 + usize::from(path_suggestions.len() > 5)
+    } else {
+        0
+    };
+    let dialog_height = 10 + suggestions_to_show as u16 + suggestion_extra as u16;
+
+    let area = centered_rect(65, dialog_height, frame.area());
+
+    let block = Block::default()
+        .title(trf!(" Send to {} ", target))
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(theme.dialog));
+
+    let label_style = Style::default()
+        .fg(theme.highlight)
+        .add_modifier(Modifier::BOLD);
+
+    let mut lines = Vec::new();
+
+    // File field with ghost text
+    let mut path_spans = vec![
+        Span::styled(tr("File:     "), label_style),
+        Span::styled(path, Style::default().fg(theme.highlight)),
+    ];
+    if let Some(ghost) = crate::completion::complete_path(path).ghost_text {
+        path_spans.push(Span::styled(
+            ghost,
+            Style::default().fg(theme.dim).add_modifier(Modifier::DIM),
+        ));
+    }
+    path_spans.push(Span::raw("_"));
+    if path.trim().is_empty() {
+        path_spans.push(Span::styled(
+            tr(" (empty: send clipboard)"),
+            Style::default().fg(theme.dim),
+        ));
+    }
+    lines.push(Line::from(path_spans));
+
+    if suggestions_to_show > 0 {
+        lines.push(Line::styled(
+            "          ────────────────────────────────────",
+            Style::default().fg(theme.dim),
+        ));
+        for (i, suggestion) in path_suggestions.iter().take(5).enumerate() {
+            let is_selected = path_selected == Some(i);
+            let prefix = if is_selected {
+                "        > "
+            } else {
+                "          "
+            };
+            let style = if is_selected {
+                Style::default()
+                    .fg(theme.dialog)
+                    .add_modifier(Modifier::BOLD)
+            } else {
+                Style::default().fg(theme.dim)
+            };
+            lines.push(Line::styled(format!("{}{}", prefix, suggestion), style));
+        }
+        if path_suggestions.len() > 5 {
+            lines.push(Line::styled(
+                trf!("          ... and {} more", path_suggestions.len() - 5),
+                Style::default().fg(theme.dim),
+            ));
+        }
+        lines.push(Line::styled(
+            "          ────────────────────────────────────",
+            Style::default().fg(theme.dim),
+        ));
+    }
+
+    lines.push(Line::raw(""));
+    lines.push(Line::from(vec![
+        Span::styled(
+            tr("Template: "),
+            Style::default().add_modifier(Modifier::BOLD),
+        ),
+        match template {
+            Some(name) => Span::styled(name, Style::default().fg(theme.dialog)),
+            None => Span::styled(tr("none (send as-is)"), Style::default().fg(theme.dim)),
+        },
+    ]));
+
+    lines.push(Line::raw(""));
+    lines.push(Line::styled(
+        tr("[Enter] Send  [Tab] Template  [→] Accept  [Ctrl+U] Clear  [Esc] Cancel"),
+        Style::default().fg(theme.dim),
+    ));
+
+    let paragraph = Paragraph::new(Text::from(lines)).block(block);
+
+    frame.render_widget(Clear, area);
+    frame.render_widget(paragraph, area);
+}
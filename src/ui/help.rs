@@ -1,74 +1,219 @@
 //! Help screen and message overlays
+//!
+//! The help screen is generated from the keymap in use, so it lists every
+//! action with the key it is bound to.
 
 use ratatui::{
     layout::Rect,
-    style::{Color, Modifier, Style},
+    style::{Modifier, Style},
     text::{Line, Span},
     widgets::{Block, Borders, Clear, Paragraph, Wrap},
     Frame,
 };
 
-pub fn render_help(frame: &mut Frame) {
-    let area = centered_rect(60, 21, frame.area());
+use crate::config::{Action, CustomAction, Keymap};
+use crate::i18n::{tr, trf};
 
-    let block = Block::default()
-        .title(" Help ")
-        .borders(Borders::ALL)
-        .border_style(Style::default().fg(Color::Cyan));
-
-    let help_text = vec![
-        Line::from(Span::styled(
-            "Navigation",
-            Style::default().add_modifier(Modifier::BOLD),
-        )),
-        Line::raw("  j / ↓       Move down"),
-        Line::raw("  k / ↑       Move up"),
-        Line::raw("  l / →       Open action menu"),
-        Line::raw("  Enter       Switch to session"),
+use super::Theme;
+
+/// Groups of session list actions on the help screen
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Section {
+    Navigation,
+    Actions,
+    Git,
+    Other,
+}
+
+/// Group an action is listed under; every action belongs to one, so new
+/// actions can't be left out of the help screen
+fn section(action: Action) -> Section {
+    match action {
+        Action::SelectNext
+        | Action::SelectPrev
+        | Action::OpenMenu
+        | Action::Switch
+        | Action::SwitchBack => Section::Navigation,
+        Action::NewSession
+        | Action::Kill
+        | Action::Rename
+        | Action::Duplicate
+        | Action::Filter
+        | Action::Continue
+        | Action::Interrupt
+        | Action::SlashCommand
+        | Action::Template
+        | Action::SendFile
+        | Action::Schedule
+        | Action::ScheduledPrompts
+        | Action::Archived
+        | Action::Logs
+        | Action::Snapshots
+        | Action::WorkTime
+        | Action::Stats
+        | Action::AllPanes
+        | Action::Sessions
+        | Action::Workspace
+        | Action::Search
+        | Action::Mark
+        | Action::Sync
+        | Action::Tag
+        | Action::GroupByTag
+        | Action::Sort
+        | Action::PreviewLarger
+        | Action::PreviewSmaller
+        | Action::Refresh => Section::Actions,
+        Action::Review
+        | Action::Stage
+        | Action::Commit
+        | Action::Push
+        | Action::Pull
+        | Action::Fetch
+        | Action::Upstream
+        | Action::NewWorktree => Section::Git,
+        Action::Palette
+        | Action::ReloadConfig
+        | Action::Keybindings
+        | Action::Help
+        | Action::Quit => Section::Other,
+    }
+}
+
+/// Fixed key that also triggers an action in the session list
+fn alternative(action: Action) -> &'static str {
+    match action {
+        Action::SelectNext => "↓",
+        Action::SelectPrev => "↑",
+        Action::OpenMenu => "→",
+        Action::Switch => "Enter",
+        Action::Quit => "Esc",
+        Action::Palette => "Ctrl+p",
+        _ => "",
+    }
+}
+
+/// Every binding of the keymap and of the custom actions, under the mode
+/// it applies to
+fn help_lines<'a>(theme: &Theme, keys: &Keymap, actions: &'a [CustomAction]) -> Vec<Line<'a>> {
+    let key = |action| keys.key(action);
+    let mut lines = vec![mode_heading(theme, tr("Session list"))];
+    for (group, title) in [
+        (Section::Navigation, tr("Navigation")),
+        (Section::Actions, tr("Actions")),
+        (Section::Git, tr("Git")),
+        (Section::Other, tr("Other")),
+    ] {
+        if group != Section::Navigation {
+            lines.push(Line::raw(""));
+        }
+        lines.push(heading(title));
+        lines.extend(
+            Action::ALL
+                .into_iter()
+                .filter(|&action| section(action) == group)
+                .map(|action| action_entry(keys, action, alternative(action))),
+        );
+    }
+    if actions.iter().any(|a| a.key.is_some()) {
+        lines.push(Line::raw(""));
+        lines.push(heading(tr("Custom actions")));
+        lines.extend(
+            actions
+                .iter()
+                .filter_map(|action| Some(entry(&action.key?.to_string(), action.name.as_str()))),
+        );
+    }
+
+    lines.extend([
+        Line::raw(""),
+        mode_heading(theme, tr("Action Menu")),
+        action_entry(keys, Action::SelectNext, "↓"),
+        action_entry(keys, Action::SelectPrev, "↑"),
+        entry(
+            &format!("{} / Enter", key(Action::OpenMenu)),
+            tr("Execute action"),
+        ),
+        entry("h / ←", tr("Go back")),
+        action_entry(keys, Action::Quit, ""),
         Line::raw(""),
-        Line::from(Span::styled(
-            "Actions",
-            Style::default().add_modifier(Modifier::BOLD),
-        )),
-        Line::raw("  n           New session"),
-        Line::raw("  K           Kill session"),
-        Line::raw("  r           Rename session"),
-        Line::raw("  /           Filter sessions"),
-        Line::raw("  R           Refresh list"),
+        mode_heading(theme, tr("Lists and dialogs")),
+        action_entry(keys, Action::SelectNext, "↓"),
+        action_entry(keys, Action::SelectPrev, "↑"),
+        entry("Enter", tr("Select or confirm")),
+        entry(&format!("{} / Esc", key(Action::Quit)), tr("Close")),
         Line::raw(""),
-        Line::from(Span::styled(
-            "Action Menu",
-            Style::default().add_modifier(Modifier::BOLD),
-        )),
-        Line::raw("  h / ←       Go back"),
-        Line::raw("  Enter       Execute action"),
+        mode_heading(theme, tr("Filter")),
+        entry("Enter", tr("Apply filter")),
+        entry("Esc", tr("Cancel")),
         Line::raw(""),
-        Line::from(Span::styled(
-            "Other",
-            Style::default().add_modifier(Modifier::BOLD),
-        )),
-        Line::raw("  ?           Show this help"),
-        Line::raw("  q / Esc     Quit"),
-    ];
+        mode_heading(theme, tr("All sessions")),
+        entry("Enter", tr("Switch to session")),
+        action_entry(keys, Action::Kill, ""),
+        action_entry(keys, Action::Rename, ""),
+        entry(&format!("{} / Esc", key(Action::Sessions)), tr("Close")),
+        Line::raw(""),
+        mode_heading(theme, tr("Help")),
+        entry(
+            &format!("{} / {}", key(Action::SelectNext), key(Action::SelectPrev)),
+            tr("Scroll"),
+        ),
+        entry("PgDn / PgUp", tr("Scroll a page")),
+        entry(&format!("{} / Esc", key(Action::Help)), tr("Close")),
+    ]);
+    lines
+}
+
+/// Render the help screen scrolled down by `scroll` lines, returning the
+/// scroll actually used once clamped to the content
+pub fn render_help(
+    frame: &mut Frame,
+    theme: &Theme,
+    keys: &Keymap,
+    actions: &[CustomAction],
+    scroll: usize,
+) -> usize {
+    let help_text = help_lines(theme, keys, actions);
+    let full = frame.area();
+    // Leave the footer visible
+    let height = (help_text.len() as u16 + 2).min(full.height.saturating_sub(1));
+    let area = centered_rect(64, height, full);
+
+    let visible = area.height.saturating_sub(2) as usize;
+    let scroll = scroll.min(help_text.len().saturating_sub(visible));
+    let mut block = Block::default()
+        .title(tr(" Help "))
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(theme.dialog));
+    if visible < help_text.len() {
+        block = block.title_bottom(
+            Line::from(trf!(
+                " {}-{} of {} ",
+                scroll + 1,
+                (scroll + visible).min(help_text.len()),
+                help_text.len()
+            ))
+            .right_aligned(),
+        );
+    }
 
     let paragraph = Paragraph::new(help_text)
         .block(block)
-        .wrap(Wrap { trim: true });
+        .scroll((scroll as u16, 0));
 
     frame.render_widget(Clear, area);
     frame.render_widget(paragraph, area);
+    scroll
 }
 
-pub fn render_message(frame: &mut Frame, message: &str, color: Color) {
+pub fn render_message(frame: &mut Frame, message: &str, style: Style) {
     let area = frame.area();
 
     // Calculate height needed (at least 1, up to 3 for longer messages)
     let max_width = area.width.saturating_sub(6) as usize;
-    let lines_needed = if max_width > 0 {
-        (message.len() / max_width + 1).min(3)
-    } else {
-        1
-    };
+    let lines_needed = message
+        .len()
+        .checked_div(max_width)
+        .map_or(1, |n| (n + 1).min(3));
     let height = lines_needed as u16;
 
     let msg_area = Rect {
@@ -79,14 +224,44 @@ pub fn render_message(frame: &mut Frame, message: &str, color: Color) {
     };
 
     let text = format!(" {} ", message);
-    let paragraph = Paragraph::new(text)
-        .style(Style::default().fg(Color::White).bg(color))
-        .wrap(Wrap { trim: true });
+    let paragraph = Paragraph::new(text).style(style).wrap(Wrap { trim: true });
 
     frame.render_widget(Clear, msg_area);
     frame.render_widget(paragraph, msg_area);
 }
 
+fn heading(text: &str) -> Line<'_> {
+    Line::from(Span::styled(
+        text,
+        Style::default().add_modifier(Modifier::BOLD),
+    ))
+}
+
+/// Heading naming the mode the lines below it apply to
+fn mode_heading<'a>(theme: &Theme, text: &'a str) -> Line<'a> {
+    Line::from(Span::styled(
+        text,
+        Style::default()
+            .fg(theme.dialog)
+            .add_modifier(Modifier::BOLD | Modifier::UNDERLINED),
+    ))
+}
+
+/// A help line: keys in a fixed-width column, then what they do
+fn entry<'a>(keys: &str, description: &'a str) -> Line<'a> {
+    Line::raw(format!("  {:<14}{}", keys, description))
+}
+
+/// A help line for a rebindable action, with its fixed alternative key if any
+fn action_entry(keys: &Keymap, action: Action, alternative: &str) -> Line<'static> {
+    let key = keys.key(action).to_string();
+    if alternative.is_empty() {
+        entry(&key, action.label())
+    } else {
+        entry(&format!("{} / {}", key, alternative), action.label())
+    }
+}
+
 /// Create a centered rectangle of the given size within the parent area
 pub fn centered_rect(width: u16, height: u16, parent: Rect) -> Rect {
     let x = parent.x + (parent.width.saturating_sub(width)) / 2;
@@ -4,11 +4,25 @@
 //! - Main layout and components (header, session list, preview, status, footer)
 //! - Modal dialogs for user input
 //! - Help screen and message overlays
+//! - Command palette
+//! - Diff viewer for reviews
+//! - Accessibility mode adjustments
+//! - Color themes
+//! - Snapshot tests of rendered screens
 
+mod accessible;
 mod dialogs;
 mod help;
+mod palette;
+mod review;
+#[cfg(test)]
+mod snapshots;
+mod theme;
+
+use std::fmt::Display;
 
 use ansi_to_tui::IntoText;
+use crossterm::event::KeyCode;
 use ratatui::{
     layout::{Alignment, Constraint, Layout, Rect},
     style::{Color, Modifier, Style},
@@ -18,16 +32,30 @@ use ratatui::{
 };
 use unicode_width::UnicodeWidthStr;
 
-use crate::app::{App, LoadingState, Mode};
-use crate::session::ClaudeCodeStatus;
+use crate::app::{App, FooterButton, LoadingState, Mode};
+use crate::config::Action;
+use crate::git::{
+    format_age, CheckBucket, DiffStat, GitContext, PendingOperation, PullRequestChecks,
+};
+use crate::i18n::{fill, tr, tr_pair, trf};
+use crate::session::{short_path, ClaudeCodeStatus};
+use crate::store::now_unix;
+use crate::ui_state::DEFAULT_PREVIEW_PERCENT;
+use crate::worktime;
+
+pub use theme::Theme;
 
 /// Render the application UI
 pub fn render(frame: &mut Frame, app: &mut App) {
     let area = frame.area();
 
-    // Calculate preview height (roughly 50% of available space, min 8, max 20 lines)
     let available_height = area.height.saturating_sub(4); // minus header, status, footer
-    let preview_height = (available_height * 50 / 100).clamp(8, 20);
+                                                          // Picking shows the instance list alone
+    let preview_height = if app.pick.is_some() {
+        0
+    } else {
+        preview_height(available_height, app.preview_percent)
+    };
 
     // Main layout: header, spacing, session list, preview, status bar, footer
     let layout = Layout::vertical([
@@ -43,11 +71,16 @@ pub fn render(frame: &mut Frame, app: &mut App) {
     render_header(frame, app, layout[0]);
     // layout[1] is empty spacing
     render_session_list(frame, app, layout[2]);
-    render_preview(frame, app, layout[3]);
+    if app.pick.is_none() {
+        let preview_area = render_preview(frame, app, layout[3]);
+        app.scroll_state.set_preview_area(preview_area);
+    }
     render_status_bar(frame, app, layout[4]);
-    render_footer(frame, app, layout[5]);
+    app.footer_buttons = render_footer(frame, app, layout[5]);
 
     // Render modal overlays
+    let theme = theme(app);
+    let mut help_scroll = None;
     match &app.mode {
         Mode::ConfirmAction => {
             dialogs::render_confirm_action(frame, app);
@@ -61,6 +94,7 @@ pub fn render(frame: &mut Frame, app: &mut App) {
         } => {
             dialogs::render_new_session_dialog(
                 frame,
+                &theme,
                 name,
                 path,
                 *field,
@@ -68,15 +102,38 @@ pub fn render(frame: &mut Frame, app: &mut App) {
                 *path_selected,
             );
         }
+        Mode::EditTags { path, input } => {
+            dialogs::render_edit_tags_dialog(frame, &theme, path, input);
+        }
         Mode::Rename { old_name, new_name } => {
-            dialogs::render_rename_dialog(frame, old_name, new_name);
+            dialogs::render_rename_dialog(frame, &theme, old_name, new_name);
         }
         Mode::Commit { message } => {
-            dialogs::render_commit_dialog(frame, message);
+            dialogs::render_commit_dialog(frame, &theme, message);
+        }
+        Mode::BranchFromIssue {
+            input,
+            selected,
+            send_prompt,
+            ..
+        } => {
+            dialogs::render_branch_from_issue_dialog(
+                frame,
+                &theme,
+                input,
+                &app.filtered_issues(),
+                *selected,
+                app.issue_branch_preview().as_deref(),
+                *send_prompt,
+            );
+        }
+        Mode::Rebase { branch, base, .. } => {
+            dialogs::render_rebase_dialog(frame, &theme, branch, base);
         }
         Mode::NewWorktree {
             branch_input,
             selected_branch,
+            upstream,
             worktree_path,
             session_name,
             field,
@@ -89,6 +146,7 @@ pub fn render(frame: &mut Frame, app: &mut App) {
                 app,
                 branch_input,
                 *selected_branch,
+                upstream,
                 worktree_path,
                 session_name,
                 *field,
@@ -97,7 +155,7 @@ pub fn render(frame: &mut Frame, app: &mut App) {
             );
         }
         Mode::Filter { input } => {
-            render_filter_bar(frame, input, layout[3]);
+            render_filter_bar(frame, &theme, input, layout[3]);
         }
         Mode::CreatePullRequest {
             title,
@@ -105,19 +163,317 @@ pub fn render(frame: &mut Frame, app: &mut App) {
             base_branch,
             field,
         } => {
-            dialogs::render_create_pr_dialog(frame, title, body, base_branch, *field);
+            dialogs::render_create_pr_dialog(frame, &theme, title, body, base_branch, *field);
+        }
+        Mode::Credentials {
+            url,
+            username,
+            password,
+            store,
+            field,
+            rejected,
+            ..
+        } => {
+            dialogs::render_credentials_dialog(
+                frame, &theme, url, username, password, *store, *field, *rejected,
+            );
+        }
+        Mode::Passphrase {
+            key,
+            passphrase,
+            rejected,
+            ..
+        } => {
+            dialogs::render_passphrase_dialog(frame, &theme, key, passphrase, *rejected);
+        }
+        Mode::SchedulePrompt {
+            prompt,
+            when,
+            field,
+        } => {
+            let target = app
+                .selected_instance()
+                .map(|i| i.display_name())
+                .unwrap_or_default();
+            dialogs::render_schedule_prompt_dialog(frame, &theme, &target, prompt, when, *field);
+        }
+        Mode::SlashCommandMenu { selected } => {
+            dialogs::render_slash_command_menu(
+                frame,
+                &theme,
+                &app.config.all_slash_commands(),
+                *selected,
+                &app.config.keys,
+            );
+        }
+        Mode::TemplatePicker { selected } => {
+            let preview = app.expanded_selected_template();
+            dialogs::render_template_picker(
+                frame,
+                &theme,
+                &app.config.templates,
+                *selected,
+                preview.as_deref(),
+                &app.config.keys,
+            );
+        }
+        Mode::Workspaces { selected } => {
+            let members = app.selected_workspace_members();
+            dialogs::render_workspace_picker(
+                frame,
+                &theme,
+                &app.config.workspaces,
+                *selected,
+                &members,
+                &app.config.keys,
+            );
+        }
+        Mode::SendFile {
+            path,
+            template,
+            path_suggestions,
+            path_selected,
+        } => {
+            let target = app
+                .selected_instance()
+                .map(|i| i.display_name())
+                .unwrap_or_default();
+            let template = template
+                .and_then(|i| app.config.templates.get(i))
+                .map(|t| t.name.as_str());
+            dialogs::render_send_file_dialog(
+                frame,
+                &theme,
+                &target,
+                path,
+                template,
+                path_suggestions,
+                *path_selected,
+            );
+        }
+        Mode::Review {
+            lines,
+            cursor,
+            comments,
+            input,
+        } => {
+            let target = app
+                .selected_instance()
+                .map(|i| i.display_name())
+                .unwrap_or_default();
+            review::render_review(
+                frame,
+                &theme,
+                &target,
+                lines,
+                *cursor,
+                comments,
+                input.as_deref(),
+            );
+        }
+        Mode::CloneLayout {
+            source_session,
+            main_repo,
+            name,
+            branch,
+            field,
+            ..
+        } => {
+            dialogs::render_clone_layout_dialog(
+                frame,
+                &theme,
+                source_session,
+                name,
+                branch,
+                main_repo.is_some(),
+                *field,
+            );
+        }
+        Mode::ArrangePanes { selected, .. } => {
+            let target = app
+                .selected_instance()
+                .map(|i| i.display_name())
+                .unwrap_or_default();
+            dialogs::render_arrange_panes(frame, &theme, &target, *selected, &app.config.keys);
+        }
+        Mode::MoveWindow {
+            window,
+            link,
+            input,
+            selected,
+            ..
+        } => {
+            let sessions = app.filtered_move_sessions();
+            dialogs::render_move_window_dialog(
+                frame, &theme, window, *link, input, &sessions, *selected,
+            );
+        }
+        Mode::PendingJobs { jobs, selected } => {
+            dialogs::render_pending_jobs(frame, &theme, jobs, *selected, &app.config.keys);
+        }
+        Mode::Logs { lines, scroll } => {
+            dialogs::render_logs(frame, &theme, lines, *scroll);
+        }
+        Mode::ActionOutput {
+            name,
+            target,
+            lines,
+            state,
+            scroll,
+        } => {
+            dialogs::render_action_output(frame, &theme, name, target, lines, state, *scroll);
+        }
+        Mode::Conflicts {
+            files,
+            selected,
+            operation,
+            ..
+        } => {
+            dialogs::render_conflicts(frame, &theme, files, *selected, *operation);
+        }
+        Mode::Archived { sessions, selected } => {
+            dialogs::render_archived(frame, &theme, sessions, *selected, &app.config.keys);
+        }
+        Mode::Snapshots {
+            snapshots,
+            selected,
+            content,
+            scroll,
+        } => match (content, snapshots.get(*selected)) {
+            (Some(lines), Some(snapshot)) => {
+                dialogs::render_snapshot(frame, &theme, snapshot, lines, *scroll);
+            }
+            _ => dialogs::render_snapshots(
+                frame,
+                &theme,
+                snapshots,
+                *selected,
+                app.config.ascii_glyphs(),
+                &app.config.keys,
+            ),
+        },
+        Mode::WorkTime { totals, selected } => {
+            dialogs::render_work_time(frame, &theme, totals, *selected, &app.config.keys);
+        }
+        Mode::Stats { stats } => {
+            dialogs::render_stats(frame, &theme, stats, app.config.usage_stats);
+        }
+        Mode::Search {
+            query,
+            results,
+            selected,
+        } => {
+            dialogs::render_search(frame, &theme, query, results.as_deref(), *selected);
+        }
+        Mode::Palette {
+            input,
+            commands,
+            selected,
+        } => {
+            palette::render_palette(frame, &theme, input, commands, *selected, &app.config.keys);
+        }
+        Mode::AllPanes { panes, selected } => {
+            dialogs::render_all_panes(frame, &theme, panes, *selected, &app.config.keys);
+        }
+        Mode::Sessions {
+            sessions,
+            selected,
+            confirm_kill,
+            rename,
+            ..
+        } => {
+            dialogs::render_sessions(
+                frame,
+                app,
+                sessions,
+                *selected,
+                *confirm_kill,
+                rename.as_deref(),
+            );
+        }
+        Mode::Keybindings {
+            selected,
+            capturing,
+            conflict,
+        } => {
+            dialogs::render_keybindings(
+                frame,
+                &theme,
+                &app.config.keys,
+                *selected,
+                *capturing,
+                *conflict,
+            );
+        }
+        Mode::Help { scroll } => {
+            help_scroll = Some(help::render_help(
+                frame,
+                &theme,
+                &app.config.keys,
+                &app.config.actions,
+                *scroll,
+            ));
         }
-        Mode::Help => {
-            help::render_help(frame);
+        Mode::ConfigError { error } => {
+            dialogs::render_config_error(
+                frame,
+                &theme,
+                error,
+                app.config.keys.key(Action::ReloadConfig),
+            );
+        }
+        Mode::Sync { rows, selected } => {
+            dialogs::render_sync(frame, &theme, rows, *selected, app.is_syncing());
         }
         Mode::Normal | Mode::ActionMenu => {}
     }
+    // Keep the help screen's scroll within its content
+    if let (Some(clamped), Mode::Help { scroll }) = (help_scroll, &mut app.mode) {
+        *scroll = clamped;
+    }
 
     // Render error/message overlay
+    let accessible = app.config.accessibility;
+    let palette = &app.config.palette;
     if let Some(ref error) = app.error {
-        help::render_message(frame, error, Color::Red);
+        if accessible {
+            let style = Style::default()
+                .fg(Color::Black)
+                .bg(palette.crashed)
+                .add_modifier(Modifier::BOLD);
+            help::render_message(frame, &trf!("Error: {}", error), style);
+        } else {
+            help::render_message(
+                frame,
+                error,
+                Style::default().fg(theme.selection_text).bg(theme.error),
+            );
+        }
     } else if let Some(ref message) = app.message {
-        help::render_message(frame, message, Color::Green);
+        let style = if accessible {
+            Style::default().fg(Color::Black).bg(palette.working)
+        } else {
+            Style::default().fg(theme.selection_text).bg(theme.success)
+        };
+        help::render_message(frame, message, style);
+    }
+
+    if accessible {
+        accessible::adapt(frame.buffer_mut(), palette, &theme);
+    } else if app.config.ascii {
+        accessible::to_ascii(frame.buffer_mut());
+    }
+    if app.config.no_color {
+        accessible::strip_colors(frame.buffer_mut());
+    }
+}
+
+/// Colors in use: accessibility mode recolors the defaults through its own palette
+fn theme(app: &App) -> Theme {
+    if app.config.accessibility {
+        Theme::default()
+    } else {
+        app.config.theme
     }
 }
 
@@ -125,18 +481,24 @@ fn render_header(frame: &mut Frame, app: &App, area: Rect) {
     let current = app
         .current_pane
         .as_ref()
-        .map(|s| format!(" attached: {} ", s))
+        .map(|s| trf!(" attached: {} ", s))
         .unwrap_or_default();
 
+    // The `--cwd` directory the list is limited to
+    let name = match &app.scope {
+        Some(scope) => format!("─ claude-tmux {} ─", short_path(scope)),
+        None => "─ claude-tmux ─".to_string(),
+    };
     let title = format!(
-        "─ claude-tmux ─{:─>width$}",
+        "{}{:─>width$}",
+        name,
         current,
-        width = area.width as usize - 15
+        width = (area.width as usize).saturating_sub(name.width())
     );
 
     let header = Paragraph::new(title).style(
         Style::default()
-            .fg(Color::Rgb(215, 119, 87))
+            .fg(theme(app).accent)
             .add_modifier(Modifier::BOLD),
     );
 
@@ -147,6 +509,17 @@ fn render_session_list(frame: &mut Frame, app: &mut App, area: Rect) {
     // Compute scroll state values before borrowing for items
     let selected_index = app.compute_flat_list_index();
     let total_items = app.compute_total_list_items();
+    let accessible = app.config.accessibility;
+    let palette = app.config.palette;
+    let theme = theme(app);
+
+    // Reserve the last row for the paging indicator when the list overflows
+    let (area, indicator_area) = if total_items > area.height as usize && area.height > 1 {
+        let chunks = Layout::vertical([Constraint::Min(1), Constraint::Length(1)]).split(area);
+        (chunks[0], Some(chunks[1]))
+    } else {
+        (area, None)
+    };
     let visible_height = area.height as usize;
 
     // Take scroll_state out of app to avoid borrow conflicts
@@ -158,18 +531,26 @@ fn render_session_list(frame: &mut Frame, app: &mut App, area: Rect) {
     if filtered.is_empty() {
         let empty_msg = match app.loading_state {
             LoadingState::NotStarted | LoadingState::LoadingInstances => {
-                "Loading Claude Code instances..."
+                tr("Loading Claude Code instances...").to_string()
             }
-            LoadingState::LoadingGitContexts | LoadingState::Complete => {
-                if app.filter.is_empty() {
-                    "No Claude Code instances found. Press 'n' to create a new session."
+            LoadingState::DetectingStatus
+            | LoadingState::LoadingGitContexts
+            | LoadingState::Complete => {
+                if app.offers_agent_here() {
+                    let scope = app.scope.as_deref().map(short_path).unwrap_or_default();
+                    trf!("No agents in {}. Press ⏎ to start one here.", scope)
+                } else if app.filter.is_empty() {
+                    trf!(
+                        "No Claude Code instances found. Press '{}' to create a new session.",
+                        app.config.keys.key(Action::NewSession)
+                    )
                 } else {
-                    "No instances match the filter."
+                    tr("No instances match the filter.").to_string()
                 }
             }
         };
         let paragraph = Paragraph::new(empty_msg)
-            .style(Style::default().fg(Color::DarkGray))
+            .style(Style::default().fg(theme.dim))
             .alignment(Alignment::Center);
         frame.render_widget(paragraph, area);
         // Put scroll_state back before returning
@@ -185,9 +566,47 @@ fn render_session_list(frame: &mut Frame, app: &mut App, area: Rect) {
         .unwrap_or(10)
         .max(10);
 
-    let mut items: Vec<ListItem> = Vec::new();
+    // Only build the rows of the visible window. Rows map one-to-one to
+    // instances except for the expanded block below the selected instance,
+    // which the centered offset never scrolls past.
+    let offset = scroll_state.update(selected_index, total_items, visible_height);
+    let is_menu_open = matches!(app.mode, Mode::ActionMenu);
+    // Group headers break the mapping; build rows from the top then
+    let first = if app.group_by_tag {
+        0
+    } else if is_menu_open && offset > app.selected {
+        app.selected
+    } else {
+        offset
+    };
+    let skip_rows = offset - first;
 
-    for (i, instance) in filtered.iter().enumerate() {
+    let mut items: Vec<ListItem> = Vec::new();
+    // Instance of each item, for mouse clicks
+    let mut rows: Vec<Option<usize>> = Vec::new();
+    let mut first_shown = None;
+    let mut last = first;
+
+    for (i, instance) in filtered.iter().enumerate().skip(first) {
+        if items.len() >= skip_rows + visible_height {
+            break;
+        }
+        if let Some(group) = app.group_header(&filtered, i) {
+            let label = match group {
+                Some(tag) => format!("#{}", tag),
+                None => tr("untagged").to_string(),
+            };
+            items.push(ListItem::new(Line::styled(
+                format!(" {}", label),
+                Style::default()
+                    .fg(theme.dialog)
+                    .add_modifier(Modifier::BOLD),
+            )));
+        }
+        if items.len() >= skip_rows {
+            first_shown.get_or_insert(i);
+        }
+        last = i;
         let is_selected = i == app.selected;
         let is_current = app
             .current_pane
@@ -195,7 +614,7 @@ fn render_session_list(frame: &mut Frame, app: &mut App, area: Rect) {
             .is_some_and(|c| c == &instance.tmux_target());
 
         // Show ▾ when action menu is open for this instance, ▸ when selected but collapsed
-        let is_expanded = is_selected && matches!(app.mode, Mode::ActionMenu);
+        let is_expanded = is_selected && is_menu_open;
         let marker = if is_selected {
             if is_expanded {
                 "▾"
@@ -205,38 +624,52 @@ fn render_session_list(frame: &mut Frame, app: &mut App, area: Rect) {
         } else {
             " "
         };
+        let mark = if app.marked.contains(&instance.pane_id) {
+            "*"
+        } else {
+            " "
+        };
         let status = &instance.status;
 
         // Colors based on status for non-selected lines
         let (status_color, line_color) = if is_selected {
-            (Color::White, Color::White)
+            (theme.selection_text, theme.selection_text)
+        } else if accessible {
+            let c = match status {
+                ClaudeCodeStatus::WaitingInput => palette.waiting,
+                ClaudeCodeStatus::Working => palette.working,
+                ClaudeCodeStatus::Crashed => palette.crashed,
+                ClaudeCodeStatus::Unknown => palette.unknown,
+                ClaudeCodeStatus::Idle => palette.idle,
+            };
+            (c, c)
         } else {
-            match status {
-                ClaudeCodeStatus::WaitingInput => {
-                    let c = Color::Rgb(229, 192, 123); // #E5C07B - Yellow/Gold, most visible
-                    (c, c)
-                }
-                ClaudeCodeStatus::Working => {
-                    let c = Color::Rgb(152, 195, 121); // #98C379 - Green, active
-                    (c, c)
-                }
-                ClaudeCodeStatus::Unknown => {
-                    let c = Color::Rgb(102, 102, 102); // #666666 - Medium gray
-                    (c, c)
-                }
-                ClaudeCodeStatus::Idle => {
-                    let c = Color::Rgb(153, 153, 153); // #999999 - Medium gray, low priority
-                    (c, c)
-                }
-            }
+            let c = theme.status(*status);
+            (c, c)
         };
 
         let path_color = line_color;
 
+        // In accessibility mode, statuses that need attention are also set
+        // apart by emphasis rather than color alone
+        let status_style = match status {
+            ClaudeCodeStatus::WaitingInput if accessible => Style::default()
+                .fg(status_color)
+                .add_modifier(Modifier::BOLD),
+            ClaudeCodeStatus::Crashed if accessible => Style::default()
+                .fg(status_color)
+                .add_modifier(Modifier::BOLD | Modifier::UNDERLINED),
+            _ => Style::default().fg(status_color),
+        };
+
         let name_style = if is_selected {
             Style::default()
-                .fg(Color::White)
+                .fg(theme.selection_text)
                 .add_modifier(Modifier::BOLD)
+        } else if is_current && accessible {
+            Style::default()
+                .fg(line_color)
+                .add_modifier(Modifier::BOLD | Modifier::UNDERLINED)
         } else if is_current {
             Style::default().fg(line_color).add_modifier(Modifier::BOLD)
         } else {
@@ -244,16 +677,28 @@ fn render_session_list(frame: &mut Frame, app: &mut App, area: Rect) {
         };
 
         // Build git info spans
-        let git_spans = if let Some(ref git) = instance.git_context {
+        let git_spans = if instance.is_directory_missing() {
+            let color = if is_selected {
+                theme.selection_text
+            } else if accessible {
+                palette.crashed
+            } else {
+                theme.crashed
+            };
+            vec![Span::styled(
+                tr(" [missing dir]"),
+                Style::default().fg(color),
+            )]
+        } else if let Some(ref git) = instance.git_context {
             let (open, close) = if git.is_worktree {
                 ("[", "]")
             } else {
                 ("(", ")")
             };
             let git_color = if is_selected {
-                Color::White
+                theme.selection_text
             } else {
-                Color::Rgb(215, 119, 87) // Claude Code orange #D77757
+                theme.accent
             };
 
             // Show status indicators: + for staged, * for unstaged
@@ -266,9 +711,9 @@ fn render_session_list(frame: &mut Frame, app: &mut App, area: Rect) {
             }
             let status_spans = if !status_str.is_empty() {
                 let color = if git.has_staged && !git.has_unstaged {
-                    Color::Green // Only staged = green
+                    theme.staged // Only staged
                 } else {
-                    Color::Yellow // Mixed state = yellow
+                    theme.unstaged // Mixed state
                 };
                 vec![Span::styled(
                     format!(" {}", status_str),
@@ -285,16 +730,53 @@ fn render_session_list(frame: &mut Frame, app: &mut App, area: Rect) {
                 Span::styled(close, Style::default().fg(git_color)),
             ];
             spans.extend(status_spans);
+            spans.extend(branch_badge_spans(git, is_selected, &theme));
+            if let Some(operation) = git.in_progress {
+                spans.push(Span::styled(
+                    format!(" {}", in_progress_label(operation)),
+                    Style::default()
+                        .fg(if is_selected {
+                            theme.selection_text
+                        } else {
+                            theme.conflict
+                        })
+                        .add_modifier(Modifier::BOLD),
+                ));
+            }
+            if let Some(stat) = app.diff_stats.get(&instance.working_directory) {
+                spans.extend(diff_stat_spans(stat, is_selected, &theme));
+            }
+            if let Some(checks) = app.pr_checks.get(&instance.working_directory) {
+                spans.extend(checks_span(checks, is_selected, &theme));
+            }
+            if let Some(commit) = git
+                .last_commit
+                .as_ref()
+                .filter(|_| app.config.show_last_commit)
+            {
+                let color = if is_selected {
+                    theme.selection_text
+                } else {
+                    theme.dim
+                };
+                spans.push(Span::styled(
+                    format!("  {}", trf!("{} ago", commit.age(now_unix()))),
+                    Style::default().fg(color),
+                ));
+            }
             spans
-        } else if app.loading_state == LoadingState::LoadingGitContexts {
+        } else if matches!(
+            app.loading_state,
+            LoadingState::DetectingStatus | LoadingState::LoadingGitContexts
+        ) {
             // Show loading placeholder while git contexts are being loaded
-            vec![Span::styled(" (...)", Style::default().fg(Color::DarkGray))]
+            vec![Span::styled(" (...)", Style::default().fg(theme.dim))]
         } else {
             vec![]
         };
 
         let mut line_spans = vec![
-            Span::raw(format!(" {} ", marker)),
+            Span::raw(format!("{}{} ", mark, marker)),
             Span::styled(
                 format!("{:<width$}", instance.display_name(), width = max_name_len),
                 name_style,
@@ -302,21 +784,54 @@ fn render_session_list(frame: &mut Frame, app: &mut App, area: Rect) {
             Span::raw("  "),
             Span::styled(status.symbol(), Style::default().fg(status_color)),
             Span::raw(" "),
-            Span::styled(
-                format!("{:<8}", status.label()),
-                Style::default().fg(status_color),
-            ),
+            Span::styled(format!("{:<8}", tr(status.label())), status_style),
             Span::raw("  "),
             Span::styled(instance.display_path(), Style::default().fg(path_color)),
         ];
         line_spans.extend(git_spans);
+        for tag in app.instance_tags(instance) {
+            let color = if is_selected {
+                theme.selection_text
+            } else {
+                theme.special
+            };
+            line_spans.push(Span::styled(
+                format!(" #{}", tag),
+                Style::default().fg(color),
+            ));
+        }
+        // Claude works elsewhere than the pane's current path shows
+        if instance.pane_path.is_some() {
+            let color = if is_selected {
+                theme.selection_text
+            } else {
+                theme.warning
+            };
+            line_spans.push(Span::styled(
+                tr(" [pane moved]"),
+                Style::default().fg(color),
+            ));
+        }
+        if instance.logging {
+            let color = if is_selected {
+                theme.selection_text
+            } else {
+                theme.error
+            };
+            line_spans.push(Span::styled(
+                tr(" [log]"),
+                Style::default().fg(color).add_modifier(Modifier::BOLD),
+            ));
+        }
 
         let line = Line::from(line_spans);
 
-        let style = if is_selected {
+        let style = if is_selected && accessible {
+            Style::default().add_modifier(Modifier::REVERSED | Modifier::BOLD)
+        } else if is_selected {
             Style::default()
-                .fg(Color::White)
-                .bg(Color::Rgb(215, 119, 87))
+                .fg(theme.selection_text)
+                .bg(theme.selection)
                 .add_modifier(Modifier::BOLD)
         } else {
             Style::default()
@@ -328,61 +843,244 @@ fn render_session_list(frame: &mut Frame, app: &mut App, area: Rect) {
         if is_expanded {
             render_expanded_instance_content(app, instance, &mut items);
         }
+        rows.resize(items.len(), Some(i));
     }
+    rows.drain(..skip_rows.min(rows.len()));
+    rows.truncate(visible_height);
+    scroll_state.set_rows(area, rows);
 
     // Scope the list rendering so borrows are released before we restore scroll_state
     {
-        let list = List::new(items);
+        let list = List::new(items.into_iter().skip(skip_rows));
 
-        // Update scroll state with centered scrolling behavior
-        let list_state = scroll_state.update(selected_index, total_items, visible_height);
-
-        // Render with stateful widget for proper scrolling
+        // Render the window with the selection relative to its first row
+        let list_state = scroll_state.window_state(selected_index);
         StatefulWidget::render(list, area, frame.buffer_mut(), list_state);
     }
 
+    if let Some(indicator_area) = indicator_area {
+        let first = if app.group_by_tag {
+            first_shown.unwrap_or(first)
+        } else {
+            first
+        };
+        render_paging_indicator(frame, &theme, first, last, filtered.len(), indicator_area);
+    }
+
     // Put scroll_state back into app (list borrows are now released)
     app.scroll_state = scroll_state;
 }
 
+/// Tag of a merge or rebase that stopped halfway
+fn in_progress_label(operation: PendingOperation) -> &'static str {
+    match operation {
+        PendingOperation::Merge => tr("MERGING"),
+        PendingOperation::Rebase => tr("REBASING"),
+    }
+}
+
+/// Compact diffstat of a checkout, e.g. "4 files +120 −33"
+fn diff_stat_spans(stat: &DiffStat, is_selected: bool, theme: &Theme) -> Vec<Span<'static>> {
+    let color = |c| if is_selected { theme.selection_text } else { c };
+    let files = if stat.files == 1 {
+        tr("1 file").to_string()
+    } else {
+        trf!("{} files", stat.files)
+    };
+    vec![
+        Span::styled(
+            format!("  {}", files),
+            Style::default().fg(color(theme.dim)),
+        ),
+        Span::styled(
+            format!(" +{}", stat.insertions),
+            Style::default().fg(color(theme.insertions)),
+        ),
+        Span::styled(
+            format!(" −{}", stat.deletions),
+            Style::default().fg(color(theme.deletions)),
+        ),
+    ]
+}
+
+/// Badges for stashes ("$2") and commits not yet on the default branch ("main+3")
+fn branch_badge_spans(git: &GitContext, is_selected: bool, theme: &Theme) -> Vec<Span<'static>> {
+    let color = |c| if is_selected { theme.selection_text } else { c };
+    let mut spans = Vec::new();
+    if let Some(default_branch) = git
+        .default_branch
+        .as_ref()
+        .filter(|_| git.ahead_of_default > 0)
+    {
+        spans.push(Span::styled(
+            format!(" {}+{}", default_branch, git.ahead_of_default),
+            Style::default().fg(color(theme.ahead)),
+        ));
+    }
+    if git.stash_count > 0 {
+        spans.push(Span::styled(
+            format!(" ${}", git.stash_count),
+            Style::default().fg(color(theme.stash)),
+        ));
+    }
+    spans
+}
+
+/// CI summary for the PR row, naming the failing checks
+fn checks_detail_spans(checks: &PullRequestChecks, theme: &Theme) -> Vec<Span<'static>> {
+    let mut spans = vec![Span::styled(
+        format!("  {} ", tr("CI:")),
+        Style::default().fg(theme.dim),
+    )];
+    let failing: Vec<&str> = checks.failing().map(|r| r.name.as_str()).collect();
+    if !failing.is_empty() {
+        spans.push(Span::styled(
+            trf!("{} failing ({})", failing.len(), failing.join(", ")),
+            Style::default().fg(theme.error),
+        ));
+        spans.push(Span::raw(", "));
+    }
+    let pending = checks.count(CheckBucket::Pending);
+    if pending > 0 {
+        spans.push(Span::styled(
+            trf!("{} pending", pending),
+            Style::default().fg(theme.warning),
+        ));
+        spans.push(Span::raw(", "));
+    }
+    spans.push(Span::styled(
+        trf!("{} passed", checks.count(CheckBucket::Pass)),
+        Style::default().fg(theme.success),
+    ));
+    spans
+}
+
+/// CI status column: "CI ✓", "CI ✗2" (failing checks) or "CI …" (pending)
+fn checks_span(
+    checks: &PullRequestChecks,
+    is_selected: bool,
+    theme: &Theme,
+) -> Option<Span<'static>> {
+    let (text, color) = match checks.overall()? {
+        CheckBucket::Fail => (
+            format!("CI ✗{}", checks.count(CheckBucket::Fail)),
+            theme.error,
+        ),
+        CheckBucket::Pending => ("CI …".to_string(), theme.warning),
+        CheckBucket::Pass | CheckBucket::Skipping => ("CI ✓".to_string(), theme.success),
+    };
+    let color = if is_selected {
+        theme.selection_text
+    } else {
+        color
+    };
+    Some(Span::styled(
+        format!("  {}", text),
+        Style::default().fg(color),
+    ))
+}
+
+/// Render which instances are visible, e.g. "↑ 41–60 of 312 ↓"
+fn render_paging_indicator(
+    frame: &mut Frame,
+    theme: &Theme,
+    first: usize,
+    last: usize,
+    total: usize,
+    area: Rect,
+) {
+    let up = if first > 0 { "↑" } else { " " };
+    let down = if last + 1 < total { "↓" } else { " " };
+    let text = trf!("{} {}–{} of {} {} ", up, first + 1, last + 1, total, down);
+
+    let indicator = Paragraph::new(text)
+        .style(Style::default().fg(theme.dim))
+        .alignment(Alignment::Right);
+    frame.render_widget(indicator, area);
+}
+
 /// Render the expanded content for an instance in action menu mode
 fn render_expanded_instance_content<'a>(
     app: &'a App,
     instance: &'a crate::session::ClaudeInstance,
     items: &mut Vec<ListItem<'a>>,
 ) {
-    let label_style = Style::default().fg(Color::DarkGray);
-    let value_style = Style::default().fg(Color::White);
+    let theme = theme(app);
+    let label_style = Style::default().fg(theme.dim);
+    let value_style = Style::default().fg(theme.text);
 
     // Instance metadata row
     let attached_str = if instance.session_attached {
-        "yes"
+        tr("yes")
     } else {
-        "no"
+        tr("no")
     };
 
     let meta_line = Line::from(vec![
         Span::raw("     "),
-        Span::styled("session: ", label_style),
+        Span::styled(tr("session: "), label_style),
         Span::styled(&instance.session_name, value_style),
         Span::raw("  "),
-        Span::styled("window: ", label_style),
+        Span::styled(tr("window: "), label_style),
         Span::styled(&instance.window_name, value_style),
         Span::raw("  "),
-        Span::styled("pane: ", label_style),
+        Span::styled(tr("pane: "), label_style),
         Span::styled(format!("{}", instance.pane_index), value_style),
         Span::raw("  "),
-        Span::styled("attached: ", label_style),
+        Span::styled(tr("attached: "), label_style),
         Span::styled(attached_str, value_style),
     ]);
     items.push(ListItem::new(meta_line));
 
+    // Where the pane is, when Claude was started elsewhere
+    if let Some(ref pane_path) = instance.pane_path {
+        items.push(ListItem::new(Line::from(vec![
+            Span::raw("     "),
+            Span::styled(tr("claude in: "), label_style),
+            Span::styled(instance.display_path(), value_style),
+            Span::raw("  "),
+            Span::styled(tr("pane in: "), label_style),
+            Span::styled(crate::session::short_path(pane_path), value_style),
+        ])));
+    }
+
+    // Environment of the Claude process, to tell agents' setups apart
+    if let Some(env) = app.agent_env.as_ref().filter(|e| !e.is_empty()) {
+        let mut env_spans = vec![Span::raw("     ")];
+        if let Some(ref version) = env.node_version {
+            env_spans.push(Span::styled(tr("node: "), label_style));
+            env_spans.push(Span::styled(version.as_str(), value_style));
+        }
+        for (name, value) in &env.vars {
+            if env_spans.len() > 1 {
+                env_spans.push(Span::raw("  "));
+            }
+            env_spans.push(Span::styled(format!("{}=", name), label_style));
+            env_spans.push(Span::styled(value.as_str(), value_style));
+        }
+        if env_spans.len() > 1 {
+            items.push(ListItem::new(Line::from(env_spans)));
+        }
+
+        if !env.mcp.is_empty() {
+            let mut mcp_spans = vec![Span::raw("     "), Span::styled(tr("mcp: "), label_style)];
+            for (i, config) in env.mcp.iter().enumerate() {
+                if i > 0 {
+                    mcp_spans.push(Span::raw("  "));
+                }
+                mcp_spans.push(Span::styled(config.servers.join(", "), value_style));
+                mcp_spans.push(Span::styled(format!(" ({})", config.source), label_style));
+            }
+            items.push(ListItem::new(Line::from(mcp_spans)));
+        }
+    }
+
     // Git metadata row (if available)
     if let Some(ref git) = instance.git_context {
         let mut git_spans = vec![
             Span::raw("     "),
-            Span::styled("branch: ", label_style),
-            Span::styled(&git.branch, Style::default().fg(Color::Rgb(215, 119, 87))),
+            Span::styled(tr("branch: "), label_style),
+            Span::styled(&git.branch, Style::default().fg(theme.accent)),
         ];
 
         if git.ahead > 0 || git.behind > 0 {
@@ -390,7 +1088,7 @@ fn render_expanded_instance_content<'a>(
             if git.ahead > 0 {
                 git_spans.push(Span::styled(
                     format!("↑{}", git.ahead),
-                    Style::default().fg(Color::Green),
+                    Style::default().fg(theme.success),
                 ));
             }
             if git.behind > 0 {
@@ -399,32 +1097,120 @@ fn render_expanded_instance_content<'a>(
                 }
                 git_spans.push(Span::styled(
                     format!("↓{}", git.behind),
-                    Style::default().fg(Color::Red),
+                    Style::default().fg(theme.error),
+                ));
+            }
+        }
+
+        if let Some(default_branch) = git
+            .default_branch
+            .as_ref()
+            .filter(|_| git.ahead_of_default > 0)
+        {
+            git_spans.push(Span::raw("  "));
+            git_spans.push(Span::styled(
+                trf!("ahead of {}: ", default_branch),
+                label_style,
+            ));
+            git_spans.push(Span::styled(
+                git.ahead_of_default.to_string(),
+                Style::default().fg(theme.ahead),
+            ));
+        }
+
+        if git.has_remote {
+            let fetched = match git.last_fetched {
+                Some(time) => trf!(
+                    "{} ago",
+                    format_age(now_unix().saturating_sub(time.max(0) as u64))
+                ),
+                None => tr("never").to_string(),
+            };
+            git_spans.push(Span::raw("  "));
+            git_spans.push(Span::styled(tr("fetched: "), label_style));
+            git_spans.push(Span::styled(fetched, value_style));
+            if git.auto_fetch.unwrap_or(app.config.auto_fetch) {
+                git_spans.push(Span::styled(
+                    format!(" {}", tr("(auto)")),
+                    Style::default().fg(theme.dim),
                 ));
             }
         }
 
+        if git.stash_count > 0 {
+            git_spans.push(Span::raw("  "));
+            git_spans.push(Span::styled(tr("stashes: "), label_style));
+            git_spans.push(Span::styled(
+                git.stash_count.to_string(),
+                Style::default().fg(theme.stash),
+            ));
+        }
+
         // Show staged/unstaged status
         if git.has_staged {
             git_spans.push(Span::raw("  "));
-            git_spans.push(Span::styled("staged: ", label_style));
-            git_spans.push(Span::styled("yes", Style::default().fg(Color::Green)));
+            git_spans.push(Span::styled(tr("staged: "), label_style));
+            git_spans.push(Span::styled(tr("yes"), Style::default().fg(theme.staged)));
         }
 
         if git.has_unstaged {
             git_spans.push(Span::raw("  "));
-            git_spans.push(Span::styled("unstaged: ", label_style));
-            git_spans.push(Span::styled("yes", Style::default().fg(Color::Yellow)));
+            git_spans.push(Span::styled(tr("unstaged: "), label_style));
+            git_spans.push(Span::styled(tr("yes"), Style::default().fg(theme.unstaged)));
+        }
+
+        if let Some(operation) = git.in_progress {
+            git_spans.push(Span::raw("  "));
+            git_spans.push(Span::styled(
+                in_progress_label(operation),
+                Style::default().fg(theme.conflict),
+            ));
+            if git.has_conflicts {
+                git_spans.push(Span::styled(tr(" (conflicts)"), label_style));
+            }
         }
 
         if git.is_worktree {
             git_spans.push(Span::raw("  "));
-            git_spans.push(Span::styled("worktree: ", label_style));
-            git_spans.push(Span::styled("yes", Style::default().fg(Color::Magenta)));
+            git_spans.push(Span::styled(tr("worktree: "), label_style));
+            git_spans.push(Span::styled(tr("yes"), Style::default().fg(theme.special)));
+        }
+
+        if let Some(ref time) = app.branch_time {
+            git_spans.push(Span::raw("  "));
+            git_spans.push(Span::styled(tr("worked: "), label_style));
+            git_spans.push(Span::styled(
+                trf!(
+                    "{} today, {} total",
+                    worktime::format_seconds(time.today),
+                    worktime::format_seconds(time.total)
+                ),
+                value_style,
+            ));
         }
 
         items.push(ListItem::new(Line::from(git_spans)));
 
+        // Last commit row (if the branch has a commit)
+        if let Some(ref commit) = git.last_commit {
+            let mut commit_spans = vec![
+                Span::raw("     "),
+                Span::styled(tr("last commit: "), label_style),
+                Span::styled(&commit.summary, value_style),
+            ];
+            if !commit.author.is_empty() {
+                commit_spans.push(Span::raw("  "));
+                commit_spans.push(Span::styled(tr("by "), label_style));
+                commit_spans.push(Span::styled(&commit.author, value_style));
+            }
+            commit_spans.push(Span::raw("  "));
+            commit_spans.push(Span::styled(
+                trf!("{} ago", commit.age(now_unix())),
+                label_style,
+            ));
+            items.push(ListItem::new(Line::from(commit_spans)));
+        }
+
         // PR status row (if available)
         if let Some(ref pr_info) = app.pr_info {
             let mut pr_spans = vec![
@@ -432,17 +1218,17 @@ fn render_expanded_instance_content<'a>(
                 Span::styled("PR #", label_style),
                 Span::styled(
                     format!("{}", pr_info.number),
-                    Style::default().fg(Color::Cyan),
+                    Style::default().fg(theme.dialog),
                 ),
                 Span::raw(": "),
             ];
 
             // State with color
             let (state_text, state_color) = match pr_info.state.as_str() {
-                "OPEN" => ("open", Color::Green),
-                "CLOSED" => ("closed", Color::Red),
-                "MERGED" => ("merged", Color::Magenta),
-                _ => (pr_info.state.as_str(), Color::Gray),
+                "OPEN" => (tr("open"), theme.success),
+                "CLOSED" => (tr("closed"), theme.error),
+                "MERGED" => (tr("merged"), theme.special),
+                _ => (pr_info.state.as_str(), theme.dim),
             };
             pr_spans.push(Span::styled(state_text, Style::default().fg(state_color)));
 
@@ -450,11 +1236,16 @@ fn render_expanded_instance_content<'a>(
             if pr_info.state == "OPEN" {
                 pr_spans.push(Span::raw("  "));
                 let (merge_text, merge_color) = match pr_info.mergeable.as_str() {
-                    "MERGEABLE" => ("ready to merge", Color::Green),
-                    "CONFLICTING" => ("has conflicts", Color::Red),
-                    _ => ("merge status unknown", Color::Yellow),
+                    "MERGEABLE" => (tr("ready to merge"), theme.success),
+                    "CONFLICTING" => (tr("has conflicts"), theme.conflict),
+                    _ => (tr("merge status unknown"), theme.warning),
                 };
                 pr_spans.push(Span::styled(merge_text, Style::default().fg(merge_color)));
+
+                let checks = app.pr_checks.get(&instance.working_directory);
+                if let Some(checks) = checks.filter(|c| !c.runs.is_empty()) {
+                    pr_spans.extend(checks_detail_spans(checks, &theme));
+                }
             }
 
             items.push(ListItem::new(Line::from(pr_spans)));
@@ -464,7 +1255,7 @@ fn render_expanded_instance_content<'a>(
     // Separator
     let sep_line = Line::from(Span::styled(
         "     ────────────────────────",
-        Style::default().fg(Color::DarkGray),
+        Style::default().fg(theme.dim),
     ));
     items.push(ListItem::new(sep_line));
 
@@ -473,9 +1264,9 @@ fn render_expanded_instance_content<'a>(
         let is_action_selected = action_idx == app.selected_action;
         let action_marker = if is_action_selected { "▸" } else { " " };
         let action_style = if is_action_selected {
-            Style::default().fg(Color::Yellow)
+            Style::default().fg(theme.highlight)
         } else {
-            Style::default().fg(Color::White)
+            Style::default().fg(theme.text)
         };
 
         let action_line = Line::from(vec![
@@ -489,11 +1280,26 @@ fn render_expanded_instance_content<'a>(
     }
 
     // White separator at end of submenu
-    let end_sep = Line::from(Span::styled("", Style::default().fg(Color::White)));
+    let end_sep = Line::from(Span::styled("", Style::default().fg(theme.text)));
     items.push(ListItem::new(end_sep));
 }
 
-fn render_preview(frame: &mut Frame, app: &App, area: Rect) {
+/// Lines given to the preview: its share, leaving 3 for the list
+fn preview_height(available: u16, percent: u16) -> u16 {
+    preview_share(available, percent).min(available.saturating_sub(3))
+}
+
+/// `percent` of `available` lines, between 8 and 20 lines at the default
+/// 50%, bounds that scale with the share
+pub fn preview_share(available: u16, percent: u16) -> u16 {
+    let min = 8 * percent / DEFAULT_PREVIEW_PERCENT;
+    let max = 20 * percent / DEFAULT_PREVIEW_PERCENT;
+    ((u32::from(available) * u32::from(percent) / 100) as u16).clamp(min, max)
+}
+
+/// Render the preview, returning where its content goes
+fn render_preview(frame: &mut Frame, app: &App, area: Rect) -> Rect {
+    let theme = theme(app);
     // Clear the entire preview area first to prevent stale content
     frame.render_widget(Clear, area);
 
@@ -506,7 +1312,7 @@ fn render_preview(frame: &mut Frame, app: &App, area: Rect) {
         width: area.width,
         height: 1,
     };
-    let top_sep = Paragraph::new(separator.clone()).style(Style::default().fg(Color::DarkGray));
+    let top_sep = Paragraph::new(separator.clone()).style(Style::default().fg(theme.dim));
     frame.render_widget(top_sep, top_sep_area);
 
     let bottom_sep_area = Rect {
@@ -515,7 +1321,7 @@ fn render_preview(frame: &mut Frame, app: &App, area: Rect) {
         width: area.width,
         height: 1,
     };
-    let bottom_sep = Paragraph::new(separator).style(Style::default().fg(Color::White));
+    let bottom_sep = Paragraph::new(separator).style(Style::default().fg(theme.text));
     frame.render_widget(bottom_sep, bottom_sep_area);
 
     // Content area (between separators)
@@ -526,18 +1332,22 @@ fn render_preview(frame: &mut Frame, app: &App, area: Rect) {
         height: area.height.saturating_sub(2),
     };
 
-    let content = match &app.preview_content {
-        Some(text) if !text.is_empty() => text,
+    // Scrolled back into history with the mouse wheel
+    let back = app
+        .selected_instance()
+        .map_or(0, |inst| app.scroll_state.preview_offset(&inst.pane_id));
+    let content = match app.selected_preview() {
+        Some(buffer) if !buffer.is_empty() => buffer.window(content_area.height as usize, back),
         _ => {
-            let msg = Paragraph::new("  No preview available")
-                .style(Style::default().fg(Color::DarkGray));
+            let msg =
+                Paragraph::new(tr("  No preview available")).style(Style::default().fg(theme.dim));
             frame.render_widget(msg, content_area);
-            return;
+            return content_area;
         }
     };
 
     // Parse ANSI escape sequences into styled ratatui Text
-    let styled_text = match content.into_text() {
+    let styled_text = match content.as_str().into_text() {
         Ok(text) => text,
         Err(_) => {
             // Fallback to plain text if parsing fails
@@ -545,7 +1355,7 @@ fn render_preview(frame: &mut Frame, app: &App, area: Rect) {
         }
     };
 
-    // Take only the last N lines that fit in the content area
+    // Parsing may split lines further; keep only the last N that fit
     let available_lines = content_area.height as usize;
     let total_lines = styled_text.lines.len();
     let start = total_lines.saturating_sub(available_lines);
@@ -553,71 +1363,287 @@ fn render_preview(frame: &mut Frame, app: &App, area: Rect) {
 
     let preview = Paragraph::new(visible_lines);
     frame.render_widget(preview, content_area);
+    content_area
 }
 
 fn render_status_bar(frame: &mut Frame, app: &App, area: Rect) {
     let (working, waiting, _idle) = app.status_counts();
     let total = app.instances.len();
 
-    let mut parts = vec![format!("{} instances", total)];
+    let mut parts = vec![trf!("{} instances", total)];
 
     if working > 0 {
-        parts.push(format!("{} working", working));
+        parts.push(trf!("{} working", working));
     }
     if waiting > 0 {
-        parts.push(format!("{} awaiting input", waiting));
+        parts.push(trf!("{} awaiting input", waiting));
+    }
+    let crashed = app
+        .instances
+        .iter()
+        .filter(|i| i.status == ClaudeCodeStatus::Crashed)
+        .count();
+    if crashed > 0 {
+        parts.push(trf!("{} crashed", crashed));
+    }
+    let marked = app.marked_count();
+    if marked > 0 {
+        parts.push(trf!("{} marked", marked));
+    }
+    if app.group_by_tag {
+        parts.push(tr("grouped by tag").to_string());
     }
 
     // Add loading indicator
     match app.loading_state {
         LoadingState::NotStarted | LoadingState::LoadingInstances => {
-            parts.push("loading...".to_string());
+            parts.push(tr("loading...").to_string());
+        }
+        LoadingState::DetectingStatus => {
+            parts.push(tr("detecting status...").to_string());
         }
         LoadingState::LoadingGitContexts => {
-            parts.push("loading git info...".to_string());
+            parts.push(tr("loading git info...").to_string());
         }
         LoadingState::Complete => {}
     }
 
+    let job = app.git_job.as_ref();
+    for queued in &app.queued_operations {
+        if job.is_some_and(|job| job.description == queued.operation) {
+            continue;
+        }
+        parts.push(trf!("{} queued behind {}", queued.operation, queued.behind));
+    }
+    if let Some(job) = job {
+        match app
+            .queued_operations
+            .iter()
+            .find(|q| q.operation == job.description)
+        {
+            Some(queued) => parts.push(trf!(
+                "{} queued behind {} (^C cancel)",
+                job.description,
+                queued.behind
+            )),
+            None => parts.push(trf!("{}... (^C cancel)", job.description)),
+        }
+    }
+
     let status = parts.join(" │ ");
 
     let filter_info = if !app.filter.is_empty() {
-        format!(" │ filter: \"{}\"", app.filter)
+        trf!(" │ filter: \"{}\"", app.filter)
     } else {
         String::new()
     };
 
     let text = format!("  {}{}", status, filter_info);
 
-    let bar = Paragraph::new(text).style(Style::default().fg(Color::DarkGray));
+    let bar = Paragraph::new(text).style(Style::default().fg(theme(app).dim));
 
     frame.render_widget(bar, area);
 }
 
-fn render_footer(frame: &mut Frame, app: &App, area: Rect) {
-    let hints = match app.mode {
-        Mode::Normal => {
-            "  ? help  jk navigate  l actions  ⏎ switch  n new  K kill  R reload  / filter  q quit"
-        }
-        Mode::ActionMenu => "  jk navigate  ⏎/l select  h/esc back  q quit",
-        Mode::Filter { .. } => "  ⏎ apply  esc cancel",
-        Mode::ConfirmAction => "  y/⏎ confirm  n/esc cancel",
-        Mode::NewSession { .. } => "  ⏎ create  tab switch  ↑↓ select  → accept  esc cancel",
-        Mode::Rename { .. } => "  ⏎ confirm  esc cancel",
-        Mode::Commit { .. } => "  ⏎ commit  esc cancel",
-        Mode::NewWorktree { .. } => "  ⏎ create  tab switch  ↑↓ select  → accept  esc cancel",
-        Mode::CreatePullRequest { .. } => "  ⏎ create PR  tab switch  esc cancel",
-        Mode::Help => "  q close",
+/// Render the footer, returning its key hints as buttons
+fn render_footer(frame: &mut Frame, app: &App, area: Rect) -> Vec<FooterButton> {
+    let key = |action| app.config.keys.key(action);
+    let ((english, translated), keys): ((&str, &str), Vec<char>) = match app.mode {
+        Mode::Normal if app.pick.is_some() => (
+            tr_pair("  {}{} navigate  ⏎ pick  {} filter  esc cancel"),
+            vec![key(Action::SelectNext), key(Action::SelectPrev), key(Action::Filter)],
+        ),
+        Mode::Normal if app.offers_agent_here() => (
+            tr_pair("  {} help  ⏎ start agent here  {} new  {} reload  {} quit"),
+            vec![
+                key(Action::Help),
+                key(Action::NewSession),
+                key(Action::Refresh),
+                key(Action::Quit),
+            ],
+        ),
+        Mode::Normal => (
+            tr_pair("  {} help  {}{} navigate  {} actions  ⏎ switch  {} new  {} kill  {} reload  {} filter  {} quit"),
+            vec![
+                key(Action::Help),
+                key(Action::SelectNext),
+                key(Action::SelectPrev),
+                key(Action::OpenMenu),
+                key(Action::NewSession),
+                key(Action::Kill),
+                key(Action::Refresh),
+                key(Action::Filter),
+                key(Action::Quit),
+            ],
+        ),
+        Mode::ActionMenu => (
+            tr_pair("  {}{} navigate  ⏎/{} select  h/esc back  {} quit"),
+            vec![
+                key(Action::SelectNext),
+                key(Action::SelectPrev),
+                key(Action::OpenMenu),
+                key(Action::Quit),
+            ],
+        ),
+        Mode::Filter { .. } => (tr_pair("  ⏎ apply  esc cancel"), vec![]),
+        Mode::ConfirmAction => (tr_pair("  y/⏎ confirm  n/esc cancel"), vec![]),
+        Mode::NewSession { .. } => (
+            tr_pair("  ⏎ create  tab switch  ↑↓ select  → accept  esc cancel"),
+            vec![],
+        ),
+        Mode::Rename { .. } => (tr_pair("  ⏎ confirm  esc cancel"), vec![]),
+        Mode::EditTags { .. } => (tr_pair("  ⏎ save  esc cancel"), vec![]),
+        Mode::Commit { .. } => (tr_pair("  ⏎ commit  esc cancel"), vec![]),
+        Mode::Rebase { .. } => (tr_pair("  ⏎ rebase  esc cancel"), vec![]),
+        Mode::BranchFromIssue { .. } => (
+            tr_pair("  ↑↓ select  tab prompt  ⏎ create  esc cancel"),
+            vec![],
+        ),
+        Mode::NewWorktree { .. } => (
+            tr_pair("  ⏎ create  tab switch  ↑↓ select  → accept  esc cancel"),
+            vec![],
+        ),
+        Mode::CreatePullRequest { .. } => (
+            tr_pair("  ⏎ create PR  tab switch  esc cancel"),
+            vec![],
+        ),
+        Mode::SlashCommandMenu { .. } => (tr_pair("  jk navigate  ⏎ send  esc cancel"), vec![]),
+        Mode::TemplatePicker { .. } => (tr_pair("  jk navigate  ⏎ send  esc cancel"), vec![]),
+        Mode::Workspaces { .. } => (tr_pair("  jk navigate  ⏎ start  esc cancel"), vec![]),
+        Mode::SchedulePrompt { .. } => (tr_pair("  ⏎ schedule  tab switch  esc cancel"), vec![]),
+        Mode::SendFile { .. } => (
+            tr_pair("  ⏎ send  tab template  ↑↓ select  → accept  esc cancel"),
+            vec![],
+        ),
+        Mode::Review { input: Some(_), .. } => (tr_pair("  ⏎ save comment  esc discard"), vec![]),
+        Mode::Review { .. } => (
+            tr_pair("  jk move  [] file  c comment  d delete  s send to agent  esc close"),
+            vec![],
+        ),
+        Mode::PendingJobs { .. } => (tr_pair("  jk navigate  d cancel job  esc close"), vec![]),
+        Mode::Conflicts { .. } => (
+            tr_pair("  jk navigate  e edit  o/t ours/theirs  s resolved  c continue  esc close"),
+            vec![],
+        ),
+        Mode::Archived { .. } => (tr_pair("  jk navigate  ⏎ restore  d delete  esc close"), vec![]),
+        Mode::Logs { .. } => (
+            tr_pair("  jk scroll  g/G oldest/newest  r reload  esc close"),
+            vec![],
+        ),
+        Mode::Snapshots { content: None, .. } => (
+            tr_pair("  jk navigate  ⏎ view  d delete  esc close"),
+            vec![],
+        ),
+        Mode::Snapshots { .. } => (tr_pair("  jk scroll  g/G top/bottom  esc back"), vec![]),
+        Mode::ActionOutput { .. } => (
+            tr_pair("  jk scroll  g/G top/bottom  r run again  esc close"),
+            vec![],
+        ),
+        Mode::WorkTime { .. } => (tr_pair("  jk navigate  esc close"), vec![]),
+        Mode::Stats { .. } => (tr_pair("  r reload  esc close"), vec![]),
+        Mode::CloneLayout { .. } => (tr_pair("  ⏎ create  tab switch  esc cancel"), vec![]),
+        Mode::MoveWindow { .. } => (tr_pair("  ↑↓ select  ⏎ confirm  esc cancel"), vec![]),
+        Mode::ArrangePanes { .. } => (tr_pair("  jk navigate  ⏎ apply  esc close"), vec![]),
+        Mode::Search { results: None, .. } => (tr_pair("  ⏎ search  esc close"), vec![]),
+        Mode::Search { .. } => (tr_pair("  ↑↓ select  ⏎ switch  esc close"), vec![]),
+        Mode::Palette { .. } => (tr_pair("  ↑↓ select  ⏎ run  esc close"), vec![]),
+        Mode::AllPanes { .. } => (
+            tr_pair("  jk navigate  ⏎ switch  c start claude  v split  esc close"),
+            vec![],
+        ),
+        Mode::Sessions {
+            rename: Some(_), ..
+        } => (tr_pair("  ⏎ rename  esc cancel"), vec![]),
+        Mode::Sessions {
+            confirm_kill: true, ..
+        } => (tr_pair("  y/⏎ confirm  n/esc cancel"), vec![]),
+        Mode::Sessions { .. } => (
+            tr_pair("  jk navigate  ⏎ switch  {} kill  {} rename  esc close"),
+            vec![key(Action::Kill), key(Action::Rename)],
+        ),
+        Mode::Keybindings {
+            capturing: true, ..
+        } => (tr_pair("  press a key  esc cancel"), vec![]),
+        Mode::Keybindings { .. } => (
+            tr_pair("  jk navigate  ⏎ rebind  d default  esc close"),
+            vec![],
+        ),
+        Mode::Credentials { .. } => (
+            tr_pair("  ⏎ retry  tab switch  space toggle save  esc cancel"),
+            vec![],
+        ),
+        Mode::Passphrase { .. } => (tr_pair("  ⏎ retry  esc cancel"), vec![]),
+        Mode::Help { .. } => (tr_pair("  jk scroll  {} close"), vec![key(Action::Quit)]),
+        Mode::ConfigError { .. } => (tr_pair("  ⏎ close"), vec![]),
+        Mode::Sync { .. } if app.is_syncing() => (tr_pair("  jk navigate  esc stop"), vec![]),
+        Mode::Sync { .. } => (tr_pair("  jk navigate  esc close"), vec![]),
     };
 
-    let footer = Paragraph::new(hints).style(Style::default().fg(Color::DarkGray));
+    let keys: Vec<&dyn Display> = keys.iter().map(|k| k as &dyn Display).collect();
+    let english = dialogs::nav_hint(&fill(english, &keys), &app.config.keys);
+    let hints = dialogs::nav_hint(&fill(translated, &keys), &app.config.keys);
+    // ⏎ is missing from many terminal fonts
+    let hints = if app.config.ascii_glyphs() {
+        hints.replace('⏎', "Enter")
+    } else {
+        hints
+    };
+    let buttons = footer_buttons(&english, &hints, area);
+    let footer = Paragraph::new(hints).style(Style::default().fg(theme(app).dim));
 
     frame.render_widget(footer, area);
+    buttons
+}
+
+/// The `key label` hints of a footer line drawn in `area`, separated by two
+/// spaces, with the key each one names
+///
+/// `hints` is the line as shown and `english` the same line untranslated,
+/// whose key names are the ones recognized; translations keep the hints
+/// and their order.
+fn footer_buttons(english: &str, hints: &str, area: Rect) -> Vec<FooterButton> {
+    let mut buttons = Vec::new();
+    let mut column = area.x;
+    for (i, (source, hint)) in english.split("  ").zip(hints.split("  ")).enumerate() {
+        if i > 0 {
+            column = column.saturating_add(2);
+        }
+        let width = hint.width() as u16;
+        let key = source
+            .split_once(' ')
+            .and_then(|(keys, _)| keys.split('/').find_map(hint_key));
+        if let Some(key) = key {
+            buttons.push(FooterButton {
+                area: Rect::new(column, area.y, width, 1).intersection(area),
+                key,
+            });
+        }
+        column = column.saturating_add(width);
+    }
+    buttons
+}
+
+/// The key a footer hint names in English, such as `⏎`, `esc` or `q`
+fn hint_key(name: &str) -> Option<KeyCode> {
+    match name {
+        "⏎" => Some(KeyCode::Enter),
+        "esc" => Some(KeyCode::Esc),
+        "tab" => Some(KeyCode::Tab),
+        "→" => Some(KeyCode::Right),
+        "←" => Some(KeyCode::Left),
+        _ => {
+            let mut chars = name.chars();
+            match (chars.next(), chars.next()) {
+                (Some(c), None) => Some(KeyCode::Char(c)),
+                _ => None,
+            }
+        }
+    }
 }
 
-fn render_filter_bar(frame: &mut Frame, input: &str, area: Rect) {
+fn render_filter_bar(frame: &mut Frame, theme: &Theme, input: &str, area: Rect) {
     frame.render_widget(Clear, area);
     let text = format!("  / {}", input);
-    let bar = Paragraph::new(text).style(Style::default().fg(Color::Yellow));
+    let bar = Paragraph::new(text).style(Style::default().fg(theme.highlight));
     frame.render_widget(bar, area);
 }
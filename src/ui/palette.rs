@@ -0,0 +1,102 @@
+//! Command palette overlay
+//!
+//! Lists the commands matching what has been typed, best first, with the
+//! matched letters highlighted and the key of keyed actions alongside.
+
+use ratatui::{
+    style::{Modifier, Style},
+    text::{Line, Span, Text},
+    widgets::{Block, Borders, Clear, Paragraph},
+    Frame,
+};
+
+use crate::app::{palette_matches, Command};
+use crate::config::Keymap;
+use crate::i18n::tr;
+
+use super::help::centered_rect;
+use super::Theme;
+
+/// Commands shown at once
+const VISIBLE: usize = 12;
+
+pub fn render_palette(
+    frame: &mut Frame,
+    theme: &Theme,
+    input: &str,
+    commands: &[Command],
+    selected: usize,
+    keys: &Keymap,
+) {
+    let matches = palette_matches(input, commands);
+    let visible = matches.len().clamp(1, VISIBLE);
+    let area = centered_rect(60, visible as u16 + 6, frame.area());
+
+    let block = Block::default()
+        .title(tr(" Command Palette "))
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(theme.dialog));
+
+    let mut lines = vec![
+        Line::from(vec![
+            Span::styled(
+                "> ",
+                Style::default()
+                    .fg(theme.highlight)
+                    .add_modifier(Modifier::BOLD),
+            ),
+            Span::styled(input, Style::default().fg(theme.highlight)),
+            Span::raw("_"),
+        ]),
+        Line::raw(""),
+    ];
+
+    if matches.is_empty() {
+        lines.push(Line::styled(
+            tr("No matching command"),
+            Style::default().fg(theme.dim),
+        ));
+    }
+    // Keep the selection visible when there are more matches than rows
+    let start = selected.saturating_sub(visible - 1);
+    let label_width = area.width.saturating_sub(5) as usize;
+    for (i, (command, positions)) in matches.iter().enumerate().skip(start).take(visible) {
+        let is_selected = i == selected;
+        let style = if is_selected {
+            Style::default()
+                .fg(theme.dialog)
+                .add_modifier(Modifier::BOLD)
+        } else {
+            Style::default()
+        };
+        let mut spans = vec![Span::styled(if is_selected { "> " } else { "  " }, style)];
+        let label = command.label();
+        spans.extend(label.chars().enumerate().map(|(j, c)| {
+            let style = if positions.contains(&j) {
+                style.fg(theme.highlight)
+            } else {
+                style
+            };
+            Span::styled(c.to_string(), style)
+        }));
+        if let Command::Key(action) = command {
+            let padding = label_width.saturating_sub(label.chars().count());
+            spans.push(Span::styled(
+                format!("{:>width$}", keys.key(*action), width = padding),
+                Style::default().fg(theme.dim),
+            ));
+        }
+        lines.push(Line::from(spans));
+    }
+
+    lines.push(Line::raw(""));
+    lines.push(Line::styled(
+        tr("Type to filter  [Enter] Run  [↑↓] Select  [Esc] Close"),
+        Style::default().fg(theme.dim),
+    ));
+
+    let paragraph = Paragraph::new(Text::from(lines)).block(block);
+
+    frame.render_widget(Clear, area);
+    frame.render_widget(paragraph, area);
+}
@@ -0,0 +1,131 @@
+//! Diff viewer for reviewing an agent's changes
+
+use ratatui::{
+    layout::Rect,
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph},
+    Frame,
+};
+
+use crate::git::{DiffLine, DiffLineKind};
+use crate::i18n::{tr, trf};
+use crate::review::ReviewComment;
+
+use super::Theme;
+
+pub fn render_review(
+    frame: &mut Frame,
+    theme: &Theme,
+    target: &str,
+    lines: &[DiffLine],
+    cursor: usize,
+    comments: &[ReviewComment],
+    input: Option<&str>,
+) {
+    let full = frame.area();
+    // Leave the footer visible
+    let area = Rect {
+        height: full.height.saturating_sub(1),
+        ..full
+    };
+
+    let block = Block::default()
+        .title(if comments.len() == 1 {
+            trf!(" Review {} — 1 comment ", target)
+        } else {
+            trf!(" Review {} — {} comments ", target, comments.len())
+        })
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(theme.dialog));
+
+    let inner_height = area.height.saturating_sub(2) as usize;
+    let input_rows = if input.is_some() { 2 } else { 0 };
+    let diff_rows = inner_height.saturating_sub(input_rows).max(1);
+
+    // Keep the cursor in the upper third of the view
+    let start = cursor.saturating_sub(diff_rows / 3);
+
+    let mut rows: Vec<Line> = Vec::new();
+    for (i, line) in lines.iter().enumerate().skip(start) {
+        if rows.len() >= diff_rows {
+            break;
+        }
+
+        let is_cursor = i == cursor;
+        let comment = comments.iter().find(|c| c.line == i);
+
+        let mut style = match line.kind {
+            DiffLineKind::File => Style::default()
+                .fg(theme.dialog)
+                .add_modifier(Modifier::BOLD),
+            DiffLineKind::Hunk => Style::default().fg(theme.special),
+            DiffLineKind::Added => Style::default().fg(theme.insertions),
+            DiffLineKind::Removed => Style::default().fg(theme.deletions),
+            DiffLineKind::Context => Style::default(),
+        };
+        if is_cursor {
+            style = style.add_modifier(Modifier::REVERSED);
+        }
+
+        let prefix = match line.kind {
+            DiffLineKind::Added => "+",
+            DiffLineKind::Removed => "-",
+            DiffLineKind::Context => " ",
+            DiffLineKind::File | DiffLineKind::Hunk => "",
+        };
+        let lineno = line
+            .new_lineno
+            .or(line.old_lineno)
+            .map(|n| format!("{:>5} ", n))
+            .unwrap_or_else(|| "      ".to_string());
+
+        rows.push(Line::from(vec![
+            Span::styled(
+                if comment.is_some() { "● " } else { "  " },
+                Style::default().fg(theme.highlight),
+            ),
+            Span::styled(lineno, Style::default().fg(theme.dim)),
+            Span::styled(format!("{}{}", prefix, line.text), style),
+        ]));
+
+        if let Some(comment) = comment {
+            for text in comment.text.lines() {
+                if rows.len() >= diff_rows {
+                    break;
+                }
+                rows.push(Line::styled(
+                    format!("        ↳ {}", text),
+                    Style::default()
+                        .fg(theme.highlight)
+                        .add_modifier(Modifier::ITALIC),
+                ));
+            }
+        }
+    }
+
+    if let Some(text) = input {
+        while rows.len() < diff_rows {
+            rows.push(Line::raw(""));
+        }
+        rows.push(Line::styled(
+            "────────────────────────────────────",
+            Style::default().fg(theme.dim),
+        ));
+        rows.push(Line::from(vec![
+            Span::styled(
+                tr("Comment: "),
+                Style::default()
+                    .fg(theme.highlight)
+                    .add_modifier(Modifier::BOLD),
+            ),
+            Span::raw(text),
+            Span::raw("_"),
+        ]));
+    }
+
+    let paragraph = Paragraph::new(rows).block(block);
+
+    frame.render_widget(Clear, area);
+    frame.render_widget(paragraph, area);
+}
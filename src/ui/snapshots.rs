@@ -0,0 +1,345 @@
+//! Snapshot tests of rendered screens
+//!
+//! Screens are drawn on a ratatui `TestBackend` from an app whose tmux
+//! commands go to a [`MockTmux`], and compared as text with the files in
+//! `src/ui/snapshots/`. A missing snapshot is written on first run; set
+//! `UPDATE_SNAPSHOTS=1` to rewrite the ones that changed on purpose.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers, MouseButton, MouseEvent, MouseEventKind};
+use ratatui::{backend::TestBackend, layout::Rect, style::Color, Terminal};
+use unicode_width::UnicodeWidthStr;
+
+use crate::app::{ActionRun, App, Mode, PickOutput};
+use crate::config::{Config, CustomAction, Workspace};
+use crate::git::GitContext;
+use crate::input::{handle_key, handle_mouse};
+use crate::session::{test_instance, ClaudeCodeStatus, ClaudeInstance};
+use crate::tmux::mock::MockTmux;
+
+use super::Theme;
+
+const WIDTH: u16 = 100;
+const HEIGHT: u16 = 24;
+
+fn instance(session: &str, dir: &str, status: ClaudeCodeStatus) -> ClaudeInstance {
+    ClaudeInstance {
+        session_name: session.to_string(),
+        window_index: 0,
+        working_directory: PathBuf::from(dir),
+        ..test_instance(&format!("%{}", session.len()), status)
+    }
+}
+
+fn git(branch: &str) -> GitContext {
+    GitContext {
+        branch: branch.to_string(),
+        has_staged: true,
+        has_unstaged: false,
+        is_worktree: false,
+        main_repo_path: None,
+        has_upstream: true,
+        has_remote: true,
+        ahead: 2,
+        behind: 0,
+        default_branch: Some("main".to_string()),
+        ahead_of_default: 3,
+        stash_count: 1,
+        last_fetched: None,
+        auto_fetch: None,
+        last_commit: None,
+        in_progress: None,
+        has_conflicts: false,
+    }
+}
+
+/// An app with three agents, as seen from pane `main:0.0`
+fn app() -> App {
+    MockTmux::new()
+        .reply("display-message -p", "main:0.0\n")
+        .reply(
+            "capture-pane",
+            "> Refactor the parser\n\n● Reading src/parser.rs\n",
+        )
+        .install();
+
+    let mut api = instance("api", "/usr", ClaudeCodeStatus::Working);
+    api.session_attached = true;
+    api.git_context = Some(git("feature/parser"));
+    let instances = vec![
+        api,
+        instance("docs", "/tmp", ClaudeCodeStatus::WaitingInput),
+        instance("web", "/nonexistent/web", ClaudeCodeStatus::Crashed),
+    ];
+    // Rendered the same whatever the terminal's locale and `NO_COLOR`
+    let config = Config {
+        ascii: false,
+        no_color: false,
+        ..Config::default()
+    };
+    App::for_test(instances, config)
+}
+
+fn press(app: &mut App, keys: &str) {
+    for c in keys.chars() {
+        handle_key(app, KeyEvent::new(KeyCode::Char(c), KeyModifiers::NONE));
+    }
+}
+
+/// The screen as text, without styles
+fn render(app: &mut App) -> String {
+    app.wait_for_workers();
+    let mut terminal = Terminal::new(TestBackend::new(WIDTH, HEIGHT)).unwrap();
+    terminal.draw(|frame| super::render(frame, app)).unwrap();
+
+    let buffer = terminal.backend().buffer();
+    let mut screen = String::new();
+    for y in 0..buffer.area.height {
+        let mut line = String::new();
+        let mut x = 0;
+        while x < buffer.area.width {
+            let symbol = buffer[(x, y)].symbol();
+            line.push_str(symbol);
+            // Wide glyphs cover the cells after them
+            x += symbol.width().max(1) as u16;
+        }
+        screen.push_str(line.trim_end());
+        screen.push('\n');
+    }
+    screen
+}
+
+/// Colors of every cell of the screen that aren't the terminal's own
+fn colors(app: &mut App) -> Vec<Color> {
+    app.wait_for_workers();
+    let mut terminal = Terminal::new(TestBackend::new(WIDTH, HEIGHT)).unwrap();
+    terminal.draw(|frame| super::render(frame, app)).unwrap();
+    let buffer = terminal.backend().buffer();
+    buffer
+        .content
+        .iter()
+        .flat_map(|cell| [cell.fg, cell.bg])
+        .filter(|&color| color != Color::Reset)
+        .collect()
+}
+
+fn assert_snapshot(name: &str, screen: &str) {
+    let path = Path::new(env!("CARGO_MANIFEST_DIR"))
+        .join("src/ui/snapshots")
+        .join(format!("{}.txt", name));
+    let update = std::env::var_os("UPDATE_SNAPSHOTS").is_some();
+    match fs::read_to_string(&path) {
+        Ok(expected) if !update => assert_eq!(
+            screen,
+            expected,
+            "{} differs from {}; run with UPDATE_SNAPSHOTS=1 if the change is intended",
+            name,
+            path.display()
+        ),
+        _ => {
+            fs::create_dir_all(path.parent().unwrap()).unwrap();
+            fs::write(&path, screen).unwrap();
+        }
+    }
+}
+
+#[test]
+fn test_session_list() {
+    let mut app = app();
+    assert_snapshot("session_list", &render(&mut app));
+
+    press(&mut app, "j");
+    assert_snapshot("session_list_second", &render(&mut app));
+}
+
+fn click(app: &mut App, kind: MouseEventKind, column: u16, row: u16) {
+    let modifiers = KeyModifiers::NONE;
+    handle_mouse(
+        app,
+        MouseEvent {
+            kind,
+            column,
+            row,
+            modifiers,
+        },
+    );
+}
+
+#[test]
+fn test_mouse() {
+    let mut app = app();
+    render(&mut app);
+
+    // The `docs` row, then the `?` footer hint
+    click(&mut app, MouseEventKind::Down(MouseButton::Left), 5, 3);
+    assert_eq!(app.selected_instance().unwrap().session_name, "docs");
+    click(&mut app, MouseEventKind::ScrollDown, 5, 2);
+    assert_eq!(app.selected_instance().unwrap().session_name, "web");
+    click(&mut app, MouseEventKind::Down(MouseButton::Left), 2, 23);
+    assert!(matches!(app.mode, Mode::Help { .. }));
+}
+
+#[test]
+fn test_footer_buttons() {
+    let area = Rect::new(0, 23, 80, 1);
+    let buttons = super::footer_buttons(
+        "  ⏎ apply  n/esc cancel  jk navigate",
+        "  ⏎ appliquer  n/Échap annuler  jk naviguer",
+        area,
+    );
+    let keys: Vec<(u16, KeyCode)> = buttons.iter().map(|b| (b.area.x, b.key)).collect();
+    assert_eq!(keys, [(2, KeyCode::Enter), (15, KeyCode::Char('n'))]);
+}
+
+#[test]
+fn test_pick() {
+    let mut app = app();
+    app.pick = Some(PickOutput::Target);
+    assert_snapshot("pick", &render(&mut app));
+
+    // Actions other than picking are off
+    press(&mut app, "jn");
+    assert!(matches!(app.mode, Mode::Normal));
+    handle_key(&mut app, KeyEvent::new(KeyCode::Enter, KeyModifiers::NONE));
+    assert!(app.should_quit);
+    assert_eq!(app.attach_on_exit.as_deref(), Some("docs:0.0"));
+}
+
+#[test]
+fn test_pick_directory() {
+    let mut app = app();
+    app.pick = Some(PickOutput::Directory);
+    press(&mut app, "j");
+    handle_key(&mut app, KeyEvent::new(KeyCode::Enter, KeyModifiers::NONE));
+    assert_eq!(app.attach_on_exit.as_deref(), Some("/tmp"));
+}
+
+#[test]
+fn test_scope() {
+    let mut app = app();
+    app.scope = Some(PathBuf::from("/usr"));
+    assert_eq!(app.filtered_instances().len(), 1);
+    assert!(!app.offers_agent_here());
+    assert_snapshot("scope", &render(&mut app));
+
+    app.scope = Some(PathBuf::from("/srv"));
+    assert!(app.offers_agent_here());
+    assert_snapshot("scope_empty", &render(&mut app));
+}
+
+#[test]
+fn test_filter() {
+    let mut app = app();
+    press(&mut app, "/do");
+    assert_snapshot("filter_input", &render(&mut app));
+
+    handle_key(&mut app, KeyEvent::new(KeyCode::Enter, KeyModifiers::NONE));
+    assert_eq!(app.filtered_instances().len(), 1);
+    assert_snapshot("filter_applied", &render(&mut app));
+}
+
+#[test]
+fn test_dialogs() {
+    let mut app = app();
+    press(&mut app, "K");
+    assert_snapshot("confirm_kill", &render(&mut app));
+    app.unpushed_commits = 2;
+    assert_snapshot("confirm_kill_unpushed", &render(&mut app));
+
+    handle_key(&mut app, KeyEvent::new(KeyCode::Esc, KeyModifiers::NONE));
+    press(&mut app, "r");
+    assert_snapshot("rename", &render(&mut app));
+
+    handle_key(&mut app, KeyEvent::new(KeyCode::Esc, KeyModifiers::NONE));
+    press(&mut app, "?");
+    assert_snapshot("help", &render(&mut app));
+
+    // Scrolling stops at the last line
+    handle_key(&mut app, KeyEvent::new(KeyCode::End, KeyModifiers::NONE));
+    assert_snapshot("help_end", &render(&mut app));
+    press(&mut app, "k");
+    let scrolled_back = render(&mut app);
+    press(&mut app, "jj");
+    assert_snapshot("help_end", &render(&mut app));
+    assert_ne!(scrolled_back, render(&mut app));
+
+    handle_key(&mut app, KeyEvent::new(KeyCode::Esc, KeyModifiers::NONE));
+    handle_key(
+        &mut app,
+        KeyEvent::new(KeyCode::Char('p'), KeyModifiers::CONTROL),
+    );
+    press(&mut app, "push");
+    assert_snapshot("palette", &render(&mut app));
+}
+
+#[test]
+fn test_workspaces() {
+    let mut app = app();
+    app.config.workspaces = vec![Workspace {
+        name: "product".to_string(),
+        paths: vec!["/usr".to_string(), "/nonexistent/app".to_string()],
+    }];
+    press(&mut app, "e");
+    assert_snapshot("workspaces", &render(&mut app));
+}
+
+#[test]
+fn test_custom_action() {
+    let mut app = app();
+    app.config.actions = vec![CustomAction {
+        name: "Tests".to_string(),
+        key: Some('X'),
+        command: "echo {target} {branch}; echo failed >&2; exit 3".to_string(),
+    }];
+    press(&mut app, "X");
+    for _ in 0..500 {
+        app.poll_action_job();
+        if matches!(
+            app.mode,
+            Mode::ActionOutput {
+                state: ActionRun::Exited(_),
+                ..
+            }
+        ) {
+            break;
+        }
+        std::thread::sleep(std::time::Duration::from_millis(10));
+    }
+    assert_snapshot("custom_action", &render(&mut app));
+}
+
+#[test]
+fn test_accessible_list() {
+    let mut app = app();
+    app.config.accessibility = true;
+    assert_snapshot("session_list_accessible", &render(&mut app));
+
+    // The theme's accent is redrawn in the palette's
+    let accent = app.config.palette.accent;
+    let colors = colors(&mut app);
+    assert!(colors.contains(&accent));
+    assert!(!colors.contains(&Theme::default().accent));
+}
+
+#[test]
+fn test_theme_colors() {
+    // Every color of the solarized theme is RGB, so a named color on
+    // screen was drawn without going through the theme
+    let mut app = app();
+    app.config.theme = Theme::named("solarized").unwrap();
+    let mut screens = vec![colors(&mut app)];
+    for keys in ["K", "r", "?", "s"] {
+        press(&mut app, keys);
+        screens.push(colors(&mut app));
+        handle_key(&mut app, KeyEvent::new(KeyCode::Esc, KeyModifiers::NONE));
+    }
+    for color in screens.concat() {
+        assert!(
+            matches!(color, Color::Rgb(..)),
+            "{:?} is not from the theme",
+            color
+        );
+    }
+}
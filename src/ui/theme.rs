@@ -0,0 +1,200 @@
+//! Colors of the interface outside accessibility mode
+//!
+//! A built-in theme is picked with `theme`, and any of its colors can be
+//! overridden in the `[colors]` table, written as in `[palette]`:
+//!
+//! ```toml
+//! theme = "light"
+//!
+//! [colors]
+//! waiting = "#b8860b"
+//! selection = "blue"
+//! ```
+//!
+//! Accessibility mode keeps its own `[palette]`.
+
+use ratatui::style::Color;
+
+use crate::session::ClaudeCodeStatus;
+
+/// Colors by role
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Theme {
+    /// Agents that are working
+    pub working: Color,
+    /// Agents waiting for input
+    pub waiting: Color,
+    /// Idle agents
+    pub idle: Color,
+    /// Crashed agents and missing directories
+    pub crashed: Color,
+    /// Agents whose status is unknown
+    pub unknown: Color,
+    /// Background of the selected row
+    pub selection: Color,
+    /// Text of the selected row
+    pub selection_text: Color,
+    /// Header and branch names
+    pub accent: Color,
+    /// Staged changes (`+`)
+    pub staged: Color,
+    /// Unstaged changes (`*`)
+    pub unstaged: Color,
+    /// Lines added in the diffstat
+    pub insertions: Color,
+    /// Lines removed in the diffstat
+    pub deletions: Color,
+    /// Commits not on the default branch (`main+3`)
+    pub ahead: Color,
+    /// Stashed changes (`$2`)
+    pub stash: Color,
+    /// Stopped merges and rebases
+    pub conflict: Color,
+    /// Warnings in dialogs, such as commits a kill would leave on no remote
+    pub warning: Color,
+    /// Hints, labels and other secondary text
+    pub dim: Color,
+    /// Plain values, such as the details of the expanded instance
+    pub text: Color,
+    /// Dialog borders and the selected row of dialog lists
+    pub dialog: Color,
+    /// Typed input and the names a dialog acts on
+    pub highlight: Color,
+    /// Errors, failed checks and destructive actions
+    pub error: Color,
+    /// Completed actions and passing checks
+    pub success: Color,
+    /// Tags, branches in dialog lists and merged pull requests
+    pub special: Color,
+}
+
+impl Default for Theme {
+    fn default() -> Self {
+        Self {
+            working: Color::Rgb(152, 195, 121),
+            waiting: Color::Rgb(229, 192, 123),
+            idle: Color::Rgb(153, 153, 153),
+            crashed: Color::Rgb(224, 108, 117),
+            unknown: Color::Rgb(102, 102, 102),
+            selection: Color::Rgb(215, 119, 87),
+            selection_text: Color::White,
+            accent: Color::Rgb(215, 119, 87),
+            staged: Color::Green,
+            unstaged: Color::Yellow,
+            insertions: Color::Green,
+            deletions: Color::Red,
+            ahead: Color::Cyan,
+            stash: Color::Magenta,
+            conflict: Color::Red,
+            warning: Color::Yellow,
+            dim: Color::DarkGray,
+            text: Color::White,
+            dialog: Color::Cyan,
+            highlight: Color::Yellow,
+            error: Color::Red,
+            success: Color::Green,
+            special: Color::Magenta,
+        }
+    }
+}
+
+impl Theme {
+    /// Names of the built-in themes
+    pub const NAMES: [&'static str; 4] = ["default", "light", "ansi", "solarized"];
+
+    /// Built-in theme called `name`
+    pub fn named(name: &str) -> Option<Self> {
+        match name {
+            "default" => Some(Self::default()),
+            // Darker colors that stay readable on a white background
+            "light" => Some(Self {
+                working: Color::Rgb(56, 118, 29),
+                waiting: Color::Rgb(156, 101, 0),
+                idle: Color::Rgb(85, 85, 85),
+                crashed: Color::Rgb(192, 28, 40),
+                unknown: Color::Rgb(120, 120, 120),
+                selection: Color::Rgb(168, 72, 40),
+                selection_text: Color::White,
+                accent: Color::Rgb(168, 72, 40),
+                staged: Color::Rgb(56, 118, 29),
+                unstaged: Color::Rgb(156, 101, 0),
+                insertions: Color::Rgb(56, 118, 29),
+                deletions: Color::Rgb(192, 28, 40),
+                ahead: Color::Rgb(0, 110, 140),
+                stash: Color::Rgb(136, 57, 159),
+                conflict: Color::Rgb(192, 28, 40),
+                warning: Color::Rgb(156, 101, 0),
+                dim: Color::Rgb(120, 120, 120),
+                text: Color::Reset,
+                dialog: Color::Rgb(0, 110, 140),
+                highlight: Color::Rgb(156, 101, 0),
+                error: Color::Rgb(192, 28, 40),
+                success: Color::Rgb(56, 118, 29),
+                special: Color::Rgb(136, 57, 159),
+            }),
+            // The terminal's own 16 colors, following its color scheme
+            "ansi" => Some(Self {
+                working: Color::Green,
+                waiting: Color::Yellow,
+                idle: Color::Reset,
+                crashed: Color::Red,
+                unknown: Color::Gray,
+                selection: Color::Blue,
+                selection_text: Color::White,
+                accent: Color::Magenta,
+                staged: Color::Green,
+                unstaged: Color::Yellow,
+                insertions: Color::Green,
+                deletions: Color::Red,
+                ahead: Color::Cyan,
+                stash: Color::Magenta,
+                conflict: Color::Red,
+                warning: Color::Yellow,
+                dim: Color::DarkGray,
+                text: Color::Reset,
+                dialog: Color::Cyan,
+                highlight: Color::Yellow,
+                error: Color::Red,
+                success: Color::Green,
+                special: Color::Magenta,
+            }),
+            "solarized" => Some(Self {
+                working: Color::Rgb(133, 153, 0),
+                waiting: Color::Rgb(181, 137, 0),
+                idle: Color::Rgb(131, 148, 150),
+                crashed: Color::Rgb(220, 50, 47),
+                unknown: Color::Rgb(88, 110, 117),
+                selection: Color::Rgb(38, 139, 210),
+                selection_text: Color::Rgb(253, 246, 227),
+                accent: Color::Rgb(203, 75, 22),
+                staged: Color::Rgb(133, 153, 0),
+                unstaged: Color::Rgb(181, 137, 0),
+                insertions: Color::Rgb(133, 153, 0),
+                deletions: Color::Rgb(220, 50, 47),
+                ahead: Color::Rgb(42, 161, 152),
+                stash: Color::Rgb(211, 54, 130),
+                conflict: Color::Rgb(220, 50, 47),
+                warning: Color::Rgb(181, 137, 0),
+                dim: Color::Rgb(88, 110, 117),
+                text: Color::Rgb(147, 161, 161),
+                dialog: Color::Rgb(38, 139, 210),
+                highlight: Color::Rgb(181, 137, 0),
+                error: Color::Rgb(220, 50, 47),
+                success: Color::Rgb(133, 153, 0),
+                special: Color::Rgb(108, 113, 196),
+            }),
+            _ => None,
+        }
+    }
+
+    /// Color of an agent's status
+    pub fn status(&self, status: ClaudeCodeStatus) -> Color {
+        match status {
+            ClaudeCodeStatus::WaitingInput => self.waiting,
+            ClaudeCodeStatus::Working => self.working,
+            ClaudeCodeStatus::Crashed => self.crashed,
+            ClaudeCodeStatus::Unknown => self.unknown,
+            ClaudeCodeStatus::Idle => self.idle,
+        }
+    }
+}
@@ -0,0 +1,141 @@
+//! UI state kept across runs
+//!
+//! The selected instance, the filter, grouping by tag, the sort order and
+//! the size of the preview are saved when the TUI exits and restored at the
+//! next start, so a popup opened many times a day reopens where it was left.
+//! The instance last switched away from is kept too, for switching back
+//! from the TUI or with `claude-tmux switch --last`.
+
+use std::fs;
+use std::path::PathBuf;
+
+use anyhow::Result;
+
+use crate::session::SortOrder;
+use crate::store::{self, data_dir, escape_field, unescape_field};
+
+/// Share of the space below the header given to the preview by default
+pub const DEFAULT_PREVIEW_PERCENT: u16 = 50;
+/// Smallest and largest preview share, in percent
+pub const PREVIEW_PERCENT_RANGE: (u16, u16) = (20, 80);
+/// Change of the preview share per key press, in percent
+pub const PREVIEW_PERCENT_STEP: u16 = 10;
+
+/// What the TUI restores at start
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UiState {
+    /// Selected instance (`session:window.pane`)
+    pub selected: Option<String>,
+    /// Filter text
+    pub filter: String,
+    /// Whether the list is grouped by tag
+    pub group_by_tag: bool,
+    /// Sort order picked in the TUI, when it differs from `sort_order`
+    pub sort_order: Option<SortOrder>,
+    /// Share of the space below the header given to the preview, in percent
+    pub preview_percent: u16,
+    /// Instance switched away from last (`session:window.pane`)
+    pub previous: Option<String>,
+}
+
+impl Default for UiState {
+    fn default() -> Self {
+        Self {
+            selected: None,
+            filter: String::new(),
+            group_by_tag: false,
+            sort_order: None,
+            preview_percent: DEFAULT_PREVIEW_PERCENT,
+            previous: None,
+        }
+    }
+}
+
+fn state_path() -> PathBuf {
+    data_dir().join("ui-state.tsv")
+}
+
+/// Load the state saved by the previous run (defaults if none)
+pub fn load() -> UiState {
+    fs::read_to_string(state_path())
+        .map(|content| parse(&content))
+        .unwrap_or_default()
+}
+
+/// Save the state for the next start
+pub fn save(state: &UiState) -> Result<()> {
+    store::write_atomic(&state_path(), &format(state), "UI state")
+}
+
+/// One `key<TAB>value` line per setting, skipping those left at defaults
+fn format(state: &UiState) -> String {
+    let mut lines = Vec::new();
+    if let Some(selected) = &state.selected {
+        lines.push(format!("selected\t{}\n", escape_field(selected)));
+    }
+    if !state.filter.is_empty() {
+        lines.push(format!("filter\t{}\n", escape_field(&state.filter)));
+    }
+    if state.group_by_tag {
+        lines.push("group_by_tag\t1\n".to_string());
+    }
+    if let Some(order) = state.sort_order {
+        lines.push(format!("sort_order\t{}\n", order.code()));
+    }
+    if state.preview_percent != DEFAULT_PREVIEW_PERCENT {
+        lines.push(format!("preview_percent\t{}\n", state.preview_percent));
+    }
+    if let Some(previous) = &state.previous {
+        lines.push(format!("previous\t{}\n", escape_field(previous)));
+    }
+    lines.concat()
+}
+
+/// Parse saved state, ignoring unknown keys and invalid values
+fn parse(content: &str) -> UiState {
+    let mut state = UiState::default();
+    for (key, value) in content.lines().filter_map(|l| l.split_once('\t')) {
+        match key {
+            "selected" => state.selected = Some(unescape_field(value)),
+            "filter" => state.filter = unescape_field(value),
+            "group_by_tag" => state.group_by_tag = value == "1",
+            "sort_order" => state.sort_order = SortOrder::parse(value),
+            "previous" => state.previous = Some(unescape_field(value)),
+            "preview_percent" => {
+                let (min, max) = PREVIEW_PERCENT_RANGE;
+                if let Some(percent) = value.parse().ok().filter(|p| (min..=max).contains(p)) {
+                    state.preview_percent = percent;
+                }
+            }
+            _ => {}
+        }
+    }
+    state
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_round_trip() {
+        assert_eq!(format(&UiState::default()), "");
+        assert_eq!(parse(""), UiState::default());
+
+        let state = UiState {
+            selected: Some("api:0.1".to_string()),
+            filter: "#front\tend".to_string(),
+            group_by_tag: true,
+            sort_order: Some(SortOrder::Path),
+            preview_percent: 70,
+            previous: Some("docs:1.0".to_string()),
+        };
+        assert_eq!(parse(&format(&state)), state);
+    }
+
+    #[test]
+    fn test_parse_invalid() {
+        let state = parse("sort_order\tsize\npreview_percent\t95\nwidth\t3\nselected");
+        assert_eq!(state, UiState::default());
+    }
+}
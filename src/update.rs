@@ -0,0 +1,267 @@
+//! Self-update (`claude-tmux self-update [--check] [--feed <url>]`)
+//!
+//! Looks up the latest GitHub release of this repository and, when it is
+//! newer than the running binary, downloads the asset built for this
+//! platform, checks it against the release's SHA-256 checksums and replaces
+//! the running executable with it. `--check` only reports whether an update
+//! is available; `--feed` reads the release from another URL serving the
+//! same JSON, e.g. an internal mirror.
+//!
+//! Downloads go through `curl`.
+
+use std::fs;
+#[cfg(unix)]
+use std::os::unix::fs::PermissionsExt;
+use std::path::Path;
+use std::process::Command;
+
+use anyhow::{bail, Context, Result};
+use sha2::{Digest, Sha256};
+
+use crate::json::json_field;
+
+/// Latest release of the repository the binary was built from
+fn feed_url() -> String {
+    let repository = env!("CARGO_PKG_REPOSITORY").trim_end_matches('/');
+    let slug = repository.trim_start_matches("https://github.com/");
+    format!("https://api.github.com/repos/{}/releases/latest", slug)
+}
+
+/// Release asset name for this platform, e.g. `claude-tmux-linux-x86_64`
+fn asset_name() -> String {
+    format!(
+        "claude-tmux-{}-{}",
+        std::env::consts::OS,
+        std::env::consts::ARCH
+    )
+}
+
+/// Run the update; `args` are the arguments after `self-update`
+pub fn run(args: &[String]) -> Result<()> {
+    let mut check_only = false;
+    let mut feed = feed_url();
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--check" => check_only = true,
+            "--feed" => feed = args.next().context("--feed needs a URL")?.clone(),
+            other => bail!("Unknown option '{}' (expected --check or --feed)", other),
+        }
+    }
+
+    let current = env!("CARGO_PKG_VERSION");
+    let release =
+        String::from_utf8(curl(&feed, None)?).context("Release feed is not valid UTF-8")?;
+    let tag = json_field(&release, "tag_name").context("Release has no tag")?;
+    let latest = tag.trim_start_matches('v');
+
+    if !is_newer(latest, current) {
+        println!("claude-tmux {} is up to date", current);
+        return Ok(());
+    }
+    if check_only {
+        println!(
+            "claude-tmux {} is available (installed: {})",
+            latest, current
+        );
+        return Ok(());
+    }
+
+    let asset = asset_name();
+    let urls = download_urls(&release);
+    let binary_url = urls
+        .iter()
+        .find(|url| url.ends_with(&format!("/{}", asset)))
+        .with_context(|| format!("Release {} has no build for this platform ({})", tag, asset))?;
+    let checksums_url = urls
+        .iter()
+        .find(|url| url.ends_with("/SHA256SUMS") || url.ends_with(&format!("/{}.sha256", asset)))
+        .with_context(|| format!("Release {} publishes no checksums", tag))?;
+
+    let exe = std::env::current_exe()
+        .and_then(fs::canonicalize)
+        .context("Failed to locate the running executable")?;
+    // Download next to the executable so the final rename stays on one filesystem
+    let download = exe.with_file_name(format!(".{}.download", asset));
+    let result = install(&exe, &download, binary_url, checksums_url, &asset);
+    if result.is_err() {
+        let _ = fs::remove_file(&download);
+    }
+    result?;
+
+    println!(
+        "Updated claude-tmux {} -> {} ({})",
+        current,
+        latest,
+        exe.display()
+    );
+    println!("Restart the daemon, if you run it, to use the new version");
+    Ok(())
+}
+
+/// Download, verify and swap in the new binary
+fn install(
+    exe: &Path,
+    download: &Path,
+    binary_url: &str,
+    checksums_url: &str,
+    asset: &str,
+) -> Result<()> {
+    println!("Downloading {}", binary_url);
+    curl(binary_url, Some(download))?;
+
+    let checksums = String::from_utf8_lossy(&curl(checksums_url, None)?).into_owned();
+    let expected = expected_checksum(&checksums, asset)
+        .with_context(|| format!("No checksum listed for {}", asset))?;
+    let actual = sha256(download)?;
+    if !actual.eq_ignore_ascii_case(&expected) {
+        bail!(
+            "Checksum mismatch for {}: expected {}, got {}",
+            asset,
+            expected,
+            actual
+        );
+    }
+
+    #[cfg(unix)]
+    fs::set_permissions(download, fs::Permissions::from_mode(0o755))
+        .context("Failed to make the download executable")?;
+    fs::rename(download, exe).with_context(|| format!("Failed to replace {}", exe.display()))
+}
+
+/// Fetch `url`, into `output` if given, otherwise returning the body
+fn curl(url: &str, output: Option<&Path>) -> Result<Vec<u8>> {
+    let mut command = Command::new("curl");
+    command.args(["--fail", "--silent", "--show-error", "--location"]);
+    command.args(["--header", "User-Agent: claude-tmux"]);
+    if let Some(path) = output {
+        command.arg("--output").arg(path);
+    }
+    let result = command
+        .arg(url)
+        .output()
+        .context("Failed to run curl; is it installed?")?;
+    if !result.status.success() {
+        bail!(
+            "Download of {} failed: {}",
+            url,
+            String::from_utf8_lossy(&result.stderr).trim()
+        );
+    }
+    Ok(result.stdout)
+}
+
+/// Hex SHA-256 digest of a file
+fn sha256(path: &Path) -> Result<String> {
+    let content = fs::read(path).with_context(|| format!("Failed to read {}", path.display()))?;
+    Ok(format!("{:x}", Sha256::digest(content)))
+}
+
+/// Checksum for `asset` in a `sha256sum`-style listing, or a bare digest
+fn expected_checksum(checksums: &str, asset: &str) -> Option<String> {
+    checksums.lines().find_map(|line| {
+        let mut fields = line.split_whitespace();
+        let digest = fields.next()?;
+        match fields.next() {
+            // `*` marks binary mode in sha256sum output
+            Some(name) if name.trim_start_matches('*') == asset => Some(digest.to_string()),
+            None if is_hex_digest(digest) => Some(digest.to_string()),
+            _ => None,
+        }
+    })
+}
+
+fn is_hex_digest(value: &str) -> bool {
+    value.len() == 64 && value.chars().all(|c| c.is_ascii_hexdigit())
+}
+
+/// Every `browser_download_url` in a release
+fn download_urls(json: &str) -> Vec<String> {
+    let Ok(release) = serde_json::from_str::<serde_json::Value>(json) else {
+        return Vec::new();
+    };
+    release["assets"]
+        .as_array()
+        .into_iter()
+        .flatten()
+        .filter_map(|asset| asset["browser_download_url"].as_str())
+        .map(str::to_string)
+        .collect()
+}
+
+/// Whether version `candidate` is newer than `current` (dotted numbers,
+/// pre-release suffixes ignored)
+fn is_newer(candidate: &str, current: &str) -> bool {
+    let parse = |version: &str| -> Vec<u64> {
+        version
+            .split(['-', '+'])
+            .next()
+            .unwrap_or_default()
+            .split('.')
+            .map(|part| part.parse().unwrap_or(0))
+            .collect()
+    };
+    parse(candidate) > parse(current)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_is_newer() {
+        assert!(is_newer("0.4.0", "0.3.0"));
+        assert!(is_newer("0.10.0", "0.9.1"));
+        assert!(is_newer("1.0", "0.9.9"));
+        assert!(!is_newer("0.3.0", "0.3.0"));
+        assert!(!is_newer("0.2.9", "0.3.0"));
+        assert!(!is_newer("0.3.0-rc1", "0.3.0"));
+    }
+
+    #[test]
+    fn test_expected_checksum() {
+        let digest = "a".repeat(64);
+        let listing = format!(
+            "{}  claude-tmux-macos-aarch64\n{} *claude-tmux-linux-x86_64\n",
+            "b".repeat(64),
+            digest
+        );
+        assert_eq!(
+            expected_checksum(&listing, "claude-tmux-linux-x86_64"),
+            Some(digest.clone())
+        );
+        assert_eq!(expected_checksum(&listing, "claude-tmux-linux-arm"), None);
+        assert_eq!(
+            expected_checksum(&format!("{}\n", digest), "anything"),
+            Some(digest)
+        );
+    }
+
+    #[test]
+    fn test_sha256() {
+        let path = std::env::temp_dir().join(format!("claude-tmux-sha256-{}", std::process::id()));
+        fs::write(&path, "abc").unwrap();
+        let digest = sha256(&path);
+        let _ = fs::remove_file(&path);
+        assert_eq!(
+            digest.unwrap(),
+            "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad"
+        );
+    }
+
+    #[test]
+    fn test_release_parsing() {
+        let json = r#"{"tag_name": "v0.4.0", "assets": [
+            {"name": "claude-tmux-linux-x86_64", "browser_download_url": "https://example.com/v0.4.0/claude-tmux-linux-x86_64"},
+            {"name": "SHA256SUMS", "browser_download_url":"https://example.com/v0.4.0/SHA256SUMS"}
+        ]}"#;
+        assert_eq!(json_field(json, "tag_name").as_deref(), Some("v0.4.0"));
+        assert_eq!(
+            download_urls(json),
+            vec![
+                "https://example.com/v0.4.0/claude-tmux-linux-x86_64",
+                "https://example.com/v0.4.0/SHA256SUMS"
+            ]
+        );
+    }
+}
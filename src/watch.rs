@@ -0,0 +1,233 @@
+//! Status events for automation (`claude-tmux watch`)
+//!
+//! Watches every Claude instance and prints one JSON object per line each
+//! time an agent's status changes, until interrupted or stdout is closed:
+//!
+//! ```json
+//! {"time": 1767323045, "pane_id": "%3", "target": "api:1.0", "old": "working",
+//!  "new": "input", "instance": {"target": "api:1.0", "status": "input", ...}}
+//! ```
+//!
+//! Agents already running when the watch starts, and those started later,
+//! are announced with `old` set to `null`; an agent whose pane closed or
+//! stopped matching gets a last event with `new` set to `null` and no
+//! `instance`. `instance` is the object printed by `claude-tmux list
+//! --json`. Statuses are checked every 2 seconds, or `--interval <secs>`.
+
+use std::collections::HashMap;
+use std::io::{self, Write};
+use std::thread;
+use std::time::Duration;
+
+use anyhow::{Context, Result};
+use serde_json::{json, Value};
+
+use crate::config;
+use crate::git::GitContext;
+use crate::list::instance_json;
+use crate::session::{ClaudeCodeStatus, ClaudeInstance};
+use crate::status::detect_statuses;
+use crate::store::now_unix;
+use crate::tmux::Tmux;
+
+/// Seconds between two checks unless `--interval` is given
+const DEFAULT_INTERVAL_SECS: u64 = 2;
+
+/// A status change of one agent
+#[derive(Debug)]
+struct Transition<'a> {
+    pane_id: String,
+    target: String,
+    old: Option<ClaudeCodeStatus>,
+    /// The agent now, or `None` when it's gone
+    instance: Option<&'a ClaudeInstance>,
+}
+
+/// Watch the instances; `args` are the arguments after `watch`
+pub fn run(args: &[String]) -> Result<()> {
+    let interval = match args {
+        [] => DEFAULT_INTERVAL_SECS,
+        [flag, secs] if flag == "--interval" => secs
+            .parse()
+            .ok()
+            .filter(|s| *s > 0)
+            .with_context(|| format!("Invalid interval: {}", secs))?,
+        _ => anyhow::bail!("Usage: claude-tmux watch [--interval <secs>]"),
+    };
+
+    config::load_for_subcommand("watch");
+
+    let mut known: HashMap<String, (String, ClaudeCodeStatus)> = HashMap::new();
+    let mut stdout = io::stdout();
+    loop {
+        let mut instances = Tmux::list_claude_panes()?;
+        detect_statuses(&mut instances);
+
+        let time = now_unix();
+        let mut events = Vec::new();
+        for transition in transitions(&mut known, &instances) {
+            // Git state only for the agents printed
+            let git = transition
+                .instance
+                .map(|i| GitContext::detect(&i.working_directory));
+            events.push(event_json(&transition, time, git));
+        }
+        for event in events {
+            match writeln!(stdout, "{}", event).and_then(|_| stdout.flush()) {
+                Ok(()) => {}
+                // The reader went away, e.g. `watch | head`
+                Err(e) if e.kind() == io::ErrorKind::BrokenPipe => return Ok(()),
+                Err(e) => return Err(e).context("Failed to write event"),
+            }
+        }
+        thread::sleep(Duration::from_secs(interval));
+    }
+}
+
+/// Changes since the last check, updating `known` (pane ID to target and
+/// status)
+///
+/// Unknown statuses (a failed capture) keep the last status known.
+fn transitions<'a>(
+    known: &mut HashMap<String, (String, ClaudeCodeStatus)>,
+    instances: &'a [ClaudeInstance],
+) -> Vec<Transition<'a>> {
+    let mut changes = Vec::new();
+    for instance in instances {
+        let old = known.get(&instance.pane_id).map(|(_, status)| *status);
+        if instance.status == ClaudeCodeStatus::Unknown && old.is_some() {
+            continue;
+        }
+        if old == Some(instance.status) {
+            continue;
+        }
+        known.insert(
+            instance.pane_id.clone(),
+            (instance.tmux_target(), instance.status),
+        );
+        changes.push(Transition {
+            pane_id: instance.pane_id.clone(),
+            target: instance.tmux_target(),
+            old,
+            instance: Some(instance),
+        });
+    }
+
+    let mut gone: Vec<String> = known
+        .keys()
+        .filter(|pane_id| !instances.iter().any(|i| &&i.pane_id == pane_id))
+        .cloned()
+        .collect();
+    gone.sort();
+    for pane_id in gone {
+        if let Some((target, status)) = known.remove(&pane_id) {
+            changes.push(Transition {
+                pane_id,
+                target,
+                old: Some(status),
+                instance: None,
+            });
+        }
+    }
+    changes
+}
+
+/// A transition as one JSON line, with `git` set on the instance
+fn event_json(transition: &Transition, time: u64, git: Option<Option<GitContext>>) -> Value {
+    let instance = transition.instance.map(|instance| {
+        let mut instance = instance.clone();
+        if let Some(git) = git {
+            instance.git_context = git;
+        }
+        instance_json(&instance)
+    });
+    json!({
+        "time": time,
+        "pane_id": transition.pane_id,
+        "target": transition.target,
+        "old": transition.old.map(|s| s.label()),
+        "new": transition.instance.map(|i| i.status.label()),
+        "instance": instance,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::session::test_instance;
+
+    fn instance(pane_id: &str, status: ClaudeCodeStatus) -> ClaudeInstance {
+        ClaudeInstance {
+            pane_index: pane_id[1..].parse().unwrap(),
+            ..test_instance(pane_id, status)
+        }
+    }
+
+    /// `pane old->new` for each transition
+    fn changes(
+        known: &mut HashMap<String, (String, ClaudeCodeStatus)>,
+        instances: &[ClaudeInstance],
+    ) -> Vec<String> {
+        transitions(known, instances)
+            .iter()
+            .map(|t| {
+                format!(
+                    "{} {}->{}",
+                    t.pane_id,
+                    t.old.map(|s| s.label()).unwrap_or("-"),
+                    t.instance.map(|i| i.status.label()).unwrap_or("-")
+                )
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_transitions() {
+        let mut known = HashMap::new();
+        let first = [
+            instance("%1", ClaudeCodeStatus::Working),
+            instance("%2", ClaudeCodeStatus::Idle),
+        ];
+        assert_eq!(
+            changes(&mut known, &first),
+            vec!["%1 -->working", "%2 -->idle"]
+        );
+        assert!(changes(&mut known, &first).is_empty());
+
+        // A failed capture doesn't count as a change
+        let second = [
+            instance("%1", ClaudeCodeStatus::WaitingInput),
+            instance("%2", ClaudeCodeStatus::Unknown),
+        ];
+        assert_eq!(changes(&mut known, &second), vec!["%1 working->input"]);
+
+        let third = [instance("%1", ClaudeCodeStatus::WaitingInput)];
+        assert_eq!(changes(&mut known, &third), vec!["%2 idle->-"]);
+    }
+
+    #[test]
+    fn test_event_json() {
+        let waiting = instance("%3", ClaudeCodeStatus::WaitingInput);
+        let transition = Transition {
+            pane_id: "%3".to_string(),
+            target: "api:1.3".to_string(),
+            old: Some(ClaudeCodeStatus::Working),
+            instance: Some(&waiting),
+        };
+        let event = event_json(&transition, 1_767_323_045, Some(None)).to_string();
+        assert!(event.starts_with(
+            "{\"time\":1767323045,\"pane_id\":\"%3\",\"target\":\"api:1.3\",\
+             \"old\":\"working\",\"new\":\"input\",\"instance\":{\"target\":\"api:1.3\""
+        ));
+
+        let gone = Transition {
+            instance: None,
+            ..transition
+        };
+        assert_eq!(
+            event_json(&gone, 1_767_323_045, None).to_string(),
+            "{\"time\":1767323045,\"pane_id\":\"%3\",\"target\":\"api:1.3\",\
+             \"old\":\"working\",\"new\":null,\"instance\":null}"
+        );
+    }
+}
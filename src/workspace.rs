@@ -0,0 +1,239 @@
+//! Starting workspaces (`claude-tmux workspace <name>`)
+//!
+//! A workspace (`[[workspaces]]`) names a set of directories. Starting it
+//! creates a session running Claude in each directory that has no agent
+//! yet and leaves the others alone, so starting it again checks that the
+//! whole set is running. The TUI starts them from its workspace picker.
+
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result};
+
+use crate::app::templated_session_name;
+use crate::config::{Config, Workspace};
+use crate::git::{self, GitContext};
+use crate::session::{short_path, ClaudeCodeStatus, ClaudeInstance};
+use crate::tmux::Tmux;
+
+/// Where a directory of a workspace stands
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum MemberState {
+    /// An agent runs there, at this `session:window.pane`
+    Running {
+        target: String,
+        status: ClaudeCodeStatus,
+    },
+    /// No agent runs there
+    Stopped,
+    /// The directory doesn't exist
+    Missing,
+}
+
+/// A directory of a workspace and where it stands
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Member {
+    pub dir: PathBuf,
+    pub state: MemberState,
+}
+
+/// What starting a workspace did for one of its directories
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Outcome {
+    /// An agent already ran there
+    Running(String),
+    /// A session was created, with this name
+    Started(String),
+    /// The directory doesn't exist
+    Missing,
+    /// Creating the session failed
+    Failed(String),
+}
+
+/// Where each directory of `workspace` stands among `instances`
+///
+/// An agent anywhere under a directory counts as running there.
+pub fn members(workspace: &Workspace, instances: &[ClaudeInstance]) -> Vec<Member> {
+    workspace
+        .dirs()
+        .into_iter()
+        .map(|dir| {
+            let agent = instances
+                .iter()
+                .find(|i| i.working_directory.starts_with(&dir));
+            let state = match agent {
+                Some(agent) => MemberState::Running {
+                    target: agent.tmux_target(),
+                    status: agent.status,
+                },
+                None if !dir.is_dir() => MemberState::Missing,
+                None => MemberState::Stopped,
+            };
+            Member { dir, state }
+        })
+        .collect()
+}
+
+/// Start Claude in each directory of `workspace` without an agent among
+/// `instances`
+pub fn start(
+    workspace: &Workspace,
+    config: &Config,
+    instances: &[ClaudeInstance],
+) -> Vec<(PathBuf, Outcome)> {
+    let mut taken = Tmux::list_sessions().unwrap_or_default();
+    members(workspace, instances)
+        .into_iter()
+        .map(|member| {
+            let outcome = match member.state {
+                MemberState::Running { target, .. } => Outcome::Running(target),
+                MemberState::Missing => Outcome::Missing,
+                MemberState::Stopped => {
+                    let name = session_name(&config.session_name_template, &member.dir, &taken);
+                    let command = config.claude_command(&[&member.dir]);
+                    match Tmux::new_session(&name, &member.dir, Some(&command)) {
+                        Ok(()) => {
+                            tracing::info!("workspace {}: started {}", workspace.name, name);
+                            taken.push(name.clone());
+                            Outcome::Started(name)
+                        }
+                        Err(e) => Outcome::Failed(format!("{:#}", e)),
+                    }
+                }
+            };
+            (member.dir, outcome)
+        })
+        .collect()
+}
+
+/// Session name for a directory from the `session_name_template`, with a
+/// number added when a session already has it
+///
+/// Outside a repository, `{repo}` is the directory's name.
+pub fn session_name(template: &str, dir: &Path, taken: &[String]) -> String {
+    let repo = GitContext::repo_name(dir)
+        .or_else(|| {
+            dir.file_name()
+                .map(|name| name.to_string_lossy().to_string())
+        })
+        .unwrap_or_default();
+    let branch = git::current_branch(dir);
+    templated_session_name(template, &repo, branch.as_deref(), taken)
+}
+
+/// Start a workspace; `args` are the arguments after `workspace`
+///
+/// Without a name, lists the configured workspaces.
+pub fn run(args: &[String]) -> Result<()> {
+    let config = Config::load()?;
+    let name = match args {
+        [] => {
+            if config.workspaces.is_empty() {
+                println!("No workspaces configured; add [[workspaces]] to the config");
+            }
+            for workspace in &config.workspaces {
+                println!("{}\t{}", workspace.name, workspace.paths.join(" "));
+            }
+            return Ok(());
+        }
+        [name] => name,
+        _ => anyhow::bail!("workspace takes a single name"),
+    };
+    let workspace = config
+        .workspaces
+        .iter()
+        .find(|w| &w.name == name)
+        .with_context(|| format!("No workspace named '{}'", name))?;
+
+    Tmux::set_claude_process(config.claude_process.clone());
+    let instances = Tmux::list_claude_instances()?;
+    let mut failed = 0;
+    for (dir, outcome) in start(workspace, &config, &instances) {
+        let dir = short_path(&dir);
+        match outcome {
+            Outcome::Running(target) => println!("{}: running ({})", dir, target),
+            Outcome::Started(session) => println!("{}: started ({})", dir, session),
+            Outcome::Missing => {
+                failed += 1;
+                println!("{}: directory not found", dir);
+            }
+            Outcome::Failed(e) => {
+                failed += 1;
+                println!("{}: failed: {}", dir, e);
+            }
+        }
+    }
+    if failed > 0 {
+        anyhow::bail!(
+            "{} of the workspace's directories could not be started",
+            failed
+        );
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::session::test_instance;
+
+    #[test]
+    fn test_session_name() {
+        let taken = vec!["api".to_string(), "api-2".to_string()];
+        assert_eq!(
+            session_name("{repo}-{branch}", Path::new("/src/web"), &taken),
+            "web"
+        );
+        assert_eq!(
+            session_name("{repo}-{branch}", Path::new("/src/api"), &taken),
+            "api-3"
+        );
+        assert_eq!(
+            session_name("{repo}-{branch}", Path::new("/src/my.app"), &taken),
+            "my-app"
+        );
+        assert_eq!(
+            session_name("{repo}-{branch}", Path::new("/"), &taken),
+            "claude"
+        );
+
+        let login = Some("feature/login");
+        assert_eq!(
+            templated_session_name("{repo}-{branch}", "api", login, &taken),
+            "api-login"
+        );
+        assert_eq!(
+            templated_session_name("{branch} ({repo})", "my.app", login, &taken),
+            "login-(my-app)"
+        );
+        assert_eq!(
+            templated_session_name("{branch}", "api", None, &taken),
+            "claude"
+        );
+    }
+
+    #[test]
+    fn test_members() {
+        let workspace = Workspace {
+            name: "product".to_string(),
+            paths: vec!["/usr".to_string(), "/nonexistent/web".to_string()],
+        };
+        let instance = ClaudeInstance {
+            working_directory: PathBuf::from("/usr/lib"),
+            ..test_instance("%3", ClaudeCodeStatus::Idle)
+        };
+
+        let members = members(&workspace, &[instance]);
+        assert_eq!(
+            members[0].state,
+            MemberState::Running {
+                target: "api:1.0".to_string(),
+                status: ClaudeCodeStatus::Idle
+            }
+        );
+        assert_eq!(members[1].state, MemberState::Missing);
+        assert_eq!(
+            super::members(&workspace, &[])[0].state,
+            MemberState::Stopped
+        );
+    }
+}
@@ -0,0 +1,304 @@
+//! Time agents spend working, per branch and day
+//!
+//! Every few seconds the agents' statuses are sampled, and the time since the
+//! previous sample is credited to the repository and branch of each agent
+//! that is working. The daemon samples while it runs; otherwise the TUI does,
+//! so the same time is never counted twice. Totals are kept per local day in a
+//! tab-separated file in the data directory.
+//!
+//! Agents outside a git repository are not tracked. Time between two samples
+//! more than `MAX_SAMPLE_GAP` apart (nothing sampling, machine asleep) is not
+//! credited.
+
+use std::collections::HashMap;
+use std::fs;
+use std::path::PathBuf;
+
+use anyhow::Result;
+
+use crate::git::GitContext;
+use crate::logging::format_timestamp;
+use crate::session::{ClaudeCodeStatus, ClaudeInstance};
+use crate::store::{self, data_dir, escape_field, local_utc_offset, unescape_field};
+
+/// Longest interval between samples that is still credited, in seconds
+const MAX_SAMPLE_GAP: u64 = 60;
+
+/// Working time of one branch on one day
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WorkTime {
+    /// Local date, `YYYY-MM-DD`
+    pub day: String,
+    /// Repository name (the main checkout's directory)
+    pub repo: String,
+    pub branch: String,
+    pub seconds: u64,
+}
+
+/// Working time of one branch, summed over days
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BranchTotal {
+    pub repo: String,
+    pub branch: String,
+    /// Seconds worked today
+    pub today: u64,
+    /// Seconds worked over the last 7 days, today included
+    pub week: u64,
+    /// Seconds worked overall
+    pub total: u64,
+    /// Last day any time was recorded
+    pub last_day: String,
+}
+
+/// Credits working time from successive samples of the agents
+pub struct Tracker {
+    /// Time of the previous sample
+    last_sample: Option<u64>,
+    /// Repository name per working directory
+    repos: HashMap<PathBuf, Option<String>>,
+}
+
+impl Tracker {
+    pub fn new() -> Self {
+        Self {
+            last_sample: None,
+            repos: HashMap::new(),
+        }
+    }
+
+    /// Credit the time since the previous sample to every working agent
+    pub fn sample(&mut self, instances: &[ClaudeInstance], now: u64) {
+        let elapsed = self
+            .last_sample
+            .replace(now)
+            .map(|last| now.saturating_sub(last));
+        let Some(elapsed) = elapsed.filter(|e| (1..=MAX_SAMPLE_GAP).contains(e)) else {
+            return;
+        };
+
+        let day = local_day(now, local_utc_offset(now));
+        let mut credits = Vec::new();
+        for instance in instances {
+            if instance.status != ClaudeCodeStatus::Working {
+                continue;
+            }
+            let Some(git) = &instance.git_context else {
+                continue;
+            };
+            let repo = self
+                .repos
+                .entry(instance.working_directory.clone())
+                .or_insert_with_key(|path| GitContext::repo_name(path));
+            let Some(repo) = repo else {
+                continue;
+            };
+            credits.push(WorkTime {
+                day: day.clone(),
+                repo: repo.clone(),
+                branch: git.branch.clone(),
+                seconds: elapsed,
+            });
+        }
+        if credits.is_empty() {
+            return;
+        }
+        if let Err(e) = add(&credits) {
+            tracing::warn!("{:#}", e);
+        }
+    }
+
+    /// Forget the previous sample, so the time until the next one is not
+    /// credited (another process is tracking meanwhile)
+    pub fn pause(&mut self) {
+        self.last_sample = None;
+    }
+}
+
+fn worktime_path() -> PathBuf {
+    data_dir().join("worktime.tsv")
+}
+
+/// Local date of a unix timestamp, `YYYY-MM-DD`
+pub fn local_day(unix: u64, utc_offset: i64) -> String {
+    format_timestamp(unix as i64 + utc_offset)[..10].to_string()
+}
+
+/// Load the recorded working time
+pub fn load() -> Vec<WorkTime> {
+    let Ok(content) = fs::read_to_string(worktime_path()) else {
+        return Vec::new();
+    };
+    content.lines().filter_map(parse_line).collect()
+}
+
+/// Add `credits` to the recorded working time
+pub fn add(credits: &[WorkTime]) -> Result<()> {
+    let _lock = store::lock(&worktime_path())?;
+    let mut entries = load();
+    for credit in credits {
+        match entries
+            .iter_mut()
+            .find(|e| e.day == credit.day && e.repo == credit.repo && e.branch == credit.branch)
+        {
+            Some(entry) => entry.seconds += credit.seconds,
+            None => entries.push(credit.clone()),
+        }
+    }
+
+    let content: String = entries.iter().map(format_line).collect();
+    store::write_atomic(&worktime_path(), &content, "working time")
+}
+
+/// Totals per branch, most recently worked on first
+///
+/// `today` and `week_start` are local dates; days from `week_start` on count
+/// towards `week`.
+pub fn summarize(entries: &[WorkTime], today: &str, week_start: &str) -> Vec<BranchTotal> {
+    let mut totals: Vec<BranchTotal> = Vec::new();
+    for entry in entries {
+        let index = match totals
+            .iter()
+            .position(|t| t.repo == entry.repo && t.branch == entry.branch)
+        {
+            Some(index) => index,
+            None => {
+                totals.push(BranchTotal {
+                    repo: entry.repo.clone(),
+                    branch: entry.branch.clone(),
+                    today: 0,
+                    week: 0,
+                    total: 0,
+                    last_day: String::new(),
+                });
+                totals.len() - 1
+            }
+        };
+        let total = &mut totals[index];
+        total.total += entry.seconds;
+        if entry.day.as_str() >= week_start {
+            total.week += entry.seconds;
+        }
+        if entry.day == today {
+            total.today += entry.seconds;
+        }
+        if entry.day > total.last_day {
+            total.last_day = entry.day.clone();
+        }
+    }
+    totals.sort_by(|a, b| {
+        b.last_day
+            .cmp(&a.last_day)
+            .then(b.total.cmp(&a.total))
+            .then_with(|| (&a.repo, &a.branch).cmp(&(&b.repo, &b.branch)))
+    });
+    totals
+}
+
+/// Totals per branch as of `now`
+pub fn branch_totals(now: u64) -> Vec<BranchTotal> {
+    let week_start = now.saturating_sub(6 * 86_400);
+    summarize(
+        &load(),
+        &local_day(now, local_utc_offset(now)),
+        &local_day(week_start, local_utc_offset(week_start)),
+    )
+}
+
+/// Format a duration for display (e.g., "2h05m", "12m", "<1m", "-" for none)
+pub fn format_seconds(secs: u64) -> String {
+    let (hours, minutes) = (secs / 3600, (secs % 3600) / 60);
+    if secs == 0 {
+        "-".to_string()
+    } else if hours > 0 {
+        format!("{}h{:02}m", hours, minutes)
+    } else if minutes > 0 {
+        format!("{}m", minutes)
+    } else {
+        "<1m".to_string()
+    }
+}
+
+fn format_line(entry: &WorkTime) -> String {
+    format!(
+        "{}\t{}\t{}\t{}\n",
+        entry.day,
+        escape_field(&entry.repo),
+        escape_field(&entry.branch),
+        entry.seconds
+    )
+}
+
+fn parse_line(line: &str) -> Option<WorkTime> {
+    let parts: Vec<&str> = line.split('\t').collect();
+    if parts.len() != 4 {
+        return None;
+    }
+    Some(WorkTime {
+        day: parts[0].to_string(),
+        repo: unescape_field(parts[1]),
+        branch: unescape_field(parts[2]),
+        seconds: parts[3].parse().ok()?,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(day: &str, repo: &str, branch: &str, seconds: u64) -> WorkTime {
+        WorkTime {
+            day: day.to_string(),
+            repo: repo.to_string(),
+            branch: branch.to_string(),
+            seconds,
+        }
+    }
+
+    #[test]
+    fn test_local_day() {
+        // 2024-01-01 08:00:00 UTC
+        assert_eq!(local_day(1_704_096_000, 0), "2024-01-01");
+        assert_eq!(local_day(1_704_096_000, -9 * 3600), "2023-12-31");
+    }
+
+    #[test]
+    fn test_summarize() {
+        let entries = vec![
+            entry("2024-01-01", "api", "main", 600),
+            entry("2024-01-08", "api", "login", 3_000),
+            entry("2024-01-10", "api", "login", 900),
+            entry("2024-01-10", "web", "main", 60),
+            entry("2024-01-09", "api", "main", 120),
+        ];
+        let totals = summarize(&entries, "2024-01-10", "2024-01-04");
+        let rows: Vec<(&str, &str, u64, u64, u64)> = totals
+            .iter()
+            .map(|t| (t.repo.as_str(), t.branch.as_str(), t.today, t.week, t.total))
+            .collect();
+        assert_eq!(
+            rows,
+            vec![
+                ("api", "login", 900, 3_900, 3_900),
+                ("web", "main", 60, 60, 60),
+                ("api", "main", 0, 120, 720),
+            ]
+        );
+        assert_eq!(totals[2].last_day, "2024-01-09");
+    }
+
+    #[test]
+    fn test_line_roundtrip() {
+        let entry = entry("2024-01-10", "my\tapi", "feature/login", 3_725);
+        let line = format_line(&entry);
+        assert_eq!(parse_line(line.trim_end_matches('\n')), Some(entry));
+        assert_eq!(parse_line("2024-01-10\tapi\tmain"), None);
+    }
+
+    #[test]
+    fn test_format_seconds() {
+        assert_eq!(format_seconds(0), "-");
+        assert_eq!(format_seconds(42), "<1m");
+        assert_eq!(format_seconds(720), "12m");
+        assert_eq!(format_seconds(3_725), "1h02m");
+    }
+}